@@ -7,6 +7,7 @@ use color_eyre::{
 };
 use jcli_lib::utils::{output_file::OutputFile, output_format::OutputFormat};
 
+use std::num::NonZeroU64;
 use std::path::PathBuf;
 
 use reqwest::Url;
@@ -36,6 +37,26 @@ pub struct ReplayCli {
     #[structopt(flatten)]
     output_format: OutputFormat,
 
+    /// Path to a checkpoint file. If it exists the recovery resumes from it,
+    /// and the state of the recovery is saved there when done
+    #[structopt(long)]
+    checkpoint: Option<PathBuf>,
+
+    /// Also save a checkpoint every given number of fragments processed
+    #[structopt(long, requires = "checkpoint")]
+    checkpoint_every: Option<NonZeroU64>,
+
+    /// Path to a folder containing the merged decryption shares of the private
+    /// vote plans, one `<vote plan id>.json` file per vote plan as produced by
+    /// `jcli votes tally merge-shares`. The private tallies are decrypted when set
+    #[structopt(long)]
+    shares: Option<PathBuf>,
+
+    /// Path to a folder of tally decryption tables, reused across runs.
+    /// Missing tables are generated and saved there
+    #[structopt(long, requires = "shares")]
+    tally_tables: Option<PathBuf>,
+
     /// Verbose mode (-v, -vv, -vvv, etc)
     #[structopt(short = "v", long = "verbose", parse(from_occurrences))]
     verbose: usize,
@@ -59,6 +80,10 @@ impl ReplayCli {
             logs_path,
             output,
             output_format,
+            checkpoint,
+            checkpoint_every,
+            shares,
+            tally_tables,
             verbose,
         } = self;
 
@@ -72,7 +97,13 @@ impl ReplayCli {
             bail!("block0 unavailable");
         };
 
-        let replay = Replay::new(block0, logs_path, output, output_format);
+        let mut replay = Replay::new(block0, logs_path, output, output_format);
+        if let Some(checkpoint) = checkpoint {
+            replay = replay.with_checkpoint(checkpoint, checkpoint_every);
+        }
+        if let Some(shares) = shares {
+            replay = replay.with_decryption(shares, tally_tables);
+        }
         replay.exec().map_err(Into::into)
     }
 }
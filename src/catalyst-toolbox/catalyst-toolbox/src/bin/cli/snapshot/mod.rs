@@ -4,7 +4,7 @@ use jormungandr_lib::interfaces::Value;
 use snapshot_lib::Fraction;
 use snapshot_lib::{
     voting_group::{RepsVotersAssigner, DEFAULT_DIRECT_VOTER_GROUP, DEFAULT_REPRESENTATIVE_GROUP},
-    RawSnapshot, Snapshot,
+    RawSnapshot, Snapshot, VotingPowerTransform,
 };
 use std::fs::File;
 use std::io::Write;
@@ -36,6 +36,12 @@ pub struct SnapshotCmd {
     #[structopt(short, long)]
     voting_power_cap: Fraction,
 
+    /// Mapping from stake to voting power, applied before the voting power cap.
+    /// One of "linear", "sqrt", "log:<scale>" or
+    /// "piecewise:<from>=<rate>,<from>=<rate>,...[;cap=<max voting power>]"
+    #[structopt(long, default_value = "linear")]
+    voting_power_transform: VotingPowerTransform,
+
     #[structopt(flatten)]
     output: OutputFile,
 
@@ -58,6 +64,7 @@ impl SnapshotCmd {
             self.min_stake_threshold,
             self.voting_power_cap,
             &assigner,
+            &self.voting_power_transform,
         )?
         .to_full_snapshot_info();
         let mut out_writer = self.output.open()?;
@@ -0,0 +1,270 @@
+//! Checkpoints of the tally recovery
+//!
+//! Replaying the fragment logs from block0 is slow for a full fund. A
+//! [`RecoveryCheckpoint`] captures everything the recovery needs to carry on
+//! from a given fragment: the mirror keys used to re-sign the votes, the state
+//! of the accounts of the mirror ledger, the state of the fragment filter, the
+//! vote delegations and the ballots and (encrypted) incremental tally of every
+//! vote plan.
+//!
+//! Ledger state not involved in the voting (e.g. the fees collected in the
+//! pots) is not part of the checkpoint.
+
+use chain_core::{
+    packer::Codec,
+    property::{Deserialize, DeserializeFromSlice, ReadError, Serialize, WriteError},
+};
+use chain_crypto::{Ed25519Extended, SecretKey};
+use chain_impl_mockchain::{
+    account::{self, SpendingCounter},
+    accounting::account::SpendingCounterIncreasing,
+    block::BlockDate,
+    certificate::VoteDelegation,
+    fragment::FragmentId,
+    value::Value,
+    vote::VotePlanCheckpoint,
+};
+use jormungandr_lib::interfaces::Address;
+use std::path::Path;
+
+const CHECKPOINT_VERSION: u8 = 2;
+
+#[derive(thiserror::Error, Debug)]
+pub enum Error {
+    #[error(transparent)]
+    Io(#[from] std::io::Error),
+
+    #[error("malformed recovery checkpoint")]
+    Read(#[from] ReadError),
+
+    #[error("cannot serialize recovery checkpoint")]
+    Write(#[from] WriteError),
+
+    #[error("unsupported recovery checkpoint version {0}")]
+    UnsupportedVersion(u8),
+}
+
+/// mirror key generated for one of the original accounts
+#[derive(Clone)]
+pub(crate) struct MirrorKey {
+    pub(crate) original: Address,
+    pub(crate) key: SecretKey<Ed25519Extended>,
+    /// whether the account was listed in block0 or only received funds
+    /// during the replay
+    pub(crate) voting: bool,
+}
+
+/// value and spending counters of one of the accounts of the mirror ledger
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) struct AccountCheckpoint {
+    pub(crate) identifier: account::Identifier,
+    pub(crate) value: Value,
+    pub(crate) spending: SpendingCounterIncreasing,
+}
+
+/// State of the tally recovery after a given number of fragments of the logs
+#[derive(Clone)]
+pub struct RecoveryCheckpoint {
+    pub(crate) fragments_processed: u64,
+    pub(crate) date: BlockDate,
+    pub(crate) mirror_keys: Vec<MirrorKey>,
+    pub(crate) accounts: Vec<AccountCheckpoint>,
+    pub(crate) spending_counters: Vec<(account::Identifier, u32)>,
+    pub(crate) replay_protection: Vec<FragmentId>,
+    pub(crate) vote_plans: Vec<VotePlanCheckpoint>,
+    /// vote delegations of the mirror accounts
+    pub(crate) vote_delegations: Vec<(account::Identifier, VoteDelegation)>,
+}
+
+impl RecoveryCheckpoint {
+    /// number of fragments of the logs (valid or not) already processed
+    pub fn fragments_processed(&self) -> u64 {
+        self.fragments_processed
+    }
+
+    /// date of the ledger at the time of the checkpoint
+    pub fn date(&self) -> BlockDate {
+        self.date
+    }
+
+    pub fn vote_plans(&self) -> &[VotePlanCheckpoint] {
+        &self.vote_plans
+    }
+
+    pub fn load<P: AsRef<Path>>(path: P) -> Result<Self, Error> {
+        let bytes = std::fs::read(path)?;
+        Ok(Self::deserialize_from_slice(&mut Codec::new(
+            bytes.as_slice(),
+        ))?)
+    }
+
+    pub fn save<P: AsRef<Path>>(&self, path: P) -> Result<(), Error> {
+        let bytes = self.serialize_as_vec()?;
+        // write to a temporary file first, so an interrupted run never leaves
+        // a truncated checkpoint behind
+        let tmp = path.as_ref().with_extension("tmp");
+        std::fs::write(&tmp, bytes)?;
+        std::fs::rename(tmp, path)?;
+        Ok(())
+    }
+}
+
+fn serialize_secret_key<W: std::io::Write>(
+    key: &SecretKey<Ed25519Extended>,
+    codec: &mut Codec<W>,
+) -> Result<(), WriteError> {
+    codec.put_bytes(key.clone().leak_secret().as_ref())
+}
+
+fn deserialize_secret_key(
+    codec: &mut Codec<&[u8]>,
+) -> Result<SecretKey<Ed25519Extended>, ReadError> {
+    let bytes = codec.get_slice(64)?;
+    SecretKey::from_binary(bytes)
+        .map_err(|e| ReadError::StructureInvalid(format!("invalid mirror key: {}", e)))
+}
+
+fn serialize_length<W: std::io::Write>(
+    length: usize,
+    codec: &mut Codec<W>,
+) -> Result<(), WriteError> {
+    codec.put_be_u32(length as u32)
+}
+
+impl Serialize for RecoveryCheckpoint {
+    fn serialize<W: std::io::Write>(&self, codec: &mut Codec<W>) -> Result<(), WriteError> {
+        codec.put_u8(CHECKPOINT_VERSION)?;
+        codec.put_be_u64(self.fragments_processed)?;
+        codec.put_be_u32(self.date.epoch)?;
+        codec.put_be_u32(self.date.slot_id)?;
+
+        serialize_length(self.mirror_keys.len(), codec)?;
+        for mirror_key in &self.mirror_keys {
+            let address = chain_addr::Address::from(mirror_key.original.clone()).to_bytes();
+            codec.put_u8(address.len() as u8)?;
+            codec.put_bytes(&address)?;
+            serialize_secret_key(&mirror_key.key, codec)?;
+            codec.put_u8(mirror_key.voting as u8)?;
+        }
+
+        serialize_length(self.accounts.len(), codec)?;
+        for account in &self.accounts {
+            account.identifier.serialize(codec)?;
+            codec.put_be_u64(account.value.0)?;
+            for counter in account.spending.get_valid_counters() {
+                codec.put_bytes(&counter.to_bytes())?;
+            }
+        }
+
+        serialize_length(self.spending_counters.len(), codec)?;
+        for (identifier, counter) in &self.spending_counters {
+            identifier.serialize(codec)?;
+            codec.put_be_u32(*counter)?;
+        }
+
+        serialize_length(self.replay_protection.len(), codec)?;
+        for fragment_id in &self.replay_protection {
+            fragment_id.serialize(codec)?;
+        }
+
+        serialize_length(self.vote_plans.len(), codec)?;
+        for vote_plan in &self.vote_plans {
+            let bytes = vote_plan.serialize_as_vec()?;
+            serialize_length(bytes.len(), codec)?;
+            codec.put_bytes(&bytes)?;
+        }
+
+        serialize_length(self.vote_delegations.len(), codec)?;
+        for (delegator, vote_delegation) in &self.vote_delegations {
+            delegator.serialize(codec)?;
+            vote_delegation.serialize(codec)?;
+        }
+        Ok(())
+    }
+}
+
+impl DeserializeFromSlice for RecoveryCheckpoint {
+    fn deserialize_from_slice(codec: &mut Codec<&[u8]>) -> Result<Self, ReadError> {
+        let version = codec.get_u8()?;
+        if version != CHECKPOINT_VERSION {
+            return Err(ReadError::StructureInvalid(
+                Error::UnsupportedVersion(version).to_string(),
+            ));
+        }
+        let fragments_processed = codec.get_be_u64()?;
+        let date = BlockDate {
+            epoch: codec.get_be_u32()?,
+            slot_id: codec.get_be_u32()?,
+        };
+
+        let mirror_keys = (0..codec.get_be_u32()?)
+            .map(|_| {
+                let len = codec.get_u8()? as usize;
+                let original = chain_addr::Address::from_bytes(codec.get_slice(len)?)
+                    .map_err(|e| ReadError::StructureInvalid(e.to_string()))?
+                    .into();
+                let key = deserialize_secret_key(codec)?;
+                let voting = codec.get_u8()? != 0;
+                Ok(MirrorKey {
+                    original,
+                    key,
+                    voting,
+                })
+            })
+            .collect::<Result<Vec<_>, ReadError>>()?;
+
+        let accounts = (0..codec.get_be_u32()?)
+            .map(|_| {
+                let identifier = account::Identifier::deserialize_from_slice(codec)?;
+                let value = Value(codec.get_be_u64()?);
+                let mut counters = [SpendingCounter::zero(); SpendingCounterIncreasing::LANES];
+                for counter in counters.iter_mut() {
+                    *counter = SpendingCounter::from_bytes(<[u8; 4]>::deserialize(codec)?);
+                }
+                let spending = SpendingCounterIncreasing::new_from_counters(counters)
+                    .map_err(|e| ReadError::StructureInvalid(e.to_string()))?;
+                Ok(AccountCheckpoint {
+                    identifier,
+                    value,
+                    spending,
+                })
+            })
+            .collect::<Result<Vec<_>, ReadError>>()?;
+
+        let spending_counters = (0..codec.get_be_u32()?)
+            .map(|_| {
+                let identifier = account::Identifier::deserialize_from_slice(codec)?;
+                Ok((identifier, codec.get_be_u32()?))
+            })
+            .collect::<Result<Vec<_>, ReadError>>()?;
+
+        let replay_protection = (0..codec.get_be_u32()?)
+            .map(|_| FragmentId::deserialize(codec))
+            .collect::<Result<Vec<_>, _>>()?;
+
+        let vote_plans = (0..codec.get_be_u32()?)
+            .map(|_| {
+                let len = codec.get_be_u32()? as usize;
+                VotePlanCheckpoint::deserialize_from_slice(&mut Codec::new(codec.get_slice(len)?))
+            })
+            .collect::<Result<Vec<_>, _>>()?;
+
+        let vote_delegations = (0..codec.get_be_u32()?)
+            .map(|_| {
+                let delegator = account::Identifier::deserialize_from_slice(codec)?;
+                Ok((delegator, VoteDelegation::deserialize_from_slice(codec)?))
+            })
+            .collect::<Result<Vec<_>, ReadError>>()?;
+
+        Ok(Self {
+            fragments_processed,
+            date,
+            mirror_keys,
+            accounts,
+            spending_counters,
+            replay_protection,
+            vote_plans,
+            vote_delegations,
+        })
+    }
+}
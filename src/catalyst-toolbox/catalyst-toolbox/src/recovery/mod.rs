@@ -1,4 +1,6 @@
+pub mod checkpoint;
 mod replay;
 pub mod tally;
 
+pub use checkpoint::RecoveryCheckpoint;
 pub use replay::{Error as ReplayError, Replay};
@@ -1,16 +1,23 @@
-use crate::recovery::tally::recover_ledger_from_logs;
+use crate::recovery::{
+    checkpoint::RecoveryCheckpoint, tally::recover_ledger_from_logs_with_checkpoints,
+};
 use chain_core::property::Fragment;
 use chain_impl_mockchain::block::Block;
 pub use jcli_lib::utils::{
     output_file::{Error as OutputFileError, OutputFile},
     output_format::{Error as OutputFormatError, OutputFormat},
 };
+use jcli_lib::{
+    utils::vote::{read_vote_plan_shares_from_file, SharesError},
+    vote::decrypt_vote_plan,
+};
 use jormungandr_lib::interfaces::{
-    load_persistent_fragments_logs_from_folder_path, VotePlanStatus,
+    load_persistent_fragments_logs_from_folder_path, PrivateTallyState, Tally, VotePlanStatus,
 };
 use std::io::Write;
-use std::path::PathBuf;
-use tracing::warn;
+use std::num::NonZeroU64;
+use std::path::{Path, PathBuf};
+use tracing::{info, warn};
 
 /// Recover the tally from fragment log files and the initial preloaded block0 binary file.
 pub struct Replay {
@@ -19,6 +26,16 @@ pub struct Replay {
     logs_path: PathBuf,
     output: OutputFile,
     output_format: OutputFormat,
+    /// Path to the checkpoint file the recovery resumes from, if it exists,
+    /// and where new checkpoints are saved
+    checkpoint_path: Option<PathBuf>,
+    /// Number of fragments processed between two checkpoints
+    checkpoint_every: Option<NonZeroU64>,
+    /// Path to the folder containing the merged decryption shares of the
+    /// private vote plans to decrypt
+    shares_path: Option<PathBuf>,
+    /// Path to the folder of precomputed tally decryption tables
+    tally_tables_path: Option<PathBuf>,
 }
 
 impl Replay {
@@ -33,14 +50,62 @@ impl Replay {
             logs_path,
             output,
             output_format,
+            checkpoint_path: None,
+            checkpoint_every: None,
+            shares_path: None,
+            tally_tables_path: None,
         }
     }
 
+    /// resume from the checkpoint saved at `path` if any, and save a new one
+    /// there every `every` fragments processed and at the end of the replay
+    pub fn with_checkpoint(mut self, path: PathBuf, every: Option<NonZeroU64>) -> Self {
+        self.checkpoint_path = Some(path);
+        self.checkpoint_every = every;
+        self
+    }
+
+    /// decrypt the tallies of the private vote plans with the merged
+    /// decryption shares found in `shares_path`, in a `<vote plan id>.json` file
+    /// for every vote plan. The tally decryption tables are loaded from, or saved
+    /// to, `tally_tables_path` if any.
+    pub fn with_decryption(
+        mut self,
+        shares_path: PathBuf,
+        tally_tables_path: Option<PathBuf>,
+    ) -> Self {
+        self.shares_path = Some(shares_path);
+        self.tally_tables_path = tally_tables_path;
+        self
+    }
+
     pub fn exec(self) -> Result<(), Error> {
         let fragments = load_persistent_fragments_logs_from_folder_path(&self.logs_path)
             .map_err(Error::PersistenLogsLoading)?;
 
-        let (ledger, failed) = recover_ledger_from_logs(&self.block0, fragments)?;
+        let checkpoint = match &self.checkpoint_path {
+            Some(path) if path.exists() => {
+                let checkpoint = RecoveryCheckpoint::load(path)?;
+                info!(
+                    "resuming from checkpoint after {} fragments",
+                    checkpoint.fragments_processed()
+                );
+                Some(checkpoint)
+            }
+            _ => None,
+        };
+        let (ledger, failed) = recover_ledger_from_logs_with_checkpoints(
+            &self.block0,
+            fragments,
+            checkpoint,
+            self.checkpoint_every,
+            |checkpoint| {
+                if let Some(path) = &self.checkpoint_path {
+                    checkpoint.save(path)?;
+                }
+                Ok(())
+            },
+        )?;
         if !failed.is_empty() {
             warn!("{} fragments couldn't be properly processed", failed.len());
             for failed_fragment in failed {
@@ -48,8 +113,13 @@ impl Replay {
             }
         }
         let voteplans = ledger.active_vote_plans();
-        let voteplan_status: Vec<VotePlanStatus> =
+        let mut voteplan_status: Vec<VotePlanStatus> =
             voteplans.into_iter().map(VotePlanStatus::from).collect();
+        if let Some(shares_path) = &self.shares_path {
+            for vote_plan in voteplan_status.iter_mut() {
+                self.decrypt(vote_plan, shares_path)?;
+            }
+        }
         let mut out_writer = self.output.open()?;
         let content = self
             .output_format
@@ -57,6 +127,31 @@ impl Replay {
         out_writer.write_all(content.as_bytes())?;
         Ok(())
     }
+
+    fn decrypt(&self, vote_plan: &mut VotePlanStatus, shares_path: &Path) -> Result<(), Error> {
+        let encrypted = !vote_plan.proposals.is_empty()
+            && vote_plan.proposals.iter().all(|proposal| {
+                matches!(
+                    proposal.tally,
+                    Tally::Private {
+                        state: PrivateTallyState::Encrypted { .. }
+                    }
+                )
+            });
+        if !encrypted {
+            return Ok(());
+        }
+
+        let path = shares_path.join(format!("{}.json", vote_plan.id));
+        if !path.exists() {
+            warn!("no decryption shares for the vote plan {}", vote_plan.id);
+            return Ok(());
+        }
+        let shares = read_vote_plan_shares_from_file(Some(&path), vote_plan.proposals.len(), None)?;
+        decrypt_vote_plan(vote_plan, shares, self.tally_tables_path.as_deref())?;
+        info!("decrypted the tally of the vote plan {}", vote_plan.id);
+        Ok(())
+    }
 }
 
 #[allow(clippy::large_enum_variant)]
@@ -71,6 +166,9 @@ pub enum Error {
     #[error(transparent)]
     Recovery(#[from] crate::recovery::tally::Error),
 
+    #[error(transparent)]
+    Checkpoint(#[from] crate::recovery::checkpoint::Error),
+
     #[error(transparent)]
     OutputFile(#[from] OutputFileError),
 
@@ -79,4 +177,10 @@ pub enum Error {
 
     #[error("Could not load persistent logs from path")]
     PersistenLogsLoading(#[source] std::io::Error),
+
+    #[error("cannot read the decryption shares")]
+    Shares(#[from] SharesError),
+
+    #[error("cannot decrypt the tally")]
+    Decryption(#[from] jcli_lib::vote::Error),
 }
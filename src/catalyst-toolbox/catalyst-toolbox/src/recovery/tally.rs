@@ -1,11 +1,14 @@
+use super::checkpoint::{
+    AccountCheckpoint, Error as CheckpointError, MirrorKey, RecoveryCheckpoint,
+};
 use chain_addr::{Discrimination, Kind};
 use chain_core::property::Fragment as _;
-use chain_crypto::{Ed25519Extended, SecretKey};
+use chain_crypto::{Ed25519, Ed25519Extended, PublicKey, SecretKey};
 use chain_impl_mockchain::{
     account::{self, LedgerError, SpendingCounter},
     accounting::account::SpendingCounterIncreasing,
     block::{Block, BlockDate, HeaderId},
-    certificate::{self, VoteCast, VotePlan, VotePlanId},
+    certificate::{self, VoteCast, VoteDelegation, VotePlan, VotePlanId},
     chaineval::ConsensusEvalContext,
     fee::{FeeAlgorithm, LinearFee},
     fragment::{Fragment, FragmentId},
@@ -15,7 +18,7 @@ use chain_impl_mockchain::{
         WitnessAccountData,
     },
     value::ValueError,
-    vote::CommitteeId,
+    vote::{CommitteeId, VoteDelegationRatio, VoteDelegationType},
 };
 use chain_time::{Epoch, Slot, SlotDuration, TimeEra, TimeFrame, Timeline};
 use jormungandr_lib::{
@@ -27,6 +30,7 @@ use jormungandr_lib::{
     time::SecondsSinceUnixEpoch,
 };
 use std::collections::{HashMap, HashSet};
+use std::num::NonZeroU64;
 use std::ops::{Add, Range};
 use std::time::{Duration, SystemTime};
 use tracing::{debug, error, trace, warn};
@@ -49,6 +53,9 @@ pub enum Error {
 
     #[error(transparent)]
     ReplayError(#[from] ReplayError),
+
+    #[error(transparent)]
+    CheckpointError(#[from] CheckpointError),
 }
 
 fn timestamp_to_system_time(ts: SecondsSinceUnixEpoch) -> SystemTime {
@@ -105,6 +112,14 @@ pub(crate) fn valid_vote_cast(tx: &TransactionSlice<certificate::VoteCast>) -> b
         || tx.outputs().nb_outputs() != 0)
 }
 
+/// check that the transaction input/outputs/witnesses is valid for the vote delegation
+/// * Only 1 input (subsequently 1 witness), no output
+fn valid_vote_delegation(tx: &TransactionSlice<VoteDelegation>) -> bool {
+    !(tx.inputs().nb_inputs() != 1
+        || tx.witnesses().nb_witnesses() != 1
+        || tx.outputs().nb_outputs() != 0)
+}
+
 fn verify_original_tx(
     spending_counter: SpendingCounter,
     block0_hash: &HeaderId,
@@ -176,13 +191,18 @@ pub enum ValidationError {
     #[error("Invalid ballot, only 1 input (subsequently 1 witness) and no output is accepted")]
     InvalidVoteCast,
 
+    #[error(
+        "Invalid vote delegation, only 1 input (subsequently 1 witness) and no output is accepted"
+    )]
+    InvalidVoteDelegation,
+
     #[error("Out of voting period")]
     VotingPeriodError,
 
     #[error("Out of tally period")]
     TallyPeriodError,
 
-    #[error("Fragment should be either a votecast or a votetally")]
+    #[error("Fragment should be either a votecast, a vote delegation or a votetally")]
     NotAVotingFragment,
 
     #[error("Cannot handle utxo inputs")]
@@ -310,6 +330,21 @@ impl<I: Iterator<Item = PersistentFragmentLog>> VoteFragmentFilter<I> {
         *self.spending_counters.get_mut(&identifier).unwrap() += 1;
         Ok(sc)
     }
+
+    fn checkpoint(&self) -> (Vec<(account::Identifier, u32)>, Vec<FragmentId>) {
+        (
+            self.spending_counters
+                .iter()
+                .map(|(identifier, counter)| (identifier.clone(), *counter))
+                .collect(),
+            self.replay_protection.iter().cloned().collect(),
+        )
+    }
+
+    fn restore(&mut self, checkpoint: &RecoveryCheckpoint) {
+        self.spending_counters = checkpoint.spending_counters.iter().cloned().collect();
+        self.replay_protection = checkpoint.replay_protection.iter().cloned().collect();
+    }
 }
 
 impl<I: Iterator<Item = PersistentFragmentLog>> Iterator for VoteFragmentFilter<I> {
@@ -328,6 +363,14 @@ impl<I: Iterator<Item = PersistentFragmentLog>> Iterator for VoteFragmentFilter<
 
                     self.validate_tx(&transaction_slice, fragment.id())
                 }
+                Fragment::VoteDelegation(tx) => {
+                    let transaction_slice = tx.as_slice();
+                    if !valid_vote_delegation(&transaction_slice) {
+                        return Err((fragment, ValidationError::InvalidVoteDelegation));
+                    }
+
+                    self.validate_tx(&transaction_slice, fragment.id())
+                }
                 Fragment::VoteTally(tx) => self.validate_tx(&tx.as_slice(), fragment.id()),
                 Fragment::Transaction(tx) => self.validate_tx(&tx.as_slice(), fragment.id()),
                 _ => Err(ValidationError::NotAVotingFragment),
@@ -347,25 +390,93 @@ impl<I: Iterator<Item = PersistentFragmentLog>> Iterator for VoteFragmentFilter<
     }
 }
 
+fn recovery_checkpoint<I: Iterator<Item = PersistentFragmentLog>>(
+    fragments_processed: u64,
+    date: BlockDate,
+    ledger: &Ledger,
+    fragment_filter: &VoteFragmentFilter<I>,
+    fragment_replayer: &FragmentReplayer,
+) -> RecoveryCheckpoint {
+    let (spending_counters, replay_protection) = fragment_filter.checkpoint();
+    RecoveryCheckpoint {
+        fragments_processed,
+        date,
+        mirror_keys: fragment_replayer.mirror_keys(),
+        accounts: ledger
+            .accounts()
+            .iter()
+            .map(|(identifier, state)| AccountCheckpoint {
+                identifier: identifier.clone(),
+                value: state.value,
+                spending: state.spending.clone(),
+            })
+            .collect(),
+        spending_counters,
+        replay_protection,
+        vote_plans: ledger.vote_plan_checkpoints(),
+        vote_delegations: ledger
+            .vote_delegations()
+            .iter()
+            .map(|(delegator, scope, delegation)| {
+                (
+                    delegator.clone(),
+                    VoteDelegation {
+                        scope: scope.clone(),
+                        delegation: delegation.clone(),
+                    },
+                )
+            })
+            .collect(),
+    }
+}
+
 pub fn recover_ledger_from_logs(
     block0: &Block,
     fragment_logs: impl Iterator<Item = Result<PersistentFragmentLog, FragmentLogDeserializeError>>,
 ) -> Result<(Ledger, Vec<Fragment>), Error> {
-    let (mut fragment_replayer, new_block0) = FragmentReplayer::from_block0(block0)?;
+    recover_ledger_from_logs_with_checkpoints(block0, fragment_logs, None, None, |_| Ok(()))
+}
+
+/// Same as [`recover_ledger_from_logs`], but resuming from `checkpoint` if any
+/// and calling `on_checkpoint` every `checkpoint_every` fragments processed, as
+/// well as once all the fragments were processed.
+///
+/// The fragment logs should be the same ones used to build the checkpoint: the
+/// first [`RecoveryCheckpoint::fragments_processed`] fragments are skipped.
+/// Only the fragments recorded as failed after the checkpoint are returned.
+pub fn recover_ledger_from_logs_with_checkpoints(
+    block0: &Block,
+    fragment_logs: impl Iterator<Item = Result<PersistentFragmentLog, FragmentLogDeserializeError>>,
+    checkpoint: Option<RecoveryCheckpoint>,
+    checkpoint_every: Option<NonZeroU64>,
+    mut on_checkpoint: impl FnMut(RecoveryCheckpoint) -> Result<(), Error>,
+) -> Result<(Ledger, Vec<Fragment>), Error> {
+    let mirror_keys = checkpoint
+        .as_ref()
+        .map(|checkpoint| checkpoint.mirror_keys.clone())
+        .unwrap_or_default();
+    let (mut fragment_replayer, new_block0) = FragmentReplayer::from_block0(block0, mirror_keys)?;
 
     // we use block0 header id instead of the new one, to keep validation on old tx that uses the original block0 id.
     // This is used so we can run the VoteTally certificates with the original (issued) committee members ones.
     let mut ledger =
         Ledger::new(block0.header().id(), new_block0.fragments()).map_err(Error::LedgerError)?;
 
+    let fragments_processed = checkpoint
+        .as_ref()
+        .map(|checkpoint| checkpoint.fragments_processed)
+        .unwrap_or(0);
+
     // deserialize fragments to get a clean iterator over them
-    let deserialized_fragment_logs = fragment_logs.filter_map(|fragment_log| match fragment_log {
-        Ok(fragment) => Some(fragment),
-        Err(e) => {
-            error!("Error deserializing PersistentFragmentLog: {:?}", e);
-            None
-        }
-    });
+    let deserialized_fragment_logs = fragment_logs
+        .filter_map(|fragment_log| match fragment_log {
+            Ok(fragment) => Some(fragment),
+            Err(e) => {
+                error!("Error deserializing PersistentFragmentLog: {:?}", e);
+                None
+            }
+        })
+        .skip(fragments_processed as usize);
 
     // use double of proposals range as possible spending counters to check
     let spending_counter_max_check: u32 = voteplans_from_block0(block0)
@@ -374,14 +485,42 @@ pub fn recover_ledger_from_logs(
         .count() as u32
         * 2;
 
-    let fragment_filter = VoteFragmentFilter::new(
+    let mut fragment_filter = VoteFragmentFilter::new(
         block0.clone(),
         0..spending_counter_max_check,
         deserialized_fragment_logs,
     )?;
     let mut failed_fragments = Vec::new();
     let mut current_date = BlockDate::first();
-    for filtered_fragment in fragment_filter {
+
+    if let Some(checkpoint) = &checkpoint {
+        for account in &checkpoint.accounts {
+            ledger = ledger.restore_account(
+                &account.identifier,
+                account.value,
+                account.spending.clone(),
+            );
+        }
+        for vote_plan in &checkpoint.vote_plans {
+            ledger = ledger.restore_vote_plan_checkpoint(vote_plan)?;
+        }
+        for (delegator, vote_delegation) in &checkpoint.vote_delegations {
+            ledger = ledger.apply_vote_delegation(delegator.clone(), vote_delegation.clone());
+        }
+        if checkpoint.date > current_date {
+            ledger = increment_ledger_time_up_to(&ledger, checkpoint.date);
+            current_date = checkpoint.date;
+        }
+        fragment_filter.restore(checkpoint);
+        fragment_replayer.restore(&ledger);
+        debug!(
+            "resuming recovery after {} fragments at {}",
+            fragments_processed, current_date
+        );
+    }
+
+    let mut processed = fragments_processed;
+    while let Some(filtered_fragment) = fragment_filter.next() {
         let new_fragment = filtered_fragment
             .map_err(|(fragment, err)| (Error::from(err), fragment))
             .and_then(|fragment| fragment_replayer.replay(fragment))
@@ -420,8 +559,29 @@ pub fn recover_ledger_from_logs(
             }
             Err(e) => unreachable!("Should be impossible to fail, since we should be using proper spending counters and signatures {:?}", e)
         }
+
+        processed += 1;
+        if let Some(every) = checkpoint_every {
+            if processed % every.get() == 0 {
+                on_checkpoint(recovery_checkpoint(
+                    processed,
+                    current_date,
+                    &ledger,
+                    &fragment_filter,
+                    &fragment_replayer,
+                ))?;
+            }
+        }
     }
 
+    on_checkpoint(recovery_checkpoint(
+        processed,
+        current_date,
+        &ledger,
+        &fragment_filter,
+        &fragment_replayer,
+    ))?;
+
     Ok((ledger, failed_fragments))
 }
 
@@ -429,19 +589,36 @@ struct FragmentReplayer {
     wallets: HashMap<Address, Wallet>,
     non_voting_wallets: HashMap<Address, Wallet>,
     pending_requests: HashMap<FragmentId, Address>,
+    mirror_keys: HashMap<Address, SecretKey<Ed25519Extended>>,
 
     settings: Settings,
 }
 
 impl FragmentReplayer {
-    // build a new block0 with mirror accounts and same configuration as original one
-    fn from_block0(block0: &Block) -> Result<(Self, Block), Error> {
+    // build a new block0 with mirror accounts and same configuration as original one,
+    // reusing the given mirror keys so the new block0 matches the one of a checkpoint
+    fn from_block0(block0: &Block, mirror_keys: Vec<MirrorKey>) -> Result<(Self, Block), Error> {
         let mut config =
             Block0Configuration::from_block(block0).map_err(Error::Block0ConfigurationError)?;
 
         let mut wallets = HashMap::new();
+        let mut non_voting_wallets = HashMap::new();
+        let mut keys = HashMap::new();
         let mut rng = rand::thread_rng();
 
+        let mut voting_keys = HashMap::new();
+        for mirror_key in mirror_keys {
+            if mirror_key.voting {
+                voting_keys.insert(mirror_key.original, mirror_key.key);
+            } else {
+                non_voting_wallets.insert(
+                    mirror_key.original.clone(),
+                    Wallet::new_from_key(mirror_key.key.clone()),
+                );
+                keys.insert(mirror_key.original, mirror_key.key);
+            }
+        }
+
         let committee_members = config
             .blockchain_configuration
             .committees
@@ -454,8 +631,12 @@ impl FragmentReplayer {
             if let Initial::Fund(ref mut utxos) = initial {
                 let mut new_committee_accounts = Vec::new();
                 for utxo in utxos.iter_mut() {
-                    let mut wallet =
-                        Wallet::new_from_key(<SecretKey<Ed25519Extended>>::generate(&mut rng));
+                    let key = voting_keys
+                        .get(&utxo.address)
+                        .cloned()
+                        .unwrap_or_else(|| <SecretKey<Ed25519Extended>>::generate(&mut rng));
+                    keys.insert(utxo.address.clone(), key.clone());
+                    let mut wallet = Wallet::new_from_key(key);
                     let new_initial_utxo = InitialUTxO {
                         address: wallet
                             .account_id()
@@ -494,9 +675,10 @@ impl FragmentReplayer {
         Ok((
             Self {
                 wallets,
-                non_voting_wallets: HashMap::new(),
+                non_voting_wallets,
                 settings: Settings::new(block0).unwrap(),
                 pending_requests: HashMap::new(),
+                mirror_keys: keys,
             },
             config.to_block(),
         ))
@@ -527,6 +709,74 @@ impl FragmentReplayer {
         Ok(res)
     }
 
+    // the mirror account of one of the original accounts, the account itself
+    // if it is not known to the replay
+    fn mirror_identifier(&self, original: &account::Identifier) -> account::Identifier {
+        let address: Address = Identifier::from(original.clone())
+            .to_address(Discrimination::Production)
+            .into();
+        self.wallets
+            .get(&address)
+            .or_else(|| self.non_voting_wallets.get(&address))
+            .map(|wallet| {
+                account::Identifier::from(<PublicKey<Ed25519>>::from(wallet.account_id()))
+            })
+            .unwrap_or_else(|| original.clone())
+    }
+
+    fn replay_vote_delegation(
+        &mut self,
+        tx: TransactionSlice<VoteDelegation>,
+    ) -> Result<Fragment, Error> {
+        let (vote_delegation, identifier, _) = deconstruct_account_transaction(&tx)?;
+        let address =
+            Identifier::from(identifier).to_address(chain_addr::Discrimination::Production);
+
+        // the representatives vote with their mirror accounts
+        let delegation = match vote_delegation.delegation {
+            VoteDelegationType::NonDelegated => VoteDelegationType::NonDelegated,
+            VoteDelegationType::Full(representative) => {
+                VoteDelegationType::Full(self.mirror_identifier(&representative))
+            }
+            VoteDelegationType::Ratio(ratio) => VoteDelegationType::Ratio(
+                VoteDelegationRatio::new(
+                    ratio.parts(),
+                    ratio
+                        .representatives()
+                        .iter()
+                        .map(|(representative, part)| {
+                            (self.mirror_identifier(representative), *part)
+                        })
+                        .collect(),
+                )
+                .expect("mirror accounts are distinct"),
+            ),
+        };
+        let vote_delegation = VoteDelegation {
+            scope: vote_delegation.scope,
+            delegation,
+        };
+
+        let address: Address = address.into();
+        let wallet = self
+            .wallets
+            .get_mut(&address)
+            .ok_or_else(|| ReplayError::NonVotingAccount(address.to_string()))?;
+
+        // unwrap checked in the validation step
+        let builder_help = wallet
+            .new_transaction(tx.total_input().unwrap(), 0)
+            .unwrap();
+        let mut builder =
+            TransactionBuilder::new(self.settings.clone(), vote_delegation, tx.valid_until());
+        builder.add_input(builder_help.input(), builder_help.witness_builder());
+        let res = Fragment::VoteDelegation(builder.finalize_tx(()).unwrap());
+
+        debug!("replaying vote delegation from {}", address);
+        self.pending_requests.insert(res.id(), address);
+        Ok(res)
+    }
+
     fn replay_tx(&mut self, tx: TransactionSlice<NoExtra>) -> Result<Fragment, Error> {
         let (_, identifier, _) = deconstruct_account_transaction(&tx)?;
         let address =
@@ -545,9 +795,9 @@ impl FragmentReplayer {
             self.non_voting_wallets
                 .entry(address.clone())
                 .or_insert_with(|| {
-                    Wallet::new_from_key(<SecretKey<Ed25519Extended>>::generate(
-                        &mut rand::thread_rng(),
-                    ))
+                    let key = <SecretKey<Ed25519Extended>>::generate(&mut rand::thread_rng());
+                    self.mirror_keys.insert(address.clone(), key.clone());
+                    Wallet::new_from_key(key)
                 })
                 .account_id()
         }
@@ -583,6 +833,7 @@ impl FragmentReplayer {
     ) -> Result<ReplayedFragment, (Error, Fragment)> {
         let replayed = match &original.fragment {
             Fragment::VoteCast(ref tx) => self.replay_votecast(tx.as_slice()),
+            Fragment::VoteDelegation(ref tx) => self.replay_vote_delegation(tx.as_slice()),
             Fragment::Transaction(ref tx) => self.replay_tx(tx.as_slice()),
             fragment @ Fragment::VoteTally(_) => Ok(fragment.clone()),
             fragment => Err(ReplayError::NotAVotingFragment {
@@ -594,6 +845,34 @@ impl FragmentReplayer {
         Ok(ReplayedFragment { replayed, original })
     }
 
+    fn mirror_keys(&self) -> Vec<MirrorKey> {
+        self.mirror_keys
+            .iter()
+            .map(|(original, key)| MirrorKey {
+                original: original.clone(),
+                key: key.clone(),
+                voting: self.wallets.contains_key(original),
+            })
+            .collect()
+    }
+
+    // sync the mirror wallets with the accounts of a ledger restored from a checkpoint
+    fn restore(&mut self, ledger: &Ledger) {
+        for wallet in self
+            .wallets
+            .values_mut()
+            .chain(self.non_voting_wallets.values_mut())
+        {
+            let identifier =
+                account::Identifier::from(<PublicKey<Ed25519>>::from(wallet.account_id()));
+            if let Ok(state) = ledger.accounts().get_state(&identifier) {
+                wallet
+                    .set_state(state.value, state.spending.get_valid_counters())
+                    .expect("cannot update wallet state");
+            }
+        }
+    }
+
     fn confirm_fragment(&mut self, fragment: &Fragment) {
         if let Some(addr) = self.pending_requests.get(&fragment.id()) {
             if let Some(wallet) = self.wallets.get_mut(addr) {
@@ -605,16 +884,20 @@ impl FragmentReplayer {
 
 #[cfg(test)]
 mod test {
-    use super::recover_ledger_from_logs;
+    use super::{recover_ledger_from_logs, recover_ledger_from_logs_with_checkpoints};
+    use crate::recovery::RecoveryCheckpoint;
     use assert_fs::fixture::PathChild;
     use assert_fs::TempDir;
     use chain_addr::Discrimination;
-    use chain_impl_mockchain::certificate::VoteTallyPayload;
+    use chain_impl_mockchain::block::Block;
+    use chain_impl_mockchain::certificate::{VoteDelegation, VoteTallyPayload};
     use chain_impl_mockchain::chaintypes::ConsensusType;
     use chain_impl_mockchain::key::BftLeaderId;
+    use chain_impl_mockchain::ledger::Ledger;
     use chain_impl_mockchain::tokens::minting_policy::MintingPolicy;
     use chain_impl_mockchain::vote::Choice;
     use chain_impl_mockchain::vote::Tally;
+    use chain_impl_mockchain::vote::{VoteDelegationScope, VoteDelegationType};
     use jormungandr_automation::jormungandr::Block0ConfigurationBuilder;
     use jormungandr_automation::testing::block0::Block0ConfigurationExtension;
     use jormungandr_automation::testing::VotePlanBuilder;
@@ -624,15 +907,19 @@ mod test {
     use jormungandr_lib::interfaces::{load_persistent_fragments_logs_from_folder_path, Initial};
     use jormungandr_lib::time::SecondsSinceUnixEpoch;
     use rand::rngs::OsRng;
+    use std::num::NonZeroU64;
     use thor::vote_plan_cert;
     use thor::write_into_persistent_log;
     use thor::FragmentBuilder;
     use thor::Wallet as TestWallet;
 
-    #[test]
-    fn test_vote_flow() {
-        let temp_dir = TempDir::new().unwrap();
-        let funds = 1_000_000;
+    const FUNDS: u64 = 1_000_000;
+
+    /// block0 and the fragment logs of 3 public votes followed by the tally,
+    /// with `with_vote_delegation` the third voter also delegates its voting
+    /// power to the first one after the first vote
+    fn vote_flow(temp_dir: &TempDir, with_vote_delegation: bool) -> (Block, std::path::PathBuf) {
+        let funds = FUNDS;
         let slot_duration = 4;
         let slots_per_epoch = 10;
 
@@ -640,7 +927,7 @@ mod test {
         let alice =
             TestWallet::new_account_with_discrimination(&mut rng, Discrimination::Production);
         let bob = TestWallet::new_account_with_discrimination(&mut rng, Discrimination::Production);
-        let clarice =
+        let mut clarice =
             TestWallet::new_account_with_discrimination(&mut rng, Discrimination::Production);
 
         let vote_plan = VotePlanBuilder::new().proposals_count(3).public().build();
@@ -697,17 +984,26 @@ mod test {
         );
 
         //vote fragments
-        let mut fragments: Vec<PersistentFragmentLog> = vec![
-            fragment_builder.vote_cast(&alice, &vote_plan, 0, &Choice::new(1)),
-            fragment_builder.vote_cast(&bob, &vote_plan, 1, &Choice::new(1)),
-            fragment_builder.vote_cast(&clarice, &vote_plan, 2, &Choice::new(1)),
-        ]
-        .into_iter()
-        .map(|fragment| PersistentFragmentLog {
-            time: block0_configuration.blockchain_configuration.block0_date,
-            fragment,
-        })
-        .collect();
+        let mut fragments =
+            vec![fragment_builder.vote_cast(&alice, &vote_plan, 0, &Choice::new(1))];
+        if with_vote_delegation {
+            let vote_delegation = VoteDelegation {
+                scope: VoteDelegationScope::Token(token_id.clone()),
+                delegation: VoteDelegationType::Full(alice.public_key().into()),
+            };
+            fragments.push(fragment_builder.vote_delegation(&clarice, &vote_delegation));
+            clarice.confirm_transaction();
+        }
+        fragments.push(fragment_builder.vote_cast(&bob, &vote_plan, 1, &Choice::new(1)));
+        fragments.push(fragment_builder.vote_cast(&clarice, &vote_plan, 2, &Choice::new(1)));
+
+        let mut fragments: Vec<PersistentFragmentLog> = fragments
+            .into_iter()
+            .map(|fragment| PersistentFragmentLog {
+                time: block0_configuration.blockchain_configuration.block0_date,
+                fragment,
+            })
+            .collect();
 
         let valid_until = chain_impl_mockchain::block::BlockDate {
             epoch: 2,
@@ -739,22 +1035,23 @@ mod test {
         let fragment_log = persistent_fragment_log_output.child("log.log");
         // below loop of writing/reading is done not to loose original test
         write_into_persistent_log(fragment_log.path(), fragments).unwrap();
-        let fragments =
-            load_persistent_fragments_logs_from_folder_path(persistent_fragment_log_output.path())
-                .unwrap();
 
-        let block0 = block0_configuration.to_block();
-        let (ledger, failed) = recover_ledger_from_logs(&block0, fragments).unwrap();
+        (
+            block0_configuration.to_block(),
+            persistent_fragment_log_output.path().to_path_buf(),
+        )
+    }
 
-        assert_eq!(failed.len(), 0, "Failed: {}", failed.len());
+    /// check the weight of the voted option of every proposal
+    fn assert_public_tally(ledger: &Ledger, expected: &[u64]) {
         for voteplan in ledger.active_vote_plans() {
             println!("Voteplan: {}", voteplan.id);
-            for proposal in voteplan.proposals {
+            for (proposal, expected) in voteplan.proposals.into_iter().zip(expected) {
                 match proposal.tally {
                     Tally::Public { result } => {
                         let results = result.results();
                         assert_eq!(*results.get(0).unwrap(), 0.into());
-                        assert_eq!(*results.get(1).unwrap(), funds.into());
+                        assert_eq!(*results.get(1).unwrap(), (*expected).into());
                         assert_eq!(*results.get(2).unwrap(), 0.into());
                     }
                     Tally::Private { .. } => {
@@ -764,4 +1061,102 @@ mod test {
             }
         }
     }
+
+    #[test]
+    fn test_vote_flow() {
+        let temp_dir = TempDir::new().unwrap();
+        let (block0, logs_path) = vote_flow(&temp_dir, false);
+        let fragments = load_persistent_fragments_logs_from_folder_path(&logs_path).unwrap();
+
+        let (ledger, failed) = recover_ledger_from_logs(&block0, fragments).unwrap();
+
+        assert_eq!(failed.len(), 0, "Failed: {}", failed.len());
+        assert_public_tally(&ledger, &[FUNDS, FUNDS, FUNDS]);
+    }
+
+    #[test]
+    fn test_vote_flow_resumed_from_checkpoint() {
+        let temp_dir = TempDir::new().unwrap();
+        let (block0, logs_path) = vote_flow(&temp_dir, false);
+
+        let mut checkpoints = Vec::new();
+        let fragments = load_persistent_fragments_logs_from_folder_path(&logs_path).unwrap();
+        recover_ledger_from_logs_with_checkpoints(
+            &block0,
+            fragments,
+            None,
+            NonZeroU64::new(2),
+            |checkpoint| {
+                checkpoints.push(checkpoint);
+                Ok(())
+            },
+        )
+        .unwrap();
+        assert_eq!(checkpoints.len(), 3);
+        assert_eq!(checkpoints[0].fragments_processed(), 2);
+        assert_eq!(checkpoints[1].fragments_processed(), 4);
+
+        // go through the binary format, as a checkpoint file would
+        let checkpoint_file = temp_dir.child("checkpoint");
+        checkpoints[0].save(checkpoint_file.path()).unwrap();
+        let checkpoint = RecoveryCheckpoint::load(checkpoint_file.path()).unwrap();
+        assert_eq!(checkpoint.vote_plans()[0].votes_count(), 2);
+
+        let fragments = load_persistent_fragments_logs_from_folder_path(&logs_path).unwrap();
+        let (ledger, failed) = recover_ledger_from_logs_with_checkpoints(
+            &block0,
+            fragments,
+            Some(checkpoint),
+            None,
+            |_| Ok(()),
+        )
+        .unwrap();
+
+        assert_eq!(failed.len(), 0, "Failed: {}", failed.len());
+        assert_public_tally(&ledger, &[FUNDS, FUNDS, FUNDS]);
+    }
+
+    #[test]
+    fn test_vote_flow_with_vote_delegation_resumed_from_checkpoint() {
+        let temp_dir = TempDir::new().unwrap();
+        let (block0, logs_path) = vote_flow(&temp_dir, true);
+        // the voting power of the third voter is added to the vote of the
+        // first one, except on the proposal it voted on itself
+        let expected = [2 * FUNDS, FUNDS, FUNDS];
+
+        let mut checkpoints = Vec::new();
+        let fragments = load_persistent_fragments_logs_from_folder_path(&logs_path).unwrap();
+        let (ledger, failed) = recover_ledger_from_logs_with_checkpoints(
+            &block0,
+            fragments,
+            None,
+            NonZeroU64::new(2),
+            |checkpoint| {
+                checkpoints.push(checkpoint);
+                Ok(())
+            },
+        )
+        .unwrap();
+        assert_eq!(failed.len(), 0, "Failed: {}", failed.len());
+        assert_public_tally(&ledger, &expected);
+
+        // the checkpoint is taken right after the vote delegation
+        let checkpoint_file = temp_dir.child("checkpoint");
+        checkpoints[0].save(checkpoint_file.path()).unwrap();
+        let checkpoint = RecoveryCheckpoint::load(checkpoint_file.path()).unwrap();
+        assert_eq!(checkpoint.vote_delegations.len(), 1);
+
+        let fragments = load_persistent_fragments_logs_from_folder_path(&logs_path).unwrap();
+        let (ledger, failed) = recover_ledger_from_logs_with_checkpoints(
+            &block0,
+            fragments,
+            Some(checkpoint),
+            None,
+            |_| Ok(()),
+        )
+        .unwrap();
+
+        assert_eq!(failed.len(), 0, "Failed: {}", failed.len());
+        assert_public_tally(&ledger, &expected);
+    }
 }
@@ -137,7 +137,7 @@ mod tests {
     use jormungandr_lib::crypto::{account::Identifier, hash::Hash};
     use snapshot_lib::registration::{Delegations, VotingRegistration};
     use snapshot_lib::Snapshot;
-    use snapshot_lib::{Fraction, RawSnapshot};
+    use snapshot_lib::{Fraction, RawSnapshot, VotingPowerTransform};
     use test_strategy::proptest;
 
     const DEFAULT_TEST_THRESHOLD: usize = 1;
@@ -150,6 +150,7 @@ mod tests {
             DEFAULT_SNAPSHOT_THRESHOLD.into(),
             Fraction::from(1),
             &|_vk: &Identifier| String::new(),
+            &VotingPowerTransform::Linear,
         )
         .unwrap();
 
@@ -183,6 +184,7 @@ mod tests {
             DEFAULT_SNAPSHOT_THRESHOLD.into(),
             Fraction::from(1),
             &|_vk: &Identifier| String::new(),
+            &VotingPowerTransform::Linear,
         )
         .unwrap();
 
@@ -206,6 +208,7 @@ mod tests {
             DEFAULT_SNAPSHOT_THRESHOLD.into(),
             Fraction::from(1),
             &|_vk: &Identifier| String::new(),
+            &VotingPowerTransform::Linear,
         )
         .unwrap();
 
@@ -318,6 +321,7 @@ mod tests {
             0.into(),
             Fraction::from(1u64),
             &|_voting_key: &Identifier| String::new(),
+            &VotingPowerTransform::Linear,
         )
         .unwrap();
 
@@ -364,6 +368,7 @@ mod tests {
             0.into(),
             Fraction::new(1u64, 9u64),
             &|_vk: &Identifier| String::new(),
+            &VotingPowerTransform::Linear,
         )
         .unwrap();
 
@@ -389,6 +394,7 @@ mod tests {
             DEFAULT_SNAPSHOT_THRESHOLD.into(),
             Fraction::from(1),
             &|_vk: &Identifier| String::new(),
+            &VotingPowerTransform::Linear,
         )
         .unwrap();
 
@@ -199,7 +199,7 @@ impl VoteRoundGenerator {
                 let vote_end = manager.plan().vote_end();
 
                 match manager.plan().payload_type() {
-                    PayloadType::Private => {
+                    PayloadType::Private | PayloadType::PrivateWeighted => {
                         let mut results = Vec::new();
                         let mut shares = Vec::new();
                         for proposal in manager.statuses().proposals {
@@ -228,7 +228,11 @@ impl VoteRoundGenerator {
                                 .zip(shares.into_iter())
                                 .map(|(tally_result, decrypt_shares)| {
                                     DecryptedPrivateTallyProposal {
-                                        decrypt_shares,
+                                        decrypt_shares: decrypt_shares
+                                            .into_vec()
+                                            .into_iter()
+                                            .map(Some)
+                                            .collect(),
                                         tally_result,
                                     }
                                 })
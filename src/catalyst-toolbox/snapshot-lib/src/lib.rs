@@ -5,12 +5,14 @@ use registration::{Delegations, MainnetRewardAddress, VotingRegistration};
 use serde::{Deserialize, Serialize};
 use std::{borrow::Borrow, collections::BTreeMap, iter::Iterator, num::NonZeroU64};
 use thiserror::Error;
+pub use transform::{PiecewiseSegment, VotingPowerTransform};
 pub use voter_hir::VoterHIR;
 pub use voter_hir::VotingGroup;
 use voting_group::VotingGroupAssigner;
 
 mod influence_cap;
 pub mod registration;
+mod transform;
 mod voter_hir;
 pub mod voting_group;
 
@@ -31,6 +33,8 @@ pub enum Error {
     NotEnoughVoters,
     #[error("voting power overflow")]
     Overflow,
+    #[error("invalid voting power transform: {0}")]
+    InvalidTransform(String),
 }
 
 /// Contribution to a voting key for some registration
@@ -49,6 +53,19 @@ pub struct SnapshotInfo {
     /// VoterHIR, due to voting power caps or additional transformations.
     pub contributions: Vec<KeyContribution>,
     pub hir: VoterHIR,
+    /// Voting power of this voter before the influence cap is applied, only present
+    /// when the entry was computed from a raw snapshot.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub voting_power_audit: Option<VotingPowerAudit>,
+}
+
+/// Record of how the voting power of a voter was derived from its stake
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct VotingPowerAudit {
+    /// Total stake delegated to the voting key
+    pub raw_stake: Value,
+    /// Voting power after the voting power transform, but before the influence cap
+    pub transformed_power: Value,
 }
 
 #[derive(Clone, Debug, PartialEq, Eq)]
@@ -65,7 +82,9 @@ impl Snapshot {
         stake_threshold: Value,
         cap: Fraction,
         voting_group_assigner: &impl VotingGroupAssigner,
+        transform: &VotingPowerTransform,
     ) -> Result<Self, Error> {
+        transform.validate()?;
         let raw_contribs = raw_snapshot
             .0
             .into_iter()
@@ -125,15 +144,23 @@ impl Snapshot {
             });
         let entries = raw_contribs
             .into_iter()
-            .map(|(k, contributions)| SnapshotInfo {
-                hir: VoterHIR {
-                    voting_group: voting_group_assigner.assign(&k),
-                    voting_key: k,
-                    voting_power: contributions.iter().map(|c| c.value).sum::<u64>().into(),
-                },
-                contributions,
+            .map(|(k, contributions)| {
+                let raw_stake = contributions.iter().map(|c| c.value).sum::<u64>();
+                let voting_power = transform.apply(raw_stake)?.into();
+                Ok(SnapshotInfo {
+                    hir: VoterHIR {
+                        voting_group: voting_group_assigner.assign(&k),
+                        voting_key: k,
+                        voting_power,
+                    },
+                    contributions,
+                    voting_power_audit: Some(VotingPowerAudit {
+                        raw_stake: raw_stake.into(),
+                        transformed_power: voting_power,
+                    }),
+                })
             })
-            .collect();
+            .collect::<Result<_, Error>>()?;
         Ok(Self {
             inner: Self::apply_voting_power_cap(entries, cap)?
                 .into_iter()
@@ -234,14 +261,16 @@ pub mod tests {
                 raw,
                 stake_threshold.into(),
                 Fraction::from(1u64),
-                &DummyAssigner
+                &DummyAssigner,
+                &VotingPowerTransform::Linear,
             )
             .unwrap()
                 == Snapshot::from_raw_snapshot(
                     add,
                     stake_threshold.into(),
                     Fraction::from(1u64),
-                    &DummyAssigner
+                    &DummyAssigner,
+                    &VotingPowerTransform::Linear,
                 )
                 .unwrap(),
             additional_reg.voting_power < stake_threshold.into()
@@ -260,6 +289,7 @@ pub mod tests {
                         threshold.into(),
                         Fraction::from(1),
                         &|_vk: &Identifier| String::new(),
+                        &VotingPowerTransform::Linear,
                     )
                     .unwrap()
                 })
@@ -276,6 +306,7 @@ pub mod tests {
             0.into(),
             Fraction::from(1),
             &|_vk: &Identifier| String::new(),
+            &VotingPowerTransform::Linear,
         )
         .unwrap();
         let total_stake = snapshot
@@ -295,14 +326,16 @@ pub mod tests {
                 vec![reg].into(),
                 0.into(),
                 Fraction::from(1u64),
-                &DummyAssigner
+                &DummyAssigner,
+                &VotingPowerTransform::Linear,
             )
             .unwrap(),
             Snapshot::from_raw_snapshot(
                 vec![].into(),
                 0.into(),
                 Fraction::from(1u64),
-                &DummyAssigner
+                &DummyAssigner,
+                &VotingPowerTransform::Linear,
             )
             .unwrap(),
         )
@@ -335,6 +368,7 @@ pub mod tests {
             0.into(),
             Fraction::from(1u64),
             &DummyAssigner,
+            &VotingPowerTransform::Linear,
         )
         .unwrap();
         let vp_1: u64 = snapshot
@@ -351,6 +385,60 @@ pub mod tests {
         assert_eq!(vp_2 - vp_1, n / 2); // last key get the remainder during distribution
     }
 
+    #[cfg(test)]
+    #[test]
+    fn test_voting_power_transform() {
+        let voting_pub_key_1 = Identifier::from_hex(&hex::encode([0; 32])).unwrap();
+        let voting_pub_key_2 = Identifier::from_hex(&hex::encode([1; 32])).unwrap();
+        let raw_snapshot = [
+            (&voting_pub_key_1, 100),
+            (&voting_pub_key_1, 300),
+            (&voting_pub_key_2, 9),
+        ]
+        .into_iter()
+        .map(|(vk, voting_power)| VotingRegistration {
+            stake_public_key: String::new(),
+            voting_power: voting_power.into(),
+            reward_address: String::new(),
+            delegations: Delegations::Legacy(vk.clone()),
+            voting_purpose: 0,
+        })
+        .collect::<Vec<_>>();
+
+        let snapshot = Snapshot::from_raw_snapshot(
+            raw_snapshot.into(),
+            0.into(),
+            Fraction::from(1u64),
+            &DummyAssigner,
+            &VotingPowerTransform::SquareRoot,
+        )
+        .unwrap();
+        let audits = snapshot
+            .to_full_snapshot_info()
+            .into_iter()
+            .map(|entry| (entry.hir.voting_power, entry.voting_power_audit.unwrap()))
+            .collect::<Vec<_>>();
+        assert_eq!(
+            audits,
+            vec![
+                (
+                    20.into(),
+                    VotingPowerAudit {
+                        raw_stake: 400.into(),
+                        transformed_power: 20.into(),
+                    }
+                ),
+                (
+                    3.into(),
+                    VotingPowerAudit {
+                        raw_stake: 9.into(),
+                        transformed_power: 3.into(),
+                    }
+                ),
+            ]
+        );
+    }
+
     #[cfg(test)]
     #[test]
     fn test_parsing() {
@@ -367,9 +455,14 @@ pub mod tests {
             }
         ]"#,
         ).unwrap();
-        let snapshot =
-            Snapshot::from_raw_snapshot(raw, 0.into(), Fraction::from(1u64), &DummyAssigner)
-                .unwrap();
+        let snapshot = Snapshot::from_raw_snapshot(
+            raw,
+            0.into(),
+            Fraction::from(1u64),
+            &DummyAssigner,
+            &VotingPowerTransform::Linear,
+        )
+        .unwrap();
         assert_eq!(
             snapshot.contributions_for_voting_key(
                 Identifier::from_hex(
@@ -0,0 +1,264 @@
+use super::Error;
+use jormungandr_lib::interfaces::Value;
+use rust_decimal::{prelude::ToPrimitive, Decimal};
+use serde::{Deserialize, Serialize};
+use std::str::FromStr;
+
+/// Mapping from the stake delegated to a voting key to its voting power.
+///
+/// The transform is applied to the total stake of each voting key before the voting
+/// influence cap, so the cap is computed on the transformed voting power.
+#[derive(Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum VotingPowerTransform {
+    /// Voting power is equal to the stake
+    #[default]
+    Linear,
+    /// Voting power is the (integer) square root of the stake, as in quadratic voting
+    SquareRoot,
+    /// Voting power is `floor(scale * ln(1 + stake))`
+    Logarithmic { scale: u64 },
+    /// Each segment weights the stake between its own `from` and the `from` of the next
+    /// segment by `rate`. Stake below the first segment does not count.
+    /// The resulting voting power is then capped to `cap`, if any.
+    PiecewiseLinear {
+        segments: Vec<PiecewiseSegment>,
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        cap: Option<Value>,
+    },
+}
+
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct PiecewiseSegment {
+    pub from: Value,
+    pub rate: Decimal,
+}
+
+impl VotingPowerTransform {
+    /// Check the transform parameters, this is done before building a snapshot
+    pub fn validate(&self) -> Result<(), Error> {
+        match self {
+            Self::Linear | Self::SquareRoot => Ok(()),
+            Self::Logarithmic { scale } if *scale == 0 => Err(Error::InvalidTransform(
+                "logarithmic scale must be greater than 0".to_string(),
+            )),
+            Self::Logarithmic { .. } => Ok(()),
+            Self::PiecewiseLinear { segments, .. } => {
+                if segments.is_empty() {
+                    return Err(Error::InvalidTransform(
+                        "piecewise linear transform requires at least one segment".to_string(),
+                    ));
+                }
+                if segments
+                    .iter()
+                    .any(|segment| segment.rate.is_sign_negative())
+                {
+                    return Err(Error::InvalidTransform(
+                        "piecewise linear rates must not be negative".to_string(),
+                    ));
+                }
+                if segments.windows(2).any(|pair| pair[0].from >= pair[1].from) {
+                    return Err(Error::InvalidTransform(
+                        "piecewise linear segments must be sorted by increasing stake".to_string(),
+                    ));
+                }
+                Ok(())
+            }
+        }
+    }
+
+    /// Voting power of a voting key with `stake` delegated to it
+    pub fn apply(&self, stake: u64) -> Result<u64, Error> {
+        match self {
+            Self::Linear => Ok(stake),
+            Self::SquareRoot => Ok(int_sqrt(stake)),
+            // f64 precision is more than enough here as the result is rounded down anyway
+            Self::Logarithmic { scale } => Ok((*scale as f64 * (stake as f64).ln_1p()) as u64),
+            Self::PiecewiseLinear { segments, cap } => {
+                let mut power = Decimal::ZERO;
+                for (i, segment) in segments.iter().enumerate() {
+                    let from = u64::from(segment.from);
+                    if stake <= from {
+                        break;
+                    }
+                    let to = segments
+                        .get(i + 1)
+                        .map_or(stake, |next| stake.min(next.from.into()));
+                    power = Decimal::from(to - from)
+                        .checked_mul(segment.rate)
+                        .and_then(|weighted| power.checked_add(weighted))
+                        .ok_or(Error::Overflow)?;
+                }
+                let power = power.floor().to_u64().ok_or(Error::Overflow)?;
+                Ok(cap.map_or(power, |cap| power.min(cap.into())))
+            }
+        }
+    }
+}
+
+/// Calculates floor(sqrt(x)) with perfect precision
+fn int_sqrt(x: u64) -> u64 {
+    let x = x as u128;
+    let mut root = (x as f64).sqrt() as u128;
+    while root * root > x {
+        root -= 1;
+    }
+    while (root + 1) * (root + 1) <= x {
+        root += 1;
+    }
+    root as u64
+}
+
+/// Parse a transform from its command line representation:
+/// * `linear`
+/// * `sqrt`
+/// * `log:<scale>`
+/// * `piecewise:<from>=<rate>,<from>=<rate>,...[;cap=<max voting power>]`
+impl FromStr for VotingPowerTransform {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let invalid = |reason: &str| Error::InvalidTransform(format!("{}: '{}'", reason, s));
+        let (kind, params) = s.split_once(':').unwrap_or((s, ""));
+        let transform = match (kind, params) {
+            ("linear", "") => Self::Linear,
+            ("sqrt", "") => Self::SquareRoot,
+            ("log", scale) => Self::Logarithmic {
+                scale: scale.parse().map_err(|_| invalid("invalid scale"))?,
+            },
+            ("piecewise", params) => {
+                let (segments, cap) = match params.split_once(';') {
+                    Some((segments, cap)) => {
+                        let cap = cap
+                            .strip_prefix("cap=")
+                            .and_then(|cap| cap.parse().ok())
+                            .ok_or_else(|| invalid("invalid cap"))?;
+                        (segments, Some(cap))
+                    }
+                    None => (params, None),
+                };
+                let segments = segments
+                    .split(',')
+                    .map(|segment| {
+                        let (from, rate) = segment
+                            .split_once('=')
+                            .ok_or_else(|| invalid("invalid segment"))?;
+                        Ok(PiecewiseSegment {
+                            from: from.parse().map_err(|_| invalid("invalid segment"))?,
+                            rate: rate.parse().map_err(|_| invalid("invalid segment"))?,
+                        })
+                    })
+                    .collect::<Result<_, Error>>()?;
+                Self::PiecewiseLinear { segments, cap }
+            }
+            _ => return Err(invalid("unknown voting power transform")),
+        };
+        transform.validate()?;
+        Ok(transform)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rust_decimal_macros::dec;
+    use test_strategy::proptest;
+
+    #[proptest]
+    fn test_int_sqrt(x: u64) {
+        let root = int_sqrt(x) as u128;
+        assert!(root * root <= x as u128);
+        assert!((root + 1) * (root + 1) > x as u128);
+    }
+
+    #[test]
+    fn test_transforms() {
+        assert_eq!(VotingPowerTransform::Linear.apply(1234).unwrap(), 1234);
+        assert_eq!(
+            VotingPowerTransform::SquareRoot.apply(1_000_000).unwrap(),
+            1000
+        );
+        assert_eq!(VotingPowerTransform::SquareRoot.apply(99).unwrap(), 9);
+        assert_eq!(
+            VotingPowerTransform::Logarithmic { scale: 1000 }
+                .apply(99)
+                .unwrap(),
+            4605
+        );
+    }
+
+    #[test]
+    fn test_piecewise_linear() {
+        let transform = VotingPowerTransform::PiecewiseLinear {
+            segments: vec![
+                PiecewiseSegment {
+                    from: 0.into(),
+                    rate: dec!(1),
+                },
+                PiecewiseSegment {
+                    from: 100.into(),
+                    rate: dec!(0.5),
+                },
+                PiecewiseSegment {
+                    from: 200.into(),
+                    rate: dec!(0.1),
+                },
+            ],
+            cap: Some(170.into()),
+        };
+        assert_eq!(transform.apply(50).unwrap(), 50);
+        assert_eq!(transform.apply(150).unwrap(), 125);
+        assert_eq!(transform.apply(300).unwrap(), 160);
+        assert_eq!(transform.apply(1000).unwrap(), 170);
+    }
+
+    #[test]
+    fn test_parsing() {
+        assert_eq!(
+            "sqrt".parse::<VotingPowerTransform>().unwrap(),
+            VotingPowerTransform::SquareRoot
+        );
+        assert_eq!(
+            "log:10".parse::<VotingPowerTransform>().unwrap(),
+            VotingPowerTransform::Logarithmic { scale: 10 }
+        );
+        assert_eq!(
+            "piecewise:0=1,100=0.5;cap=1000"
+                .parse::<VotingPowerTransform>()
+                .unwrap(),
+            VotingPowerTransform::PiecewiseLinear {
+                segments: vec![
+                    PiecewiseSegment {
+                        from: 0.into(),
+                        rate: dec!(1),
+                    },
+                    PiecewiseSegment {
+                        from: 100.into(),
+                        rate: dec!(0.5),
+                    },
+                ],
+                cap: Some(1000.into()),
+            }
+        );
+        assert!("piecewise:100=1,0=0.5"
+            .parse::<VotingPowerTransform>()
+            .is_err());
+        assert!("log:0".parse::<VotingPowerTransform>().is_err());
+        assert!("cubic".parse::<VotingPowerTransform>().is_err());
+
+        let json = serde_json::json!({
+            "type": "piecewise_linear",
+            "segments": [{ "from": 0, "rate": "1" }],
+        });
+        assert_eq!(
+            serde_json::from_value::<VotingPowerTransform>(json).unwrap(),
+            VotingPowerTransform::PiecewiseLinear {
+                segments: vec![PiecewiseSegment {
+                    from: 0.into(),
+                    rate: dec!(1),
+                }],
+                cap: None,
+            }
+        );
+    }
+}
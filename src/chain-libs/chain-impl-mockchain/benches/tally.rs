@@ -235,7 +235,7 @@ fn tally_benchmark(
         .into_iter()
         .zip(decrypt_shares.into_iter())
         .map(|(tally, decrypt_shares)| DecryptedPrivateTallyProposal {
-            decrypt_shares: decrypt_shares.into_boxed_slice(),
+            decrypt_shares: decrypt_shares.into_iter().map(Some).collect(),
             tally_result: tally.votes.into_boxed_slice(),
         })
         .collect();
@@ -3,7 +3,7 @@ use super::{LastRewards, LedgerError};
 use crate::date::Epoch;
 use crate::value::*;
 use crate::{certificate::PoolId, tokens::identifier::TokenIdentifier};
-use imhamt::{Hamt, HamtIter};
+use imhamt::{Hamt, HamtIter, UpdateError};
 use std::collections::hash_map::DefaultHasher;
 
 /// Set the choice of delegation:
@@ -187,6 +187,31 @@ impl<Extra: Clone> AccountState<Extra> {
         })
     }
 
+    /// Subtract a value from a token in an account state. The token is removed
+    /// from the account state once its value drops to zero.
+    ///
+    /// Error if the account holds less than the value of the token
+    pub fn token_sub(&self, token: &TokenIdentifier, v: Value) -> Result<Self, LedgerError> {
+        let tokens = self
+            .tokens
+            .update(token, |current_value| {
+                let new_value = (*current_value - v)?;
+                Ok(if new_value == Value::zero() {
+                    None
+                } else {
+                    Some(new_value)
+                })
+            })
+            .map_err(|e| match e {
+                UpdateError::KeyNotFound => ValueError::NegativeAmount,
+                UpdateError::ValueCallbackError(e) => e,
+            })?;
+        Ok(Self {
+            tokens,
+            ..self.clone()
+        })
+    }
+
     /// Set delegation
     pub fn set_delegation(&self, delegation: DelegationType) -> Self {
         let mut st = self.clone();
@@ -611,6 +636,26 @@ mod tests {
         assert!(account_state.token_add(token, Value(1)).is_err());
     }
 
+    #[test]
+    pub fn sub_token() {
+        let token = TestGen::token_id();
+        let mut account_state = AccountState::new(Value::zero(), ());
+        account_state = account_state.token_add(token.clone(), Value(3)).unwrap();
+        account_state = account_state.token_sub(&token, Value(1)).unwrap();
+        assert_eq!(account_state.tokens.lookup(&token).unwrap(), &Value(2));
+        account_state = account_state.token_sub(&token, Value(2)).unwrap();
+        assert!(account_state.tokens.lookup(&token).is_none());
+    }
+
+    #[test]
+    pub fn sub_token_underflow() {
+        let token = TestGen::token_id();
+        let account_state = AccountState::new(Value::zero(), ());
+        assert!(account_state.token_sub(&token, Value(1)).is_err());
+        let account_state = account_state.token_add(token.clone(), Value(1)).unwrap();
+        assert!(account_state.token_sub(&token, Value(2)).is_err());
+    }
+
     #[cfg(any(test, feature = "property-test-api"))]
     mod prop_impls {
         use imhamt::Hamt;
@@ -154,6 +154,30 @@ impl<ID: Clone + Eq + Hash, Extra: Clone> Ledger<ID, Extra> {
             .map(Ledger)
     }
 
+    /// Set the value and the spending counters of an account, keeping the
+    /// rest of its state (tokens, delegation, rewards) untouched.
+    ///
+    /// If the account doesn't exist, it creates it with the value
+    pub fn restore_account(
+        &self,
+        identifier: &ID,
+        value: Value,
+        spending: SpendingCounterIncreasing,
+        extra: Extra,
+    ) -> Self {
+        let mut new_state = AccountState::new(value, extra);
+        new_state.spending = spending.clone();
+        Ledger(
+            self.0
+                .insert_or_update_simple(identifier.clone(), new_state, |st| {
+                    let mut st = st.clone();
+                    st.value = value;
+                    st.spending = spending;
+                    Some(st)
+                }),
+        )
+    }
+
     /// Add rewards to an existing account.
     ///
     /// If the account doesn't exist, it creates it with the value
@@ -208,6 +232,18 @@ impl<ID: Clone + Eq + Hash, Extra: Clone> Ledger<ID, Extra> {
             .map_err(|e| e.into())
     }
 
+    pub fn token_sub(
+        &self,
+        identifier: &ID,
+        token: &TokenIdentifier,
+        value: Value,
+    ) -> Result<Self, LedgerError> {
+        self.0
+            .update(identifier, |st| st.token_sub(token, value).map(Some))
+            .map(Ledger)
+            .map_err(|e| e.into())
+    }
+
     #[cfg(feature = "evm")]
     pub fn evm_move_state(
         mut self,
@@ -0,0 +1,131 @@
+use crate::{
+    account::Identifier,
+    certificate::CertificateSlice,
+    tokens::{minting_policy::MintingPolicy, name::TokenName},
+    transaction::{Payload, PayloadAuthData, PayloadData, PayloadSlice},
+    value::Value,
+};
+use chain_core::{
+    packer::Codec,
+    property::{Deserialize, DeserializeFromSlice, ReadError, Serialize, WriteError},
+};
+use typed_bytes::{ByteArray, ByteBuilder};
+
+use std::marker::PhantomData;
+
+/// Burn `value` tokens held by the account `from`. The transaction must be
+/// signed by `from` and by the signature entries of the minting `policy`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(
+    any(test, feature = "property-test-api"),
+    derive(test_strategy::Arbitrary)
+)]
+pub struct BurnToken {
+    pub name: TokenName,
+    pub policy: MintingPolicy,
+    pub from: Identifier,
+    pub value: Value,
+}
+
+impl BurnToken {
+    pub fn serialize_in(&self, bb: ByteBuilder<Self>) -> ByteBuilder<Self> {
+        let name = self.name.as_ref();
+        bb.u8(name.len() as u8)
+            .bytes(name)
+            .bytes(&self.policy.bytes())
+            .bytes(self.from.as_ref().as_ref())
+            .bytes(&self.value.bytes())
+    }
+
+    pub fn serialize(&self) -> ByteArray<Self> {
+        self.serialize_in(ByteBuilder::new()).finalize()
+    }
+}
+
+impl Payload for BurnToken {
+    const HAS_DATA: bool = true;
+
+    const HAS_AUTH: bool = false;
+
+    type Auth = ();
+
+    fn payload_data(&self) -> PayloadData<Self> {
+        PayloadData(
+            self.serialize_in(ByteBuilder::new())
+                .finalize_as_vec()
+                .into(),
+            PhantomData,
+        )
+    }
+
+    fn payload_auth_data(_: &Self::Auth) -> PayloadAuthData<Self> {
+        PayloadAuthData(Box::new([]), PhantomData)
+    }
+
+    fn payload_to_certificate_slice(p: PayloadSlice<'_, Self>) -> Option<CertificateSlice<'_>> {
+        Some(CertificateSlice::from(p))
+    }
+}
+
+impl Serialize for BurnToken {
+    fn serialized_size(&self) -> usize {
+        self.name.serialized_size()
+            + self.policy.serialized_size()
+            + self.from.serialized_size()
+            + self.value.serialized_size()
+    }
+
+    fn serialize<W: std::io::Write>(&self, codec: &mut Codec<W>) -> Result<(), WriteError> {
+        self.name.serialize(codec)?;
+        self.policy.serialize(codec)?;
+        self.from.serialize(codec)?;
+        self.value.serialize(codec)
+    }
+}
+
+impl DeserializeFromSlice for BurnToken {
+    fn deserialize_from_slice(codec: &mut Codec<&[u8]>) -> Result<Self, ReadError> {
+        let name = TokenName::deserialize(codec)?;
+        let policy = MintingPolicy::deserialize_from_slice(codec)?;
+        let from = Identifier::deserialize_from_slice(codec)?;
+        let value = Value::deserialize(codec)?;
+
+        Ok(Self {
+            name,
+            policy,
+            from,
+            value,
+        })
+    }
+}
+
+#[cfg(any(test, feature = "property-test-api"))]
+mod tests {
+    use super::*;
+    #[cfg(test)]
+    use crate::testing::serialization::serialization_bijection;
+    #[cfg(test)]
+    use quickcheck::TestResult;
+    use quickcheck::{Arbitrary, Gen};
+
+    impl Arbitrary for BurnToken {
+        fn arbitrary<G: Gen>(g: &mut G) -> Self {
+            let name = Arbitrary::arbitrary(g);
+            let policy = MintingPolicy::arbitrary(g);
+            let from = Arbitrary::arbitrary(g);
+            let value = Arbitrary::arbitrary(g);
+            Self {
+                name,
+                policy,
+                from,
+                value,
+            }
+        }
+    }
+
+    quickcheck! {
+        fn burntoken_serialization_bijection(b: BurnToken) -> TestResult {
+            serialization_bijection(b)
+        }
+    }
+}
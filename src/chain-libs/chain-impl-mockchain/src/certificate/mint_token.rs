@@ -84,7 +84,7 @@ impl Serialize for MintToken {
 impl DeserializeFromSlice for MintToken {
     fn deserialize_from_slice(codec: &mut Codec<&[u8]>) -> Result<Self, ReadError> {
         let name = TokenName::deserialize(codec)?;
-        let policy = MintingPolicy::deserialize(codec)?;
+        let policy = MintingPolicy::deserialize_from_slice(codec)?;
         let to = Identifier::deserialize_from_slice(codec)?;
         let value = Value::deserialize(codec)?;
 
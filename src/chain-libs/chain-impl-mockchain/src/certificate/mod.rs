@@ -1,3 +1,4 @@
+mod burn_token;
 mod delegation;
 mod evm_mapping;
 mod mint_token;
@@ -5,6 +6,7 @@ mod pool;
 mod update_proposal;
 mod update_vote;
 mod vote_cast;
+mod vote_delegation;
 mod vote_plan;
 mod vote_tally;
 
@@ -14,6 +16,7 @@ mod test;
 use crate::transaction::{Payload, PayloadData, PayloadSlice};
 
 pub use self::vote_cast::VoteCast;
+pub use self::vote_delegation::VoteDelegation;
 pub use self::vote_plan::{
     ExternalProposalDocument, ExternalProposalId, Proposal, Proposals, PushProposal, VoteAction,
     VotePlan, VotePlanId, VotePlanProof,
@@ -22,6 +25,7 @@ pub use self::vote_tally::{
     DecryptedPrivateTally, DecryptedPrivateTallyError, DecryptedPrivateTallyProposal, TallyProof,
     VoteTally, VoteTallyPayload,
 };
+pub use burn_token::BurnToken;
 pub use delegation::{OwnerStakeDelegation, StakeDelegation};
 pub use evm_mapping::EvmMapping;
 pub use mint_token::MintToken;
@@ -46,6 +50,8 @@ pub enum CertificateSlice<'a> {
     UpdateVote(PayloadSlice<'a, UpdateVote>),
     MintToken(PayloadSlice<'a, MintToken>),
     EvmMapping(PayloadSlice<'a, EvmMapping>),
+    BurnToken(PayloadSlice<'a, BurnToken>),
+    VoteDelegation(PayloadSlice<'a, VoteDelegation>),
 }
 
 impl<'a> From<PayloadSlice<'a, StakeDelegation>> for CertificateSlice<'a> {
@@ -119,6 +125,18 @@ impl<'a> From<PayloadSlice<'a, EvmMapping>> for CertificateSlice<'a> {
     }
 }
 
+impl<'a> From<PayloadSlice<'a, BurnToken>> for CertificateSlice<'a> {
+    fn from(payload: PayloadSlice<'a, BurnToken>) -> CertificateSlice<'a> {
+        CertificateSlice::BurnToken(payload)
+    }
+}
+
+impl<'a> From<PayloadSlice<'a, VoteDelegation>> for CertificateSlice<'a> {
+    fn from(payload: PayloadSlice<'a, VoteDelegation>) -> CertificateSlice<'a> {
+        CertificateSlice::VoteDelegation(payload)
+    }
+}
+
 impl<'a> CertificateSlice<'a> {
     pub fn into_owned(self) -> Certificate {
         match self {
@@ -138,6 +156,8 @@ impl<'a> CertificateSlice<'a> {
             CertificateSlice::UpdateVote(c) => Certificate::UpdateVote(c.into_payload()),
             CertificateSlice::MintToken(c) => Certificate::MintToken(c.into_payload()),
             CertificateSlice::EvmMapping(c) => Certificate::EvmMapping(c.into_payload()),
+            CertificateSlice::BurnToken(c) => Certificate::BurnToken(c.into_payload()),
+            CertificateSlice::VoteDelegation(c) => Certificate::VoteDelegation(c.into_payload()),
         }
     }
 }
@@ -156,6 +176,8 @@ pub enum CertificatePayload {
     UpdateVote(PayloadData<UpdateVote>),
     MintToken(PayloadData<MintToken>),
     EvmMapping(PayloadData<EvmMapping>),
+    BurnToken(PayloadData<BurnToken>),
+    VoteDelegation(PayloadData<VoteDelegation>),
 }
 
 impl CertificatePayload {
@@ -173,6 +195,8 @@ impl CertificatePayload {
             CertificatePayload::UpdateVote(payload) => payload.borrow().into(),
             CertificatePayload::MintToken(payload) => payload.borrow().into(),
             CertificatePayload::EvmMapping(payload) => payload.borrow().into(),
+            CertificatePayload::BurnToken(payload) => payload.borrow().into(),
+            CertificatePayload::VoteDelegation(payload) => payload.borrow().into(),
         }
     }
 }
@@ -212,6 +236,12 @@ impl<'a> From<&'a Certificate> for CertificatePayload {
             Certificate::EvmMapping(payload) => {
                 CertificatePayload::EvmMapping(payload.payload_data())
             }
+            Certificate::BurnToken(payload) => {
+                CertificatePayload::BurnToken(payload.payload_data())
+            }
+            Certificate::VoteDelegation(payload) => {
+                CertificatePayload::VoteDelegation(payload.payload_data())
+            }
         }
     }
 }
@@ -231,6 +261,8 @@ pub enum Certificate {
     UpdateVote(UpdateVote),
     MintToken(MintToken),
     EvmMapping(EvmMapping),
+    BurnToken(BurnToken),
+    VoteDelegation(VoteDelegation),
 }
 
 impl From<StakeDelegation> for Certificate {
@@ -305,6 +337,18 @@ impl From<EvmMapping> for Certificate {
     }
 }
 
+impl From<BurnToken> for Certificate {
+    fn from(burn_token: BurnToken) -> Self {
+        Self::BurnToken(burn_token)
+    }
+}
+
+impl From<VoteDelegation> for Certificate {
+    fn from(vote_delegation: VoteDelegation) -> Self {
+        Self::VoteDelegation(vote_delegation)
+    }
+}
+
 impl Certificate {
     pub fn need_auth(&self) -> bool {
         match self {
@@ -320,6 +364,8 @@ impl Certificate {
             Certificate::UpdateVote(_) => <UpdateVote as Payload>::HAS_AUTH,
             Certificate::MintToken(_) => <MintToken as Payload>::HAS_AUTH,
             Certificate::EvmMapping(_) => <EvmMapping as Payload>::HAS_AUTH,
+            Certificate::BurnToken(_) => <BurnToken as Payload>::HAS_AUTH,
+            Certificate::VoteDelegation(_) => <VoteDelegation as Payload>::HAS_AUTH,
         }
     }
 }
@@ -363,6 +409,8 @@ mod tests {
             Certificate::UpdateVote(_) => true,
             Certificate::MintToken(_) => false,
             Certificate::EvmMapping(_) => true,
+            Certificate::BurnToken(_) => false,
+            Certificate::VoteDelegation(_) => false,
         };
         TestResult::from_bool(certificate.need_auth() == expected_result)
     }
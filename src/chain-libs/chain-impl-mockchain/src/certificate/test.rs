@@ -201,6 +201,19 @@ impl Arbitrary for VotePlan {
         let committee_end = BlockDate::arbitrary(g);
         let proposals = Proposals::arbitrary(g);
         let payload_type = vote::PayloadType::arbitrary(g);
+        let weighted_ballot = match payload_type {
+            vote::PayloadType::PrivateWeighted => {
+                let total = NonZeroU8::new((g.next_u32() % 16 + 1) as u8).unwrap();
+                if g.next_u32() % 2 == 0 {
+                    Some(chain_vote::WeightedBallotKind::Approval {
+                        max_selections: total,
+                    })
+                } else {
+                    Some(chain_vote::WeightedBallotKind::Budget { budget: total })
+                }
+            }
+            vote::PayloadType::Public | vote::PayloadType::Private => None,
+        };
 
         let mut keys = Vec::new();
         // it should have been 256 but is limited for the sake of adequate test times
@@ -216,6 +229,8 @@ impl Arbitrary for VotePlan {
             keys.push(m1.public_key());
         }
 
+        let committee_threshold = NonZeroU8::new((g.next_u32() % (keys_n + 1)) as u8);
+
         let voting_token = TokenIdentifier::arbitrary(g);
 
         Self::new(
@@ -224,7 +239,9 @@ impl Arbitrary for VotePlan {
             committee_end,
             proposals,
             payload_type,
+            weighted_ballot,
             keys,
+            committee_threshold,
             voting_token,
         )
     }
@@ -279,7 +296,11 @@ fn arbitrary_decrypted_private_tally<G: Gen>(g: &mut G) -> DecryptedPrivateTally
             tally_result: (0..n_options.get())
                 .map(|_| u64::arbitrary(g))
                 .collect::<Box<[_]>>(),
-            decrypt_shares: decrypte_shares.into_boxed_slice(),
+            // members of a threshold committee may not take part in the decryption
+            decrypt_shares: decrypte_shares
+                .into_iter()
+                .map(|share| Some(share).filter(|_| bool::arbitrary(g)))
+                .collect(),
         });
     }
     DecryptedPrivateTally::new(inner).unwrap()
@@ -310,7 +331,7 @@ impl Arbitrary for TallyProof {
 
 impl Arbitrary for Certificate {
     fn arbitrary<G: Gen>(g: &mut G) -> Self {
-        let option = u8::arbitrary(g) % 11;
+        let option = u8::arbitrary(g) % 13;
         match option {
             0 => Certificate::StakeDelegation(Arbitrary::arbitrary(g)),
             1 => Certificate::OwnerStakeDelegation(Arbitrary::arbitrary(g)),
@@ -323,11 +344,54 @@ impl Arbitrary for Certificate {
             8 => Certificate::UpdateProposal(Arbitrary::arbitrary(g)),
             9 => Certificate::UpdateVote(Arbitrary::arbitrary(g)),
             10 => Certificate::MintToken(Arbitrary::arbitrary(g)),
+            11 => Certificate::BurnToken(Arbitrary::arbitrary(g)),
+            12 => Certificate::VoteDelegation(Arbitrary::arbitrary(g)),
             _ => panic!("unimplemented"),
         }
     }
 }
 
+#[quickcheck]
+fn vote_tally_flags_missing_decrypt_shares(vote_tally: VoteTally) -> TestResult {
+    let bytes = vote_tally.serialize();
+    let flagged = bytes.as_slice()[32] != vote_tally.tally_type() as u8;
+    let partial = vote_tally
+        .tally_decrypted()
+        .map_or(false, DecryptedPrivateTally::is_partial);
+    let decoded = VoteTally::deserialize_from_slice(&mut Codec::new(bytes.as_slice())).unwrap();
+    TestResult::from_bool(flagged == partial && decoded == vote_tally)
+}
+
+#[quickcheck]
+fn vote_tally_rejects_partial_flag_with_every_decrypt_share(vote_tally: VoteTally) -> TestResult {
+    let tally = match vote_tally.tally_decrypted() {
+        Some(tally) if !tally.is_partial() => tally,
+        _ => return TestResult::discard(),
+    };
+    // the same tally in the partial encoding: flagged type, one presence byte per share
+    let mut bytes = vote_tally.serialize().as_slice()[..33].to_vec();
+    bytes[32] |= 0b1000_0000;
+    bytes.push(tally.iter().count() as u8);
+    for proposal in tally.iter() {
+        if proposal.decrypt_shares.is_empty() {
+            bytes.extend([0, 0]);
+            continue;
+        }
+        bytes.push(proposal.decrypt_shares.len() as u8);
+        bytes.push(proposal.tally_result.len() as u8);
+        for share in proposal.decrypt_shares.iter().flatten() {
+            bytes.push(1);
+            bytes.extend(share.to_bytes());
+        }
+        for count in proposal.tally_result.iter() {
+            bytes.extend(count.to_be_bytes());
+        }
+    }
+    TestResult::from_bool(
+        VoteTally::deserialize_from_slice(&mut Codec::new(bytes.as_slice())).is_err(),
+    )
+}
+
 #[quickcheck]
 fn pool_reg_serialization_bijection(b: PoolRegistration) -> TestResult {
     let b_got = b.serialize();
@@ -0,0 +1,226 @@
+use crate::{
+    account::Identifier,
+    accounting::account::DELEGATION_RATIO_MAX_DECLS,
+    certificate::{CertificateSlice, VotePlanId},
+    tokens::identifier::TokenIdentifier,
+    transaction::{Payload, PayloadAuthData, PayloadData, PayloadSlice},
+    vote::{VoteDelegationRatio, VoteDelegationScope, VoteDelegationType},
+};
+use chain_core::{
+    packer::Codec,
+    property::{Deserialize, DeserializeFromSlice, ReadError, Serialize, WriteError},
+};
+use typed_bytes::{ByteArray, ByteBuilder};
+
+use std::marker::PhantomData;
+
+/// A delegation of the voting power of an account to one or more
+/// representatives, for the vote plans in the given scope.
+///
+/// This structure is not sufficient to identify the delegator, and instead we rely on a special
+/// authenticated transaction, which has 1 input.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct VoteDelegation {
+    pub scope: VoteDelegationScope,
+    pub delegation: VoteDelegationType,
+}
+
+impl VoteDelegation {
+    pub fn serialize_in(&self, bb: ByteBuilder<Self>) -> ByteBuilder<Self> {
+        serialize_vote_delegation_type(
+            &self.delegation,
+            serialize_vote_delegation_scope(&self.scope, bb),
+        )
+    }
+
+    pub fn serialize(&self) -> ByteArray<Self> {
+        self.serialize_in(ByteBuilder::new()).finalize()
+    }
+}
+
+// Format is either:
+// 0 (byte) TOKEN_IDENTIFIER
+// 1 (byte) VOTE_PLAN_ID (32 bytes)
+fn serialize_vote_delegation_scope<T>(
+    scope: &VoteDelegationScope,
+    bb: ByteBuilder<T>,
+) -> ByteBuilder<T> {
+    match scope {
+        VoteDelegationScope::Token(token) => bb.u8(0).bytes(&token.bytes()),
+        VoteDelegationScope::VotePlan(id) => bb.u8(1).bytes(id.as_ref()),
+    }
+}
+
+fn deserialize_vote_delegation_scope(
+    codec: &mut Codec<&[u8]>,
+) -> Result<VoteDelegationScope, ReadError> {
+    match codec.get_u8()? {
+        0 => Ok(VoteDelegationScope::Token(TokenIdentifier::deserialize(
+            codec,
+        )?)),
+        1 => Ok(VoteDelegationScope::VotePlan(VotePlanId::deserialize(
+            codec,
+        )?)),
+        tag => Err(ReadError::UnknownTag(tag as u32)),
+    }
+}
+
+// Format is either:
+// 0 (byte)
+// 1 (byte)     ACCOUNT_ID (32 bytes)
+// PARTS (byte) #REPRESENTATIVES (bytes) [ PART (1 byte) ACCOUNT_ID (32 bytes)] (repeated #REPRESENTATIVES time)
+fn serialize_vote_delegation_type<T>(
+    delegation: &VoteDelegationType,
+    bb: ByteBuilder<T>,
+) -> ByteBuilder<T> {
+    match delegation {
+        VoteDelegationType::NonDelegated => bb.u8(0),
+        VoteDelegationType::Full(representative) => {
+            bb.u8(1).bytes(representative.as_ref().as_ref())
+        }
+        VoteDelegationType::Ratio(ratio) => {
+            let parts = ratio.parts();
+            assert!(parts >= 2);
+            bb.u8(parts).iter8(
+                ratio.representatives().iter(),
+                |b, (representative, part)| b.u8(*part).bytes(representative.as_ref().as_ref()),
+            )
+        }
+    }
+}
+
+fn deserialize_vote_delegation_type(
+    codec: &mut Codec<&[u8]>,
+) -> Result<VoteDelegationType, ReadError> {
+    let parts = codec.get_u8()?;
+    match parts {
+        0 => Ok(VoteDelegationType::NonDelegated),
+        1 => Ok(VoteDelegationType::Full(
+            Identifier::deserialize_from_slice(codec)?,
+        )),
+        _ => {
+            let sz = codec.get_u8()?;
+            if sz as usize > DELEGATION_RATIO_MAX_DECLS {
+                return Err(ReadError::SizeTooBig(
+                    sz as usize,
+                    DELEGATION_RATIO_MAX_DECLS,
+                ));
+            }
+            let mut representatives = Vec::with_capacity(sz as usize);
+            for _ in 0..sz {
+                let part = codec.get_u8()?;
+                let representative = Identifier::deserialize_from_slice(codec)?;
+                representatives.push((representative, part))
+            }
+            match VoteDelegationRatio::new(parts, representatives) {
+                None => Err(ReadError::StructureInvalid(
+                    "invalid vote delegation ratio".to_string(),
+                )),
+                Some(ratio) => Ok(VoteDelegationType::Ratio(ratio)),
+            }
+        }
+    }
+}
+
+impl Payload for VoteDelegation {
+    const HAS_DATA: bool = true;
+
+    const HAS_AUTH: bool = false;
+
+    type Auth = ();
+
+    fn payload_data(&self) -> PayloadData<Self> {
+        PayloadData(
+            self.serialize_in(ByteBuilder::new())
+                .finalize_as_vec()
+                .into(),
+            PhantomData,
+        )
+    }
+
+    fn payload_auth_data(_: &Self::Auth) -> PayloadAuthData<Self> {
+        PayloadAuthData(Box::new([]), PhantomData)
+    }
+
+    fn payload_to_certificate_slice(p: PayloadSlice<'_, Self>) -> Option<CertificateSlice<'_>> {
+        Some(CertificateSlice::from(p))
+    }
+}
+
+impl Serialize for VoteDelegation {
+    fn serialized_size(&self) -> usize {
+        self.serialize_in(ByteBuilder::new())
+            .finalize_as_vec()
+            .len()
+    }
+
+    fn serialize<W: std::io::Write>(&self, codec: &mut Codec<W>) -> Result<(), WriteError> {
+        codec.put_bytes(
+            self.serialize_in(ByteBuilder::new())
+                .finalize_as_vec()
+                .as_slice(),
+        )
+    }
+}
+
+impl DeserializeFromSlice for VoteDelegation {
+    fn deserialize_from_slice(codec: &mut Codec<&[u8]>) -> Result<Self, ReadError> {
+        let scope = deserialize_vote_delegation_scope(codec)?;
+        let delegation = deserialize_vote_delegation_type(codec)?;
+        Ok(Self { scope, delegation })
+    }
+}
+
+#[cfg(any(test, feature = "property-test-api"))]
+mod tests {
+    use super::*;
+    #[cfg(test)]
+    use crate::testing::serialization::serialization_bijection;
+    #[cfg(test)]
+    use quickcheck::TestResult;
+    use quickcheck::{Arbitrary, Gen};
+
+    impl Arbitrary for VoteDelegationScope {
+        fn arbitrary<G: Gen>(g: &mut G) -> Self {
+            if bool::arbitrary(g) {
+                VoteDelegationScope::Token(Arbitrary::arbitrary(g))
+            } else {
+                VoteDelegationScope::VotePlan(Arbitrary::arbitrary(g))
+            }
+        }
+    }
+
+    impl Arbitrary for VoteDelegationType {
+        fn arbitrary<G: Gen>(g: &mut G) -> Self {
+            match u8::arbitrary(g) % 3 {
+                0 => VoteDelegationType::NonDelegated,
+                1 => VoteDelegationType::Full(Arbitrary::arbitrary(g)),
+                _ => {
+                    let count = u8::arbitrary(g) % (DELEGATION_RATIO_MAX_DECLS as u8 - 1) + 2;
+                    let representatives = (0..count)
+                        .map(|_| (Identifier::arbitrary(g), u8::arbitrary(g) % 4 + 1))
+                        .collect::<Vec<_>>();
+                    let parts = representatives.iter().map(|(_, part)| *part).sum();
+                    VoteDelegationRatio::new(parts, representatives)
+                        .map(VoteDelegationType::Ratio)
+                        .unwrap_or(VoteDelegationType::NonDelegated)
+                }
+            }
+        }
+    }
+
+    impl Arbitrary for VoteDelegation {
+        fn arbitrary<G: Gen>(g: &mut G) -> Self {
+            Self {
+                scope: Arbitrary::arbitrary(g),
+                delegation: Arbitrary::arbitrary(g),
+            }
+        }
+    }
+
+    quickcheck! {
+        fn vote_delegation_serialization_bijection(b: VoteDelegation) -> TestResult {
+            serialization_bijection(b)
+        }
+    }
+}
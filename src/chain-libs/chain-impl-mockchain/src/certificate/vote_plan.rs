@@ -14,8 +14,8 @@ use chain_core::{
     property::{Deserialize, DeserializeFromSlice, ReadError, Serialize, WriteError},
 };
 use chain_crypto::{digest::DigestOf, Blake2b256, Verification};
-use chain_vote::MemberPublicKey;
-use std::ops::Deref;
+use chain_vote::{MemberPublicKey, WeightedBallotKind};
+use std::{num::NonZeroU8, ops::Deref};
 use typed_bytes::{ByteArray, ByteBuilder};
 
 /// abstract tag type to represent an external document, whatever it may be
@@ -29,6 +29,11 @@ pub type ExternalProposalId = DigestOf<Blake2b256, ExternalProposalDocument>;
 /// the vote plan identifier on the blockchain
 pub type VotePlanId = DigestOf<Blake2b256, VotePlan>;
 
+/// set on the payload type byte of the serialized vote plan when the
+/// committee threshold follows the committee keys. Vote plans without a
+/// threshold keep the original encoding and identifier.
+const COMMITTEE_THRESHOLD_FLAG: u8 = 0b1000_0000;
+
 /// a vote plan for the voting system
 ///
 /// A vote plan defines what is being voted, for how long and how long
@@ -48,8 +53,15 @@ pub struct VotePlan {
     proposals: Proposals,
     /// vote payload type
     payload_type: vote::PayloadType,
+    /// how the voters distribute their vote over the options of a proposal, only
+    /// set for the `PrivateWeighted` payload type
+    weighted_ballot: Option<WeightedBallotKind>,
     /// encrypting votes public keys
     committee_public_keys: Vec<chain_vote::MemberPublicKey>,
+    /// number of committee members needed to decrypt the tally when the election
+    /// secret key is threshold shared among the committee, or `None` if every
+    /// member has to take part in the decryption
+    committee_threshold: Option<NonZeroU8>,
     /// voting token used for weigthing the votes for any proposal in this voteplan
     voting_token: TokenIdentifier,
 }
@@ -197,7 +209,9 @@ impl VotePlan {
         committee_end: BlockDate,
         proposals: Proposals,
         payload_type: vote::PayloadType,
+        weighted_ballot: Option<WeightedBallotKind>,
         committee_public_keys: Vec<chain_vote::MemberPublicKey>,
+        committee_threshold: Option<NonZeroU8>,
         voting_token: TokenIdentifier,
     ) -> Self {
         Self {
@@ -206,7 +220,9 @@ impl VotePlan {
             committee_end,
             proposals,
             payload_type,
+            weighted_ballot,
             committee_public_keys,
+            committee_threshold,
             voting_token,
         }
     }
@@ -255,10 +271,34 @@ impl VotePlan {
         self.payload_type
     }
 
+    pub fn weighted_ballot(&self) -> Option<&WeightedBallotKind> {
+        self.weighted_ballot.as_ref()
+    }
+
     pub fn committee_public_keys(&self) -> &[chain_vote::MemberPublicKey] {
         &self.committee_public_keys
     }
 
+    pub fn committee_threshold(&self) -> Option<NonZeroU8> {
+        self.committee_threshold
+    }
+
+    /// the public key the votes are encrypted with, derived from the committee keys
+    /// according to how the election secret key is shared among the committee
+    ///
+    /// # panics
+    ///
+    /// if the vote plan has no committee keys
+    pub fn election_public_key(&self) -> chain_vote::ElectionPublicKey {
+        match self.committee_threshold {
+            None => chain_vote::ElectionPublicKey::from_participants(&self.committee_public_keys),
+            Some(threshold) => chain_vote::ElectionPublicKey::from_threshold_participants(
+                threshold.get() as usize,
+                &self.committee_public_keys,
+            ),
+        }
+    }
+
     pub fn voting_token(&self) -> &TokenIdentifier {
         &self.voting_token
     }
@@ -309,16 +349,33 @@ impl VotePlan {
             .u32(self.vote_end.slot_id)
             .u32(self.committee_end.epoch)
             .u32(self.committee_end.slot_id)
-            .u8(self.payload_type as u8)
+            .u8(self.payload_type as u8 | self.committee_threshold_flag())
+            .fold(self.weighted_ballot.iter(), |bb, kind| match kind {
+                WeightedBallotKind::Approval { max_selections } => {
+                    bb.u8(1).u8(max_selections.get())
+                }
+                WeightedBallotKind::Budget { budget } => bb.u8(2).u8(budget.get()),
+            })
             .iter8(&mut self.proposals.iter(), |bb, proposal| {
                 proposal.serialize_in(bb)
             })
             .iter8(self.committee_public_keys.iter(), |bb, key| {
                 bb.bytes(key.to_bytes().as_ref())
             })
+            .fold(self.committee_threshold.iter(), |bb, threshold| {
+                bb.u8(threshold.get())
+            })
             .bytes(self.voting_token.bytes().as_ref())
     }
 
+    fn committee_threshold_flag(&self) -> u8 {
+        if self.committee_threshold.is_some() {
+            COMMITTEE_THRESHOLD_FLAG
+        } else {
+            0
+        }
+    }
+
     pub fn serialize(&self) -> ByteArray<Self> {
         self.serialize_in(ByteBuilder::new()).finalize()
     }
@@ -432,11 +489,17 @@ impl DeserializeFromSlice for VotePlan {
             slot_id: codec.get_be_u32()?,
         };
 
-        let payload_type = codec
-            .get_u8()?
+        let payload_type = codec.get_u8()?;
+        let has_committee_threshold = payload_type & COMMITTEE_THRESHOLD_FLAG != 0;
+        let payload_type = (payload_type & !COMMITTEE_THRESHOLD_FLAG)
             .try_into()
             .map_err(|e: vote::TryFromIntError| ReadError::StructureInvalid(e.to_string()))?;
 
+        let weighted_ballot = match payload_type {
+            vote::PayloadType::PrivateWeighted => Some(read_weighted_ballot_kind(codec)?),
+            vote::PayloadType::Public | vote::PayloadType::Private => None,
+        };
+
         let proposal_size = codec.get_u8()? as usize;
         let mut proposals = Proposals {
             proposals: Vec::with_capacity(proposal_size),
@@ -467,6 +530,23 @@ impl DeserializeFromSlice for VotePlan {
             })?);
         }
 
+        let committee_threshold = if has_committee_threshold {
+            let threshold = NonZeroU8::new(codec.get_u8()?).ok_or_else(|| {
+                ReadError::StructureInvalid("committee threshold cannot be zero".to_string())
+            })?;
+            Some(threshold)
+        } else {
+            None
+        };
+        if let Some(threshold) = committee_threshold {
+            if threshold.get() > member_keys_len {
+                return Err(ReadError::StructureInvalid(format!(
+                    "committee threshold {} is greater than the number of committee members {}",
+                    threshold, member_keys_len
+                )));
+            }
+        }
+
         let voting_token = TokenIdentifier::deserialize(codec)?;
 
         Ok(Self {
@@ -475,12 +555,28 @@ impl DeserializeFromSlice for VotePlan {
             committee_end,
             proposals,
             payload_type,
+            weighted_ballot,
             committee_public_keys,
+            committee_threshold,
             voting_token,
         })
     }
 }
 
+fn read_weighted_ballot_kind(codec: &mut Codec<&[u8]>) -> Result<WeightedBallotKind, ReadError> {
+    let tag = codec.get_u8()?;
+    let value = NonZeroU8::new(codec.get_u8()?).ok_or_else(|| {
+        ReadError::StructureInvalid("weighted ballot total cannot be zero".to_string())
+    })?;
+    match tag {
+        1 => Ok(WeightedBallotKind::Approval {
+            max_selections: value,
+        }),
+        2 => Ok(WeightedBallotKind::Budget { budget: value }),
+        t => Err(ReadError::UnknownTag(t as u32)),
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -502,6 +598,44 @@ mod tests {
         decoded == vote_plan
     }
 
+    #[test]
+    pub fn committee_threshold_is_flagged_in_the_encoding() {
+        let legacy = VoteTestGen::private_vote_plan();
+        let threshold = VotePlan::new(
+            legacy.vote_start(),
+            legacy.vote_end(),
+            legacy.committee_end(),
+            legacy.proposals().clone(),
+            legacy.payload_type(),
+            None,
+            legacy.committee_public_keys().to_vec(),
+            NonZeroU8::new(2),
+            legacy.voting_token().clone(),
+        );
+
+        // payload type byte after the three block dates
+        let legacy_bytes = legacy.serialize();
+        assert_eq!(
+            legacy_bytes.as_slice()[24],
+            vote::PayloadType::Private as u8
+        );
+
+        let threshold_bytes = threshold.serialize();
+        assert_eq!(
+            threshold_bytes.as_slice()[24],
+            vote::PayloadType::Private as u8 | COMMITTEE_THRESHOLD_FLAG
+        );
+        assert_eq!(
+            threshold_bytes.as_slice().len(),
+            legacy_bytes.as_slice().len() + 1
+        );
+
+        let decoded =
+            VotePlan::deserialize_from_slice(&mut Codec::new(threshold_bytes.as_slice())).unwrap();
+        assert_eq!(decoded, threshold);
+        assert_ne!(legacy.to_id(), threshold.to_id());
+    }
+
     #[test]
     pub fn proposals_are_full() {
         let mut proposals = VoteTestGen::proposals(Proposals::MAX_LEN);
@@ -524,7 +658,9 @@ mod tests {
             committee_finished,
             VoteTestGen::proposals(1),
             vote::PayloadType::Public,
+            None,
             Vec::new(),
+            None,
             TokenIdentifier {
                 policy_hash: PolicyHash::from([0u8; POLICY_HASH_SIZE]),
                 token_name: TokenName::try_from(vec![0u8; TOKEN_NAME_MAX_SIZE]).unwrap(),
@@ -548,7 +684,9 @@ mod tests {
             committee_finished,
             VoteTestGen::proposals(1),
             vote::PayloadType::Public,
+            None,
             Vec::new(),
+            None,
             TokenIdentifier {
                 policy_hash: PolicyHash::from([0u8; POLICY_HASH_SIZE]),
                 token_name: TokenName::try_from(vec![0u8; TOKEN_NAME_MAX_SIZE]).unwrap(),
@@ -584,7 +722,9 @@ mod tests {
             vote_start,
             VoteTestGen::proposals(1),
             vote::PayloadType::Public,
+            None,
             Vec::new(),
+            None,
             TokenIdentifier {
                 policy_hash: PolicyHash::from([0u8; POLICY_HASH_SIZE]),
                 token_name: TokenName::try_from(vec![0u8; TOKEN_NAME_MAX_SIZE]).unwrap(),
@@ -15,6 +15,11 @@ use chain_vote::TallyDecryptShare;
 use thiserror::Error;
 use typed_bytes::{ByteArray, ByteBuilder};
 
+/// set on the tally type byte of a private tally when some committee members
+/// have no decrypt share, each share is then preceded by a presence byte.
+/// Tallies with all the shares keep the original encoding.
+const PARTIAL_DECRYPTION_FLAG: u8 = 0b1000_0000;
+
 #[derive(Debug, Eq, PartialEq, Hash, Clone)]
 pub struct VoteTally {
     id: VotePlanId,
@@ -51,7 +56,10 @@ pub struct DecryptedPrivateTally {
 
 #[derive(Debug, Eq, PartialEq, Hash, Clone)]
 pub struct DecryptedPrivateTallyProposal {
-    pub decrypt_shares: Box<[TallyDecryptShare]>,
+    /// one entry per committee member, in the order of the vote plan committee keys.
+    /// Members that did not take part in the decryption of a threshold committee have
+    /// no share.
+    pub decrypt_shares: Box<[Option<TallyDecryptShare>]>,
     pub tally_result: Box<[u64]>,
 }
 
@@ -101,7 +109,13 @@ impl VoteTally {
     }
 
     pub fn serialize_in(&self, bb: ByteBuilder<Self>) -> ByteBuilder<Self> {
-        let bb = bb.bytes(self.id().as_ref()).u8(self.tally_type() as u8);
+        let partial = self
+            .tally_decrypted()
+            .map_or(false, DecryptedPrivateTally::is_partial);
+        let flag = if partial { PARTIAL_DECRYPTION_FLAG } else { 0 };
+        let bb = bb
+            .bytes(self.id().as_ref())
+            .u8(self.tally_type() as u8 | flag);
 
         match &self.payload {
             VoteTallyPayload::Public => bb,
@@ -117,8 +131,12 @@ impl VoteTally {
                             let n_options = proposal.tally_result.len().try_into().unwrap();
                             bb.u8(n_members)
                                 .u8(n_options)
-                                .fold(proposal.decrypt_shares.iter(), |bb, s| {
-                                    bb.bytes(&s.to_bytes())
+                                .fold(proposal.decrypt_shares.iter(), |bb, s| match (partial, s) {
+                                    (true, Some(s)) => bb.u8(1).bytes(&s.to_bytes()),
+                                    (true, None) => bb.u8(0),
+                                    (false, s) => bb.bytes(
+                                        &s.as_ref().expect("all shares are present").to_bytes(),
+                                    ),
                                 })
                                 .fold(proposal.tally_result.iter(), |bb, count| bb.u64(*count))
                         }
@@ -174,7 +192,7 @@ impl DecryptedPrivateTally {
         proposals: Vec<DecryptedPrivateTallyProposal>,
     ) -> Result<Self, DecryptedPrivateTallyError> {
         if proposals.iter().all(|proposal| {
-            let mut shares = proposal.decrypt_shares.iter();
+            let mut shares = proposal.decrypt_shares.iter().flatten();
             match shares.next() {
                 Some(first_share) => shares.all(|share| share.options() == first_share.options()),
                 None => true,
@@ -191,6 +209,13 @@ impl DecryptedPrivateTally {
     pub fn iter(&self) -> impl Iterator<Item = &DecryptedPrivateTallyProposal> {
         self.inner.iter()
     }
+
+    /// whether the decrypt share of at least one committee member is missing
+    pub fn is_partial(&self) -> bool {
+        self.inner
+            .iter()
+            .any(|proposal| proposal.decrypt_shares.iter().any(Option::is_none))
+    }
 }
 
 /* Auth/Payload ************************************************************* */
@@ -258,12 +283,18 @@ impl DeserializeFromSlice for TallyProof {
 impl DeserializeFromSlice for VoteTally {
     fn deserialize_from_slice(codec: &mut Codec<&[u8]>) -> Result<Self, ReadError> {
         let id = <[u8; 32]>::deserialize(codec)?.into();
-        let payload_type = codec
-            .get_u8()?
+        let payload_type = codec.get_u8()?;
+        let partial = payload_type & PARTIAL_DECRYPTION_FLAG != 0;
+        let payload_type = (payload_type & !PARTIAL_DECRYPTION_FLAG)
             .try_into()
             .map_err(|e: TryFromIntError| ReadError::StructureInvalid(e.to_string()))?;
 
         let payload = match payload_type {
+            PayloadType::Public if partial => {
+                return Err(ReadError::StructureInvalid(
+                    "public tallies have no decrypt shares".to_owned(),
+                ))
+            }
             PayloadType::Public => VoteTallyPayload::Public,
             PayloadType::Private => {
                 let proposals_number = codec.get_u8()? as usize;
@@ -274,12 +305,25 @@ impl DeserializeFromSlice for VoteTally {
                     let share_bytes = TallyDecryptShare::bytes_len(options_number);
                     let mut shares = Vec::with_capacity(shares_number);
                     for _j in 0..shares_number {
-                        let s_buf = codec.get_slice(share_bytes)?;
-                        let share = TallyDecryptShare::from_bytes(s_buf).ok_or_else(|| {
-                            ReadError::StructureInvalid(
-                                "invalid decrypt share structure".to_owned(),
-                            )
-                        })?;
+                        let present = if partial {
+                            match codec.get_u8()? {
+                                0 => false,
+                                1 => true,
+                                t => return Err(ReadError::UnknownTag(t as u32)),
+                            }
+                        } else {
+                            true
+                        };
+                        let share = if present {
+                            let s_buf = codec.get_slice(share_bytes)?;
+                            Some(TallyDecryptShare::from_bytes(s_buf).ok_or_else(|| {
+                                ReadError::StructureInvalid(
+                                    "invalid decrypt share structure".to_owned(),
+                                )
+                            })?)
+                        } else {
+                            None
+                        };
                         shares.push(share);
                     }
                     let mut decrypted = Vec::with_capacity(options_number);
@@ -294,10 +338,23 @@ impl DeserializeFromSlice for VoteTally {
                     });
                 }
 
-                VoteTallyPayload::Private {
-                    inner: DecryptedPrivateTally::new(proposals)
-                        .map_err(|err| ReadError::InvalidData(err.to_string()))?,
+                let inner = DecryptedPrivateTally::new(proposals)
+                    .map_err(|err| ReadError::InvalidData(err.to_string()))?;
+                // only one encoding is accepted for a given tally, flagging a
+                // complete set of shares as partial would make it malleable
+                if partial && !inner.is_partial() {
+                    return Err(ReadError::StructureInvalid(
+                        "partial decryption flag set with every decrypt share".to_owned(),
+                    ));
                 }
+                VoteTallyPayload::Private { inner }
+            }
+            // tallies of private weighted vote plans are encoded with the
+            // `Private` payload type, this one never appears in a tally
+            PayloadType::PrivateWeighted => {
+                return Err(ReadError::StructureInvalid(
+                    "private weighted vote plans use private tallies".to_owned(),
+                ))
             }
         };
 
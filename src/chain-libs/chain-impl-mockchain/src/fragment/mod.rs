@@ -41,6 +41,8 @@ pub enum Fragment {
     MintToken(Transaction<certificate::MintToken>),
     Evm(EvmTransaction),
     EvmMapping(Transaction<certificate::EvmMapping>),
+    BurnToken(Transaction<certificate::BurnToken>),
+    VoteDelegation(Transaction<certificate::VoteDelegation>),
 }
 
 impl PartialEq for Fragment {
@@ -69,6 +71,8 @@ pub(super) enum FragmentTag {
     MintToken = 13,
     Evm = 14,
     EvmMapping = 15,
+    BurnToken = 16,
+    VoteDelegation = 17,
 }
 
 impl FragmentTag {
@@ -90,6 +94,8 @@ impl FragmentTag {
             13 => Some(FragmentTag::MintToken),
             14 => Some(FragmentTag::Evm),
             15 => Some(FragmentTag::EvmMapping),
+            16 => Some(FragmentTag::BurnToken),
+            17 => Some(FragmentTag::VoteDelegation),
             _ => None,
         }
     }
@@ -115,6 +121,8 @@ impl Fragment {
             Fragment::MintToken(_) => FragmentTag::MintToken,
             Fragment::Evm(_) => FragmentTag::Evm,
             Fragment::EvmMapping(_) => FragmentTag::EvmMapping,
+            Fragment::BurnToken(_) => FragmentTag::BurnToken,
+            Fragment::VoteDelegation(_) => FragmentTag::VoteDelegation,
         }
     }
 
@@ -188,6 +196,12 @@ impl Deserialize for Fragment {
             Some(FragmentTag::EvmMapping) => {
                 Transaction::deserialize(&mut codec).map(Fragment::EvmMapping)
             }
+            Some(FragmentTag::BurnToken) => {
+                Transaction::deserialize(&mut codec).map(Fragment::BurnToken)
+            }
+            Some(FragmentTag::VoteDelegation) => {
+                Transaction::deserialize(&mut codec).map(Fragment::VoteDelegation)
+            }
             None => Err(ReadError::UnknownTag(tag as u32)),
         }
     }
@@ -214,6 +228,8 @@ impl Serialize for Fragment {
                 Fragment::MintToken(mint_token) => mint_token.serialized_size(),
                 Fragment::Evm(deployment) => deployment.serialized_size(),
                 Fragment::EvmMapping(evm_mapping) => evm_mapping.serialized_size(),
+                Fragment::BurnToken(burn_token) => burn_token.serialized_size(),
+                Fragment::VoteDelegation(vote_delegation) => vote_delegation.serialized_size(),
             }
             + Codec::u32_size()
     }
@@ -239,6 +255,8 @@ impl Serialize for Fragment {
             Fragment::MintToken(mint_token) => mint_token.serialize(&mut tmp)?,
             Fragment::Evm(deployment) => deployment.serialize(&mut tmp)?,
             Fragment::EvmMapping(evm_mapping) => evm_mapping.serialize(&mut tmp)?,
+            Fragment::BurnToken(burn_token) => burn_token.serialize(&mut tmp)?,
+            Fragment::VoteDelegation(vote_delegation) => vote_delegation.serialize(&mut tmp)?,
         };
         let bytes = tmp.into_inner();
         codec.put_be_u32(bytes.len() as u32)?;
@@ -87,6 +87,20 @@ pub(super) fn valid_vote_cast(tx: &TransactionSlice<certificate::VoteCast>) -> L
     )
 }
 
+/// check that the transaction input/outputs/witnesses is valid for the vote delegation
+///
+/// * Only 1 input (subsequently 1 witness), no output
+pub(super) fn valid_vote_delegation(
+    tx: &TransactionSlice<certificate::VoteDelegation>,
+) -> LedgerCheck {
+    if_cond_fail_with!(
+        tx.inputs().nb_inputs() != 1
+            || tx.witnesses().nb_witnesses() != 1
+            || tx.outputs().nb_outputs() != 0,
+        Error::VoteDelegationInvalidTransaction
+    )
+}
+
 /// check that the pool registration certificate is valid
 ///
 /// * management threshold T is valid: 0 < T <= #owners
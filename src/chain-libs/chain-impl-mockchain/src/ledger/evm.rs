@@ -2,6 +2,7 @@ use crate::account::{self, LedgerError};
 use crate::certificate::EvmMapping;
 use crate::chaineval::HeaderContentEvalContext;
 use crate::evm::{EvmActionType, EvmTransaction};
+use crate::fragment::FragmentId;
 use crate::header::BlockDate;
 use crate::key::Hash;
 use crate::value::Value;
@@ -119,9 +120,21 @@ impl AddressMapping {
     }
 }
 
+/// Outcome of an EVM transaction applied in the current block
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Receipt {
+    /// id of the fragment carrying the transaction
+    pub fragment_id: FragmentId,
+    /// address of the contract created by the transaction, if any
+    pub contract_address: Option<EvmAddress>,
+    /// logs emitted during the execution of the transaction
+    pub logs: Vec<Log>,
+}
+
 #[derive(Clone, Debug, PartialEq, Eq)]
 pub struct Ledger {
     pub(crate) logs: LogsState,
+    pub(crate) receipts: Vec<Receipt>,
     pub(crate) environment: Environment,
     pub(crate) current_epoch: BlockEpoch,
     pub(crate) address_mapping: AddressMapping,
@@ -136,6 +149,18 @@ impl Default for Ledger {
 struct EvmStateImpl {
     accounts: account::Ledger,
     evm: Ledger,
+    // logs of the transaction being executed
+    logs: Vec<Log>,
+}
+
+impl EvmStateImpl {
+    fn new(accounts: account::Ledger, evm: Ledger) -> Self {
+        Self {
+            accounts,
+            evm,
+            logs: Vec::new(),
+        }
+    }
 }
 
 impl EvmState for EvmStateImpl {
@@ -194,6 +219,8 @@ impl EvmState for EvmStateImpl {
     }
 
     fn update_logs(&mut self, block_hash: BlockHash, logs: Vec<Log>) {
+        // every commit of the execution reports all the logs emitted so far
+        self.logs = logs.clone();
         self.evm.logs.put(block_hash, logs);
     }
 }
@@ -225,7 +252,7 @@ impl Ledger {
         config: chain_evm::Config,
     ) -> Result<(EvmAddress, account::Ledger, Ledger), Error> {
         let config = config.into();
-        let mut vm_state = EvmStateImpl { accounts, evm };
+        let mut vm_state = EvmStateImpl::new(accounts, evm);
         let caller = contract.caller;
         let gas_limit = contract.gas_limit;
         match contract.action_type {
@@ -264,7 +291,7 @@ impl Ledger {
         config: chain_evm::Config,
     ) -> Result<ByteCode, Error> {
         let config = config.into();
-        let mut vm_state = EvmStateImpl { accounts, evm };
+        let mut vm_state = EvmStateImpl::new(accounts, evm);
 
         let value = transaction.value;
         let caller = transaction.caller;
@@ -303,7 +330,7 @@ impl Ledger {
         config: chain_evm::Config,
     ) -> Result<u64, Error> {
         let config = config.into();
-        let mut vm_state = EvmStateImpl { accounts, evm };
+        let mut vm_state = EvmStateImpl::new(accounts, evm);
 
         let value = transaction.value;
         let caller = transaction.caller;
@@ -346,34 +373,52 @@ impl Ledger {
     pub fn run_transaction(
         evm: Ledger,
         accounts: account::Ledger,
+        fragment_id: FragmentId,
         transaction: EvmTransaction,
         config: chain_evm::Config,
     ) -> Result<(account::Ledger, Ledger), Error> {
         Self::validate_transaction_nonce(&evm, &accounts, &transaction)?;
 
         let config = config.into();
-        let mut vm_state = EvmStateImpl { accounts, evm };
+        let mut vm_state = EvmStateImpl::new(accounts, evm);
         let value = transaction.value;
         let caller = transaction.caller;
         let gas_limit = transaction.gas_limit;
         let access_list = transaction.access_list;
-        match transaction.action_type {
+        let contract_address = match transaction.action_type {
             EvmActionType::Create { init_code } => {
+                let vm = VirtualMachine::new(&mut vm_state, &config, caller, gas_limit, true);
+                let address = generate_address_create(vm, caller);
                 let vm = VirtualMachine::new(&mut vm_state, &config, caller, gas_limit, true);
                 execute_transact_create(vm, value.into(), init_code, access_list)?;
+                Some(address)
             }
             EvmActionType::Create2 { init_code, salt } => {
+                let vm = VirtualMachine::new(&mut vm_state, &config, caller, gas_limit, true);
+                let address = generate_address_create2(vm, caller, init_code.clone(), salt);
                 let vm = VirtualMachine::new(&mut vm_state, &config, caller, gas_limit, true);
                 execute_transact_create2(vm, value.into(), init_code, salt, access_list)?;
+                Some(address)
             }
             EvmActionType::Call { address, data } => {
                 let vm = VirtualMachine::new(&mut vm_state, &config, caller, gas_limit, true);
                 let _byte_code_msg =
                     execute_transact_call(vm, address, value.into(), data, access_list)?;
+                None
             }
-        }
+        };
+        vm_state.evm.receipts.push(Receipt {
+            fragment_id,
+            contract_address,
+            logs: vm_state.logs,
+        });
         Ok((vm_state.accounts, vm_state.evm))
     }
+
+    /// Receipts of the EVM transactions applied in the current block
+    pub fn receipts(&self) -> &[Receipt] {
+        &self.receipts
+    }
 }
 
 #[derive(Copy, Clone, Debug, PartialEq, Eq)]
@@ -394,6 +439,7 @@ impl Ledger {
     ) {
         // use content hash from the apply block as the EVM block hash
         let next_hash: BlockHash = <[u8; 32]>::from(metadata.block_id).into();
+        self.receipts.clear();
         self.environment.block_hashes.insert(0, next_hash);
         self.environment.block_number = BlockNumber::from(self.environment.block_hashes.len());
         self.update_block_timestamp(metadata.block_date, slots_per_epoch, slot_duration);
@@ -438,6 +484,7 @@ impl Ledger {
     pub fn new() -> Self {
         Self {
             logs: Default::default(),
+            receipts: Vec::new(),
             environment: Environment {
                 gas_price: Default::default(),
                 chain_id: Default::default(),
@@ -480,7 +527,8 @@ impl Ledger {
             "evm: {}",
             (self.address_mapping == other.address_mapping
                 && self.environment == other.environment
-                && self.logs == other.logs)
+                && self.logs == other.logs
+                && self.receipts == other.receipts)
         )
     }
 }
@@ -589,7 +637,14 @@ mod test {
             )
             .unwrap();
 
-            (accounts, _) = Ledger::run_transaction(evm, accounts, transaction, config).unwrap();
+            (accounts, _) = Ledger::run_transaction(
+                evm,
+                accounts,
+                FragmentId::zero_hash(),
+                transaction,
+                config,
+            )
+            .unwrap();
 
             assert_eq!(
                 accounts.get_state(&account_id),
@@ -1095,7 +1150,14 @@ mod test {
                 },
             };
 
-            (accounts, _) = Ledger::run_transaction(evm, accounts, transaction, config).unwrap();
+            (accounts, _) = Ledger::run_transaction(
+                evm,
+                accounts,
+                FragmentId::zero_hash(),
+                transaction,
+                config,
+            )
+            .unwrap();
 
             assert_eq!(
                 accounts.get_state(&account_id),
@@ -1190,7 +1252,14 @@ mod test {
                 },
             };
 
-            (accounts, _) = Ledger::run_transaction(evm, accounts, transaction, config).unwrap();
+            (accounts, _) = Ledger::run_transaction(
+                evm,
+                accounts,
+                FragmentId::zero_hash(),
+                transaction,
+                config,
+            )
+            .unwrap();
 
             assert_eq!(
                 accounts.get_state(&account_id1),
@@ -1283,7 +1352,13 @@ mod test {
             };
 
             assert_eq!(
-                Ledger::run_transaction(evm, accounts, transaction, config),
+                Ledger::run_transaction(
+                    evm,
+                    accounts,
+                    FragmentId::zero_hash(),
+                    transaction,
+                    config
+                ),
                 Err(Error::EvmTransaction(
                     chain_evm::machine::Error::TransactionError(ExitError::OutOfFund)
                 ))
@@ -1362,7 +1437,13 @@ mod test {
             };
 
             assert_eq!(
-                Ledger::run_transaction(evm, accounts, transaction, config),
+                Ledger::run_transaction(
+                    evm,
+                    accounts,
+                    FragmentId::zero_hash(),
+                    transaction,
+                    config
+                ),
                 Err(Error::EvmTransaction(
                     chain_evm::machine::Error::TransactionError(ExitError::Other(
                         "Balance overflow".into()
@@ -1424,11 +1505,27 @@ mod test {
                 },
             };
 
-            let (contract_address, mut accounts, evm) =
+            let (contract_address, accounts, evm) =
                 Ledger::generate_contract_address(evm, accounts, transaction.clone(), config)
                     .unwrap();
 
-            (accounts, _) = Ledger::run_transaction(evm, accounts, transaction, config).unwrap();
+            let (accounts, evm) = Ledger::run_transaction(
+                evm,
+                accounts,
+                FragmentId::zero_hash(),
+                transaction,
+                config,
+            )
+            .unwrap();
+
+            assert_eq!(
+                evm.receipts(),
+                &[Receipt {
+                    fragment_id: FragmentId::zero_hash(),
+                    contract_address: Some(contract_address),
+                    logs: Vec::new(),
+                }]
+            );
 
             if config == chain_evm::Config::Frontier {
                 assert_eq!(
@@ -1525,11 +1622,27 @@ mod test {
                 },
             };
 
-            let (contract_address, mut accounts, evm) =
+            let (contract_address, accounts, evm) =
                 Ledger::generate_contract_address(evm, accounts, transaction.clone(), config)
                     .unwrap();
 
-            (accounts, _) = Ledger::run_transaction(evm, accounts, transaction, config).unwrap();
+            let (accounts, evm) = Ledger::run_transaction(
+                evm,
+                accounts,
+                FragmentId::zero_hash(),
+                transaction,
+                config,
+            )
+            .unwrap();
+
+            assert_eq!(
+                evm.receipts(),
+                &[Receipt {
+                    fragment_id: FragmentId::zero_hash(),
+                    contract_address: Some(contract_address),
+                    logs: Vec::new(),
+                }]
+            );
 
             if config == chain_evm::Config::Frontier {
                 assert_eq!(
@@ -1619,11 +1732,27 @@ mod test {
                 },
             };
 
-            let (contract_address, mut accounts, evm) =
+            let (contract_address, accounts, evm) =
                 Ledger::generate_contract_address(evm, accounts, transaction.clone(), config)
                     .unwrap();
 
-            (accounts, _) = Ledger::run_transaction(evm, accounts, transaction, config).unwrap();
+            let (accounts, evm) = Ledger::run_transaction(
+                evm,
+                accounts,
+                FragmentId::zero_hash(),
+                transaction,
+                config,
+            )
+            .unwrap();
+
+            assert_eq!(
+                evm.receipts(),
+                &[Receipt {
+                    fragment_id: FragmentId::zero_hash(),
+                    contract_address: Some(contract_address),
+                    logs: Vec::new(),
+                }]
+            );
 
             if config == chain_evm::Config::Frontier {
                 assert_eq!(
@@ -1720,11 +1849,27 @@ mod test {
                 },
             };
 
-            let (contract_address, mut accounts, evm) =
+            let (contract_address, accounts, evm) =
                 Ledger::generate_contract_address(evm, accounts, transaction.clone(), config)
                     .unwrap();
 
-            (accounts, _) = Ledger::run_transaction(evm, accounts, transaction, config).unwrap();
+            let (accounts, evm) = Ledger::run_transaction(
+                evm,
+                accounts,
+                FragmentId::zero_hash(),
+                transaction,
+                config,
+            )
+            .unwrap();
+
+            assert_eq!(
+                evm.receipts(),
+                &[Receipt {
+                    fragment_id: FragmentId::zero_hash(),
+                    contract_address: Some(contract_address),
+                    logs: Vec::new(),
+                }]
+            );
 
             if config == chain_evm::Config::Frontier {
                 assert_eq!(
@@ -1812,7 +1957,13 @@ mod test {
             };
 
             assert_eq!(
-                Ledger::run_transaction(evm, accounts, transaction, config),
+                Ledger::run_transaction(
+                    evm,
+                    accounts,
+                    FragmentId::zero_hash(),
+                    transaction,
+                    config
+                ),
                 Err(Error::EvmTransaction(
                     chain_evm::machine::Error::TransactionError(ExitError::StackUnderflow)
                 ))
@@ -1871,7 +2022,13 @@ mod test {
             };
 
             assert_eq!(
-                Ledger::run_transaction(evm, accounts, transaction, config),
+                Ledger::run_transaction(
+                    evm,
+                    accounts,
+                    FragmentId::zero_hash(),
+                    transaction,
+                    config
+                ),
                 Err(Error::EvmTransaction(
                     chain_evm::machine::Error::TransactionError(ExitError::OutOfFund)
                 ))
@@ -1932,7 +2089,13 @@ mod test {
             };
 
             assert_eq!(
-                Ledger::run_transaction(evm, accounts, transaction, config),
+                Ledger::run_transaction(
+                    evm,
+                    accounts,
+                    FragmentId::zero_hash(),
+                    transaction,
+                    config
+                ),
                 Err(Error::EvmTransaction(
                     chain_evm::machine::Error::TransactionError(ExitError::StackUnderflow)
                 ))
@@ -1993,7 +2156,13 @@ mod test {
             };
 
             assert_eq!(
-                Ledger::run_transaction(evm, accounts, transaction, config),
+                Ledger::run_transaction(
+                    evm,
+                    accounts,
+                    FragmentId::zero_hash(),
+                    transaction,
+                    config
+                ),
                 Err(Error::EvmTransaction(
                     chain_evm::machine::Error::TransactionError(ExitError::OutOfFund)
                 ))
@@ -2053,12 +2222,24 @@ mod test {
                 },
             };
 
-            (accounts, evm) =
-                Ledger::run_transaction(evm, accounts, transaction.clone(), config).unwrap();
+            (accounts, evm) = Ledger::run_transaction(
+                evm,
+                accounts,
+                FragmentId::zero_hash(),
+                transaction.clone(),
+                config,
+            )
+            .unwrap();
 
             // repeat the same transaction
             assert_eq!(
-                Ledger::run_transaction(evm, accounts, transaction, config),
+                Ledger::run_transaction(
+                    evm,
+                    accounts,
+                    FragmentId::zero_hash(),
+                    transaction,
+                    config
+                ),
                 Err(Error::InvalidNonce(1, 0))
             );
         }
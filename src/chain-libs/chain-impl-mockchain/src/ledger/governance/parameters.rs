@@ -1,4 +1,6 @@
-use crate::{ledger::governance::GovernanceAcceptanceCriteria, value::Value};
+use crate::{
+    fragment::BlockContentSize, ledger::governance::GovernanceAcceptanceCriteria, value::Value,
+};
 use chain_core::{
     packer::Codec,
     property::{Deserialize, ReadError},
@@ -10,13 +12,33 @@ use typed_bytes::ByteBuilder;
 #[derive(Debug, Clone, Eq, PartialEq)]
 pub enum ParametersGovernanceAction {
     NoOp,
-    RewardAdd { value: Value },
+    RewardAdd {
+        value: Value,
+    },
+    /// replace the base coefficients of the linear fee, the per certificate
+    /// fees are left untouched
+    UpdateFees {
+        constant: u64,
+        coefficient: u64,
+        certificate: u64,
+    },
+    /// change the duration (in seconds) of the slots, starting from the next epoch
+    SlotDuration {
+        duration: u8,
+    },
+    /// change the maximum size (in bytes) of the content of a block
+    BlockContentMaxSize {
+        size: BlockContentSize,
+    },
 }
 
 #[derive(Debug, Copy, Clone, Ord, PartialOrd, Eq, PartialEq, Hash)]
 pub enum ParametersGovernanceActionType {
     NoOp,
     RewardAdd,
+    UpdateFees,
+    SlotDuration,
+    BlockContentMaxSize,
 }
 
 #[derive(Default, Clone, Eq, PartialEq)]
@@ -34,6 +56,9 @@ impl ParametersGovernanceAction {
         match self {
             Self::NoOp => ParametersGovernanceActionType::NoOp,
             Self::RewardAdd { .. } => ParametersGovernanceActionType::RewardAdd,
+            Self::UpdateFees { .. } => ParametersGovernanceActionType::UpdateFees,
+            Self::SlotDuration { .. } => ParametersGovernanceActionType::SlotDuration,
+            Self::BlockContentMaxSize { .. } => ParametersGovernanceActionType::BlockContentMaxSize,
         }
     }
 
@@ -41,6 +66,13 @@ impl ParametersGovernanceAction {
         match self {
             Self::NoOp => bb.u8(0),
             Self::RewardAdd { value } => bb.u8(1).u64(value.0),
+            Self::UpdateFees {
+                constant,
+                coefficient,
+                certificate,
+            } => bb.u8(2).u64(*constant).u64(*coefficient).u64(*certificate),
+            Self::SlotDuration { duration } => bb.u8(3).u8(*duration),
+            Self::BlockContentMaxSize { size } => bb.u8(4).u32(*size),
         }
     }
 }
@@ -113,6 +145,28 @@ impl Deserialize for ParametersGovernanceAction {
                 let value = Value::deserialize(codec)?;
                 Ok(Self::RewardAdd { value })
             }
+            2 => {
+                let constant = codec.get_be_u64()?;
+                let coefficient = codec.get_be_u64()?;
+                let certificate = codec.get_be_u64()?;
+                Ok(Self::UpdateFees {
+                    constant,
+                    coefficient,
+                    certificate,
+                })
+            }
+            3 => match codec.get_u8()? {
+                0 => Err(ReadError::StructureInvalid(
+                    "slot duration cannot be zero".to_string(),
+                )),
+                duration => Ok(Self::SlotDuration { duration }),
+            },
+            4 => match codec.get_be_u32()? {
+                0 => Err(ReadError::StructureInvalid(
+                    "block content max size cannot be zero".to_string(),
+                )),
+                size => Ok(Self::BlockContentMaxSize { size }),
+            },
             t => Err(ReadError::UnknownTag(t as u32)),
         }
     }
@@ -128,10 +182,13 @@ mod tests {
 
     impl Arbitrary for ParametersGovernanceActionType {
         fn arbitrary<G: Gen>(g: &mut G) -> Self {
-            let option = u8::arbitrary(g) % 2;
+            let option = u8::arbitrary(g) % 5;
             match option {
                 0 => ParametersGovernanceActionType::NoOp,
                 1 => ParametersGovernanceActionType::RewardAdd,
+                2 => ParametersGovernanceActionType::UpdateFees,
+                3 => ParametersGovernanceActionType::SlotDuration,
+                4 => ParametersGovernanceActionType::BlockContentMaxSize,
                 _ => unreachable!(),
             }
         }
@@ -139,12 +196,23 @@ mod tests {
 
     impl Arbitrary for ParametersGovernanceAction {
         fn arbitrary<G: Gen>(g: &mut G) -> Self {
-            let option = u8::arbitrary(g) % 2;
+            let option = u8::arbitrary(g) % 5;
             match option {
                 0 => ParametersGovernanceAction::NoOp,
                 1 => ParametersGovernanceAction::RewardAdd {
                     value: Arbitrary::arbitrary(g),
                 },
+                2 => ParametersGovernanceAction::UpdateFees {
+                    constant: Arbitrary::arbitrary(g),
+                    coefficient: Arbitrary::arbitrary(g),
+                    certificate: Arbitrary::arbitrary(g),
+                },
+                3 => ParametersGovernanceAction::SlotDuration {
+                    duration: u8::arbitrary(g).max(1),
+                },
+                4 => ParametersGovernanceAction::BlockContentMaxSize {
+                    size: u32::arbitrary(g).max(1),
+                },
                 _ => unreachable!(),
             }
         }
@@ -157,6 +225,25 @@ mod tests {
 
         let action = ParametersGovernanceAction::RewardAdd { value: Value(10) };
         assert_eq!(action.to_type(), ParametersGovernanceActionType::RewardAdd);
+
+        let action = ParametersGovernanceAction::UpdateFees {
+            constant: 1,
+            coefficient: 2,
+            certificate: 3,
+        };
+        assert_eq!(action.to_type(), ParametersGovernanceActionType::UpdateFees);
+
+        let action = ParametersGovernanceAction::SlotDuration { duration: 5 };
+        assert_eq!(
+            action.to_type(),
+            ParametersGovernanceActionType::SlotDuration
+        );
+
+        let action = ParametersGovernanceAction::BlockContentMaxSize { size: 1024 };
+        assert_eq!(
+            action.to_type(),
+            ParametersGovernanceActionType::BlockContentMaxSize
+        );
     }
 
     #[test]
@@ -18,6 +18,7 @@ impl Ledger {
             pots: _,
             leaders_log: _,
             votes: _,
+            vote_delegations: _,
             governance: _,
             #[cfg(feature = "evm")]
             evm,
@@ -68,6 +69,7 @@ impl Ledger {
             pots: pots1,
             leaders_log: leaders_log1,
             votes: votes1,
+            vote_delegations: vote_delegations1,
             governance: governance1,
             #[cfg(feature = "evm")]
                 evm: evm1,
@@ -89,6 +91,7 @@ impl Ledger {
             pots: pots2,
             leaders_log: leaders_log2,
             votes: votes2,
+            vote_delegations: vote_delegations2,
             governance: governance2,
             #[cfg(feature = "evm")]
                 evm: evm2,
@@ -110,6 +113,10 @@ impl Ledger {
             format!("pots-same: {}", pots1 == pots2),
             format!("leaders-log-same: {}", leaders_log1 == leaders_log2),
             format!("vote-plans: {}", votes1 == votes2),
+            format!(
+                "vote-delegations: {}",
+                vote_delegations1 == vote_delegations2
+            ),
             format!("governance: {}", governance1 == governance2),
             #[cfg(feature = "evm")]
             evm1.info_eq(evm2),
@@ -10,7 +10,7 @@ use super::pots::Pots;
 use super::reward_info::{EpochRewardsInfo, RewardsInfoParameters};
 use super::token_distribution::{TokenDistribution, TokenTotals};
 
-use crate::certificate::MintToken;
+use crate::certificate::{BurnToken, MintToken, VoteDelegation};
 use crate::chaineval::HeaderContentEvalContext;
 use crate::chaintypes::{ChainLength, ConsensusType, HeaderId};
 use crate::config::{self, ConfigParam};
@@ -23,12 +23,14 @@ use crate::rewards;
 use crate::setting::{ActiveSlotsCoeffError, Settings};
 use crate::stake::{PercentStake, PoolError, PoolStakeInformation, PoolsState, StakeDistribution};
 use crate::tokens::identifier::TokenIdentifier;
-use crate::tokens::minting_policy::MintingPolicyViolation;
+use crate::tokens::minting_policy::{MintingContext, MintingPolicyViolation};
 use crate::transaction::*;
 use crate::treasury::Treasury;
 use crate::update::UpdateState;
 use crate::value::*;
-use crate::vote::{VotePlanLedger, VotePlanLedgerError, VotePlanStatus};
+use crate::vote::{
+    VoteDelegations, VotePlanCheckpoint, VotePlanLedger, VotePlanLedgerError, VotePlanStatus,
+};
 use crate::{account, certificate, legacy, multisig, setting, stake, update, utxo};
 use crate::{
     certificate::{
@@ -42,6 +44,7 @@ use chain_crypto::Verification;
 #[cfg(feature = "evm")]
 use chain_evm::state::ByteCode;
 use chain_time::{Epoch as TimeEpoch, SlotDuration, TimeEra, TimeFrame, Timeline};
+use chain_vote::Ballot;
 use std::collections::HashSet;
 use std::mem::swap;
 use std::sync::Arc;
@@ -80,6 +83,7 @@ pub struct Ledger {
     pub(crate) pots: Pots,
     pub(crate) leaders_log: LeadersParticipationRecord,
     pub(crate) votes: VotePlanLedger,
+    pub(crate) vote_delegations: VoteDelegations,
     pub(crate) governance: Governance,
     #[cfg(feature = "evm")]
     pub(crate) evm: evm::Ledger,
@@ -157,6 +161,10 @@ pub enum Block0Error {
     HasVoteTally,
     #[error("EvmMapping are not valid in the block0")]
     HasEvmMapping,
+    #[error("Token burning are not valid in the block0")]
+    HasBurnToken,
+    #[error("Vote delegation are not valid in the block0")]
+    HasVoteDelegation,
 }
 
 pub type OutputOldAddress = Output<legacy::OldAddress>;
@@ -243,6 +251,10 @@ pub enum Error {
     OwnerStakeDelegationInvalidTransaction,
     #[error("Transaction for VoteCast is invalid. expecting 1 input, 1 witness and 0 output")]
     VoteCastInvalidTransaction,
+    #[error(
+        "Transaction for VoteDelegation is invalid. expecting 1 input, 1 witness and 0 output"
+    )]
+    VoteDelegationInvalidTransaction,
     #[error("Wrong chain length, expected {expected} but received {actual}")]
     WrongChainLength {
         actual: ChainLength,
@@ -308,6 +320,8 @@ pub enum Error {
     UpdateVoteSignatureFailed,
     #[error("minting policy violation")]
     MintingPolicyViolation(#[from] MintingPolicyViolation),
+    #[error("the token burning transaction is not signed by the holder {0} of the tokens")]
+    BurnTokenHolderSignatureMissing(account::Identifier),
     #[error("evm transactions are disabled, the node was built without the 'evm' feature")]
     DisabledEvmTransactions,
     #[cfg(feature = "evm")]
@@ -340,6 +354,7 @@ impl Ledger {
             pots,
             leaders_log: LeadersParticipationRecord::new(),
             votes: VotePlanLedger::new(),
+            vote_delegations: VoteDelegations::new(),
             governance: Governance::default(),
             #[cfg(feature = "evm")]
             evm: evm::Ledger::new(),
@@ -384,11 +399,15 @@ impl Ledger {
                     ConfigParam::Discrimination(d) => {
                         discrimination = Some(*d);
                     }
+                    // also kept in the settings, so their updates start from
+                    // the values of block0
                     ConfigParam::SlotDuration(d) => {
                         slot_duration = Some(*d);
+                        regular_ents.push(param.clone());
                     }
                     ConfigParam::SlotsPerEpoch(n) => {
                         slots_per_epoch = Some(*n);
+                        regular_ents.push(param.clone());
                     }
                     ConfigParam::KesUpdateSpeed(n) => {
                         kes_update_speed = Some(*n);
@@ -509,6 +528,7 @@ impl Ledger {
                         (ledger.accounts, ledger.evm) = evm::Ledger::run_transaction(
                             ledger.evm,
                             ledger.accounts,
+                            fragment_id,
                             _tx.clone(),
                             ledger.settings.evm_config,
                         )?;
@@ -528,6 +548,12 @@ impl Ledger {
                         return Err(Error::DisabledEvmTransactions);
                     }
                 }
+                Fragment::BurnToken(_) => {
+                    return Err(Error::Block0(Block0Error::HasBurnToken));
+                }
+                Fragment::VoteDelegation(_) => {
+                    return Err(Error::Block0(Block0Error::HasVoteDelegation));
+                }
             }
         }
 
@@ -557,6 +583,12 @@ impl Ledger {
         self.leaders_log.total() != 0
     }
 
+    /// This need to be called at the end of an epoch, before the rewards
+    /// are distributed: the parameters governance actions that have been
+    /// accepted during the epoch are applied to the ledger.
+    ///
+    /// The new settings (fees, slot duration, block content size) are
+    /// effective from the first block of the next epoch.
     pub fn apply_protocol_changes(&self) -> Result<Self, Error> {
         let mut new = self.clone();
 
@@ -566,6 +598,21 @@ impl Ledger {
                 ParametersGovernanceAction::RewardAdd { value } => {
                     new.pots.rewards_add(*value)?;
                 }
+                ParametersGovernanceAction::UpdateFees {
+                    constant,
+                    coefficient,
+                    certificate,
+                } => {
+                    new.settings.linear_fees.constant = *constant;
+                    new.settings.linear_fees.coefficient = *coefficient;
+                    new.settings.linear_fees.certificate = *certificate;
+                }
+                ParametersGovernanceAction::SlotDuration { duration } => {
+                    new.settings.slot_duration = *duration;
+                }
+                ParametersGovernanceAction::BlockContentMaxSize { size } => {
+                    new.settings.block_content_max_size = *size;
+                }
             }
         }
 
@@ -826,6 +873,14 @@ impl Ledger {
         new_ledger.updates = updates;
         new_ledger.settings = settings;
 
+        // the state of the ledger before the first block after the end of the
+        // voting period is the one at the end of the voting period
+        new_ledger.votes = new_ledger.votes.count_delegated_votes(
+            block_date,
+            new_ledger.token_distribution(),
+            &new_ledger.vote_delegations,
+        );
+
         Ok(ApplyBlockLedger {
             ledger: new_ledger,
             block_date,
@@ -859,20 +914,63 @@ impl Ledger {
         #[cfg(feature = "evm")]
         let new_block_ledger = new_block_ledger.begin_evm_block(metadata);
 
-        let new_block_ledger = contents
-            .iter()
-            .try_fold(new_block_ledger, |new_block_ledger, fragment| {
-                new_block_ledger.apply_fragment(fragment)
-            })?;
+        // the proofs of the private votes of the block are verified together,
+        // which is much cheaper than verifying them one by one when applied
+        let verified_ballots = new_block_ledger.ledger.verify_private_votes(contents);
+
+        let new_block_ledger = contents.iter().zip(verified_ballots).try_fold(
+            new_block_ledger,
+            |new_block_ledger, (fragment, verified_ballot)| {
+                new_block_ledger.apply_fragment_with_verified_ballot(fragment, verified_ballot)
+            },
+        )?;
         Ok(new_block_ledger.finish(&metadata.consensus_eval_context))
     }
 
+    /// verify in a single batch the proofs of the private votes of `contents`
+    ///
+    /// returns the verified ballot of each of the fragments, `None` if the
+    /// fragment is not a private vote or if it has to be verified when it is
+    /// applied. If any of the proofs is invalid, every vote is verified when
+    /// applied so the invalid one is reported.
+    fn verify_private_votes(&self, contents: &Contents) -> Vec<Option<Ballot>> {
+        let votes: Vec<Option<VoteCast>> = contents
+            .iter()
+            .map(|fragment| match fragment {
+                Fragment::VoteCast(tx) => Some(tx.as_slice().payload().into_payload()),
+                _ => None,
+            })
+            .collect();
+
+        match self.votes.verify_private_votes(votes.iter().flatten()) {
+            Ok(ballots) => {
+                let mut ballots = ballots.into_iter();
+                votes
+                    .iter()
+                    .map(|vote| vote.as_ref().and_then(|_| ballots.next().flatten()))
+                    .collect()
+            }
+            Err(_) => vec![None; votes.len()],
+        }
+    }
+
     /// Try to apply a message to the State, and return the new State if successful
     ///
     /// this does not _advance_ the state to the new _state_ but apply a simple fragment
     /// of block to the current context.
     ///
     pub fn apply_fragment(&self, content: &Fragment, block_date: BlockDate) -> Result<Self, Error> {
+        self.apply_fragment_with_verified_ballot(content, block_date, None)
+    }
+
+    /// same as [`Self::apply_fragment`], the proof of a private vote is not
+    /// verified again if `verified_ballot` is the ballot of its encrypted vote
+    fn apply_fragment_with_verified_ballot(
+        &self,
+        content: &Fragment,
+        block_date: BlockDate,
+        verified_ballot: Option<Ballot>,
+    ) -> Result<Self, Error> {
         let mut new_ledger = self.clone();
 
         let fragment_id = content.hash();
@@ -1024,8 +1122,11 @@ impl Ledger {
                     }
                 };
 
-                new_ledger =
-                    new_ledger_.apply_vote_cast(account_id, tx.payload().into_payload())?;
+                new_ledger = new_ledger_.apply_vote_cast(
+                    account_id,
+                    tx.payload().into_payload(),
+                    verified_ballot,
+                )?;
             }
             Fragment::VoteTally(tx) => {
                 let tx = tx.as_slice();
@@ -1045,7 +1146,55 @@ impl Ledger {
                 let (new_ledger_, _fee) =
                     new_ledger.apply_transaction(&fragment_id, &tx, block_date)?;
 
-                new_ledger = new_ledger_.mint_token(tx.payload().into_payload())?;
+                // the witnesses of the inputs have just been verified, so the owners
+                // of the inputs are the signers of the minting transaction
+                let (accounts, multisig_accounts) = transaction_signers(&tx)?;
+                new_ledger = new_ledger_.mint_token(
+                    tx.payload().into_payload(),
+                    block_date,
+                    &accounts,
+                    &multisig_accounts,
+                )?;
+            }
+            Fragment::BurnToken(tx) => {
+                let tx = tx.as_slice();
+
+                let (new_ledger_, _fee) =
+                    new_ledger.apply_transaction(&fragment_id, &tx, block_date)?;
+
+                let (accounts, multisig_accounts) = transaction_signers(&tx)?;
+                new_ledger = new_ledger_.burn_token(
+                    tx.payload().into_payload(),
+                    &accounts,
+                    &multisig_accounts,
+                )?;
+            }
+            Fragment::VoteDelegation(tx) => {
+                let tx = tx.as_slice();
+                // this is a lightweight check, do this early to avoid doing any unnecessary computation
+                check::valid_vote_delegation(&tx)?;
+                let (new_ledger_, _fee) =
+                    new_ledger.apply_transaction(&fragment_id, &tx, block_date)?;
+
+                // we've just verified that this is a valid transaction (i.e. contains 1 input and 1 witness)
+                let account_id = match tx
+                    .inputs()
+                    .iter()
+                    .map(|input| input.to_enum())
+                    .zip(tx.witnesses().iter())
+                    .next()
+                    .unwrap()
+                {
+                    (InputEnum::AccountInput(account_id, _), Witness::Account(_, _)) => account_id
+                        .to_single_account()
+                        .ok_or(Error::AccountIdentifierInvalid)?,
+                    (_, _) => {
+                        return Err(Error::VoteDelegationInvalidTransaction);
+                    }
+                };
+
+                new_ledger =
+                    new_ledger_.apply_vote_delegation(account_id, tx.payload().into_payload());
             }
             Fragment::Evm(_tx) => {
                 #[cfg(feature = "evm")]
@@ -1053,6 +1202,7 @@ impl Ledger {
                     (new_ledger.accounts, new_ledger.evm) = evm::Ledger::run_transaction(
                         new_ledger.evm,
                         new_ledger.accounts,
+                        fragment_id,
                         _tx.clone(),
                         new_ledger.settings.evm_config,
                     )?;
@@ -1185,13 +1335,38 @@ impl Ledger {
         mut self,
         account_id: account::Identifier,
         vote: VoteCast,
+        verified_ballot: Option<Ballot>,
     ) -> Result<Self, Error> {
-        self.votes =
-            self.votes
-                .apply_vote(self.date(), account_id, vote, self.token_distribution())?;
+        self.votes = self.votes.apply_vote(
+            self.date(),
+            account_id,
+            vote,
+            verified_ballot,
+            self.token_distribution(),
+            &self.vote_delegations,
+        )?;
         Ok(self)
     }
 
+    pub fn apply_vote_delegation(
+        mut self,
+        account_id: account::Identifier,
+        delegation: VoteDelegation,
+    ) -> Self {
+        let VoteDelegation { scope, delegation } = delegation;
+        self.vote_delegations = self
+            .vote_delegations
+            .delegate(account_id, scope, delegation);
+        self
+    }
+
+    pub fn vote_delegations(&self) -> &VoteDelegations {
+        &self.vote_delegations
+    }
+
+    /// the status of every vote plan, including the voting power delegated to
+    /// the voters for the vote plans whose voting period is over, counted when
+    /// the ledger reaches the end of the voting period
     pub fn active_vote_plans(&self) -> Vec<VotePlanStatus> {
         self.votes
             .plans
@@ -1200,6 +1375,69 @@ impl Ledger {
             .collect()
     }
 
+    /// verify the proofs of the private votes among `votes`, before they are
+    /// applied
+    ///
+    /// The proofs are verified together in a single batch, and one by one only
+    /// if the batch is invalid, to find out which ones are invalid. Returns
+    /// whether the proof of each of the `votes` is valid: the votes that are
+    /// not private, or that are cast for an unknown vote plan, are fully
+    /// validated when applied and reported valid here.
+    pub fn verify_private_vote_proofs(&self, votes: &[VoteCast]) -> Vec<bool> {
+        if self.votes.verify_private_votes(votes).is_ok() {
+            return vec![true; votes.len()];
+        }
+        votes
+            .iter()
+            .map(|vote| {
+                self.votes
+                    .verify_private_votes(std::iter::once(vote))
+                    .is_ok()
+            })
+            .collect()
+    }
+
+    /// take a compact checkpoint of the votes of every vote plan of the ledger
+    pub fn vote_plan_checkpoints(&self) -> Vec<VotePlanCheckpoint> {
+        self.votes.checkpoints()
+    }
+
+    /// restore the votes of one of the vote plans of the ledger from a
+    /// checkpoint taken with [`Ledger::vote_plan_checkpoints`]
+    pub fn restore_vote_plan_checkpoint(
+        &self,
+        checkpoint: &VotePlanCheckpoint,
+    ) -> Result<Self, Error> {
+        let mut new_ledger = self.clone();
+        // the checkpoint may have been taken before the end of the voting
+        // period the ledger is already past
+        new_ledger.votes = self
+            .votes
+            .restore_checkpoint(checkpoint)?
+            .count_delegated_votes(
+                self.date(),
+                self.token_distribution(),
+                &self.vote_delegations,
+            );
+        Ok(new_ledger)
+    }
+
+    /// restore the value and the spending counters of an account, used along
+    /// with [`Ledger::restore_vote_plan_checkpoint`] to resume the replay of
+    /// the fragments of a ledger.
+    pub fn restore_account(
+        &self,
+        identifier: &account::Identifier,
+        value: Value,
+        spending: account::SpendingCounterIncreasing,
+    ) -> Self {
+        let mut new_ledger = self.clone();
+        new_ledger.accounts = self
+            .accounts
+            .restore_account(identifier, value, spending, ());
+        new_ledger
+    }
+
     pub fn apply_vote_tally<'a>(
         mut self,
         tally: &certificate::VoteTally,
@@ -1212,6 +1450,12 @@ impl Ledger {
 
         let mut actions = Vec::new();
 
+        // in case no block started after the end of the voting period
+        self.votes = self.votes.count_delegated_votes(
+            self.date(),
+            self.token_distribution(),
+            &self.vote_delegations,
+        );
         self.votes = self.votes.apply_committee_result(
             self.date(),
             &self.governance,
@@ -1355,8 +1599,28 @@ impl Ledger {
         Ok(self)
     }
 
-    pub fn mint_token(self, mt: MintToken) -> Result<Self, Error> {
-        mt.policy.check_minting_tx()?;
+    pub fn mint_token(
+        self,
+        mt: MintToken,
+        date: BlockDate,
+        accounts: &[account::Identifier],
+        multisig_accounts: &[multisig::Identifier],
+    ) -> Result<Self, Error> {
+        let token = TokenIdentifier {
+            policy_hash: mt.policy.hash(),
+            token_name: mt.name.clone(),
+        };
+        let supply = self
+            .token_totals
+            .get_total(&token)
+            .unwrap_or_else(Value::zero)
+            .saturating_add(mt.value);
+        mt.policy.check_minting_tx(&MintingContext {
+            date,
+            accounts,
+            multisig_accounts,
+            supply,
+        })?;
         self.mint_token_unchecked(mt)
     }
 
@@ -1376,6 +1640,33 @@ impl Ledger {
         Ok(self)
     }
 
+    pub fn burn_token(
+        mut self,
+        bt: BurnToken,
+        accounts: &[account::Identifier],
+        multisig_accounts: &[multisig::Identifier],
+    ) -> Result<Self, Error> {
+        let BurnToken {
+            name,
+            policy,
+            from,
+            value,
+        } = bt;
+        policy.check_burning_tx(accounts, multisig_accounts)?;
+        // the minting policy authorizes the burning of the token, but only the
+        // holder can give away its own tokens
+        if !accounts.contains(&from) {
+            return Err(Error::BurnTokenHolderSignatureMissing(from));
+        }
+        let token = TokenIdentifier {
+            policy_hash: policy.hash(),
+            token_name: name,
+        };
+        self.accounts = self.accounts.token_sub(&from, &token, value)?;
+        self.token_totals = self.token_totals.sub(&token, value)?;
+        Ok(self)
+    }
+
     #[cfg(feature = "evm")]
     pub fn apply_map_accounts<'a>(
         mut self,
@@ -1445,6 +1736,12 @@ impl Ledger {
         )?)
     }
 
+    /// Receipts of the EVM transactions applied in the last block
+    #[cfg(feature = "evm")]
+    pub fn evm_receipts(&self) -> &[evm::Receipt] {
+        self.evm.receipts()
+    }
+
     #[cfg(feature = "evm")]
     pub fn get_evm_gas_price(&self) -> u64 {
         self.settings.evm_environment.gas_price
@@ -1738,7 +2035,19 @@ impl ApplyBlockLedger {
     }
 
     pub fn apply_fragment(&self, fragment: &Fragment) -> Result<Self, Error> {
-        let ledger = self.ledger.apply_fragment(fragment, self.block_date)?;
+        self.apply_fragment_with_verified_ballot(fragment, None)
+    }
+
+    fn apply_fragment_with_verified_ballot(
+        &self,
+        fragment: &Fragment,
+        verified_ballot: Option<Ballot>,
+    ) -> Result<Self, Error> {
+        let ledger = self.ledger.apply_fragment_with_verified_ballot(
+            fragment,
+            self.block_date,
+            verified_ballot,
+        )?;
         Ok(ApplyBlockLedger {
             ledger,
             ..self.clone()
@@ -1817,6 +2126,37 @@ pub enum MatchingIdentifierWitness<'a> {
     ),
 }
 
+/// The accounts and the multisig accounts that signed the inputs of a
+/// transaction, in the order of the inputs. The witnesses of the transaction
+/// are expected to be already verified.
+fn transaction_signers<Extra>(
+    tx: &TransactionSlice<Extra>,
+) -> Result<(Vec<account::Identifier>, Vec<multisig::Identifier>), Error> {
+    let mut accounts = Vec::new();
+    let mut multisig_accounts = Vec::new();
+    for (input, witness) in tx
+        .inputs()
+        .iter()
+        .map(|input| input.to_enum())
+        .zip(tx.witnesses().iter())
+    {
+        match (input, witness) {
+            (InputEnum::AccountInput(account_id, _), Witness::Account(_, _)) => {
+                accounts.push(
+                    account_id
+                        .to_single_account()
+                        .ok_or(Error::AccountIdentifierInvalid)?,
+                );
+            }
+            (InputEnum::AccountInput(account_id, _), Witness::Multisig(_, _)) => {
+                multisig_accounts.push(account_id.to_multi_account());
+            }
+            (_, _) => {}
+        }
+    }
+    Ok((accounts, multisig_accounts))
+}
+
 fn match_identifier_witness<'a>(
     account: &UnspecifiedAccountIdentifier,
     witness: &'a Witness,
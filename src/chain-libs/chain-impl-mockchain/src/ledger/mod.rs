@@ -12,6 +12,8 @@ pub mod recovery;
 mod reward_info;
 pub mod token_distribution;
 
+#[cfg(feature = "evm")]
+pub use evm::Receipt as EvmReceipt;
 pub use iter::*;
 pub use leaderlog::LeadersParticipationRecord;
 pub use ledger::*;
@@ -71,6 +71,7 @@ use chain_crypto::digest::{DigestAlg, DigestOf};
 use chain_time::era::{pack_time_era, unpack_time_era};
 use imhamt::Hamt;
 use std::io::Write;
+use std::num::NonZeroU8;
 use std::sync::Arc;
 
 #[cfg(test)]
@@ -912,6 +913,43 @@ fn unpack_payload_type(codec: &mut Codec<&[u8]>) -> Result<vote::PayloadType, Re
     vote::PayloadType::try_from(byte).map_err(|e| ReadError::InvalidData(e.to_string()))
 }
 
+fn pack_weighted_ballot<W: std::io::Write>(
+    kind: Option<&chain_vote::WeightedBallotKind>,
+    codec: &mut Codec<W>,
+) -> Result<(), WriteError> {
+    match kind {
+        None => codec.put_u8(0),
+        Some(chain_vote::WeightedBallotKind::Approval { max_selections }) => {
+            codec.put_u8(1)?;
+            codec.put_u8(max_selections.get())
+        }
+        Some(chain_vote::WeightedBallotKind::Budget { budget }) => {
+            codec.put_u8(2)?;
+            codec.put_u8(budget.get())
+        }
+    }
+}
+
+fn unpack_weighted_ballot(
+    codec: &mut Codec<&[u8]>,
+) -> Result<Option<chain_vote::WeightedBallotKind>, ReadError> {
+    let tag = codec.get_u8()?;
+    if tag == 0 {
+        return Ok(None);
+    }
+    let value = NonZeroU8::new(codec.get_u8()?)
+        .ok_or_else(|| ReadError::InvalidData("zero weighted ballot total".to_string()))?;
+    match tag {
+        1 => Ok(Some(chain_vote::WeightedBallotKind::Approval {
+            max_selections: value,
+        })),
+        2 => Ok(Some(chain_vote::WeightedBallotKind::Budget {
+            budget: value,
+        })),
+        t => Err(ReadError::UnknownTag(t as u32)),
+    }
+}
+
 fn pack_committee_public_keys<W: std::io::Write>(
     keys: &[chain_vote::MemberPublicKey],
     codec: &mut Codec<W>,
@@ -938,6 +976,17 @@ fn unpack_committee_public_keys(
     Ok(result)
 }
 
+fn pack_committee_threshold<W: std::io::Write>(
+    threshold: Option<NonZeroU8>,
+    codec: &mut Codec<W>,
+) -> Result<(), WriteError> {
+    codec.put_u8(threshold.map_or(0, NonZeroU8::get))
+}
+
+fn unpack_committee_threshold(codec: &mut Codec<&[u8]>) -> Result<Option<NonZeroU8>, ReadError> {
+    Ok(NonZeroU8::new(codec.get_u8()?))
+}
+
 fn pack_voting_token<W: std::io::Write>(
     token_identifier: &TokenIdentifier,
     codec: &mut Codec<W>,
@@ -973,8 +1022,10 @@ fn pack_vote_plan<W: std::io::Write>(
     pack_block_date(vote_plan.vote_end(), codec)?;
     pack_block_date(vote_plan.committee_end(), codec)?;
     pack_payload_type(vote_plan.payload_type(), codec)?;
+    pack_weighted_ballot(vote_plan.weighted_ballot(), codec)?;
     pack_vote_proposals(vote_plan.proposals(), codec)?;
     pack_committee_public_keys(vote_plan.committee_public_keys(), codec)?;
+    pack_committee_threshold(vote_plan.committee_threshold(), codec)?;
     pack_voting_token(vote_plan.voting_token(), codec)?;
     Ok(())
 }
@@ -984,8 +1035,10 @@ fn unpack_vote_plan(codec: &mut Codec<&[u8]>) -> Result<VotePlan, ReadError> {
     let vote_end = unpack_block_date(codec)?;
     let committee_end = unpack_block_date(codec)?;
     let payload_type = unpack_payload_type(codec)?;
+    let weighted_ballot = unpack_weighted_ballot(codec)?;
     let proposals = unpack_proposals(codec)?;
     let keys = unpack_committee_public_keys(codec)?;
+    let committee_threshold = unpack_committee_threshold(codec)?;
     let voting_token = unpack_voting_token(codec)?;
     Ok(VotePlan::new(
         vote_start,
@@ -993,7 +1046,9 @@ fn unpack_vote_plan(codec: &mut Codec<&[u8]>) -> Result<VotePlan, ReadError> {
         committee_end,
         proposals,
         payload_type,
+        weighted_ballot,
         keys,
+        committee_threshold,
         voting_token,
     ))
 }
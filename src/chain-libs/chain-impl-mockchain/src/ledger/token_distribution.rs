@@ -2,9 +2,9 @@ use crate::{
     account::{self, LedgerError},
     ledger::Error,
     tokens::identifier::TokenIdentifier,
-    value::Value,
+    value::{Value, ValueError},
 };
-use imhamt::Hamt;
+use imhamt::{Hamt, UpdateError};
 use std::collections::hash_map::DefaultHasher;
 
 #[derive(PartialEq, Eq)]
@@ -71,6 +71,24 @@ impl TokenTotals {
             .map_err(Into::into)
     }
 
+    #[must_use = "Does not modify the internal state"]
+    pub fn sub(&self, token: &TokenIdentifier, value: Value) -> Result<TokenTotals, Error> {
+        self.0
+            .update(token, |v| {
+                let total = v.checked_sub(value)?;
+                Ok(if total == Value::zero() {
+                    None
+                } else {
+                    Some(total)
+                })
+            })
+            .map(TokenTotals)
+            .map_err(|e| match e {
+                UpdateError::KeyNotFound => ValueError::NegativeAmount.into(),
+                UpdateError::ValueCallbackError(e) => e.into(),
+            })
+    }
+
     pub fn get_total(&self, token: &TokenIdentifier) -> Option<Value> {
         self.0.lookup(token).copied()
     }
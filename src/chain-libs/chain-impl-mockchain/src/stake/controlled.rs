@@ -74,6 +74,11 @@ impl StakeControl {
         self.control.lookup(identifier).copied()
     }
 
+    /// iterate over the stake controlled by each account
+    pub fn iter(&self) -> impl Iterator<Item = (&Identifier, &Stake)> {
+        self.control.iter()
+    }
+
     /// get the ratio controlled by the given account
     ///
     /// the ratio is based on the total assigned stake, stake that is
@@ -242,6 +242,30 @@ impl TestTxCertBuilder {
                 let tx = builder.set_payload_auth(&());
                 Fragment::MintToken(tx)
             }
+            Certificate::BurnToken(burn_token) => {
+                let builder = self.set_initial_ios(
+                    valid_until,
+                    TxBuilder::new().set_payload(burn_token),
+                    funder,
+                    inputs,
+                    outputs,
+                    make_witness,
+                );
+                let tx = builder.set_payload_auth(&());
+                Fragment::BurnToken(tx)
+            }
+            Certificate::VoteDelegation(vote_delegation) => {
+                let builder = self.set_initial_ios(
+                    valid_until,
+                    TxBuilder::new().set_payload(vote_delegation),
+                    funder,
+                    inputs,
+                    outputs,
+                    make_witness,
+                );
+                let tx = builder.set_payload_auth(&());
+                Fragment::VoteDelegation(tx)
+            }
             Certificate::EvmMapping(evm_mapping) => {
                 let builder = self.set_initial_ios(
                     valid_until,
@@ -319,7 +343,9 @@ pub fn tally_sign(
 
     match payload_type {
         PayloadType::Public => TallyProof::Public { id, signature },
-        PayloadType::Private => TallyProof::Private { id, signature },
+        PayloadType::Private | PayloadType::PrivateWeighted => {
+            TallyProof::Private { id, signature }
+        }
     }
 }
 
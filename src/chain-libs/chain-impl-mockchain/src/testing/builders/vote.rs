@@ -44,7 +44,7 @@ pub fn decrypt_tally(
         .into_iter()
         .zip(tallies.into_iter())
         .map(|(shares, tally)| DecryptedPrivateTallyProposal {
-            decrypt_shares: shares.into_boxed_slice(),
+            decrypt_shares: shares.into_iter().map(Some).collect(),
             tally_result: tally.votes.into_boxed_slice(),
         })
         .collect();
@@ -0,0 +1,210 @@
+use crate::{
+    account::{Identifier, LedgerError::ValueError},
+    certificate::{BurnToken, MintToken},
+    ledger::Error::{Account, BurnTokenHolderSignatureMissing, MintingPolicyViolation},
+    testing::{
+        ledger::{ConfigBuilder, TestLedger},
+        scenario::{prepare_scenario, wallet},
+        TestGen,
+    },
+    tokens::{
+        identifier::TokenIdentifier,
+        minting_policy::{
+            MintingPolicy, MintingPolicyEntry,
+            MintingPolicyViolation::{AccountSignatureMissing, BurningNotAllowed},
+        },
+    },
+    value::{Value, ValueError::NegativeAmount},
+};
+
+const ALICE: &str = "ALICE";
+const BOB: &str = "BOB";
+
+fn token_id(mint_token: &MintToken) -> TokenIdentifier {
+    TokenIdentifier {
+        policy_hash: mint_token.policy.hash(),
+        token_name: mint_token.name.clone(),
+    }
+}
+
+fn total_supply(ledger: &TestLedger, mint_token: &MintToken) -> Option<Value> {
+    ledger
+        .ledger
+        .token_totals()
+        .get_total(&token_id(mint_token))
+}
+
+fn account_tokens(ledger: &TestLedger, mint_token: &MintToken, id: &Identifier) -> Option<Value> {
+    let token = token_id(mint_token);
+    ledger
+        .ledger
+        .token_distribution()
+        .token(&token)
+        .get_account(id)
+        .unwrap()
+}
+
+fn burn_token_for(mint_token: &MintToken, value: Value) -> BurnToken {
+    BurnToken {
+        name: mint_token.name.clone(),
+        policy: mint_token.policy.clone(),
+        from: mint_token.to.clone(),
+        value,
+    }
+}
+
+#[test]
+pub fn burn_token_signed_by_policy_account() {
+    let (mut ledger, controller) = prepare_scenario()
+        .with_config(ConfigBuilder::new())
+        .with_initials(vec![wallet(ALICE).with(1_000), wallet(BOB).with(1_000)])
+        .build()
+        .unwrap();
+
+    let mut alice = controller.wallet(ALICE).unwrap();
+    let bob = controller.wallet(BOB).unwrap();
+
+    let mint_token = MintToken {
+        policy: MintingPolicy::new()
+            .with_entry(MintingPolicyEntry::Account(alice.public_key().into()))
+            .unwrap(),
+        value: Value(10),
+        ..TestGen::mint_token_for_wallet(alice.public_key().into())
+    };
+    controller
+        .mint_token(&alice, mint_token.clone(), &mut ledger)
+        .unwrap();
+    alice.confirm_transaction();
+
+    assert_eq!(
+        controller
+            .burn_token(&bob, burn_token_for(&mint_token, Value(4)), &mut ledger)
+            .err()
+            .unwrap(),
+        MintingPolicyViolation(AccountSignatureMissing(alice.public_key().into()))
+    );
+
+    controller
+        .burn_token(&alice, burn_token_for(&mint_token, Value(4)), &mut ledger)
+        .unwrap();
+    alice.confirm_transaction();
+
+    assert_eq!(total_supply(&ledger, &mint_token), Some(Value(6)));
+    assert_eq!(
+        account_tokens(&ledger, &mint_token, &alice.public_key().into()),
+        Some(Value(6))
+    );
+
+    controller
+        .burn_token(&alice, burn_token_for(&mint_token, Value(6)), &mut ledger)
+        .unwrap();
+    alice.confirm_transaction();
+
+    assert_eq!(total_supply(&ledger, &mint_token), None);
+    assert_eq!(
+        account_tokens(&ledger, &mint_token, &alice.public_key().into()),
+        None
+    );
+}
+
+#[test]
+pub fn burn_token_of_another_holder_not_allowed() {
+    let (mut ledger, controller) = prepare_scenario()
+        .with_config(ConfigBuilder::new())
+        .with_initials(vec![wallet(ALICE).with(1_000), wallet(BOB).with(1_000)])
+        .build()
+        .unwrap();
+
+    let mut alice = controller.wallet(ALICE).unwrap();
+    let bob = controller.wallet(BOB).unwrap();
+
+    let mint_token = MintToken {
+        policy: MintingPolicy::new()
+            .with_entry(MintingPolicyEntry::Account(alice.public_key().into()))
+            .unwrap(),
+        value: Value(10),
+        ..TestGen::mint_token_for_wallet(bob.public_key().into())
+    };
+    controller
+        .mint_token(&alice, mint_token.clone(), &mut ledger)
+        .unwrap();
+    alice.confirm_transaction();
+
+    // the policy account cannot burn the tokens held by bob without the
+    // signature of bob
+    assert_eq!(
+        controller
+            .burn_token(&alice, burn_token_for(&mint_token, Value(4)), &mut ledger)
+            .err()
+            .unwrap(),
+        BurnTokenHolderSignatureMissing(bob.public_key().into())
+    );
+    assert_eq!(total_supply(&ledger, &mint_token), Some(Value(10)));
+    assert_eq!(
+        account_tokens(&ledger, &mint_token, &bob.public_key().into()),
+        Some(Value(10))
+    );
+}
+
+#[test]
+pub fn burn_token_not_allowed_without_signature_entry() {
+    // tokens of a policy without signature entries can only be minted in block0
+    let token_name = TestGen::token_name();
+    let (mut ledger, controller) = prepare_scenario()
+        .with_config(ConfigBuilder::new())
+        .with_initials(vec![wallet(ALICE)
+            .with(1_000)
+            .with_token(token_name.clone(), 10)])
+        .build()
+        .unwrap();
+
+    let alice = controller.wallet(ALICE).unwrap();
+
+    let mint_token = MintToken {
+        name: token_name,
+        policy: MintingPolicy::new(),
+        to: alice.public_key().into(),
+        value: Value(10),
+    };
+
+    assert_eq!(
+        controller
+            .burn_token(&alice, burn_token_for(&mint_token, Value(1)), &mut ledger)
+            .err()
+            .unwrap(),
+        MintingPolicyViolation(BurningNotAllowed)
+    );
+    assert_eq!(total_supply(&ledger, &mint_token), Some(mint_token.value));
+}
+
+#[test]
+pub fn burn_token_more_than_held() {
+    let (mut ledger, controller) = prepare_scenario()
+        .with_config(ConfigBuilder::new())
+        .with_initials(vec![wallet(ALICE).with(1_000)])
+        .build()
+        .unwrap();
+
+    let mut alice = controller.wallet(ALICE).unwrap();
+
+    let mint_token = MintToken {
+        policy: MintingPolicy::new()
+            .with_entry(MintingPolicyEntry::Account(alice.public_key().into()))
+            .unwrap(),
+        value: Value(2),
+        ..TestGen::mint_token_for_wallet(alice.public_key().into())
+    };
+    controller
+        .mint_token(&alice, mint_token.clone(), &mut ledger)
+        .unwrap();
+    alice.confirm_transaction();
+
+    assert_eq!(
+        controller
+            .burn_token(&alice, burn_token_for(&mint_token, Value(3)), &mut ledger)
+            .err()
+            .unwrap(),
+        Account(ValueError(NegativeAmount))
+    );
+    assert_eq!(total_supply(&ledger, &mint_token), Some(Value(2)));
+}
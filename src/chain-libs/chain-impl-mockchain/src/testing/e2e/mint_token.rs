@@ -1,14 +1,33 @@
 use crate::{
+    certificate::MintToken,
+    date::BlockDate,
     ledger::Error::MintingPolicyViolation,
     testing::{
-        ledger::ConfigBuilder,
+        ledger::{ConfigBuilder, TestLedger},
         scenario::{prepare_scenario, wallet},
         TestGen,
     },
-    tokens::minting_policy::MintingPolicyViolation::AdditionalMintingNotAllowed,
+    tokens::{
+        identifier::TokenIdentifier,
+        minting_policy::{
+            MintingPolicy, MintingPolicyEntry,
+            MintingPolicyViolation::{
+                AccountSignatureMissing, AdditionalMintingNotAllowed, MaxSupplyExceeded, TimeLocked,
+            },
+        },
+    },
+    value::Value,
 };
 
 const ALICE: &str = "ALICE";
+const BOB: &str = "BOB";
+
+fn total_supply(ledger: &TestLedger, mint_token: &MintToken) -> Option<Value> {
+    ledger.ledger.token_totals().get_total(&TokenIdentifier {
+        policy_hash: mint_token.policy.hash(),
+        token_name: mint_token.name.clone(),
+    })
+}
 
 #[test]
 pub fn mint_token_not_allowed_outside_block_0() {
@@ -32,3 +51,144 @@ pub fn mint_token_not_allowed_outside_block_0() {
         MintingPolicyViolation(AdditionalMintingNotAllowed)
     );
 }
+
+#[test]
+pub fn mint_token_not_allowed_without_signature_entry() {
+    let (mut ledger, controller) = prepare_scenario()
+        .with_config(ConfigBuilder::new())
+        .with_initials(vec![wallet(ALICE).with(1_000)])
+        .build()
+        .unwrap();
+
+    let alice = controller.wallet(ALICE).unwrap();
+
+    let mint_token = MintToken {
+        policy: MintingPolicy::new()
+            .with_entry(MintingPolicyEntry::MaxSupply(Value(10)))
+            .unwrap(),
+        ..TestGen::mint_token_for_wallet(alice.public_key().into())
+    };
+
+    assert_eq!(
+        controller
+            .mint_token(&alice, mint_token.clone(), &mut ledger)
+            .err()
+            .unwrap(),
+        MintingPolicyViolation(AdditionalMintingNotAllowed)
+    );
+    assert_eq!(total_supply(&ledger, &mint_token), None);
+}
+
+#[test]
+pub fn mint_token_signed_by_policy_account() {
+    let (mut ledger, controller) = prepare_scenario()
+        .with_config(ConfigBuilder::new())
+        .with_initials(vec![wallet(ALICE).with(1_000), wallet(BOB).with(1_000)])
+        .build()
+        .unwrap();
+
+    let mut alice = controller.wallet(ALICE).unwrap();
+    let bob = controller.wallet(BOB).unwrap();
+
+    let mint_token = MintToken {
+        policy: MintingPolicy::new()
+            .with_entry(MintingPolicyEntry::Account(alice.public_key().into()))
+            .unwrap(),
+        ..TestGen::mint_token_for_wallet(bob.public_key().into())
+    };
+
+    assert_eq!(
+        controller
+            .mint_token(&bob, mint_token.clone(), &mut ledger)
+            .err()
+            .unwrap(),
+        MintingPolicyViolation(AccountSignatureMissing(alice.public_key().into()))
+    );
+    assert_eq!(total_supply(&ledger, &mint_token), None);
+
+    controller
+        .mint_token(&alice, mint_token.clone(), &mut ledger)
+        .unwrap();
+    alice.confirm_transaction();
+
+    assert_eq!(total_supply(&ledger, &mint_token), Some(mint_token.value));
+}
+
+#[test]
+pub fn mint_token_after_time_lock() {
+    let (mut ledger, controller) = prepare_scenario()
+        .with_config(ConfigBuilder::new())
+        .with_initials(vec![wallet(ALICE).with(1_000)])
+        .build()
+        .unwrap();
+
+    let mut alice = controller.wallet(ALICE).unwrap();
+    let lock = BlockDate {
+        epoch: 1,
+        slot_id: 0,
+    };
+
+    let mint_token = MintToken {
+        policy: MintingPolicy::new()
+            .with_entry(MintingPolicyEntry::Account(alice.public_key().into()))
+            .unwrap()
+            .with_entry(MintingPolicyEntry::TimeLock(lock))
+            .unwrap(),
+        ..TestGen::mint_token_for_wallet(alice.public_key().into())
+    };
+
+    controller
+        .mint_token(&alice, mint_token.clone(), &mut ledger)
+        .unwrap();
+    alice.confirm_transaction();
+
+    ledger.fast_forward_to(lock);
+
+    assert_eq!(
+        controller
+            .mint_token(&alice, mint_token.clone(), &mut ledger)
+            .err()
+            .unwrap(),
+        MintingPolicyViolation(TimeLocked(lock))
+    );
+    assert_eq!(total_supply(&ledger, &mint_token), Some(mint_token.value));
+}
+
+#[test]
+pub fn mint_token_up_to_max_supply() {
+    let (mut ledger, controller) = prepare_scenario()
+        .with_config(ConfigBuilder::new())
+        .with_initials(vec![wallet(ALICE).with(1_000)])
+        .build()
+        .unwrap();
+
+    let mut alice = controller.wallet(ALICE).unwrap();
+
+    let mint_token = MintToken {
+        policy: MintingPolicy::new()
+            .with_entry(MintingPolicyEntry::Account(alice.public_key().into()))
+            .unwrap()
+            .with_entry(MintingPolicyEntry::MaxSupply(Value(2)))
+            .unwrap(),
+        ..TestGen::mint_token_for_wallet(alice.public_key().into())
+    };
+
+    for _ in 0..2 {
+        controller
+            .mint_token(&alice, mint_token.clone(), &mut ledger)
+            .unwrap();
+        alice.confirm_transaction();
+    }
+
+    assert_eq!(
+        controller
+            .mint_token(&alice, mint_token.clone(), &mut ledger)
+            .err()
+            .unwrap(),
+        MintingPolicyViolation(MaxSupplyExceeded {
+            supply: Value(3),
+            max: Value(2),
+        })
+    );
+    assert_eq!(total_supply(&ledger, &mint_token), Some(Value(2)));
+}
@@ -1,3 +1,4 @@
+pub mod burn_token;
 #[cfg(feature = "evm")]
 pub mod evm_mapping;
 #[cfg(feature = "evm")]
@@ -12,5 +13,7 @@ pub mod spending_counter_lanes;
 pub mod stake_distribution;
 pub mod transactions;
 pub mod update_proposal;
+pub mod vote_delegation;
+pub mod vote_parameters;
 pub mod vote_private;
 pub mod vote_public;
@@ -71,3 +71,21 @@ pub fn update_slot_duration() {
 
     assert_eq!(final_slot_duration, ledger.settings().slot_duration);
 }
+
+#[test]
+pub fn initial_settings_follow_block0() {
+    let leader_pair = TestGen::leader_pair();
+    let (ledger, _controller) = prepare_scenario()
+        .with_config(
+            ConfigBuilder::new()
+                .with_slot_duration(5)
+                .with_slots_per_epoch(30)
+                .with_discrimination(Discrimination::Test)
+                .with_leaders(&[leader_pair.id()]),
+        )
+        .build()
+        .unwrap();
+
+    assert_eq!(ledger.settings().slot_duration, 5);
+    assert_eq!(ledger.settings().slots_per_epoch, 30);
+}
@@ -0,0 +1,241 @@
+use crate::{
+    certificate::{VoteDelegation, VotePlan},
+    date::BlockDate,
+    fee::LinearFee,
+    testing::{
+        data::Wallet,
+        ledger::{ConfigBuilder, TestLedger},
+        scenario::{prepare_scenario, proposal, vote_plan, wallet, Controller},
+        VoteTestGen,
+    },
+    tokens::name::{TokenName, TOKEN_NAME_MAX_SIZE},
+    vote::{Choice, VoteDelegationRatio, VoteDelegationScope, VoteDelegationType},
+};
+
+const ALICE: &str = "Alice";
+const BOB: &str = "Bob";
+const CLARICE: &str = "Clarice";
+const STAKE_POOL: &str = "stake_pool";
+const VOTE_PLAN: &str = "fund1";
+
+/// results of the first proposal, the voting power delegated to the voters is
+/// only counted once the voting period is over
+fn proposal_results(ledger: &TestLedger) -> Vec<u64> {
+    ledger.ledger.active_vote_plans()[0].proposals[0]
+        .tally
+        .result()
+        .unwrap()
+        .results()
+        .iter()
+        .map(|weight| (*weight).into())
+        .collect()
+}
+
+fn end_voting_period(ledger: &mut TestLedger) {
+    ledger.fast_forward_to(BlockDate {
+        epoch: 1,
+        slot_id: 1,
+    });
+}
+
+fn delegate_to(
+    controller: &Controller,
+    ledger: &mut TestLedger,
+    delegator: &mut Wallet,
+    delegation: VoteDelegationType,
+) {
+    let vote_plan: VotePlan = controller.vote_plan(VOTE_PLAN).unwrap().into();
+    controller
+        .delegate_vote(
+            delegator,
+            VoteDelegation {
+                scope: VoteDelegationScope::Token(vote_plan.voting_token().clone()),
+                delegation,
+            },
+            ledger,
+        )
+        .unwrap();
+    delegator.confirm_transaction();
+}
+
+fn vote_for(controller: &Controller, ledger: &mut TestLedger, voter: &Wallet, choice: u8) {
+    let vote_plan_def = controller.vote_plan(VOTE_PLAN).unwrap();
+    controller
+        .cast_vote_public(
+            voter,
+            &vote_plan_def,
+            &vote_plan_def.proposal(0).id(),
+            Choice::new(choice),
+            ledger,
+        )
+        .unwrap();
+}
+
+fn prepare_vote_delegation_scenario() -> (TestLedger, Controller) {
+    let voting_token = TokenName::try_from(vec![0u8; TOKEN_NAME_MAX_SIZE]).unwrap();
+    prepare_scenario()
+        .with_config(ConfigBuilder::new().with_fee(LinearFee::new(1, 1, 1)))
+        .with_initials(vec![
+            wallet(ALICE)
+                .with(1_000)
+                .with_token(voting_token.clone(), 1_000)
+                .owns(STAKE_POOL)
+                .committee_member(),
+            wallet(BOB)
+                .with(1_000)
+                .with_token(voting_token.clone(), 500),
+            wallet(CLARICE).with(1_000).with_token(voting_token, 300),
+        ])
+        .with_vote_plans(vec![vote_plan(VOTE_PLAN)
+            .owner(ALICE)
+            .consecutive_epoch_dates()
+            .with_proposal(proposal(VoteTestGen::external_proposal_id()).options(3))])
+        .build()
+        .unwrap()
+}
+
+#[test]
+pub fn representative_vote_carries_delegated_voting_power() {
+    let (mut ledger, controller) = prepare_vote_delegation_scenario();
+    let alice = controller.wallet(ALICE).unwrap();
+    let mut bob = controller.wallet(BOB).unwrap();
+
+    delegate_to(
+        &controller,
+        &mut ledger,
+        &mut bob,
+        VoteDelegationType::Full(alice.public_key().into()),
+    );
+    vote_for(&controller, &mut ledger, &alice, 1);
+    assert_eq!(proposal_results(&ledger), vec![0, 1_000, 0]);
+
+    end_voting_period(&mut ledger);
+    assert_eq!(proposal_results(&ledger), vec![0, 1_500, 0]);
+}
+
+#[test]
+pub fn direct_vote_overrides_vote_delegation() {
+    let (mut ledger, controller) = prepare_vote_delegation_scenario();
+    let alice = controller.wallet(ALICE).unwrap();
+    let mut bob = controller.wallet(BOB).unwrap();
+
+    delegate_to(
+        &controller,
+        &mut ledger,
+        &mut bob,
+        VoteDelegationType::Full(alice.public_key().into()),
+    );
+    vote_for(&controller, &mut ledger, &alice, 1);
+    vote_for(&controller, &mut ledger, &bob, 2);
+
+    end_voting_period(&mut ledger);
+    assert_eq!(proposal_results(&ledger), vec![0, 1_000, 500]);
+}
+
+#[test]
+pub fn vote_delegation_after_the_vote_of_the_representative() {
+    let (mut delegation_first, controller) = prepare_vote_delegation_scenario();
+    let alice = controller.wallet(ALICE).unwrap();
+    let delegation = VoteDelegationType::Full(alice.public_key().into());
+
+    delegate_to(
+        &controller,
+        &mut delegation_first,
+        &mut controller.wallet(BOB).unwrap(),
+        delegation.clone(),
+    );
+    vote_for(&controller, &mut delegation_first, &alice, 1);
+    end_voting_period(&mut delegation_first);
+
+    // same fragments, the delegation comes after the vote of the representative
+    let (mut vote_first, controller) = prepare_vote_delegation_scenario();
+    let alice = controller.wallet(ALICE).unwrap();
+    vote_for(&controller, &mut vote_first, &alice, 1);
+    delegate_to(
+        &controller,
+        &mut vote_first,
+        &mut controller.wallet(BOB).unwrap(),
+        delegation,
+    );
+    end_voting_period(&mut vote_first);
+
+    assert_eq!(proposal_results(&vote_first), vec![0, 1_500, 0]);
+    assert_eq!(
+        proposal_results(&vote_first),
+        proposal_results(&delegation_first)
+    );
+}
+
+#[test]
+pub fn vote_delegation_split_between_representatives() {
+    let (mut ledger, controller) = prepare_vote_delegation_scenario();
+
+    let alice = controller.wallet(ALICE).unwrap();
+    let bob = controller.wallet(BOB).unwrap();
+    let mut clarice = controller.wallet(CLARICE).unwrap();
+    let vote_plan_def = controller.vote_plan(VOTE_PLAN).unwrap();
+    let vote_plan: VotePlan = vote_plan_def.clone().into();
+
+    let ratio = VoteDelegationRatio::new(
+        3,
+        vec![(alice.public_key().into(), 1), (bob.public_key().into(), 2)],
+    )
+    .unwrap();
+    controller
+        .delegate_vote(
+            &clarice,
+            VoteDelegation {
+                scope: VoteDelegationScope::VotePlan(vote_plan.to_id()),
+                delegation: VoteDelegationType::Ratio(ratio),
+            },
+            &mut ledger,
+        )
+        .unwrap();
+    clarice.confirm_transaction();
+
+    controller
+        .cast_vote_public(
+            &alice,
+            &vote_plan_def,
+            &vote_plan_def.proposal(0).id(),
+            Choice::new(1),
+            &mut ledger,
+        )
+        .unwrap();
+    controller
+        .cast_vote_public(
+            &bob,
+            &vote_plan_def,
+            &vote_plan_def.proposal(0).id(),
+            Choice::new(2),
+            &mut ledger,
+        )
+        .unwrap();
+    end_voting_period(&mut ledger);
+    assert_eq!(proposal_results(&ledger), vec![0, 1_100, 700]);
+}
+
+#[test]
+pub fn removed_vote_delegation_is_not_applied() {
+    let (mut ledger, controller) = prepare_vote_delegation_scenario();
+    let alice = controller.wallet(ALICE).unwrap();
+    let mut bob = controller.wallet(BOB).unwrap();
+
+    delegate_to(
+        &controller,
+        &mut ledger,
+        &mut bob,
+        VoteDelegationType::Full(alice.public_key().into()),
+    );
+    vote_for(&controller, &mut ledger, &alice, 1);
+    // revoked after the vote of the representative
+    delegate_to(
+        &controller,
+        &mut ledger,
+        &mut bob,
+        VoteDelegationType::NonDelegated,
+    );
+
+    end_voting_period(&mut ledger);
+    assert_eq!(proposal_results(&ledger), vec![0, 1_000, 0]);
+}
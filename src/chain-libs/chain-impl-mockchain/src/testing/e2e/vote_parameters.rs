@@ -0,0 +1,160 @@
+use crate::testing::VoteTestGen;
+use crate::tokens::name::{TokenName, TOKEN_NAME_MAX_SIZE};
+use crate::{
+    fee::{LinearFee, PerCertificateFee},
+    header::BlockDate,
+    testing::{
+        ledger::ConfigBuilder,
+        scenario::{prepare_scenario, proposal, template::ProposalDefBuilder, vote_plan, wallet},
+        TestLedger,
+    },
+    value::Value,
+    vote::Choice,
+};
+use core::num::NonZeroU64;
+
+const ALICE: &str = "Alice";
+const STAKE_POOL: &str = "stake_pool";
+const VOTE_PLAN: &str = "fund1";
+
+/// cast a single public vote on a proposal carrying the given parameters
+/// action, tally it and move the ledger to the end of the epoch, just before
+/// the protocol changes are applied.
+fn vote_on_parameters_action(
+    config: ConfigBuilder,
+    proposal_builder: &mut ProposalDefBuilder,
+    choice: Choice,
+) -> TestLedger {
+    let voting_token = TokenName::try_from(vec![0u8; TOKEN_NAME_MAX_SIZE]).unwrap();
+    let (mut ledger, controller) = prepare_scenario()
+        .with_config(config)
+        .with_initials(vec![wallet(ALICE)
+            .with(1_000)
+            .with_token(voting_token, 1_000)
+            .owns(STAKE_POOL)
+            .committee_member()])
+        .with_vote_plans(vec![vote_plan(VOTE_PLAN)
+            .owner(ALICE)
+            .consecutive_epoch_dates()
+            .with_proposal(proposal_builder)])
+        .build()
+        .unwrap();
+
+    let mut alice = controller.wallet(ALICE).unwrap();
+    let vote_plan = controller.vote_plan(VOTE_PLAN).unwrap();
+    let proposal = vote_plan.proposal(0);
+
+    controller
+        .cast_vote_public(&alice, &vote_plan, &proposal.id(), choice, &mut ledger)
+        .unwrap();
+    alice.confirm_transaction();
+
+    ledger.fast_forward_to(BlockDate {
+        epoch: 1,
+        slot_id: 1,
+    });
+
+    controller
+        .tally_vote_public(&alice, &vote_plan, &mut ledger)
+        .unwrap();
+
+    ledger
+}
+
+#[test]
+pub fn vote_cast_action_update_fees() {
+    let per_certificate_fee =
+        PerCertificateFee::new(NonZeroU64::new(2), NonZeroU64::new(3), NonZeroU64::new(4));
+    let mut ledger = vote_on_parameters_action(
+        ConfigBuilder::new()
+            .with_fee(LinearFee::new(1, 1, 1))
+            .with_per_certificate_fee(per_certificate_fee),
+        proposal(VoteTestGen::external_proposal_id())
+            .options(3)
+            .action_update_fees(10, 20, 30),
+        Choice::new(1),
+    );
+
+    assert_eq!(ledger.fee().constant, 1);
+
+    ledger.apply_protocol_changes().unwrap();
+
+    let fee = ledger.fee();
+    assert_eq!(fee.constant, 10);
+    assert_eq!(fee.coefficient, 20);
+    assert_eq!(fee.certificate, 30);
+    assert_eq!(fee.per_certificate_fees, per_certificate_fee);
+}
+
+#[test]
+pub fn vote_cast_action_slot_duration() {
+    let mut ledger = vote_on_parameters_action(
+        ConfigBuilder::new()
+            .with_fee(LinearFee::new(1, 1, 1))
+            .with_slot_duration(10),
+        proposal(VoteTestGen::external_proposal_id())
+            .options(3)
+            .action_slot_duration(20),
+        Choice::new(1),
+    );
+
+    assert_eq!(ledger.settings().slot_duration, 10);
+
+    ledger.apply_protocol_changes().unwrap();
+
+    assert_eq!(ledger.settings().slot_duration, 20);
+}
+
+#[test]
+pub fn vote_cast_action_block_content_max_size() {
+    let mut ledger = vote_on_parameters_action(
+        ConfigBuilder::new()
+            .with_fee(LinearFee::new(1, 1, 1))
+            .with_block_content_max_size(4096),
+        proposal(VoteTestGen::external_proposal_id())
+            .options(3)
+            .action_block_content_max_size(8192),
+        Choice::new(1),
+    );
+
+    assert_eq!(ledger.settings().block_content_max_size, 4096);
+
+    ledger.apply_protocol_changes().unwrap();
+
+    assert_eq!(ledger.settings().block_content_max_size, 8192);
+}
+
+#[test]
+pub fn vote_cast_action_parameters_rejected() {
+    let mut ledger = vote_on_parameters_action(
+        ConfigBuilder::new()
+            .with_fee(LinearFee::new(1, 1, 1))
+            .with_slot_duration(10)
+            .with_rewards(Value(1000)),
+        proposal(VoteTestGen::external_proposal_id())
+            .options(3)
+            .action_slot_duration(20),
+        Choice::new(2),
+    );
+
+    ledger.apply_protocol_changes().unwrap();
+
+    assert_eq!(ledger.settings().slot_duration, 10);
+}
+
+#[test]
+pub fn vote_cast_action_parameters_applied_once() {
+    let mut ledger = vote_on_parameters_action(
+        ConfigBuilder::new().with_fee(LinearFee::new(1, 1, 1)),
+        proposal(VoteTestGen::external_proposal_id())
+            .options(3)
+            .action_update_fees(10, 20, 30),
+        Choice::new(1),
+    );
+
+    ledger.apply_protocol_changes().unwrap();
+    assert!(ledger.ledger.governance.parameters.logs().next().is_none());
+
+    ledger.apply_protocol_changes().unwrap();
+    assert_eq!(ledger.fee().constant, 10);
+}
@@ -3,10 +3,10 @@ use crate::testing::TestGen;
 use crate::testing::VoteTestGen;
 use crate::tokens::name::TokenName;
 use crate::tokens::name::TOKEN_NAME_MAX_SIZE;
-use crate::vote::VoteError::AlreadyVoted;
+use crate::vote::VoteError::{AlreadyVoted, VoteVerificationError};
 use crate::vote::VotePlanLedgerError::VoteError;
 use crate::{
-    certificate::VotePlan,
+    certificate::{VoteCast, VotePlan},
     fee::LinearFee,
     header::BlockDate,
     testing::{
@@ -15,9 +15,11 @@ use crate::{
         verifiers::LedgerStateVerifier,
     },
     value::Value,
-    vote::{Choice, PayloadType},
+    vote::{Choice, Payload, PayloadType},
 };
+use chain_vote::{BallotVerificationError, WeightedBallotKind};
 use imhamt::UpdateError::ValueCallbackError;
+use std::num::NonZeroU8;
 
 const ALICE: &str = "Alice";
 const STAKE_POOL: &str = "stake_pool";
@@ -107,6 +109,93 @@ pub fn private_vote_cast_action_transfer_to_rewards_all_shares() {
         .has_remaining_rewards_equals_to(&Value(1100));
 }
 
+#[test]
+pub fn private_weighted_vote_cast_action_transfer_to_rewards() {
+    let mut rng = TestGen::rand();
+    let members = VoteTestGen::committee_members_manager(MEMBERS_NO, THRESHOLD);
+
+    let voting_token = TokenName::try_from(vec![0u8; TOKEN_NAME_MAX_SIZE]).unwrap();
+
+    let (mut ledger, controller) = prepare_scenario()
+        .with_config(
+            ConfigBuilder::new()
+                .with_fee(LinearFee::new(1, 1, 1))
+                .with_rewards(Value(1000)),
+        )
+        .with_initials(vec![wallet(ALICE)
+            .with(1_000)
+            .with_token(voting_token, 1_000)
+            .owns(STAKE_POOL)
+            .committee_member()])
+        .with_vote_plans(vec![vote_plan(VOTE_PLAN)
+            .owner(ALICE)
+            .consecutive_epoch_dates()
+            .payload_type(PayloadType::PrivateWeighted)
+            .weighted_ballot(Some(WeightedBallotKind::Budget {
+                budget: NonZeroU8::new(3).unwrap(),
+            }))
+            .committee_keys(members.members_keys())
+            .with_proposal(
+                proposal(VoteTestGen::external_proposal_id())
+                    .options(3)
+                    .action_transfer_to_rewards(100),
+            )])
+        .build()
+        .unwrap();
+
+    let mut alice = controller.wallet(ALICE).unwrap();
+    let vote_plan = controller.vote_plan(VOTE_PLAN).unwrap();
+    let proposal = vote_plan.proposal(0);
+
+    controller
+        .cast_vote_private_weighted(
+            &alice,
+            &vote_plan,
+            &proposal.id(),
+            vec![0, 2, 1],
+            &mut ledger,
+            &mut rng,
+        )
+        .unwrap();
+    alice.confirm_transaction();
+
+    ledger.fast_forward_to(BlockDate {
+        epoch: 1,
+        slot_id: 1,
+    });
+
+    let vote_plans = ledger.ledger.active_vote_plans();
+    let vote_plan_status = vote_plans
+        .iter()
+        .find(|c_vote_plan| {
+            let vote_plan: VotePlan = vote_plan.clone().into();
+            c_vote_plan.id == vote_plan.to_id()
+        })
+        .unwrap();
+
+    let shares = decrypt_tally(vote_plan_status, &members).unwrap();
+    assert_eq!(
+        shares.iter().next().unwrap().tally_result.as_ref(),
+        &[0, 2_000, 1_000]
+    );
+
+    controller
+        .tally_vote_private(&alice, &vote_plan, shares, &mut ledger)
+        .unwrap();
+
+    ledger.fast_forward_to(BlockDate {
+        epoch: 1,
+        slot_id: 1,
+    });
+
+    ledger.apply_protocol_changes().unwrap();
+
+    LedgerStateVerifier::new(ledger.into())
+        .info("rewards pot is increased")
+        .pots()
+        .has_remaining_rewards_equals_to(&Value(1100));
+}
+
 #[test]
 pub fn shouldnt_panic_when_no_initial_tokens_and_no_votes() {
     let members = VoteTestGen::committee_members_manager(MEMBERS_NO, THRESHOLD);
@@ -182,6 +271,40 @@ pub fn private_vote_plan_without_keys() {
         .unwrap();
 }
 
+#[test]
+#[should_panic(expected = "cannot build test ledger")]
+pub fn private_weighted_vote_plan_with_too_large_ballots() {
+    let members = VoteTestGen::committee_members_manager(MEMBERS_NO, THRESHOLD);
+
+    // 40 options of 7 bits each and the 7 bits of the budget need more
+    // ciphertexts than a weighted vote cast can hold
+    let (_ledger, _controller) = prepare_scenario()
+        .with_config(
+            ConfigBuilder::new()
+                .with_fee(LinearFee::new(1, 1, 1))
+                .with_rewards(Value(1000)),
+        )
+        .with_initials(vec![wallet(ALICE)
+            .with(1_000)
+            .owns(STAKE_POOL)
+            .committee_member()])
+        .with_vote_plans(vec![vote_plan(VOTE_PLAN)
+            .owner(ALICE)
+            .consecutive_epoch_dates()
+            .payload_type(PayloadType::PrivateWeighted)
+            .weighted_ballot(Some(WeightedBallotKind::Budget {
+                budget: NonZeroU8::new(100).unwrap(),
+            }))
+            .committee_keys(members.members_keys())
+            .with_proposal(
+                proposal(VoteTestGen::external_proposal_id())
+                    .options(40)
+                    .action_transfer_to_rewards(100),
+            )])
+        .build()
+        .unwrap();
+}
+
 #[test]
 pub fn vote_on_same_proposal() {
     let mut rng = TestGen::rand();
@@ -322,3 +445,111 @@ pub fn vote_on_different_proposal() {
         )
         .is_ok());
 }
+
+#[test]
+pub fn private_votes_of_a_block_verified_in_a_batch() {
+    const BOB: &str = "Bob";
+    const CLARICE: &str = "Clarice";
+
+    let mut rng = TestGen::rand();
+    let members = VoteTestGen::committee_members_manager(MEMBERS_NO, THRESHOLD);
+
+    let voting_token = TokenName::try_from(vec![0u8; TOKEN_NAME_MAX_SIZE]).unwrap();
+
+    let (mut ledger, controller) = prepare_scenario()
+        .with_initials(vec![
+            wallet(ALICE)
+                .with(1_000)
+                .with_token(voting_token.clone(), 1_000)
+                .owns(STAKE_POOL)
+                .committee_member(),
+            wallet(BOB)
+                .with(1_000)
+                .with_token(voting_token.clone(), 1_000),
+            wallet(CLARICE).with(1_000).with_token(voting_token, 1_000),
+        ])
+        .with_vote_plans(vec![vote_plan(VOTE_PLAN)
+            .owner(ALICE)
+            .consecutive_epoch_dates()
+            .payload_type(PayloadType::Private)
+            .committee_keys(members.members_keys())
+            .with_proposal(proposal(VoteTestGen::external_proposal_id()).options(3))])
+        .build()
+        .unwrap();
+
+    let stake_pool = controller.stake_pool(STAKE_POOL).unwrap();
+    let vote_plan: VotePlan = controller.vote_plan(VOTE_PLAN).unwrap().into();
+    let proposal = &vote_plan.proposals()[0];
+
+    let votes: Vec<_> = (0..3)
+        .map(|i| {
+            let payload = VoteTestGen::private_vote_cast_payload_for(
+                &vote_plan,
+                proposal,
+                Choice::new(i % 3),
+                &mut rng,
+            );
+            VoteCast::new(vote_plan.to_id(), 0, payload)
+        })
+        .collect();
+
+    // the proof of the second vote does not match its encrypted vote anymore
+    let mut invalid_votes = votes.clone();
+    invalid_votes[1] = match (votes[1].payload(), votes[2].payload()) {
+        (
+            Payload::Private { proof, .. },
+            Payload::Private {
+                encrypted_vote: other_encrypted_vote,
+                ..
+            },
+        ) => VoteCast::new(
+            vote_plan.to_id(),
+            0,
+            Payload::private(other_encrypted_vote.clone(), proof.clone()),
+        ),
+        _ => unreachable!("private votes"),
+    };
+
+    let fragments = |votes: Vec<VoteCast>| -> Vec<_> {
+        [ALICE, BOB, CLARICE]
+            .iter()
+            .zip(votes)
+            .map(|(alias, vote)| {
+                controller.fragment_factory().vote_cast(
+                    ledger.date(),
+                    &controller.wallet(alias).unwrap(),
+                    vote,
+                )
+            })
+            .collect()
+    };
+    // only the vote with the invalid proof is rejected
+    assert_eq!(
+        ledger.ledger.verify_private_vote_proofs(&invalid_votes),
+        vec![true, false, true]
+    );
+    assert_eq!(
+        ledger.ledger.verify_private_vote_proofs(&votes),
+        vec![true; 3]
+    );
+
+    let invalid_fragments = fragments(invalid_votes);
+    let valid_fragments = fragments(votes);
+
+    assert_eq!(
+        ledger
+            .apply_praos_block(&stake_pool, invalid_fragments)
+            .err()
+            .unwrap(),
+        crate::ledger::ledger::Error::VotePlan(VoteError {
+            id: vote_plan.to_id(),
+            reason: ValueCallbackError(VoteVerificationError(BallotVerificationError)),
+        })
+    );
+
+    ledger
+        .apply_praos_block(&stake_pool, valid_fragments)
+        .unwrap();
+    let vote_plans = ledger.ledger.active_vote_plans();
+    assert_eq!(vote_plans[0].proposals[0].votes.size(), 3);
+}
@@ -16,7 +16,7 @@ use crate::{
 };
 use chain_core::property::BlockDate as BlockDateProp;
 use chain_crypto::digest::DigestOf;
-use chain_vote::{Crs, ElectionPublicKey, Vote};
+use chain_vote::{Crs, Vote, WeightedVote};
 use rand::SeedableRng;
 use rand_chacha::ChaCha20Rng;
 use rand_core::{CryptoRng, RngCore};
@@ -87,7 +87,9 @@ impl VoteTestGen {
             BlockDate::from_epoch_slot_id(3, 0),
             VoteTestGen::proposals(3),
             vote::PayloadType::Public,
+            None,
             Vec::new(),
+            None,
             TokenIdentifier {
                 policy_hash: PolicyHash::from([0u8; POLICY_HASH_SIZE]),
                 token_name: TokenName::try_from(vec![0u8; TOKEN_NAME_MAX_SIZE]).unwrap(),
@@ -117,7 +119,9 @@ impl VoteTestGen {
             BlockDate::from_epoch_slot_id(3, 0),
             VoteTestGen::proposals(3),
             vote::PayloadType::Private,
+            None,
             manager.members().iter().map(|x| x.public_key()).collect(),
+            None,
             TokenIdentifier {
                 policy_hash: PolicyHash::from([0u8; POLICY_HASH_SIZE]),
                 token_name: TokenName::try_from(vec![0u8; TOKEN_NAME_MAX_SIZE]).unwrap(),
@@ -132,7 +136,9 @@ impl VoteTestGen {
             BlockDate::from_epoch_slot_id(3, 0),
             VoteTestGen::proposals(count),
             vote::PayloadType::Public,
+            None,
             Vec::new(),
+            None,
             TokenIdentifier {
                 policy_hash: PolicyHash::from([0u8; POLICY_HASH_SIZE]),
                 token_name: TokenName::try_from(vec![0u8; TOKEN_NAME_MAX_SIZE]).unwrap(),
@@ -150,8 +156,7 @@ impl VoteTestGen {
         choice: Choice,
         rng: &mut R,
     ) -> Payload {
-        let encrypting_key =
-            ElectionPublicKey::from_participants(vote_plan.committee_public_keys());
+        let encrypting_key = vote_plan.election_public_key();
 
         let crs = Crs::from_hash(vote_plan.to_id().as_ref());
         let (encrypted_vote, proof) = encrypting_key.encrypt_and_prove_vote(
@@ -170,6 +175,23 @@ impl VoteTestGen {
         }
     }
 
+    pub fn private_weighted_vote_cast_payload_for<R: RngCore + CryptoRng>(
+        vote_plan: &VotePlan,
+        weights: Vec<u8>,
+        rng: &mut R,
+    ) -> Payload {
+        let kind = *vote_plan
+            .weighted_ballot()
+            .expect("this is not a private weighted vote plan");
+        let vote = WeightedVote::new(kind, weights).unwrap();
+        let crs = Crs::from_hash(vote_plan.to_id().as_ref());
+        let (encrypted_vote, proof) =
+            vote::encrypt_weighted_vote(rng, &crs, &vote_plan.election_public_key(), &vote)
+                .unwrap();
+
+        Payload::private_weighted(encrypted_vote, proof)
+    }
+
     pub fn vote_cast_payload() -> vote::Payload {
         vote::Payload::public(vote::Choice::new(1))
     }
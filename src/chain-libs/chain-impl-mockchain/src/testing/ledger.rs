@@ -713,6 +713,12 @@ impl TestLedger {
 
     pub fn fast_forward_to(&mut self, date: BlockDate) {
         self.set_date(date);
+        // as done when a block starts after the end of a voting period
+        self.ledger.votes = self.ledger.votes.count_delegated_votes(
+            date,
+            self.ledger.token_distribution(),
+            &self.ledger.vote_delegations,
+        );
     }
 
     pub fn fire_leadership_event(
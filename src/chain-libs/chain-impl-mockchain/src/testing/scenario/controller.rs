@@ -4,8 +4,8 @@ use crate::certificate::EvmMapping;
 use crate::evm::EvmTransaction;
 use crate::{
     certificate::{
-        DecryptedPrivateTally, ExternalProposalId, MintToken, Proposal, UpdateProposal, UpdateVote,
-        VoteCast, VotePlan, VoteTally,
+        BurnToken, DecryptedPrivateTally, ExternalProposalId, MintToken, Proposal, UpdateProposal,
+        UpdateVote, VoteCast, VoteDelegation, VotePlan, VoteTally,
     },
     date::BlockDate,
     fee::LinearFee,
@@ -239,7 +239,9 @@ impl Controller {
             test_ledger,
             |vote_plan, _proposal| match vote_plan.payload_type() {
                 PayloadType::Public => Payload::Public { choice },
-                PayloadType::Private => panic!("this is a private vote plan"),
+                PayloadType::Private | PayloadType::PrivateWeighted => {
+                    panic!("this is a private vote plan")
+                }
             },
         )
     }
@@ -266,6 +268,39 @@ impl Controller {
                 PayloadType::Private => {
                     VoteTestGen::private_vote_cast_payload_for(vote_plan, proposal, choice, rng)
                 }
+                PayloadType::PrivateWeighted => panic!("this is a private weighted vote plan"),
+            },
+        )
+    }
+
+    pub fn cast_vote_private_weighted<R>(
+        &self,
+        owner: &Wallet,
+        vote_plan_def: &VotePlanDef,
+        id: &ExternalProposalId,
+        weights: Vec<u8>,
+        test_ledger: &mut TestLedger,
+        rng: &mut R,
+    ) -> Result<(), LedgerError>
+    where
+        R: RngCore + CryptoRng,
+    {
+        self.cast_vote(
+            owner,
+            vote_plan_def,
+            id,
+            test_ledger,
+            |vote_plan, _proposal| match vote_plan.payload_type() {
+                PayloadType::PrivateWeighted => {
+                    VoteTestGen::private_weighted_vote_cast_payload_for(
+                        vote_plan,
+                        weights.clone(),
+                        rng,
+                    )
+                }
+                PayloadType::Public | PayloadType::Private => {
+                    panic!("this is not a private weighted vote plan")
+                }
             },
         )
     }
@@ -368,6 +403,30 @@ impl Controller {
         test_ledger.apply_fragment(&fragment, test_ledger.date())
     }
 
+    pub fn burn_token(
+        &self,
+        owner: &Wallet,
+        burn_token: BurnToken,
+        test_ledger: &mut TestLedger,
+    ) -> Result<(), LedgerError> {
+        let fragment = self
+            .fragment_factory
+            .burn_token(test_ledger.date(), owner, burn_token);
+        test_ledger.apply_fragment(&fragment, test_ledger.date())
+    }
+
+    pub fn delegate_vote(
+        &self,
+        owner: &Wallet,
+        vote_delegation: VoteDelegation,
+        test_ledger: &mut TestLedger,
+    ) -> Result<(), LedgerError> {
+        let fragment =
+            self.fragment_factory
+                .vote_delegation(test_ledger.date(), owner, vote_delegation);
+        test_ledger.apply_fragment(&fragment, test_ledger.date())
+    }
+
     #[cfg(feature = "evm")]
     pub fn evm_mapping(
         &self,
@@ -5,8 +5,8 @@ use crate::evm::EvmTransaction;
 use crate::{
     accounting::account::{DelegationRatio, DelegationType},
     certificate::{
-        Certificate, MintToken, PoolId, PoolUpdate, UpdateProposal, UpdateVote, VoteCast, VotePlan,
-        VoteTally,
+        BurnToken, Certificate, MintToken, PoolId, PoolUpdate, UpdateProposal, UpdateVote,
+        VoteCast, VoteDelegation, VotePlan, VoteTally,
     },
     date::BlockDate,
     fee::LinearFee,
@@ -229,6 +229,24 @@ impl FragmentFactory {
         self.transaction_with_cert(valid_until, Some(owner), &min_token.into())
     }
 
+    pub fn burn_token(
+        &self,
+        valid_until: BlockDate,
+        owner: &Wallet,
+        burn_token: BurnToken,
+    ) -> Fragment {
+        self.transaction_with_cert(valid_until, Some(owner), &burn_token.into())
+    }
+
+    pub fn vote_delegation(
+        &self,
+        valid_until: BlockDate,
+        owner: &Wallet,
+        vote_delegation: VoteDelegation,
+    ) -> Fragment {
+        self.transaction_with_cert(valid_until, Some(owner), &vote_delegation.into())
+    }
+
     fn transaction_with_cert<'a>(
         &self,
         valid_until: BlockDate,
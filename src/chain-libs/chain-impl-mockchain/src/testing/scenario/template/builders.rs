@@ -16,10 +16,10 @@ use crate::{
     value::Value,
     vote::PayloadType,
 };
-use chain_vote::MemberPublicKey;
+use chain_vote::{MemberPublicKey, WeightedBallotKind};
 use std::{
     collections::{HashMap, HashSet},
-    num::NonZeroU64,
+    num::{NonZeroU64, NonZeroU8},
 };
 
 #[derive(Clone)]
@@ -258,10 +258,12 @@ pub struct VotePlanDefBuilder {
     alias: String,
     owner_alias: Option<String>,
     payload_type: PayloadType,
+    weighted_ballot: Option<WeightedBallotKind>,
     vote_date: Option<BlockDate>,
     tally_date: Option<BlockDate>,
     end_tally_date: Option<BlockDate>,
     committee_keys: Vec<MemberPublicKey>,
+    committee_threshold: Option<NonZeroU8>,
     proposals: Vec<ProposalDef>,
     voting_token: TokenIdentifier,
 }
@@ -272,10 +274,12 @@ impl VotePlanDefBuilder {
             alias: alias.to_owned(),
             owner_alias: Option::None,
             payload_type: PayloadType::Public,
+            weighted_ballot: Option::None,
             vote_date: Option::None,
             tally_date: Option::None,
             end_tally_date: Option::None,
             committee_keys: Vec::new(),
+            committee_threshold: Option::None,
             proposals: Vec::new(),
             voting_token: TokenIdentifier {
                 policy_hash: PolicyHash::from([0u8; POLICY_HASH_SIZE]),
@@ -294,11 +298,21 @@ impl VotePlanDefBuilder {
         self
     }
 
+    pub fn weighted_ballot(&mut self, weighted_ballot: Option<WeightedBallotKind>) -> &mut Self {
+        self.weighted_ballot = weighted_ballot;
+        self
+    }
+
     pub fn committee_keys(&mut self, committee_keys: Vec<MemberPublicKey>) -> &mut Self {
         self.committee_keys = committee_keys;
         self
     }
 
+    pub fn committee_threshold(&mut self, committee_threshold: Option<NonZeroU8>) -> &mut Self {
+        self.committee_threshold = committee_threshold;
+        self
+    }
+
     pub fn vote_phases(&mut self, start_epoch: u32, tally_epoch: u32, end_epoch: u32) -> &mut Self {
         self.vote_date = Some(BlockDate {
             epoch: start_epoch,
@@ -346,11 +360,13 @@ impl VotePlanDefBuilder {
             alias: self.alias.clone(),
             owner_alias: self.owner_alias.unwrap(),
             payload_type: self.payload_type,
+            weighted_ballot: self.weighted_ballot,
             vote_date: self.vote_date.unwrap(),
             tally_date: self.tally_date.unwrap(),
             end_tally_date: self.end_tally_date.unwrap(),
             proposals: self.proposals,
             committee_keys: self.committee_keys,
+            committee_threshold: self.committee_threshold,
             voting_token: self.voting_token,
         }
     }
@@ -407,6 +423,36 @@ impl ProposalDefBuilder {
         self
     }
 
+    pub fn action_update_fees(
+        &mut self,
+        constant: u64,
+        coefficient: u64,
+        certificate: u64,
+    ) -> &mut Self {
+        self.action_type = VoteAction::Parameters {
+            action: ParametersGovernanceAction::UpdateFees {
+                constant,
+                coefficient,
+                certificate,
+            },
+        };
+        self
+    }
+
+    pub fn action_slot_duration(&mut self, duration: u8) -> &mut Self {
+        self.action_type = VoteAction::Parameters {
+            action: ParametersGovernanceAction::SlotDuration { duration },
+        };
+        self
+    }
+
+    pub fn action_block_content_max_size(&mut self, size: u32) -> &mut Self {
+        self.action_type = VoteAction::Parameters {
+            action: ParametersGovernanceAction::BlockContentMaxSize { size },
+        };
+        self
+    }
+
     pub fn build(self) -> ProposalDef {
         ProposalDef {
             id: self.id,
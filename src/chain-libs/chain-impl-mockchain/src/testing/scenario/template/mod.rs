@@ -17,9 +17,10 @@ use crate::{
 pub use builders::*;
 use chain_addr::{Address, Discrimination, Kind};
 use chain_crypto::{Ed25519, PublicKey};
-use chain_vote::MemberPublicKey;
+use chain_vote::{MemberPublicKey, WeightedBallotKind};
 use std::collections::HashMap;
 use std::hash::{Hash, Hasher};
+use std::num::NonZeroU8;
 
 #[derive(Clone)]
 pub struct WalletTemplate {
@@ -138,10 +139,12 @@ pub struct VotePlanDef {
     alias: String,
     owner_alias: String,
     payload_type: PayloadType,
+    weighted_ballot: Option<WeightedBallotKind>,
     vote_date: BlockDate,
     tally_date: BlockDate,
     end_tally_date: BlockDate,
     committee_keys: Vec<MemberPublicKey>,
+    committee_threshold: Option<NonZeroU8>,
     proposals: Vec<ProposalDef>,
     voting_token: TokenIdentifier,
 }
@@ -189,7 +192,9 @@ impl VotePlanDef {
 
         builder
             .payload_type(vote_plan.payload_type())
+            .weighted_ballot(vote_plan.weighted_ballot().copied())
             .committee_keys(vote_plan.committee_public_keys().to_vec())
+            .committee_threshold(vote_plan.committee_threshold())
             .vote_phases(
                 vote_plan.vote_start().epoch,
                 vote_plan.committee_start().epoch,
@@ -227,6 +232,19 @@ impl VotePlanDef {
                     ParametersGovernanceAction::NoOp => {
                         proposal_builder.action_parameters_no_op();
                     }
+                    ParametersGovernanceAction::UpdateFees {
+                        constant,
+                        coefficient,
+                        certificate,
+                    } => {
+                        proposal_builder.action_update_fees(*constant, *coefficient, *certificate);
+                    }
+                    ParametersGovernanceAction::SlotDuration { duration } => {
+                        proposal_builder.action_slot_duration(*duration);
+                    }
+                    ParametersGovernanceAction::BlockContentMaxSize { size } => {
+                        proposal_builder.action_block_content_max_size(*size);
+                    }
                 },
             };
 
@@ -251,7 +269,9 @@ impl From<VotePlanDef> for VotePlan {
             dto.end_tally_date,
             proposals,
             dto.payload_type,
+            dto.weighted_ballot,
             dto.committee_keys,
+            dto.committee_threshold,
             dto.voting_token,
         )
     }
@@ -1,18 +1,28 @@
-use crate::tokens::policy_hash::{PolicyHash, POLICY_HASH_SIZE};
+use crate::{
+    account,
+    date::BlockDate,
+    multisig,
+    tokens::policy_hash::{PolicyHash, POLICY_HASH_SIZE},
+    value::Value,
+};
 #[cfg(any(test, feature = "property-test-api"))]
 use proptest::prelude::*;
 
 use chain_core::{
     packer::Codec,
-    property::{Deserialize, ReadError, Serialize, WriteError},
+    property::{Deserialize, DeserializeFromSlice, ReadError, Serialize, WriteError},
 };
 use cryptoxide::{blake2b::Blake2b, digest::Digest};
 use thiserror::Error;
 use typed_bytes::ByteBuilder;
 
 /// A minting policy consists of multiple entries defining different
-/// constraints on the minting process. An empty policy means that new tokens
-/// cannot be minted during the chain run.
+/// constraints on the minting process. A policy without any signature entry,
+/// including the empty policy, means that new tokens cannot be minted during
+/// the chain run.
+///
+/// The signature entries of the policy also authorize burning the token, and
+/// a policy without any of them means that the token cannot be burnt.
 ///
 /// Minting policies are meant to be ignored in block0 fragments.
 #[derive(Debug, Clone, PartialEq, Eq)]
@@ -23,21 +33,172 @@ use typed_bytes::ByteBuilder;
 pub struct MintingPolicy(
     #[cfg_attr(
         any(test, feature = "property-test-api"),
-        strategy(Just(vec![]))
+        strategy(proptest::collection::vec(any::<MintingPolicyEntry>(), 0..4))
     )]
     Vec<MintingPolicyEntry>,
 );
 
-/// An entry of a minting policy. Currently there are no entries available.
-/// This is reserved for the future use.
+/// An entry of a minting policy. Every entry of the policy of a token must be
+/// satisfied by a transaction minting the token.
 #[derive(Debug, Clone, PartialEq, Eq)]
-pub enum MintingPolicyEntry {}
+#[cfg_attr(
+    any(test, feature = "property-test-api"),
+    derive(test_strategy::Arbitrary)
+)]
+pub enum MintingPolicyEntry {
+    /// The minting and burning transactions must be signed by the given account.
+    Account(account::Identifier),
+    /// The minting and burning transactions must be signed by the given
+    /// multisig account.
+    Multisig(multisig::Identifier),
+    /// New tokens can only be minted strictly before the given date.
+    TimeLock(
+        #[cfg_attr(
+            any(test, feature = "property-test-api"),
+            strategy(any::<(u32, u32)>().prop_map(|(epoch, slot_id)| BlockDate { epoch, slot_id }))
+        )]
+        BlockDate,
+    ),
+    /// The total supply of the token can never be more than the given value.
+    MaxSupply(Value),
+}
+
+const ENTRY_TAG_ACCOUNT: u8 = 1;
+const ENTRY_TAG_MULTISIG: u8 = 2;
+const ENTRY_TAG_TIME_LOCK: u8 = 3;
+const ENTRY_TAG_MAX_SUPPLY: u8 = 4;
+
+/// The minting transaction and the state of the chain a minting policy is
+/// checked against.
+#[derive(Debug, Clone)]
+pub struct MintingContext<'a> {
+    /// the date of the block the minting transaction is included in
+    pub date: BlockDate,
+    /// the accounts that signed the inputs of the minting transaction
+    pub accounts: &'a [account::Identifier],
+    /// the multisig accounts that signed the inputs of the minting transaction
+    pub multisig_accounts: &'a [multisig::Identifier],
+    /// the total supply of the token once the minting transaction is applied
+    pub supply: Value,
+}
 
 /// Error while checking a minting transaction against the current system state.
 #[derive(Debug, Clone, PartialEq, Eq, Error)]
 pub enum MintingPolicyViolation {
     #[error("the policy of this token does not allow minting")]
     AdditionalMintingNotAllowed,
+    #[error("the policy of this token does not allow burning")]
+    BurningNotAllowed,
+    #[error("the transaction is not signed by the account {0}")]
+    AccountSignatureMissing(account::Identifier),
+    #[error("the transaction is not signed by the multisig account {0}")]
+    MultisigSignatureMissing(multisig::Identifier),
+    #[error("the minting of this token is locked since {0}")]
+    TimeLocked(BlockDate),
+    #[error("minting would bring the supply of the token to {supply}, above the maximum of {max}")]
+    MaxSupplyExceeded { supply: Value, max: Value },
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Error)]
+#[error(
+    "a minting policy can have at most {} entries",
+    MintingPolicy::MAX_ENTRIES
+)]
+pub struct TooManyEntries;
+
+impl MintingPolicyEntry {
+    fn check_minting_tx(&self, context: &MintingContext) -> Result<(), MintingPolicyViolation> {
+        match self {
+            MintingPolicyEntry::Account(id) => {
+                if !context.accounts.contains(id) {
+                    return Err(MintingPolicyViolation::AccountSignatureMissing(id.clone()));
+                }
+            }
+            MintingPolicyEntry::Multisig(id) => {
+                if !context.multisig_accounts.contains(id) {
+                    return Err(MintingPolicyViolation::MultisigSignatureMissing(id.clone()));
+                }
+            }
+            MintingPolicyEntry::TimeLock(date) => {
+                if context.date >= *date {
+                    return Err(MintingPolicyViolation::TimeLocked(*date));
+                }
+            }
+            MintingPolicyEntry::MaxSupply(max) => {
+                if context.supply > *max {
+                    return Err(MintingPolicyViolation::MaxSupplyExceeded {
+                        supply: context.supply,
+                        max: *max,
+                    });
+                }
+            }
+        }
+        Ok(())
+    }
+
+    fn serialize_in(&self, bb: ByteBuilder<MintingPolicy>) -> ByteBuilder<MintingPolicy> {
+        match self {
+            MintingPolicyEntry::Account(id) => bb.u8(ENTRY_TAG_ACCOUNT).bytes(id.as_ref().as_ref()),
+            MintingPolicyEntry::Multisig(id) => bb.u8(ENTRY_TAG_MULTISIG).bytes(id.as_ref()),
+            MintingPolicyEntry::TimeLock(date) => {
+                bb.u8(ENTRY_TAG_TIME_LOCK).u32(date.epoch).u32(date.slot_id)
+            }
+            MintingPolicyEntry::MaxSupply(value) => {
+                bb.u8(ENTRY_TAG_MAX_SUPPLY).bytes(&value.bytes())
+            }
+        }
+    }
+
+    fn serialized_size(&self) -> usize {
+        Codec::u8_size()
+            + match self {
+                MintingPolicyEntry::Account(id) => id.serialized_size(),
+                MintingPolicyEntry::Multisig(id) => id.as_ref().len(),
+                MintingPolicyEntry::TimeLock(_) => 2 * Codec::u32_size(),
+                MintingPolicyEntry::MaxSupply(value) => value.serialized_size(),
+            }
+    }
+
+    fn serialize<W: std::io::Write>(&self, codec: &mut Codec<W>) -> Result<(), WriteError> {
+        match self {
+            MintingPolicyEntry::Account(id) => {
+                codec.put_u8(ENTRY_TAG_ACCOUNT)?;
+                id.serialize(codec)
+            }
+            MintingPolicyEntry::Multisig(id) => {
+                codec.put_u8(ENTRY_TAG_MULTISIG)?;
+                codec.put_bytes(id.as_ref())
+            }
+            MintingPolicyEntry::TimeLock(date) => {
+                codec.put_u8(ENTRY_TAG_TIME_LOCK)?;
+                codec.put_be_u32(date.epoch)?;
+                codec.put_be_u32(date.slot_id)
+            }
+            MintingPolicyEntry::MaxSupply(value) => {
+                codec.put_u8(ENTRY_TAG_MAX_SUPPLY)?;
+                value.serialize(codec)
+            }
+        }
+    }
+
+    fn deserialize_from_slice(codec: &mut Codec<&[u8]>) -> Result<Self, ReadError> {
+        match codec.get_u8()? {
+            ENTRY_TAG_ACCOUNT => {
+                account::Identifier::deserialize_from_slice(codec).map(MintingPolicyEntry::Account)
+            }
+            ENTRY_TAG_MULTISIG => {
+                let mut id = [0u8; 32];
+                codec.copy_to_slice(&mut id)?;
+                Ok(MintingPolicyEntry::Multisig(id.into()))
+            }
+            ENTRY_TAG_TIME_LOCK => Ok(MintingPolicyEntry::TimeLock(BlockDate {
+                epoch: codec.get_be_u32()?,
+                slot_id: codec.get_be_u32()?,
+            })),
+            ENTRY_TAG_MAX_SUPPLY => Value::deserialize(codec).map(MintingPolicyEntry::MaxSupply),
+            tag => Err(ReadError::UnknownTag(tag as u32)),
+        }
+    }
 }
 
 impl MintingPolicy {
@@ -45,25 +206,83 @@ impl MintingPolicy {
         Self(Vec::new())
     }
 
-    pub fn check_minting_tx(&self) -> Result<(), MintingPolicyViolation> {
-        if self.0.is_empty() {
+    /// the number of entries is serialized on a single byte
+    pub const MAX_ENTRIES: usize = u8::MAX as usize;
+
+    /// Add an entry to the policy. A policy holds at most
+    /// [`Self::MAX_ENTRIES`] entries, and the order of the entries is part of
+    /// the policy hash.
+    pub fn with_entry(mut self, entry: MintingPolicyEntry) -> Result<Self, TooManyEntries> {
+        if self.0.len() >= Self::MAX_ENTRIES {
+            return Err(TooManyEntries);
+        }
+        self.0.push(entry);
+        Ok(self)
+    }
+
+    pub fn check_minting_tx(&self, context: &MintingContext) -> Result<(), MintingPolicyViolation> {
+        if !self.has_signature_entry() {
             return Err(MintingPolicyViolation::AdditionalMintingNotAllowed);
         }
 
-        for _entry in &self.0 {
-            unreachable!("implement this when we have actual minting policies");
+        for entry in &self.0 {
+            entry.check_minting_tx(context)?;
         }
 
         Ok(())
     }
 
+    /// Check a transaction burning the token, signed by the given accounts.
+    /// Only the signature entries of the policy apply to burning.
+    pub fn check_burning_tx(
+        &self,
+        accounts: &[account::Identifier],
+        multisig_accounts: &[multisig::Identifier],
+    ) -> Result<(), MintingPolicyViolation> {
+        let mut authorized = false;
+
+        for entry in &self.0 {
+            match entry {
+                MintingPolicyEntry::Account(id) => {
+                    if !accounts.contains(id) {
+                        return Err(MintingPolicyViolation::AccountSignatureMissing(id.clone()));
+                    }
+                    authorized = true;
+                }
+                MintingPolicyEntry::Multisig(id) => {
+                    if !multisig_accounts.contains(id) {
+                        return Err(MintingPolicyViolation::MultisigSignatureMissing(id.clone()));
+                    }
+                    authorized = true;
+                }
+                MintingPolicyEntry::TimeLock(_) | MintingPolicyEntry::MaxSupply(_) => {}
+            }
+        }
+
+        if authorized {
+            Ok(())
+        } else {
+            Err(MintingPolicyViolation::BurningNotAllowed)
+        }
+    }
+
     pub fn entries(&self) -> &[MintingPolicyEntry] {
         &self.0
     }
 
+    fn has_signature_entry(&self) -> bool {
+        self.0.iter().any(|entry| {
+            matches!(
+                entry,
+                MintingPolicyEntry::Account(_) | MintingPolicyEntry::Multisig(_)
+            )
+        })
+    }
+
     pub fn bytes(&self) -> Vec<u8> {
         let bb: ByteBuilder<Self> = ByteBuilder::new();
-        bb.u8(0).finalize_as_vec()
+        bb.iter8(&self.0, |bb, entry| entry.serialize_in(bb))
+            .finalize_as_vec()
     }
 
     pub fn hash(&self) -> PolicyHash {
@@ -86,23 +305,29 @@ impl Default for MintingPolicy {
 impl Serialize for MintingPolicy {
     fn serialized_size(&self) -> usize {
         Codec::u8_size()
+            + self
+                .0
+                .iter()
+                .map(MintingPolicyEntry::serialized_size)
+                .sum::<usize>()
     }
 
     fn serialize<W: std::io::Write>(&self, codec: &mut Codec<W>) -> Result<(), WriteError> {
-        codec.put_u8(0_u8)
+        codec.put_u8(self.0.len() as u8)?;
+        for entry in &self.0 {
+            entry.serialize(codec)?;
+        }
+        Ok(())
     }
 }
 
-impl Deserialize for MintingPolicy {
-    fn deserialize<R: std::io::Read>(codec: &mut Codec<R>) -> Result<Self, ReadError> {
+impl DeserializeFromSlice for MintingPolicy {
+    fn deserialize_from_slice(codec: &mut Codec<&[u8]>) -> Result<Self, ReadError> {
         let no_entries = codec.get_u8()?;
-        if no_entries != 0 {
-            return Err(ReadError::InvalidData(
-                "non-zero number of minting policy entries, but they are currently unimplemented"
-                    .to_string(),
-            ));
-        }
-        Ok(Self::new())
+        let entries = (0..no_entries)
+            .map(|_| MintingPolicyEntry::deserialize_from_slice(codec))
+            .collect::<Result<_, _>>()?;
+        Ok(Self(entries))
     }
 }
 
@@ -114,10 +339,28 @@ mod tests {
     #[cfg(test)]
     use quickcheck::TestResult;
     use quickcheck::{Arbitrary, Gen};
+    #[cfg(test)]
+    use test_strategy::proptest;
+
+    impl Arbitrary for MintingPolicyEntry {
+        fn arbitrary<G: Gen>(g: &mut G) -> Self {
+            match u8::arbitrary(g) % 4 {
+                0 => MintingPolicyEntry::Account(Arbitrary::arbitrary(g)),
+                1 => MintingPolicyEntry::Multisig(Arbitrary::arbitrary(g)),
+                2 => MintingPolicyEntry::TimeLock(Arbitrary::arbitrary(g)),
+                _ => MintingPolicyEntry::MaxSupply(Arbitrary::arbitrary(g)),
+            }
+        }
+    }
 
     impl Arbitrary for MintingPolicy {
-        fn arbitrary<G: Gen>(_g: &mut G) -> Self {
-            Self::new()
+        fn arbitrary<G: Gen>(g: &mut G) -> Self {
+            let len = usize::arbitrary(g) % 4;
+            Self(
+                std::iter::repeat_with(|| MintingPolicyEntry::arbitrary(g))
+                    .take(len)
+                    .collect(),
+            )
         }
     }
 
@@ -126,4 +369,107 @@ mod tests {
             serialization_bijection(policy)
         }
     }
+
+    #[cfg(test)]
+    #[proptest]
+    fn minting_policy_bytes_match_serialization(policy: MintingPolicy) {
+        prop_assert_eq!(policy.bytes(), policy.serialize_as_vec().unwrap());
+    }
+
+    #[cfg(test)]
+    #[proptest]
+    fn empty_policy_never_allows_minting(epoch: u32, slot_id: u32, supply: Value) {
+        let context = MintingContext {
+            date: BlockDate { epoch, slot_id },
+            accounts: &[],
+            multisig_accounts: &[],
+            supply,
+        };
+        prop_assert_eq!(
+            MintingPolicy::new().check_minting_tx(&context),
+            Err(MintingPolicyViolation::AdditionalMintingNotAllowed)
+        );
+    }
+
+    #[cfg(test)]
+    #[proptest]
+    fn max_supply_is_enforced(account: account::Identifier, max: Value, supply: Value) {
+        let policy = MintingPolicy::new()
+            .with_entry(MintingPolicyEntry::Account(account.clone()))
+            .unwrap()
+            .with_entry(MintingPolicyEntry::MaxSupply(max))
+            .unwrap();
+        let context = MintingContext {
+            date: BlockDate::first(),
+            accounts: &[account],
+            multisig_accounts: &[],
+            supply,
+        };
+        prop_assert_eq!(policy.check_minting_tx(&context).is_ok(), supply <= max);
+    }
+
+    #[cfg(test)]
+    #[proptest]
+    fn burning_requires_a_signature_entry(epoch: u32, slot_id: u32, max: Value) {
+        let policy = MintingPolicy::new()
+            .with_entry(MintingPolicyEntry::TimeLock(BlockDate { epoch, slot_id }))
+            .unwrap()
+            .with_entry(MintingPolicyEntry::MaxSupply(max))
+            .unwrap();
+        prop_assert_eq!(
+            policy.check_burning_tx(&[], &[]),
+            Err(MintingPolicyViolation::BurningNotAllowed)
+        );
+    }
+
+    #[cfg(test)]
+    #[proptest]
+    fn minting_requires_a_signature_entry(epoch: u32, slot_id: u32, max: Value) {
+        let policy = MintingPolicy::new()
+            .with_entry(MintingPolicyEntry::TimeLock(
+                BlockDate::first().next_epoch(),
+            ))
+            .unwrap()
+            .with_entry(MintingPolicyEntry::MaxSupply(max))
+            .unwrap();
+        let context = MintingContext {
+            date: BlockDate { epoch, slot_id },
+            accounts: &[],
+            multisig_accounts: &[],
+            supply: Value::zero(),
+        };
+        prop_assert_eq!(
+            policy.check_minting_tx(&context),
+            Err(MintingPolicyViolation::AdditionalMintingNotAllowed)
+        );
+    }
+
+    #[cfg(test)]
+    #[test]
+    fn policy_entries_are_limited() {
+        let entry = MintingPolicyEntry::MaxSupply(Value(1));
+        let policy = (0..MintingPolicy::MAX_ENTRIES)
+            .try_fold(MintingPolicy::new(), |policy, _| {
+                policy.with_entry(entry.clone())
+            })
+            .unwrap();
+        assert_eq!(policy.with_entry(entry), Err(TooManyEntries));
+    }
+
+    #[cfg(test)]
+    #[proptest]
+    fn burning_is_authorized_by_the_policy_account(
+        account: account::Identifier,
+        other: account::Identifier,
+    ) {
+        let policy = MintingPolicy::new()
+            .with_entry(MintingPolicyEntry::Account(account.clone()))
+            .unwrap();
+        prop_assert!(policy.check_burning_tx(&[account.clone()], &[]).is_ok());
+        prop_assume!(account != other);
+        prop_assert_eq!(
+            policy.check_burning_tx(&[other], &[]),
+            Err(MintingPolicyViolation::AccountSignatureMissing(account))
+        );
+    }
 }
@@ -141,6 +141,16 @@ impl Default for UpdateState {
     }
 }
 
+impl FromIterator<(UpdateProposalId, UpdateProposalState)> for UpdateState {
+    fn from_iter<I: IntoIterator<Item = (UpdateProposalId, UpdateProposalState)>>(
+        iter: I,
+    ) -> Self {
+        UpdateState {
+            proposals: iter.into_iter().collect(),
+        }
+    }
+}
+
 #[derive(Clone, Debug, PartialEq, Eq)]
 pub struct UpdateProposalState {
     pub proposal: UpdateProposal,
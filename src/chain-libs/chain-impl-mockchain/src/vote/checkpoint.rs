@@ -0,0 +1,331 @@
+//! compact snapshot of the votes recorded by a vote plan
+//!
+//! A [`VotePlanCheckpoint`] holds, for every proposal of a vote plan, the
+//! accounts that already voted with their ballots and the incremental tally (the
+//! encrypted tally for private vote plans). Restoring a checkpoint on a ledger
+//! that holds the same vote plan allows to carry on applying the remaining vote
+//! casts and tallies without replaying the previous ones.
+//!
+//! The votes are kept so the voting power delegated to the voters can still
+//! be counted at the end of the voting period, the vote delegations themselves
+//! are part of the ledger and not of the checkpoint. The private votes are
+//! kept with their proofs, which are verified again when the checkpoint is
+//! restored. Once the delegated voting power is counted only the voters are
+//! kept.
+
+use crate::{
+    account,
+    certificate::{Proposal, VotePlanId},
+    vote::{manager::IncrementalTally, Payload, TallyResult, Weight},
+};
+use chain_core::{
+    packer::Codec,
+    property::{Deserialize, DeserializeFromSlice, ReadError, Serialize, WriteError},
+};
+use chain_vote::EncryptedTally;
+use thiserror::Error;
+
+/// the state of the votes of all the proposals of a vote plan
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct VotePlanCheckpoint {
+    id: VotePlanId,
+    proposals: Vec<ProposalCheckpoint>,
+    delegated_votes_counted: bool,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) struct ProposalCheckpoint {
+    pub(crate) voters: Vec<account::Identifier>,
+    /// the votes of the voters, until the delegated voting power is counted
+    pub(crate) ballots: Vec<(account::Identifier, Payload)>,
+    pub(crate) tally: TallyCheckpoint,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) enum TallyCheckpoint {
+    Public(Vec<Weight>),
+    Private(EncryptedTally),
+    Decrypted(Vec<Weight>),
+}
+
+#[derive(Debug, Error, Clone, PartialEq, Eq)]
+pub enum CheckpointError {
+    #[error("checkpoint is for vote plan {actual}, expected {expected}")]
+    InvalidVotePlan {
+        expected: VotePlanId,
+        actual: VotePlanId,
+    },
+
+    #[error("checkpoint has {actual} proposals, expected {expected}")]
+    InvalidProposalsCount { expected: usize, actual: usize },
+
+    #[error("checkpoint tally of proposal {index} does not match the vote plan payload type")]
+    InvalidTallyKind { index: usize },
+
+    #[error("checkpoint tally of proposal {index} does not match the proposal options")]
+    InvalidTallyOptions { index: usize },
+
+    #[error("checkpoint has an invalid vote for proposal {index}")]
+    InvalidBallot { index: usize },
+}
+
+const TALLY_PUBLIC: u8 = 0;
+const TALLY_PRIVATE: u8 = 1;
+const TALLY_DECRYPTED: u8 = 2;
+
+impl VotePlanCheckpoint {
+    pub(crate) fn new(
+        id: VotePlanId,
+        proposals: Vec<ProposalCheckpoint>,
+        delegated_votes_counted: bool,
+    ) -> Self {
+        Self {
+            id,
+            proposals,
+            delegated_votes_counted,
+        }
+    }
+
+    pub fn id(&self) -> &VotePlanId {
+        &self.id
+    }
+
+    /// number of votes recorded in the checkpoint, over all the proposals
+    pub fn votes_count(&self) -> usize {
+        self.proposals.iter().map(|p| p.voters.len()).sum()
+    }
+
+    /// whether the voting power delegated to the voters was already added to
+    /// the tallies when the checkpoint was taken
+    pub fn delegated_votes_counted(&self) -> bool {
+        self.delegated_votes_counted
+    }
+
+    pub(crate) fn proposals(&self) -> &[ProposalCheckpoint] {
+        &self.proposals
+    }
+}
+
+impl TallyCheckpoint {
+    pub(crate) fn from_incremental(tally: &IncrementalTally) -> Self {
+        match tally {
+            IncrementalTally::Public(result) => Self::Public(result.results().to_vec()),
+            IncrementalTally::Private(encrypted_tally) => Self::Private(encrypted_tally.clone()),
+            IncrementalTally::Decrypted(result) => Self::Decrypted(result.results().to_vec()),
+        }
+    }
+
+    /// rebuild the incremental tally of the given proposal, checking it
+    /// matches the kind of tally currently held for this proposal
+    pub(crate) fn to_incremental(
+        &self,
+        index: usize,
+        proposal: &Proposal,
+        current: &IncrementalTally,
+    ) -> Result<IncrementalTally, CheckpointError> {
+        let result = |weights: &[Weight]| {
+            TallyResult::from_weights(proposal.options().clone(), weights.to_vec())
+                .ok_or(CheckpointError::InvalidTallyOptions { index })
+        };
+
+        match (self, current) {
+            (Self::Public(weights), IncrementalTally::Public(_)) => {
+                result(weights).map(IncrementalTally::Public)
+            }
+            (
+                Self::Private(encrypted_tally),
+                IncrementalTally::Private(_) | IncrementalTally::Decrypted(_),
+            ) => Ok(IncrementalTally::Private(encrypted_tally.clone())),
+            (
+                Self::Decrypted(weights),
+                IncrementalTally::Private(_) | IncrementalTally::Decrypted(_),
+            ) => result(weights).map(IncrementalTally::Decrypted),
+            _ => Err(CheckpointError::InvalidTallyKind { index }),
+        }
+    }
+}
+
+/* Ser/De ******************************************************************* */
+
+fn serialize_weights<W: std::io::Write>(
+    weights: &[Weight],
+    codec: &mut Codec<W>,
+) -> Result<(), WriteError> {
+    codec.put_u8(weights.len() as u8)?;
+    for weight in weights {
+        codec.put_be_u64((*weight).into())?;
+    }
+    Ok(())
+}
+
+fn deserialize_weights(codec: &mut Codec<&[u8]>) -> Result<Vec<Weight>, ReadError> {
+    let len = codec.get_u8()?;
+    (0..len)
+        .map(|_| codec.get_be_u64().map(Weight::from))
+        .collect()
+}
+
+impl Serialize for VotePlanCheckpoint {
+    fn serialized_size(&self) -> usize {
+        let proposals: usize = self
+            .proposals
+            .iter()
+            .map(|proposal| {
+                let voters = Codec::u32_size()
+                    + proposal
+                        .voters
+                        .iter()
+                        .map(|voter| voter.serialized_size())
+                        .sum::<usize>();
+                let ballots = Codec::u32_size()
+                    + proposal
+                        .ballots
+                        .iter()
+                        .map(|(voter, ballot)| voter.serialized_size() + ballot.serialized_size())
+                        .sum::<usize>();
+                let tally = match &proposal.tally {
+                    TallyCheckpoint::Public(weights) | TallyCheckpoint::Decrypted(weights) => {
+                        Codec::u8_size() + weights.len() * Codec::u64_size()
+                    }
+                    TallyCheckpoint::Private(encrypted_tally) => {
+                        Codec::u32_size() + encrypted_tally.to_bytes().len()
+                    }
+                };
+                voters + ballots + Codec::u8_size() + tally
+            })
+            .sum();
+        self.id.as_ref().len() + Codec::u8_size() + proposals + Codec::u8_size()
+    }
+
+    fn serialize<W: std::io::Write>(&self, codec: &mut Codec<W>) -> Result<(), WriteError> {
+        codec.put_bytes(self.id.as_ref())?;
+        codec.put_u8(self.proposals.len() as u8)?;
+        for proposal in &self.proposals {
+            codec.put_be_u32(proposal.voters.len() as u32)?;
+            for voter in &proposal.voters {
+                voter.serialize(codec)?;
+            }
+            codec.put_be_u32(proposal.ballots.len() as u32)?;
+            for (voter, ballot) in &proposal.ballots {
+                voter.serialize(codec)?;
+                ballot.serialize(codec)?;
+            }
+            match &proposal.tally {
+                TallyCheckpoint::Public(weights) => {
+                    codec.put_u8(TALLY_PUBLIC)?;
+                    serialize_weights(weights, codec)?;
+                }
+                TallyCheckpoint::Private(encrypted_tally) => {
+                    codec.put_u8(TALLY_PRIVATE)?;
+                    let bytes = encrypted_tally.to_bytes();
+                    codec.put_be_u32(bytes.len() as u32)?;
+                    codec.put_bytes(&bytes)?;
+                }
+                TallyCheckpoint::Decrypted(weights) => {
+                    codec.put_u8(TALLY_DECRYPTED)?;
+                    serialize_weights(weights, codec)?;
+                }
+            }
+        }
+        codec.put_u8(self.delegated_votes_counted as u8)?;
+        Ok(())
+    }
+}
+
+impl DeserializeFromSlice for VotePlanCheckpoint {
+    fn deserialize_from_slice(codec: &mut Codec<&[u8]>) -> Result<Self, ReadError> {
+        let id = <[u8; 32]>::deserialize(codec)?.into();
+        let proposals_count = codec.get_u8()?;
+        let mut proposals = Vec::with_capacity(proposals_count as usize);
+        for _ in 0..proposals_count {
+            let voters_count = codec.get_be_u32()?;
+            let voters = (0..voters_count)
+                .map(|_| account::Identifier::deserialize_from_slice(codec))
+                .collect::<Result<Vec<_>, ReadError>>()?;
+            let ballots_count = codec.get_be_u32()?;
+            let ballots = (0..ballots_count)
+                .map(|_| {
+                    let voter = account::Identifier::deserialize_from_slice(codec)?;
+                    Ok((voter, Payload::deserialize_from_slice(codec)?))
+                })
+                .collect::<Result<Vec<_>, ReadError>>()?;
+            let tally = match codec.get_u8()? {
+                TALLY_PUBLIC => TallyCheckpoint::Public(deserialize_weights(codec)?),
+                TALLY_PRIVATE => {
+                    let len = codec.get_be_u32()? as usize;
+                    let bytes = codec.get_slice(len)?;
+                    let encrypted_tally = EncryptedTally::from_bytes(bytes).ok_or_else(|| {
+                        ReadError::StructureInvalid("invalid encrypted tally".to_string())
+                    })?;
+                    TallyCheckpoint::Private(encrypted_tally)
+                }
+                TALLY_DECRYPTED => TallyCheckpoint::Decrypted(deserialize_weights(codec)?),
+                t => return Err(ReadError::UnknownTag(t as u32)),
+            };
+            proposals.push(ProposalCheckpoint {
+                voters,
+                ballots,
+                tally,
+            });
+        }
+        let delegated_votes_counted = codec.get_u8()? != 0;
+        Ok(Self {
+            id,
+            proposals,
+            delegated_votes_counted,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::testing::{serialization::serialization_bijection, TestGen};
+    use crate::vote::Choice;
+    use quickcheck::{Arbitrary, Gen, TestResult};
+    use quickcheck_macros::quickcheck;
+
+    impl Arbitrary for VotePlanCheckpoint {
+        fn arbitrary<G: Gen>(g: &mut G) -> Self {
+            let proposals_count = u8::arbitrary(g) % 4;
+            let proposals = (0..proposals_count)
+                .map(|_| {
+                    let voters: Vec<_> = (0..u8::arbitrary(g) % 4)
+                        .map(|_| TestGen::identifier())
+                        .collect();
+                    let ballots = voters
+                        .iter()
+                        .filter(|_| bool::arbitrary(g))
+                        .map(|voter| {
+                            let choice = Choice::new(u8::arbitrary(g));
+                            (voter.clone(), Payload::public(choice))
+                        })
+                        .collect();
+                    let weights = (0..u8::arbitrary(g) % 4)
+                        .map(|_| Weight::from(u64::arbitrary(g)))
+                        .collect();
+                    let tally = if bool::arbitrary(g) {
+                        TallyCheckpoint::Public(weights)
+                    } else {
+                        TallyCheckpoint::Decrypted(weights)
+                    };
+                    ProposalCheckpoint {
+                        voters,
+                        ballots,
+                        tally,
+                    }
+                })
+                .collect();
+            Self {
+                id: TestGen::bytes().into(),
+                proposals,
+                delegated_votes_counted: bool::arbitrary(g),
+            }
+        }
+    }
+
+    #[quickcheck]
+    fn vote_plan_checkpoint_serialization_bijection(checkpoint: VotePlanCheckpoint) -> TestResult {
+        serialization_bijection(checkpoint)
+    }
+}
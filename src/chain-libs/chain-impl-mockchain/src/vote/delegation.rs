@@ -0,0 +1,385 @@
+//! delegation of the voting power of an account to representatives
+//!
+//! An account can delegate its voting power, for all the vote plans using a
+//! given voting token or for a single vote plan, to one or more
+//! representatives. Once the voting period of a vote plan is over, the voting
+//! power of the accounts delegating to a representative that voted on a
+//! proposal is added to its vote, unless the delegator voted on this proposal
+//! itself: a direct vote always overrides the delegation.
+
+use crate::{
+    account,
+    accounting::account::DELEGATION_RATIO_MAX_DECLS,
+    certificate::{VotePlan, VotePlanId},
+    tokens::identifier::TokenIdentifier,
+};
+use imhamt::Hamt;
+use std::collections::hash_map::DefaultHasher;
+
+/// the vote plans a vote delegation applies to
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub enum VoteDelegationScope {
+    /// all the vote plans using the given voting token
+    Token(TokenIdentifier),
+    /// only the given vote plan, takes precedence over a delegation for the
+    /// voting token of the vote plan
+    VotePlan(VotePlanId),
+}
+
+/// the representatives the voting power of an account is delegated to
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum VoteDelegationType {
+    /// remove any previous delegation for the scope
+    NonDelegated,
+    Full(account::Identifier),
+    Ratio(VoteDelegationRatio),
+}
+
+/// Delegation of the voting power split between several representatives
+///
+/// Each representative receives `part / parts` of the voting power of the
+/// delegator.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct VoteDelegationRatio {
+    parts: u8,
+    representatives: Box<[(account::Identifier, u8)]>,
+}
+
+impl VoteDelegationRatio {
+    pub fn new(parts: u8, representatives: Vec<(account::Identifier, u8)>) -> Option<Self> {
+        let total: u32 = representatives.iter().map(|x| x.1 as u32).sum();
+        let has_no_zero = !representatives.iter().any(|x| x.1 == 0);
+        let has_no_duplicate =
+            representatives
+                .iter()
+                .enumerate()
+                .all(|(i, (representative, _))| {
+                    !representatives[..i]
+                        .iter()
+                        .any(|(r, _)| r == representative)
+                });
+        if has_no_zero
+            && has_no_duplicate
+            && parts > 1
+            && representatives.len() > 1
+            && representatives.len() <= DELEGATION_RATIO_MAX_DECLS
+            && total == (parts as u32)
+        {
+            Some(Self {
+                parts,
+                representatives: representatives.into(),
+            })
+        } else {
+            None
+        }
+    }
+
+    pub fn parts(&self) -> u8 {
+        self.parts
+    }
+
+    pub fn representatives(&self) -> &[(account::Identifier, u8)] {
+        &self.representatives
+    }
+}
+
+impl VoteDelegationType {
+    /// the representatives with the share (`part`, `parts`) of the voting
+    /// power they receive
+    pub fn representatives(&self) -> Vec<(&account::Identifier, u8, u8)> {
+        match self {
+            Self::NonDelegated => Vec::new(),
+            Self::Full(representative) => vec![(representative, 1, 1)],
+            Self::Ratio(ratio) => ratio
+                .representatives()
+                .iter()
+                .map(|(representative, part)| (representative, *part, ratio.parts()))
+                .collect(),
+        }
+    }
+}
+
+/// share of the voting power of a delegator received by a representative
+///
+/// `offset` is the sum of the parts of the representatives listed before this
+/// one in the delegation.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) struct DelegatedShare {
+    pub(crate) offset: u8,
+    pub(crate) part: u8,
+    pub(crate) parts: u8,
+}
+
+impl DelegatedShare {
+    /// the voting power received by the representative out of the `stake` of
+    /// the delegator
+    ///
+    /// The cumulative shares are rounded down rather than each share, so the
+    /// voting power received by all the representatives of a delegator adds
+    /// up to its stake: the remainder of a division goes to the
+    /// representative whose share crosses the next unit.
+    pub(crate) fn apply(&self, stake: u64) -> u64 {
+        let cumulative = |parts: u8| (stake as u128 * parts as u128) / self.parts as u128;
+        (cumulative(self.offset + self.part) - cumulative(self.offset)) as u64
+    }
+}
+
+type DelegationKey = (account::Identifier, VoteDelegationScope);
+
+/// vote delegations recorded in the ledger
+#[derive(Clone, PartialEq, Eq, Default)]
+pub struct VoteDelegations {
+    /// delegation of every (delegator, scope)
+    delegations: Hamt<DefaultHasher, DelegationKey, VoteDelegationType>,
+    /// delegators of every (representative, scope)
+    delegators: Hamt<DefaultHasher, DelegationKey, Hamt<DefaultHasher, account::Identifier, ()>>,
+}
+
+impl VoteDelegations {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn get(
+        &self,
+        delegator: &account::Identifier,
+        scope: &VoteDelegationScope,
+    ) -> Option<&VoteDelegationType> {
+        self.delegations.lookup(&(delegator.clone(), scope.clone()))
+    }
+
+    /// every recorded delegation, with its delegator and its scope
+    pub fn iter(
+        &self,
+    ) -> impl Iterator<
+        Item = (
+            &account::Identifier,
+            &VoteDelegationScope,
+            &VoteDelegationType,
+        ),
+    > {
+        self.delegations
+            .iter()
+            .map(|((delegator, scope), delegation)| (delegator, scope, delegation))
+    }
+
+    /// record the delegation of `delegator` for the given scope, replacing
+    /// the previous one if any.
+    #[must_use = "Does not modify the internal state"]
+    pub fn delegate(
+        &self,
+        delegator: account::Identifier,
+        scope: VoteDelegationScope,
+        delegation: VoteDelegationType,
+    ) -> Self {
+        let key = (delegator.clone(), scope.clone());
+        let mut new = self.clone();
+
+        if let Some(previous) = self.delegations.lookup(&key) {
+            for (representative, _, _) in previous.representatives() {
+                let representative_key = (representative.clone(), scope.clone());
+                if let Some(delegators) = new.delegators.lookup(&representative_key) {
+                    let delegators = delegators
+                        .remove(&delegator)
+                        .unwrap_or_else(|_| delegators.clone());
+                    new.delegators = if delegators.is_empty() {
+                        new.delegators.remove(&representative_key).unwrap()
+                    } else {
+                        new.delegators
+                            .replace(&representative_key, delegators)
+                            .unwrap()
+                            .0
+                    };
+                }
+            }
+            new.delegations = new.delegations.remove(&key).unwrap();
+        }
+
+        if delegation == VoteDelegationType::NonDelegated {
+            return new;
+        }
+
+        for (representative, _, _) in delegation.representatives() {
+            let representative_key = (representative.clone(), scope.clone());
+            let delegators = Hamt::new().insert(delegator.clone(), ()).unwrap();
+            new.delegators = new.delegators.insert_or_update_simple(
+                representative_key,
+                delegators,
+                |delegators| {
+                    Some(
+                        delegators
+                            .insert(delegator.clone(), ())
+                            .unwrap_or_else(|_| delegators.clone()),
+                    )
+                },
+            );
+        }
+        new.delegations = new.delegations.insert(key, delegation).unwrap();
+        new
+    }
+
+    /// delegation of `delegator` applying to the given vote plan: the one for
+    /// the vote plan itself if any, otherwise the one for its voting token
+    pub fn delegation_for(
+        &self,
+        delegator: &account::Identifier,
+        plan: &VotePlan,
+    ) -> Option<&VoteDelegationType> {
+        self.get(delegator, &VoteDelegationScope::VotePlan(plan.to_id()))
+            .or_else(|| {
+                self.get(
+                    delegator,
+                    &VoteDelegationScope::Token(plan.voting_token().clone()),
+                )
+            })
+    }
+
+    /// the accounts whose delegation for the given vote plan includes
+    /// `representative`, with the share of their voting power it receives
+    pub(crate) fn delegators_for(
+        &self,
+        representative: &account::Identifier,
+        plan: &VotePlan,
+    ) -> Vec<(account::Identifier, DelegatedShare)> {
+        let scopes = [
+            VoteDelegationScope::VotePlan(plan.to_id()),
+            VoteDelegationScope::Token(plan.voting_token().clone()),
+        ];
+
+        let mut delegators = Vec::new();
+        for scope in scopes {
+            let delegators_of_scope = match self.delegators.lookup(&(representative.clone(), scope))
+            {
+                Some(delegators_of_scope) => delegators_of_scope,
+                None => continue,
+            };
+            for (delegator, _) in delegators_of_scope.iter() {
+                // a delegator appears in both scopes only if it delegates to
+                // this representative at the vote plan and at the token
+                // levels, only the former applies
+                if delegators.iter().any(|(d, _)| d == delegator) {
+                    continue;
+                }
+                let share = self.delegation_for(delegator, plan).and_then(|delegation| {
+                    let representatives = delegation.representatives();
+                    let position = representatives
+                        .iter()
+                        .position(|(r, _, _)| *r == representative)?;
+                    let offset = representatives[..position]
+                        .iter()
+                        .map(|(_, part, _)| *part)
+                        .sum();
+                    let (_, part, parts) = representatives[position];
+                    Some(DelegatedShare {
+                        offset,
+                        part,
+                        parts,
+                    })
+                });
+                // a vote plan delegation to other representatives overrides
+                // the token one
+                if let Some(share) = share {
+                    delegators.push((delegator.clone(), share));
+                }
+            }
+        }
+        delegators
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::testing::{TestGen, VoteTestGen};
+
+    #[test]
+    pub fn vote_delegation_ratio_is_validated() {
+        let alice = TestGen::identifier();
+        let bob = TestGen::identifier();
+        assert!(VoteDelegationRatio::new(3, vec![(alice.clone(), 1), (bob.clone(), 2)]).is_some());
+        assert!(VoteDelegationRatio::new(3, vec![(alice.clone(), 1), (bob.clone(), 1)]).is_none());
+        assert!(VoteDelegationRatio::new(1, vec![(alice.clone(), 1)]).is_none());
+        assert!(VoteDelegationRatio::new(2, vec![(alice.clone(), 2), (bob, 0)]).is_none());
+        assert!(VoteDelegationRatio::new(2, vec![(alice.clone(), 1), (alice, 1)]).is_none());
+    }
+
+    #[test]
+    pub fn vote_plan_delegation_overrides_token_delegation() {
+        let alice = TestGen::identifier();
+        let first_representative = TestGen::identifier();
+        let second_representative = TestGen::identifier();
+        let vote_plan = VoteTestGen::vote_plan_with_proposals(1);
+
+        let delegations = VoteDelegations::new()
+            .delegate(
+                alice.clone(),
+                VoteDelegationScope::Token(vote_plan.voting_token().clone()),
+                VoteDelegationType::Full(first_representative.clone()),
+            )
+            .delegate(
+                alice.clone(),
+                VoteDelegationScope::VotePlan(vote_plan.to_id()),
+                VoteDelegationType::Full(second_representative.clone()),
+            );
+
+        assert!(delegations
+            .delegators_for(&first_representative, &vote_plan)
+            .is_empty());
+        assert_eq!(
+            delegations.delegators_for(&second_representative, &vote_plan),
+            vec![(
+                alice.clone(),
+                DelegatedShare {
+                    offset: 0,
+                    part: 1,
+                    parts: 1
+                }
+            )]
+        );
+
+        let delegations = delegations.delegate(
+            alice.clone(),
+            VoteDelegationScope::VotePlan(vote_plan.to_id()),
+            VoteDelegationType::NonDelegated,
+        );
+        assert!(delegations
+            .delegators_for(&second_representative, &vote_plan)
+            .is_empty());
+        assert_eq!(
+            delegations
+                .delegators_for(&first_representative, &vote_plan)
+                .len(),
+            1
+        );
+    }
+
+    #[test]
+    pub fn delegated_shares_add_up_to_the_stake() {
+        let representatives: Vec<_> = (0..3).map(|_| TestGen::identifier()).collect();
+        let ratio =
+            VoteDelegationRatio::new(7, representatives.iter().cloned().zip([2, 2, 3]).collect())
+                .unwrap();
+        let vote_plan = VoteTestGen::vote_plan_with_proposals(1);
+        let delegations = VoteDelegations::new().delegate(
+            TestGen::identifier(),
+            VoteDelegationScope::VotePlan(vote_plan.to_id()),
+            VoteDelegationType::Ratio(ratio),
+        );
+
+        let shares: Vec<_> = representatives
+            .iter()
+            .map(|representative| delegations.delegators_for(representative, &vote_plan)[0].1)
+            .collect();
+        for stake in [0, 1, 6, 100, u64::MAX] {
+            let total: u128 = shares.iter().map(|share| share.apply(stake) as u128).sum();
+            assert_eq!(total, stake as u128);
+        }
+        assert_eq!(
+            shares
+                .iter()
+                .map(|share| share.apply(100))
+                .collect::<Vec<_>>(),
+            vec![28, 29, 43]
+        );
+    }
+}
@@ -4,8 +4,12 @@ use crate::{
     certificate::{TallyProof, VoteAction, VoteCast, VotePlan, VotePlanId, VoteTally},
     date::BlockDate,
     ledger::governance::Governance,
-    vote::{CommitteeId, PayloadType, VoteError, VotePlanManager},
+    vote::{
+        CheckpointError, CommitteeId, EncryptedWeightedVote, Payload, PayloadType, VoteDelegations,
+        VoteError, VotePlanCheckpoint, VotePlanManager,
+    },
 };
+use chain_vote::{Ballot, BallotVerificationError};
 use imhamt::{Hamt, InsertError, UpdateError};
 use std::collections::{hash_map::DefaultHasher, HashSet};
 use thiserror::Error;
@@ -46,6 +50,29 @@ pub enum VotePlanLedgerError {
 
     #[error("Private vote plan must contain at least one committee member key")]
     VotePlanMissingCommitteeMemberKey,
+
+    #[error("Vote plan committee threshold {threshold} is greater than the number of committee members {members}")]
+    VotePlanInvalidCommitteeThreshold { threshold: u8, members: usize },
+
+    #[error("Vote plan committee member keys are not shares of the same election key")]
+    VotePlanInconsistentCommitteeKeys,
+
+    #[error("Vote plan weighted ballot kind must be set if and only if the payload type is private weighted")]
+    VotePlanInvalidWeightedBallot,
+
+    #[error("Vote plan weighted ballots of proposal {proposal} need {ciphertexts} ciphertexts, at most {max} are supported")]
+    VotePlanWeightedBallotTooLarge {
+        proposal: usize,
+        ciphertexts: usize,
+        max: usize,
+    },
+
+    #[error("cannot restore the checkpoint of the vote plan {id}")]
+    CheckpointError {
+        id: VotePlanId,
+        #[source]
+        reason: UpdateError<CheckpointError>,
+    },
 }
 
 impl VotePlanLedger {
@@ -63,18 +90,33 @@ impl VotePlanLedger {
     /// * the proposal's index does not exist;
     /// * it is no longer possible to vote (the date to vote expired)
     ///
+    /// an account without voting power of its own can vote only if some
+    /// voting power is delegated to it in `delegations`.
+    ///
+    /// the proof of a private vote is not verified again if
+    /// `verified_ballot` was returned for it by [`Self::verify_private_votes`].
+    ///
     pub fn apply_vote(
         &self,
         block_date: BlockDate,
         identifier: account::Identifier,
         vote: VoteCast,
+        verified_ballot: Option<Ballot>,
         token_distribution: TokenDistribution<()>,
+        delegations: &VoteDelegations,
     ) -> Result<Self, VotePlanLedgerError> {
         let id = vote.vote_plan().clone();
 
         let r = self.plans.update(&id, move |v| {
-            v.vote(block_date, identifier, vote, token_distribution)
-                .map(Some)
+            v.vote_with_verified_ballot(
+                block_date,
+                identifier,
+                vote,
+                verified_ballot,
+                token_distribution,
+                delegations,
+            )
+            .map(Some)
         });
 
         match r {
@@ -83,6 +125,70 @@ impl VotePlanLedger {
         }
     }
 
+    /// add the voting power delegated to the voters to the tallies of the
+    /// vote plans whose voting period is over, see
+    /// [`VotePlanManager::count_delegated_votes`]
+    #[must_use = "This function does not modify the object, the result contains the resulted new version of the vote plan ledger"]
+    pub fn count_delegated_votes(
+        &self,
+        block_date: BlockDate,
+        token_distribution: TokenDistribution<()>,
+        delegations: &VoteDelegations,
+    ) -> Self {
+        let mut plans = self.plans.clone();
+        for (id, manager) in self.plans.iter() {
+            if manager.delegated_votes_counted() || block_date < manager.plan().vote_end() {
+                continue;
+            }
+            let counted =
+                manager.count_delegated_votes(block_date, token_distribution.clone(), delegations);
+            plans = plans.replace(id, counted).unwrap().0;
+        }
+        Self { plans }
+    }
+
+    /// verify in a single batch the proofs of the private votes among `votes`
+    ///
+    /// Only the proofs are checked: the votes that are not private, or that are
+    /// cast for an unknown vote plan, are skipped here and fully validated when
+    /// applied. Returns the verified ballot of each of the `votes`, `None` for
+    /// the skipped ones, to apply them with [`Self::apply_vote`] without
+    /// verifying their proof again. Fails if the proof of any of the private
+    /// votes is invalid.
+    pub fn verify_private_votes<'a, I>(
+        &self,
+        votes: I,
+    ) -> Result<Vec<Option<Ballot>>, BallotVerificationError>
+    where
+        I: IntoIterator<Item = &'a VoteCast>,
+    {
+        let statements: Vec<_> = votes
+            .into_iter()
+            .map(|vote| {
+                let (crs, election_pk) =
+                    self.plans.lookup(vote.vote_plan())?.private_vote_keys()?;
+                match vote.payload() {
+                    Payload::Private {
+                        encrypted_vote,
+                        proof,
+                    } => Some((
+                        encrypted_vote.as_inner().clone(),
+                        proof.as_inner(),
+                        crs,
+                        election_pk,
+                    )),
+                    Payload::Public { .. } | Payload::PrivateWeighted { .. } => None,
+                }
+            })
+            .collect();
+        let mut ballots =
+            Ballot::try_from_votes_and_proofs(statements.iter().flatten().cloned())?.into_iter();
+        Ok(statements
+            .iter()
+            .map(|statement| statement.as_ref().and_then(|_| ballots.next()))
+            .collect())
+    }
+
     /// add the vote plan in a new `VotePlanLedger`
     ///
     /// the given `VotePlanLedger` is not modified and instead a new `VotePlanLedger` is
@@ -92,6 +198,7 @@ impl VotePlanLedger {
     ///
     /// * the vote_plan is set to finished votes in the past
     /// * the vote_plan has already started
+    /// * the vote_plan committee threshold is greater than its number of committee members
     ///
     #[must_use = "This function does not modify the object, the result contains the resulted new version of the vote plan ledger"]
     pub fn add_vote_plan(
@@ -114,12 +221,46 @@ impl VotePlanLedger {
             });
         }
 
-        if let PayloadType::Private = vote_plan.payload_type() {
+        if let PayloadType::Private | PayloadType::PrivateWeighted = vote_plan.payload_type() {
             if vote_plan.committee_public_keys().is_empty() {
                 return Err(VotePlanLedgerError::VotePlanMissingCommitteeMemberKey);
             }
         }
 
+        if (vote_plan.payload_type() == PayloadType::PrivateWeighted)
+            != vote_plan.weighted_ballot().is_some()
+        {
+            return Err(VotePlanLedgerError::VotePlanInvalidWeightedBallot);
+        }
+
+        if let Some(kind) = vote_plan.weighted_ballot() {
+            for (proposal, p) in vote_plan.proposals().iter().enumerate() {
+                let ciphertexts = kind.encrypted_vote_len(p.options().choice_range().len());
+                if ciphertexts > EncryptedWeightedVote::MAX_LEN {
+                    return Err(VotePlanLedgerError::VotePlanWeightedBallotTooLarge {
+                        proposal,
+                        ciphertexts,
+                        max: EncryptedWeightedVote::MAX_LEN,
+                    });
+                }
+            }
+        }
+
+        if let Some(threshold) = vote_plan.committee_threshold() {
+            if threshold.get() as usize > vote_plan.committee_public_keys().len() {
+                return Err(VotePlanLedgerError::VotePlanInvalidCommitteeThreshold {
+                    threshold: threshold.get(),
+                    members: vote_plan.committee_public_keys().len(),
+                });
+            }
+            if !chain_vote::ElectionPublicKey::check_threshold_participants(
+                threshold.get() as usize,
+                vote_plan.committee_public_keys(),
+            ) {
+                return Err(VotePlanLedgerError::VotePlanInconsistentCommitteeKeys);
+            }
+        }
+
         let id = vote_plan.to_id();
         let manager = VotePlanManager::new(vote_plan, committee);
 
@@ -178,6 +319,35 @@ impl VotePlanLedger {
             Ok(plans) => Ok(Self { plans }),
         }
     }
+
+    /// take a checkpoint of the votes of every vote plan of the ledger
+    pub fn checkpoints(&self) -> Vec<VotePlanCheckpoint> {
+        self.plans
+            .iter()
+            .map(|(_, manager)| manager.checkpoint())
+            .collect()
+    }
+
+    /// restore the votes of the associated vote plan from the given checkpoint
+    ///
+    /// # errors
+    ///
+    /// * the vote plan id does not exist;
+    /// * the checkpoint does not match the proposals of the vote plan.
+    ///
+    pub fn restore_checkpoint(
+        &self,
+        checkpoint: &VotePlanCheckpoint,
+    ) -> Result<Self, VotePlanLedgerError> {
+        let id = checkpoint.id().clone();
+
+        let r = self.plans.update(&id, |v| v.restore(checkpoint).map(Some));
+
+        match r {
+            Err(reason) => Err(VotePlanLedgerError::CheckpointError { reason, id }),
+            Ok(plans) => Ok(Self { plans }),
+        }
+    }
 }
 
 impl Default for VotePlanLedger {
@@ -9,21 +9,28 @@ use crate::{
     rewards::Ratio,
     stake::Stake,
     tokens::identifier::TokenIdentifier,
+    value::Value,
     vote::{self, CommitteeId, Options, Tally, TallyResult, VotePlanStatus, VoteProposalStatus},
 };
 use crate::{
     certificate::DecryptedPrivateTallyProposal,
     vote::{Choice, Payload, PayloadType},
 };
-use chain_vote::{committee, Ballot, Crs, ElectionPublicKey, EncryptedTally};
+use chain_vote::{
+    committee, Ballot, Crs, ElectionPublicKey, EncryptedTally, WeightedBallot, WeightedBallotKind,
+};
 use imhamt::Hamt;
 use thiserror::Error;
 
 use std::collections::{hash_map::DefaultHasher, HashSet};
-use std::num::NonZeroU64;
+use std::num::{NonZeroU64, NonZeroU8};
 use std::sync::Arc;
 
-use super::{PrivateTallyState, TallyError};
+use super::{
+    checkpoint::{ProposalCheckpoint, TallyCheckpoint},
+    delegation::VoteDelegations,
+    CheckpointError, PrivateTallyState, TallyError, VotePlanCheckpoint,
+};
 
 /// Manage the vote plan and the associated votes in the ledger
 ///
@@ -35,17 +42,23 @@ pub struct VotePlanManager {
     plan: Arc<VotePlan>,
     committee: Arc<HashSet<CommitteeId>>,
     proposal_managers: ProposalManagers,
+    /// whether the voting power delegated to the voters was added to the
+    /// tallies, this is done once the voting period is over
+    delegated_votes_counted: bool,
 }
 
 #[derive(Clone, Eq, PartialEq, Debug)]
 pub enum ValidatedPayload {
     Public(Choice),
     Private(Ballot),
+    PrivateWeighted(WeightedBallot),
 }
 
 #[derive(Clone, Eq, PartialEq, Debug)]
 struct ValidatedVoteCast {
     payload: ValidatedPayload,
+    /// the payload as cast, with the proof of the vote if it is private
+    cast_payload: Payload,
     proposal_index: usize,
 }
 
@@ -58,12 +71,18 @@ enum ProposalManagers {
         managers: Vec<ProposalManager>,
         crs: Arc<Crs>,
         election_pk: Arc<ElectionPublicKey>,
+        weighted_ballot: Option<WeightedBallotKind>,
     },
 }
 
 #[derive(Clone, PartialEq, Eq)]
 struct ProposalManager {
     votes_by_voters: Hamt<DefaultHasher, account::Identifier, ()>,
+    /// vote of every voter, to add the voting power delegated to the voter
+    /// once the voting period is over. The votes are kept as cast, with their
+    /// proofs, so they can be verified again when restored from a checkpoint,
+    /// and are dropped once the delegated voting power is counted
+    ballots: Hamt<DefaultHasher, account::Identifier, Payload>,
     options: Options,
     tally: IncrementalTally,
     action: VoteAction,
@@ -76,6 +95,46 @@ pub enum IncrementalTally {
     Decrypted(TallyResult),
 }
 
+impl IncrementalTally {
+    fn add_vote(&mut self, payload: &ValidatedPayload, stake: Value) -> Result<(), VoteError> {
+        match (self, payload) {
+            (IncrementalTally::Public(result), ValidatedPayload::Public(choice)) => {
+                result.add_vote(*choice, stake)
+            }
+            (IncrementalTally::Private(encrypted_tally), ValidatedPayload::Private(ballot)) => {
+                encrypted_tally.add(ballot, stake.0).map_err(Into::into)
+            }
+            (
+                IncrementalTally::Private(encrypted_tally),
+                ValidatedPayload::PrivateWeighted(ballot),
+            ) => encrypted_tally
+                .add_weighted(ballot, stake.0)
+                .map_err(Into::into),
+            (IncrementalTally::Public(_), ValidatedPayload::Private(_)) => {
+                Err(VoteError::InvalidPayloadType {
+                    received: PayloadType::Private,
+                    expected: PayloadType::Public,
+                })
+            }
+            (IncrementalTally::Public(_), ValidatedPayload::PrivateWeighted(_)) => {
+                Err(VoteError::InvalidPayloadType {
+                    received: PayloadType::PrivateWeighted,
+                    expected: PayloadType::Public,
+                })
+            }
+            (IncrementalTally::Private(_), ValidatedPayload::Public(_)) => {
+                Err(VoteError::InvalidPayloadType {
+                    received: PayloadType::Public,
+                    expected: PayloadType::Private,
+                })
+            }
+            (IncrementalTally::Decrypted(_), _) => {
+                unreachable!("tried to add vote after the voting period")
+            }
+        }
+    }
+}
+
 #[derive(Debug, Error, Clone, PartialEq, Eq)]
 pub enum VoteError {
     #[error("Invalid option choice")]
@@ -130,6 +189,9 @@ pub enum VoteError {
     #[error("Error during private tallying {0}")]
     PrivateTallyError(String),
 
+    #[error("The stake of the private tally overflows")]
+    PrivateTallyOverflow(#[from] chain_vote::tally::TallyOverflowError),
+
     // maybe add the expected token id to the error message?
     #[error("Account has no voting power")]
     ZeroVotingPower,
@@ -149,6 +211,7 @@ impl ProposalManager {
 
         Self {
             votes_by_voters: Hamt::new(),
+            ballots: Hamt::new(),
             options: proposal.options().clone(),
             tally: IncrementalTally::Public(results),
             action: proposal.action().clone(),
@@ -168,6 +231,7 @@ impl ProposalManager {
         let encrypted_tally = EncryptedTally::new(tally_size, election_pk, crs);
         Self {
             votes_by_voters: Hamt::new(),
+            ballots: Hamt::new(),
             options: proposal.options().clone(),
             tally: IncrementalTally::Private(encrypted_tally),
             action: proposal.action().clone(),
@@ -176,15 +240,19 @@ impl ProposalManager {
 
     /// apply the given vote cast to the proposal
     ///
-    /// if there is already a vote present for this proposal it will
-    /// simply replace the previously set one
+    /// only the voting power of the voter itself is added to the tally, the
+    /// voting power delegated to it is added by [`Self::count_delegated_votes`].
+    /// A voter without voting power of its own can only vote if
+    /// `has_delegators`.
     ///
     #[must_use = "Add the vote in a new ProposalManager, does not modify self"]
     pub fn vote(
         &self,
         identifier: account::Identifier,
         payload: ValidatedPayload,
+        cast_payload: Payload,
         token_distribution: &TokenDistribution<TokenIdentifier>,
+        has_delegators: bool,
     ) -> Result<Self, VoteError> {
         // Part of DDoS protection: do not record a new ballot if the account already voted for this
         // proposal. This protects the system from flooding in a system with cheap/free voting
@@ -194,53 +262,83 @@ impl ProposalManager {
             .insert(identifier.clone(), ())
             .map_err(|_| VoteError::AlreadyVoted)?;
 
-        let tally = if let Some(stake) = token_distribution
+        let stake = match token_distribution
             .get_account(&identifier)
             // we ignore the error since at this point we know that the account exists, since it
             // was verified with the input.
             .ok()
             .flatten()
         {
-            match (self.tally.clone(), payload) {
-                (IncrementalTally::Public(mut result), ValidatedPayload::Public(choice)) => {
-                    result.add_vote(choice, stake)?;
-                    IncrementalTally::Public(result)
-                }
-                (
-                    IncrementalTally::Private(mut encrypted_tally),
-                    ValidatedPayload::Private(ballot),
-                ) => {
-                    encrypted_tally.add(&ballot, stake.0);
-                    IncrementalTally::Private(encrypted_tally)
-                }
-                (IncrementalTally::Public(_), ValidatedPayload::Private(_)) => {
-                    return Err(VoteError::InvalidPayloadType {
-                        received: PayloadType::Private,
-                        expected: PayloadType::Public,
-                    })
-                }
-                (IncrementalTally::Private(_), ValidatedPayload::Public(_)) => {
-                    return Err(VoteError::InvalidPayloadType {
-                        received: PayloadType::Public,
-                        expected: PayloadType::Private,
-                    })
-                }
-                (IncrementalTally::Decrypted(_), _) => {
-                    unreachable!("tried to add vote after the voting period")
-                }
-            }
-        } else {
-            return Err(VoteError::ZeroVotingPower);
+            Some(stake) => stake,
+            None if has_delegators => Value::zero(),
+            None => return Err(VoteError::ZeroVotingPower),
         };
 
+        let mut tally = self.tally.clone();
+        tally.add_vote(&payload, stake)?;
+
+        let ballots = self
+            .ballots
+            .insert(identifier, cast_payload)
+            .map_err(|_| VoteError::AlreadyVoted)?;
+
         Ok(Self {
             votes_by_voters,
+            ballots,
             tally,
             options: self.options.clone(),
             action: self.action.clone(),
         })
     }
 
+    /// add to the tally the voting power of the accounts delegating to the
+    /// voters, except the delegators that voted on this proposal themselves
+    ///
+    /// The voting power is taken from `token_distribution`, so this has to be
+    /// done once with the state of the ledger at the end of the voting period.
+    /// `validate` verifies the votes of the voters that received delegations.
+    fn count_delegated_votes<F>(
+        &self,
+        plan: &VotePlan,
+        token_distribution: &TokenDistribution<TokenIdentifier>,
+        delegations: &VoteDelegations,
+        validate: F,
+    ) -> Self
+    where
+        F: Fn(&Payload) -> Result<ValidatedPayload, VoteError>,
+    {
+        let mut tally = self.tally.clone();
+        for (representative, ballot) in self.ballots.iter() {
+            let delegators = delegations.delegators_for(representative, plan);
+            if delegators.is_empty() {
+                continue;
+            }
+            let payload = validate(ballot).expect("ballots are verified when cast or restored");
+            for (delegator, share) in delegators {
+                // a direct vote overrides the delegation
+                if self.votes_by_voters.contains_key(&delegator) {
+                    continue;
+                }
+                let stake = match token_distribution.get_account(&delegator).ok().flatten() {
+                    Some(stake) => Value(share.apply(stake.0)),
+                    None => continue,
+                };
+                tally
+                    .add_vote(&payload, stake)
+                    .expect("ballots are validated against the tally when cast");
+            }
+        }
+
+        Self {
+            votes_by_voters: self.votes_by_voters.clone(),
+            // no vote can be cast anymore, the ballots are not needed
+            ballots: Hamt::new(),
+            tally,
+            options: self.options.clone(),
+            action: self.action.clone(),
+        }
+    }
+
     fn check_already_voted(&self, identifier: &account::Identifier) -> Result<(), VoteError> {
         if self.votes_by_voters.contains_key(identifier) {
             Err(VoteError::AlreadyVoted)
@@ -255,29 +353,42 @@ impl ProposalManager {
         cast: VoteCast,
     ) -> Result<ValidatedPayload, VoteError> {
         self.check_already_voted(identifier)?;
+        Self::validate_public_payload(cast.into_payload())
+    }
 
-        let payload = cast.into_payload();
-
+    fn validate_public_payload(payload: Payload) -> Result<ValidatedPayload, VoteError> {
         match payload {
             Payload::Public { choice } => Ok(ValidatedPayload::Public(choice)),
-            Payload::Private { .. } => Err(VoteError::InvalidPayloadType {
-                received: PayloadType::Private,
-                expected: PayloadType::Public,
-            }),
+            Payload::Private { .. } | Payload::PrivateWeighted { .. } => {
+                Err(VoteError::InvalidPayloadType {
+                    received: payload.payload_type(),
+                    expected: PayloadType::Public,
+                })
+            }
         }
     }
 
+    /// validate a private vote, its proof is not verified again if
+    /// `verified_ballot` is the ballot of the same encrypted vote
     pub fn validate_private_vote(
         &self,
         identifier: &account::Identifier,
         cast: VoteCast,
         crs: &Crs,
         election_pk: &ElectionPublicKey,
+        verified_ballot: Option<Ballot>,
     ) -> Result<ValidatedPayload, VoteError> {
         self.check_already_voted(identifier)?;
+        self.validate_private_payload(cast.into_payload(), crs, election_pk, verified_ballot)
+    }
 
-        let payload = cast.into_payload();
-
+    fn validate_private_payload(
+        &self,
+        payload: Payload,
+        crs: &Crs,
+        election_pk: &ElectionPublicKey,
+        verified_ballot: Option<Ballot>,
+    ) -> Result<ValidatedPayload, VoteError> {
         match payload {
             Payload::Public { .. } => Err(VoteError::InvalidPayloadType {
                 received: PayloadType::Public,
@@ -295,14 +406,75 @@ impl ProposalManager {
                         actual: actual_size,
                     })
                 } else {
-                    Ok(ValidatedPayload::Private(Ballot::try_from_vote_and_proof(
-                        encrypted_vote.as_inner().clone(),
-                        proof.as_inner(),
-                        crs,
-                        election_pk,
-                    )?))
+                    match verified_ballot {
+                        Some(ballot) if ballot.vote() == encrypted_vote.as_inner() => {
+                            Ok(ValidatedPayload::Private(ballot))
+                        }
+                        _ => Ok(ValidatedPayload::Private(Ballot::try_from_vote_and_proof(
+                            encrypted_vote.as_inner().clone(),
+                            proof.as_inner(),
+                            crs,
+                            election_pk,
+                        )?)),
+                    }
                 }
             }
+            Payload::PrivateWeighted { .. } => Err(VoteError::InvalidPayloadType {
+                received: PayloadType::PrivateWeighted,
+                expected: PayloadType::Private,
+            }),
+        }
+    }
+
+    pub fn validate_private_weighted_vote(
+        &self,
+        identifier: &account::Identifier,
+        cast: VoteCast,
+        kind: &WeightedBallotKind,
+        crs: &Crs,
+        election_pk: &ElectionPublicKey,
+    ) -> Result<ValidatedPayload, VoteError> {
+        self.check_already_voted(identifier)?;
+        self.validate_private_weighted_payload(cast.into_payload(), kind, crs, election_pk)
+    }
+
+    fn validate_private_weighted_payload(
+        &self,
+        payload: Payload,
+        kind: &WeightedBallotKind,
+        crs: &Crs,
+        election_pk: &ElectionPublicKey,
+    ) -> Result<ValidatedPayload, VoteError> {
+        match payload {
+            Payload::PrivateWeighted {
+                encrypted_vote,
+                proof,
+            } => {
+                let actual_size = encrypted_vote.as_inner().len();
+                let expected_size = kind.encrypted_vote_len(self.options.choice_range().len());
+                if actual_size != expected_size {
+                    Err(VoteError::PrivateVoteInvalidSize {
+                        expected: expected_size,
+                        actual: actual_size,
+                    })
+                } else {
+                    Ok(ValidatedPayload::PrivateWeighted(
+                        WeightedBallot::try_from_vote_and_proof(
+                            encrypted_vote.as_inner().clone(),
+                            proof.as_inner(),
+                            kind,
+                            crs,
+                            election_pk,
+                        )?,
+                    ))
+                }
+            }
+            Payload::Public { .. } | Payload::Private { .. } => {
+                Err(VoteError::InvalidPayloadType {
+                    received: payload.payload_type(),
+                    expected: PayloadType::PrivateWeighted,
+                })
+            }
         }
     }
 
@@ -329,6 +501,7 @@ impl ProposalManager {
 
         Ok(Self {
             votes_by_voters: self.votes_by_voters.clone(),
+            ballots: self.ballots.clone(),
             options: self.options.clone(),
             tally: self.tally.clone(),
             action: self.action.clone(),
@@ -338,6 +511,7 @@ impl ProposalManager {
     pub fn finalize_private_tally<F>(
         &self,
         committee_pks: &[committee::MemberPublicKey],
+        committee_threshold: Option<NonZeroU8>,
         decrypted_proposal: &DecryptedPrivateTallyProposal,
         governance: &Governance,
         token_distribution: &TokenDistribution<TokenIdentifier>,
@@ -357,11 +531,27 @@ impl ProposalManager {
         let verifiable_tally = chain_vote::Tally {
             votes: decrypted_proposal.tally_result.to_vec(),
         };
-        if !verifiable_tally.verify(
-            encrypted_tally,
-            committee_pks,
-            &decrypted_proposal.decrypt_shares,
-        ) {
+        let verified = match committee_threshold {
+            Some(threshold) => verifiable_tally.verify_threshold(
+                encrypted_tally,
+                threshold.get() as usize,
+                committee_pks,
+                &decrypted_proposal.decrypt_shares,
+            ),
+            // every committee member has to provide its share
+            None => match decrypted_proposal
+                .decrypt_shares
+                .iter()
+                .cloned()
+                .collect::<Option<Vec<_>>>()
+            {
+                Some(decrypt_shares) if decrypt_shares.len() == committee_pks.len() => {
+                    verifiable_tally.verify(encrypted_tally, committee_pks, &decrypt_shares)
+                }
+                _ => false,
+            },
+        };
+        if !verified {
             return Err(TallyError::InvalidDecryption);
         }
 
@@ -383,6 +573,7 @@ impl ProposalManager {
 
         Ok(Self {
             votes_by_voters: self.votes_by_voters.clone(),
+            ballots: self.ballots.clone(),
             options: self.options.clone(),
             tally: IncrementalTally::Decrypted(result),
             action: self.action.clone(),
@@ -482,11 +673,9 @@ impl ProposalManagers {
                     .collect();
                 Self::Public { managers }
             }
-            PayloadType::Private => {
+            PayloadType::Private | PayloadType::PrivateWeighted => {
                 let crs = Arc::new(Crs::from_hash(plan.to_id().as_ref()));
-                let election_pk = Arc::new(ElectionPublicKey::from_participants(
-                    plan.committee_public_keys(),
-                ));
+                let election_pk = Arc::new(plan.election_public_key());
 
                 let managers = plan
                     .proposals()
@@ -504,6 +693,7 @@ impl ProposalManagers {
                     managers,
                     crs,
                     election_pk,
+                    weighted_ballot: plan.weighted_ballot().copied(),
                 }
             }
         }
@@ -530,11 +720,17 @@ impl ProposalManagers {
         identifier: account::Identifier,
         vote_cast: ValidatedVoteCast,
         token_distribution: &TokenDistribution<TokenIdentifier>,
+        has_delegators: bool,
     ) -> Result<Self, VoteError> {
         let proposal_index = vote_cast.proposal_index;
         if let Some(manager) = self.managers().get(proposal_index) {
-            let updated_manager =
-                manager.vote(identifier, vote_cast.payload, token_distribution)?;
+            let updated_manager = manager.vote(
+                identifier,
+                vote_cast.payload,
+                vote_cast.cast_payload,
+                token_distribution,
+                has_delegators,
+            )?;
             // only clone the array if it does make sens to do so:
             //
             // * the index exist
@@ -549,6 +745,22 @@ impl ProposalManagers {
         }
     }
 
+    fn count_delegated_votes(
+        &self,
+        plan: &VotePlan,
+        token_distribution: &TokenDistribution<TokenIdentifier>,
+        delegations: &VoteDelegations,
+    ) -> Self {
+        let mut updated = self.clone();
+        for (index, manager) in updated.managers_mut().iter_mut().enumerate() {
+            *manager =
+                manager.count_delegated_votes(plan, token_distribution, delegations, |ballot| {
+                    self.validate_ballot(index, ballot)
+                });
+        }
+        updated
+    }
+
     pub fn public_tally<F>(
         &self,
         token_distribution: &TokenDistribution<TokenIdentifier>,
@@ -576,14 +788,33 @@ impl ProposalManagers {
         }
     }
 
+    /// the common reference string and the election public key the proofs of
+    /// the private (non weighted) votes are verified against
+    fn private_vote_keys(&self) -> Option<(&Crs, &ElectionPublicKey)> {
+        match self {
+            Self::Private {
+                crs,
+                election_pk,
+                weighted_ballot: None,
+                ..
+            } => Some((crs, election_pk)),
+            _ => None,
+        }
+    }
+
     /// validate the vote against the proposal: verify that the proposal exists
     /// and the the length of the ciphertext is correct (if applicable)
+    ///
+    /// the proof of a private vote is not verified again if `verified_ballot`
+    /// was already verified for it, see [`Ballot::try_from_votes_and_proofs`]
     pub fn validate_vote(
         &self,
         identifier: &account::Identifier,
         cast: VoteCast,
+        verified_ballot: Option<Ballot>,
     ) -> Result<ValidatedVoteCast, VoteError> {
         let proposal_index = cast.proposal_index() as usize;
+        let cast_payload = cast.payload().clone();
         let payload = match self {
             Self::Public { managers } => managers
                 .get(proposal_index)
@@ -596,24 +827,72 @@ impl ProposalManagers {
                 managers,
                 crs,
                 election_pk,
-            } => managers
-                .get(proposal_index)
-                .ok_or(VoteError::InvalidVoteProposal {
-                    num_proposals: managers.len(),
-                    vote: cast.clone(),
-                })?
-                .validate_private_vote(identifier, cast, crs, election_pk),
+                weighted_ballot,
+            } => {
+                let manager =
+                    managers
+                        .get(proposal_index)
+                        .ok_or(VoteError::InvalidVoteProposal {
+                            num_proposals: managers.len(),
+                            vote: cast.clone(),
+                        })?;
+                match weighted_ballot {
+                    None => manager.validate_private_vote(
+                        identifier,
+                        cast,
+                        crs,
+                        election_pk,
+                        verified_ballot,
+                    ),
+                    Some(kind) => manager.validate_private_weighted_vote(
+                        identifier,
+                        cast,
+                        kind,
+                        crs,
+                        election_pk,
+                    ),
+                }
+            }
         }?;
 
         Ok(ValidatedVoteCast {
             payload,
+            cast_payload,
             proposal_index,
         })
     }
 
+    /// verify again a vote recorded for the proposal at `proposal_index`,
+    /// whether the voter already voted is not checked
+    fn validate_ballot(
+        &self,
+        proposal_index: usize,
+        ballot: &Payload,
+    ) -> Result<ValidatedPayload, VoteError> {
+        let payload = ballot.clone();
+        match self {
+            Self::Public { .. } => ProposalManager::validate_public_payload(payload),
+            Self::Private {
+                managers,
+                crs,
+                election_pk,
+                weighted_ballot,
+            } => {
+                let manager = &managers[proposal_index];
+                match weighted_ballot {
+                    None => manager.validate_private_payload(payload, crs, election_pk, None),
+                    Some(kind) => {
+                        manager.validate_private_weighted_payload(payload, kind, crs, election_pk)
+                    }
+                }
+            }
+        }
+    }
+
     pub fn finalize_private_tally<F>(
         &self,
         committee_pks: &[committee::MemberPublicKey],
+        committee_threshold: Option<NonZeroU8>,
         decrypted_tally: &DecryptedPrivateTally,
         governance: &Governance,
         token_distribution: &TokenDistribution<TokenIdentifier>,
@@ -627,6 +906,7 @@ impl ProposalManagers {
                 managers,
                 crs,
                 election_pk,
+                weighted_ballot,
             } => {
                 let mut proposals = Vec::with_capacity(managers.len());
                 for (proposal_manager, decrypted_proposal) in
@@ -634,6 +914,7 @@ impl ProposalManagers {
                 {
                     proposals.push(proposal_manager.finalize_private_tally(
                         committee_pks,
+                        committee_threshold,
                         decrypted_proposal,
                         governance,
                         token_distribution,
@@ -644,6 +925,7 @@ impl ProposalManagers {
                     managers: proposals,
                     crs: crs.clone(),
                     election_pk: election_pk.clone(),
+                    weighted_ballot: *weighted_ballot,
                 })
             }
             _ => Err(VoteError::InvalidPayloadType {
@@ -664,6 +946,7 @@ impl VotePlanManager {
             plan: Arc::new(plan),
             proposal_managers,
             committee: Arc::new(committee),
+            delegated_votes_counted: false,
         }
     }
 
@@ -675,6 +958,13 @@ impl VotePlanManager {
         &self.plan
     }
 
+    /// the common reference string and the election public key the proofs of
+    /// the private votes for this vote plan are verified against, `None` if
+    /// the votes are public or weighted
+    pub(crate) fn private_vote_keys(&self) -> Option<(&Crs, &ElectionPublicKey)> {
+        self.proposal_managers.private_vote_keys()
+    }
+
     pub fn statuses(&self) -> VotePlanStatus {
         let proposals = self
             .plan()
@@ -708,11 +998,98 @@ impl VotePlanManager {
             vote_end: self.plan().vote_end(),
             committee_end: self.plan().committee_end(),
             committee_public_keys,
+            committee_threshold: self.plan().committee_threshold(),
             proposals,
             voting_token: self.plan().voting_token().clone(),
         }
     }
 
+    /// take a compact snapshot of the votes recorded so far for each of
+    /// the proposals of the vote plan
+    pub fn checkpoint(&self) -> VotePlanCheckpoint {
+        let proposals = self
+            .proposal_managers
+            .managers()
+            .iter()
+            .map(|manager| ProposalCheckpoint {
+                voters: manager
+                    .votes_by_voters
+                    .iter()
+                    .map(|(id, _)| id.clone())
+                    .collect(),
+                ballots: manager
+                    .ballots
+                    .iter()
+                    .map(|(id, payload)| (id.clone(), payload.clone()))
+                    .collect(),
+                tally: TallyCheckpoint::from_incremental(&manager.tally),
+            })
+            .collect();
+        VotePlanCheckpoint::new(self.id.clone(), proposals, self.delegated_votes_counted)
+    }
+
+    /// replace the votes recorded for each of the proposals by the ones
+    /// of the given checkpoint
+    ///
+    /// # errors
+    ///
+    /// * if the checkpoint was taken for another vote plan
+    /// * if the checkpoint does not match the proposals of the vote plan
+    /// * if one of the recorded votes is invalid for the vote plan
+    ///
+    pub fn restore(&self, checkpoint: &VotePlanCheckpoint) -> Result<Self, CheckpointError> {
+        if checkpoint.id() != self.id() {
+            return Err(CheckpointError::InvalidVotePlan {
+                expected: self.id().clone(),
+                actual: checkpoint.id().clone(),
+            });
+        }
+
+        let managers = self.proposal_managers.managers();
+        if checkpoint.proposals().len() != managers.len() {
+            return Err(CheckpointError::InvalidProposalsCount {
+                expected: managers.len(),
+                actual: checkpoint.proposals().len(),
+            });
+        }
+
+        let mut proposal_managers = self.proposal_managers.clone();
+        for (index, ((manager, proposal), proposal_checkpoint)) in proposal_managers
+            .managers_mut()
+            .iter_mut()
+            .zip(self.plan.proposals().iter())
+            .zip(checkpoint.proposals())
+            .enumerate()
+        {
+            manager.tally =
+                proposal_checkpoint
+                    .tally
+                    .to_incremental(index, proposal, &manager.tally)?;
+            manager.votes_by_voters = proposal_checkpoint
+                .voters
+                .iter()
+                .map(|id| (id.clone(), ()))
+                .collect();
+            for (voter, ballot) in &proposal_checkpoint.ballots {
+                if !manager.votes_by_voters.contains_key(voter) {
+                    return Err(CheckpointError::InvalidBallot { index });
+                }
+                self.proposal_managers
+                    .validate_ballot(index, ballot)
+                    .map_err(|_| CheckpointError::InvalidBallot { index })?;
+            }
+            manager.ballots = proposal_checkpoint.ballots.iter().cloned().collect();
+        }
+
+        Ok(Self {
+            proposal_managers,
+            plan: Arc::clone(&self.plan),
+            id: self.id.clone(),
+            committee: Arc::clone(&self.committee),
+            delegated_votes_counted: checkpoint.delegated_votes_counted(),
+        })
+    }
+
     pub fn can_vote(&self, date: BlockDate) -> bool {
         self.plan().can_vote(date)
     }
@@ -755,6 +1132,28 @@ impl VotePlanManager {
         identifier: account::Identifier,
         cast: VoteCast,
         token_distribution: TokenDistribution<()>,
+        delegations: &VoteDelegations,
+    ) -> Result<Self, VoteError> {
+        self.vote_with_verified_ballot(
+            block_date,
+            identifier,
+            cast,
+            None,
+            token_distribution,
+            delegations,
+        )
+    }
+
+    /// same as [`Self::vote`], but the proof of a private vote is not
+    /// verified again if `verified_ballot` is the ballot of its encrypted vote
+    pub fn vote_with_verified_ballot(
+        &self,
+        block_date: BlockDate,
+        identifier: account::Identifier,
+        cast: VoteCast,
+        verified_ballot: Option<Ballot>,
+        token_distribution: TokenDistribution<()>,
+        delegations: &VoteDelegations,
     ) -> Result<Self, VoteError> {
         if cast.vote_plan() != self.id() {
             return Err(VoteError::InvalidVotePlan {
@@ -777,22 +1176,73 @@ impl VotePlanManager {
             });
         }
 
-        let vote = self.proposal_managers.validate_vote(&identifier, cast)?;
+        let vote = self
+            .proposal_managers
+            .validate_vote(&identifier, cast, verified_ballot)?;
+        let token_distribution = token_distribution.token(self.plan.voting_token());
+        // only the delegators with voting power make up for the lack of
+        // voting power of the voter
+        let has_delegators = delegations
+            .delegators_for(&identifier, &self.plan)
+            .into_iter()
+            .any(|(delegator, share)| {
+                token_distribution
+                    .get_account(&delegator)
+                    .ok()
+                    .flatten()
+                    .map_or(false, |stake| share.apply(stake.0) > 0)
+            });
 
-        let proposal_managers = self.proposal_managers.vote(
-            identifier,
-            vote,
-            &token_distribution.token(self.plan.voting_token()),
-        )?;
+        let proposal_managers =
+            self.proposal_managers
+                .vote(identifier, vote, &token_distribution, has_delegators)?;
 
         Ok(Self {
             proposal_managers,
             plan: Arc::clone(&self.plan),
             id: self.id.clone(),
             committee: Arc::clone(&self.committee),
+            delegated_votes_counted: self.delegated_votes_counted,
         })
     }
 
+    /// whether the voting power delegated to the voters was already added
+    /// to the tallies, see [`Self::count_delegated_votes`]
+    pub fn delegated_votes_counted(&self) -> bool {
+        self.delegated_votes_counted
+    }
+
+    /// add the voting power delegated to the voters to the tally of every
+    /// proposal, once the voting period is over
+    ///
+    /// The delegations and the voting power are the ones of the ledger at the
+    /// end of the voting period, so the result does not depend on the order
+    /// of the votes and of the delegations. This is done only once, the vote
+    /// plan is returned unchanged if the voting period is not over or if the
+    /// delegated voting power was already counted.
+    pub fn count_delegated_votes(
+        &self,
+        block_date: BlockDate,
+        token_distribution: TokenDistribution<()>,
+        delegations: &VoteDelegations,
+    ) -> Self {
+        if self.delegated_votes_counted || block_date < self.plan().vote_end() {
+            return self.clone();
+        }
+
+        Self {
+            proposal_managers: self.proposal_managers.count_delegated_votes(
+                &self.plan,
+                &token_distribution.token(self.plan.voting_token()),
+                delegations,
+            ),
+            plan: Arc::clone(&self.plan),
+            id: self.id.clone(),
+            committee: Arc::clone(&self.committee),
+            delegated_votes_counted: true,
+        }
+    }
+
     pub fn public_tally<F>(
         &self,
         block_date: BlockDate,
@@ -830,6 +1280,7 @@ impl VotePlanManager {
             plan: Arc::clone(&self.plan),
             id: self.id.clone(),
             committee: Arc::clone(&self.committee),
+            delegated_votes_counted: self.delegated_votes_counted,
         })
     }
 
@@ -860,6 +1311,7 @@ impl VotePlanManager {
 
         let proposal_managers = self.proposal_managers.finalize_private_tally(
             committee_pks,
+            self.plan.committee_threshold(),
             decrypted_tally,
             governance,
             &token_distribution.token(self.plan.voting_token()),
@@ -870,6 +1322,7 @@ impl VotePlanManager {
             plan: Arc::clone(&self.plan),
             id: self.id.clone(),
             committee: Arc::clone(&self.committee),
+            delegated_votes_counted: self.delegated_votes_counted,
         })
     }
 }
@@ -906,6 +1359,7 @@ mod tests {
 
         let identifier = TestGen::identifier();
 
+        let cast_payload = vote_cast.payload().clone();
         let vote = proposal_manager
             .validate_public_vote(&identifier, vote_cast)
             .unwrap();
@@ -921,7 +1375,7 @@ mod tests {
             TokenDistribution::new(&token_totals, &account_ledger).token(vote_plan.voting_token());
 
         proposal_manager = proposal_manager
-            .vote(identifier, vote, &token_distribution)
+            .vote(identifier, vote, cast_payload, &token_distribution, false)
             .unwrap();
 
         let tally = match proposal_manager.tally {
@@ -984,7 +1438,8 @@ mod tests {
                     &identifier,
                     vote_cast,
                     committee_manager.crs(),
-                    &committee_manager.election_pk()
+                    &committee_manager.election_pk(),
+                    None
                 )
                 .err()
                 .unwrap(),
@@ -1012,7 +1467,9 @@ mod tests {
             BlockDate::from_epoch_slot_id(3, 0),
             proposals,
             PayloadType::Public,
+            None,
             Vec::new(),
+            None,
             TokenIdentifier {
                 policy_hash: PolicyHash::from([0u8; POLICY_HASH_SIZE]),
                 token_name: TokenName::try_from(vec![0u8; TOKEN_NAME_MAX_SIZE]).unwrap(),
@@ -1056,7 +1513,9 @@ mod tests {
             BlockDate::from_epoch_slot_id(3, 0),
             proposals,
             PayloadType::Public,
+            None,
             Vec::new(),
+            None,
             TokenIdentifier {
                 policy_hash: PolicyHash::from([0u8; POLICY_HASH_SIZE]),
                 token_name: TokenName::try_from(vec![0u8; TOKEN_NAME_MAX_SIZE]).unwrap(),
@@ -1090,6 +1549,7 @@ mod tests {
                 committee.public_key().into(),
                 vote_cast,
                 token_distribution.clone(),
+                &VoteDelegations::new(),
             )
             .unwrap();
 
@@ -1133,7 +1593,9 @@ mod tests {
             BlockDate::from_epoch_slot_id(3, 0),
             proposals,
             PayloadType::Public,
+            None,
             Vec::new(),
+            None,
             TokenIdentifier {
                 policy_hash: PolicyHash::from([0u8; POLICY_HASH_SIZE]),
                 token_name: TokenName::try_from(vec![0u8; TOKEN_NAME_MAX_SIZE]).unwrap(),
@@ -1196,7 +1658,9 @@ mod tests {
             BlockDate::from_epoch_slot_id(3, 0),
             proposals,
             PayloadType::Private,
+            None,
             members.members_keys(),
+            None,
             TokenIdentifier {
                 policy_hash: PolicyHash::from([0u8; POLICY_HASH_SIZE]),
                 token_name: TokenName::try_from(vec![0u8; TOKEN_NAME_MAX_SIZE]).unwrap(),
@@ -1246,6 +1710,312 @@ mod tests {
         );
     }
 
+    /// run the distributed key generation of a `threshold` out of `members` committee
+    fn threshold_committee(threshold: usize, members: usize) -> Vec<chain_vote::dkg::DkgOutput> {
+        use chain_vote::{dkg::DkgMember, MemberCommunicationKey};
+        use rand_chacha::ChaCha20Rng;
+        use rand_core::SeedableRng;
+
+        let mut rng = ChaCha20Rng::from_seed([0u8; 32]);
+        let keys: Vec<_> = (0..members)
+            .map(|_| MemberCommunicationKey::new(&mut rng))
+            .collect();
+        let pks: Vec<_> = keys.iter().map(|key| key.to_public()).collect();
+        let (mut states, dealings): (Vec<_>, Vec<_>) = keys
+            .iter()
+            .enumerate()
+            .map(|(i, key)| DkgMember::init(&mut rng, threshold, &pks, key, i).unwrap())
+            .unzip();
+        let complaints: Vec<_> = states
+            .iter_mut()
+            .map(|state| state.handle_dealings(&dealings).unwrap())
+            .collect();
+        let disputes: Vec<_> = states
+            .iter_mut()
+            .map(|state| state.handle_complaints(&complaints).unwrap())
+            .collect();
+        states
+            .iter_mut()
+            .map(|state| state.handle_disputes(&disputes).unwrap())
+            .collect()
+    }
+
+    #[test]
+    pub fn vote_plan_manager_tally_threshold_private() {
+        use rand_chacha::ChaCha20Rng;
+        use rand_core::SeedableRng;
+
+        let blank = Choice::new(0);
+        let favorable = Choice::new(1);
+        let rejection = Choice::new(2);
+        let committee = Wallet::from_value(Value(100));
+        let proposals = VoteTestGen::proposals(3);
+
+        // a 2-of-3 committee
+        let members = threshold_committee(2, 3);
+        let committee_keys = members[0].member_public_keys().to_vec();
+
+        let vote_start = BlockDate::from_epoch_slot_id(1, 0);
+        let vote_end = BlockDate::from_epoch_slot_id(2, 0);
+        let vote_plan = VotePlan::new(
+            vote_start,
+            vote_end,
+            BlockDate::from_epoch_slot_id(3, 0),
+            proposals,
+            PayloadType::Private,
+            None,
+            committee_keys.clone(),
+            NonZeroU8::new(2),
+            TokenIdentifier {
+                policy_hash: PolicyHash::from([0u8; POLICY_HASH_SIZE]),
+                token_name: TokenName::try_from(vec![0u8; TOKEN_NAME_MAX_SIZE]).unwrap(),
+            },
+        );
+        assert_eq!(
+            &vote_plan.election_public_key(),
+            members[0].election_public_key()
+        );
+
+        let mut committee_ids = HashSet::new();
+        committee_ids.insert(committee.public_key().into());
+
+        let governance = governance_50_percent(blank, favorable, rejection);
+
+        let identifier = TestGen::identifier();
+        let (token_totals, account_ledger, _) = ledger_with_tokens(identifier.clone());
+
+        let mut rng = ChaCha20Rng::from_seed([1u8; 32]);
+        let vote_plan_manager = VotePlanManager::new(vote_plan.clone(), committee_ids)
+            .vote(
+                BlockDate::from_epoch_slot_id(1, 10),
+                identifier,
+                VoteCast::new(
+                    vote_plan.to_id(),
+                    0,
+                    VoteTestGen::private_vote_cast_payload_for(
+                        &vote_plan,
+                        vote_plan.proposals().get(0).unwrap(),
+                        favorable,
+                        &mut rng,
+                    ),
+                ),
+                TokenDistribution::new(&token_totals, &account_ledger),
+                &VoteDelegations::new(),
+            )
+            .unwrap();
+
+        let tally_proof = get_tally_proof(vote_start, &committee, vote_plan.to_id());
+        let committee_id = match tally_proof {
+            TallyProof::Public { id, .. } => id,
+            TallyProof::Private { id, .. } => id,
+        };
+
+        let block_date = BlockDate {
+            epoch: 2,
+            slot_id: 10,
+        };
+
+        // the share of every member for every proposal
+        let encrypted_tallies: Vec<_> = vote_plan_manager
+            .statuses()
+            .proposals
+            .iter()
+            .map(|proposal| proposal.tally.private_encrypted().unwrap().clone())
+            .collect();
+        let shares: Vec<Vec<_>> = encrypted_tallies
+            .iter()
+            .map(|encrypted_tally| {
+                members
+                    .iter()
+                    .map(|member| encrypted_tally.partial_decrypt(&mut rng, member.secret_key()))
+                    .collect()
+            })
+            .collect();
+
+        // the first and the last members decrypt the tally without the second one
+        let validated_tallies = encrypted_tallies
+            .iter()
+            .zip(shares.iter())
+            .map(|(encrypted_tally, shares)| {
+                encrypted_tally
+                    .validate_threshold_partial_decryptions(
+                        2,
+                        &committee_keys,
+                        &[Some(shares[0].clone()), None, Some(shares[2].clone())],
+                    )
+                    .unwrap()
+            })
+            .collect();
+        let results = chain_vote::tally::batch_decrypt(validated_tallies).unwrap();
+        assert_eq!(results[0].votes, vec![0, 51, 0]);
+
+        // `positions[i]` is the member whose share is given at the position of the member `i`
+        let with_shares = |positions: [Option<usize>; 3]| {
+            DecryptedPrivateTally::new(
+                shares
+                    .iter()
+                    .zip(results.iter())
+                    .map(|(shares, result)| DecryptedPrivateTallyProposal {
+                        decrypt_shares: positions
+                            .iter()
+                            .map(|member| member.map(|member| shares[member].clone()))
+                            .collect(),
+                        tally_result: result.votes.clone().into_boxed_slice(),
+                    })
+                    .collect(),
+            )
+            .unwrap()
+        };
+        let tally = |decrypted: &DecryptedPrivateTally| {
+            vote_plan_manager.private_tally(
+                block_date,
+                decrypted,
+                &governance,
+                committee_id.clone(),
+                TokenDistribution::new(&token_totals, &account_ledger),
+                |_| (),
+            )
+        };
+
+        // any `threshold` members or more can decrypt
+        for positions in [
+            [Some(0), None, Some(2)],
+            [Some(0), Some(1), None],
+            [None, Some(1), Some(2)],
+            [Some(0), Some(1), Some(2)],
+        ] {
+            assert!(tally(&with_shares(positions)).is_ok());
+        }
+
+        let invalid_decryption = VoteError::CannotTallyVotes {
+            source: TallyError::InvalidDecryption,
+        };
+        // a single share is not enough
+        assert_eq!(
+            invalid_decryption,
+            tally(&with_shares([None, Some(1), None])).err().unwrap()
+        );
+        // a share given for another member
+        assert_eq!(
+            invalid_decryption,
+            tally(&with_shares([Some(2), None, Some(0)])).err().unwrap()
+        );
+    }
+
+    #[test]
+    pub fn vote_plan_manager_weighted_private_vote() {
+        use chain_vote::WeightedVote;
+        use rand_chacha::ChaCha20Rng;
+        use rand_core::SeedableRng;
+
+        let members = VoteTestGen::committee_members_manager(1, 1);
+        let committee_keys = members
+            .members()
+            .iter()
+            .map(|member| member.public_key())
+            .collect();
+        let kind = WeightedBallotKind::Budget {
+            budget: NonZeroU8::new(5).unwrap(),
+        };
+
+        let vote_plan = VotePlan::new(
+            BlockDate::from_epoch_slot_id(1, 0),
+            BlockDate::from_epoch_slot_id(2, 0),
+            BlockDate::from_epoch_slot_id(3, 0),
+            VoteTestGen::proposals(1),
+            PayloadType::PrivateWeighted,
+            Some(kind),
+            committee_keys,
+            None,
+            TokenIdentifier {
+                policy_hash: PolicyHash::from([0u8; POLICY_HASH_SIZE]),
+                token_name: TokenName::try_from(vec![0u8; TOKEN_NAME_MAX_SIZE]).unwrap(),
+            },
+        );
+        let vote_plan_manager = VotePlanManager::new(vote_plan.clone(), HashSet::new());
+
+        let identifier = TestGen::identifier();
+        let (token_totals, account_ledger, _) = ledger_with_tokens(identifier.clone());
+        let block_date = BlockDate::from_epoch_slot_id(1, 10);
+
+        let mut rng = ChaCha20Rng::from_seed([0u8; 32]);
+        let election_pk = vote_plan.election_public_key();
+        let crs = Crs::from_hash(vote_plan.to_id().as_ref());
+        let weighted_cast = |weights: Vec<u8>, rng: &mut ChaCha20Rng| {
+            let vote = WeightedVote::new(kind, weights).unwrap();
+            let (encrypted_vote, proof) =
+                vote::encrypt_weighted_vote(rng, &crs, &election_pk, &vote).unwrap();
+            VoteCast::new(
+                vote_plan.to_id(),
+                0,
+                Payload::private_weighted(encrypted_vote, proof),
+            )
+        };
+
+        // a ballot for another number of options is rejected
+        assert_eq!(
+            VoteError::PrivateVoteInvalidSize {
+                expected: kind.encrypted_vote_len(4),
+                actual: kind.encrypted_vote_len(3),
+            },
+            vote_plan_manager
+                .vote(
+                    block_date,
+                    identifier.clone(),
+                    weighted_cast(vec![1, 1, 1], &mut rng),
+                    TokenDistribution::new(&token_totals, &account_ledger),
+                    &VoteDelegations::new(),
+                )
+                .err()
+                .unwrap()
+        );
+
+        // a single choice private ballot is rejected
+        let (encrypted_vote, proof) = vote::encrypt_vote(
+            &mut rng,
+            &Crs::from_hash(vote_plan.to_id().as_ref()),
+            &election_pk,
+            chain_vote::Vote::new(4, 1).unwrap(),
+        );
+        assert_eq!(
+            VoteError::InvalidPayloadType {
+                received: PayloadType::Private,
+                expected: PayloadType::PrivateWeighted,
+            },
+            vote_plan_manager
+                .vote(
+                    block_date,
+                    identifier.clone(),
+                    VoteCast::new(
+                        vote_plan.to_id(),
+                        0,
+                        Payload::private(encrypted_vote, proof)
+                    ),
+                    TokenDistribution::new(&token_totals, &account_ledger),
+                    &VoteDelegations::new(),
+                )
+                .err()
+                .unwrap()
+        );
+
+        let vote_plan_manager = vote_plan_manager
+            .vote(
+                block_date,
+                identifier,
+                weighted_cast(vec![2, 0, 3, 0], &mut rng),
+                TokenDistribution::new(&token_totals, &account_ledger),
+                &VoteDelegations::new(),
+            )
+            .unwrap();
+
+        // the stake of the voter (51) is spread according to the weights of the ballot
+        let shares = decrypt_tally(&vote_plan_manager.statuses(), &members).unwrap();
+        assert_eq!(
+            shares.iter().next().unwrap().tally_result.as_ref(),
+            &[102, 0, 153, 0]
+        );
+    }
+
     #[test]
     pub fn vote_plan_manager_tally_invalid_date_public() {
         let (
@@ -1343,7 +2113,9 @@ mod tests {
             BlockDate::from_epoch_slot_id(3, 0),
             proposals,
             payload_type,
+            None,
             members.members_keys(),
+            None,
             TokenIdentifier {
                 policy_hash: PolicyHash::from([0u8; POLICY_HASH_SIZE]),
                 token_name: TokenName::try_from(vec![0u8; TOKEN_NAME_MAX_SIZE]).unwrap(),
@@ -1457,7 +2229,9 @@ mod tests {
             BlockDate::from_epoch_slot_id(3, 0),
             proposals,
             PayloadType::Public,
+            None,
             Vec::new(),
+            None,
             TokenIdentifier {
                 policy_hash: PolicyHash::from([0u8; POLICY_HASH_SIZE]),
                 token_name: TokenName::try_from(vec![0u8; TOKEN_NAME_MAX_SIZE]).unwrap(),
@@ -1484,13 +2258,16 @@ mod tests {
                     0,
                     VoteTestGen::vote_cast_payload_for(&favorable),
                 ),
+                None,
             )
             .unwrap();
         first_proposal_manager = first_proposal_manager
             .vote(
                 identifier.clone(),
                 first_vote_cast.payload.clone(),
+                first_vote_cast.cast_payload.clone(),
                 &token_distribution,
+                false,
             )
             .unwrap();
 
@@ -1502,18 +2279,26 @@ mod tests {
                     1,
                     VoteTestGen::vote_cast_payload_for(&favorable),
                 ),
+                None,
             )
             .unwrap();
         second_proposal_manager = second_proposal_manager
             .vote(
                 identifier.clone(),
                 second_vote_cast.payload.clone(),
+                second_vote_cast.cast_payload.clone(),
                 &token_distribution,
+                false,
             )
             .unwrap();
 
-        let _ = proposals.vote(identifier.clone(), first_vote_cast, &token_distribution);
-        let _ = proposals.vote(identifier, second_vote_cast, &token_distribution);
+        let _ = proposals.vote(
+            identifier.clone(),
+            first_vote_cast,
+            &token_distribution,
+            false,
+        );
+        let _ = proposals.vote(identifier, second_vote_cast, &token_distribution, false);
 
         let governance = governance_50_percent(blank, favorable, rejection);
 
@@ -1611,10 +2396,10 @@ mod tests {
         let identifier = TestGen::identifier();
 
         let first_vote_cast_validated = proposal_managers
-            .validate_vote(&identifier, first_vote_cast)
+            .validate_vote(&identifier, first_vote_cast, None)
             .unwrap();
         let second_vote_cast_validated = proposal_managers
-            .validate_vote(&identifier, second_vote_cast)
+            .validate_vote(&identifier, second_vote_cast, None)
             .unwrap();
 
         let account_ledger = account::Ledger::default()
@@ -1632,10 +2417,16 @@ mod tests {
                 identifier.clone(),
                 first_vote_cast_validated,
                 &token_distribution,
+                false,
             )
             .unwrap();
         proposal_managers = proposal_managers
-            .vote(identifier, second_vote_cast_validated, &token_distribution)
+            .vote(
+                identifier,
+                second_vote_cast_validated,
+                &token_distribution,
+                false,
+            )
             .unwrap();
 
         for manager in proposal_managers.managers() {
@@ -1657,6 +2448,7 @@ mod tests {
             .validate_vote(
                 &identifier,
                 VoteCast::new(vote_plan.to_id(), 2, VoteTestGen::vote_cast_payload()),
+                None,
             )
             .is_err());
     }
@@ -1677,12 +2469,14 @@ mod tests {
             .validate_vote(
                 &identifier,
                 VoteCast::new(vote_plan.to_id(), 0, first_vote_cast_payload),
+                None,
             )
             .unwrap();
         let second_vote_cast = proposal_managers
             .validate_vote(
                 &identifier,
                 VoteCast::new(vote_plan.to_id(), 0, second_vote_cast_payload),
+                None,
             )
             .unwrap();
 
@@ -1697,11 +2491,16 @@ mod tests {
             TokenDistribution::new(&token_totals, &account_ledger).token(vote_plan.voting_token());
 
         proposal_managers = proposal_managers
-            .vote(identifier.clone(), first_vote_cast, &token_distribution)
+            .vote(
+                identifier.clone(),
+                first_vote_cast,
+                &token_distribution,
+                false,
+            )
             .unwrap();
 
         assert!(proposal_managers
-            .vote(identifier, second_vote_cast, &token_distribution)
+            .vote(identifier, second_vote_cast, &token_distribution, false)
             .is_err());
 
         let tally = match &proposal_managers.managers()[0].tally {
@@ -1770,7 +2569,8 @@ mod tests {
                     BlockDate::first(),
                     TestGen::identifier(),
                     vote_cast.clone(),
-                    token_distribution
+                    token_distribution,
+                    &VoteDelegations::new(),
                 )
                 .err()
                 .unwrap(),
@@ -1798,6 +2598,7 @@ mod tests {
                     TestGen::identifier(),
                     vote_cast.clone(),
                     token_distribution,
+                    &VoteDelegations::new(),
                 )
                 .err()
                 .unwrap(),
@@ -1817,7 +2618,9 @@ mod tests {
             BlockDate::from_epoch_slot_id(3, 0),
             VoteTestGen::proposals(3),
             PayloadType::Public,
+            None,
             Vec::new(),
+            None,
             TokenIdentifier {
                 policy_hash: PolicyHash::from([0u8; POLICY_HASH_SIZE]),
                 token_name: TokenName::try_from(vec![0u8; TOKEN_NAME_MAX_SIZE]).unwrap(),
@@ -1837,7 +2640,8 @@ mod tests {
                     BlockDate::first(),
                     TestGen::identifier(),
                     vote_cast.clone(),
-                    token_distribution
+                    token_distribution,
+                    &VoteDelegations::new(),
                 )
                 .err()
                 .unwrap(),
@@ -1861,7 +2665,9 @@ mod tests {
             BlockDate::from_epoch_slot_id(3, 0),
             VoteTestGen::proposals(3),
             PayloadType::Public,
+            None,
             Vec::new(),
+            None,
             token_id.clone(),
         );
 
@@ -1884,10 +2690,199 @@ mod tests {
                 account,
                 vote_cast,
                 token_distribution,
+                &VoteDelegations::new(),
             )
             .unwrap();
     }
 
+    #[test]
+    pub fn vote_manager_checkpoint_restore() {
+        let token_id = TokenIdentifier {
+            policy_hash: PolicyHash::from([0u8; POLICY_HASH_SIZE]),
+            token_name: TokenName::try_from(vec![0u8; TOKEN_NAME_MAX_SIZE]).unwrap(),
+        };
+        let vote_plan = VotePlan::new(
+            BlockDate::from_epoch_slot_id(1, 0),
+            BlockDate::from_epoch_slot_id(2, 0),
+            BlockDate::from_epoch_slot_id(3, 0),
+            VoteTestGen::proposals(3),
+            PayloadType::Public,
+            None,
+            Vec::new(),
+            None,
+            token_id.clone(),
+        );
+
+        let vote_cast = VoteCast::new(vote_plan.to_id(), 0, VoteTestGen::vote_cast_payload());
+
+        let account = TestGen::identifier();
+        let token_totals = Default::default();
+        let account_ledger = account::Ledger::default()
+            .add_account(account.clone(), Value(1_000), ())
+            .unwrap()
+            .token_add(&account, token_id, Value(1_000))
+            .unwrap();
+
+        let vote_plan_manager = VotePlanManager::new(vote_plan, HashSet::new());
+        let voted = vote_plan_manager
+            .vote(
+                BlockDate::from_epoch_slot_id(1, 1),
+                account.clone(),
+                vote_cast.clone(),
+                TokenDistribution::new(&token_totals, &account_ledger),
+                &VoteDelegations::new(),
+            )
+            .unwrap();
+
+        let checkpoint = voted.checkpoint();
+        assert_eq!(checkpoint.votes_count(), 1);
+
+        let restored = vote_plan_manager.restore(&checkpoint).unwrap();
+        assert_eq!(restored.checkpoint(), checkpoint);
+        assert_eq!(
+            restored.statuses().proposals[0].tally,
+            voted.statuses().proposals[0].tally
+        );
+
+        // the ballots are dropped once the delegated voting power is counted,
+        // the voters are kept
+        let counted = voted.count_delegated_votes(
+            BlockDate::from_epoch_slot_id(2, 0),
+            TokenDistribution::new(&token_totals, &account_ledger),
+            &VoteDelegations::new(),
+        );
+        let counted_checkpoint = counted.checkpoint();
+        assert_eq!(counted_checkpoint.votes_count(), 1);
+        assert!(counted_checkpoint
+            .proposals()
+            .iter()
+            .all(|proposal| proposal.ballots.is_empty()));
+        assert_eq!(
+            vote_plan_manager.restore(&counted_checkpoint).unwrap(),
+            counted
+        );
+
+        // the voters are restored along the tally
+        assert_eq!(
+            restored
+                .vote(
+                    BlockDate::from_epoch_slot_id(1, 2),
+                    account,
+                    vote_cast,
+                    TokenDistribution::new(&token_totals, &account_ledger),
+                    &VoteDelegations::new(),
+                )
+                .err(),
+            Some(VoteError::AlreadyVoted)
+        );
+    }
+
+    #[test]
+    pub fn vote_manager_private_checkpoint_restore() {
+        use chain_core::{
+            packer::Codec,
+            property::{DeserializeFromSlice, Serialize},
+        };
+        use rand_chacha::ChaCha20Rng;
+        use rand_core::SeedableRng;
+
+        let members = VoteTestGen::committee_members_manager(1, 1);
+        let private_vote_plan = |vote_start| {
+            VotePlan::new(
+                vote_start,
+                BlockDate::from_epoch_slot_id(2, 0),
+                BlockDate::from_epoch_slot_id(3, 0),
+                VoteTestGen::proposals(2),
+                PayloadType::Private,
+                None,
+                members
+                    .members()
+                    .iter()
+                    .map(|member| member.public_key())
+                    .collect(),
+                None,
+                TokenIdentifier {
+                    policy_hash: PolicyHash::from([0u8; POLICY_HASH_SIZE]),
+                    token_name: TokenName::try_from(vec![0u8; TOKEN_NAME_MAX_SIZE]).unwrap(),
+                },
+            )
+        };
+        let vote_plan = private_vote_plan(BlockDate::from_epoch_slot_id(1, 0));
+
+        let identifier = TestGen::identifier();
+        let (token_totals, account_ledger, _) = ledger_with_tokens(identifier.clone());
+        let mut rng = ChaCha20Rng::from_seed([0u8; 32]);
+
+        let vote_plan_manager = VotePlanManager::new(vote_plan.clone(), HashSet::new());
+        let voted = vote_plan_manager
+            .vote(
+                BlockDate::from_epoch_slot_id(1, 10),
+                identifier,
+                VoteCast::new(
+                    vote_plan.to_id(),
+                    1,
+                    VoteTestGen::private_vote_cast_payload_for(
+                        &vote_plan,
+                        vote_plan.proposals().get(1).unwrap(),
+                        Choice::new(2),
+                        &mut rng,
+                    ),
+                ),
+                TokenDistribution::new(&token_totals, &account_ledger),
+                &VoteDelegations::new(),
+            )
+            .unwrap();
+
+        let bytes = voted.checkpoint().serialize_as_vec().unwrap();
+        let checkpoint =
+            VotePlanCheckpoint::deserialize_from_slice(&mut Codec::new(bytes.as_slice())).unwrap();
+        assert_eq!(checkpoint, voted.checkpoint());
+
+        // the encrypted tallies and the ballots are restored
+        let restored = vote_plan_manager.restore(&checkpoint).unwrap();
+        assert!(restored == voted);
+        let results = |manager: &VotePlanManager| {
+            decrypt_tally(&manager.statuses(), &members)
+                .unwrap()
+                .iter()
+                .map(|proposal| proposal.tally_result.clone())
+                .collect::<Vec<_>>()
+        };
+        assert_eq!(results(&restored), results(&voted));
+
+        // a ballot whose proof does not hold for this vote plan is rejected
+        let other_vote_plan = private_vote_plan(BlockDate::from_epoch_slot_id(1, 1));
+        let mut proposals = checkpoint.proposals().to_vec();
+        proposals[1].ballots[0].1 = VoteTestGen::private_vote_cast_payload_for(
+            &other_vote_plan,
+            other_vote_plan.proposals().get(1).unwrap(),
+            Choice::new(2),
+            &mut rng,
+        );
+        assert_eq!(
+            vote_plan_manager
+                .restore(&VotePlanCheckpoint::new(
+                    checkpoint.id().clone(),
+                    proposals,
+                    checkpoint.delegated_votes_counted(),
+                ))
+                .err(),
+            Some(CheckpointError::InvalidBallot { index: 1 })
+        );
+    }
+
+    #[test]
+    pub fn vote_manager_restore_checkpoint_of_another_vote_plan() {
+        let vote_plan_manager = VotePlanManager::new(VoteTestGen::vote_plan(), HashSet::new());
+        let other_vote_plan_manager =
+            VotePlanManager::new(VoteTestGen::vote_plan(), HashSet::new());
+
+        assert!(matches!(
+            vote_plan_manager.restore(&other_vote_plan_manager.checkpoint()),
+            Err(CheckpointError::InvalidVotePlan { .. })
+        ));
+    }
+
     #[test]
     pub fn vote_manager_zero_tokens_vote_should_fail() {
         let token_id = TokenIdentifier {
@@ -1900,7 +2895,9 @@ mod tests {
             BlockDate::from_epoch_slot_id(3, 0),
             VoteTestGen::proposals(3),
             PayloadType::Public,
+            None,
             Vec::new(),
+            None,
             token_id,
         );
 
@@ -1921,6 +2918,7 @@ mod tests {
                 account,
                 vote_cast,
                 token_distribution,
+                &VoteDelegations::new(),
             ),
             Err(VoteError::ZeroVotingPower)
         ))
@@ -3,8 +3,10 @@
 //! module).
 //!
 
+mod checkpoint;
 mod choice;
 mod committee;
+mod delegation;
 mod ledger;
 mod manager;
 mod payload;
@@ -13,12 +15,17 @@ mod status;
 mod tally;
 
 pub use self::{
+    checkpoint::{CheckpointError, VotePlanCheckpoint},
     choice::{Choice, Options},
     committee::CommitteeId,
+    delegation::{VoteDelegationRatio, VoteDelegationScope, VoteDelegationType, VoteDelegations},
     ledger::{VotePlanLedger, VotePlanLedgerError},
     manager::{ValidatedPayload, VoteError, VotePlanManager},
-    payload::{EncryptedVote, Payload, PayloadType, ProofOfCorrectVote, TryFromIntError},
-    privacy::encrypt_vote,
+    payload::{
+        EncryptedVote, EncryptedWeightedVote, Payload, PayloadType, ProofOfCorrectVote,
+        ProofOfCorrectWeightedVote, TryFromIntError, WeightedVoteTooLarge,
+    },
+    privacy::{encrypt_vote, encrypt_weighted_vote},
     status::{VotePlanStatus, VoteProposalStatus},
     tally::{PrivateTallyState, Tally, TallyError, TallyResult, Weight},
 };
@@ -1,6 +1,6 @@
 use crate::vote::Choice;
 use chain_core::packer::Codec;
-use chain_core::property::ReadError;
+use chain_core::property::{DeserializeFromSlice, ReadError, Serialize, WriteError};
 use chain_vote::Ciphertext;
 use std::hash::Hash;
 use thiserror::Error;
@@ -21,6 +21,9 @@ use typed_bytes::{ByteArray, ByteBuilder};
 pub enum PayloadType {
     Public = 1,
     Private = 2,
+    /// private votes spreading several selections, or a budget, over the options
+    /// of a proposal, see [`chain_vote::WeightedBallotKind`]
+    PrivateWeighted = 3,
 }
 
 #[derive(Debug, Clone, PartialEq, Eq, Hash)]
@@ -32,6 +35,10 @@ pub enum Payload {
         encrypted_vote: EncryptedVote,
         proof: ProofOfCorrectVote,
     },
+    PrivateWeighted {
+        encrypted_vote: EncryptedWeightedVote,
+        proof: ProofOfCorrectWeightedVote,
+    },
 }
 
 #[derive(Debug, Clone, PartialEq, Eq, Hash)]
@@ -40,6 +47,16 @@ pub struct ProofOfCorrectVote(chain_vote::ProofOfCorrectVote);
 #[derive(Debug, Clone, PartialEq, Eq, Hash)]
 pub struct EncryptedVote(chain_vote::EncryptedVote);
 
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct ProofOfCorrectWeightedVote(chain_vote::ProofOfCorrectWeightedVote);
+
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct EncryptedWeightedVote(chain_vote::EncryptedWeightedVote);
+
+#[derive(Debug, Error, Clone, PartialEq, Eq)]
+#[error("an encrypted weighted vote has {0} ciphertexts, at most {max} are supported", max = EncryptedWeightedVote::MAX_LEN)]
+pub struct WeightedVoteTooLarge(pub usize);
+
 #[derive(Debug, Error)]
 pub enum TryFromIntError {
     #[error("Found a `0` PayloadType. This is unexpected and known to be an error to read a 0.")]
@@ -60,10 +77,21 @@ impl Payload {
         }
     }
 
+    pub fn private_weighted(
+        encrypted_vote: EncryptedWeightedVote,
+        proof: ProofOfCorrectWeightedVote,
+    ) -> Self {
+        Self::PrivateWeighted {
+            encrypted_vote,
+            proof,
+        }
+    }
+
     pub fn payload_type(&self) -> PayloadType {
         match self {
             Self::Public { .. } => PayloadType::Public,
             Self::Private { .. } => PayloadType::Private,
+            Self::PrivateWeighted { .. } => PayloadType::PrivateWeighted,
         }
     }
 
@@ -80,6 +108,12 @@ impl Payload {
             } => bb
                 .sub(|bb| encrypted_vote.serialize_in(bb))
                 .sub(|bb| proof.serialize_in(bb)),
+            Self::PrivateWeighted {
+                encrypted_vote,
+                proof,
+            } => bb
+                .sub(|bb| encrypted_vote.serialize_in(bb))
+                .sub(|bb| proof.serialize_in(bb)),
         }
     }
 
@@ -99,10 +133,31 @@ impl Payload {
                     proof,
                 })
             }
+            PayloadType::PrivateWeighted => {
+                let encrypted_vote = EncryptedWeightedVote::read(codec)?;
+                let proof = ProofOfCorrectWeightedVote::read(codec)?;
+                Ok(Self::PrivateWeighted {
+                    encrypted_vote,
+                    proof,
+                })
+            }
         }
     }
 }
 
+impl Serialize for Payload {
+    fn serialize<W: std::io::Write>(&self, codec: &mut Codec<W>) -> Result<(), WriteError> {
+        let bb: ByteBuilder<Self> = self.serialize_in(ByteBuilder::new());
+        codec.put_bytes(bb.finalize().as_slice())
+    }
+}
+
+impl DeserializeFromSlice for Payload {
+    fn deserialize_from_slice(codec: &mut Codec<&[u8]>) -> Result<Self, ReadError> {
+        Self::read(codec)
+    }
+}
+
 impl ProofOfCorrectVote {
     pub(crate) fn from_inner(proof: chain_vote::ProofOfCorrectVote) -> Self {
         assert!(
@@ -169,6 +224,72 @@ impl EncryptedVote {
     }
 }
 
+impl ProofOfCorrectWeightedVote {
+    pub(crate) fn from_inner(proof: chain_vote::ProofOfCorrectWeightedVote) -> Self {
+        Self(proof)
+    }
+
+    pub(super) fn as_inner(&self) -> &chain_vote::ProofOfCorrectWeightedVote {
+        &self.0
+    }
+
+    pub(crate) fn serialize_in(&self, bb: ByteBuilder<Self>) -> ByteBuilder<Self> {
+        let bytes = self.0.to_bytes();
+        debug_assert!(bytes.len() <= u16::MAX as usize);
+        bb.u16(bytes.len() as u16).bytes(&bytes)
+    }
+
+    pub fn serialize(&self) -> ByteArray<Self> {
+        self.serialize_in(ByteBuilder::new()).finalize()
+    }
+
+    pub(crate) fn read(codec: &mut Codec<&[u8]>) -> Result<Self, ReadError> {
+        let len = codec.get_be_u16()? as usize;
+        let buf = codec.get_slice(len)?;
+        chain_vote::ProofOfCorrectWeightedVote::from_bytes(buf)
+            .map(Self)
+            .ok_or_else(|| ReadError::StructureInvalid("Invalid weighted vote proof".to_string()))
+    }
+}
+
+impl EncryptedWeightedVote {
+    /// maximum number of ciphertexts of an encrypted weighted vote, the number
+    /// of ciphertexts is serialized on a single byte
+    pub const MAX_LEN: usize = u8::MAX as usize;
+
+    pub(crate) fn from_inner(
+        vote: chain_vote::EncryptedWeightedVote,
+    ) -> Result<Self, WeightedVoteTooLarge> {
+        if vote.len() > Self::MAX_LEN {
+            return Err(WeightedVoteTooLarge(vote.len()));
+        }
+        Ok(Self(vote))
+    }
+
+    pub(super) fn as_inner(&self) -> &chain_vote::EncryptedWeightedVote {
+        &self.0
+    }
+
+    pub(crate) fn serialize_in(&self, bb: ByteBuilder<Self>) -> ByteBuilder<Self> {
+        bb.iter8(self.0.ciphertexts(), |bb, ct| {
+            let buffer = ct.to_bytes();
+            bb.bytes(&buffer)
+        })
+    }
+
+    pub fn serialize(&self) -> ByteArray<Self> {
+        self.serialize_in(ByteBuilder::new()).finalize()
+    }
+
+    pub(crate) fn read(codec: &mut Codec<&[u8]>) -> Result<Self, ReadError> {
+        let len: usize = codec.get_u8()? as usize;
+        let buf = codec.get_slice(len * Ciphertext::BYTES_LEN)?;
+        chain_vote::EncryptedWeightedVote::from_bytes(buf)
+            .map(Self)
+            .ok_or_else(|| ReadError::StructureInvalid("Invalid private weighted vote".to_string()))
+    }
+}
+
 impl TryFrom<u8> for PayloadType {
     type Error = TryFromIntError;
     fn try_from(value: u8) -> Result<Self, Self::Error> {
@@ -176,6 +297,7 @@ impl TryFrom<u8> for PayloadType {
             0 => Err(TryFromIntError::Zero),
             1 => Ok(Self::Public),
             2 => Ok(Self::Private),
+            3 => Ok(Self::PrivateWeighted),
             _ => Err(TryFromIntError::InvalidValue { value }),
         }
     }
@@ -195,18 +317,21 @@ mod tests {
 
     impl Arbitrary for PayloadType {
         fn arbitrary<G: Gen>(g: &mut G) -> Self {
-            if g.next_u32() % 2 == 0 {
-                Self::Public
-            } else {
-                Self::Private
+            match g.next_u32() % 3 {
+                0 => Self::Public,
+                1 => Self::Private,
+                _ => Self::PrivateWeighted,
             }
         }
     }
 
     impl Arbitrary for Payload {
         fn arbitrary<G: Gen>(g: &mut G) -> Self {
-            use chain_vote::{MemberCommunicationKey, MemberState, Vote};
+            use chain_vote::{
+                MemberCommunicationKey, MemberState, Vote, WeightedBallotKind, WeightedVote,
+            };
             use rand_core::SeedableRng;
+            use std::num::NonZeroU8;
 
             match PayloadType::arbitrary(g) {
                 PayloadType::Public => Payload::public(Choice::arbitrary(g)),
@@ -232,6 +357,28 @@ mod tests {
                         ProofOfCorrectVote::from_inner(proof),
                     )
                 }
+                PayloadType::PrivateWeighted => {
+                    let mut seed = [0u8; 32];
+                    g.fill_bytes(&mut seed);
+                    let mut gen = rand_chacha::ChaCha20Rng::from_seed(seed);
+                    let mc = MemberCommunicationKey::new(&mut gen);
+                    let threshold = 1;
+                    let h = Crs::from_hash(&seed);
+                    let m = MemberState::new(&mut gen, threshold, &h, &[mc.to_public()], 0);
+                    let participants = vec![m.public_key()];
+                    let ek = ElectionPublicKey::from_participants(&participants);
+                    let kind = WeightedBallotKind::Budget {
+                        budget: NonZeroU8::new(3).unwrap(),
+                    };
+                    let first = (g.next_u32() % 4) as u8;
+                    let second = (g.next_u32() % (4 - first as u32)) as u8;
+                    let vote = WeightedVote::new(kind, vec![first, second, 0]).unwrap();
+                    let (vote, proof) = ek.encrypt_and_prove_weighted_vote(&mut gen, &h, &vote);
+                    Payload::private_weighted(
+                        EncryptedWeightedVote::from_inner(vote).unwrap(),
+                        ProofOfCorrectWeightedVote::from_inner(proof),
+                    )
+                }
             }
         }
     }
@@ -1,5 +1,8 @@
-use crate::vote::{EncryptedVote, ProofOfCorrectVote};
-use chain_vote::{Crs, ElectionPublicKey, Vote};
+use crate::vote::{
+    EncryptedVote, EncryptedWeightedVote, ProofOfCorrectVote, ProofOfCorrectWeightedVote,
+    WeightedVoteTooLarge,
+};
+use chain_vote::{Crs, ElectionPublicKey, Vote, WeightedVote};
 use rand_core::{CryptoRng, RngCore};
 
 #[allow(dead_code)]
@@ -15,3 +18,22 @@ pub fn encrypt_vote<R: RngCore + CryptoRng>(
         ProofOfCorrectVote::from_inner(proof),
     )
 }
+
+/// encrypt a weighted vote, fails if the encrypted vote would have more than
+/// [`EncryptedWeightedVote::MAX_LEN`] ciphertexts
+pub fn encrypt_weighted_vote<R: RngCore + CryptoRng>(
+    rng: &mut R,
+    crs: &Crs,
+    public_key: &ElectionPublicKey,
+    vote: &WeightedVote,
+) -> Result<(EncryptedWeightedVote, ProofOfCorrectWeightedVote), WeightedVoteTooLarge> {
+    let len = vote.kind().encrypted_vote_len(vote.weights().len());
+    if len > EncryptedWeightedVote::MAX_LEN {
+        return Err(WeightedVoteTooLarge(len));
+    }
+    let (ev, proof) = public_key.encrypt_and_prove_weighted_vote(rng, crs, vote);
+    Ok((
+        EncryptedWeightedVote::from_inner(ev)?,
+        ProofOfCorrectWeightedVote::from_inner(proof),
+    ))
+}
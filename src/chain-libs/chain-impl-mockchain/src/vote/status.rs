@@ -8,6 +8,7 @@ use crate::{
 use chain_vote::MemberPublicKey;
 use imhamt::Hamt;
 use std::collections::hash_map::DefaultHasher;
+use std::num::NonZeroU8;
 
 pub struct VotePlanStatus {
     pub id: VotePlanId,
@@ -16,6 +17,7 @@ pub struct VotePlanStatus {
     pub vote_end: BlockDate,
     pub committee_end: BlockDate,
     pub committee_public_keys: Vec<MemberPublicKey>,
+    pub committee_threshold: Option<NonZeroU8>,
     pub proposals: Vec<VoteProposalStatus>,
     pub voting_token: TokenIdentifier,
 }
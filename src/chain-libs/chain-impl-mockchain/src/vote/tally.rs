@@ -125,6 +125,19 @@ impl TallyResult {
         Self { results, options }
     }
 
+    /// rebuild the tally results from the weights of each of the options
+    ///
+    /// returns `None` if there is not exactly one weight per option
+    pub(crate) fn from_weights(options: Options, weights: Vec<Weight>) -> Option<Self> {
+        if weights.len() != options.choice_range().len() {
+            return None;
+        }
+        Some(Self {
+            results: weights.into(),
+            options,
+        })
+    }
+
     pub fn results(&self) -> &[Weight] {
         &self.results
     }
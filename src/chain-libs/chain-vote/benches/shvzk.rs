@@ -1,5 +1,5 @@
 use chain_vote::*;
-use criterion::{criterion_group, criterion_main, BenchmarkId, Criterion};
+use criterion::{criterion_group, criterion_main, BenchmarkId, Criterion, Throughput};
 use rand_chacha::ChaCha20Rng;
 use rand_core::SeedableRng;
 
@@ -57,6 +57,50 @@ fn verify(c: &mut Criterion) {
     group.finish();
 }
 
+fn batch_verify(c: &mut Criterion) {
+    let mut rng = ChaCha20Rng::from_seed([0u8; 32]);
+    let mut group = c.benchmark_group("Verify ballots");
+    let crs = Crs::from_hash(&[0u8; 32]);
+    let ek = common(&mut rng);
+
+    for &number_ballots in [16usize, 64, 256].iter() {
+        let votes: Vec<_> = (0..number_ballots)
+            .map(|i| ek.encrypt_and_prove_vote(&mut rng, &crs, Vote::new(4, i % 4).unwrap()))
+            .collect();
+        let parameter_string = format!("{} ballots", number_ballots);
+        group.throughput(Throughput::Elements(number_ballots as u64));
+        group.bench_with_input(
+            BenchmarkId::new("Individually", &parameter_string),
+            &votes,
+            |b, votes| {
+                b.iter(|| {
+                    votes
+                        .iter()
+                        .map(|(vote, proof)| {
+                            Ballot::try_from_vote_and_proof(vote.clone(), proof, &crs, &ek)
+                        })
+                        .collect::<Result<Vec<_>, _>>()
+                })
+            },
+        );
+        group.bench_with_input(
+            BenchmarkId::new("In a batch", &parameter_string),
+            &votes,
+            |b, votes| {
+                b.iter(|| {
+                    Ballot::try_from_votes_and_proofs(
+                        votes
+                            .iter()
+                            .map(|(vote, proof)| (vote.clone(), proof, &crs, &ek)),
+                    )
+                })
+            },
+        );
+    }
+
+    group.finish();
+}
+
 criterion_group!(
     name = shvzk;
     config = Criterion::default().sample_size(500);
@@ -65,4 +109,10 @@ criterion_group!(
     verify,
 );
 
-criterion_main!(shvzk);
+criterion_group!(
+    name = batch;
+    config = Criterion::default().sample_size(20);
+    targets = batch_verify,
+);
+
+criterion_main!(shvzk, batch);
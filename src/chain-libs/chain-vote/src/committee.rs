@@ -1,6 +1,8 @@
 use crate::cryptography::{Ciphertext, HybridCiphertext, PublicKey, SecretKey};
 use crate::encrypted_vote::{EncryptedVote, ProofOfCorrectVote, Vote};
-use crate::math::polynomial::Polynomial;
+use crate::math::polynomial::{
+    lagrange_coefficients_at, lagrange_coefficients_at_zero, Polynomial,
+};
 use crate::tally::Crs;
 use crate::{GroupElement, Scalar, CURVE_HRP};
 use chain_crypto::bech32::{to_bech32_from_bytes, try_from_bech32_to_bytes, Bech32, Error};
@@ -16,11 +18,11 @@ pub struct MemberSecretKey(pub(crate) SecretKey);
 pub struct MemberPublicKey(pub(crate) PublicKey);
 
 #[derive(Clone)]
-pub struct MemberCommunicationKey(SecretKey);
+pub struct MemberCommunicationKey(pub(crate) SecretKey);
 
 /// Committee Member communication public key (with other committee members)
 #[derive(Clone)]
-pub struct MemberCommunicationPublicKey(PublicKey);
+pub struct MemberCommunicationPublicKey(pub(crate) PublicKey);
 
 /// The overall committee public key used for everyone to encrypt their vote to.
 #[derive(Debug, Clone, Eq, PartialEq)]
@@ -66,6 +68,43 @@ impl ElectionPublicKey {
         ElectionPublicKey(PublicKey { pk: k })
     }
 
+    /// Create an election public key from the participants of a committee sharing the
+    /// election secret key with a `threshold` out of `pks.len()` scheme. The key at position
+    /// `i` of `pks` is the public counterpart of the share of the member with index `i + 1`,
+    /// so any `threshold` of them are enough to interpolate the election public key.
+    ///
+    /// Only the first `threshold` keys are used, the other ones are not checked here, use
+    /// [`Self::check_threshold_participants`] to verify they belong to the same sharing.
+    pub fn from_threshold_participants(threshold: usize, pks: &[MemberPublicKey]) -> Self {
+        assert!(threshold > 0);
+        assert!(threshold <= pks.len());
+        let points = threshold_points(threshold);
+        let pk = GroupElement::vartime_multiscalar_multiplication(
+            lagrange_coefficients_at_zero(&points),
+            pks[..threshold].iter().map(|pk| pk.0.pk.clone()),
+        );
+        ElectionPublicKey(PublicKey { pk })
+    }
+
+    /// Check that the keys of a `threshold` out of `pks.len()` committee are the public
+    /// counterparts of shares of the same secret, i.e. every key after the first `threshold`
+    /// ones is the one interpolated from them. A member with an inconsistent key could not
+    /// take part in the decryption of the tally.
+    pub fn check_threshold_participants(threshold: usize, pks: &[MemberPublicKey]) -> bool {
+        if threshold == 0 || threshold > pks.len() {
+            return false;
+        }
+        let points = threshold_points(threshold);
+        pks.iter().enumerate().skip(threshold).all(|(i, pk)| {
+            let x = Scalar::from_u64(i as u64 + 1);
+            let expected = GroupElement::vartime_multiscalar_multiplication(
+                lagrange_coefficients_at(&points, &x),
+                pks[..threshold].iter().map(|pk| pk.0.pk.clone()),
+            );
+            expected == pk.0.pk
+        })
+    }
+
     pub fn to_bytes(&self) -> Vec<u8> {
         self.0.to_bytes()
     }
@@ -75,6 +114,13 @@ impl ElectionPublicKey {
     }
 }
 
+/// evaluation points of the shares of the first `threshold` committee members
+fn threshold_points(threshold: usize) -> Vec<Scalar> {
+    (1..=threshold)
+        .map(|index| Scalar::from_u64(index as u64))
+        .collect()
+}
+
 impl Bech32 for ElectionPublicKey {
     const BECH32_HRP: &'static str = concatcp!(CURVE_HRP, "_votepk");
     const BYTES_LEN: usize = PublicKey::BYTES_LEN;
@@ -0,0 +1,41 @@
+use crate::{Ciphertext, GroupElement, Scalar};
+use cryptoxide::blake2b::Blake2b;
+use cryptoxide::digest::Digest;
+
+/// Challenge context for the proof that a ciphertext encrypts either 0 or 1. The
+/// statement consists of the common reference string of the election, the public
+/// key and the ciphertext, and the challenge is computed over the announcements of
+/// both branches of the disjunction. Including the common reference string binds
+/// the proof to the election, so it cannot be replayed in another vote plan.
+pub struct ChallengeContext(Blake2b);
+
+impl ChallengeContext {
+    /// Initialise the challenge context, by including the full statement
+    pub(crate) fn new(
+        crs: &GroupElement,
+        public_key: &GroupElement,
+        ciphertext: &Ciphertext,
+    ) -> Self {
+        let mut ctx = Blake2b::new(64);
+        ctx.input(&crs.to_bytes());
+        ctx.input(&public_key.to_bytes());
+        ctx.input(&ciphertext.e1.to_bytes());
+        ctx.input(&ciphertext.e2.to_bytes());
+
+        ChallengeContext(ctx)
+    }
+
+    /// Generation of the challenge shared by both branches, once the announcements of
+    /// the two branches are "sent".
+    pub(crate) fn challenge(
+        &mut self,
+        announcements: &[(GroupElement, GroupElement); 2],
+    ) -> Scalar {
+        for (a1, a2) in announcements {
+            self.0.input(&a1.to_bytes());
+            self.0.input(&a2.to_bytes());
+        }
+
+        Scalar::hash_to_scalar(&self.0)
+    }
+}
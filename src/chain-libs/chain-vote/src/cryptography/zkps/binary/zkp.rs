@@ -0,0 +1,191 @@
+//! Non-interactive Zero Knowledge proof that an ElGamal ciphertext encrypts
+//! either 0 or 1.
+//!
+//! The proof is the following:
+//!
+//! `NIZK{(pk, C = (e1, e2)), (r): (e1 = g^r AND e2 = pk^r) OR (e1 = g^r AND e2 / g = pk^r)}`
+//!
+//! Each branch is a proof of discrete log equality, and the disjunction is built by
+//! simulating the branch that does not hold, with the challenges of both branches
+//! adding up to the challenge of the transcript.
+#![allow(clippy::many_single_char_names)]
+use super::challenge_context::ChallengeContext;
+use crate::cryptography::{Ciphertext, PublicKey};
+use crate::{Crs, GroupElement, Scalar};
+use rand_core::{CryptoRng, RngCore};
+
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct Zkp {
+    challenges: [Scalar; 2],
+    responses: [Scalar; 2],
+}
+
+/// the second point of the statement of the branch where the ciphertext encrypts `bit`
+fn branch_point(ciphertext: &Ciphertext, bit: usize) -> GroupElement {
+    if bit == 0 {
+        ciphertext.e2.clone()
+    } else {
+        &ciphertext.e2 - &GroupElement::generator()
+    }
+}
+
+fn announcement(
+    pk: &PublicKey,
+    ciphertext: &Ciphertext,
+    bit: usize,
+    challenge: &Scalar,
+    response: &Scalar,
+) -> (GroupElement, GroupElement) {
+    (
+        GroupElement::generator() * response - &ciphertext.e1 * challenge,
+        &pk.pk * response - branch_point(ciphertext, bit) * challenge,
+    )
+}
+
+impl Zkp {
+    pub const BYTES_LEN: usize = 4 * Scalar::BYTES_LEN;
+
+    /// Generate the proof that `ciphertext` is the encryption of `bit` under `pk`,
+    /// with `randomness`, for the election with the common reference string `crs`.
+    pub fn generate<R>(
+        crs: &Crs,
+        pk: &PublicKey,
+        ciphertext: &Ciphertext,
+        bit: bool,
+        randomness: &Scalar,
+        rng: &mut R,
+    ) -> Self
+    where
+        R: CryptoRng + RngCore,
+    {
+        let real = bit as usize;
+        let simulated = 1 - real;
+
+        let mut challenges = [Scalar::zero(), Scalar::zero()];
+        let mut responses = [Scalar::zero(), Scalar::zero()];
+        challenges[simulated] = Scalar::random(rng);
+        responses[simulated] = Scalar::random(rng);
+
+        let w = Scalar::random(rng);
+        let mut announcements = [
+            (GroupElement::zero(), GroupElement::zero()),
+            (GroupElement::zero(), GroupElement::zero()),
+        ];
+        announcements[real] = (GroupElement::generator() * &w, &pk.pk * &w);
+        announcements[simulated] = announcement(
+            pk,
+            ciphertext,
+            simulated,
+            &challenges[simulated],
+            &responses[simulated],
+        );
+
+        let challenge = ChallengeContext::new(crs, &pk.pk, ciphertext).challenge(&announcements);
+        challenges[real] = &challenge - &challenges[simulated];
+        responses[real] = &w + &(&challenges[real] * randomness);
+
+        Zkp {
+            challenges,
+            responses,
+        }
+    }
+
+    /// Verify the proof that `ciphertext` encrypts either 0 or 1 under `pk`, for the
+    /// election with the common reference string `crs`
+    pub fn verify(&self, crs: &Crs, pk: &PublicKey, ciphertext: &Ciphertext) -> bool {
+        let announcements = [0, 1].map(|bit| {
+            announcement(
+                pk,
+                ciphertext,
+                bit,
+                &self.challenges[bit],
+                &self.responses[bit],
+            )
+        });
+        let challenge = ChallengeContext::new(crs, &pk.pk, ciphertext).challenge(&announcements);
+        // no need for constant time equality because of the hash in challenge()
+        challenge == &self.challenges[0] + &self.challenges[1]
+    }
+
+    pub fn to_bytes(&self) -> [u8; Self::BYTES_LEN] {
+        let mut output = [0u8; Self::BYTES_LEN];
+        for (chunk, scalar) in output
+            .chunks_mut(Scalar::BYTES_LEN)
+            .zip(self.challenges.iter().chain(self.responses.iter()))
+        {
+            chunk.copy_from_slice(&scalar.to_bytes());
+        }
+        output
+    }
+
+    pub fn from_bytes(slice: &[u8]) -> Option<Self> {
+        if slice.len() != Self::BYTES_LEN {
+            return None;
+        }
+        let mut scalars = slice.chunks(Scalar::BYTES_LEN).map(Scalar::from_bytes);
+        let mut next = || scalars.next().flatten();
+        Some(Zkp {
+            challenges: [next()?, next()?],
+            responses: [next()?, next()?],
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::cryptography::Keypair;
+    use rand_chacha::ChaCha20Rng;
+    use rand_core::SeedableRng;
+
+    #[test]
+    fn it_works() {
+        let mut r = ChaCha20Rng::from_seed([0u8; 32]);
+        let keypair = Keypair::generate(&mut r);
+        let crs = Crs::from_hash(b"election");
+
+        for bit in [false, true] {
+            let (ciphertext, randomness) = keypair
+                .public_key
+                .encrypt_return_r(&Scalar::from(bit), &mut r);
+            let proof = Zkp::generate(
+                &crs,
+                &keypair.public_key,
+                &ciphertext,
+                bit,
+                &randomness,
+                &mut r,
+            );
+            assert!(proof.verify(&crs, &keypair.public_key, &ciphertext));
+
+            let proof = Zkp::from_bytes(&proof.to_bytes()).unwrap();
+            assert!(proof.verify(&crs, &keypair.public_key, &ciphertext));
+
+            // the proof is bound to the election
+            let other_crs = Crs::from_hash(b"another election");
+            assert!(!proof.verify(&other_crs, &keypair.public_key, &ciphertext));
+        }
+    }
+
+    #[test]
+    fn not_a_bit() {
+        let mut r = ChaCha20Rng::from_seed([1u8; 32]);
+        let keypair = Keypair::generate(&mut r);
+        let crs = Crs::from_hash(b"election");
+
+        let (ciphertext, randomness) = keypair
+            .public_key
+            .encrypt_return_r(&Scalar::from_u64(2), &mut r);
+        for bit in [false, true] {
+            let proof = Zkp::generate(
+                &crs,
+                &keypair.public_key,
+                &ciphertext,
+                bit,
+                &randomness,
+                &mut r,
+            );
+            assert!(!proof.verify(&crs, &keypair.public_key, &ciphertext));
+        }
+    }
+}
@@ -9,6 +9,7 @@ use crate::{GroupElement, Scalar};
 use chain_core::packer::Codec;
 use chain_core::property::ReadError;
 use rand_core::{CryptoRng, RngCore};
+use rayon::prelude::*;
 use {rand::thread_rng, std::iter};
 
 use super::challenge_context::ChallengeContext;
@@ -234,6 +235,108 @@ impl Zkp {
         mega_check == GroupElement::zero()
     }
 
+    /// Verify many unit vector proofs at once. Rather than checking the
+    /// verification equations of every proof on its own, we check a random
+    /// linear combination of all of them (see `batch_terms`), computed with
+    /// vartime multiscalar multiplications split across the rayon threads.
+    ///
+    /// Returns `true` if all the proofs are valid. When it returns `false`,
+    /// at least one proof is invalid, and `verify` has to be used on every
+    /// proof to find out which ones.
+    pub fn batch_verify(statements: &[(&Self, &Crs, &PublicKey, &[Ciphertext])]) -> bool {
+        let terms = match statements
+            .par_iter()
+            .map(|(proof, crs, public_key, ciphertexts)| {
+                proof.batch_terms(crs, public_key, ciphertexts)
+            })
+            .collect::<Option<Vec<_>>>()
+        {
+            Some(terms) => terms,
+            None => return false,
+        };
+
+        let generator = terms
+            .iter()
+            .fold(Scalar::zero(), |acc, terms| acc + &terms.generator);
+
+        let chunk_len = (terms.len() / rayon::current_num_threads()).max(1);
+        let check = terms
+            .par_chunks(chunk_len)
+            .map(|chunk| {
+                GroupElement::vartime_multiscalar_multiplication(
+                    chunk.iter().flat_map(|terms| terms.scalars.iter().cloned()),
+                    chunk.iter().flat_map(|terms| terms.points.iter().cloned()),
+                )
+            })
+            .reduce(GroupElement::zero, |acc, check| acc + check);
+
+        check + GroupElement::generator() * generator == GroupElement::zero()
+    }
+
+    /// Terms of the verification equations of the proof, such that the proof is
+    /// valid if the multiscalar multiplication of the terms is the identity.
+    ///
+    /// Each of the equations checked by `verify_statements` is multiplied by its
+    /// own random weight, so that the terms of many proofs can be added
+    /// together and checked at once. Returns `None` if the proof does not have
+    /// the structure expected for `ciphertexts`.
+    fn batch_terms(
+        &self,
+        crs: &Crs,
+        public_key: &PublicKey,
+        ciphertexts: &[Ciphertext],
+    ) -> Option<BatchTerms> {
+        let ck = CommitmentKey::from(crs.clone());
+        let ciphertexts = Ptp::new(ciphertexts.to_vec(), Ciphertext::zero);
+        let bits = ciphertexts.bits();
+
+        if self.ibas.len() != bits || self.zwvs.len() != bits {
+            return None;
+        }
+
+        let mut cc = ChallengeContext::new(&ck, public_key, ciphertexts.as_ref());
+        let cy = cc.first_challenge(&self.ibas);
+        let cx = cc.second_challenge(&self.ds);
+        let cx_pow = cx.power(bits);
+
+        let mut rng = thread_rng();
+        let mut terms = BatchTerms::with_capacity(6 * bits + 2 * ciphertexts.len() + 1);
+
+        for (zwv, iba) in self.zwvs.iter().zip(self.ibas.iter()) {
+            let alpha = Scalar::random(&mut rng);
+            let gamma = Scalar::random(&mut rng);
+            terms.generator = &terms.generator + &alpha * &zwv.z;
+            terms.push(&alpha * &zwv.w + &gamma * &zwv.v, ck.h.clone());
+            terms.push(&gamma * (&zwv.z - &cx) - &alpha * &cx, iba.i.clone());
+            terms.push(alpha.negate(), iba.b.clone());
+            terms.push(gamma.negate(), iba.a.clone());
+        }
+
+        // the equations over the randomness (`e1`) and the message (`e2`) parts of
+        // the ciphertexts get their own weights
+        let delta_1 = Scalar::random(&mut rng);
+        let delta_2 = Scalar::random(&mut rng);
+
+        for (index, (ctxt, cy_pow)) in ciphertexts.iter().zip(cy.exp_iter()).enumerate() {
+            let scalar = &cy_pow * &cx_pow;
+            terms.push(&delta_1 * &scalar, ctxt.e1.clone());
+            terms.push(&delta_2 * &scalar, ctxt.e2.clone());
+            let z_pow = powers_z_encs(&self.zwvs, cx.clone(), index, bits as u32);
+            terms.generator = &terms.generator - &delta_2 * &cy_pow * &z_pow;
+        }
+
+        for (d, cx_pow) in self.ds.iter().zip(cx.exp_iter()) {
+            terms.push(&delta_1 * &cx_pow, d.e1.clone());
+            terms.push(&delta_2 * &cx_pow, d.e2.clone());
+        }
+
+        // encryption of zero with randomness `r`
+        terms.generator = &terms.generator - &delta_1 * &self.r;
+        terms.push((&delta_2 * &self.r).negate(), public_key.pk.clone());
+
+        Some(terms)
+    }
+
     /// Try to generate a `Proof` from a buffer
     pub fn from_buffer(codec: &mut Codec<&[u8]>) -> Result<Self, ReadError> {
         let bits = codec.get_u8()? as usize;
@@ -329,6 +432,29 @@ fn powers_z_encs(
     multz
 }
 
+/// Terms of the weighted verification equations of a proof, see `Zkp::batch_terms`
+struct BatchTerms {
+    /// scalar of the group generator, shared by the equations of all the proofs
+    generator: Scalar,
+    scalars: Vec<Scalar>,
+    points: Vec<GroupElement>,
+}
+
+impl BatchTerms {
+    fn with_capacity(capacity: usize) -> Self {
+        Self {
+            generator: Scalar::zero(),
+            scalars: Vec::with_capacity(capacity),
+            points: Vec::with_capacity(capacity),
+        }
+    }
+
+    fn push(&mut self, scalar: Scalar, point: GroupElement) {
+        self.scalars.push(scalar);
+        self.points.push(point);
+    }
+}
+
 /// Provides an iterator over the encryptions of the product of the powers of `z`.
 ///
 /// This struct is created by the `powers_z_encs_iter` function.
@@ -465,6 +591,75 @@ mod tests {
         assert!(!proof.verify(&crs, &public_key, &fake_encryption))
     }
 
+    fn generate_statement(
+        r: &mut ChaCha20Rng,
+        crs: &Crs,
+        public_key: &PublicKey,
+        unit_vector: UnitVector,
+    ) -> (Zkp, Vec<Ciphertext>) {
+        let encryption_randomness: Vec<Scalar> = (0..unit_vector.len())
+            .map(|_| Scalar::random(&mut *r))
+            .collect();
+        let ciphertexts: Vec<Ciphertext> = unit_vector
+            .iter()
+            .zip(encryption_randomness.iter())
+            .map(|(i, r)| public_key.encrypt_with_r(&Scalar::from(i), r))
+            .collect();
+        let proof = Zkp::generate(
+            r,
+            crs,
+            public_key,
+            &unit_vector,
+            &encryption_randomness,
+            &ciphertexts,
+        );
+        (proof, ciphertexts)
+    }
+
+    #[test]
+    fn batch_verify() {
+        let mut r = ChaCha20Rng::from_seed([0u8; 32]);
+        let public_key = PublicKey {
+            pk: GroupElement::from_hash(&[1u8]),
+        };
+        let crs = Crs::from_hash(b"first vote plan");
+        let other_crs = Crs::from_hash(b"second vote plan");
+
+        let statements: Vec<_> = (0..8)
+            .map(|i| {
+                let crs = if i % 2 == 0 { &crs } else { &other_crs };
+                let unit_vector = UnitVector::new(3 + i % 3, i % 3).unwrap();
+                let (proof, ciphertexts) =
+                    generate_statement(&mut r, crs, &public_key, unit_vector);
+                (proof, crs, ciphertexts)
+            })
+            .collect();
+
+        let batch: Vec<_> = statements
+            .iter()
+            .map(|(proof, crs, ciphertexts)| (proof, *crs, &public_key, ciphertexts.as_slice()))
+            .collect();
+        assert!(Zkp::batch_verify(&batch));
+        assert!(Zkp::batch_verify(&[]));
+
+        // a single invalid statement fails the whole batch
+        let other_public_key = PublicKey {
+            pk: GroupElement::from_hash(&[2u8]),
+        };
+        let mut invalid_batch = batch.clone();
+        invalid_batch[3].2 = &other_public_key;
+        assert!(!Zkp::batch_verify(&invalid_batch));
+
+        let mut invalid_batch = batch.clone();
+        invalid_batch[5].1 = &crs;
+        assert!(!Zkp::batch_verify(&invalid_batch));
+
+        let swapped_ciphertexts: Vec<_> = statements[0].2.iter().rev().cloned().collect();
+        let mut invalid_batch = batch;
+        invalid_batch[0].3 = &swapped_ciphertexts;
+        assert!(!Zkp::batch_verify(&invalid_batch));
+    }
+
     #[test]
     fn challenge_context() {
         let mut r = ChaCha20Rng::from_seed([0u8; 32]);
@@ -0,0 +1,159 @@
+//! Messages broadcast by the committee members during the rounds of the distributed
+//! key generation. Every member is expected to receive the exact same set of messages
+//! for a given round, so that all of them reach the same conclusion about which dealers
+//! are qualified.
+use crate::cryptography::HybridCiphertext;
+use crate::{GroupElement, Scalar};
+
+/// Size of a share of the dealer's secret, hybrid encrypted for one of the members.
+const ENCRYPTED_SHARE_SIZE: usize = GroupElement::BYTES_LEN + Scalar::BYTES_LEN;
+
+/// Broadcast of the dealing round.
+///
+/// The dealer commits to the coefficients of its secret polynomial and sends to every
+/// member (itself included) the evaluation of that polynomial at the member's index,
+/// encrypted under the member's communication key.
+#[derive(Clone)]
+pub struct Dealing {
+    pub(crate) dealer: usize,
+    pub(crate) commitments: Vec<GroupElement>,
+    pub(crate) encrypted_shares: Vec<HybridCiphertext>,
+}
+
+/// Broadcast of the complaint round: the dealers whose share, addressed to the
+/// complainer, did not match their commitments.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Complaints {
+    pub(crate) complainer: usize,
+    pub(crate) accused: Vec<usize>,
+}
+
+/// Broadcast of the dispute round: the dealer answers every complaint against it by
+/// publishing the share of the complainer in the clear, so that anyone can check it
+/// against the dealer's commitments.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Disputes {
+    pub(crate) dealer: usize,
+    pub(crate) revealed: Vec<(usize, Scalar)>,
+}
+
+impl Dealing {
+    /// Position of the dealer in the committee
+    pub fn dealer(&self) -> usize {
+        self.dealer
+    }
+
+    pub fn to_bytes(&self) -> Vec<u8> {
+        let mut bytes = Vec::with_capacity(
+            3 + self.commitments.len() * GroupElement::BYTES_LEN
+                + self.encrypted_shares.len() * ENCRYPTED_SHARE_SIZE,
+        );
+        bytes.push(self.dealer as u8);
+        bytes.push(self.commitments.len() as u8);
+        for commitment in &self.commitments {
+            bytes.extend_from_slice(&commitment.to_bytes());
+        }
+        bytes.push(self.encrypted_shares.len() as u8);
+        for share in &self.encrypted_shares {
+            bytes.extend_from_slice(&share.to_bytes());
+        }
+        bytes
+    }
+
+    pub fn from_bytes(bytes: &[u8]) -> Option<Self> {
+        let (&dealer, bytes) = bytes.split_first()?;
+        let (&commitments_len, bytes) = bytes.split_first()?;
+        let commitments_size = commitments_len as usize * GroupElement::BYTES_LEN;
+        if bytes.len() < commitments_size {
+            return None;
+        }
+        let (commitments_bytes, bytes) = bytes.split_at(commitments_size);
+        let commitments = commitments_bytes
+            .chunks(GroupElement::BYTES_LEN)
+            .map(GroupElement::from_bytes)
+            .collect::<Option<Vec<_>>>()?;
+
+        let (&shares_len, bytes) = bytes.split_first()?;
+        if bytes.len() != shares_len as usize * ENCRYPTED_SHARE_SIZE {
+            return None;
+        }
+        let encrypted_shares = bytes
+            .chunks(ENCRYPTED_SHARE_SIZE)
+            .map(HybridCiphertext::from_bytes)
+            .collect::<Option<Vec<_>>>()?;
+
+        Some(Self {
+            dealer: dealer as usize,
+            commitments,
+            encrypted_shares,
+        })
+    }
+}
+
+impl Complaints {
+    /// Position of the complaining member in the committee
+    pub fn complainer(&self) -> usize {
+        self.complainer
+    }
+
+    /// Positions of the dealers the member complains against
+    pub fn accused(&self) -> &[usize] {
+        &self.accused
+    }
+
+    pub fn to_bytes(&self) -> Vec<u8> {
+        let mut bytes = Vec::with_capacity(2 + self.accused.len());
+        bytes.push(self.complainer as u8);
+        bytes.push(self.accused.len() as u8);
+        bytes.extend(self.accused.iter().map(|accused| *accused as u8));
+        bytes
+    }
+
+    pub fn from_bytes(bytes: &[u8]) -> Option<Self> {
+        let (&complainer, bytes) = bytes.split_first()?;
+        let (&accused_len, bytes) = bytes.split_first()?;
+        if bytes.len() != accused_len as usize {
+            return None;
+        }
+        Some(Self {
+            complainer: complainer as usize,
+            accused: bytes.iter().map(|accused| *accused as usize).collect(),
+        })
+    }
+}
+
+impl Disputes {
+    const REVEALED_SHARE_SIZE: usize = 1 + Scalar::BYTES_LEN;
+
+    /// Position of the dealer answering the complaints in the committee
+    pub fn dealer(&self) -> usize {
+        self.dealer
+    }
+
+    pub fn to_bytes(&self) -> Vec<u8> {
+        let mut bytes = Vec::with_capacity(2 + self.revealed.len() * Self::REVEALED_SHARE_SIZE);
+        bytes.push(self.dealer as u8);
+        bytes.push(self.revealed.len() as u8);
+        for (complainer, share) in &self.revealed {
+            bytes.push(*complainer as u8);
+            bytes.extend_from_slice(&share.to_bytes());
+        }
+        bytes
+    }
+
+    pub fn from_bytes(bytes: &[u8]) -> Option<Self> {
+        let (&dealer, bytes) = bytes.split_first()?;
+        let (&revealed_len, bytes) = bytes.split_first()?;
+        if bytes.len() != revealed_len as usize * Self::REVEALED_SHARE_SIZE {
+            return None;
+        }
+        let revealed = bytes
+            .chunks(Self::REVEALED_SHARE_SIZE)
+            .map(|chunk| Some((chunk[0] as usize, Scalar::from_bytes(&chunk[1..])?)))
+            .collect::<Option<Vec<_>>>()?;
+        Some(Self {
+            dealer: dealer as usize,
+            revealed,
+        })
+    }
+}
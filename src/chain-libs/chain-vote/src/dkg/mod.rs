@@ -0,0 +1,13 @@
+//! Distributed key generation of the election key of a vote committee.
+//!
+//! The committee members run a message driven protocol to generate an `ElectionPublicKey`
+//! without any trusted dealer: every member only ever learns its own share of the
+//! election secret key, and any `threshold` of them are required to decrypt a tally.
+//! See the [`DkgMember`] state machine for the details of the rounds.
+mod broadcast;
+mod procedure;
+
+pub use self::{
+    broadcast::{Complaints, Dealing, Disputes},
+    procedure::{DkgError, DkgMember, DkgOutput, DkgPhase, MAX_COMMITTEE_SIZE},
+};
@@ -0,0 +1,771 @@
+//! State machine of a committee member taking part in the distributed key generation.
+//!
+//! The protocol runs in three broadcast rounds:
+//!
+//! 1. **dealing**: every member deals shares of a random secret with a Feldman verifiable
+//!    secret sharing of degree `threshold - 1`;
+//! 2. **complaint**: every member checks the shares it received against the dealers'
+//!    commitments and complains against the dealers that sent an invalid share;
+//! 3. **dispute**: every accused dealer reveals the shares of its complainers in the clear.
+//!
+//! A dealer is disqualified if it did not deal, if `threshold` members or more complained
+//! against it, or if it failed to reveal a valid share for one of its complainers. The
+//! election secret key is the sum of the secrets of the qualified dealers and is never
+//! known by any single member: each member ends up with a share of it, whose public
+//! counterpart can be computed by anyone from the broadcast messages.
+use super::broadcast::{Complaints, Dealing, Disputes};
+use crate::committee::{
+    ElectionPublicKey, MemberCommunicationKey, MemberCommunicationPublicKey, MemberPublicKey,
+    MemberSecretKey,
+};
+use crate::cryptography::{PublicKey, SecretKey};
+use crate::math::polynomial::Polynomial;
+use crate::{GroupElement, Scalar};
+use rand_core::{CryptoRng, RngCore};
+
+/// Maximum number of members of a committee running the key generation. The position of
+/// a member in the committee is encoded on a single byte in the broadcast messages.
+pub const MAX_COMMITTEE_SIZE: usize = u8::MAX as usize;
+
+/// Round of the protocol a member is expecting the messages of.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DkgPhase {
+    Dealing = 0,
+    Complaint = 1,
+    Dispute = 2,
+    Finished = 3,
+}
+
+#[derive(Debug, thiserror::Error, PartialEq, Eq)]
+pub enum DkgError {
+    #[error("threshold should be in range (0..{members}] and is {threshold}")]
+    InvalidThreshold { threshold: usize, members: usize },
+    #[error(
+        "committee has {members} members, at most {} are supported",
+        MAX_COMMITTEE_SIZE
+    )]
+    CommitteeTooLarge { members: usize },
+    #[error("invalid committee member index {index}")]
+    InvalidMemberIndex { index: usize },
+    #[error("the communication key does not match the one of the committee member")]
+    CommunicationKeyMismatch,
+    #[error(
+        "expected messages of the {expected:?} round, but the member is in the {actual:?} round"
+    )]
+    UnexpectedPhase {
+        expected: DkgPhase,
+        actual: DkgPhase,
+    },
+    #[error("received more than one message from committee member {index}")]
+    DuplicateMessage { index: usize },
+    #[error("only {qualified} dealers are qualified, at least {threshold} are required")]
+    NotEnoughQualifiedDealers { qualified: usize, threshold: usize },
+    #[error("no valid share from the qualified dealer {dealer}, the complaint of the member against it is missing from the complaints round")]
+    MissingShare { dealer: usize },
+}
+
+/// Local state of a committee member during the distributed key generation.
+///
+/// This contains the secret polynomial dealt by the member and its communication
+/// secret key, it should be kept as secret as the resulting `MemberSecretKey`.
+#[derive(Clone)]
+pub struct DkgMember {
+    phase: DkgPhase,
+    my: usize,
+    threshold: usize,
+    committee: Vec<MemberCommunicationPublicKey>,
+    communication_key: MemberCommunicationKey,
+    polynomial: Polynomial,
+    dealings: Vec<Dealing>,
+    complaints: Vec<Complaints>,
+}
+
+/// Outcome of a successful key generation.
+#[derive(Clone)]
+pub struct DkgOutput {
+    secret_key: MemberSecretKey,
+    member_public_keys: Vec<MemberPublicKey>,
+    election_public_key: ElectionPublicKey,
+    qualified: Vec<usize>,
+}
+
+fn evaluation_point(position: usize) -> Scalar {
+    // committee members are 1-indexed, the evaluation at 0 being the secret
+    Scalar::from_u64((position + 1) as u64)
+}
+
+fn evaluate_commitments(commitments: &[GroupElement], position: usize) -> GroupElement {
+    GroupElement::vartime_multiscalar_multiplication(
+        evaluation_point(position)
+            .exp_iter()
+            .take(commitments.len()),
+        commitments.iter().cloned(),
+    )
+}
+
+fn verify_share(commitments: &[GroupElement], position: usize, share: &Scalar) -> bool {
+    GroupElement::generator() * share == evaluate_commitments(commitments, position)
+}
+
+/// check every committee member sent at most one message for the round
+fn check_senders(senders: impl Iterator<Item = usize>, members: usize) -> Result<(), DkgError> {
+    let mut seen = vec![false; members];
+    for index in senders {
+        match seen.get_mut(index) {
+            None => return Err(DkgError::InvalidMemberIndex { index }),
+            Some(true) => return Err(DkgError::DuplicateMessage { index }),
+            Some(sent) => *sent = true,
+        }
+    }
+    Ok(())
+}
+
+impl DkgMember {
+    /// Start the key generation for the member at position `my` in the `committee`, with
+    /// any `threshold` members being enough to decrypt a tally.
+    ///
+    /// Returns the state of the member and the dealing to broadcast to the committee.
+    pub fn init<R: RngCore + CryptoRng>(
+        rng: &mut R,
+        threshold: usize,
+        committee: &[MemberCommunicationPublicKey],
+        communication_key: &MemberCommunicationKey,
+        my: usize,
+    ) -> Result<(Self, Dealing), DkgError> {
+        let members = committee.len();
+        if members > MAX_COMMITTEE_SIZE {
+            return Err(DkgError::CommitteeTooLarge { members });
+        }
+        if threshold == 0 || threshold > members {
+            return Err(DkgError::InvalidThreshold { threshold, members });
+        }
+        if my >= members {
+            return Err(DkgError::InvalidMemberIndex { index: my });
+        }
+        if communication_key.to_public().to_bytes() != committee[my].to_bytes() {
+            return Err(DkgError::CommunicationKeyMismatch);
+        }
+
+        let member = Self {
+            phase: DkgPhase::Dealing,
+            my,
+            threshold,
+            committee: committee.to_vec(),
+            communication_key: communication_key.clone(),
+            polynomial: Polynomial::random(rng, threshold - 1),
+            dealings: Vec::new(),
+            complaints: Vec::new(),
+        };
+
+        let commitments = member
+            .polynomial
+            .get_coefficients()
+            .map(|coefficient| GroupElement::generator() * coefficient)
+            .collect();
+        let encrypted_shares = member
+            .committee
+            .iter()
+            .enumerate()
+            .map(|(position, pk)| {
+                let share = member.polynomial.evaluate(&evaluation_point(position));
+                pk.0.hybrid_encrypt(&share.to_bytes(), rng)
+            })
+            .collect();
+        let dealing = Dealing {
+            dealer: my,
+            commitments,
+            encrypted_shares,
+        };
+
+        Ok((member, dealing))
+    }
+
+    pub fn phase(&self) -> DkgPhase {
+        self.phase
+    }
+
+    /// Position of the member in the committee
+    pub fn index(&self) -> usize {
+        self.my
+    }
+
+    pub fn threshold(&self) -> usize {
+        self.threshold
+    }
+
+    fn check_phase(&self, expected: DkgPhase) -> Result<(), DkgError> {
+        if self.phase != expected {
+            return Err(DkgError::UnexpectedPhase {
+                expected,
+                actual: self.phase,
+            });
+        }
+        Ok(())
+    }
+
+    fn well_formed(&self, dealing: &Dealing) -> bool {
+        dealing.commitments.len() == self.threshold
+            && dealing.encrypted_shares.len() == self.committee.len()
+    }
+
+    /// decrypt the share addressed to this member, returns `None` if the share does not
+    /// match the dealer's commitments
+    fn received_share(&self, dealing: &Dealing) -> Option<Scalar> {
+        if dealing.dealer == self.my {
+            return Some(self.polynomial.evaluate(&evaluation_point(self.my)));
+        }
+        let bytes = self
+            .communication_key
+            .0
+            .hybrid_decrypt(&dealing.encrypted_shares[self.my]);
+        let share = Scalar::from_bytes(&bytes)?;
+        if verify_share(&dealing.commitments, self.my, &share) {
+            Some(share)
+        } else {
+            None
+        }
+    }
+
+    /// Process the dealings broadcast by the committee, including the member's own one,
+    /// and return the complaints to broadcast.
+    ///
+    /// Malformed dealings are ignored, their dealers are disqualified by every member.
+    pub fn handle_dealings(&mut self, dealings: &[Dealing]) -> Result<Complaints, DkgError> {
+        self.check_phase(DkgPhase::Dealing)?;
+        check_senders(
+            dealings.iter().map(|dealing| dealing.dealer),
+            self.committee.len(),
+        )?;
+
+        let mut dealings: Vec<_> = dealings
+            .iter()
+            .filter(|dealing| self.well_formed(dealing))
+            .cloned()
+            .collect();
+        dealings.sort_by_key(|dealing| dealing.dealer);
+
+        let accused = dealings
+            .iter()
+            .filter(|dealing| self.received_share(dealing).is_none())
+            .map(|dealing| dealing.dealer)
+            .collect();
+
+        self.dealings = dealings;
+        self.phase = DkgPhase::Complaint;
+        Ok(Complaints {
+            complainer: self.my,
+            accused,
+        })
+    }
+
+    /// Process the complaints broadcast by the committee and return the shares this member
+    /// reveals in order to answer the complaints against it.
+    pub fn handle_complaints(&mut self, complaints: &[Complaints]) -> Result<Disputes, DkgError> {
+        self.check_phase(DkgPhase::Complaint)?;
+        check_senders(
+            complaints.iter().map(|complaints| complaints.complainer),
+            self.committee.len(),
+        )?;
+
+        let mut complaints = complaints.to_vec();
+        complaints.sort_by_key(|complaints| complaints.complainer);
+
+        let revealed = complaints
+            .iter()
+            .filter(|complaints| {
+                complaints.complainer != self.my && complaints.accused.contains(&self.my)
+            })
+            .map(|complaints| {
+                let share = self
+                    .polynomial
+                    .evaluate(&evaluation_point(complaints.complainer));
+                (complaints.complainer, share)
+            })
+            .collect();
+
+        self.complaints = complaints;
+        self.phase = DkgPhase::Dispute;
+        Ok(Disputes {
+            dealer: self.my,
+            revealed,
+        })
+    }
+
+    /// Process the disputes broadcast by the committee and complete the key generation.
+    pub fn handle_disputes(&mut self, disputes: &[Disputes]) -> Result<DkgOutput, DkgError> {
+        self.check_phase(DkgPhase::Dispute)?;
+        check_senders(
+            disputes.iter().map(|disputes| disputes.dealer),
+            self.committee.len(),
+        )?;
+
+        let mut qualified = Vec::new();
+        let mut share = Scalar::zero();
+        let mut commitments = vec![GroupElement::zero(); self.threshold];
+
+        for dealing in &self.dealings {
+            let complainers: Vec<usize> = self
+                .complaints
+                .iter()
+                .filter(|complaints| {
+                    complaints.complainer != dealing.dealer
+                        && complaints.accused.contains(&dealing.dealer)
+                })
+                .map(|complaints| complaints.complainer)
+                .collect();
+
+            // revealing `threshold` shares would reveal the dealer's secret
+            if complainers.len() >= self.threshold {
+                continue;
+            }
+
+            let answers = disputes
+                .iter()
+                .find(|disputes| disputes.dealer == dealing.dealer);
+            let revealed = |complainer: usize| {
+                answers?
+                    .revealed
+                    .iter()
+                    .find(|(position, share)| {
+                        *position == complainer
+                            && verify_share(&dealing.commitments, complainer, share)
+                    })
+                    .map(|(_, share)| share.clone())
+            };
+            if complainers
+                .iter()
+                .any(|complainer| revealed(*complainer).is_none())
+            {
+                continue;
+            }
+
+            // the share of the member is missing if its own complaint against the dealer
+            // was not part of the complaints round
+            let received = if complainers.contains(&self.my) {
+                revealed(self.my)
+            } else {
+                self.received_share(dealing)
+            };
+            let received = received.ok_or(DkgError::MissingShare {
+                dealer: dealing.dealer,
+            })?;
+
+            share = &share + &received;
+            for (commitment, dealer_commitment) in
+                commitments.iter_mut().zip(dealing.commitments.iter())
+            {
+                *commitment = &*commitment + dealer_commitment;
+            }
+            qualified.push(dealing.dealer);
+        }
+
+        if qualified.len() < self.threshold {
+            return Err(DkgError::NotEnoughQualifiedDealers {
+                qualified: qualified.len(),
+                threshold: self.threshold,
+            });
+        }
+
+        let member_public_keys = (0..self.committee.len())
+            .map(|position| {
+                MemberPublicKey(PublicKey {
+                    pk: evaluate_commitments(&commitments, position),
+                })
+            })
+            .collect();
+
+        self.phase = DkgPhase::Finished;
+        Ok(DkgOutput {
+            secret_key: MemberSecretKey(SecretKey { sk: share }),
+            member_public_keys,
+            election_public_key: ElectionPublicKey(PublicKey {
+                pk: commitments[0].clone(),
+            }),
+            qualified,
+        })
+    }
+
+    pub fn to_bytes(&self) -> Vec<u8> {
+        let mut bytes = vec![
+            self.phase as u8,
+            self.my as u8,
+            self.threshold as u8,
+            self.committee.len() as u8,
+        ];
+        for pk in &self.committee {
+            bytes.extend_from_slice(&pk.to_bytes());
+        }
+        bytes.extend_from_slice(&self.communication_key.to_bytes());
+        for coefficient in self.polynomial.get_coefficients() {
+            bytes.extend_from_slice(&coefficient.to_bytes());
+        }
+
+        let messages = |bytes: &mut Vec<u8>, messages: Vec<Vec<u8>>| {
+            bytes.push(messages.len() as u8);
+            for message in messages {
+                bytes.extend_from_slice(&(message.len() as u32).to_le_bytes());
+                bytes.extend_from_slice(&message);
+            }
+        };
+        messages(
+            &mut bytes,
+            self.dealings.iter().map(Dealing::to_bytes).collect(),
+        );
+        messages(
+            &mut bytes,
+            self.complaints.iter().map(Complaints::to_bytes).collect(),
+        );
+        bytes
+    }
+
+    pub fn from_bytes(bytes: &[u8]) -> Option<Self> {
+        let mut reader = Reader(bytes);
+        let phase = match reader.u8()? {
+            0 => DkgPhase::Dealing,
+            1 => DkgPhase::Complaint,
+            2 => DkgPhase::Dispute,
+            3 => DkgPhase::Finished,
+            _ => return None,
+        };
+        let my = reader.u8()? as usize;
+        let threshold = reader.u8()? as usize;
+        let members = reader.u8()? as usize;
+        if threshold == 0 || threshold > members || my >= members {
+            return None;
+        }
+        let committee = (0..members)
+            .map(|_| MemberCommunicationPublicKey::from_bytes(reader.take(PublicKey::BYTES_LEN)?))
+            .collect::<Option<Vec<_>>>()?;
+        let communication_key =
+            MemberCommunicationKey::from_bytes(reader.take(SecretKey::BYTES_LEN)?)?;
+        let polynomial = Polynomial::from_vec(
+            (0..threshold)
+                .map(|_| Scalar::from_bytes(reader.take(Scalar::BYTES_LEN)?))
+                .collect::<Option<Vec<_>>>()?,
+        );
+        let dealings = reader.messages(Dealing::from_bytes)?;
+        let complaints = reader.messages(Complaints::from_bytes)?;
+        if !reader.0.is_empty() {
+            return None;
+        }
+
+        Some(Self {
+            phase,
+            my,
+            threshold,
+            committee,
+            communication_key,
+            polynomial,
+            dealings,
+            complaints,
+        })
+    }
+}
+
+struct Reader<'a>(&'a [u8]);
+
+impl<'a> Reader<'a> {
+    fn take(&mut self, len: usize) -> Option<&'a [u8]> {
+        if self.0.len() < len {
+            return None;
+        }
+        let (taken, rest) = self.0.split_at(len);
+        self.0 = rest;
+        Some(taken)
+    }
+
+    fn u8(&mut self) -> Option<u8> {
+        Some(self.take(1)?[0])
+    }
+
+    fn messages<T>(&mut self, from_bytes: impl Fn(&[u8]) -> Option<T>) -> Option<Vec<T>> {
+        let count = self.u8()?;
+        (0..count)
+            .map(|_| {
+                let len = u32::from_le_bytes(self.take(4)?.try_into().unwrap());
+                from_bytes(self.take(len as usize)?)
+            })
+            .collect()
+    }
+}
+
+impl DkgOutput {
+    /// The share of the election secret key of this member
+    pub fn secret_key(&self) -> &MemberSecretKey {
+        &self.secret_key
+    }
+
+    /// The public counterparts of the shares of every committee member, in the order of
+    /// the committee. These are the committee keys of a threshold vote plan.
+    pub fn member_public_keys(&self) -> &[MemberPublicKey] {
+        &self.member_public_keys
+    }
+
+    pub fn election_public_key(&self) -> &ElectionPublicKey {
+        &self.election_public_key
+    }
+
+    /// Positions of the dealers whose secret is part of the election secret key
+    pub fn qualified(&self) -> &[usize] {
+        &self.qualified
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{Ballot, Crs, EncryptedTally, TallyDecryptShare, TallyOptimizationTable, Vote};
+    use rand_chacha::ChaCha20Rng;
+    use rand_core::SeedableRng;
+
+    fn committee(
+        rng: &mut ChaCha20Rng,
+        members: usize,
+    ) -> (
+        Vec<MemberCommunicationKey>,
+        Vec<MemberCommunicationPublicKey>,
+    ) {
+        let keys: Vec<_> = (0..members)
+            .map(|_| MemberCommunicationKey::new(rng))
+            .collect();
+        let pks = keys.iter().map(|key| key.to_public()).collect();
+        (keys, pks)
+    }
+
+    fn start(
+        rng: &mut ChaCha20Rng,
+        threshold: usize,
+        members: usize,
+    ) -> (Vec<DkgMember>, Vec<Dealing>) {
+        let (keys, pks) = committee(rng, members);
+        keys.iter()
+            .enumerate()
+            .map(|(i, key)| DkgMember::init(rng, threshold, &pks, key, i).unwrap())
+            .unzip()
+    }
+
+    fn finish(
+        states: &mut [DkgMember],
+        dealings: &[Dealing],
+        tamper_disputes: impl Fn(&mut Vec<Disputes>),
+    ) -> Vec<Result<DkgOutput, DkgError>> {
+        let complaints: Vec<_> = states
+            .iter_mut()
+            .map(|state| state.handle_dealings(dealings).unwrap())
+            .collect();
+        let mut disputes: Vec<_> = states
+            .iter_mut()
+            .map(|state| state.handle_complaints(&complaints).unwrap())
+            .collect();
+        tamper_disputes(&mut disputes);
+        states
+            .iter_mut()
+            .map(|state| state.handle_disputes(&disputes))
+            .collect()
+    }
+
+    fn assert_consistent(outputs: &[DkgOutput], threshold: usize) {
+        for output in outputs {
+            assert_eq!(output.election_public_key, outputs[0].election_public_key);
+            assert_eq!(output.member_public_keys, outputs[0].member_public_keys);
+            assert_eq!(output.qualified, outputs[0].qualified);
+        }
+        for (i, output) in outputs.iter().enumerate() {
+            assert_eq!(output.secret_key.to_public(), output.member_public_keys[i]);
+        }
+        assert_eq!(
+            ElectionPublicKey::from_threshold_participants(
+                threshold,
+                &outputs[0].member_public_keys
+            ),
+            outputs[0].election_public_key
+        );
+    }
+
+    #[test]
+    fn honest_key_generation_and_threshold_decryption() {
+        let mut rng = ChaCha20Rng::from_seed([0u8; 32]);
+        let threshold = 3;
+        let (mut states, dealings) = start(&mut rng, threshold, 5);
+        let outputs = finish(&mut states, &dealings, |_| ())
+            .into_iter()
+            .collect::<Result<Vec<_>, _>>()
+            .unwrap();
+        assert_consistent(&outputs, threshold);
+        assert_eq!(outputs[0].qualified(), &[0, 1, 2, 3, 4]);
+
+        let crs = Crs::from_hash(b"dkg");
+        let ek = outputs[0].election_public_key().clone();
+        let pks = outputs[0].member_public_keys().to_vec();
+        let mut encrypted_tally = EncryptedTally::new(2, ek.clone(), crs.clone());
+        for (choice, weight) in [(0, 2), (1, 5), (1, 1)] {
+            let (vote, proof) =
+                ek.encrypt_and_prove_vote(&mut rng, &crs, Vote::new(2, choice).unwrap());
+            let ballot = Ballot::try_from_vote_and_proof(vote, &proof, &crs, &ek).unwrap();
+            encrypted_tally.add(&ballot, weight).unwrap();
+        }
+
+        let mut shares: Vec<Option<TallyDecryptShare>> = vec![None; pks.len()];
+        for i in [1, 2, 4] {
+            shares[i] = Some(encrypted_tally.partial_decrypt(&mut rng, outputs[i].secret_key()));
+        }
+        let table = TallyOptimizationTable::generate_with_balance(
+            10.try_into().unwrap(),
+            1.try_into().unwrap(),
+        );
+        let tally = encrypted_tally
+            .validate_threshold_partial_decryptions(threshold, &pks, &shares)
+            .unwrap()
+            .decrypt_tally(&table)
+            .unwrap();
+        assert_eq!(tally.votes, vec![2, 6]);
+    }
+
+    #[test]
+    fn invalid_share_answered_in_dispute() {
+        let mut rng = ChaCha20Rng::from_seed([1u8; 32]);
+        let threshold = 2;
+        let (mut states, mut dealings) = start(&mut rng, threshold, 4);
+        // dealer 1 sends garbage to member 3
+        dealings[1].encrypted_shares[3] = dealings[1].encrypted_shares[2].clone();
+
+        let outputs = finish(&mut states, &dealings, |_| ())
+            .into_iter()
+            .collect::<Result<Vec<_>, _>>()
+            .unwrap();
+        assert_consistent(&outputs, threshold);
+        assert_eq!(outputs[0].qualified(), &[0, 1, 2, 3]);
+    }
+
+    #[test]
+    fn own_complaint_missing() {
+        let mut rng = ChaCha20Rng::from_seed([6u8; 32]);
+        let threshold = 2;
+        let (mut states, mut dealings) = start(&mut rng, threshold, 4);
+        dealings[1].encrypted_shares[3] = dealings[1].encrypted_shares[2].clone();
+
+        let mut complaints: Vec<_> = states
+            .iter_mut()
+            .map(|state| state.handle_dealings(&dealings).unwrap())
+            .collect();
+        // the complaint of member 3 against dealer 1 never reaches the committee
+        assert_eq!(complaints[3].accused, vec![1]);
+        complaints.remove(3);
+        let disputes: Vec<_> = states
+            .iter_mut()
+            .map(|state| state.handle_complaints(&complaints).unwrap())
+            .collect();
+
+        assert_eq!(
+            states[3].handle_disputes(&disputes).err(),
+            Some(DkgError::MissingShare { dealer: 1 })
+        );
+        let outputs = states[..3]
+            .iter_mut()
+            .map(|state| state.handle_disputes(&disputes))
+            .collect::<Result<Vec<_>, _>>()
+            .unwrap();
+        assert_eq!(outputs[0].qualified(), &[0, 1, 2, 3]);
+    }
+
+    #[test]
+    fn unanswered_complaint_disqualifies_dealer() {
+        let mut rng = ChaCha20Rng::from_seed([2u8; 32]);
+        let threshold = 2;
+        let (mut states, mut dealings) = start(&mut rng, threshold, 4);
+        dealings[1].encrypted_shares[3] = dealings[1].encrypted_shares[2].clone();
+        // dealer 2 does not deal at all
+        dealings.remove(2);
+
+        let outputs = finish(&mut states, &dealings, |disputes| {
+            disputes[1].revealed.clear();
+        })
+        .into_iter()
+        .collect::<Result<Vec<_>, _>>()
+        .unwrap();
+        assert_consistent(&outputs, threshold);
+        assert_eq!(outputs[0].qualified(), &[0, 3]);
+    }
+
+    #[test]
+    fn not_enough_qualified_dealers() {
+        let mut rng = ChaCha20Rng::from_seed([3u8; 32]);
+        let (mut states, mut dealings) = start(&mut rng, 3, 3);
+        dealings.truncate(2);
+
+        for output in finish(&mut states, &dealings, |_| ()) {
+            assert_eq!(
+                output.err(),
+                Some(DkgError::NotEnoughQualifiedDealers {
+                    qualified: 2,
+                    threshold: 3
+                })
+            );
+        }
+    }
+
+    #[test]
+    fn rounds_in_order() {
+        let mut rng = ChaCha20Rng::from_seed([4u8; 32]);
+        let (mut states, dealings) = start(&mut rng, 1, 2);
+        assert_eq!(
+            states[0].handle_complaints(&[]).err(),
+            Some(DkgError::UnexpectedPhase {
+                expected: DkgPhase::Complaint,
+                actual: DkgPhase::Dealing
+            })
+        );
+        assert_eq!(
+            states[0]
+                .handle_dealings(&[dealings[0].clone(), dealings[0].clone()])
+                .err(),
+            Some(DkgError::DuplicateMessage { index: 0 })
+        );
+    }
+
+    #[test]
+    fn serialization() {
+        let mut rng = ChaCha20Rng::from_seed([5u8; 32]);
+        let threshold = 2;
+        let (states, dealings) = start(&mut rng, threshold, 3);
+        let dealings: Vec<_> = dealings
+            .iter()
+            .map(|dealing| Dealing::from_bytes(&dealing.to_bytes()).unwrap())
+            .collect();
+
+        // restore the state of the members from bytes between every round
+        let mut states: Vec<_> = states
+            .iter()
+            .map(|state| DkgMember::from_bytes(&state.to_bytes()).unwrap())
+            .collect();
+        let complaints: Vec<_> = states
+            .iter_mut()
+            .map(|state| state.handle_dealings(&dealings).unwrap())
+            .map(|complaints| Complaints::from_bytes(&complaints.to_bytes()).unwrap())
+            .collect();
+
+        let mut states: Vec<_> = states
+            .iter()
+            .map(|state| DkgMember::from_bytes(&state.to_bytes()).unwrap())
+            .collect();
+        let disputes: Vec<_> = states
+            .iter_mut()
+            .map(|state| state.handle_complaints(&complaints).unwrap())
+            .map(|disputes| Disputes::from_bytes(&disputes.to_bytes()).unwrap())
+            .collect();
+
+        let outputs = states
+            .iter()
+            .map(|state| {
+                DkgMember::from_bytes(&state.to_bytes())
+                    .unwrap()
+                    .handle_disputes(&disputes)
+            })
+            .collect::<Result<Vec<_>, _>>()
+            .unwrap();
+        assert_consistent(&outputs, threshold);
+
+        assert!(DkgMember::from_bytes(&[]).is_none());
+        assert!(Dealing::from_bytes(&dealings[0].to_bytes()[1..]).is_none());
+    }
+}
@@ -51,6 +51,41 @@ impl Ballot {
         })
     }
 
+    /// Verify the votes and proofs of many ballots at once, possibly cast in
+    /// different elections. This is much faster than verifying each of them
+    /// with [`Ballot::try_from_vote_and_proof`], but on failure it does not
+    /// tell which of the ballots are invalid.
+    pub fn try_from_votes_and_proofs<'a, I>(
+        ballots: I,
+    ) -> Result<Vec<Self>, BallotVerificationError>
+    where
+        I: IntoIterator<
+            Item = (
+                EncryptedVote,
+                &'a ProofOfCorrectVote,
+                &'a Crs,
+                &'a ElectionPublicKey,
+            ),
+        >,
+    {
+        let ballots: Vec<_> = ballots.into_iter().collect();
+        let statements: Vec<_> = ballots
+            .iter()
+            .map(|(vote, proof, crs, pk)| (*proof, *crs, &pk.0, vote.as_slice()))
+            .collect();
+        if !ProofOfCorrectVote::batch_verify(&statements) {
+            return Err(BallotVerificationError);
+        }
+
+        Ok(ballots
+            .into_iter()
+            .map(|(vote, _, crs, pk)| Self {
+                vote,
+                fingerprint: (pk, crs).into(),
+            })
+            .collect())
+    }
+
     pub fn vote(&self) -> &EncryptedVote {
         &self.vote
     }
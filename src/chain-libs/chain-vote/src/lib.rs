@@ -2,9 +2,11 @@
 mod macros;
 pub mod committee;
 mod cryptography;
+pub mod dkg;
 mod encrypted_vote;
 mod math;
 pub mod tally;
+mod weighted_vote;
 
 // re-export under a debug module
 #[doc(hidden)]
@@ -24,7 +26,9 @@ const CURVE_HRP: &str = "p256k1";
 #[cfg(crypto_backend = "__internal_ex_backend_ristretto255")]
 const CURVE_HRP: &str = "ristretto255";
 
-pub use math::babystep::BabyStepsTable as TallyOptimizationTable;
+pub use math::babystep::{
+    BabyStepsTable as TallyOptimizationTable, TableError as TallyOptimizationTableError,
+};
 
 pub use crate::{
     committee::{ElectionPublicKey, MemberCommunicationKey, MemberPublicKey, MemberState},
@@ -34,4 +38,8 @@ pub use crate::{
         UnitVectorInitializationError, Vote,
     },
     tally::{Crs, EncryptedTally, Tally, TallyDecryptShare},
+    weighted_vote::{
+        EncryptedWeightedVote, ProofOfCorrectWeightedVote, WeightedBallot, WeightedBallotKind,
+        WeightedVote, WeightedVoteInitializationError,
+    },
 };
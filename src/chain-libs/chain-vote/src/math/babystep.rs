@@ -2,22 +2,89 @@
 //! a group of prime order.
 #[cfg(crypto_backend = "__internal_ex_backend_p256k1")]
 use crate::Coordinate;
-use crate::{GroupElement, Scalar};
+use crate::{GroupElement, Scalar, CURVE_HRP};
 use rayon::prelude::*;
-use std::{collections::HashMap, num::NonZeroU64};
+use std::{
+    collections::HashMap,
+    fs::File,
+    io::{self, BufReader, BufWriter, Read, Write},
+    num::NonZeroU64,
+    path::{Path, PathBuf},
+};
 
 // make steps asymmetric, in order to better use caching of baby steps.
 // balance of 2 means that baby steps are 2 time more than sqrt(max_votes)
 const DEFAULT_BALANCE: u64 = 2;
 
+const TABLE_MAGIC: &[u8; 4] = b"BSGS";
+const TABLE_VERSION: u8 = 1;
+const TABLE_EXTENSION: &str = "bsgs";
+
+// upper bound of the entries allocated upfront when the size of the table
+// cannot be checked against the length of its file
+const MAX_PREALLOCATED_STEPS: u64 = 1 << 20;
+
+// number of baby steps of a table that are checked against the generator
+// when the table is read
+const CHECKED_STEPS: u64 = 16;
+
+#[cfg(crypto_backend = "__internal_ex_backend_p256k1")]
+const KEY_LEN: usize = Coordinate::BYTES_LEN;
+#[cfg(crypto_backend = "__internal_ex_backend_ristretto255")]
+const KEY_LEN: usize = GroupElement::BYTES_LEN;
+
+type TableKey = Option<[u8; KEY_LEN]>;
+
+// With sec2 curves we can use the property that P and -P share a coordinate
+#[cfg(crypto_backend = "__internal_ex_backend_p256k1")]
+fn table_key(e: &GroupElement) -> TableKey {
+    e.compress().map(|(c, _sign)| c.to_bytes())
+}
+
+// Not with ristretto group. the ristretto group API does not allow to use the x coordinate
+// for security properties (see [here](https://github.com/dalek-cryptography/curve25519-dalek/issues/235))
+#[cfg(crypto_backend = "__internal_ex_backend_ristretto255")]
+fn table_key(e: &GroupElement) -> TableKey {
+    Some(e.to_bytes())
+}
+
+/// The largest baby step stored in a table with the given baby step size
+fn last_baby_step(baby_step_size: u64) -> u64 {
+    if cfg!(crypto_backend = "__internal_ex_backend_p256k1") {
+        baby_step_size / 2
+    } else {
+        baby_step_size
+    }
+}
+
+fn baby_step_size(max_value: NonZeroU64, balance: NonZeroU64) -> u64 {
+    let sqrt_step_size = (u64::from(max_value) as f64).sqrt().ceil() as u64;
+    sqrt_step_size * u64::from(balance)
+}
+
+#[derive(Debug, thiserror::Error)]
+pub enum TableError {
+    #[error(transparent)]
+    Io(#[from] io::Error),
+    #[error("not a baby steps table")]
+    Malformed,
+    #[error("unsupported baby steps table version {0}")]
+    UnsupportedVersion(u8),
+    #[error("baby steps table generated for the {0} curve")]
+    WrongCurve(String),
+    #[error(
+        "baby steps table generated for a max value of {max_value} with a balance of {balance}"
+    )]
+    WrongParameters { max_value: u64, balance: u64 },
+}
+
 /// Holds precomputed baby steps for the baby-stap giant-step algorithm
 /// for solving discrete log on ECC
 #[derive(Debug, Clone)]
 pub struct BabyStepsTable {
-    #[cfg(crypto_backend = "__internal_ex_backend_p256k1")]
-    table: HashMap<Option<[u8; Coordinate::BYTES_LEN]>, u64>,
-    #[cfg(crypto_backend = "__internal_ex_backend_ristretto255")]
-    table: HashMap<Option<[u8; GroupElement::BYTES_LEN]>, u64>,
+    table: HashMap<TableKey, u64>,
+    max_value: NonZeroU64,
+    balance: NonZeroU64,
     baby_step_size: u64,
     giant_step: GroupElement,
 }
@@ -36,39 +103,284 @@ impl BabyStepsTable {
     ///
     /// For example, a balance of 2 means that the table will precompute 2 times more
     /// baby steps than the standard O(sqrt(n)), 1 means symmetrical steps.
+    ///
+    /// The baby steps are computed in parallel, each thread of the rayon pool
+    /// taking care of a contiguous range of them.
     pub fn generate_with_balance(max_value: NonZeroU64, balance: NonZeroU64) -> Self {
-        let sqrt_step_size = (u64::from(max_value) as f64).sqrt().ceil() as u64;
-        let baby_step_size = sqrt_step_size * u64::from(balance);
-        let mut bs = HashMap::new();
-        let gen = GroupElement::generator();
-        let mut e = GroupElement::zero();
+        let baby_step_size = baby_step_size(max_value, balance);
+        let steps = last_baby_step(baby_step_size) + 1;
+        let chunks = rayon::current_num_threads() as u64;
+        let chunk_len = (steps + chunks - 1) / chunks;
 
-        // With sec2 curves we can use the property that P and -P share a coordinate
-        #[cfg(crypto_backend = "__internal_ex_backend_p256k1")]
-        for i in 0..=baby_step_size / 2 {
-            bs.insert(e.compress().map(|(c, _sign)| c.to_bytes()), i);
-            e = e + &gen;
-        }
-        // Not with ristretto group. the ristretto group API does not allow to use the x coordinate
-        // for security properties (see [here](https://github.com/dalek-cryptography/curve25519-dalek/issues/235))
-        #[cfg(crypto_backend = "__internal_ex_backend_ristretto255")]
-        for i in 0..=baby_step_size {
-            bs.insert(Some(e.to_bytes()), i);
-            e = e + &gen;
-        }
+        let bs: HashMap<_, _> = (0..chunks)
+            .into_par_iter()
+            .flat_map_iter(|chunk| {
+                let start = (chunk * chunk_len).min(steps);
+                let end = ((chunk + 1) * chunk_len).min(steps);
+                let gen = GroupElement::generator();
+                let mut e = &gen * Scalar::from_u64(start);
+                (start..end).map(move |i| {
+                    let key = table_key(&e);
+                    e = &e + &gen;
+                    (key, i)
+                })
+            })
+            .collect();
         assert!(!bs.is_empty());
         Self {
             table: bs,
+            max_value,
+            balance,
             baby_step_size,
             giant_step: GroupElement::generator() * Scalar::from_u64(baby_step_size).negate(),
         }
     }
+
+    /// Load the table for `max_value` from the directory `dir`, generating
+    /// and saving it there first if it does not exist yet.
+    pub fn load_or_generate<P: AsRef<Path>>(
+        dir: P,
+        max_value: NonZeroU64,
+    ) -> Result<Self, TableError> {
+        Self::load_or_generate_with_balance(dir, max_value, DEFAULT_BALANCE.try_into().unwrap())
+    }
+
+    /// Load the table for `max_value` and `balance` from the directory `dir`,
+    /// generating and saving it there first if it does not exist yet. See
+    /// [`BabyStepsTable::generate_with_balance`] for the meaning of the balance.
+    ///
+    /// A table saved for a larger max value can be used for a smaller one, so
+    /// the smallest of such tables saved in `dir` is loaded if there is no
+    /// table for `max_value` itself. A saved table that cannot be read is
+    /// ignored with a warning, and a new one is generated.
+    pub fn load_or_generate_with_balance<P: AsRef<Path>>(
+        dir: P,
+        max_value: NonZeroU64,
+        balance: NonZeroU64,
+    ) -> Result<Self, TableError> {
+        if let Some(path) = Self::find(&dir, max_value, balance)? {
+            let table = Self::read_file(&path).and_then(|table| {
+                if table.max_value < max_value || table.balance != balance {
+                    return Err(TableError::WrongParameters {
+                        max_value: table.max_value.get(),
+                        balance: table.balance.get(),
+                    });
+                }
+                Ok(table)
+            });
+            match table {
+                Ok(table) => return Ok(table),
+                Err(error) => tracing::warn!(
+                    path = %path.display(),
+                    %error,
+                    "cannot use the saved baby steps table, generating a new one"
+                ),
+            }
+        }
+
+        let path = Self::path(dir, max_value, balance);
+        let table = Self::generate_with_balance(max_value, balance);
+        // write to a temporary file first, so an interrupted run never leaves
+        // a truncated table behind
+        let tmp = path.with_extension("tmp");
+        let mut writer = BufWriter::new(File::create(&tmp)?);
+        table.write(&mut writer)?;
+        writer.flush()?;
+        std::fs::rename(tmp, path)?;
+        Ok(table)
+    }
+
+    /// Path of the table for `max_value` and `balance` in the directory `dir`
+    pub fn path<P: AsRef<Path>>(dir: P, max_value: NonZeroU64, balance: NonZeroU64) -> PathBuf {
+        dir.as_ref().join(format!(
+            "{}-{}-{}.{}",
+            CURVE_HRP, max_value, balance, TABLE_EXTENSION
+        ))
+    }
+
+    /// Path of the table saved in `dir` for the smallest max value that is at
+    /// least `max_value`, with the given `balance`
+    fn find<P: AsRef<Path>>(
+        dir: P,
+        max_value: NonZeroU64,
+        balance: NonZeroU64,
+    ) -> Result<Option<PathBuf>, TableError> {
+        let path = Self::path(&dir, max_value, balance);
+        if path.exists() {
+            return Ok(Some(path));
+        }
+        if !dir.as_ref().is_dir() {
+            return Ok(None);
+        }
+
+        let mut found: Option<(u64, PathBuf)> = None;
+        for entry in std::fs::read_dir(dir)? {
+            let path = entry?.path();
+            if path.extension().and_then(|ext| ext.to_str()) != Some(TABLE_EXTENSION) {
+                continue;
+            }
+            let name = match path.file_stem().and_then(|stem| stem.to_str()) {
+                Some(name) => name,
+                None => continue,
+            };
+            let mut parts = name.rsplitn(3, '-');
+            let (table_balance, table_max_value, curve) =
+                match (parts.next(), parts.next(), parts.next()) {
+                    (Some(balance), Some(max_value), Some(curve)) => (balance, max_value, curve),
+                    _ => continue,
+                };
+            let table_max_value = match table_max_value.parse::<u64>() {
+                Ok(table_max_value) => table_max_value,
+                Err(_) => continue,
+            };
+            if curve != CURVE_HRP
+                || table_balance.parse::<u64>().ok() != Some(balance.get())
+                || table_max_value < max_value.get()
+            {
+                continue;
+            }
+            if found.as_ref().map_or(true, |(found_max_value, _)| {
+                table_max_value < *found_max_value
+            }) {
+                found = Some((table_max_value, path));
+            }
+        }
+        Ok(found.map(|(_, path)| path))
+    }
+
+    pub fn max_value(&self) -> NonZeroU64 {
+        self.max_value
+    }
+
+    pub fn balance(&self) -> NonZeroU64 {
+        self.balance
+    }
+
+    /// Write the table, the baby steps being stored in increasing order
+    pub fn write<W: Write>(&self, mut writer: W) -> io::Result<()> {
+        writer.write_all(TABLE_MAGIC)?;
+        writer.write_all(&[TABLE_VERSION, CURVE_HRP.len() as u8])?;
+        writer.write_all(CURVE_HRP.as_bytes())?;
+        writer.write_all(&self.max_value.get().to_be_bytes())?;
+        writer.write_all(&self.balance.get().to_be_bytes())?;
+
+        let mut steps: Vec<_> = self.table.iter().collect();
+        steps.sort_unstable_by_key(|(_, i)| **i);
+        for (key, _) in steps {
+            match key {
+                None => writer.write_all(&[0; KEY_LEN + 1])?,
+                Some(key) => {
+                    writer.write_all(&[1])?;
+                    writer.write_all(key)?;
+                }
+            }
+        }
+        Ok(())
+    }
+
+    /// Read a table written with [`BabyStepsTable::write`] from the file at
+    /// `path`, the size of the table given in its header being checked
+    /// against the length of the file before reading the baby steps
+    pub fn read_file<P: AsRef<Path>>(path: P) -> Result<Self, TableError> {
+        let file = File::open(path)?;
+        let len = file.metadata()?.len();
+        Self::read_with_len(BufReader::new(file), Some(len))
+    }
+
+    /// Read a table written with [`BabyStepsTable::write`]
+    ///
+    /// A few of the baby steps are checked against the generator and the
+    /// table is rejected if any of the baby steps appears twice.
+    pub fn read<R: Read>(reader: R) -> Result<Self, TableError> {
+        Self::read_with_len(reader, None)
+    }
+
+    fn read_with_len<R: Read>(mut reader: R, len: Option<u64>) -> Result<Self, TableError> {
+        let mut magic = [0; 4];
+        reader.read_exact(&mut magic)?;
+        if &magic != TABLE_MAGIC {
+            return Err(TableError::Malformed);
+        }
+        let mut header = [0; 2];
+        reader.read_exact(&mut header)?;
+        if header[0] != TABLE_VERSION {
+            return Err(TableError::UnsupportedVersion(header[0]));
+        }
+        let mut curve = vec![0; header[1] as usize];
+        reader.read_exact(&mut curve)?;
+        if curve != CURVE_HRP.as_bytes() {
+            return Err(TableError::WrongCurve(
+                String::from_utf8_lossy(&curve).into_owned(),
+            ));
+        }
+        let max_value = read_non_zero_u64(&mut reader)?;
+        let balance = read_non_zero_u64(&mut reader)?;
+
+        let baby_step_size = baby_step_size(max_value, balance);
+        let steps = last_baby_step(baby_step_size) + 1;
+        let capacity = match len {
+            Some(len) => {
+                let header_len = (TABLE_MAGIC.len() + 2 + CURVE_HRP.len() + 2 * 8) as u64;
+                let expected_len = steps
+                    .checked_mul((KEY_LEN + 1) as u64)
+                    .and_then(|steps_len| steps_len.checked_add(header_len));
+                if expected_len != Some(len) {
+                    return Err(TableError::Malformed);
+                }
+                steps
+            }
+            None => steps.min(MAX_PREALLOCATED_STEPS),
+        };
+
+        // the steps at both ends of the table and a few in between
+        let check_every = (steps / CHECKED_STEPS).max(1);
+        let gen = GroupElement::generator();
+
+        let mut table = HashMap::with_capacity(capacity as usize);
+        let mut entry = [0; KEY_LEN + 1];
+        for i in 0..steps {
+            reader.read_exact(&mut entry)?;
+            let key = match entry[0] {
+                0 => None,
+                1 => Some(entry[1..].try_into().unwrap()),
+                _ => return Err(TableError::Malformed),
+            };
+            if (i % check_every == 0 || i == steps - 1)
+                && key != table_key(&(&gen * Scalar::from_u64(i)))
+            {
+                return Err(TableError::Malformed);
+            }
+            if table.insert(key, i).is_some() {
+                return Err(TableError::Malformed);
+            }
+        }
+        if reader.read(&mut entry)? != 0 {
+            return Err(TableError::Malformed);
+        }
+
+        Ok(Self {
+            table,
+            max_value,
+            balance,
+            baby_step_size,
+            giant_step: GroupElement::generator() * Scalar::from_u64(baby_step_size).negate(),
+        })
+    }
+}
+
+fn read_non_zero_u64<R: Read>(reader: &mut R) -> Result<NonZeroU64, TableError> {
+    let mut bytes = [0; 8];
+    reader.read_exact(&mut bytes)?;
+    NonZeroU64::new(u64::from_be_bytes(bytes)).ok_or(TableError::Malformed)
 }
 
 #[derive(Debug)]
 pub struct MaxLogExceeded;
 
 /// Solve the discrete log on ECC using baby step giant step algorithm
+///
+/// The points are solved in parallel, and so are the giant steps for each of
+/// them: every thread of the rayon pool walks its own lane of giant steps.
 pub fn baby_step_giant_step(
     points: Vec<GroupElement>,
     max_log: u64,
@@ -76,34 +388,40 @@ pub fn baby_step_giant_step(
 ) -> Result<Vec<u64>, MaxLogExceeded> {
     let baby_step_size = table.baby_step_size;
     let giant_step = &table.giant_step;
+    let max_giant_steps = max_log / baby_step_size + 1;
+    let lanes = (rayon::current_num_threads() as u64).min(max_giant_steps + 1);
+    let lane_step = giant_step * Scalar::from_u64(lanes);
     let table = &table.table;
     points
         .into_par_iter()
-        .map(|mut point| {
-            let mut a = 0;
-            loop {
-                #[cfg(crypto_backend = "__internal_ex_backend_p256k1")]
-                if let Some(x) = table.get(&point.compress().map(|(c, _sign)| c.to_bytes())) {
-                    let r = if Scalar::from_u64(*x) * GroupElement::generator() == point {
-                        a * baby_step_size + x
-                    } else {
-                        a * baby_step_size - x
-                    };
-                    return Ok(r);
-                }
+        .map(|point| {
+            (0..lanes)
+                .into_par_iter()
+                .find_map_any(|lane| {
+                    let mut point = &point + giant_step * Scalar::from_u64(lane);
+                    let mut a = lane;
+                    while a <= max_giant_steps {
+                        #[cfg(crypto_backend = "__internal_ex_backend_p256k1")]
+                        if let Some(x) = table.get(&table_key(&point)) {
+                            let r = if Scalar::from_u64(*x) * GroupElement::generator() == point {
+                                a * baby_step_size + x
+                            } else {
+                                a * baby_step_size - x
+                            };
+                            return Some(r);
+                        }
 
-                #[cfg(crypto_backend = "__internal_ex_backend_ristretto255")]
-                if let Some(x) = table.get(&Some(point.to_bytes())) {
-                    let r = a * baby_step_size + x;
-                    return Ok(r);
-                }
+                        #[cfg(crypto_backend = "__internal_ex_backend_ristretto255")]
+                        if let Some(x) = table.get(&table_key(&point)) {
+                            return Some(a * baby_step_size + x);
+                        }
 
-                if a * baby_step_size > max_log {
-                    return Err(MaxLogExceeded);
-                }
-                point = point + giant_step;
-                a += 1;
-            }
+                        point = point + &lane_step;
+                        a += lanes;
+                    }
+                    None
+                })
+                .ok_or(MaxLogExceeded)
         })
         .collect()
 }
@@ -137,6 +455,107 @@ mod tests {
         assert_eq!(votes, results);
     }
 
+    #[test]
+    fn max_log_exceeded() {
+        let table = BabyStepsTable::generate_with_balance(nz(25), nz(1));
+        let points = vec![GroupElement::generator() * Scalar::from_u64(1_000)];
+        assert!(baby_step_giant_step(points, 100, &table).is_err());
+    }
+
+    #[test]
+    fn table_write_read() {
+        let table = BabyStepsTable::generate_with_balance(nz(1_000), nz(3));
+        let mut bytes = Vec::new();
+        table.write(&mut bytes).unwrap();
+
+        let read = BabyStepsTable::read(bytes.as_slice()).unwrap();
+        assert_eq!(read.table, table.table);
+        assert_eq!(read.max_value(), table.max_value());
+        assert_eq!(read.balance(), table.balance());
+        assert_eq!(read.baby_step_size, table.baby_step_size);
+        assert_eq!(read.giant_step, table.giant_step);
+
+        let points = (0..1_000)
+            .map(|k| GroupElement::generator() * Scalar::from_u64(k))
+            .collect();
+        assert_eq!(
+            baby_step_giant_step(points, 1_000, &read).unwrap(),
+            (0..1_000u64).collect::<Vec<_>>()
+        );
+
+        assert!(matches!(
+            BabyStepsTable::read(&bytes[..bytes.len() - 1]),
+            Err(TableError::Io(_))
+        ));
+        bytes.push(0);
+        assert!(matches!(
+            BabyStepsTable::read(bytes.as_slice()),
+            Err(TableError::Malformed)
+        ));
+    }
+
+    #[test]
+    fn table_read_rejects_invalid_steps() {
+        let table = BabyStepsTable::generate_with_balance(nz(1_000), nz(3));
+        let mut bytes = Vec::new();
+        table.write(&mut bytes).unwrap();
+        let header_len = bytes.len() - table.table.len() * (KEY_LEN + 1);
+
+        // the header claims a much larger table than the one that follows
+        let mut large = bytes.clone();
+        large[header_len - 16..header_len - 8].copy_from_slice(&u64::MAX.to_be_bytes());
+        assert!(BabyStepsTable::read(large.as_slice()).is_err());
+
+        // the first two baby steps are swapped
+        let mut swapped = bytes.clone();
+        let (first, second) = swapped[header_len..].split_at_mut(KEY_LEN + 1);
+        first.swap_with_slice(&mut second[..KEY_LEN + 1]);
+        assert!(matches!(
+            BabyStepsTable::read(swapped.as_slice()),
+            Err(TableError::Malformed)
+        ));
+
+        // a baby step in the middle of the table appears twice
+        let mut duplicated = bytes.clone();
+        let step = |i: usize| header_len + i * (KEY_LEN + 1)..header_len + (i + 1) * (KEY_LEN + 1);
+        let copy = duplicated[step(4)].to_vec();
+        duplicated[step(5)].copy_from_slice(&copy);
+        assert!(matches!(
+            BabyStepsTable::read(duplicated.as_slice()),
+            Err(TableError::Malformed)
+        ));
+    }
+
+    #[test]
+    fn load_table_generated_for_larger_max_value() {
+        let dir = std::env::temp_dir().join(format!("bsgs-tables-{}", std::process::id()));
+        std::fs::create_dir_all(&dir).unwrap();
+
+        let large = BabyStepsTable::load_or_generate_with_balance(&dir, nz(1_000), nz(2)).unwrap();
+        // a truncated table for a larger max value, only used when no smaller
+        // table fits
+        let truncated = dir.join(format!("{}-{}-{}.bsgs", CURVE_HRP, 2_000, 2));
+        std::fs::write(&truncated, b"BSGS").unwrap();
+
+        let table = BabyStepsTable::load_or_generate_with_balance(&dir, nz(100), nz(2)).unwrap();
+        assert_eq!(table.max_value(), large.max_value());
+        assert_eq!(table.table, large.table);
+        assert!(!BabyStepsTable::path(&dir, nz(100), nz(2)).exists());
+
+        // no table for this balance yet
+        let other = BabyStepsTable::load_or_generate_with_balance(&dir, nz(100), nz(1)).unwrap();
+        assert_eq!(other.max_value(), nz(100));
+
+        // the truncated table is the only one that fits, a new one is
+        // generated instead
+        let regenerated =
+            BabyStepsTable::load_or_generate_with_balance(&dir, nz(1_500), nz(2)).unwrap();
+        assert_eq!(regenerated.max_value(), nz(1_500));
+        assert!(BabyStepsTable::path(&dir, nz(1_500), nz(2)).exists());
+
+        std::fs::remove_dir_all(dir).unwrap();
+    }
+
     fn fe_vec_generator() -> BoxGenerator<[(GroupElement, u64); 64]> {
         generator::Array64::new(generator::num::<u16>().map(|a| {
             (
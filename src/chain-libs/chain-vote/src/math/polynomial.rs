@@ -84,6 +84,34 @@ impl Polynomial {
     }
 }
 
+/// Compute the Lagrange basis polynomials evaluated at x=0 for a set of distinct
+/// evaluation `points`, i.e. the coefficients `l_i` such that `P(0) = sum(l_i * P(x_i))`
+/// for any polynomial `P` of degree strictly lower than the number of points.
+pub fn lagrange_coefficients_at_zero(points: &[Scalar]) -> Vec<Scalar> {
+    lagrange_coefficients_at(points, &Scalar::zero())
+}
+
+/// Compute the Lagrange basis polynomials evaluated at `x` for a set of distinct
+/// evaluation `points`, i.e. the coefficients `l_i` such that `P(x) = sum(l_i * P(x_i))`
+/// for any polynomial `P` of degree strictly lower than the number of points.
+pub fn lagrange_coefficients_at(points: &[Scalar], x: &Scalar) -> Vec<Scalar> {
+    points
+        .iter()
+        .enumerate()
+        .map(|(i, xi)| {
+            let mut numerator = Scalar::one();
+            let mut denominator = Scalar::one();
+            for (j, xj) in points.iter().enumerate() {
+                if i != j {
+                    numerator = numerator * (x - xj);
+                    denominator = denominator * (xi - xj);
+                }
+            }
+            numerator * denominator.inverse()
+        })
+        .collect()
+}
+
 impl std::ops::Add<Polynomial> for Polynomial {
     type Output = Polynomial;
 
@@ -162,4 +190,61 @@ mod tests {
             assert_eq!(a, b);
         }
     }
+
+    #[test]
+    fn lagrange_interpolation_at_zero() {
+        use rand_chacha::ChaCha20Rng;
+        use rand_core::SeedableRng;
+
+        let mut rng = ChaCha20Rng::from_seed([0u8; 32]);
+        let poly = Polynomial::random(&mut rng, 2);
+
+        for indices in [[1u64, 2, 3], [2, 4, 5], [5, 1, 3]] {
+            let points: Vec<Scalar> = indices.iter().map(|i| Scalar::from_u64(*i)).collect();
+            let coefficients = lagrange_coefficients_at_zero(&points);
+            let interpolated = Scalar::sum(
+                coefficients
+                    .iter()
+                    .zip(points.iter())
+                    .map(|(l, x)| l * poly.evaluate(x)),
+            )
+            .unwrap();
+            assert_eq!(interpolated, poly.at_zero());
+        }
+
+        // not enough points to interpolate a degree 2 polynomial
+        let points = [Scalar::from_u64(1), Scalar::from_u64(2)];
+        let coefficients = lagrange_coefficients_at_zero(&points);
+        let interpolated = Scalar::sum(
+            coefficients
+                .iter()
+                .zip(points.iter())
+                .map(|(l, x)| l * poly.evaluate(x)),
+        )
+        .unwrap();
+        assert_ne!(interpolated, poly.at_zero());
+    }
+
+    #[test]
+    fn lagrange_interpolation_at_point() {
+        use rand_chacha::ChaCha20Rng;
+        use rand_core::SeedableRng;
+
+        let mut rng = ChaCha20Rng::from_seed([0u8; 32]);
+        let poly = Polynomial::random(&mut rng, 2);
+
+        let points: Vec<Scalar> = [1u64, 2, 3].iter().map(|i| Scalar::from_u64(*i)).collect();
+        for x in [4u64, 7] {
+            let x = Scalar::from_u64(x);
+            let coefficients = lagrange_coefficients_at(&points, &x);
+            let interpolated = Scalar::sum(
+                coefficients
+                    .iter()
+                    .zip(points.iter())
+                    .map(|(l, xi)| l * poly.evaluate(xi)),
+            )
+            .unwrap();
+            assert_eq!(interpolated, poly.evaluate(&x));
+        }
+    }
 }
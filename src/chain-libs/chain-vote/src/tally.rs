@@ -4,11 +4,12 @@ use crate::{
     committee::*,
     cryptography::{Ciphertext, CorrectShareGenerationZkp},
     encrypted_vote::Ballot,
-    math::babystep::baby_step_giant_step,
+    math::{babystep::baby_step_giant_step, polynomial::lagrange_coefficients_at_zero},
+    weighted_vote::WeightedBallot,
     TallyOptimizationTable,
 };
 
-use crate::GroupElement;
+use crate::{GroupElement, Scalar};
 use cryptoxide::blake2b::Blake2b;
 use cryptoxide::digest::Digest;
 use rand_core::{CryptoRng, RngCore};
@@ -53,10 +54,10 @@ pub struct EncryptedTally {
     max_stake: u64,
 }
 
-/// `TallyDecryptShare` contains one decryption share per existing option. All committee
-/// members (todo: this will change once DKG is completed)
-/// need to submit a `TallyDecryptShare` in order to successfully decrypt
-/// the `EncryptedTally`.
+/// `TallyDecryptShare` contains one decryption share per existing option. Depending on
+/// how the election secret key is shared among the committee, either all committee
+/// members or any `threshold` of them need to submit a `TallyDecryptShare` in order to
+/// successfully decrypt the `EncryptedTally`.
 #[derive(Clone, Debug, Eq, PartialEq, Hash)]
 pub struct TallyDecryptShare {
     elements: Vec<ProvenDecryptShare>,
@@ -75,6 +76,9 @@ pub struct TallyDecryptShare {
 pub struct ValidatedTally {
     r: Vec<Ciphertext>,
     decrypt_shares: Vec<TallyDecryptShare>,
+    // Lagrange coefficients used to combine the `decrypt_shares` of a threshold committee,
+    // the shares of all the members of an n-out-of-n committee are simply added up.
+    lagrange_coefficients: Option<Vec<Scalar>>,
     max_stake: u64,
 }
 
@@ -101,6 +105,10 @@ pub struct TallyError;
 #[error("Incorrect decryption shares")]
 pub struct DecryptionError;
 
+#[derive(Debug, Clone, Copy, PartialEq, Eq, thiserror::Error)]
+#[error("the maximum stake of the tally overflows")]
+pub struct TallyOverflowError;
+
 impl EncryptedTally {
     const MAX_STAKE_BYTES_LEN: usize = std::mem::size_of::<u64>();
 
@@ -123,13 +131,46 @@ impl EncryptedTally {
     ///
     /// Note that the encrypted vote needs to have the exact same number of
     /// options as the initialised tally, otherwise an assert will trigger.
-    pub fn add(&mut self, ballot: &Ballot, weight: u64) {
+    /// The tally is left untouched if its maximum stake would overflow.
+    pub fn add(&mut self, ballot: &Ballot, weight: u64) -> Result<(), TallyOverflowError> {
         assert_eq!(ballot.vote().len(), self.r.len());
         assert_eq!(ballot.fingerprint(), &self.fingerprint);
+        let max_stake = self
+            .max_stake
+            .checked_add(weight)
+            .ok_or(TallyOverflowError)?;
         for (ri, ci) in self.r.iter_mut().zip(ballot.vote().iter()) {
             *ri = &*ri + &(ci * weight);
         }
-        self.max_stake += weight;
+        self.max_stake = max_stake;
+        Ok(())
+    }
+
+    /// Add a weighted ballot with the specified weight to the tally.
+    ///
+    /// Every option receives the weight of the ballot multiplied by the weight the voter
+    /// gave to the option, so the maximum of the tally grows by the weight multiplied by
+    /// the largest weight the ballot can give to a single option.
+    ///
+    /// Note that the weighted ballot needs to have the exact same number of
+    /// options as the initialised tally, otherwise an assert will trigger.
+    /// The tally is left untouched if its maximum stake would overflow.
+    pub fn add_weighted(
+        &mut self,
+        ballot: &WeightedBallot,
+        weight: u64,
+    ) -> Result<(), TallyOverflowError> {
+        assert_eq!(ballot.weights().len(), self.r.len());
+        assert_eq!(ballot.fingerprint(), &self.fingerprint);
+        let max_stake = weight
+            .checked_mul(ballot.max_weight() as u64)
+            .and_then(|max_weight| self.max_stake.checked_add(max_weight))
+            .ok_or(TallyOverflowError)?;
+        for (ri, ci) in self.r.iter_mut().zip(ballot.weights().iter()) {
+            *ri = &*ri + &(ci * weight);
+        }
+        self.max_stake = max_stake;
+        Ok(())
     }
 
     /// Given a single committee member's `secret_key`, returns a partial decryption of
@@ -175,6 +216,48 @@ impl EncryptedTally {
         Ok(ValidatedTally {
             r: self.r.clone(),
             decrypt_shares: decrypt_shares.to_vec(),
+            lagrange_coefficients: None,
+            max_stake: self.max_stake,
+        })
+    }
+
+    /// Given the members `pks` of a committee sharing the election secret key with a
+    /// `threshold` out of `pks.len()` scheme, and the `decrypt_shares` of the members
+    /// that took part in the decryption (in the same order as `pks`, with `None` for the
+    /// members that did not), this function validates the different shares and returns
+    /// a `ValidatedTally`, or a `DecryptionError` if any share is invalid or if less than
+    /// `threshold` shares were provided.
+    pub fn validate_threshold_partial_decryptions(
+        &self,
+        threshold: usize,
+        pks: &[MemberPublicKey],
+        decrypt_shares: &[Option<TallyDecryptShare>],
+    ) -> Result<ValidatedTally, DecryptionError> {
+        if threshold == 0 || pks.len() != decrypt_shares.len() {
+            return Err(DecryptionError);
+        }
+
+        let mut points = Vec::with_capacity(threshold);
+        let mut validated_shares = Vec::with_capacity(threshold);
+        for (index, (pk, decrypt_share)) in pks.iter().zip(decrypt_shares.iter()).enumerate() {
+            if let Some(decrypt_share) = decrypt_share {
+                if !decrypt_share.verify(self, pk) {
+                    return Err(DecryptionError);
+                }
+                // committee members are 1-indexed
+                points.push(Scalar::from_u64(index as u64 + 1));
+                validated_shares.push(decrypt_share.clone());
+            }
+        }
+
+        if validated_shares.len() < threshold {
+            return Err(DecryptionError);
+        }
+
+        Ok(ValidatedTally {
+            r: self.r.clone(),
+            decrypt_shares: validated_shares,
+            lagrange_coefficients: Some(lagrange_coefficients_at_zero(&points)),
             max_stake: self.max_stake,
         })
     }
@@ -237,8 +320,15 @@ impl ValidatedTally {
     // `decrypt_tally`.
     fn decrypt(&self) -> Vec<GroupElement> {
         let state: Vec<GroupElement> = self.r.iter().map(|c| c.e2.clone()).collect();
-        let ris = (0..state.len())
-            .map(|i| GroupElement::sum(self.decrypt_shares.iter().map(|ds| &ds.elements[i].r1)));
+        let ris = (0..state.len()).map(|i| match &self.lagrange_coefficients {
+            None => GroupElement::sum(self.decrypt_shares.iter().map(|ds| &ds.elements[i].r1)),
+            Some(coefficients) => GroupElement::vartime_multiscalar_multiplication(
+                coefficients.iter().cloned(),
+                self.decrypt_shares
+                    .iter()
+                    .map(|ds| ds.elements[i].r1.clone()),
+            ),
+        });
 
         state
             .iter()
@@ -369,12 +459,33 @@ impl Tally {
         pks: &[MemberPublicKey],
         decrypt_shares: &[TallyDecryptShare],
     ) -> bool {
-        let validated_decryptions =
-            match encrypted_tally.validate_partial_decryptions(pks, decrypt_shares) {
-                Ok(dec) => dec,
-                Err(_) => return false,
-            };
+        match encrypted_tally.validate_partial_decryptions(pks, decrypt_shares) {
+            Ok(validated_decryptions) => self.verify_decryption(&validated_decryptions),
+            Err(_) => false,
+        }
+    }
+
+    /// Verifies that the decrypted tally was correctly obtained from the given
+    /// `EncryptedTally` and the `TallyDecryptShare`s of at least `threshold` members of
+    /// a threshold committee with keys `pks`.
+    ///
+    /// See [`EncryptedTally::validate_threshold_partial_decryptions`] for the expected
+    /// layout of `decrypt_shares`.
+    pub fn verify_threshold(
+        &self,
+        encrypted_tally: &EncryptedTally,
+        threshold: usize,
+        pks: &[MemberPublicKey],
+        decrypt_shares: &[Option<TallyDecryptShare>],
+    ) -> bool {
+        match encrypted_tally.validate_threshold_partial_decryptions(threshold, pks, decrypt_shares)
+        {
+            Ok(validated_decryptions) => self.verify_decryption(&validated_decryptions),
+            Err(_) => false,
+        }
+    }
 
+    fn verify_decryption(&self, validated_decryptions: &ValidatedTally) -> bool {
         let r_results = validated_decryptions.decrypt();
         let gen = GroupElement::generator();
         for (i, &w) in self.votes.iter().enumerate() {
@@ -390,6 +501,22 @@ impl Tally {
 pub fn batch_decrypt(
     validated_tallies: impl AsRef<[ValidatedTally]>,
 ) -> Result<Vec<Tally>, TallyError> {
+    batch_decrypt_with_table(validated_tallies, |max_stake| {
+        Ok(TallyOptimizationTable::generate(max_stake))
+    })
+}
+
+/// Decrypt a slice of `ValidatedTally`s, using a single baby-step giant-step table
+/// obtained from `table` for the largest max stake of the tallies, e.g. a table
+/// precomputed and saved to disk with [`TallyOptimizationTable::load_or_generate`].
+pub fn batch_decrypt_with_table<F, E>(
+    validated_tallies: impl AsRef<[ValidatedTally]>,
+    table: F,
+) -> Result<Vec<Tally>, E>
+where
+    F: FnOnce(NonZeroU64) -> Result<TallyOptimizationTable, E>,
+    E: From<TallyError>,
+{
     let validated_tallies = validated_tallies.as_ref();
     let absolute_max_stake = validated_tallies.iter().map(|tally| tally.max_stake).max();
 
@@ -402,10 +529,10 @@ pub fn batch_decrypt(
         // if absolute_max_stake == 0, all stakes are zero, so safe to do a trivial conversion
         Err(_) => Ok(validated_tallies.iter().map(trivial_convert).collect()),
         Ok(absolute_max_stake) => {
-            let table = TallyOptimizationTable::generate(absolute_max_stake);
+            let table = table(absolute_max_stake)?;
             validated_tallies
                 .iter()
-                .map(|tally| tally.decrypt_tally(&table))
+                .map(|tally| tally.decrypt_tally(&table).map_err(E::from))
                 .collect()
         }
     }
@@ -423,9 +550,10 @@ fn trivial_convert(validated_tally: &ValidatedTally) -> Tally {
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::cryptography::{Keypair, PublicKey};
+    use crate::cryptography::{Keypair, PublicKey, SecretKey};
     use crate::encrypted_vote::Vote;
-    use crate::Ballot;
+    use crate::math::polynomial::Polynomial;
+    use crate::{Ballot, BallotVerificationError};
     use rand_chacha::ChaCha20Rng;
     use rand_core::{CryptoRng, RngCore, SeedableRng};
 
@@ -467,9 +595,9 @@ mod tests {
         println!("tallying");
 
         let mut encrypted_tally = EncryptedTally::new(vote_options, ek.clone(), h.clone());
-        encrypted_tally.add(&e1, 6);
-        encrypted_tally.add(&e2, 5);
-        encrypted_tally.add(&e3, 4);
+        encrypted_tally.add(&e1, 6).unwrap();
+        encrypted_tally.add(&e2, 5).unwrap();
+        encrypted_tally.add(&e3, 4).unwrap();
 
         let tds1 = encrypted_tally.partial_decrypt(&mut rng, m1.secret_key());
 
@@ -497,6 +625,50 @@ mod tests {
         assert!(tr.verify(&encrypted_tally, &participants, &shares));
     }
 
+    #[test]
+    fn batch_verified_ballots() {
+        let mut rng = ChaCha20Rng::from_seed([0u8; 32]);
+
+        let h = Crs::from_hash(b"Example of a shared string. This should be VotePlan.to_id()");
+        let mc1 = MemberCommunicationKey::new(&mut rng);
+        let m1 = MemberState::new(&mut rng, 1, &h, &[mc1.to_public()], 0);
+        let ek = ElectionPublicKey::from_participants(&[m1.public_key()]);
+
+        let vote_options = 3;
+        let votes: Vec<_> = (0..6)
+            .map(|i| {
+                ek.encrypt_and_prove_vote(&mut rng, &h, Vote::new(vote_options, i % 3).unwrap())
+            })
+            .collect();
+
+        let ballots = Ballot::try_from_votes_and_proofs(
+            votes
+                .iter()
+                .map(|(vote, proof)| (vote.clone(), proof, &h, &ek)),
+        )
+        .unwrap();
+        let expected: Vec<_> = votes
+            .iter()
+            .map(|(vote, proof)| {
+                Ballot::try_from_vote_and_proof(vote.clone(), proof, &h, &ek).unwrap()
+            })
+            .collect();
+        assert_eq!(ballots, expected);
+
+        // the vote of a ballot is swapped with the one of another ballot
+        let result = Ballot::try_from_votes_and_proofs(votes.iter().enumerate().map(
+            |(i, (vote, proof))| {
+                let vote = if i == 4 {
+                    votes[5].0.clone()
+                } else {
+                    vote.clone()
+                };
+                (vote, proof, &h, &ek)
+            },
+        ));
+        assert_eq!(result, Err(BallotVerificationError));
+    }
+
     #[test]
     fn encdec3() {
         let mut rng = ChaCha20Rng::from_seed([0u8; 32]);
@@ -529,9 +701,9 @@ mod tests {
         println!("tallying");
 
         let mut encrypted_tally = EncryptedTally::new(vote_options, ek, h);
-        encrypted_tally.add(&e1, 1);
-        encrypted_tally.add(&e2, 3);
-        encrypted_tally.add(&e3, 4);
+        encrypted_tally.add(&e1, 1).unwrap();
+        encrypted_tally.add(&e2, 3).unwrap();
+        encrypted_tally.add(&e3, 4).unwrap();
 
         let tds1 = encrypted_tally.partial_decrypt(&mut rng, m1.secret_key());
         let tds2 = encrypted_tally.partial_decrypt(&mut rng, m2.secret_key());
@@ -589,10 +761,12 @@ mod tests {
         println!("tallying");
 
         let mut encrypted_tally = EncryptedTally::new(vote_options, ek.clone(), h.clone());
-        encrypted_tally.add(
-            &get_encrypted_ballot(&mut rng, &ek, &h, Vote::new(vote_options, 0).unwrap()),
-            42,
-        );
+        encrypted_tally
+            .add(
+                &get_encrypted_ballot(&mut rng, &ek, &h, Vote::new(vote_options, 0).unwrap()),
+                42,
+            )
+            .unwrap();
 
         let tds1 = encrypted_tally.partial_decrypt(&mut rng, m1.secret_key());
 
@@ -696,7 +870,7 @@ mod tests {
         println!("tallying");
 
         let mut encrypted_tally = EncryptedTally::new(vote_options, ek, h);
-        encrypted_tally.add(&e, 1);
+        encrypted_tally.add(&e, 1).unwrap();
 
         let mut tds = encrypted_tally.partial_decrypt(&mut rng, m.secret_key());
 
@@ -707,6 +881,25 @@ mod tests {
         assert!(!tds.verify(&encrypted_tally, &m.public_key()))
     }
 
+    #[test]
+    fn tally_max_stake_overflow() {
+        let mut rng = ChaCha20Rng::from_seed([0u8; 32]);
+
+        let h = Crs::from_hash(&[1u8]);
+        let mc = MemberCommunicationKey::new(&mut rng);
+        let m = MemberState::new(&mut rng, 1, &h, &[mc.to_public()], 0);
+        let ek = ElectionPublicKey::from_participants(&[m.public_key()]);
+
+        let vote_options = 2;
+        let e = get_encrypted_ballot(&mut rng, &ek, &h, Vote::new(vote_options, 0).unwrap());
+
+        let mut encrypted_tally = EncryptedTally::new(vote_options, ek, h);
+        encrypted_tally.add(&e, u64::MAX).unwrap();
+        let before = encrypted_tally.clone();
+        assert!(encrypted_tally.add(&e, 1).is_err());
+        assert_eq!(encrypted_tally, before);
+    }
+
     #[test]
     fn zero_encrypted_tally_serialization_sanity() {
         let election_key = ElectionPublicKey(PublicKey {
@@ -754,6 +947,145 @@ mod tests {
         assert!(tally_from_bytes.is_some());
     }
 
+    // Shamir-share a random election secret key among `members` committee members, so
+    // that any `threshold` of them can decrypt.
+    fn threshold_committee<R: RngCore + CryptoRng>(
+        rng: &mut R,
+        threshold: usize,
+        members: usize,
+    ) -> (Vec<MemberSecretKey>, Vec<MemberPublicKey>) {
+        let polynomial = Polynomial::random(rng, threshold - 1);
+        let sks: Vec<MemberSecretKey> = (1..=members)
+            .map(|index| {
+                MemberSecretKey(SecretKey {
+                    sk: polynomial.evaluate(&Scalar::from_u64(index as u64)),
+                })
+            })
+            .collect();
+        let pks = sks.iter().map(|sk| sk.to_public()).collect();
+        (sks, pks)
+    }
+
+    #[test]
+    fn threshold_participants_consistency() {
+        let mut rng = ChaCha20Rng::from_seed([0u8; 32]);
+
+        let (_, mut pks) = threshold_committee(&mut rng, 2, 4);
+        assert!(ElectionPublicKey::check_threshold_participants(2, &pks));
+        assert!(ElectionPublicKey::check_threshold_participants(4, &pks));
+        assert!(!ElectionPublicKey::check_threshold_participants(0, &pks));
+        assert!(!ElectionPublicKey::check_threshold_participants(5, &pks));
+
+        // a key from another sharing after the first `threshold` ones
+        let (_, other_pks) = threshold_committee(&mut rng, 2, 4);
+        pks[3] = other_pks[3].clone();
+        assert!(!ElectionPublicKey::check_threshold_participants(2, &pks));
+    }
+
+    #[test]
+    fn threshold_encdec() {
+        let mut rng = ChaCha20Rng::from_seed([0u8; 32]);
+
+        let shared_string =
+            b"Example of a shared string. This should be VotePlan.to_id()".to_owned();
+        let h = Crs::from_hash(&shared_string);
+
+        let threshold = 3;
+        let (sks, pks) = threshold_committee(&mut rng, threshold, 5);
+        let ek = ElectionPublicKey::from_threshold_participants(threshold, &pks);
+
+        let vote_options = 2;
+        let e1 = get_encrypted_ballot(&mut rng, &ek, &h, Vote::new(vote_options, 0).unwrap());
+        let e2 = get_encrypted_ballot(&mut rng, &ek, &h, Vote::new(vote_options, 1).unwrap());
+        let e3 = get_encrypted_ballot(&mut rng, &ek, &h, Vote::new(vote_options, 0).unwrap());
+
+        let mut encrypted_tally = EncryptedTally::new(vote_options, ek, h);
+        encrypted_tally.add(&e1, 1).unwrap();
+        encrypted_tally.add(&e2, 3).unwrap();
+        encrypted_tally.add(&e3, 4).unwrap();
+
+        let table = TallyOptimizationTable::generate_with_balance(
+            20.try_into().unwrap(),
+            1.try_into().unwrap(),
+        );
+
+        // any `threshold` members are enough to decrypt, in any position
+        for participants in [[0, 1, 2], [1, 3, 4], [4, 0, 2]] {
+            let mut shares = vec![None; pks.len()];
+            for i in participants {
+                shares[i] = Some(encrypted_tally.partial_decrypt(&mut rng, &sks[i]));
+            }
+
+            let tr = encrypted_tally
+                .validate_threshold_partial_decryptions(threshold, &pks, &shares)
+                .unwrap()
+                .decrypt_tally(&table)
+                .unwrap();
+
+            assert_eq!(tr.votes, vec![5, 3]);
+            assert!(tr.verify_threshold(&encrypted_tally, threshold, &pks, &shares));
+            assert!(!Tally { votes: vec![4, 3] }.verify_threshold(
+                &encrypted_tally,
+                threshold,
+                &pks,
+                &shares
+            ));
+        }
+
+        // more than `threshold` shares can be provided as well
+        let shares: Vec<_> = sks
+            .iter()
+            .map(|sk| Some(encrypted_tally.partial_decrypt(&mut rng, sk)))
+            .collect();
+        let tr = encrypted_tally
+            .validate_threshold_partial_decryptions(threshold, &pks, &shares)
+            .unwrap()
+            .decrypt_tally(&table)
+            .unwrap();
+        assert_eq!(tr.votes, vec![5, 3]);
+    }
+
+    #[test]
+    fn threshold_not_enough_or_invalid_shares() {
+        let mut rng = ChaCha20Rng::from_seed([0u8; 32]);
+
+        let h = Crs::from_hash(&[1u8]);
+        let threshold = 3;
+        let (sks, pks) = threshold_committee(&mut rng, threshold, 5);
+        let ek = ElectionPublicKey::from_threshold_participants(threshold, &pks);
+
+        let vote_options = 2;
+        let mut encrypted_tally = EncryptedTally::new(vote_options, ek.clone(), h.clone());
+        encrypted_tally
+            .add(
+                &get_encrypted_ballot(&mut rng, &ek, &h, Vote::new(vote_options, 1).unwrap()),
+                7,
+            )
+            .unwrap();
+
+        let mut shares = vec![None; pks.len()];
+        shares[0] = Some(encrypted_tally.partial_decrypt(&mut rng, &sks[0]));
+        shares[3] = Some(encrypted_tally.partial_decrypt(&mut rng, &sks[3]));
+        assert!(encrypted_tally
+            .validate_threshold_partial_decryptions(threshold, &pks, &shares)
+            .is_err());
+
+        // a share placed at the position of a different member is rejected
+        shares[1] = Some(encrypted_tally.partial_decrypt(&mut rng, &sks[2]));
+        assert!(encrypted_tally
+            .validate_threshold_partial_decryptions(threshold, &pks, &shares)
+            .is_err());
+
+        // shares must be provided for the whole committee
+        shares[1] = Some(encrypted_tally.partial_decrypt(&mut rng, &sks[1]));
+        assert!(encrypted_tally
+            .validate_threshold_partial_decryptions(threshold, &pks, &shares)
+            .is_ok());
+        assert!(encrypted_tally
+            .validate_threshold_partial_decryptions(threshold, &pks[..4], &shares[..3])
+            .is_err());
+    }
+
     #[test]
     fn batch_decrypt_empty_slice() {
         assert_eq!(batch_decrypt(&[]).unwrap(), []);
@@ -791,9 +1123,9 @@ mod tests {
         println!("tallying");
 
         let mut encrypted_tally = EncryptedTally::new(vote_options, ek, h);
-        encrypted_tally.add(&e1, 1);
-        encrypted_tally.add(&e2, 3);
-        encrypted_tally.add(&e3, 4);
+        encrypted_tally.add(&e1, 1).unwrap();
+        encrypted_tally.add(&e2, 3).unwrap();
+        encrypted_tally.add(&e3, 4).unwrap();
 
         let tds1 = encrypted_tally.partial_decrypt(&mut rng, m1.secret_key());
         let tds2 = encrypted_tally.partial_decrypt(&mut rng, m2.secret_key());
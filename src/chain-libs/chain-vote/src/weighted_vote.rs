@@ -0,0 +1,536 @@
+//! Private ballots where the voter spreads several selections, or a budget, over the
+//! options of a proposal, instead of picking a single one of them.
+//!
+//! The weight given to every option is encrypted bit by bit, and every bit comes with
+//! a proof that it encrypts either 0 or 1. The ballot also encrypts, bit by bit, the
+//! part of the selections or of the budget that the voter leaves unused, so that a single
+//! proof of discrete log equality over the homomorphic sum of all the ciphertexts shows
+//! that the voter spent exactly the selections or the budget of the ballot kind.
+use crate::cryptography::{BinaryZkp, Ciphertext, DleqZkp, PublicKey};
+use crate::tally::ElectionFingerprint;
+use crate::{BallotVerificationError, Crs, ElectionPublicKey, GroupElement, Scalar};
+use rand_core::{CryptoRng, RngCore};
+use std::num::NonZeroU8;
+
+/// The kind of a weighted ballot, which sets how the voter can distribute their vote
+/// over the options of a proposal.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum WeightedBallotKind {
+    /// Approval voting: the voter selects up to `max_selections` options, and every
+    /// selected option receives the full weight of the voter.
+    Approval { max_selections: NonZeroU8 },
+    /// Budget voting: the voter spreads up to `budget` points over the options.
+    Budget { budget: NonZeroU8 },
+}
+
+/// number of bits needed to represent `value`
+fn bits(value: u8) -> usize {
+    (u8::BITS - value.leading_zeros()) as usize
+}
+
+impl WeightedBallotKind {
+    /// The total of the weights a voter can give to the options of a proposal
+    pub fn total(&self) -> u8 {
+        match self {
+            WeightedBallotKind::Approval { max_selections } => max_selections.get(),
+            WeightedBallotKind::Budget { budget } => budget.get(),
+        }
+    }
+
+    /// The largest weight a voter can give to a single option
+    pub fn max_weight(&self) -> u8 {
+        match self {
+            WeightedBallotKind::Approval { .. } => 1,
+            WeightedBallotKind::Budget { budget } => budget.get(),
+        }
+    }
+
+    fn option_bits(&self) -> usize {
+        bits(self.max_weight())
+    }
+
+    fn slack_bits(&self) -> usize {
+        bits(self.total())
+    }
+
+    /// Number of ciphertexts in an encrypted vote of this kind for `options` options
+    pub fn encrypted_vote_len(&self, options: usize) -> usize {
+        options * self.option_bits() + self.slack_bits()
+    }
+
+    /// Number of options of an encrypted vote of this kind made of `len` ciphertexts,
+    /// if the length is consistent with this kind.
+    fn options_of_len(&self, len: usize) -> Option<usize> {
+        let len = len.checked_sub(self.slack_bits())?;
+        if len == 0 || len % self.option_bits() != 0 {
+            return None;
+        }
+        Some(len / self.option_bits())
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, thiserror::Error)]
+pub enum WeightedVoteInitializationError {
+    #[error("A weighted vote needs at least one option")]
+    NoOptions,
+    #[error("The weight {weight} of option {option} is larger than the maximum weight {max}")]
+    WeightTooLarge { option: usize, weight: u8, max: u8 },
+    #[error("The total of the weights {total} is larger than the maximum {max}")]
+    TotalTooLarge { total: u64, max: u8 },
+}
+
+/// Weights given by a voter to each option of a proposal
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct WeightedVote {
+    kind: WeightedBallotKind,
+    weights: Vec<u8>,
+}
+
+impl WeightedVote {
+    /// Create a new vote of the given `kind`, with one weight per option of the proposal.
+    pub fn new(
+        kind: WeightedBallotKind,
+        weights: Vec<u8>,
+    ) -> Result<Self, WeightedVoteInitializationError> {
+        if weights.is_empty() {
+            return Err(WeightedVoteInitializationError::NoOptions);
+        }
+        if let Some((option, &weight)) = weights
+            .iter()
+            .enumerate()
+            .find(|(_, weight)| **weight > kind.max_weight())
+        {
+            return Err(WeightedVoteInitializationError::WeightTooLarge {
+                option,
+                weight,
+                max: kind.max_weight(),
+            });
+        }
+        let total = weights.iter().map(|weight| *weight as u64).sum();
+        if total > kind.total() as u64 {
+            return Err(WeightedVoteInitializationError::TotalTooLarge {
+                total,
+                max: kind.total(),
+            });
+        }
+        Ok(WeightedVote { kind, weights })
+    }
+
+    pub fn kind(&self) -> &WeightedBallotKind {
+        &self.kind
+    }
+
+    pub fn weights(&self) -> &[u8] {
+        &self.weights
+    }
+
+    /// the unused part of the total of the ballot kind
+    fn slack(&self) -> u8 {
+        self.kind.total() - self.weights.iter().sum::<u8>()
+    }
+}
+
+/// Encrypted weighted vote: the bits of the weight of every option, least significant
+/// first, followed by the bits of the unused part of the total, each of them encrypted
+/// with the Election Public Key.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct EncryptedWeightedVote(Vec<Ciphertext>);
+
+impl EncryptedWeightedVote {
+    pub fn len(&self) -> usize {
+        self.0.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.0.is_empty()
+    }
+
+    pub fn ciphertexts(&self) -> &[Ciphertext] {
+        &self.0
+    }
+
+    pub fn to_bytes(&self) -> Vec<u8> {
+        self.0.iter().flat_map(Ciphertext::to_bytes).collect()
+    }
+
+    pub fn from_bytes(bytes: &[u8]) -> Option<Self> {
+        if bytes.is_empty() || bytes.len() % Ciphertext::BYTES_LEN != 0 {
+            return None;
+        }
+        bytes
+            .chunks(Ciphertext::BYTES_LEN)
+            .map(Ciphertext::from_bytes)
+            .collect::<Option<Vec<_>>>()
+            .map(EncryptedWeightedVote)
+    }
+
+    /// sum of the ciphertexts of `bits`, each one multiplied by its power of two
+    fn sum_bits(bits: &[Ciphertext]) -> Ciphertext {
+        bits.iter()
+            .enumerate()
+            .fold(Ciphertext::zero(), |acc, (j, bit)| {
+                &acc + &(bit * (1u64 << j))
+            })
+    }
+
+    /// The encryption of the weight of every option, and the encryption of the total
+    /// of the weights and of the unused part of the total.
+    fn aggregate(&self, kind: &WeightedBallotKind) -> Option<(Vec<Ciphertext>, Ciphertext)> {
+        let options = kind.options_of_len(self.len())?;
+        let (option_bits, slack_bits) = self.0.split_at(options * kind.option_bits());
+        let weights: Vec<_> = option_bits
+            .chunks(kind.option_bits())
+            .map(Self::sum_bits)
+            .collect();
+        let total = weights
+            .iter()
+            .fold(Self::sum_bits(slack_bits), |acc, weight| &acc + weight);
+        Some((weights, total))
+    }
+}
+
+/// A proof of correct encryption of a weighted vote, made of a proof for every
+/// encrypted bit that it is either 0 or 1, and a proof that the weights and the
+/// unused part add up to the total of the ballot kind.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct ProofOfCorrectWeightedVote {
+    bit_proofs: Vec<BinaryZkp>,
+    sum_proof: DleqZkp,
+}
+
+/// The statement of the sum proof: the total ciphertext `(e1, e2)` encrypts `total`
+/// if `e1 = g^r` and `e2 / g^total = pk^r`.
+fn sum_statement(
+    total_ciphertext: &Ciphertext,
+    total: u8,
+) -> (GroupElement, GroupElement, GroupElement) {
+    let generator = GroupElement::generator();
+    let point_2 = &total_ciphertext.e2 - &(&generator * total as u64);
+    (generator, total_ciphertext.e1.clone(), point_2)
+}
+
+impl ProofOfCorrectWeightedVote {
+    fn generate<R: RngCore + CryptoRng>(
+        rng: &mut R,
+        crs: &Crs,
+        pk: &PublicKey,
+        vote: &WeightedVote,
+    ) -> (EncryptedWeightedVote, Self) {
+        let kind = vote.kind();
+        let bits = vote
+            .weights()
+            .iter()
+            .flat_map(|weight| (0..kind.option_bits()).map(move |j| (*weight >> j) & 1 == 1))
+            .chain((0..kind.slack_bits()).map(|j| (vote.slack() >> j) & 1 == 1));
+
+        let mut ciphertexts = Vec::with_capacity(kind.encrypted_vote_len(vote.weights().len()));
+        let mut bit_proofs = Vec::with_capacity(ciphertexts.capacity());
+        let mut randomness = Vec::with_capacity(ciphertexts.capacity());
+        for bit in bits {
+            let (ciphertext, r) = pk.encrypt_return_r(&Scalar::from(bit), rng);
+            bit_proofs.push(BinaryZkp::generate(crs, pk, &ciphertext, bit, &r, rng));
+            ciphertexts.push(ciphertext);
+            randomness.push(r);
+        }
+
+        let sum_randomness = |bits: &[Scalar]| {
+            bits.iter().enumerate().fold(Scalar::zero(), |acc, (j, r)| {
+                acc + r * Scalar::from_u64(1u64 << j)
+            })
+        };
+        let (option_randomness, slack_randomness) =
+            randomness.split_at(vote.weights().len() * kind.option_bits());
+        let total_randomness = option_randomness
+            .chunks(kind.option_bits())
+            .fold(sum_randomness(slack_randomness), |acc, bits| {
+                acc + sum_randomness(bits)
+            });
+
+        let encrypted_vote = EncryptedWeightedVote(ciphertexts);
+        let (_, total_ciphertext) = encrypted_vote
+            .aggregate(kind)
+            .expect("the encrypted vote is consistent with its kind");
+        let (base_1, point_1, point_2) = sum_statement(&total_ciphertext, kind.total());
+        let sum_proof =
+            DleqZkp::generate(&base_1, &pk.pk, &point_1, &point_2, &total_randomness, rng);
+
+        (
+            encrypted_vote,
+            ProofOfCorrectWeightedVote {
+                bit_proofs,
+                sum_proof,
+            },
+        )
+    }
+
+    /// Verify that `vote` is a well formed encrypted vote of the given `kind`, for the
+    /// election with the common reference string `crs`.
+    pub fn verify(
+        &self,
+        crs: &Crs,
+        pk: &ElectionPublicKey,
+        kind: &WeightedBallotKind,
+        vote: &EncryptedWeightedVote,
+    ) -> bool {
+        if self.bit_proofs.len() != vote.len() {
+            return false;
+        }
+        let (_, total_ciphertext) = match vote.aggregate(kind) {
+            Some(aggregated) => aggregated,
+            None => return false,
+        };
+        let all_bits = self
+            .bit_proofs
+            .iter()
+            .zip(vote.ciphertexts())
+            .all(|(proof, ciphertext)| proof.verify(crs, &pk.0, ciphertext));
+        let (base_1, point_1, point_2) = sum_statement(&total_ciphertext, kind.total());
+        all_bits && self.sum_proof.verify(&base_1, &pk.0.pk, &point_1, &point_2)
+    }
+
+    pub fn to_bytes(&self) -> Vec<u8> {
+        let mut bytes =
+            Vec::with_capacity(self.bit_proofs.len() * BinaryZkp::BYTES_LEN + DleqZkp::BYTES_LEN);
+        for proof in &self.bit_proofs {
+            bytes.extend_from_slice(&proof.to_bytes());
+        }
+        bytes.extend_from_slice(&self.sum_proof.to_bytes());
+        bytes
+    }
+
+    pub fn from_bytes(bytes: &[u8]) -> Option<Self> {
+        let bits_len = bytes.len().checked_sub(DleqZkp::BYTES_LEN)?;
+        if bits_len % BinaryZkp::BYTES_LEN != 0 {
+            return None;
+        }
+        let (bit_proofs, sum_proof) = bytes.split_at(bits_len);
+        Some(ProofOfCorrectWeightedVote {
+            bit_proofs: bit_proofs
+                .chunks(BinaryZkp::BYTES_LEN)
+                .map(BinaryZkp::from_bytes)
+                .collect::<Option<_>>()?,
+            sum_proof: DleqZkp::from_bytes(sum_proof)?,
+        })
+    }
+}
+
+impl ElectionPublicKey {
+    /// Take a weighted vote and encrypt it + provide a proof of correct voting
+    pub fn encrypt_and_prove_weighted_vote<R: RngCore + CryptoRng>(
+        &self,
+        rng: &mut R,
+        crs: &Crs,
+        vote: &WeightedVote,
+    ) -> (EncryptedWeightedVote, ProofOfCorrectWeightedVote) {
+        ProofOfCorrectWeightedVote::generate(rng, crs, &self.0, vote)
+    }
+}
+
+/// Submitted weighted ballot, which contains an always verified vote, already
+/// aggregated to the encrypted weight of every option.
+#[derive(Clone, Eq, PartialEq, Debug)]
+pub struct WeightedBallot {
+    weights: Vec<Ciphertext>,
+    max_weight: u8,
+    // Used to verify that the ballot is applied to the correct
+    // encrypted tally
+    fingerprint: ElectionFingerprint,
+}
+
+impl WeightedBallot {
+    pub fn try_from_vote_and_proof(
+        vote: EncryptedWeightedVote,
+        proof: &ProofOfCorrectWeightedVote,
+        kind: &WeightedBallotKind,
+        crs: &Crs,
+        pk: &ElectionPublicKey,
+    ) -> Result<Self, BallotVerificationError> {
+        if !proof.verify(crs, pk, kind, &vote) {
+            return Err(BallotVerificationError);
+        }
+        let (weights, _) = vote.aggregate(kind).ok_or(BallotVerificationError)?;
+
+        Ok(Self {
+            weights,
+            max_weight: kind.max_weight(),
+            fingerprint: (pk, crs).into(),
+        })
+    }
+
+    /// The encrypted weight given to every option
+    pub fn weights(&self) -> &[Ciphertext] {
+        &self.weights
+    }
+
+    /// The largest weight the ballot can give to a single option
+    pub fn max_weight(&self) -> u8 {
+        self.max_weight
+    }
+
+    pub(super) fn fingerprint(&self) -> &ElectionFingerprint {
+        &self.fingerprint
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::committee::{MemberCommunicationKey, MemberState};
+    use crate::{EncryptedTally, TallyOptimizationTable};
+    use rand_chacha::ChaCha20Rng;
+    use rand_core::SeedableRng;
+
+    fn approval(max_selections: u8) -> WeightedBallotKind {
+        WeightedBallotKind::Approval {
+            max_selections: NonZeroU8::new(max_selections).unwrap(),
+        }
+    }
+
+    fn budget(budget: u8) -> WeightedBallotKind {
+        WeightedBallotKind::Budget {
+            budget: NonZeroU8::new(budget).unwrap(),
+        }
+    }
+
+    #[test]
+    fn vote_initialization() {
+        assert!(WeightedVote::new(approval(2), vec![1, 0, 1]).is_ok());
+        assert!(WeightedVote::new(approval(2), vec![0, 0, 0]).is_ok());
+        assert_eq!(
+            WeightedVote::new(approval(2), vec![]),
+            Err(WeightedVoteInitializationError::NoOptions)
+        );
+        assert_eq!(
+            WeightedVote::new(approval(2), vec![1, 1, 1]),
+            Err(WeightedVoteInitializationError::TotalTooLarge { total: 3, max: 2 })
+        );
+        assert_eq!(
+            WeightedVote::new(approval(2), vec![0, 2, 0]),
+            Err(WeightedVoteInitializationError::WeightTooLarge {
+                option: 1,
+                weight: 2,
+                max: 1
+            })
+        );
+        assert!(WeightedVote::new(budget(10), vec![3, 7]).is_ok());
+        assert_eq!(
+            WeightedVote::new(budget(10), vec![255, 255]),
+            Err(WeightedVoteInitializationError::WeightTooLarge {
+                option: 0,
+                weight: 255,
+                max: 10
+            })
+        );
+    }
+
+    #[test]
+    fn prove_and_verify() {
+        let mut rng = ChaCha20Rng::from_seed([0u8; 32]);
+        let crs = Crs::from_hash(b"weighted ballots");
+        let mc = [MemberCommunicationKey::new(&mut rng).to_public()];
+        let member = MemberState::new(&mut rng, 1, &crs, &mc, 0);
+        let pk = ElectionPublicKey::from_participants(&[member.public_key()]);
+
+        for (kind, weights) in [
+            (approval(1), vec![0, 1, 0]),
+            (approval(3), vec![1, 1, 0, 1]),
+            (budget(10), vec![3, 0, 6]),
+            (budget(7), vec![7, 0]),
+        ] {
+            let vote = WeightedVote::new(kind, weights).unwrap();
+            let (encrypted_vote, proof) = pk.encrypt_and_prove_weighted_vote(&mut rng, &crs, &vote);
+            assert_eq!(
+                encrypted_vote.len(),
+                kind.encrypted_vote_len(vote.weights().len())
+            );
+            assert!(proof.verify(&crs, &pk, &kind, &encrypted_vote));
+
+            let encrypted_vote =
+                EncryptedWeightedVote::from_bytes(&encrypted_vote.to_bytes()).unwrap();
+            let proof = ProofOfCorrectWeightedVote::from_bytes(&proof.to_bytes()).unwrap();
+            assert!(proof.verify(&crs, &pk, &kind, &encrypted_vote));
+
+            // the proof does not hold for a ballot of another kind
+            assert!(!proof.verify(&crs, &pk, &budget(kind.total() + 1), &encrypted_vote));
+            // nor in another election
+            let other_crs = Crs::from_hash(b"another election");
+            assert!(!proof.verify(&other_crs, &pk, &kind, &encrypted_vote));
+        }
+    }
+
+    #[test]
+    fn overspending_is_rejected() {
+        let mut rng = ChaCha20Rng::from_seed([1u8; 32]);
+        let crs = Crs::from_hash(b"weighted ballots");
+        let mc = [MemberCommunicationKey::new(&mut rng).to_public()];
+        let member = MemberState::new(&mut rng, 1, &crs, &mc, 0);
+        let pk = ElectionPublicKey::from_participants(&[member.public_key()]);
+
+        // a vote spending 2 selections can not pass as a vote with only 1 selection
+        let kind = approval(2);
+        let vote = WeightedVote::new(kind, vec![1, 1, 0]).unwrap();
+        let (encrypted_vote, proof) = pk.encrypt_and_prove_weighted_vote(&mut rng, &crs, &vote);
+        assert!(!proof.verify(&crs, &pk, &approval(1), &encrypted_vote));
+
+        // replacing a bit with the encryption of a larger value breaks its proof
+        let mut ciphertexts = encrypted_vote.ciphertexts().to_vec();
+        ciphertexts[1] = pk.0.encrypt(&Scalar::from_u64(2), &mut rng);
+        assert!(!proof.verify(&crs, &pk, &kind, &EncryptedWeightedVote(ciphertexts)));
+    }
+
+    #[test]
+    fn tally() {
+        let mut rng = ChaCha20Rng::from_seed([2u8; 32]);
+        let crs = Crs::from_hash(b"weighted ballots");
+        let mc = [MemberCommunicationKey::new(&mut rng).to_public()];
+        let member = MemberState::new(&mut rng, 1, &crs, &mc, 0);
+        let participants = [member.public_key()];
+        let pk = ElectionPublicKey::from_participants(&participants);
+
+        let kind = budget(5);
+        let mut encrypted_tally = EncryptedTally::new(3, pk.clone(), crs.clone());
+        for (weights, stake) in [(vec![5, 0, 0], 2), (vec![1, 2, 2], 3), (vec![0, 0, 1], 1)] {
+            let vote = WeightedVote::new(kind, weights).unwrap();
+            let (encrypted_vote, proof) = pk.encrypt_and_prove_weighted_vote(&mut rng, &crs, &vote);
+            let ballot =
+                WeightedBallot::try_from_vote_and_proof(encrypted_vote, &proof, &kind, &crs, &pk)
+                    .unwrap();
+            encrypted_tally.add_weighted(&ballot, stake).unwrap();
+        }
+
+        let shares = [encrypted_tally.partial_decrypt(&mut rng, member.secret_key())];
+        let table = TallyOptimizationTable::generate(30.try_into().unwrap());
+        let tally = encrypted_tally
+            .validate_partial_decryptions(&participants, &shares)
+            .unwrap()
+            .decrypt_tally(&table)
+            .unwrap();
+        assert_eq!(tally.votes, vec![13, 6, 7]);
+        assert!(tally.verify(&encrypted_tally, &participants, &shares));
+    }
+
+    #[test]
+    fn tally_max_stake_overflow() {
+        let mut rng = ChaCha20Rng::from_seed([2u8; 32]);
+        let crs = Crs::from_hash(b"weighted ballots");
+        let mc = [MemberCommunicationKey::new(&mut rng).to_public()];
+        let member = MemberState::new(&mut rng, 1, &crs, &mc, 0);
+        let pk = ElectionPublicKey::from_participants(&[member.public_key()]);
+
+        let kind = budget(5);
+        let vote = WeightedVote::new(kind, vec![5, 0, 0]).unwrap();
+        let (encrypted_vote, proof) = pk.encrypt_and_prove_weighted_vote(&mut rng, &crs, &vote);
+        let ballot =
+            WeightedBallot::try_from_vote_and_proof(encrypted_vote, &proof, &kind, &crs, &pk)
+                .unwrap();
+
+        let mut encrypted_tally = EncryptedTally::new(3, pk, crs);
+        // the stake multiplied by the weight of the ballot overflows
+        assert!(encrypted_tally.add_weighted(&ballot, u64::MAX / 2).is_err());
+        encrypted_tally.add_weighted(&ballot, 1).unwrap();
+        // the stake alone fits, but not on top of the stake already tallied
+        let before = encrypted_tally.clone();
+        assert!(encrypted_tally.add_weighted(&ballot, u64::MAX / 5).is_err());
+        assert_eq!(encrypted_tally, before);
+    }
+}
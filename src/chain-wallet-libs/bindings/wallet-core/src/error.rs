@@ -74,6 +74,13 @@ pub enum ErrorCode {
 
     /// invalid spending counters provided to the set_state function
     InvalidSpendingCounters = 12,
+
+    /// the state of the accounts of a multi account wallet could not be
+    /// retrieved during the accounts discovery
+    AccountDiscovery = 13,
+
+    /// the total value of the accounts of a multi account wallet overflows
+    ValueOverflow = 14,
 }
 
 #[derive(Debug)]
@@ -119,6 +126,12 @@ pub enum ErrorKind {
 
     /// invalid spending counters provided to the set_state function
     InvalidSpendingCounters,
+
+    /// the accounts discovery of a multi account wallet failed
+    AccountDiscovery,
+
+    /// the total value of the accounts of a multi account wallet overflows
+    ValueOverflow,
 }
 
 impl ErrorKind {
@@ -140,6 +153,8 @@ impl ErrorKind {
             Self::InvalidFragment => ErrorCode::InvalidFragment,
             Self::InvalidTransactionValidityDate => ErrorCode::InvalidTransactionValidityDate,
             Self::InvalidSpendingCounters => ErrorCode::InvalidSpendingCounters,
+            Self::AccountDiscovery => ErrorCode::AccountDiscovery,
+            Self::ValueOverflow => ErrorCode::ValueOverflow,
         }
     }
 }
@@ -269,6 +284,20 @@ impl Error {
         }
     }
 
+    pub fn account_discovery() -> Self {
+        Self {
+            kind: ErrorKind::AccountDiscovery,
+            details: None,
+        }
+    }
+
+    pub fn value_overflow() -> Self {
+        Self {
+            kind: ErrorKind::ValueOverflow,
+            details: None,
+        }
+    }
+
     /// set some details to the `Result` object if the `Result` is of
     /// error kind
     ///
@@ -419,6 +448,8 @@ impl Display for ErrorKind {
             Self::InvalidSpendingCounters => {
                 f.write_str("invalid spending counters provided to the set account state function")
             }
+            Self::AccountDiscovery => f.write_str("failed to discover the wallet accounts"),
+            Self::ValueOverflow => f.write_str("the total value of the accounts overflows"),
         }
     }
 }
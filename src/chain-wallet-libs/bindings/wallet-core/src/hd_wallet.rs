@@ -0,0 +1,210 @@
+use crate::{
+    wallet::{set_state, spending_counter, spending_counters, vote},
+    Error, Proposal,
+};
+use chain_impl_mockchain::{
+    account::SpendingCounterIncreasing, block::BlockDate, fragment::FragmentId, value::Value,
+    vote::Choice,
+};
+use ed25519_bip32::{XPrv, XPRV_SIZE};
+use std::{convert::TryInto, error};
+use wallet::{hd, AccountId, Settings};
+
+/// source of the state of the accounts, see [`HdWallet::discover`]
+pub trait AccountStateProvider {
+    type Error: error::Error + Send + Sync + 'static;
+
+    /// retrieve the value and spending counters of the given account
+    ///
+    /// returns `None` if the account is not known to the blockchain
+    fn account_state(
+        &mut self,
+        account_id: AccountId,
+    ) -> Result<Option<(Value, [u32; SpendingCounterIncreasing::LANES])>, Self::Error>;
+}
+
+struct Provider<'a, P>(&'a mut P);
+
+impl<'a, P: AccountStateProvider> hd::AccountStateProvider for Provider<'a, P> {
+    type Error = P::Error;
+
+    fn account_state(
+        &mut self,
+        account_id: &AccountId,
+    ) -> Result<Option<hd::AccountState>, Self::Error> {
+        Ok(self
+            .0
+            .account_state(*account_id)?
+            .map(|(value, counters)| hd::AccountState {
+                value,
+                counters: spending_counters(counters),
+            }))
+    }
+}
+
+/// the multi account wallet
+///
+/// * use the `recover` function to recover the wallet from its root key;
+/// * use `discover` to retrieve the used accounts and their state from the
+///   blockchain;
+/// * then `total_value` gives the value of all the accounts and each account
+///   can be used to vote with its own index.
+///
+pub struct HdWallet {
+    accounts: hd::HdWallet,
+}
+
+impl HdWallet {
+    /// Retrieve a wallet from its root key
+    ///
+    /// Parameters
+    ///
+    /// * `root_key`: the 96 bytes root extended private key, the extended
+    ///   secret key followed by the chain code
+    ///
+    /// # Errors
+    ///
+    /// The function may fail if the `root_key` is not a valid extended
+    /// private key
+    ///
+    pub fn recover(root_key: &[u8]) -> Result<Self, Error> {
+        let root_key: [u8; XPRV_SIZE] = root_key
+            .try_into()
+            .map_err(|_| Error::invalid_input("root_key"))?;
+        let root_key =
+            XPrv::from_bytes_verified(root_key).map_err(|_| Error::wallet_recovering())?;
+
+        Ok(Self {
+            accounts: hd::HdWallet::from_root_key(root_key),
+        })
+    }
+
+    /// Discover the used accounts of the wallet
+    ///
+    /// Accounts are scanned until `gap_limit` consecutive accounts are not
+    /// known to the `provider`. The state of the used accounts is set from
+    /// the `provider`, see [`Self::set_state`].
+    ///
+    /// returns the number of accounts up to the last used one
+    pub fn discover<P>(&mut self, provider: &mut P, gap_limit: u32) -> Result<u32, Error>
+    where
+        P: AccountStateProvider,
+    {
+        self.accounts
+            .discover(&mut Provider(provider), gap_limit)
+            .map_err(|e| match e {
+                hd::DiscoveryError::Provider { error, .. } => {
+                    Error::account_discovery().with(error)
+                }
+                hd::DiscoveryError::Wallet(e) => Error::account_discovery().with(e),
+            })
+    }
+
+    /// Start using the next account of the wallet
+    ///
+    /// returns the index of the new account
+    pub fn add_account(&mut self) -> Result<u32, Error> {
+        self.accounts
+            .add_account()
+            .map_err(|e| Error::invalid_input("index").with(e))
+    }
+
+    /// Secret key of the account at `index`, to sign transactions with
+    /// [`crate::TxBuilder`]
+    pub fn account_key(&self, index: u32) -> Result<Box<[u8]>, Error> {
+        self.accounts
+            .account_key(index)
+            .map(|key| key.leak_secret().as_ref().into())
+            .map_err(|e| Error::invalid_input("index").with(e))
+    }
+
+    /// number of accounts currently in use
+    pub fn accounts_count(&self) -> u32 {
+        self.accounts.accounts().len() as u32
+    }
+
+    pub fn id(&self, index: u32) -> Result<AccountId, Error> {
+        Ok(self.wallet(index)?.account_id())
+    }
+
+    /// Returns address of the account at `index` with the given chain discrimination.
+    pub fn account(
+        &self,
+        index: u32,
+        discrimination: chain_addr::Discrimination,
+    ) -> Result<chain_addr::Address, Error> {
+        Ok(self.id(index)?.address(discrimination))
+    }
+
+    /// get the current spending counter of the account at `index`
+    ///
+    pub fn spending_counter(
+        &self,
+        index: u32,
+    ) -> Result<[u32; SpendingCounterIncreasing::LANES], Error> {
+        Ok(spending_counter(self.wallet(index)?))
+    }
+
+    /// get the total value of all the accounts of the wallet
+    ///
+    /// # Errors
+    ///
+    /// The function fails if the sum of the values of the accounts overflows
+    ///
+    pub fn total_value(&self) -> Result<Value, Error> {
+        self.accounts
+            .total_value()
+            .map_err(|e| Error::value_overflow().with(e))
+    }
+
+    /// Update the state of the account at `index`
+    ///
+    /// See [`crate::Wallet::set_state`].
+    pub fn set_state(
+        &mut self,
+        index: u32,
+        value: Value,
+        counters: [u32; SpendingCounterIncreasing::LANES],
+    ) -> Result<(), Error> {
+        set_state(self.wallet_mut(index)?, value, counters)
+    }
+
+    /// use this function to confirm a transaction has been properly received
+    pub fn confirm_transaction(&mut self, id: FragmentId) {
+        self.accounts.confirm(&id);
+    }
+
+    /// Cast a vote with the account at `index`
+    ///
+    /// See [`crate::Wallet::vote`].
+    pub fn vote(
+        &mut self,
+        index: u32,
+        settings: Settings,
+        proposal: &Proposal,
+        choice: Choice,
+        valid_until: &BlockDate,
+        lane: u8,
+    ) -> Result<Box<[u8]>, Error> {
+        vote(
+            self.wallet_mut(index)?,
+            settings,
+            proposal,
+            choice,
+            valid_until,
+            lane,
+        )
+    }
+
+    fn wallet(&self, index: u32) -> Result<&wallet::Wallet, Error> {
+        self.accounts
+            .account(index)
+            .ok_or_else(|| Error::invalid_input("index"))
+    }
+
+    fn wallet_mut(&mut self, index: u32) -> Result<&mut wallet::Wallet, Error> {
+        self.accounts
+            .account_mut(index)
+            .ok_or_else(|| Error::invalid_input("index"))
+    }
+}
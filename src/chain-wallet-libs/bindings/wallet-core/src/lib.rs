@@ -1,16 +1,18 @@
 pub mod c;
 mod error;
+mod hd_wallet;
 mod tx_builder;
 mod vote;
 mod wallet;
 
 pub use self::{
     error::{Error, ErrorCode, ErrorKind, Result},
+    hd_wallet::{AccountStateProvider, HdWallet},
     tx_builder::TxBuilder,
     vote::Proposal,
     wallet::Wallet,
 };
-pub use ::wallet::Settings;
+pub use ::wallet::{AccountId, Settings};
 pub use chain_impl_mockchain::{
     fragment::{Fragment, FragmentId},
     value::Value,
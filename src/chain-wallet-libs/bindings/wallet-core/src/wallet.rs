@@ -2,7 +2,7 @@ use crate::{Error, Proposal};
 use chain_core::property::Serialize as _;
 use chain_crypto::SecretKey;
 use chain_impl_mockchain::{
-    account::SpendingCounterIncreasing,
+    account::{SpendingCounter, SpendingCounterIncreasing},
     block::BlockDate,
     certificate::Certificate,
     fragment::{Fragment, FragmentId},
@@ -63,17 +63,7 @@ impl Wallet {
     /// get the current spending counter
     ///
     pub fn spending_counter(&self) -> [u32; SpendingCounterIncreasing::LANES] {
-        let spending_counters = self.account.spending_counter();
-        [
-            spending_counters[0].into(),
-            spending_counters[1].into(),
-            spending_counters[2].into(),
-            spending_counters[3].into(),
-            spending_counters[4].into(),
-            spending_counters[5].into(),
-            spending_counters[6].into(),
-            spending_counters[7].into(),
-        ]
+        spending_counter(&self.account)
     }
 
     /// get the total value in the wallet
@@ -103,21 +93,7 @@ impl Wallet {
         value: Value,
         counters: [u32; SpendingCounterIncreasing::LANES],
     ) -> Result<(), Error> {
-        self.account
-            .set_state(
-                value,
-                [
-                    counters[0].into(),
-                    counters[1].into(),
-                    counters[2].into(),
-                    counters[3].into(),
-                    counters[4].into(),
-                    counters[5].into(),
-                    counters[6].into(),
-                    counters[7].into(),
-                ],
-            )
-            .map_err(|_| Error::invalid_spending_counters())
+        set_state(&mut self.account, value, counters)
     }
 
     fn sign_transaction_impl<P: Payload>(
@@ -196,35 +172,93 @@ impl Wallet {
         valid_until: &BlockDate,
         lane: u8,
     ) -> Result<Box<[u8]>, Error> {
-        let payload = if let Some(payload) = proposal.vote(choice) {
-            payload
-        } else {
-            return Err(Error::wallet_vote_range());
-        };
+        vote(
+            &mut self.account,
+            settings,
+            proposal,
+            choice,
+            valid_until,
+            lane,
+        )
+    }
+}
 
-        let mut builder = wallet::TransactionBuilder::new(settings, payload, *valid_until);
+pub(crate) fn spending_counter(
+    account: &wallet::Wallet,
+) -> [u32; SpendingCounterIncreasing::LANES] {
+    let spending_counters = account.spending_counter();
+    [
+        spending_counters[0].into(),
+        spending_counters[1].into(),
+        spending_counters[2].into(),
+        spending_counters[3].into(),
+        spending_counters[4].into(),
+        spending_counters[5].into(),
+        spending_counters[6].into(),
+        spending_counters[7].into(),
+    ]
+}
 
-        let value = builder.estimate_fee_with(1, 0);
+pub(crate) fn set_state(
+    account: &mut wallet::Wallet,
+    value: Value,
+    counters: [u32; SpendingCounterIncreasing::LANES],
+) -> Result<(), Error> {
+    account
+        .set_state(value, spending_counters(counters))
+        .map_err(|_| Error::invalid_spending_counters())
+}
 
-        let account_tx_builder = self
-            .account
-            .new_transaction(value, lane)
-            .map_err(|_| Error::not_enough_funds())?;
+pub(crate) fn spending_counters(
+    counters: [u32; SpendingCounterIncreasing::LANES],
+) -> [SpendingCounter; SpendingCounterIncreasing::LANES] {
+    [
+        counters[0].into(),
+        counters[1].into(),
+        counters[2].into(),
+        counters[3].into(),
+        counters[4].into(),
+        counters[5].into(),
+        counters[6].into(),
+        counters[7].into(),
+    ]
+}
 
-        let input = account_tx_builder.input();
-        let witness_builder = account_tx_builder.witness_builder();
+pub(crate) fn vote(
+    account: &mut wallet::Wallet,
+    settings: Settings,
+    proposal: &Proposal,
+    choice: Choice,
+    valid_until: &BlockDate,
+    lane: u8,
+) -> Result<Box<[u8]>, Error> {
+    let payload = if let Some(payload) = proposal.vote(choice) {
+        payload
+    } else {
+        return Err(Error::wallet_vote_range());
+    };
 
-        builder.add_input(input, witness_builder);
+    let mut builder = wallet::TransactionBuilder::new(settings, payload, *valid_until);
 
-        let tx = builder
-            .finalize_tx(())
-            .map_err(|e| Error::wallet_transaction().with(e))?;
+    let value = builder.estimate_fee_with(1, 0);
 
-        let fragment = Fragment::VoteCast(tx);
-        let id = fragment.hash();
+    let account_tx_builder = account
+        .new_transaction(value, lane)
+        .map_err(|_| Error::not_enough_funds())?;
 
-        account_tx_builder.add_fragment_id(id);
+    let input = account_tx_builder.input();
+    let witness_builder = account_tx_builder.witness_builder();
 
-        Ok(fragment.serialize_as_vec().unwrap().into_boxed_slice())
-    }
+    builder.add_input(input, witness_builder);
+
+    let tx = builder
+        .finalize_tx(())
+        .map_err(|e| Error::wallet_transaction().with(e))?;
+
+    let fragment = Fragment::VoteCast(tx);
+    let id = fragment.hash();
+
+    account_tx_builder.add_fragment_id(id);
+
+    Ok(fragment.serialize_as_vec().unwrap().into_boxed_slice())
 }
@@ -0,0 +1,106 @@
+use chain_impl_mockchain::account::SpendingCounterIncreasing;
+use ed25519_bip32::XPrv;
+use std::collections::HashMap;
+use wallet_core::{AccountId, AccountStateProvider, ErrorKind, HdWallet, Value};
+
+type State = (Value, [u32; SpendingCounterIncreasing::LANES]);
+
+#[derive(Default)]
+struct Ledger {
+    accounts: HashMap<AccountId, State>,
+    unavailable: Option<AccountId>,
+}
+
+#[derive(Debug, thiserror::Error)]
+#[error("node unavailable")]
+struct Unavailable;
+
+impl AccountStateProvider for Ledger {
+    type Error = Unavailable;
+
+    fn account_state(&mut self, account_id: AccountId) -> Result<Option<State>, Self::Error> {
+        if self.unavailable == Some(account_id) {
+            return Err(Unavailable);
+        }
+        Ok(self.accounts.get(&account_id).copied())
+    }
+}
+
+fn root_key() -> Vec<u8> {
+    let key = XPrv::from_nonextended_force(&[1; 32], &[2; 32]);
+    let bytes: &[u8] = key.as_ref();
+    bytes.to_vec()
+}
+
+/// id of the account at `index` of the wallet of `root_key`
+fn account_id(index: u32) -> AccountId {
+    let mut wallet = HdWallet::recover(&root_key()).unwrap();
+    while wallet.accounts_count() <= index {
+        wallet.add_account().unwrap();
+    }
+    wallet.id(index).unwrap()
+}
+
+fn counters(first_lane: u32) -> [u32; SpendingCounterIncreasing::LANES] {
+    let mut counters = [0; SpendingCounterIncreasing::LANES];
+    counters[0] = first_lane;
+    counters
+}
+
+#[test]
+fn recover_rejects_invalid_root_key() {
+    let error = HdWallet::recover(&[0; 32]).err().unwrap();
+    assert!(matches!(
+        error.kind(),
+        ErrorKind::InvalidInput {
+            argument_name: "root_key"
+        }
+    ));
+}
+
+#[test]
+fn discovered_accounts_state() {
+    let mut ledger = Ledger::default();
+    ledger.accounts.insert(account_id(0), (Value(100), counters(2)));
+    ledger.accounts.insert(account_id(2), (Value(50), counters(1)));
+
+    let mut wallet = HdWallet::recover(&root_key()).unwrap();
+    assert_eq!(wallet.discover(&mut ledger, 5).unwrap(), 3);
+
+    assert_eq!(wallet.accounts_count(), 3);
+    assert_eq!(wallet.id(2).unwrap(), account_id(2));
+    assert_eq!(wallet.spending_counter(0).unwrap()[0], 2);
+    assert_eq!(wallet.spending_counter(1).unwrap()[0], 0);
+    assert_eq!(wallet.total_value().unwrap(), Value(150));
+    assert!(wallet.id(3).is_err());
+}
+
+#[test]
+fn provider_error_is_reported() {
+    let mut ledger = Ledger::default();
+    ledger.accounts.insert(account_id(0), (Value(100), counters(0)));
+    ledger.unavailable = Some(account_id(1));
+
+    let mut wallet = HdWallet::recover(&root_key()).unwrap();
+    let error = wallet.discover(&mut ledger, 5).err().unwrap();
+
+    assert!(matches!(error.kind(), ErrorKind::AccountDiscovery));
+    assert!(error.details().unwrap().is::<Unavailable>());
+    // the wallet is left untouched
+    assert_eq!(wallet.accounts_count(), 0);
+}
+
+#[test]
+fn total_value_overflow() {
+    let mut ledger = Ledger::default();
+    ledger
+        .accounts
+        .insert(account_id(0), (Value(u64::MAX), counters(0)));
+    ledger.accounts.insert(account_id(1), (Value(1), counters(0)));
+
+    let mut wallet = HdWallet::recover(&root_key()).unwrap();
+    wallet.discover(&mut ledger, 1).unwrap();
+
+    let error = wallet.total_value().err().unwrap();
+    assert!(matches!(error.kind(), ErrorKind::ValueOverflow));
+}
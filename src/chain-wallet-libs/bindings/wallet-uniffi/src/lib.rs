@@ -1,6 +1,7 @@
 use chain_crypto::bech32::Bech32;
 use chain_crypto::Ed25519Extended;
 use chain_crypto::SecretKey;
+use chain_impl_mockchain::account::SpendingCounterIncreasing;
 use chain_impl_mockchain::certificate::VotePlanId;
 use chain_impl_mockchain::config;
 use chain_impl_mockchain::fee;
@@ -16,7 +17,9 @@ use std::sync::Arc;
 use std::sync::Mutex;
 use std::time::Duration;
 use std::time::SystemTime;
+use wallet_core::AccountStateProvider as CoreAccountStateProvider;
 use wallet_core::Error as CoreError;
+use wallet_core::HdWallet as InnerHdWallet;
 use wallet_core::Options;
 use wallet_core::Settings as InnerSettings;
 use wallet_core::Wallet as InnerWallet;
@@ -43,10 +46,14 @@ pub enum WalletError {
     InvalidFragment,
     #[error("invalid spending counters")]
     InvalidSpendingCounters,
+    #[error("account state provider error {0}")]
+    AccountStateProvider(#[from] AccountStateProviderError),
 }
 
 pub struct Wallet(Mutex<InnerWallet>);
 
+pub struct HdWallet(Mutex<InnerHdWallet>);
+
 pub struct Settings(Mutex<InnerSettings>);
 
 pub struct Fragment(Mutex<fragment::Fragment>);
@@ -108,6 +115,58 @@ pub struct BlockDate {
     slot: u32,
 }
 
+pub struct AccountState {
+    pub value: u64,
+    pub counters: Vec<u32>,
+}
+
+/// thrown by the foreign implementations of [`AccountStateProvider`]
+#[derive(Debug, Clone, thiserror::Error)]
+pub enum AccountStateProviderError {
+    #[error("{reason}")]
+    Failed { reason: String },
+}
+
+impl From<uniffi::UnexpectedUniFFICallbackError> for AccountStateProviderError {
+    fn from(error: uniffi::UnexpectedUniFFICallbackError) -> Self {
+        Self::Failed {
+            reason: error.reason,
+        }
+    }
+}
+
+/// implemented by the foreign language to give the state of the accounts
+/// during the discovery of the accounts of a [`HdWallet`]
+pub trait AccountStateProvider: Send + Sync + std::fmt::Debug {
+    fn account_state(
+        &self,
+        account_id: AccountId,
+    ) -> Result<Option<AccountState>, AccountStateProviderError>;
+}
+
+struct Provider(Box<dyn AccountStateProvider>);
+
+impl CoreAccountStateProvider for Provider {
+    type Error = WalletError;
+
+    fn account_state(
+        &mut self,
+        account_id: wallet::AccountId,
+    ) -> Result<Option<(wallet_core::Value, [u32; SpendingCounterIncreasing::LANES])>, Self::Error>
+    {
+        self.0
+            .account_state(account_id.as_ref().to_vec())?
+            .map(|state| {
+                let counters = state
+                    .counters
+                    .try_into()
+                    .map_err(|_| WalletError::InvalidSpendingCounters)?;
+                Ok((wallet_core::Value(state.value), counters))
+            })
+            .transpose()
+    }
+}
+
 pub struct SecretKeyEd25519Extended(SecretKey<Ed25519Extended>);
 
 // kotlin codegen does not support wrapped types (rust newtypes) for now at least,
@@ -211,6 +270,100 @@ impl Wallet {
     }
 }
 
+impl HdWallet {
+    pub fn new(root_key: Vec<u8>) -> Result<Self, WalletError> {
+        let inner = InnerHdWallet::recover(&root_key)?;
+
+        Ok(Self(Mutex::new(inner)))
+    }
+
+    pub fn discover(
+        &self,
+        provider: Box<dyn AccountStateProvider>,
+        gap_limit: u32,
+    ) -> Result<u32, WalletError> {
+        self.0
+            .lock()
+            .unwrap()
+            .discover(&mut Provider(provider), gap_limit)
+            .map_err(|error| {
+                // the error thrown by the provider, rather than the discovery error holding it
+                match error
+                    .details()
+                    .and_then(|details| details.downcast_ref::<WalletError>())
+                {
+                    Some(WalletError::AccountStateProvider(provider_error)) => {
+                        WalletError::AccountStateProvider(provider_error.clone())
+                    }
+                    _ => WalletError::from(error),
+                }
+            })
+    }
+
+    pub fn add_account(&self) -> Result<u32, WalletError> {
+        self.0.lock().unwrap().add_account().map_err(From::from)
+    }
+
+    pub fn accounts_count(&self) -> u32 {
+        self.0.lock().unwrap().accounts_count()
+    }
+
+    pub fn set_state(&self, index: u32, value: u64, counter: Vec<u32>) -> Result<(), WalletError> {
+        let counter = counter
+            .try_into()
+            .map_err(|_| WalletError::InvalidSpendingCounters)?;
+
+        self.0
+            .lock()
+            .unwrap()
+            .set_state(index, wallet_core::Value(value), counter)
+            .map_err(From::from)
+    }
+
+    pub fn vote(
+        &self,
+        index: u32,
+        settings: Arc<Settings>,
+        proposal: Proposal,
+        choice: u8,
+        valid_until: BlockDate,
+        lane: u8,
+    ) -> Result<Vec<u8>, WalletError> {
+        let settings = settings.0.lock().unwrap();
+        let mut wallet = self.0.lock().unwrap();
+
+        wallet
+            .vote(
+                index,
+                settings.clone(),
+                &proposal.try_into()?,
+                wallet_core::Choice::new(choice),
+                &valid_until.into(),
+                lane,
+            )
+            .map(|bytes| bytes.into_vec())
+            .map_err(WalletError::from)
+    }
+
+    pub fn account_id(&self, index: u32) -> Result<AccountId, WalletError> {
+        let id = self.0.lock().unwrap().id(index)?;
+
+        Ok(id.as_ref().to_vec())
+    }
+
+    pub fn spending_counters(&self, index: u32) -> Result<Vec<u32>, WalletError> {
+        let counters = self.0.lock().unwrap().spending_counter(index)?;
+
+        Ok(counters.to_vec())
+    }
+
+    pub fn total_value(&self) -> Result<Value, WalletError> {
+        let value = self.0.lock().unwrap().total_value()?;
+
+        Ok(value.0)
+    }
+}
+
 impl Settings {
     pub fn new(settings_init: SettingsRaw) -> Result<Self, WalletError> {
         let SettingsRaw {
@@ -409,3 +562,77 @@ impl From<chain_impl_mockchain::block::BlockDate> for BlockDate {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// knows the accounts of `accounts`, fails for any other account if
+    /// `unavailable` is set
+    #[derive(Debug)]
+    struct Ledger {
+        accounts: Vec<(AccountId, Value)>,
+        unavailable: bool,
+    }
+
+    impl AccountStateProvider for Ledger {
+        fn account_state(
+            &self,
+            account_id: AccountId,
+        ) -> Result<Option<AccountState>, AccountStateProviderError> {
+            match self.accounts.iter().find(|(id, _)| *id == account_id) {
+                Some((_, value)) => Ok(Some(AccountState {
+                    value: *value,
+                    counters: vec![0; SpendingCounterIncreasing::LANES],
+                })),
+                None if self.unavailable => Err(AccountStateProviderError::Failed {
+                    reason: "node unavailable".to_string(),
+                }),
+                None => Ok(None),
+            }
+        }
+    }
+
+    fn root_key() -> Vec<u8> {
+        let mut root_key = vec![1; 96];
+        root_key[0] = 0;
+        root_key[31] = 0b0100_0000;
+        root_key
+    }
+
+    fn first_account_id() -> AccountId {
+        let wallet = HdWallet::new(root_key()).unwrap();
+        wallet.add_account().unwrap();
+        wallet.account_id(0).unwrap()
+    }
+
+    #[test]
+    fn discover_accounts() {
+        let wallet = HdWallet::new(root_key()).unwrap();
+        let ledger = Ledger {
+            accounts: vec![(first_account_id(), 100)],
+            unavailable: false,
+        };
+
+        assert_eq!(wallet.discover(Box::new(ledger), 5).unwrap(), 1);
+        assert_eq!(wallet.accounts_count(), 1);
+        assert_eq!(wallet.total_value().unwrap(), 100);
+    }
+
+    #[test]
+    fn provider_error_is_propagated() {
+        let wallet = HdWallet::new(root_key()).unwrap();
+        let ledger = Ledger {
+            accounts: vec![(first_account_id(), 100)],
+            unavailable: true,
+        };
+
+        match wallet.discover(Box::new(ledger), 5) {
+            Err(WalletError::AccountStateProvider(AccountStateProviderError::Failed {
+                reason,
+            })) => assert_eq!(reason, "node unavailable"),
+            result => panic!("unexpected discovery result {:?}", result),
+        }
+        assert_eq!(wallet.accounts_count(), 0);
+    }
+}
@@ -0,0 +1,162 @@
+use chain_impl_mockchain::account::SpendingCounterIncreasing;
+use js_sys::{Array, BigInt, Function, Reflect};
+use std::fmt;
+use wasm_bindgen::prelude::*;
+
+/// Wallet made of multiple accounts derived from the same root key.
+///
+/// Use `discover` to find the accounts already in use, then `account_key`
+/// and `spending_counters` to build transactions with `VoteCastTxBuilder`.
+#[wasm_bindgen]
+pub struct HdWallet(wallet_core::HdWallet);
+
+#[derive(Debug)]
+struct ProviderError(String);
+
+impl fmt::Display for ProviderError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "account state provider error: {}", self.0)
+    }
+}
+
+impl std::error::Error for ProviderError {}
+
+struct Provider<'a>(&'a Function);
+
+impl<'a> wallet_core::AccountStateProvider for Provider<'a> {
+    type Error = ProviderError;
+
+    fn account_state(
+        &mut self,
+        account_id: wallet_core::AccountId,
+    ) -> Result<Option<(wallet_core::Value, [u32; SpendingCounterIncreasing::LANES])>, Self::Error>
+    {
+        let state = self
+            .0
+            .call1(&JsValue::NULL, &JsValue::from(hex::encode(account_id)))
+            .map_err(|e| ProviderError(format!("{:?}", e)))?;
+        if state.is_null() || state.is_undefined() {
+            return Ok(None);
+        }
+
+        let field = |name: &str| {
+            Reflect::get(&state, &JsValue::from_str(name))
+                .map_err(|e| ProviderError(format!("{:?}", e)))
+        };
+        let value = integer(&field("value")?)
+            .ok_or_else(|| ProviderError("value is not a non negative integer".to_string()))?;
+        let counters = Array::from(&field("counters")?)
+            .iter()
+            .map(|counter| integer(&counter).and_then(|counter| u32::try_from(counter).ok()))
+            .collect::<Option<Vec<_>>>()
+            .and_then(|counters| counters.try_into().ok())
+            .ok_or_else(|| ProviderError("invalid spending counters".to_string()))?;
+
+        Ok(Some((wallet_core::Value(value), counters)))
+    }
+}
+
+/// the value of a `BigInt`, a decimal string or a number holding a non
+/// negative integer, `None` for anything else (fractions, `NaN`, ...)
+fn integer(value: &JsValue) -> Option<u64> {
+    if value.is_bigint() {
+        let value = BigInt::from(value.clone()).to_string(10).ok()?;
+        String::from(value).parse().ok()
+    } else if let Some(value) = value.as_string() {
+        value.parse().ok()
+    } else {
+        // numbers beyond 2^53 may have been rounded
+        const MAX_SAFE_INTEGER: f64 = 9_007_199_254_740_991.0;
+        let value = value.as_f64()?;
+        if value.fract() == 0.0 && (0.0..=MAX_SAFE_INTEGER).contains(&value) {
+            Some(value as u64)
+        } else {
+            None
+        }
+    }
+}
+
+/// the error with its details, e.g. the error thrown by the account state
+/// provider
+fn error_message(error: wallet_core::Error) -> JsValue {
+    match error.details() {
+        Some(details) => JsValue::from(format!("{}: {}", error, details)),
+        None => JsValue::from(error.to_string()),
+    }
+}
+
+#[wasm_bindgen]
+impl HdWallet {
+    /// Recover the wallet from the hex encoded 96 bytes root extended private key
+    pub fn recover(hex_root_key: String) -> Result<HdWallet, JsValue> {
+        let root_key = hex::decode(hex_root_key).map_err(|e| JsValue::from(e.to_string()))?;
+        wallet_core::HdWallet::recover(&root_key)
+            .map(HdWallet)
+            .map_err(|e| JsValue::from(e.to_string()))
+    }
+
+    /// Discover the accounts in use, stopping after `gap_limit` consecutive unused accounts
+    ///
+    /// `provider` is called with the hex encoded account id and returns `undefined` for an
+    /// account unknown to the blockchain, or `{ value, counters }` with the value of the account
+    /// and its 8 spending counters. The value and the counters are non negative integers, given
+    /// as `BigInt`s, decimal strings or numbers up to `Number.MAX_SAFE_INTEGER`.
+    ///
+    /// Returns the number of accounts up to the last used one
+    pub fn discover(&mut self, provider: &Function, gap_limit: u32) -> Result<u32, JsValue> {
+        self.0
+            .discover(&mut Provider(provider), gap_limit)
+            .map_err(error_message)
+    }
+
+    /// Start using the next account, returns its index
+    pub fn add_account(&mut self) -> Result<u32, JsValue> {
+        self.0
+            .add_account()
+            .map_err(|e| JsValue::from(e.to_string()))
+    }
+
+    pub fn accounts_count(&self) -> u32 {
+        self.0.accounts_count()
+    }
+
+    /// Hex encoded account id of the account at `index`
+    pub fn account_id(&self, index: u32) -> Result<String, JsValue> {
+        self.0
+            .id(index)
+            .map(hex::encode)
+            .map_err(|e| JsValue::from(e.to_string()))
+    }
+
+    /// Hex encoded private key of the account at `index`, to sign transactions
+    pub fn account_key(&self, index: u32) -> Result<String, JsValue> {
+        self.0
+            .account_key(index)
+            .map(hex::encode)
+            .map_err(|e| JsValue::from(e.to_string()))
+    }
+
+    pub fn set_state(&mut self, index: u32, value: u64, counters: Vec<u32>) -> Result<(), JsValue> {
+        let counters = counters
+            .try_into()
+            .map_err(|_| JsValue::from("expected 8 spending counters"))?;
+        self.0
+            .set_state(index, wallet_core::Value(value), counters)
+            .map_err(|e| JsValue::from(e.to_string()))
+    }
+
+    pub fn spending_counters(&self, index: u32) -> Result<Vec<u32>, JsValue> {
+        self.0
+            .spending_counter(index)
+            .map(|counters| counters.to_vec())
+            .map_err(|e| JsValue::from(e.to_string()))
+    }
+
+    /// Total value of all the accounts
+    pub fn total_value(&self) -> Result<u64, JsValue> {
+        self.0
+            .total_value()
+            .map(|value| value.0)
+            .map_err(|e| JsValue::from(e.to_string()))
+    }
+}
@@ -11,10 +11,12 @@ use chain_impl_mockchain::{
     certificate::VoteCast as VoteCastLib, fragment::Fragment as FragmentLib,
 };
 pub use fragment::{Fragment, FragmentId};
+pub use hd_wallet::HdWallet;
 use wasm_bindgen::prelude::*;
 
 mod certificates;
 mod fragment;
+mod hd_wallet;
 mod utils;
 
 #[wasm_bindgen]
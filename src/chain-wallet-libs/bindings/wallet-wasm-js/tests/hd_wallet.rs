@@ -0,0 +1,65 @@
+use js_sys::Function;
+use wallet_js::*;
+use wasm_bindgen_test::*;
+
+fn root_key() -> String {
+    let mut root_key = vec![1; 96];
+    root_key[0] = 0;
+    root_key[31] = 0b0100_0000;
+    hex::encode(root_key)
+}
+
+/// provider knowing only the first account of the wallet, with the given
+/// javascript `value` and first spending counter
+fn provider(value: &str, counter: &str) -> Function {
+    let mut wallet = HdWallet::recover(root_key()).unwrap();
+    wallet.add_account().unwrap();
+    let account_id = wallet.account_id(0).unwrap();
+
+    Function::new_with_args(
+        "accountId",
+        &format!(
+            "return accountId === '{}' ? {{ value: {}, counters: [{}, 0, 0, 0, 0, 0, 0, 0] }} : undefined;",
+            account_id, value, counter
+        ),
+    )
+}
+
+#[wasm_bindgen_test]
+fn discover_accepts_integers() {
+    for (value, counter) in [("10n", "'2'"), ("'10'", "2n"), ("10", "2")] {
+        let mut wallet = HdWallet::recover(root_key()).unwrap();
+
+        assert_eq!(wallet.discover(&provider(value, counter), 5).unwrap(), 1);
+        assert_eq!(wallet.total_value().unwrap(), 10);
+        assert_eq!(wallet.spending_counters(0).unwrap()[0], 2);
+    }
+}
+
+#[wasm_bindgen_test]
+fn discover_rejects_invalid_integers() {
+    for (value, counter) in [
+        ("-1", "0"),
+        ("1.5", "0"),
+        ("NaN", "0"),
+        ("'1e3'", "0"),
+        ("-1n", "0"),
+        ("18446744073709551616n", "0"),
+        ("10", "-1"),
+        ("10", "4294967296"),
+    ] {
+        let mut wallet = HdWallet::recover(root_key()).unwrap();
+
+        assert!(wallet.discover(&provider(value, counter), 5).is_err());
+        assert_eq!(wallet.accounts_count(), 0);
+    }
+}
+
+#[wasm_bindgen_test]
+fn discover_reports_the_provider_error() {
+    let mut wallet = HdWallet::recover(root_key()).unwrap();
+    let provider = Function::new_no_args("throw new Error('node unavailable');");
+
+    let error = wallet.discover(&provider, 5).unwrap_err();
+    assert!(error.as_string().unwrap().contains("node unavailable"));
+}
@@ -10,6 +10,7 @@ use wasm_bindgen::JsValue;
 use wasm_bindgen_test::*;
 
 mod certificates;
+mod hd_wallet;
 
 wasm_bindgen_test_configure!(run_in_browser);
 
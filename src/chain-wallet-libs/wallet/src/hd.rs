@@ -0,0 +1,230 @@
+//! Wallet made of multiple accounts derived from a single root key
+//!
+//! Account keys are derived following the chimeric BIP44 scheme
+//! (`m/1852'/1815'/account'/2/0`) and used accounts are discovered
+//! with the BIP44 gap limit rule: the scan stops after `gap_limit`
+//! consecutive accounts without any state on chain.
+
+use crate::account::{self, Wallet};
+use chain_crypto::{Ed25519Extended, SecretKey};
+use chain_impl_mockchain::{
+    account::SpendingCounter,
+    accounting::account::SpendingCounterIncreasing,
+    fragment::{Fragment, FragmentId},
+    value::{Value, ValueError},
+};
+use chain_path_derivation::{
+    bip44::{self, Bip44},
+    HardDerivation, SoftDerivation,
+};
+use ed25519_bip32::{DerivationScheme, XPrv, XPRV_SIZE};
+use hdkeygen::{account::AccountId, Key};
+use thiserror::Error;
+
+/// number of consecutive unused accounts after which the discovery stops,
+/// as recommended by BIP44
+pub const DEFAULT_GAP_LIMIT: u32 = 20;
+
+/// state of an account as known by the blockchain nodes
+#[derive(Debug, Clone)]
+pub struct AccountState {
+    pub value: Value,
+    pub counters: [SpendingCounter; SpendingCounterIncreasing::LANES],
+}
+
+/// source of the accounts' state, usually a Jormungandr node
+pub trait AccountStateProvider {
+    type Error;
+
+    /// retrieve the state of the given account
+    ///
+    /// returns `None` if the account was never used, i.e. it is not known
+    /// to the ledger
+    fn account_state(
+        &mut self,
+        account_id: &AccountId,
+    ) -> Result<Option<AccountState>, Self::Error>;
+}
+
+#[derive(Debug, Error)]
+pub enum Error {
+    #[error("account index {0} is out of the hardened derivation range")]
+    InvalidAccountIndex(u32),
+    #[error("invalid spending counters for account {index}")]
+    SpendingCounters {
+        index: u32,
+        #[source]
+        source: account::Error,
+    },
+}
+
+#[derive(Debug, Error)]
+pub enum DiscoveryError<E> {
+    #[error("cannot retrieve the state of account {index}")]
+    Provider { index: u32, error: E },
+    #[error(transparent)]
+    Wallet(#[from] Error),
+}
+
+/// the multi account wallet
+///
+/// * use `discover` to recover the used accounts from the blockchain;
+/// * use `add_account` to start using the next account;
+/// * then `total_value` and `spending_counters` give the aggregated state
+///   of all the accounts.
+///
+pub struct HdWallet {
+    root: Key<XPrv, Bip44<bip44::CoinType>>,
+    accounts: Vec<Wallet>,
+}
+
+impl HdWallet {
+    /// create a wallet without any account from the root private key
+    pub fn from_root_key(root_key: XPrv) -> Self {
+        let root = Key::new_unchecked(root_key, bip44::new(), DerivationScheme::V2)
+            .chimeric_bip44()
+            .cardano();
+
+        Self {
+            root,
+            accounts: Vec::new(),
+        }
+    }
+
+    /// derive the private key of the account at the given index
+    pub fn account_key(&self, index: u32) -> Result<SecretKey<Ed25519Extended>, Error> {
+        let account = HardDerivation::min_value()
+            .checked_add(index)
+            .ok_or(Error::InvalidAccountIndex(index))?;
+        let key = self
+            .root
+            .account(account)
+            .account()
+            .address(SoftDerivation::min_value());
+
+        let xprv: &XPrv = key.as_ref();
+        let bytes: &[u8] = xprv.as_ref();
+        // the last 32 bytes are the chain code, the extended secret key
+        // derived with the V2 scheme is always valid
+        Ok(SecretKey::from_binary(&bytes[..XPRV_SIZE - 32]).expect("valid extended secret key"))
+    }
+
+    /// the accounts of the wallet, ordered by account index
+    pub fn accounts(&self) -> &[Wallet] {
+        &self.accounts
+    }
+
+    pub fn account(&self, index: u32) -> Option<&Wallet> {
+        self.accounts.get(index as usize)
+    }
+
+    pub fn account_mut(&mut self, index: u32) -> Option<&mut Wallet> {
+        self.accounts.get_mut(index as usize)
+    }
+
+    /// derive the next account and add it to the wallet
+    ///
+    /// returns the index of the new account
+    pub fn add_account(&mut self) -> Result<u32, Error> {
+        let index = self.accounts.len() as u32;
+        let account = Wallet::new_from_key(self.account_key(index)?);
+        self.accounts.push(account);
+        Ok(index)
+    }
+
+    /// scan the accounts from index 0 until `gap_limit` consecutive accounts
+    /// are unknown to the `provider`
+    ///
+    /// The accounts up to the last used one are added to the wallet, with
+    /// their state set from the `provider`. The accounts already in the
+    /// wallet are kept, their state is only updated if they have no pending
+    /// transaction. The first account is always kept so that a new wallet
+    /// has an account to receive funds. A `gap_limit` of `0` is handled as
+    /// `1`.
+    ///
+    /// On error the wallet is left untouched.
+    ///
+    /// returns the number of accounts up to the last used one
+    pub fn discover<P>(
+        &mut self,
+        provider: &mut P,
+        gap_limit: u32,
+    ) -> Result<u32, DiscoveryError<P::Error>>
+    where
+        P: AccountStateProvider,
+    {
+        // the discovered accounts and whether they are known to the provider
+        let mut discovered = Vec::new();
+        let mut used = 0;
+        let mut unused = 0;
+
+        while unused < gap_limit.max(1) {
+            let index = discovered.len() as u32;
+            let mut account = Wallet::new_from_key(self.account_key(index)?);
+
+            let state = provider
+                .account_state(&account.account_id())
+                .map_err(|error| DiscoveryError::Provider { index, error })?;
+
+            let known = state.is_some();
+            if let Some(AccountState { value, counters }) = state {
+                account
+                    .set_state(value, counters)
+                    .map_err(|source| Error::SpendingCounters { index, source })?;
+                used = index + 1;
+                unused = 0;
+            } else {
+                unused += 1;
+            }
+
+            discovered.push((account, known));
+        }
+
+        discovered.truncate(used.max(1) as usize);
+        for (index, (account, known)) in discovered.into_iter().enumerate() {
+            match self.accounts.get_mut(index) {
+                None => self.accounts.push(account),
+                Some(existing) if known && existing.pending_transactions().next().is_none() => {
+                    existing
+                        .set_state(account.value(), account.spending_counter())
+                        .expect("spending counters checked during the scan");
+                }
+                Some(_) => {}
+            }
+        }
+
+        Ok(used)
+    }
+
+    /// get the total value of all the accounts
+    pub fn total_value(&self) -> Result<Value, ValueError> {
+        Value::sum(self.accounts.iter().map(Wallet::value))
+    }
+
+    /// get the spending counters of every account, ordered by account index
+    pub fn spending_counters(&self) -> Vec<[SpendingCounter; SpendingCounterIncreasing::LANES]> {
+        self.accounts.iter().map(Wallet::spending_counter).collect()
+    }
+
+    /// update the state of every account with the given fragment
+    ///
+    /// returns `true` if the fragment is relevant to at least one account
+    pub fn check_fragment(
+        &mut self,
+        fragment_id: &FragmentId,
+        fragment: &Fragment,
+    ) -> Result<bool, account::Error> {
+        let mut relevant = false;
+        for account in self.accounts.iter_mut() {
+            relevant |= account.check_fragment(fragment_id, fragment)?;
+        }
+        Ok(relevant)
+    }
+
+    /// confirm a pending transaction on every account
+    pub fn confirm(&mut self, fragment_id: &FragmentId) {
+        for account in self.accounts.iter_mut() {
+            account.confirm(fragment_id);
+        }
+    }
+}
@@ -2,6 +2,7 @@
 
 mod account;
 mod blockchain;
+pub mod hd;
 mod password;
 mod scheme;
 mod states;
@@ -11,6 +12,7 @@ pub mod transaction;
 pub use self::{
     account::{EitherAccount, Wallet},
     blockchain::Settings,
+    hd::{AccountState, AccountStateProvider, HdWallet},
     password::{Password, ScrubbedBytes},
     transaction::{AccountWitnessBuilder, TransactionBuilder},
 };
@@ -23,6 +23,8 @@ where
         Fragment::VoteCast(tx) => for_each_output(tx, on_output),
         Fragment::VoteTally(tx) => for_each_output(tx, on_output),
         Fragment::MintToken(tx) => for_each_output(tx, on_output),
+        Fragment::BurnToken(tx) => for_each_output(tx, on_output),
+        Fragment::VoteDelegation(tx) => for_each_output(tx, on_output),
         Fragment::EvmMapping(tx) => for_each_output(tx, on_output),
     }
 }
@@ -100,6 +102,16 @@ where
             .inputs_and_witnesses()
             .iter()
             .for_each(on_input),
+        Fragment::BurnToken(tx) => tx
+            .as_slice()
+            .inputs_and_witnesses()
+            .iter()
+            .for_each(on_input),
+        Fragment::VoteDelegation(tx) => tx
+            .as_slice()
+            .inputs_and_witnesses()
+            .iter()
+            .for_each(on_input),
         Fragment::EvmMapping(tx) => tx
             .as_slice()
             .inputs_and_witnesses()
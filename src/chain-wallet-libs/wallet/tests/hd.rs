@@ -0,0 +1,214 @@
+use chain_impl_mockchain::{account::SpendingCounter, fragment::FragmentId, value::Value};
+use ed25519_bip32::XPrv;
+use std::collections::HashMap;
+use wallet::{
+    hd::{DiscoveryError, DEFAULT_GAP_LIMIT},
+    AccountId, AccountState, AccountStateProvider, HdWallet,
+};
+
+#[derive(Default)]
+struct Ledger {
+    accounts: HashMap<AccountId, AccountState>,
+    queried: usize,
+}
+
+impl AccountStateProvider for Ledger {
+    type Error = std::convert::Infallible;
+
+    fn account_state(
+        &mut self,
+        account_id: &AccountId,
+    ) -> Result<Option<AccountState>, Self::Error> {
+        self.queried += 1;
+        Ok(self.accounts.get(account_id).cloned())
+    }
+}
+
+/// answers like `ledger`, but fails for the account `unavailable`
+struct Unavailable {
+    ledger: Ledger,
+    unavailable: AccountId,
+}
+
+impl AccountStateProvider for Unavailable {
+    type Error = &'static str;
+
+    fn account_state(
+        &mut self,
+        account_id: &AccountId,
+    ) -> Result<Option<AccountState>, Self::Error> {
+        if *account_id == self.unavailable {
+            return Err("node unavailable");
+        }
+        Ok(self.ledger.account_state(account_id).unwrap())
+    }
+}
+
+fn root_key() -> XPrv {
+    XPrv::from_nonextended_force(&[1; 32], &[2; 32])
+}
+
+fn state(value: u64, counter: u32) -> AccountState {
+    AccountState {
+        value: Value(value),
+        counters: [
+            SpendingCounter::new(0, counter).unwrap(),
+            SpendingCounter::new(1, 0).unwrap(),
+            SpendingCounter::new(2, 0).unwrap(),
+            SpendingCounter::new(3, 0).unwrap(),
+            SpendingCounter::new(4, 0).unwrap(),
+            SpendingCounter::new(5, 0).unwrap(),
+            SpendingCounter::new(6, 0).unwrap(),
+            SpendingCounter::new(7, 0).unwrap(),
+        ],
+    }
+}
+
+fn account_id(wallet: &HdWallet, index: u32) -> AccountId {
+    wallet::Wallet::new_from_key(wallet.account_key(index).unwrap()).account_id()
+}
+
+#[test]
+fn accounts_are_derived_deterministically() {
+    let mut first = HdWallet::from_root_key(root_key());
+    let mut second = HdWallet::from_root_key(root_key());
+
+    for _ in 0..3 {
+        first.add_account().unwrap();
+        second.add_account().unwrap();
+    }
+
+    let ids = |wallet: &HdWallet| -> Vec<AccountId> {
+        wallet
+            .accounts()
+            .iter()
+            .map(wallet::Wallet::account_id)
+            .collect()
+    };
+    assert_eq!(ids(&first), ids(&second));
+    assert_ne!(ids(&first)[0], ids(&first)[1]);
+    assert!(first.account_key(1 << 31).is_err());
+}
+
+#[test]
+fn discovery_stops_at_gap_limit() {
+    let mut wallet = HdWallet::from_root_key(root_key());
+    let mut ledger = Ledger::default();
+    ledger
+        .accounts
+        .insert(account_id(&wallet, 0), state(100, 2));
+    ledger.accounts.insert(account_id(&wallet, 3), state(50, 1));
+    // beyond the gap limit after account 3, never discovered
+    ledger.accounts.insert(
+        account_id(&wallet, 3 + DEFAULT_GAP_LIMIT + 1),
+        state(1_000, 0),
+    );
+
+    let used = wallet.discover(&mut ledger, DEFAULT_GAP_LIMIT).unwrap();
+
+    assert_eq!(used, 4);
+    assert_eq!(wallet.accounts().len(), 4);
+    assert_eq!(ledger.queried, 4 + DEFAULT_GAP_LIMIT as usize);
+    assert_eq!(wallet.total_value().unwrap(), Value(150));
+
+    let counters = wallet.spending_counters();
+    assert_eq!(counters.len(), 4);
+    assert_eq!(counters[0][0], SpendingCounter::new(0, 2).unwrap());
+    assert_eq!(counters[1][0], SpendingCounter::new(0, 0).unwrap());
+    assert_eq!(counters[3][0], SpendingCounter::new(0, 1).unwrap());
+}
+
+#[test]
+fn discovery_keeps_first_account_of_new_wallet() {
+    let mut wallet = HdWallet::from_root_key(root_key());
+    let mut ledger = Ledger::default();
+
+    assert_eq!(wallet.discover(&mut ledger, 0).unwrap(), 0);
+    assert_eq!(wallet.accounts().len(), 1);
+    assert_eq!(wallet.total_value().unwrap(), Value::zero());
+}
+
+/// spend `value` from the account at `index`, leaving a pending transaction
+fn spend(wallet: &mut HdWallet, index: u32, value: u64) {
+    wallet
+        .account_mut(index)
+        .unwrap()
+        .new_transaction(Value(value), 0)
+        .unwrap()
+        .add_fragment_id(FragmentId::hash_bytes(&index.to_be_bytes()));
+}
+
+#[test]
+fn discovery_keeps_existing_accounts_and_pending_transactions() {
+    let mut wallet = HdWallet::from_root_key(root_key());
+    for _ in 0..3 {
+        wallet.add_account().unwrap();
+    }
+    wallet
+        .account_mut(0)
+        .unwrap()
+        .set_state(Value(50), state(50, 0).counters)
+        .unwrap();
+    spend(&mut wallet, 0, 10);
+
+    let mut ledger = Ledger::default();
+    ledger
+        .accounts
+        .insert(account_id(&wallet, 0), state(100, 2));
+    ledger.accounts.insert(account_id(&wallet, 1), state(20, 0));
+
+    assert_eq!(wallet.discover(&mut ledger, 1).unwrap(), 2);
+
+    // the third account is not used yet but was added to the wallet
+    assert_eq!(wallet.accounts().len(), 3);
+    let first = wallet.account(0).unwrap();
+    assert_eq!(first.pending_transactions().count(), 1);
+    assert_eq!(first.value(), Value(40));
+    assert_eq!(wallet.account(1).unwrap().value(), Value(20));
+    assert_eq!(wallet.total_value().unwrap(), Value(60));
+}
+
+#[test]
+fn failed_discovery_leaves_the_wallet_untouched() {
+    let mut wallet = HdWallet::from_root_key(root_key());
+    wallet.add_account().unwrap();
+    wallet
+        .account_mut(0)
+        .unwrap()
+        .set_state(Value(50), state(50, 0).counters)
+        .unwrap();
+    spend(&mut wallet, 0, 10);
+
+    let mut ledger = Ledger::default();
+    ledger
+        .accounts
+        .insert(account_id(&wallet, 0), state(100, 2));
+    ledger.accounts.insert(account_id(&wallet, 1), state(20, 0));
+    let mut provider = Unavailable {
+        ledger,
+        unavailable: account_id(&wallet, 2),
+    };
+
+    assert!(matches!(
+        wallet.discover(&mut provider, DEFAULT_GAP_LIMIT),
+        Err(DiscoveryError::Provider { index: 2, .. })
+    ));
+
+    assert_eq!(wallet.accounts().len(), 1);
+    let first = wallet.account(0).unwrap();
+    assert_eq!(first.pending_transactions().count(), 1);
+    assert_eq!(first.value(), Value(40));
+}
+
+#[test]
+fn total_value_overflow_is_an_error() {
+    let mut wallet = HdWallet::from_root_key(root_key());
+    let mut ledger = Ledger::default();
+    ledger
+        .accounts
+        .insert(account_id(&wallet, 0), state(u64::MAX, 0));
+    ledger.accounts.insert(account_id(&wallet, 1), state(1, 0));
+
+    assert_eq!(wallet.discover(&mut ledger, 1).unwrap(), 2);
+    assert!(wallet.total_value().is_err());
+}
@@ -2,11 +2,11 @@ use super::{
     config_param::ConfigParams,
     error::ApiError,
     extract_context,
-    scalars::{PayloadType, PoolId, PublicKey, TimeOffsetSeconds, VotePlanId},
+    scalars::{PayloadType, PoolId, PublicKey, TimeOffsetSeconds, Value, VotePlanId},
     Address, BftLeader, BlockDate, ExplorerAddress, Pool, Proposal, TaxType,
 };
 use async_graphql::{Context, FieldResult, Object, Union};
-use chain_impl_mockchain::certificate;
+use chain_impl_mockchain::{certificate, vote::VoteDelegationScope};
 
 // interface for grouping certificates as a graphl union
 #[derive(Union)]
@@ -23,6 +23,8 @@ pub enum Certificate {
     UpdateVote(UpdateVote),
     MintToken(MintToken),
     EvmMapping(EvmMapping),
+    BurnToken(BurnToken),
+    VoteDelegation(VoteDelegation),
 }
 
 pub struct StakeDelegation(certificate::StakeDelegation);
@@ -50,6 +52,10 @@ pub struct MintToken(certificate::MintToken);
 
 pub struct EvmMapping(certificate::EvmMapping);
 
+pub struct BurnToken(certificate::BurnToken);
+
+pub struct VoteDelegation(certificate::VoteDelegation);
+
 #[Object]
 impl StakeDelegation {
     // FIXME: Maybe a new Account type would be better?
@@ -273,6 +279,65 @@ impl EvmMapping {
     }
 }
 
+#[Object]
+impl BurnToken {
+    pub async fn name(&self) -> String {
+        format!("{:?}", self.0.name)
+    }
+
+    /// the account the tokens are burnt from
+    pub async fn from(&self, context: &Context<'_>) -> Address {
+        let discrimination = extract_context(context).db.blockchain_config.discrimination;
+        let addr = chain_addr::Address(
+            discrimination,
+            chain_addr::Kind::Account(self.0.from.clone().into()),
+        );
+        Address::from(&ExplorerAddress::New(addr))
+    }
+
+    pub async fn value(&self) -> Value {
+        Value(self.0.value)
+    }
+}
+
+#[Object]
+impl VoteDelegation {
+    /// the vote plan the delegation applies to, if it is not for a voting token
+    pub async fn vote_plan(&self) -> Option<VotePlanId> {
+        match &self.0.scope {
+            VoteDelegationScope::VotePlan(id) => Some(id.clone().into()),
+            VoteDelegationScope::Token(_) => None,
+        }
+    }
+
+    /// the voting token of the vote plans the delegation applies to, if it is
+    /// not for a single vote plan
+    pub async fn voting_token(&self) -> Option<String> {
+        match &self.0.scope {
+            VoteDelegationScope::VotePlan(_) => None,
+            VoteDelegationScope::Token(token) => Some(token.to_string()),
+        }
+    }
+
+    /// the accounts the voting power is delegated to, empty if the delegation
+    /// is removed
+    pub async fn representatives(&self, context: &Context<'_>) -> Vec<Address> {
+        let discrimination = extract_context(context).db.blockchain_config.discrimination;
+        self.0
+            .delegation
+            .representatives()
+            .into_iter()
+            .map(|(representative, _, _)| {
+                let addr = chain_addr::Address(
+                    discrimination,
+                    chain_addr::Kind::Account(representative.clone().into()),
+                );
+                Address::from(&ExplorerAddress::New(addr))
+            })
+            .collect()
+    }
+}
+
 /*------------------------------*/
 /*------- Conversions ---------*/
 /*----------------------------*/
@@ -302,6 +367,10 @@ impl From<chain_impl_mockchain::certificate::Certificate> for Certificate {
             certificate::Certificate::UpdateVote(c) => Certificate::UpdateVote(UpdateVote(c)),
             certificate::Certificate::MintToken(c) => Certificate::MintToken(MintToken(c)),
             certificate::Certificate::EvmMapping(c) => Certificate::EvmMapping(EvmMapping(c)),
+            certificate::Certificate::BurnToken(c) => Certificate::BurnToken(BurnToken(c)),
+            certificate::Certificate::VoteDelegation(c) => {
+                Certificate::VoteDelegation(VoteDelegation(c))
+            }
         }
     }
 }
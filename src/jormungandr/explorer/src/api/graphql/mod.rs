@@ -12,8 +12,8 @@ use self::{
     error::ApiError,
     scalars::{
         BlockCount, ChainLength, EpochNumber, ExternalProposalId, IndexCursor, NonZero,
-        PayloadType, PoolCount, PoolId, PublicKey, Slot, TransactionCount, Value, VoteOptionRange,
-        VotePlanId, VotePlanStatusCount, Weight,
+        PayloadType, PoolCount, PoolId, PublicKey, Slot, TransactionCount, Value, VoteBucketKind,
+        VoteCount, VoteOptionRange, VotePlanId, VotePlanStatusCount, VoterCount, Weight,
     },
 };
 use crate::db::{
@@ -23,6 +23,7 @@ use crate::db::{
     },
     persistent_sequence::PersistentSequence,
     tally::compute_public_tally,
+    vote_stats::{count_unique_voters, VoteBucket as ExplorerVoteBucket, VoteBucketing},
     ExplorerDb, Settings as ChainSettings,
 };
 use async_graphql::{
@@ -37,7 +38,11 @@ use chain_impl_mockchain::{
     fragment::FragmentId,
     key::BftLeaderId,
     stake::StakeControl,
-    vote::{EncryptedVote, PayloadType as OtherPayloadType, ProofOfCorrectVote},
+    tokens::identifier::TokenIdentifier,
+    vote::{
+        EncryptedVote, EncryptedWeightedVote, PayloadType as OtherPayloadType, ProofOfCorrectVote,
+        ProofOfCorrectWeightedVote,
+    },
 };
 use std::{
     convert::{TryFrom, TryInto},
@@ -777,6 +782,14 @@ impl Transaction {
         }
     }
 
+    fn from_block(block: &ExplorerBlock, contents: &ExplorerTransaction) -> Transaction {
+        Transaction {
+            id: contents.id,
+            block_hashes: vec![block.id()],
+            contents: Some(contents.clone()),
+        }
+    }
+
     async fn get_blocks(&self, context: &Context<'_>) -> FieldResult<Vec<Arc<ExplorerBlock>>> {
         let block_ids = if self.block_hashes.is_empty() {
             extract_context(context)
@@ -1263,6 +1276,12 @@ pub struct VotePayloadPrivateStatus {
     encrypted_vote: EncryptedVote,
 }
 
+#[derive(Clone)]
+pub struct VotePayloadPrivateWeightedStatus {
+    proof: ProofOfCorrectWeightedVote,
+    encrypted_vote: EncryptedWeightedVote,
+}
+
 #[Object]
 impl VotePayloadPublicStatus {
     pub async fn choice(&self, _context: &Context<'_>) -> i32 {
@@ -1283,10 +1302,24 @@ impl VotePayloadPrivateStatus {
     }
 }
 
+#[Object]
+impl VotePayloadPrivateWeightedStatus {
+    pub async fn proof(&self, _context: &Context<'_>) -> String {
+        let bytes_proof = self.proof.serialize();
+        base64::encode_config(bytes_proof, base64::URL_SAFE)
+    }
+
+    pub async fn encrypted_vote(&self, _context: &Context<'_>) -> String {
+        let encrypted_vote_bytes = self.encrypted_vote.serialize();
+        base64::encode_config(encrypted_vote_bytes, base64::URL_SAFE)
+    }
+}
+
 #[derive(Clone, Union)]
 pub enum VotePayloadStatus {
     Public(VotePayloadPublicStatus),
     Private(VotePayloadPrivateStatus),
+    PrivateWeighted(VotePayloadPrivateWeightedStatus),
 }
 
 // TODO do proper vote tally
@@ -1315,6 +1348,9 @@ pub struct VotePlanStatus {
     vote_end: BlockDate,
     committee_end: BlockDate,
     payload_type: PayloadType,
+    voting_token: String,
+    /// number of distinct addresses that voted on at least one proposal
+    unique_voters: VoterCount,
     proposals: Vec<VoteProposalStatus>,
 }
 
@@ -1323,8 +1359,7 @@ impl VotePlanStatus {
         vote_plan_id: VotePlanId,
         context: &Context<'_>,
     ) -> FieldResult<Self> {
-        let vote_plan_id = chain_impl_mockchain::certificate::VotePlanId::from_str(&vote_plan_id.0)
-            .map_err(|err| -> FieldError { ApiError::InvalidAddress(err.to_string()).into() })?;
+        let vote_plan_id = vote_plan_id.try_into_internal()?;
         if let Some(vote_plan) = extract_context(context)
             .db
             .get_vote_plan_by_id(&vote_plan_id)
@@ -1337,12 +1372,14 @@ impl VotePlanStatus {
     }
 
     pub fn vote_plan_from_data(vote_plan: Arc<ExplorerVotePlan>) -> Self {
+        let unique_voters = count_unique_voters(std::iter::once(vote_plan.as_ref()));
         let ExplorerVotePlan {
             id,
             vote_start,
             vote_end,
             committee_end,
             payload_type,
+            voting_token,
             proposals,
         } = (*vote_plan).clone();
 
@@ -1352,6 +1389,8 @@ impl VotePlanStatus {
             vote_end: BlockDate::from(vote_end),
             committee_end: BlockDate::from(committee_end),
             payload_type: PayloadType::from(payload_type),
+            voting_token: voting_token.to_string(),
+            unique_voters,
             proposals: proposals
                 .into_iter()
                 .map(|proposal| VoteProposalStatus {
@@ -1364,6 +1403,7 @@ impl VotePlanStatus {
                                 options: proposal.options,
                                 tally: proposal.tally,
                                 votes: proposal.votes.clone(),
+                                vote_history: proposal.vote_history.clone(),
                             },
                             payload_type,
                         )),
@@ -1389,6 +1429,18 @@ impl VotePlanStatus {
                                     encrypted_vote: encrypted_vote.clone(),
                                 }),
                             },
+                            ExplorerVote::PrivateWeighted {
+                                proof,
+                                encrypted_vote,
+                            } => VoteStatus {
+                                address: key.into(),
+                                payload: VotePayloadStatus::PrivateWeighted(
+                                    VotePayloadPrivateWeightedStatus {
+                                        proof: proof.clone(),
+                                        encrypted_vote: encrypted_vote.clone(),
+                                    },
+                                ),
+                            },
                         })
                         .collect(),
                 })
@@ -1417,13 +1469,15 @@ pub fn generic_tally_status(p: ExplorerVoteProposal, payload: OtherPayloadType)
                 }
             }
         }
-        OtherPayloadType::Private => TallyStatus::Private(TallyPrivateStatus {
-            results: Some(vec![
-                Weight("0".to_string());
-                p.options.choice_range().end as usize
-            ]),
-            options: p.options.into(),
-        }),
+        OtherPayloadType::Private | OtherPayloadType::PrivateWeighted => {
+            TallyStatus::Private(TallyPrivateStatus {
+                results: Some(vec![
+                    Weight("0".to_string());
+                    p.options.choice_range().end as usize
+                ]),
+                options: p.options.into(),
+            })
+        }
     }
 }
 
@@ -1621,6 +1675,119 @@ impl Query {
     ) -> FieldResult<VotePlanStatus> {
         VotePlanStatus::vote_plan_from_id(VotePlanId(id), context).await
     }
+
+    /// Votes cast on each proposal of the vote plan, grouped over time. `size` is
+    /// required for the `SLOTS` and `SECONDS` kinds of buckets. The stake is the
+    /// stake of the voters when they cast their votes, and the buckets are placed
+    /// in time with the slot durations in effect at their dates.
+    pub async fn vote_time_series(
+        &self,
+        context: &Context<'_>,
+        vote_plan_id: VotePlanId,
+        proposal_index: Option<i32>,
+        bucket: VoteBucketKind,
+        size: Option<u32>,
+    ) -> FieldResult<Vec<ProposalVoteTimeSeries>> {
+        let id = vote_plan_id.try_into_internal()?;
+        let bucketing = match (bucket, size) {
+            (VoteBucketKind::Epoch, _) => VoteBucketing::Epoch,
+            (VoteBucketKind::Slots, Some(size)) if size > 0 => VoteBucketing::Slots(size),
+            (VoteBucketKind::Seconds, Some(size)) if size > 0 => {
+                VoteBucketing::Seconds(u64::from(size))
+            }
+            _ => {
+                return Err(ApiError::ArgumentError(
+                    "a positive size is required for this kind of bucket".to_owned(),
+                )
+                .into())
+            }
+        };
+
+        let time_series = extract_context(context)
+            .db
+            .get_vote_time_series(&id, bucketing)
+            .await
+            .ok_or_else(|| ApiError::NotFound(format!("Vote plan with id {} not found", id)))?;
+
+        Ok(time_series
+            .into_iter()
+            .enumerate()
+            .filter(|(index, _)| proposal_index.map_or(true, |wanted| *index as i32 == wanted))
+            .map(|(index, buckets)| ProposalVoteTimeSeries {
+                proposal_index: index as i32,
+                buckets: buckets.into_iter().map(VoteBucket::from).collect(),
+            })
+            .collect())
+    }
+
+    /// Number of vote plans using the given voting token, and of distinct
+    /// addresses that voted on them
+    pub async fn voting_token_voters(
+        &self,
+        context: &Context<'_>,
+        voting_token: String,
+    ) -> FieldResult<VotingTokenVoters> {
+        let token = TokenIdentifier::from_str(&voting_token)
+            .map_err(|err| ApiError::ArgumentError(err.to_string()))?;
+        let (vote_plans, unique_voters) = extract_context(context)
+            .db
+            .get_voting_token_voters(&token)
+            .await;
+
+        Ok(VotingTokenVoters {
+            voting_token,
+            vote_plans: u64::from(vote_plans),
+            unique_voters,
+        })
+    }
+}
+
+#[derive(SimpleObject)]
+pub struct ProposalVoteTimeSeries {
+    proposal_index: i32,
+    buckets: Vec<VoteBucket>,
+}
+
+#[derive(SimpleObject)]
+pub struct VoteBucket {
+    start_date: BlockDate,
+    /// seconds since the UNIX epoch
+    start_time: u64,
+    votes: VoteCount,
+    stake: Value,
+}
+
+impl From<ExplorerVoteBucket> for VoteBucket {
+    fn from(bucket: ExplorerVoteBucket) -> VoteBucket {
+        VoteBucket {
+            start_date: BlockDate::from(bucket.start_date),
+            start_time: bucket.start_time,
+            votes: bucket.votes,
+            stake: Value::from(bucket.stake),
+        }
+    }
+}
+
+#[derive(SimpleObject)]
+pub struct VotingTokenVoters {
+    voting_token: String,
+    vote_plans: VotePlanStatusCount,
+    unique_voters: VoterCount,
+}
+
+impl VotePlanId {
+    fn try_into_internal(&self) -> FieldResult<chain_impl_mockchain::certificate::VotePlanId> {
+        chain_impl_mockchain::certificate::VotePlanId::from_str(&self.0)
+            .map_err(|err| -> FieldError { ApiError::InvalidAddress(err.to_string()).into() })
+    }
+}
+
+/// A vote cast included in a block of the main branch
+#[derive(SimpleObject)]
+pub struct VoteCastEvent {
+    block: Block,
+    transaction: Transaction,
+    vote_cast: VoteCast,
 }
 
 pub struct Subscription;
@@ -1639,6 +1806,121 @@ impl Subscription {
                     .map(|(hash, state)| Branch::from_id_and_state(hash, state))
             })
     }
+
+    /// Blocks joining the main branch, in chain order. On a switch of branch,
+    /// the blocks of the new branch after the fork point are sent.
+    async fn blocks(&self, context: &Context<'_>) -> impl futures::Stream<Item = Block> {
+        use futures::StreamExt;
+        extract_context(context)
+            .db
+            .block_subscription()
+            .map(|(block, _)| Block::from_contents(block))
+    }
+
+    /// Votes cast on the given vote plan, optionally restricted to a single proposal
+    async fn vote_casts(
+        &self,
+        context: &Context<'_>,
+        vote_plan_id: VotePlanId,
+        proposal_index: Option<i32>,
+    ) -> FieldResult<impl futures::Stream<Item = VoteCastEvent>> {
+        use futures::StreamExt;
+        let vote_plan_id = vote_plan_id.try_into_internal()?;
+        Ok(extract_context(context)
+            .db
+            .block_subscription()
+            .flat_map(move |(block, _)| {
+                let mut transactions: Vec<_> = block
+                    .transactions
+                    .values()
+                    .filter_map(|tx| match &tx.certificate {
+                        Some(certificate::Certificate::VoteCast(vote_cast))
+                            if vote_cast.vote_plan() == &vote_plan_id
+                                && proposal_index.map_or(true, |index| {
+                                    i32::from(vote_cast.proposal_index()) == index
+                                }) =>
+                        {
+                            Some((tx, vote_cast.clone()))
+                        }
+                        _ => None,
+                    })
+                    .collect();
+                transactions.sort_by_key(|(tx, _)| tx.offset_in_block);
+
+                let events: Vec<_> = transactions
+                    .into_iter()
+                    .map(|(tx, vote_cast)| VoteCastEvent {
+                        block: Block::from_contents(Arc::clone(&block)),
+                        transaction: Transaction::from_block(&block, tx),
+                        vote_cast: VoteCast::from(vote_cast),
+                    })
+                    .collect();
+                futures::stream::iter(events)
+            }))
+    }
+
+    /// Status of the given vote plan, sent each time a block tallies it
+    async fn tally(
+        &self,
+        context: &Context<'_>,
+        vote_plan_id: VotePlanId,
+    ) -> FieldResult<impl futures::Stream<Item = VotePlanStatus>> {
+        use futures::StreamExt;
+        let vote_plan_id = vote_plan_id.try_into_internal()?;
+        Ok(extract_context(context)
+            .db
+            .block_subscription()
+            .filter_map(move |(block, state)| {
+                let tallied = block.transactions.values().any(|tx| {
+                    matches!(
+                        &tx.certificate,
+                        Some(certificate::Certificate::VoteTally(vote_tally))
+                            if vote_tally.id() == &vote_plan_id
+                    )
+                });
+                // the state of the branch may already contain later blocks, but
+                // this is still the latest status of the vote plan
+                let status = if tallied {
+                    state
+                        .state()
+                        .get_vote_plan(&vote_plan_id)
+                        .map(VotePlanStatus::vote_plan_from_data)
+                } else {
+                    None
+                };
+                futures::future::ready(status)
+            }))
+    }
+
+    /// Transactions with an input or an output on the given address
+    async fn address_transactions(
+        &self,
+        context: &Context<'_>,
+        bech32: String,
+    ) -> FieldResult<impl futures::Stream<Item = Transaction>> {
+        use futures::StreamExt;
+        let address = Address::from_bech32(&bech32)?.id;
+        Ok(extract_context(context)
+            .db
+            .block_subscription()
+            .flat_map(move |(block, _)| {
+                let mut transactions: Vec<_> = block
+                    .transactions
+                    .values()
+                    .filter(|tx| {
+                        tx.inputs().iter().any(|input| input.address == address)
+                            || tx.outputs().iter().any(|output| output.address == address)
+                    })
+                    .collect();
+                transactions.sort_by_key(|tx| tx.offset_in_block);
+
+                let transactions: Vec<_> = transactions
+                    .into_iter()
+                    .map(|tx| Transaction::from_block(&block, tx))
+                    .collect();
+                futures::stream::iter(transactions)
+            }))
+    }
 }
 
 pub type Schema = async_graphql::Schema<Query, EmptyMutation, Subscription>;
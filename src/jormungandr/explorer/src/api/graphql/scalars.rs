@@ -111,6 +111,8 @@ pub type BlockCount = u64;
 pub type TransactionCount = u64;
 pub type PoolCount = u64;
 pub type VotePlanStatusCount = u64;
+pub type VoteCount = u64;
+pub type VoterCount = u64;
 
 pub struct PublicKey(pub String);
 
@@ -203,6 +205,18 @@ impl ScalarType for ExternalProposalId {
 pub enum PayloadType {
     Public,
     Private,
+    PrivateWeighted,
+}
+
+/// How votes are grouped in a time series
+#[derive(Clone, Copy, PartialEq, Eq, Enum)]
+pub enum VoteBucketKind {
+    /// one bucket per epoch
+    Epoch,
+    /// buckets of `size` slots, restarting at the beginning of every epoch
+    Slots,
+    /// buckets of `size` seconds, starting at the date of block0
+    Seconds,
 }
 
 #[derive(Clone)]
@@ -336,6 +350,7 @@ impl From<vote::PayloadType> for PayloadType {
         match payload_type {
             vote::PayloadType::Public => Self::Public,
             vote::PayloadType::Private => Self::Private,
+            vote::PayloadType::PrivateWeighted => Self::PrivateWeighted,
         }
     }
 }
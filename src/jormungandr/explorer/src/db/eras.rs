@@ -0,0 +1,162 @@
+//! Periods of the chain during which the slots have the same duration, to
+//! place the blocks in time.
+
+use super::{indexing::ExplorerBlock, persistent_sequence::PersistentSequence, BlockchainConfig};
+use chain_impl_mockchain::{
+    block::BlockDate, certificate::Certificate, config::ConfigParam, fragment::ConfigParams,
+    setting::Settings, update::UpdateState,
+};
+use std::sync::Arc;
+
+/// slot duration in application from the first slot of `first_epoch`
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Era {
+    pub first_epoch: u32,
+    /// seconds since the UNIX epoch at the start of the era
+    pub start_time: u64,
+    pub slot_duration: u8,
+}
+
+/// The eras of a branch of the chain.
+///
+/// The ledger keeps the number of slots per epoch of block0 for the whole
+/// chain, but the slot duration follows its settings and the node changes it
+/// at the first slot of an epoch. The settings are replayed here from the
+/// update proposals and votes of the blocks. The slot duration set by a
+/// parameters governance action is not followed, the acceptance of the action
+/// depends on the stake distribution of the ledger.
+#[derive(Clone)]
+pub struct Eras {
+    pub(super) slots_per_epoch: u32,
+    pub(super) settings: Settings,
+    pub(super) updates: UpdateState,
+    /// ordered by first epoch, the first era starts at block0
+    pub(super) eras: PersistentSequence<Era>,
+}
+
+impl Eras {
+    pub fn new(block0_params: &ConfigParams, config: &BlockchainConfig) -> Self {
+        // the same settings as the ledger, without its static parameters
+        let mut params = ConfigParams::new();
+        for param in block0_params.iter() {
+            match param {
+                ConfigParam::Block0Date(_)
+                | ConfigParam::Discrimination(_)
+                | ConfigParam::KesUpdateSpeed(_)
+                | ConfigParam::TreasuryAdd(_)
+                | ConfigParam::RewardPot(_) => (),
+                param => params.push(param.clone()),
+            }
+        }
+
+        Eras {
+            slots_per_epoch: config.slots_per_epoch,
+            settings: Settings::new()
+                .try_apply(&params)
+                .expect("valid settings in the initial params"),
+            updates: UpdateState::new(),
+            eras: PersistentSequence::new().append(Era {
+                first_epoch: 0,
+                start_time: config.block0_date,
+                slot_duration: config.slot_duration,
+            }),
+        }
+    }
+
+    /// update the settings with the block following the block of date
+    /// `prev_date`, in the same order as the ledger
+    pub fn apply_block(self, prev_date: BlockDate, block: &ExplorerBlock) -> Self {
+        let Eras {
+            slots_per_epoch,
+            mut settings,
+            mut updates,
+            mut eras,
+        } = self;
+        let date = block.date();
+
+        if prev_date < date {
+            let (new_updates, new_settings) = updates.process_proposals(settings, prev_date, date);
+            updates = new_updates;
+            settings = new_settings;
+        }
+
+        if prev_date.epoch < date.epoch {
+            let current = Self::era_of(&eras, date.epoch);
+            if current.slot_duration != settings.slot_duration {
+                let start_time = current.start_time
+                    + u64::from(date.epoch - current.first_epoch)
+                        * u64::from(slots_per_epoch)
+                        * u64::from(current.slot_duration);
+                eras = eras.append(Era {
+                    first_epoch: date.epoch,
+                    start_time,
+                    slot_duration: settings.slot_duration,
+                });
+            }
+        }
+
+        let mut transactions: Vec<_> = block.transactions.values().collect();
+        transactions.sort_by_key(|tx| tx.offset_in_block);
+        for tx in transactions {
+            // the node already checked the proposals and votes, an invalid
+            // one is skipped like the ledger would have rejected it
+            updates = match &tx.certificate {
+                Some(Certificate::UpdateProposal(proposal)) => updates
+                    .clone()
+                    .apply_proposal(tx.id(), proposal.clone(), &settings, date)
+                    .unwrap_or(updates),
+                Some(Certificate::UpdateVote(vote)) => updates
+                    .clone()
+                    .apply_vote(vote, &settings)
+                    .unwrap_or(updates),
+                _ => updates,
+            };
+        }
+
+        Eras {
+            slots_per_epoch,
+            settings,
+            updates,
+            eras,
+        }
+    }
+
+    /// seconds since the UNIX epoch at the start of the slot of `date`
+    pub fn time(&self, date: BlockDate) -> u64 {
+        let era = Self::era_of(&self.eras, date.epoch);
+        era.start_time
+            + (u64::from(date.epoch - era.first_epoch) * u64::from(self.slots_per_epoch)
+                + u64::from(date.slot_id))
+                * u64::from(era.slot_duration)
+    }
+
+    /// date of the first slot starting at or after `time`, which must not be
+    /// before block0
+    pub fn date_from(&self, time: u64) -> BlockDate {
+        let era = Self::last_era(&self.eras, |era| era.start_time <= time);
+        let elapsed = time.saturating_sub(era.start_time);
+        let slot = match u64::from(era.slot_duration) {
+            0 => elapsed,
+            slot_duration => (elapsed + slot_duration - 1) / slot_duration,
+        };
+        let slots_per_epoch = u64::from(self.slots_per_epoch.max(1));
+        BlockDate {
+            epoch: era.first_epoch + (slot / slots_per_epoch) as u32,
+            slot_id: (slot % slots_per_epoch) as u32,
+        }
+    }
+
+    fn era_of(eras: &PersistentSequence<Era>, epoch: u32) -> &Arc<Era> {
+        Self::last_era(eras, |era| era.first_epoch <= epoch)
+    }
+
+    /// the last era matching `predicate`, the era of block0 if none does
+    fn last_era(eras: &PersistentSequence<Era>, predicate: impl Fn(&Era) -> bool) -> &Arc<Era> {
+        (0..eras.len())
+            .rev()
+            .filter_map(|i| eras.get(i))
+            .find(|era| predicate(era))
+            .or_else(|| eras.get(0u64))
+            .expect("the era of block0")
+    }
+}
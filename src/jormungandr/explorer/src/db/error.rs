@@ -1,7 +1,9 @@
+use super::storage::StorageError;
 use chain_impl_mockchain::{
     block::{ChainLength, HeaderId as HeaderHash},
     fragment::FragmentId,
 };
+use std::sync::Arc;
 use thiserror::Error;
 
 #[derive(Debug, Error, Clone)]
@@ -26,6 +28,14 @@ pub enum ExplorerError {
     TxCalculationFailure,
     #[error("unable to transmute to explorer API representation")]
     ExplorerTransmuteFail,
+    #[error("explorer storage error")]
+    StorageError(#[source] Arc<StorageError>),
+}
+
+impl From<StorageError> for ExplorerError {
+    fn from(error: StorageError) -> Self {
+        ExplorerError::StorageError(Arc::new(error))
+    }
 }
 
 #[derive(Debug, Error, Clone)]
@@ -11,9 +11,13 @@ use chain_impl_mockchain::{
     fragment::{ConfigParams, Fragment, FragmentId},
     header::{BlockDate, ChainLength, Epoch, HeaderId as HeaderHash},
     key::{BftLeaderId, Hash},
+    tokens::identifier::TokenIdentifier,
     transaction::{InputEnum, TransactionSlice, Witness},
     value::Value,
-    vote::{Choice, EncryptedVote, Options, PayloadType, ProofOfCorrectVote, Weight},
+    vote::{
+        Choice, EncryptedVote, EncryptedWeightedVote, Options, PayloadType, ProofOfCorrectVote,
+        ProofOfCorrectWeightedVote, Weight,
+    },
 };
 use error::ExplorerError as Error;
 use std::{
@@ -120,6 +124,7 @@ pub struct ExplorerVotePlan {
     pub vote_end: BlockDate,
     pub committee_end: BlockDate,
     pub payload_type: PayloadType,
+    pub voting_token: TokenIdentifier,
     pub proposals: Vec<ExplorerVoteProposal>,
 }
 
@@ -130,6 +135,10 @@ pub enum ExplorerVote {
         proof: ProofOfCorrectVote,
         encrypted_vote: EncryptedVote,
     },
+    PrivateWeighted {
+        proof: ProofOfCorrectWeightedVote,
+        encrypted_vote: EncryptedWeightedVote,
+    },
 }
 
 #[derive(Clone, Debug)]
@@ -138,6 +147,17 @@ pub struct ExplorerVoteProposal {
     pub options: Options,
     pub tally: Option<ExplorerVoteTally>,
     pub votes: Hamt<ExplorerAddress, ExplorerVote>,
+    /// every vote cast on the proposal, in chain order, including the ones
+    /// later replaced by a new vote of the same voter
+    pub vote_history: PersistentSequence<ExplorerVoteCast>,
+}
+
+#[derive(Clone, Debug)]
+pub struct ExplorerVoteCast {
+    pub voter: ExplorerAddress,
+    pub date: BlockDate,
+    /// stake of the voter when the vote was cast
+    pub stake: Value,
 }
 
 // TODO do proper vote tally
@@ -379,6 +399,40 @@ impl ExplorerBlock {
                         }
                     }
                 }
+                Fragment::BurnToken(tx) => {
+                    let tx = tx.as_slice();
+                    match ExplorerTransaction::from(
+                        &context,
+                        &fragment_id,
+                        &tx,
+                        Some(Certificate::BurnToken(tx.payload().into_payload())),
+                        offset,
+                        &current_block_txs,
+                    ) {
+                        Ok(tx) => Some(tx),
+                        Err(e) => {
+                            error!(error = %e, "unable to map burn token fragment");
+                            return Err(Error::ExplorerTransmuteFail);
+                        }
+                    }
+                }
+                Fragment::VoteDelegation(tx) => {
+                    let tx = tx.as_slice();
+                    match ExplorerTransaction::from(
+                        &context,
+                        &fragment_id,
+                        &tx,
+                        Some(Certificate::VoteDelegation(tx.payload().into_payload())),
+                        offset,
+                        &current_block_txs,
+                    ) {
+                        Ok(tx) => Some(tx),
+                        Err(e) => {
+                            error!(error = %e, "unable to map vote delegation fragment");
+                            return Err(Error::ExplorerTransmuteFail);
+                        }
+                    }
+                }
                 Fragment::OldUtxoDeclaration(decl) => {
                     let outputs = decl
                         .addrs
@@ -1,29 +1,42 @@
+pub mod eras;
 pub mod error;
 pub mod indexing;
 pub mod multiverse;
 pub mod persistent_sequence;
+pub mod snapshot;
+pub mod storage;
 pub(crate) mod tally;
+#[cfg(test)]
+mod tests;
+pub mod vote_stats;
 
 use self::{
+    eras::Eras,
     error::{BlockNotFound, ExplorerError as Error},
     indexing::{
         Addresses, Blocks, ChainLengths, EpochData, Epochs, ExplorerAddress, ExplorerBlock,
-        ExplorerVote, ExplorerVotePlan, ExplorerVoteProposal, StakePool, StakePoolBlocks,
-        StakePoolData, Transactions, VotePlans,
+        ExplorerVote, ExplorerVoteCast, ExplorerVotePlan, ExplorerVoteProposal, StakePool,
+        StakePoolBlocks, StakePoolData, Transactions, VotePlans,
     },
     persistent_sequence::PersistentSequence,
+    snapshot::Snapshot,
+    storage::Storage,
+    vote_stats::{
+        compute_vote_time_series, count_unique_voters, voter_stake, VoteBucket, VoteBucketing,
+    },
 };
 use crate::db::tally::{compute_private_tally, compute_public_tally};
 use chain_addr::Discrimination;
 use chain_core::property::Block as _;
 use chain_impl_mockchain::{
     block::{Block, ChainLength, Epoch, HeaderId as HeaderHash},
-    certificate::{Certificate, PoolId, VotePlanId},
+    certificate::{Certificate, PoolId, VoteCast, VotePlanId},
     chaintypes::ConsensusVersion,
     config::ConfigParam,
     fee::LinearFee,
     fragment::{ConfigParams, Fragment, FragmentId},
     stake::{Stake, StakeControl},
+    tokens::identifier::TokenIdentifier,
     vote::PayloadType,
 };
 use futures::prelude::*;
@@ -39,6 +52,13 @@ use std::{
 use tokio::sync::{broadcast, RwLock};
 use tracing::error;
 
+/// number of blocks restored from the storage between two garbage
+/// collections of the multiverse
+const RESTORE_GC_INTERVAL: u32 = 1024;
+/// number of confirmed blocks between two snapshots of the indexed state in
+/// the storage
+const SNAPSHOT_INTERVAL: u32 = 1024;
+
 #[derive(Clone)]
 pub struct Explorer {
     pub db: ExplorerDb,
@@ -60,11 +80,17 @@ pub struct ExplorerDb {
     pub blockchain_config: BlockchainConfig,
     stable_store: StableIndex,
     tip_broadcast: tokio::sync::broadcast::Sender<(HeaderHash, multiverse::Ref)>,
+    /// On-disk copy of the indexed blocks and of the indexed state of a
+    /// confirmed block, the indexes are restored from it when the explorer
+    /// restarts instead of pulling the whole chain again from the node.
+    storage: Option<Storage>,
 }
 
 #[derive(Clone)]
 pub struct StableIndex {
     confirmed_block_chain_length: Arc<AtomicU32>,
+    /// chain length of the block of the last snapshot written to the storage
+    snapshot_chain_length: Arc<AtomicU32>,
 }
 
 #[derive(Clone)]
@@ -75,6 +101,11 @@ pub struct BlockchainConfig {
     pub consensus_version: ConsensusVersion,
     pub fees: LinearFee,
     pub epoch_stability_depth: u32,
+    /// Start of the first era, the blocks are placed in time with the eras of
+    /// their branch
+    pub block0_date: u64,
+    pub slots_per_epoch: u32,
+    pub slot_duration: u8,
 }
 
 /// Inmutable data structure used to represent the explorer's state at a given Block
@@ -91,6 +122,7 @@ pub struct State {
     stake_pool_blocks: StakePoolBlocks,
     vote_plans: VotePlans,
     stake_control: StakeControl,
+    eras: Eras,
 }
 
 #[derive(Clone)]
@@ -106,17 +138,9 @@ pub struct Settings {
 }
 
 impl ExplorerDb {
-    pub fn bootstrap(block0: Block) -> Result<Self, Error> {
-        let blockchain_config = BlockchainConfig::from_config_params(
-            block0
-                .contents()
-                .iter()
-                .find_map(|fragment| match fragment {
-                    Fragment::Initial(config_params) => Some(config_params),
-                    _ => None,
-                })
-                .expect("the Initial fragment to be present in the genesis block"),
-        );
+    pub fn bootstrap(block0: Block, storage: Option<Storage>) -> Result<Self, Error> {
+        let blockchain_config = BlockchainConfig::from_config_params(initial_params(&block0));
+        let eras = Eras::new(initial_params(&block0), &blockchain_config);
 
         let block = ExplorerBlock::resolve_from(
             &block0,
@@ -147,33 +171,219 @@ impl ExplorerDb {
             stake_pool_blocks,
             vote_plans,
             stake_control,
+            eras,
         };
 
-        let block0_id = block0.id();
-        let (_, multiverse) = Multiverse::new(block0.chain_length(), block0_id, initial_state);
+        if let Some(storage) = &storage {
+            storage.put_block(&block0)?;
+        }
+
+        Ok(Self::new(
+            blockchain_config,
+            block0.chain_length(),
+            block0.id(),
+            initial_state,
+            storage,
+        ))
+    }
 
-        let block0_id = block0.id();
+    fn new(
+        blockchain_config: BlockchainConfig,
+        chain_length: ChainLength,
+        block_id: HeaderHash,
+        state: State,
+        storage: Option<Storage>,
+    ) -> Self {
+        let (_, multiverse) = Multiverse::new(chain_length, block_id, state);
 
         let (tx, _) = broadcast::channel(10);
 
-        let bootstraped_db = ExplorerDb {
+        ExplorerDb {
             multiverse,
-            longest_chain_tip: Tip::new(block0_id),
+            longest_chain_tip: Tip::new(block_id),
             blockchain_config,
             stable_store: StableIndex {
-                confirmed_block_chain_length: Arc::new(AtomicU32::default()),
+                confirmed_block_chain_length: Arc::new(AtomicU32::new(chain_length.into())),
+                snapshot_chain_length: Arc::new(AtomicU32::new(chain_length.into())),
             },
             tip_broadcast: tx,
+            storage,
+        }
+    }
+
+    /// Restore the indexes from the storage, `None` is returned if the
+    /// storage is empty.
+    ///
+    /// The indexing resumes from the snapshot of the indexed state if it is
+    /// in the branch of the stored tip, from the block0 otherwise.
+    pub async fn load(storage: Storage) -> Result<Option<Self>, Error> {
+        let (block0, tip) = match (storage.get_block0()?, storage.get_tip()?) {
+            (Some(block0), Some(tip)) => (block0, tip),
+            _ => return Ok(None),
+        };
+
+        let snapshot = match storage.get_snapshot() {
+            Ok(Some(snapshot)) if storage.is_ancestor(snapshot.hash, tip)? => Some(snapshot),
+            Ok(Some(snapshot)) => {
+                tracing::warn!(
+                    "ignoring the snapshot of block {} which is not in the branch of the tip",
+                    snapshot.hash
+                );
+                None
+            }
+            Ok(None) => None,
+            Err(error) => {
+                tracing::warn!(
+                    %error,
+                    "discarding the unreadable snapshot of the explorer, reindexing from the block0"
+                );
+                storage.remove_snapshot()?;
+                None
+            }
+        };
+
+        let (db, from) = match snapshot {
+            Some(Snapshot {
+                hash,
+                chain_length,
+                state,
+            }) => {
+                tracing::info!("restoring the explorer indexes from block {}", hash);
+                let db = Self::new(
+                    BlockchainConfig::from_config_params(initial_params(&block0)),
+                    chain_length,
+                    hash,
+                    state,
+                    Some(storage.clone()),
+                );
+                (db, chain_length)
+            }
+            None => {
+                let chain_length = block0.chain_length();
+                (
+                    Self::bootstrap(block0, Some(storage.clone()))?,
+                    chain_length,
+                )
+            }
         };
 
-        Ok(bootstraped_db)
+        for block in storage.iter_branch(from, tip)? {
+            let block = block?;
+            let chain_length = block.header().chain_length();
+            db.index_block(&block).await?;
+
+            // keep the memory usage in check, the states of the old blocks
+            // are not needed anymore
+            if u32::from(chain_length) % RESTORE_GC_INTERVAL == 0 {
+                db.multiverse
+                    .gc(db.blockchain_config.epoch_stability_depth)
+                    .await;
+            }
+        }
+
+        // the other branches only hold blocks that are not stable yet
+        for branch in storage.get_branches()? {
+            db.restore_branch(&storage, branch, from).await?;
+        }
+
+        db.set_tip(tip).await?;
+
+        tracing::info!("restored the explorer indexes up to {}", tip);
+
+        Ok(Some(db))
+    }
+
+    /// index the blocks of the branch ending at `tip` that are not indexed
+    /// yet, the branches that forked at or before `from` cannot be restored
+    /// and are skipped
+    async fn restore_branch(
+        &self,
+        storage: &Storage,
+        tip: HeaderHash,
+        from: ChainLength,
+    ) -> Result<(), Error> {
+        let mut blocks = Vec::new();
+        let mut current = tip;
+
+        while self.multiverse.get_ref(&current).await.is_none() {
+            let block = storage
+                .get(current)?
+                .ok_or(Error::AncestorNotFound(current))?;
+            if block.header().chain_length() <= from {
+                tracing::debug!(
+                    "skipping the branch with head {} forked before {}",
+                    tip,
+                    from
+                );
+                return Ok(());
+            }
+            current = block.header().block_parent_hash();
+            blocks.push(block);
+        }
+
+        for block in blocks.iter().rev() {
+            self.index_block(block).await?;
+        }
+
+        Ok(())
+    }
+
+    /// Blocks from which the synchronization with the node can be resumed:
+    /// the last stable block, the current tip and the tips of the other
+    /// branches, so the node does not send again the blocks already indexed.
+    pub async fn checkpoints(&self) -> Vec<HeaderHash> {
+        let (tip, state_ref) = self.get_tip().await;
+        let confirmed_block_chain_length: ChainLength = self
+            .stable_store
+            .confirmed_block_chain_length
+            .load(Ordering::Acquire)
+            .into();
+
+        let mut checkpoints: Vec<_> = state_ref
+            .state()
+            .chain_lengths
+            .lookup(&confirmed_block_chain_length)
+            .map(|hash| **hash)
+            .into_iter()
+            .collect();
+        if !checkpoints.contains(&tip) {
+            checkpoints.push(tip);
+        }
+        for (branch, _) in self.multiverse.tips().await {
+            if !checkpoints.contains(&branch) {
+                checkpoints.push(branch);
+            }
+        }
+
+        checkpoints
     }
 
     /// Try to add a new block to the indexes, this can fail if the parent of the block is
     /// not processed.
     /// This doesn't perform any validation on the given block and the previous state, it
     /// is assumed that the Block is valid
-    pub async fn apply_block(&self, block: Block) -> Result<multiverse::Ref, Error> {
+    ///
+    /// returns `None` if the block is already indexed, the node may send
+    /// again the blocks of a branch when the synchronization resumes
+    pub async fn apply_block(&self, block: Block) -> Result<Option<multiverse::Ref>, Error> {
+        let block_id = block.header().hash();
+        if self.multiverse.get_ref(&block_id).await.is_some()
+            || self.get_block(&block_id).await.is_some()
+        {
+            tracing::debug!("block {} is already indexed", block_id);
+            return Ok(None);
+        }
+
+        let state_ref = self.index_block(&block).await?;
+
+        if let Some(storage) = &self.storage {
+            storage.put_block(&block)?;
+        }
+
+        Ok(Some(state_ref))
+    }
+
+    async fn index_block(&self, block: &Block) -> Result<multiverse::Ref, Error> {
         let previous_block = block.header().block_parent_hash();
         let chain_length = block.header().chain_length();
         let block_id = block.header().hash();
@@ -194,10 +404,15 @@ impl ExplorerDb {
             stake_pool_blocks,
             vote_plans,
             stake_control,
+            eras,
         } = previous_state.state().clone();
+        let previous_date = blocks
+            .lookup(&previous_block)
+            .ok_or(Error::AncestorNotFound(block_id))?
+            .date();
 
         let explorer_block = ExplorerBlock::resolve_from(
-            &block,
+            block,
             indexing::ExplorerBlockBuildingContext {
                 discrimination,
                 prev_transactions: &transactions,
@@ -211,6 +426,8 @@ impl ExplorerDb {
 
         let vote_plans = apply_block_to_vote_plans(vote_plans, &explorer_block, &stake_control)?;
 
+        let eras = eras.apply_block(previous_date, &explorer_block);
+
         let state_ref = multiverse
             .insert(
                 chain_length,
@@ -226,6 +443,7 @@ impl ExplorerDb {
                     stake_pool_blocks,
                     vote_plans,
                     stake_control,
+                    eras,
                 },
             )
             .await;
@@ -272,11 +490,42 @@ impl ExplorerDb {
                 .confirmed_block_chain_length
                 .store(confirmed_block_chain_length.into(), Ordering::Release);
 
+            // the state of the confirmed block is still in the multiverse
+            // before the garbage collection
+            let last_snapshot = self
+                .stable_store
+                .snapshot_chain_length
+                .load(Ordering::Acquire);
+            if u32::from(confirmed_block_chain_length) >= last_snapshot + SNAPSHOT_INTERVAL {
+                if let Some(confirmed_block) =
+                    state.chain_lengths.lookup(&confirmed_block_chain_length)
+                {
+                    if let Err(error) = self
+                        .save_snapshot(**confirmed_block, confirmed_block_chain_length)
+                        .await
+                    {
+                        error!(%error, "failed to save the snapshot of the explorer indexes");
+                    }
+                }
+            }
+
             self.multiverse
                 .gc(self.blockchain_config.epoch_stability_depth)
                 .await;
         }
 
+        if let Some(storage) = self.storage.clone() {
+            let depth = self.blockchain_config.epoch_stability_depth;
+            if let Err(error) = tokio::task::spawn_blocking(move || {
+                storage.put_tip(hash).and_then(|()| storage.gc(depth, hash))
+            })
+            .await
+            .expect("storage task to not panic")
+            {
+                error!(%error, "failed to update the explorer storage");
+            }
+        }
+
         let mut guard = self.longest_chain_tip.0.write().await;
 
         *guard = hash;
@@ -286,6 +535,42 @@ impl ExplorerDb {
         Ok(())
     }
 
+    /// write the indexed state of the given block to the storage, nothing is
+    /// done if the state is not in the multiverse anymore
+    async fn save_snapshot(
+        &self,
+        hash: HeaderHash,
+        chain_length: ChainLength,
+    ) -> Result<(), Error> {
+        let storage = match &self.storage {
+            Some(storage) => storage.clone(),
+            None => return Ok(()),
+        };
+        let state_ref = match self.multiverse.get_ref(&hash).await {
+            Some(state_ref) => state_ref,
+            None => return Ok(()),
+        };
+        let snapshot = Snapshot {
+            hash,
+            chain_length,
+            state: state_ref.state().clone(),
+        };
+
+        tokio::task::spawn_blocking(move || storage.put_snapshot(&snapshot))
+            .await
+            .expect("snapshot task to not panic")?;
+
+        self.stable_store
+            .snapshot_chain_length
+            .store(chain_length.into(), Ordering::Release);
+        tracing::debug!(
+            "saved the snapshot of the explorer indexes at block {}",
+            hash
+        );
+
+        Ok(())
+    }
+
     pub async fn get_block_with_branches(
         &self,
         block_id: &HeaderHash,
@@ -417,6 +702,44 @@ impl ExplorerDb {
         None
     }
 
+    /// Time series of the votes cast on each proposal of the vote plan, in the
+    /// main branch
+    pub async fn get_vote_time_series(
+        &self,
+        vote_plan_id: &VotePlanId,
+        bucketing: VoteBucketing,
+    ) -> Option<Vec<Vec<VoteBucket>>> {
+        let (_hash, state_ref) = self.get_tip().await;
+        let state = state_ref.state();
+        let vote_plan = state.vote_plans.lookup(vote_plan_id)?;
+
+        Some(
+            vote_plan
+                .proposals
+                .iter()
+                .map(|proposal| compute_vote_time_series(proposal, &state.eras, bucketing))
+                .collect(),
+        )
+    }
+
+    /// Number of vote plans using the given voting token in the main branch, and
+    /// the number of distinct addresses that voted on them
+    pub async fn get_voting_token_voters(&self, voting_token: &TokenIdentifier) -> (u32, u64) {
+        let (_hash, state_ref) = self.get_tip().await;
+        let vote_plans: Vec<_> = state_ref
+            .state()
+            .vote_plans
+            .iter()
+            .map(|(_id, vote_plan)| vote_plan)
+            .filter(|vote_plan| &vote_plan.voting_token == voting_token)
+            .collect();
+
+        (
+            vote_plans.len() as u32,
+            count_unique_voters(vote_plans.iter().map(|vote_plan| vote_plan.as_ref())),
+        )
+    }
+
     pub async fn get_branch(&self, hash: &HeaderHash) -> Option<multiverse::Ref> {
         self.multiverse.get_ref(hash).await
     }
@@ -440,6 +763,51 @@ impl ExplorerDb {
     > {
         tokio_stream::wrappers::BroadcastStream::new(self.tip_broadcast.subscribe())
     }
+
+    /// Stream of the blocks joining the main branch, in chain order, each one
+    /// with the state of the branch it was observed on.
+    ///
+    /// This is built on top of the tip subscription: every block between the
+    /// previously seen tip and the new one is sent, so a lagging subscriber
+    /// doesn't miss blocks. When the main branch switches to another branch,
+    /// the blocks of the new branch after the fork point are sent, so a chain
+    /// length may be seen again.
+    pub fn block_subscription(&self) -> impl Stream<Item = (Arc<ExplorerBlock>, multiverse::Ref)> {
+        let mut last_tip: Option<multiverse::Ref> = None;
+        self.tip_subscription()
+            .filter_map(|tip| future::ready(tip.ok()))
+            .flat_map(move |(hash, state_ref)| {
+                let blocks = &state_ref.state().blocks;
+                let mut new_blocks = Vec::new();
+                let mut current = blocks.lookup(&hash);
+                while let Some(block) = current {
+                    let already_sent = match &last_tip {
+                        // walk back to the fork point with the last branch
+                        Some(last_tip) => last_tip
+                            .state()
+                            .chain_lengths
+                            .lookup(&block.chain_length())
+                            .map_or(false, |sent| **sent == block.id()),
+                        // only the current tip on the first update
+                        None => !new_blocks.is_empty(),
+                    };
+                    if already_sent {
+                        break;
+                    }
+                    new_blocks.push(Arc::clone(block));
+                    current = blocks.lookup(&block.parent_hash);
+                }
+
+                last_tip = Some(state_ref.clone());
+
+                new_blocks.reverse();
+                stream::iter(
+                    new_blocks
+                        .into_iter()
+                        .map(move |block| (block, state_ref.clone())),
+                )
+            })
+    }
 }
 
 fn apply_block_to_transactions(
@@ -602,6 +970,52 @@ fn apply_block_to_stake_pools(
     Ok((data, blocks))
 }
 
+fn insert_private_vote(
+    vote_plans: VotePlans,
+    vote_cast: &VoteCast,
+    voter: ExplorerAddress,
+    vote: ExplorerVote,
+) -> VotePlans {
+    vote_plans
+        .update(vote_cast.vote_plan(), |vote_plan| {
+            let mut proposals = vote_plan.proposals.clone();
+            proposals[vote_cast.proposal_index() as usize].votes = proposals
+                [vote_cast.proposal_index() as usize]
+                .votes
+                .insert_or_update(voter, Arc::new(vote.clone()), |_| {
+                    Ok::<_, std::convert::Infallible>(Some(Arc::new(vote.clone())))
+                })
+                .unwrap();
+
+            let vote_plan = ExplorerVotePlan {
+                proposals,
+                ..(**vote_plan).clone()
+            };
+            Ok::<_, std::convert::Infallible>(Some(Arc::new(vote_plan)))
+        })
+        .unwrap()
+}
+
+fn record_vote_cast(
+    vote_plans: VotePlans,
+    vote_cast: &VoteCast,
+    history_entry: ExplorerVoteCast,
+) -> VotePlans {
+    vote_plans
+        .update(vote_cast.vote_plan(), |vote_plan| {
+            let mut proposals = vote_plan.proposals.clone();
+            let proposal = &mut proposals[vote_cast.proposal_index() as usize];
+            proposal.vote_history = proposal.vote_history.append(history_entry.clone());
+
+            let vote_plan = ExplorerVotePlan {
+                proposals,
+                ..(**vote_plan).clone()
+            };
+            Ok::<_, std::convert::Infallible>(Some(Arc::new(vote_plan)))
+        })
+        .unwrap()
+}
+
 #[tracing::instrument]
 fn apply_block_to_vote_plans(
     mut vote_plans: VotePlans,
@@ -620,6 +1034,7 @@ fn apply_block_to_vote_plans(
                             vote_end: vote_plan.vote_end(),
                             committee_end: vote_plan.committee_end(),
                             payload_type: vote_plan.payload_type(),
+                            voting_token: vote_plan.voting_token().clone(),
                             proposals: vote_plan
                                 .proposals()
                                 .iter()
@@ -628,6 +1043,7 @@ fn apply_block_to_vote_plans(
                                     options: proposal.options().clone(),
                                     tally: None,
                                     votes: Default::default(),
+                                    vote_history: Default::default(),
                                 })
                                 .collect(),
                         }),
@@ -636,7 +1052,12 @@ fn apply_block_to_vote_plans(
                 Certificate::VoteCast(vote_cast) => {
                     use chain_impl_mockchain::vote::Payload;
                     let voter = tx.inputs[0].address.clone();
-                    match vote_cast.payload() {
+                    let history_entry = ExplorerVoteCast {
+                        voter: voter.clone(),
+                        date: block.date(),
+                        stake: voter_stake(&voter, stake),
+                    };
+                    let vote_plans = match vote_cast.payload() {
                         Payload::Public { choice } => vote_plans
                             .update(vote_cast.vote_plan(), |vote_plan| {
                                 let mut proposals = vote_plan.proposals.clone();
@@ -668,37 +1089,29 @@ fn apply_block_to_vote_plans(
                         Payload::Private {
                             proof,
                             encrypted_vote,
-                        } => vote_plans
-                            .update(vote_cast.vote_plan(), |vote_plan| {
-                                let mut proposals = vote_plan.proposals.clone();
-                                proposals[vote_cast.proposal_index() as usize].votes = proposals
-                                    [vote_cast.proposal_index() as usize]
-                                    .votes
-                                    .insert_or_update(
-                                        voter,
-                                        Arc::new(ExplorerVote::Private {
-                                            proof: proof.clone(),
-                                            encrypted_vote: encrypted_vote.clone(),
-                                        }),
-                                        |_| {
-                                            Ok::<_, std::convert::Infallible>(Some(Arc::new(
-                                                ExplorerVote::Private {
-                                                    proof: proof.clone(),
-                                                    encrypted_vote: encrypted_vote.clone(),
-                                                },
-                                            )))
-                                        },
-                                    )
-                                    .unwrap();
-
-                                let vote_plan = ExplorerVotePlan {
-                                    proposals,
-                                    ..(**vote_plan).clone()
-                                };
-                                Ok::<_, std::convert::Infallible>(Some(Arc::new(vote_plan)))
-                            })
-                            .unwrap(),
-                    }
+                        } => insert_private_vote(
+                            vote_plans,
+                            vote_cast,
+                            voter,
+                            ExplorerVote::Private {
+                                proof: proof.clone(),
+                                encrypted_vote: encrypted_vote.clone(),
+                            },
+                        ),
+                        Payload::PrivateWeighted {
+                            proof,
+                            encrypted_vote,
+                        } => insert_private_vote(
+                            vote_plans,
+                            vote_cast,
+                            voter,
+                            ExplorerVote::PrivateWeighted {
+                                proof: proof.clone(),
+                                encrypted_vote: encrypted_vote.clone(),
+                            },
+                        ),
+                    };
+                    record_vote_cast(vote_plans, vote_cast, history_entry)
                 }
                 Certificate::VoteTally(vote_tally) => vote_plans
                     .update(vote_tally.id(), |vote_plan| {
@@ -713,7 +1126,7 @@ fn apply_block_to_vote_plans(
                                     proposal
                                 })
                                 .collect(),
-                            PayloadType::Private => {
+                            PayloadType::Private | PayloadType::PrivateWeighted => {
                                 if let Some(decrypted_tally) = vote_tally.tally_decrypted() {
                                     vote_plan
                                         .proposals
@@ -795,12 +1208,26 @@ fn apply_block_to_stake_control(
     stake_control
 }
 
+fn initial_params(block0: &Block) -> &ConfigParams {
+    block0
+        .contents()
+        .iter()
+        .find_map(|fragment| match fragment {
+            Fragment::Initial(config_params) => Some(config_params),
+            _ => None,
+        })
+        .expect("the Initial fragment to be present in the genesis block")
+}
+
 impl BlockchainConfig {
     fn from_config_params(params: &ConfigParams) -> BlockchainConfig {
         let mut discrimination: Option<Discrimination> = None;
         let mut consensus_version: Option<ConsensusVersion> = None;
         let mut fees: Option<LinearFee> = None;
         let mut epoch_stability_depth: Option<u32> = None;
+        let mut block0_date: Option<u64> = None;
+        let mut slots_per_epoch: Option<u32> = None;
+        let mut slot_duration: Option<u8> = None;
 
         for p in params.iter() {
             match p {
@@ -816,6 +1243,15 @@ impl BlockchainConfig {
                 ConfigParam::EpochStabilityDepth(d) => {
                     epoch_stability_depth.replace(*d);
                 }
+                ConfigParam::Block0Date(d) => {
+                    block0_date.replace(d.0);
+                }
+                ConfigParam::SlotsPerEpoch(n) => {
+                    slots_per_epoch.replace(*n);
+                }
+                ConfigParam::SlotDuration(d) => {
+                    slot_duration.replace(*d);
+                }
                 _ => (),
             }
         }
@@ -827,6 +1263,9 @@ impl BlockchainConfig {
             fees: fees.expect("fees not found in initial params"),
             epoch_stability_depth: epoch_stability_depth
                 .expect("epoch stability depth not found in initial params"),
+            block0_date: block0_date.expect("block0 date not found in initial params"),
+            slots_per_epoch: slots_per_epoch.expect("slots per epoch not found in initial params"),
+            slot_duration: slot_duration.expect("slot duration not found in initial params"),
         }
     }
 }
@@ -842,6 +1281,10 @@ impl Tip {
 }
 
 impl State {
+    pub fn get_vote_plan(&self, id: &VotePlanId) -> Option<Arc<ExplorerVotePlan>> {
+        self.vote_plans.lookup(id).cloned()
+    }
+
     pub fn get_vote_plans(&self) -> Vec<(VotePlanId, Arc<ExplorerVotePlan>)> {
         self.vote_plans
             .iter()
@@ -36,6 +36,10 @@ impl<T> PersistentSequence<T> {
     pub fn is_empty(&self) -> bool {
         self.len() == 0
     }
+
+    pub fn iter(&self) -> impl Iterator<Item = &Arc<T>> {
+        (0..self.len).filter_map(move |i| self.get(i))
+    }
 }
 
 impl<T> Default for PersistentSequence<T> {
@@ -0,0 +1,908 @@
+//! Binary encoding of the indexed [`State`] of a block, so the explorer can
+//! resume from the last snapshot instead of indexing the whole chain again
+//! when it restarts.
+
+use super::{
+    eras::{Era, Eras},
+    indexing::{
+        BlockProducer, EpochData, ExplorerAddress, ExplorerBlock, ExplorerInput, ExplorerOutput,
+        ExplorerTransaction, ExplorerVote, ExplorerVoteCast, ExplorerVotePlan,
+        ExplorerVoteProposal, ExplorerVoteTally, Hamt, StakePoolData,
+    },
+    persistent_sequence::PersistentSequence,
+    State,
+};
+use cardano_legacy_address::Addr as OldAddress;
+use chain_addr::Address;
+use chain_core::{
+    packer::Codec,
+    property::{Deserialize, DeserializeFromSlice, ReadError, Serialize, WriteError},
+};
+use chain_impl_mockchain::{
+    account::Identifier,
+    block::{BlockDate, ChainLength, HeaderId as HeaderHash},
+    certificate::{
+        Certificate, ExternalProposalId, PoolId, PoolRegistration, PoolRetirement, UpdateProposal,
+        VotePlanId,
+    },
+    fragment::ConfigParams,
+    key::BftLeaderId,
+    setting::Settings,
+    stake::{Stake, StakeControl},
+    tokens::identifier::TokenIdentifier,
+    update::{UpdateProposalState, UpdateState},
+    value::Value,
+    vote::{Options, Payload, PayloadType, Weight},
+};
+use jormungandr_lib::interfaces;
+use std::{
+    any::Any,
+    collections::HashMap,
+    convert::TryFrom,
+    hash::Hash,
+    ops::{Deref, DerefMut},
+    sync::Arc,
+};
+
+/// bumped every time the encoding changes, a snapshot of another version is
+/// ignored and the explorer indexes the stored blocks again
+const SNAPSHOT_VERSION: u8 = 3;
+
+/// the indexed state of the block `hash`
+pub struct Snapshot {
+    pub hash: HeaderHash,
+    pub chain_length: ChainLength,
+    pub state: State,
+}
+
+impl Snapshot {
+    pub fn serialize(&self) -> Result<Vec<u8>, WriteError> {
+        let mut codec = Writer {
+            codec: Codec::new(Vec::new()),
+            shared: HashMap::new(),
+        };
+        codec.put_u8(SNAPSHOT_VERSION)?;
+        self.hash.encode(&mut codec)?;
+        codec.put_be_u32(self.chain_length.into())?;
+        self.state.encode(&mut codec)?;
+        Ok(codec.codec.into_inner())
+    }
+
+    /// returns `None` if the snapshot was written by another version of the
+    /// explorer
+    pub fn deserialize(bytes: &[u8]) -> Result<Option<Self>, ReadError> {
+        let mut codec = Reader {
+            codec: Codec::new(bytes),
+            shared: Vec::new(),
+        };
+        if codec.get_u8()? != SNAPSHOT_VERSION {
+            return Ok(None);
+        }
+        let hash = HeaderHash::decode(&mut codec)?;
+        let chain_length = codec.get_be_u32()?.into();
+        let state = State::decode(&mut codec)?;
+        if codec.has_bytes_left() {
+            return Err(ReadError::UnconsumedData(codec.bytes_left()));
+        }
+        Ok(Some(Snapshot {
+            hash,
+            chain_length,
+            state,
+        }))
+    }
+}
+
+/// the encoder of a snapshot, an `Arc` shared by several parts of the state
+/// is written once and referred to by its index afterwards
+struct Writer {
+    codec: Codec<Vec<u8>>,
+    /// index of every `Arc` already written, by address
+    shared: HashMap<usize, u64>,
+}
+
+/// the decoder of a snapshot, the `Arc`s decoded so far are kept to share
+/// them again where the state refers to them by index
+struct Reader<'a> {
+    codec: Codec<&'a [u8]>,
+    shared: Vec<Arc<dyn Any + Send + Sync>>,
+}
+
+impl Deref for Writer {
+    type Target = Codec<Vec<u8>>;
+
+    fn deref(&self) -> &Self::Target {
+        &self.codec
+    }
+}
+
+impl DerefMut for Writer {
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        &mut self.codec
+    }
+}
+
+impl<'a> Deref for Reader<'a> {
+    type Target = Codec<&'a [u8]>;
+
+    fn deref(&self) -> &Self::Target {
+        &self.codec
+    }
+}
+
+impl<'a> DerefMut for Reader<'a> {
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        &mut self.codec
+    }
+}
+
+trait Encode {
+    fn encode(&self, codec: &mut Writer) -> Result<(), WriteError>;
+}
+
+trait Decode: Sized {
+    fn decode(codec: &mut Reader) -> Result<Self, ReadError>;
+}
+
+fn invalid(what: &str) -> ReadError {
+    ReadError::StructureInvalid(format!("invalid {} in the explorer snapshot", what))
+}
+
+/* containers *************************************************************** */
+
+fn encode_len(len: usize, codec: &mut Writer) -> Result<(), WriteError> {
+    codec.put_be_u64(len as u64)
+}
+
+fn decode_len(codec: &mut Reader) -> Result<usize, ReadError> {
+    let len = codec.get_be_u64()?;
+    // every element is at least one byte long
+    if len > codec.bytes_left() as u64 {
+        return Err(invalid("length"));
+    }
+    Ok(len as usize)
+}
+
+const ARC_SHARED: u8 = 0;
+const ARC_NEW: u8 = 1;
+
+impl<T: Encode> Encode for Arc<T> {
+    fn encode(&self, codec: &mut Writer) -> Result<(), WriteError> {
+        let address = Arc::as_ptr(self) as usize;
+        if let Some(index) = codec.shared.get(&address).copied() {
+            codec.put_u8(ARC_SHARED)?;
+            return codec.put_be_u64(index);
+        }
+        let index = codec.shared.len() as u64;
+        codec.shared.insert(address, index);
+        codec.put_u8(ARC_NEW)?;
+        T::encode(self, codec)
+    }
+}
+
+impl<T: Decode + Send + Sync + 'static> Decode for Arc<T> {
+    fn decode(codec: &mut Reader) -> Result<Self, ReadError> {
+        match codec.get_u8()? {
+            ARC_SHARED => {
+                let index = codec.get_be_u64()?;
+                let shared = codec
+                    .shared
+                    .get(index as usize)
+                    .ok_or_else(|| invalid("shared value index"))?;
+                Arc::clone(shared)
+                    .downcast::<T>()
+                    .map_err(|_| invalid("shared value type"))
+            }
+            ARC_NEW => {
+                // the index is taken before decoding the nested values, as
+                // when encoding
+                let index = codec.shared.len();
+                codec.shared.push(Arc::new(()));
+                let value = Arc::new(T::decode(codec)?);
+                codec.shared[index] = Arc::clone(&value) as Arc<dyn Any + Send + Sync>;
+                Ok(value)
+            }
+            tag => Err(ReadError::UnknownTag(tag as u32)),
+        }
+    }
+}
+
+impl<T: Encode> Encode for Option<T> {
+    fn encode(&self, codec: &mut Writer) -> Result<(), WriteError> {
+        match self {
+            None => codec.put_u8(0),
+            Some(value) => {
+                codec.put_u8(1)?;
+                value.encode(codec)
+            }
+        }
+    }
+}
+
+impl<T: Decode> Decode for Option<T> {
+    fn decode(codec: &mut Reader) -> Result<Self, ReadError> {
+        match codec.get_u8()? {
+            0 => Ok(None),
+            1 => T::decode(codec).map(Some),
+            tag => Err(ReadError::UnknownTag(tag as u32)),
+        }
+    }
+}
+
+impl<T: Encode> Encode for Vec<T> {
+    fn encode(&self, codec: &mut Writer) -> Result<(), WriteError> {
+        encode_len(self.len(), codec)?;
+        self.iter().try_for_each(|value| value.encode(codec))
+    }
+}
+
+impl<T: Decode> Decode for Vec<T> {
+    fn decode(codec: &mut Reader) -> Result<Self, ReadError> {
+        let len = decode_len(codec)?;
+        (0..len).map(|_| T::decode(codec)).collect()
+    }
+}
+
+impl<K: Encode, V: Encode> Encode for HashMap<K, V> {
+    fn encode(&self, codec: &mut Writer) -> Result<(), WriteError> {
+        encode_len(self.len(), codec)?;
+        self.iter().try_for_each(|(key, value)| {
+            key.encode(codec)?;
+            value.encode(codec)
+        })
+    }
+}
+
+impl<K: Decode + Eq + Hash, V: Decode> Decode for HashMap<K, V> {
+    fn decode(codec: &mut Reader) -> Result<Self, ReadError> {
+        let len = decode_len(codec)?;
+        (0..len)
+            .map(|_| Ok((K::decode(codec)?, V::decode(codec)?)))
+            .collect()
+    }
+}
+
+impl<K: Encode + Clone + Eq + Hash, V: Encode> Encode for Hamt<K, V> {
+    fn encode(&self, codec: &mut Writer) -> Result<(), WriteError> {
+        encode_len(self.size(), codec)?;
+        self.iter().try_for_each(|(key, value)| {
+            key.encode(codec)?;
+            value.encode(codec)
+        })
+    }
+}
+
+impl<K: Decode + Clone + Eq + Hash, V: Decode + Send + Sync + 'static> Decode for Hamt<K, V> {
+    fn decode(codec: &mut Reader) -> Result<Self, ReadError> {
+        let len = decode_len(codec)?;
+        let mut hamt = Hamt::new();
+        for _ in 0..len {
+            let key = K::decode(codec)?;
+            let value = Arc::<V>::decode(codec)?;
+            hamt = hamt
+                .insert(key, value)
+                .map_err(|_| invalid("duplicated key"))?;
+        }
+        Ok(hamt)
+    }
+}
+
+impl<T: Encode> Encode for PersistentSequence<T> {
+    fn encode(&self, codec: &mut Writer) -> Result<(), WriteError> {
+        encode_len(self.len() as usize, codec)?;
+        self.iter().try_for_each(|value| T::encode(value, codec))
+    }
+}
+
+impl<T: Decode> Decode for PersistentSequence<T> {
+    fn decode(codec: &mut Reader) -> Result<Self, ReadError> {
+        let len = decode_len(codec)?;
+        (0..len).try_fold(PersistentSequence::new(), |sequence, _| {
+            T::decode(codec).map(|value| sequence.append(value))
+        })
+    }
+}
+
+/* chain types ************************************************************** */
+
+/// implement the encoding with the chain's own serialization
+macro_rules! impl_with_chain_serialization {
+    ($($ty:ty),*) => {
+        $(
+            impl Encode for $ty {
+                fn encode(&self, codec: &mut Writer) -> Result<(), WriteError> {
+                    Serialize::serialize(self, &mut **codec)
+                }
+            }
+
+            impl Decode for $ty {
+                fn decode(codec: &mut Reader) -> Result<Self, ReadError> {
+                    DeserializeFromSlice::deserialize_from_slice(&mut **codec)
+                }
+            }
+        )*
+    };
+}
+
+impl_with_chain_serialization!(
+    HeaderHash,
+    Value,
+    Address,
+    Identifier,
+    BftLeaderId,
+    ConfigParams,
+    PoolRegistration,
+    PoolRetirement,
+    Payload,
+    UpdateProposal
+);
+
+/// implement the encoding of the 32 bytes identifiers
+macro_rules! impl_digest {
+    ($($ty:ty),*) => {
+        $(
+            impl Encode for $ty {
+                fn encode(&self, codec: &mut Writer) -> Result<(), WriteError> {
+                    codec.put_bytes(self.as_ref())
+                }
+            }
+
+            impl Decode for $ty {
+                fn decode(codec: &mut Reader) -> Result<Self, ReadError> {
+                    <[u8; 32]>::deserialize(&mut **codec).map(Into::into)
+                }
+            }
+        )*
+    };
+}
+
+impl_digest!(PoolId, VotePlanId, ExternalProposalId);
+
+impl Encode for u32 {
+    fn encode(&self, codec: &mut Writer) -> Result<(), WriteError> {
+        codec.put_be_u32(*self)
+    }
+}
+
+impl Decode for u32 {
+    fn decode(codec: &mut Reader) -> Result<Self, ReadError> {
+        codec.get_be_u32()
+    }
+}
+
+impl Encode for ChainLength {
+    fn encode(&self, codec: &mut Writer) -> Result<(), WriteError> {
+        codec.put_be_u32((*self).into())
+    }
+}
+
+impl Decode for ChainLength {
+    fn decode(codec: &mut Reader) -> Result<Self, ReadError> {
+        codec.get_be_u32().map(Into::into)
+    }
+}
+
+impl Encode for BlockDate {
+    fn encode(&self, codec: &mut Writer) -> Result<(), WriteError> {
+        codec.put_be_u32(self.epoch)?;
+        codec.put_be_u32(self.slot_id)
+    }
+}
+
+impl Decode for BlockDate {
+    fn decode(codec: &mut Reader) -> Result<Self, ReadError> {
+        Ok(BlockDate {
+            epoch: codec.get_be_u32()?,
+            slot_id: codec.get_be_u32()?,
+        })
+    }
+}
+
+/// certificates are encoded like in the REST API of the node
+impl Encode for Certificate {
+    fn encode(&self, codec: &mut Writer) -> Result<(), WriteError> {
+        Serialize::serialize(&interfaces::Certificate::from(self.clone()), &mut **codec)
+    }
+}
+
+impl Decode for Certificate {
+    fn decode(codec: &mut Reader) -> Result<Self, ReadError> {
+        interfaces::Certificate::deserialize_from_slice(&mut **codec).map(Into::into)
+    }
+}
+
+impl Encode for TokenIdentifier {
+    fn encode(&self, codec: &mut Writer) -> Result<(), WriteError> {
+        codec.put_bytes(&self.bytes())
+    }
+}
+
+impl Decode for TokenIdentifier {
+    fn decode(codec: &mut Reader) -> Result<Self, ReadError> {
+        TokenIdentifier::deserialize(&mut **codec)
+    }
+}
+
+impl Encode for PayloadType {
+    fn encode(&self, codec: &mut Writer) -> Result<(), WriteError> {
+        codec.put_u8(*self as u8)
+    }
+}
+
+impl Decode for PayloadType {
+    fn decode(codec: &mut Reader) -> Result<Self, ReadError> {
+        PayloadType::try_from(codec.get_u8()?).map_err(|_| invalid("payload type"))
+    }
+}
+
+impl Encode for Options {
+    fn encode(&self, codec: &mut Writer) -> Result<(), WriteError> {
+        codec.put_u8(self.choice_range().end)
+    }
+}
+
+impl Decode for Options {
+    fn decode(codec: &mut Reader) -> Result<Self, ReadError> {
+        Options::new_length(codec.get_u8()?).map_err(|_| invalid("vote options"))
+    }
+}
+
+impl Encode for Weight {
+    fn encode(&self, codec: &mut Writer) -> Result<(), WriteError> {
+        codec.put_be_u64((*self).into())
+    }
+}
+
+impl Decode for Weight {
+    fn decode(codec: &mut Reader) -> Result<Self, ReadError> {
+        codec.get_be_u64().map(Into::into)
+    }
+}
+
+impl Encode for StakeControl {
+    fn encode(&self, codec: &mut Writer) -> Result<(), WriteError> {
+        codec.put_be_u64(self.unassigned().0)?;
+        encode_len(self.iter().count(), codec)?;
+        self.iter().try_for_each(|(identifier, stake)| {
+            identifier.encode(codec)?;
+            codec.put_be_u64(stake.0)
+        })
+    }
+}
+
+impl Decode for StakeControl {
+    fn decode(codec: &mut Reader) -> Result<Self, ReadError> {
+        let unassigned = Stake(codec.get_be_u64()?);
+        let len = decode_len(codec)?;
+        (0..len).try_fold(
+            StakeControl::new().add_unassigned(unassigned),
+            |stake_control, _| {
+                let identifier = Identifier::decode(codec)?;
+                let stake = Stake(codec.get_be_u64()?);
+                Ok(stake_control.add_to(identifier, stake))
+            },
+        )
+    }
+}
+
+/* explorer types *********************************************************** */
+
+impl Encode for State {
+    fn encode(&self, codec: &mut Writer) -> Result<(), WriteError> {
+        self.transactions.encode(codec)?;
+        self.blocks.encode(codec)?;
+        self.addresses.encode(codec)?;
+        self.epochs.encode(codec)?;
+        self.chain_lengths.encode(codec)?;
+        self.stake_pool_data.encode(codec)?;
+        self.stake_pool_blocks.encode(codec)?;
+        self.vote_plans.encode(codec)?;
+        self.stake_control.encode(codec)?;
+        self.eras.encode(codec)
+    }
+}
+
+impl Decode for State {
+    fn decode(codec: &mut Reader) -> Result<Self, ReadError> {
+        Ok(State {
+            transactions: Decode::decode(codec)?,
+            blocks: Decode::decode(codec)?,
+            addresses: Decode::decode(codec)?,
+            epochs: Decode::decode(codec)?,
+            chain_lengths: Decode::decode(codec)?,
+            stake_pool_data: Decode::decode(codec)?,
+            stake_pool_blocks: Decode::decode(codec)?,
+            vote_plans: Decode::decode(codec)?,
+            stake_control: Decode::decode(codec)?,
+            eras: Decode::decode(codec)?,
+        })
+    }
+}
+
+impl Encode for Eras {
+    fn encode(&self, codec: &mut Writer) -> Result<(), WriteError> {
+        self.slots_per_epoch.encode(codec)?;
+        self.settings.config_params().encode(codec)?;
+        let proposals = self.updates.proposals();
+        encode_len(proposals.len(), codec)?;
+        for (id, state) in proposals {
+            id.encode(codec)?;
+            state.proposal.encode(codec)?;
+            state.proposal_date.encode(codec)?;
+            let voters: Vec<_> = state
+                .votes
+                .iter()
+                .map(|(voter, ())| voter.clone())
+                .collect();
+            voters.encode(codec)?;
+        }
+        self.eras.encode(codec)
+    }
+}
+
+impl Decode for Eras {
+    fn decode(codec: &mut Reader) -> Result<Self, ReadError> {
+        let slots_per_epoch = Decode::decode(codec)?;
+        let settings = Settings::new()
+            .try_apply(&ConfigParams::decode(codec)?)
+            .map_err(|_| invalid("settings"))?;
+        let len = decode_len(codec)?;
+        let updates = (0..len)
+            .map(|_| {
+                let id = HeaderHash::decode(codec)?;
+                let proposal = UpdateProposal::decode(codec)?;
+                let proposal_date = BlockDate::decode(codec)?;
+                let votes = Vec::<BftLeaderId>::decode(codec)?
+                    .into_iter()
+                    .try_fold(imhamt::Hamt::new(), |votes, voter| votes.insert(voter, ()))
+                    .map_err(|_| invalid("duplicated update vote"))?;
+                Ok((
+                    id,
+                    UpdateProposalState {
+                        proposal,
+                        proposal_date,
+                        votes,
+                    },
+                ))
+            })
+            .collect::<Result<UpdateState, ReadError>>()?;
+        Ok(Eras {
+            slots_per_epoch,
+            settings,
+            updates,
+            eras: Decode::decode(codec)?,
+        })
+    }
+}
+
+impl Encode for Era {
+    fn encode(&self, codec: &mut Writer) -> Result<(), WriteError> {
+        self.first_epoch.encode(codec)?;
+        codec.put_be_u64(self.start_time)?;
+        codec.put_u8(self.slot_duration)
+    }
+}
+
+impl Decode for Era {
+    fn decode(codec: &mut Reader) -> Result<Self, ReadError> {
+        Ok(Era {
+            first_epoch: Decode::decode(codec)?,
+            start_time: codec.get_be_u64()?,
+            slot_duration: codec.get_u8()?,
+        })
+    }
+}
+
+impl Encode for ExplorerAddress {
+    fn encode(&self, codec: &mut Writer) -> Result<(), WriteError> {
+        match self {
+            ExplorerAddress::New(address) => {
+                codec.put_u8(0)?;
+                address.encode(codec)
+            }
+            ExplorerAddress::Old(address) => {
+                codec.put_u8(1)?;
+                encode_len(address.as_ref().len(), codec)?;
+                codec.put_bytes(address.as_ref())
+            }
+        }
+    }
+}
+
+impl Decode for ExplorerAddress {
+    fn decode(codec: &mut Reader) -> Result<Self, ReadError> {
+        match codec.get_u8()? {
+            0 => Address::decode(codec).map(ExplorerAddress::New),
+            1 => {
+                let len = decode_len(codec)?;
+                OldAddress::try_from(codec.get_slice(len)?)
+                    .map(ExplorerAddress::Old)
+                    .map_err(|_| invalid("legacy address"))
+            }
+            tag => Err(ReadError::UnknownTag(tag as u32)),
+        }
+    }
+}
+
+impl Encode for ExplorerBlock {
+    fn encode(&self, codec: &mut Writer) -> Result<(), WriteError> {
+        self.transactions.encode(codec)?;
+        self.id.encode(codec)?;
+        self.date.encode(codec)?;
+        self.chain_length.encode(codec)?;
+        self.parent_hash.encode(codec)?;
+        self.producer.encode(codec)?;
+        self.total_input.encode(codec)?;
+        self.total_output.encode(codec)
+    }
+}
+
+impl Decode for ExplorerBlock {
+    fn decode(codec: &mut Reader) -> Result<Self, ReadError> {
+        Ok(ExplorerBlock {
+            transactions: Decode::decode(codec)?,
+            id: Decode::decode(codec)?,
+            date: Decode::decode(codec)?,
+            chain_length: Decode::decode(codec)?,
+            parent_hash: Decode::decode(codec)?,
+            producer: Decode::decode(codec)?,
+            total_input: Decode::decode(codec)?,
+            total_output: Decode::decode(codec)?,
+        })
+    }
+}
+
+impl Encode for BlockProducer {
+    fn encode(&self, codec: &mut Writer) -> Result<(), WriteError> {
+        match self {
+            BlockProducer::None => codec.put_u8(0),
+            BlockProducer::StakePool(id) => {
+                codec.put_u8(1)?;
+                id.encode(codec)
+            }
+            BlockProducer::BftLeader(id) => {
+                codec.put_u8(2)?;
+                id.encode(codec)
+            }
+        }
+    }
+}
+
+impl Decode for BlockProducer {
+    fn decode(codec: &mut Reader) -> Result<Self, ReadError> {
+        match codec.get_u8()? {
+            0 => Ok(BlockProducer::None),
+            1 => Decode::decode(codec).map(BlockProducer::StakePool),
+            2 => Decode::decode(codec).map(BlockProducer::BftLeader),
+            tag => Err(ReadError::UnknownTag(tag as u32)),
+        }
+    }
+}
+
+impl Encode for ExplorerTransaction {
+    fn encode(&self, codec: &mut Writer) -> Result<(), WriteError> {
+        self.id.encode(codec)?;
+        self.inputs.encode(codec)?;
+        self.outputs.encode(codec)?;
+        self.certificate.encode(codec)?;
+        self.offset_in_block.encode(codec)?;
+        self.config_params.encode(codec)
+    }
+}
+
+impl Decode for ExplorerTransaction {
+    fn decode(codec: &mut Reader) -> Result<Self, ReadError> {
+        Ok(ExplorerTransaction {
+            id: Decode::decode(codec)?,
+            inputs: Decode::decode(codec)?,
+            outputs: Decode::decode(codec)?,
+            certificate: Decode::decode(codec)?,
+            offset_in_block: Decode::decode(codec)?,
+            config_params: Decode::decode(codec)?,
+        })
+    }
+}
+
+impl Encode for ExplorerInput {
+    fn encode(&self, codec: &mut Writer) -> Result<(), WriteError> {
+        self.address.encode(codec)?;
+        self.value.encode(codec)
+    }
+}
+
+impl Decode for ExplorerInput {
+    fn decode(codec: &mut Reader) -> Result<Self, ReadError> {
+        Ok(ExplorerInput {
+            address: Decode::decode(codec)?,
+            value: Decode::decode(codec)?,
+        })
+    }
+}
+
+impl Encode for ExplorerOutput {
+    fn encode(&self, codec: &mut Writer) -> Result<(), WriteError> {
+        self.address.encode(codec)?;
+        self.value.encode(codec)
+    }
+}
+
+impl Decode for ExplorerOutput {
+    fn decode(codec: &mut Reader) -> Result<Self, ReadError> {
+        Ok(ExplorerOutput {
+            address: Decode::decode(codec)?,
+            value: Decode::decode(codec)?,
+        })
+    }
+}
+
+impl Encode for EpochData {
+    fn encode(&self, codec: &mut Writer) -> Result<(), WriteError> {
+        self.first_block.encode(codec)?;
+        self.last_block.encode(codec)?;
+        self.total_blocks.encode(codec)
+    }
+}
+
+impl Decode for EpochData {
+    fn decode(codec: &mut Reader) -> Result<Self, ReadError> {
+        Ok(EpochData {
+            first_block: Decode::decode(codec)?,
+            last_block: Decode::decode(codec)?,
+            total_blocks: Decode::decode(codec)?,
+        })
+    }
+}
+
+impl Encode for StakePoolData {
+    fn encode(&self, codec: &mut Writer) -> Result<(), WriteError> {
+        self.registration.encode(codec)?;
+        self.retirement.encode(codec)
+    }
+}
+
+impl Decode for StakePoolData {
+    fn decode(codec: &mut Reader) -> Result<Self, ReadError> {
+        Ok(StakePoolData {
+            registration: Decode::decode(codec)?,
+            retirement: Decode::decode(codec)?,
+        })
+    }
+}
+
+impl Encode for ExplorerVotePlan {
+    fn encode(&self, codec: &mut Writer) -> Result<(), WriteError> {
+        self.id.encode(codec)?;
+        self.vote_start.encode(codec)?;
+        self.vote_end.encode(codec)?;
+        self.committee_end.encode(codec)?;
+        self.payload_type.encode(codec)?;
+        self.voting_token.encode(codec)?;
+        self.proposals.encode(codec)
+    }
+}
+
+impl Decode for ExplorerVotePlan {
+    fn decode(codec: &mut Reader) -> Result<Self, ReadError> {
+        Ok(ExplorerVotePlan {
+            id: Decode::decode(codec)?,
+            vote_start: Decode::decode(codec)?,
+            vote_end: Decode::decode(codec)?,
+            committee_end: Decode::decode(codec)?,
+            payload_type: Decode::decode(codec)?,
+            voting_token: Decode::decode(codec)?,
+            proposals: Decode::decode(codec)?,
+        })
+    }
+}
+
+impl Encode for ExplorerVoteProposal {
+    fn encode(&self, codec: &mut Writer) -> Result<(), WriteError> {
+        self.proposal_id.encode(codec)?;
+        self.options.encode(codec)?;
+        self.tally.encode(codec)?;
+        self.votes.encode(codec)?;
+        self.vote_history.encode(codec)
+    }
+}
+
+impl Decode for ExplorerVoteProposal {
+    fn decode(codec: &mut Reader) -> Result<Self, ReadError> {
+        Ok(ExplorerVoteProposal {
+            proposal_id: Decode::decode(codec)?,
+            options: Decode::decode(codec)?,
+            tally: Decode::decode(codec)?,
+            votes: Decode::decode(codec)?,
+            vote_history: Decode::decode(codec)?,
+        })
+    }
+}
+
+/// the votes are encoded as the payload of the vote cast they come from
+impl Encode for ExplorerVote {
+    fn encode(&self, codec: &mut Writer) -> Result<(), WriteError> {
+        let payload = match self {
+            ExplorerVote::Public(choice) => Payload::public(*choice),
+            ExplorerVote::Private {
+                proof,
+                encrypted_vote,
+            } => Payload::private(encrypted_vote.clone(), proof.clone()),
+            ExplorerVote::PrivateWeighted {
+                proof,
+                encrypted_vote,
+            } => Payload::private_weighted(encrypted_vote.clone(), proof.clone()),
+        };
+        payload.encode(codec)
+    }
+}
+
+impl Decode for ExplorerVote {
+    fn decode(codec: &mut Reader) -> Result<Self, ReadError> {
+        Ok(match Payload::decode(codec)? {
+            Payload::Public { choice } => ExplorerVote::Public(choice),
+            Payload::Private {
+                encrypted_vote,
+                proof,
+            } => ExplorerVote::Private {
+                proof,
+                encrypted_vote,
+            },
+            Payload::PrivateWeighted {
+                encrypted_vote,
+                proof,
+            } => ExplorerVote::PrivateWeighted {
+                proof,
+                encrypted_vote,
+            },
+        })
+    }
+}
+
+impl Encode for ExplorerVoteCast {
+    fn encode(&self, codec: &mut Writer) -> Result<(), WriteError> {
+        self.voter.encode(codec)?;
+        self.date.encode(codec)?;
+        self.stake.encode(codec)
+    }
+}
+
+impl Decode for ExplorerVoteCast {
+    fn decode(codec: &mut Reader) -> Result<Self, ReadError> {
+        Ok(ExplorerVoteCast {
+            voter: Decode::decode(codec)?,
+            date: Decode::decode(codec)?,
+            stake: Decode::decode(codec)?,
+        })
+    }
+}
+
+impl Encode for ExplorerVoteTally {
+    fn encode(&self, codec: &mut Writer) -> Result<(), WriteError> {
+        match self {
+            ExplorerVoteTally::Public { results, options } => {
+                codec.put_u8(0)?;
+                results.to_vec().encode(codec)?;
+                options.encode(codec)
+            }
+            ExplorerVoteTally::Private { results, options } => {
+                codec.put_u8(1)?;
+                results.encode(codec)?;
+                options.encode(codec)
+            }
+        }
+    }
+}
+
+impl Decode for ExplorerVoteTally {
+    fn decode(codec: &mut Reader) -> Result<Self, ReadError> {
+        match codec.get_u8()? {
+            0 => Ok(ExplorerVoteTally::Public {
+                results: Vec::<Weight>::decode(codec)?.into_boxed_slice(),
+                options: Decode::decode(codec)?,
+            }),
+            1 => Ok(ExplorerVoteTally::Private {
+                results: Decode::decode(codec)?,
+                options: Decode::decode(codec)?,
+            }),
+            tag => Err(ReadError::UnknownTag(tag as u32)),
+        }
+    }
+}
@@ -0,0 +1,259 @@
+use super::snapshot::Snapshot;
+use chain_core::{
+    packer::Codec,
+    property::{Deserialize, ReadError, Serialize, WriteError},
+};
+use chain_impl_mockchain::block::{Block, ChainLength, HeaderId as HeaderHash};
+use chain_storage::{BlockInfo, BlockStore, Error as BlockStoreError};
+use std::{
+    fs,
+    io::{self, Write},
+    path::{Path, PathBuf},
+};
+use thiserror::Error;
+
+/// tag of the block that was the explorer's tip when it was last updated
+const TIP_TAG: &str = "tip";
+const MINIMUM_BLOCKS_TO_FLUSH: usize = 256;
+/// name of the file of the snapshot of the indexed state, in the storage
+/// directory
+pub(super) const SNAPSHOT_FILE: &str = "state";
+
+#[derive(Debug, Error)]
+pub enum StorageError {
+    #[error("database backend error")]
+    BackendError(#[from] BlockStoreError),
+    #[error("deserialization error")]
+    Deserialize(#[source] ReadError),
+    #[error("serialization error")]
+    Serialize(#[source] WriteError),
+    #[error("cannot access the snapshot of the indexed state")]
+    Snapshot(#[source] io::Error),
+}
+
+/// On-disk copy of the blocks indexed by the explorer, along with a snapshot
+/// of the indexed state of a stable block.
+///
+/// The blocks below the stability depth are moved to the permanent store and
+/// the branches that forked before them are pruned, the other branches are
+/// kept until they are settled.
+#[derive(Clone)]
+pub struct Storage {
+    storage: BlockStore,
+    snapshot_path: PathBuf,
+}
+
+impl Storage {
+    pub fn file<P: AsRef<Path>>(path: P) -> Result<Self, StorageError> {
+        let storage = BlockStore::file(&path, HeaderHash::zero_hash().as_bytes().to_vec())?;
+        Ok(Storage {
+            storage,
+            snapshot_path: path.as_ref().join(SNAPSHOT_FILE),
+        })
+    }
+
+    /// write the block to the storage, nothing is done if it is already
+    /// present
+    pub fn put_block(&self, block: &Block) -> Result<(), StorageError> {
+        let id = block
+            .header()
+            .hash()
+            .serialize_as_vec()
+            .map_err(StorageError::Serialize)?;
+        let parent_id = block
+            .header()
+            .block_parent_hash()
+            .serialize_as_vec()
+            .map_err(StorageError::Serialize)?;
+        let chain_length = block.header().chain_length().into();
+        let block_info = BlockInfo::new(id, parent_id, chain_length);
+        match self.storage.put_block(
+            &block.serialize_as_vec().map_err(StorageError::Serialize)?[..],
+            block_info,
+        ) {
+            Ok(()) | Err(BlockStoreError::BlockAlreadyPresent) => Ok(()),
+            Err(e) => Err(e.into()),
+        }
+    }
+
+    pub fn get(&self, header_hash: HeaderHash) -> Result<Option<Block>, StorageError> {
+        match self.storage.get_block(header_hash.as_bytes()) {
+            Ok(block) => deserialize_block(block.as_ref()).map(Some),
+            Err(BlockStoreError::BlockNotFound) => Ok(None),
+            Err(e) => Err(e.into()),
+        }
+    }
+
+    pub fn get_block0(&self) -> Result<Option<Block>, StorageError> {
+        self.storage
+            .get_blocks_by_chain_length(0)?
+            .first()
+            .map(|block| deserialize_block(block.as_ref()))
+            .transpose()
+    }
+
+    pub fn get_parent(&self, header_hash: HeaderHash) -> Result<Option<HeaderHash>, StorageError> {
+        match self.storage.get_block_info(header_hash.as_bytes()) {
+            Ok(block_info) => deserialize_hash(block_info.parent_id().as_ref()).map(Some),
+            Err(BlockStoreError::BlockNotFound) => Ok(None),
+            Err(e) => Err(e.into()),
+        }
+    }
+
+    pub fn get_chain_length(
+        &self,
+        header_hash: HeaderHash,
+    ) -> Result<Option<ChainLength>, StorageError> {
+        match self.storage.get_block_info(header_hash.as_bytes()) {
+            Ok(block_info) => Ok(Some(block_info.chain_length().into())),
+            Err(BlockStoreError::BlockNotFound) => Ok(None),
+            Err(e) => Err(e.into()),
+        }
+    }
+
+    /// check that `ancestor` is in the branch ending at `tip`
+    pub fn is_ancestor(&self, ancestor: HeaderHash, tip: HeaderHash) -> Result<bool, StorageError> {
+        let (ancestor_length, tip_length) = match (
+            self.get_chain_length(ancestor)?,
+            self.get_chain_length(tip)?,
+        ) {
+            (Some(ancestor_length), Some(tip_length)) => (ancestor_length, tip_length),
+            _ => return Ok(false),
+        };
+        let distance = match u32::from(tip_length).checked_sub(ancestor_length.into()) {
+            Some(distance) => distance,
+            None => return Ok(false),
+        };
+        let block_info = self.storage.get_nth_ancestor(tip.as_bytes(), distance)?;
+        Ok(block_info.id().as_ref() == ancestor.as_bytes())
+    }
+
+    pub fn get_tip(&self) -> Result<Option<HeaderHash>, StorageError> {
+        self.storage
+            .get_tag(TIP_TAG)?
+            .map(|block_id| deserialize_hash(block_id.as_ref()))
+            .transpose()
+    }
+
+    pub fn put_tip(&self, header_hash: HeaderHash) -> Result<(), StorageError> {
+        self.storage
+            .put_tag(TIP_TAG, header_hash.as_bytes())
+            .map_err(Into::into)
+    }
+
+    pub fn get_branches(&self) -> Result<Vec<HeaderHash>, StorageError> {
+        self.storage
+            .get_tips_ids()?
+            .into_iter()
+            .map(|branch| deserialize_hash(branch.as_ref()))
+            .collect()
+    }
+
+    /// iterate over the blocks of the branch ending at `to`, from its block
+    /// following the chain length `from` up to `to`
+    pub fn iter_branch(
+        &self,
+        from: ChainLength,
+        to: HeaderHash,
+    ) -> Result<impl Iterator<Item = Result<Block, StorageError>>, StorageError> {
+        let distance = self
+            .storage
+            .get_block_info(to.as_bytes())?
+            .chain_length()
+            .saturating_sub(from.into());
+        // the underlying iterator cannot be empty
+        let raw_blocks = if distance == 0 {
+            None
+        } else {
+            Some(self.storage.iter(to.as_bytes(), distance)?)
+        };
+        Ok(raw_blocks.into_iter().flatten().map(|raw_block| {
+            raw_block
+                .map_err(Into::into)
+                .and_then(|raw_block| deserialize_block(raw_block.as_ref()))
+        }))
+    }
+
+    /// prune the branches that forked before the stability depth of the main
+    /// branch, and move the stable blocks of the main branch to the permanent
+    /// store
+    pub fn gc(
+        &self,
+        threshold_depth: u32,
+        main_branch_tip: HeaderHash,
+    ) -> Result<(), StorageError> {
+        let main_info = self.storage.get_block_info(main_branch_tip.as_bytes())?;
+        let threshold_length = match main_info.chain_length().checked_sub(threshold_depth) {
+            Some(result) => result,
+            None => return Ok(()),
+        };
+
+        for id in self.storage.get_tips_ids()? {
+            let info = self.storage.get_block_info(id.as_ref())?;
+
+            if info.chain_length() > threshold_length {
+                continue;
+            }
+
+            self.storage.prune_branch(id.as_ref())?;
+
+            tracing::debug!(
+                "removed branch with head {}",
+                deserialize_hash(id.as_ref())?
+            );
+        }
+
+        let to_block_info = self
+            .storage
+            .get_nth_ancestor(main_branch_tip.as_bytes(), threshold_depth)?;
+        let blocks_flushed = self
+            .storage
+            .flush_to_permanent_store(to_block_info.id().as_ref(), MINIMUM_BLOCKS_TO_FLUSH)?;
+
+        tracing::debug!("flushed {} blocks to the permanent store", blocks_flushed);
+
+        Ok(())
+    }
+
+    /// read the snapshot of the indexed state, `None` if there is none or if
+    /// it was written by another version of the explorer
+    pub fn get_snapshot(&self) -> Result<Option<Snapshot>, StorageError> {
+        let bytes = match fs::read(&self.snapshot_path) {
+            Ok(bytes) => bytes,
+            Err(error) if error.kind() == io::ErrorKind::NotFound => return Ok(None),
+            Err(error) => return Err(StorageError::Snapshot(error)),
+        };
+        Snapshot::deserialize(&bytes).map_err(StorageError::Deserialize)
+    }
+
+    /// replace the snapshot of the indexed state, the previous snapshot is
+    /// kept if writing the new one fails midway
+    pub fn put_snapshot(&self, snapshot: &Snapshot) -> Result<(), StorageError> {
+        let bytes = snapshot.serialize().map_err(StorageError::Serialize)?;
+        let tmp_path = self.snapshot_path.with_extension("tmp");
+        let mut file = fs::File::create(&tmp_path).map_err(StorageError::Snapshot)?;
+        file.write_all(&bytes)
+            .and_then(|()| file.sync_all())
+            .map_err(StorageError::Snapshot)?;
+        fs::rename(&tmp_path, &self.snapshot_path).map_err(StorageError::Snapshot)
+    }
+
+    /// remove the snapshot of the indexed state, so the next start reindexes
+    /// the blocks from the block0
+    pub fn remove_snapshot(&self) -> Result<(), StorageError> {
+        match fs::remove_file(&self.snapshot_path) {
+            Err(error) if error.kind() != io::ErrorKind::NotFound => {
+                Err(StorageError::Snapshot(error))
+            }
+            _ => Ok(()),
+        }
+    }
+}
+
+fn deserialize_block(bytes: &[u8]) -> Result<Block, StorageError> {
+    Block::deserialize(&mut Codec::new(bytes)).map_err(StorageError::Deserialize)
+}
+
+fn deserialize_hash(bytes: &[u8]) -> Result<HeaderHash, StorageError> {
+    HeaderHash::deserialize(&mut Codec::new(bytes)).map_err(StorageError::Deserialize)
+}
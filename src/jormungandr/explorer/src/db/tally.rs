@@ -26,10 +26,7 @@ pub fn compute_public_tally(
                         let index = choice.as_byte() as usize;
                         results[index] = results[index].saturating_add(stake.into());
                     }
-                    ExplorerVote::Private {
-                        proof: _,
-                        encrypted_vote: _,
-                    } => {
+                    ExplorerVote::Private { .. } | ExplorerVote::PrivateWeighted { .. } => {
                         unreachable!("internal error: found private vote when computing tally for public proposal")
                     }
                 }
@@ -0,0 +1,221 @@
+use super::{storage::Storage, ExplorerDb};
+use assert_fs::TempDir;
+use chain_addr::Discrimination;
+use chain_impl_mockchain::{
+    block::{self, Block, BlockDate, BlockVersion, ChainLength, Contents, ContentsBuilder},
+    chaintypes::ConsensusVersion,
+    config::{Block0Date, ConfigParam},
+    fee::LinearFee,
+    fragment::{ConfigParams, Fragment},
+};
+use futures::prelude::*;
+use std::sync::atomic::Ordering;
+
+fn block0(epoch_stability_depth: u32) -> Block {
+    let mut params = ConfigParams::new();
+    params.push(ConfigParam::Discrimination(Discrimination::Test));
+    params.push(ConfigParam::ConsensusVersion(ConsensusVersion::Bft));
+    params.push(ConfigParam::LinearFee(LinearFee::new(0, 0, 0)));
+    params.push(ConfigParam::EpochStabilityDepth(epoch_stability_depth));
+    params.push(ConfigParam::Block0Date(Block0Date(0)));
+    params.push(ConfigParam::SlotsPerEpoch(100));
+    params.push(ConfigParam::SlotDuration(1));
+
+    let mut contents = ContentsBuilder::new();
+    contents.push(Fragment::Initial(params));
+    block::builder(BlockVersion::Genesis, contents.into(), |header| {
+        Ok::<_, ()>(
+            header
+                .set_genesis()
+                .set_date(BlockDate::first())
+                .into_unsigned_header()
+                .unwrap()
+                .generalize(),
+        )
+    })
+    .unwrap()
+}
+
+/// an empty block on top of `parent`, the slot makes the siblings distinct
+fn child(parent: &Block, slot_id: u32) -> Block {
+    let chain_length = ChainLength::from(u32::from(parent.header().chain_length()) + 1);
+    block::builder(BlockVersion::Genesis, Contents::empty(), |header| {
+        Ok::<_, ()>(
+            header
+                .set_parent(&parent.header().hash(), chain_length)
+                .set_date(BlockDate { epoch: 0, slot_id })
+                .into_unsigned_header()
+                .unwrap()
+                .generalize(),
+        )
+    })
+    .unwrap()
+}
+
+fn branch(from: &Block, first_slot: u32, len: u32) -> Vec<Block> {
+    let mut blocks: Vec<Block> = Vec::new();
+    for i in 0..len {
+        let block = child(blocks.last().unwrap_or(from), first_slot + i);
+        blocks.push(block);
+    }
+    blocks
+}
+
+#[tokio::test]
+async fn restart_resumes_from_the_snapshot() {
+    let temp_dir = TempDir::new().unwrap();
+    let storage = Storage::file(temp_dir.path()).unwrap();
+    let block0 = block0(2);
+    let blocks = branch(&block0, 1, 6);
+
+    let db = ExplorerDb::bootstrap(block0.clone(), Some(storage.clone())).unwrap();
+    for block in &blocks {
+        assert!(db.apply_block(block.clone()).await.unwrap().is_some());
+    }
+    db.save_snapshot(blocks[2].header().hash(), blocks[2].header().chain_length())
+        .await
+        .unwrap();
+    let tip = blocks[5].header().hash();
+    db.set_tip(tip).await.unwrap();
+    drop(db);
+
+    let db = ExplorerDb::load(storage).await.unwrap().unwrap();
+
+    assert_eq!(db.get_tip().await.0, tip);
+    for block in &blocks {
+        assert!(db.get_block(&block.header().hash()).await.is_some());
+    }
+    assert_eq!(
+        db.stable_store
+            .snapshot_chain_length
+            .load(Ordering::Acquire),
+        3
+    );
+    // the blocks up to the snapshot were not indexed again
+    assert!(db
+        .multiverse
+        .get_ref(&block0.header().hash())
+        .await
+        .is_none());
+    assert!(db.apply_block(blocks[5].clone()).await.unwrap().is_none());
+}
+
+#[tokio::test]
+async fn restart_without_snapshot_indexes_from_block0() {
+    let temp_dir = TempDir::new().unwrap();
+    let storage = Storage::file(temp_dir.path()).unwrap();
+    let block0 = block0(10);
+    let blocks = branch(&block0, 1, 3);
+
+    let db = ExplorerDb::bootstrap(block0.clone(), Some(storage.clone())).unwrap();
+    for block in &blocks {
+        db.apply_block(block.clone()).await.unwrap();
+    }
+    let tip = blocks[2].header().hash();
+    db.set_tip(tip).await.unwrap();
+    drop(db);
+
+    let db = ExplorerDb::load(storage).await.unwrap().unwrap();
+
+    assert_eq!(db.get_tip().await.0, tip);
+    assert!(db
+        .multiverse
+        .get_ref(&block0.header().hash())
+        .await
+        .is_some());
+    assert!(db.get_block(&blocks[0].header().hash()).await.is_some());
+}
+
+#[tokio::test]
+async fn restart_with_corrupt_snapshot_indexes_from_block0() {
+    let temp_dir = TempDir::new().unwrap();
+    let storage = Storage::file(temp_dir.path()).unwrap();
+    let block0 = block0(2);
+    let blocks = branch(&block0, 1, 6);
+
+    let db = ExplorerDb::bootstrap(block0.clone(), Some(storage.clone())).unwrap();
+    for block in &blocks {
+        db.apply_block(block.clone()).await.unwrap();
+    }
+    db.save_snapshot(blocks[2].header().hash(), blocks[2].header().chain_length())
+        .await
+        .unwrap();
+    let tip = blocks[5].header().hash();
+    db.set_tip(tip).await.unwrap();
+    drop(db);
+
+    let snapshot_path = temp_dir.path().join(super::storage::SNAPSHOT_FILE);
+    let mut bytes = std::fs::read(&snapshot_path).unwrap();
+    bytes.truncate(bytes.len() / 2);
+    std::fs::write(&snapshot_path, bytes).unwrap();
+
+    let db = ExplorerDb::load(storage.clone()).await.unwrap().unwrap();
+
+    assert_eq!(db.get_tip().await.0, tip);
+    assert!(db
+        .multiverse
+        .get_ref(&block0.header().hash())
+        .await
+        .is_some());
+    assert!(db.get_block(&blocks[0].header().hash()).await.is_some());
+    assert!(storage.get_snapshot().unwrap().is_none());
+}
+
+#[tokio::test]
+async fn reorg_blocks_sent_again_are_skipped() {
+    let block0 = block0(10);
+    let main = branch(&block0, 1, 2);
+    // forks after the first block of the main branch
+    let fork = branch(&main[0], 50, 2);
+
+    let db = ExplorerDb::bootstrap(block0, None).unwrap();
+    for block in &main {
+        db.apply_block(block.clone()).await.unwrap();
+    }
+    db.set_tip(main[1].header().hash()).await.unwrap();
+    for block in &fork {
+        assert!(db.apply_block(block.clone()).await.unwrap().is_some());
+    }
+    let fork_tip = fork[1].header().hash();
+    db.set_tip(fork_tip).await.unwrap();
+
+    // the node sends the blocks of both branches again when the
+    // synchronization resumes
+    for block in main.iter().chain(fork.iter()) {
+        assert!(db.apply_block(block.clone()).await.unwrap().is_none());
+    }
+
+    assert_eq!(db.get_tip().await.0, fork_tip);
+    let checkpoints = db.checkpoints().await;
+    assert!(checkpoints.contains(&fork_tip));
+    assert!(checkpoints.contains(&main[1].header().hash()));
+}
+
+#[tokio::test]
+async fn block_subscription_sends_the_new_branch_on_reorg() {
+    let block0 = block0(10);
+    let main = branch(&block0, 1, 3);
+    // forks after the first block of the main branch
+    let fork = branch(&main[0], 50, 3);
+
+    let db = ExplorerDb::bootstrap(block0, None).unwrap();
+    let subscription = db.block_subscription();
+    for block in main.iter().chain(fork.iter()) {
+        db.apply_block(block.clone()).await.unwrap();
+    }
+    db.set_tip(main[0].header().hash()).await.unwrap();
+    db.set_tip(main[2].header().hash()).await.unwrap();
+    db.set_tip(fork[2].header().hash()).await.unwrap();
+
+    let sent: Vec<_> = subscription
+        .take(6)
+        .map(|(block, _)| block.id())
+        .collect()
+        .await;
+    let expected: Vec<_> = main
+        .iter()
+        .chain(fork.iter())
+        .map(|block| block.header().hash())
+        .collect();
+    assert_eq!(sent, expected);
+}
@@ -0,0 +1,328 @@
+use super::{
+    eras::Eras,
+    indexing::{ExplorerAddress, ExplorerVotePlan, ExplorerVoteProposal},
+};
+use chain_impl_mockchain::{block::BlockDate, stake::StakeControl, value::Value};
+use std::collections::{BTreeMap, HashSet};
+
+/// How the votes cast on a proposal are grouped over time
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum VoteBucketing {
+    /// one bucket per epoch
+    Epoch,
+    /// buckets of the given number of slots, restarting at the beginning of every epoch
+    Slots(u32),
+    /// buckets of the given number of seconds, starting at the date of block0
+    Seconds(u64),
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct VoteBucket {
+    /// date of the first slot of the bucket
+    pub start_date: BlockDate,
+    /// seconds since the UNIX epoch at the start of the bucket
+    pub start_time: u64,
+    pub votes: u64,
+    /// stake of the voters when they cast their votes
+    pub stake: u64,
+}
+
+/// Votes cast on the proposal, and the stake of their voters, grouped in buckets.
+/// Only the buckets with at least one vote are returned, in chronological order.
+///
+/// The votes are placed in time with the eras of the branch.
+pub fn compute_vote_time_series(
+    proposal: &ExplorerVoteProposal,
+    eras: &Eras,
+    bucketing: VoteBucketing,
+) -> Vec<VoteBucket> {
+    let mut buckets = BTreeMap::new();
+
+    for vote_cast in proposal.vote_history.iter() {
+        let (start_date, start_time) = bucket_start(vote_cast.date, eras, bucketing);
+        let bucket = buckets.entry(start_time).or_insert(VoteBucket {
+            start_date,
+            start_time,
+            votes: 0,
+            stake: 0,
+        });
+        bucket.votes += 1;
+        bucket.stake = bucket.stake.saturating_add(vote_cast.stake.0);
+    }
+
+    buckets.into_values().collect()
+}
+
+/// Number of distinct addresses that voted on at least one proposal of the given vote plans
+pub fn count_unique_voters<'a>(vote_plans: impl IntoIterator<Item = &'a ExplorerVotePlan>) -> u64 {
+    let voters: HashSet<&ExplorerAddress> = vote_plans
+        .into_iter()
+        .flat_map(|vote_plan| vote_plan.proposals.iter())
+        .flat_map(|proposal| proposal.votes.iter().map(|(voter, _)| voter))
+        .collect();
+
+    voters.len() as u64
+}
+
+/// stake of the account of the voter, zero for the other kinds of addresses
+pub(super) fn voter_stake(voter: &ExplorerAddress, stake: &StakeControl) -> Value {
+    voter
+        .to_identifier()
+        .and_then(|account_id| stake.by(&account_id))
+        .map_or_else(Value::zero, |stake| Value(stake.into()))
+}
+
+fn bucket_start(date: BlockDate, eras: &Eras, bucketing: VoteBucketing) -> (BlockDate, u64) {
+    match bucketing {
+        VoteBucketing::Epoch => {
+            let start_date = BlockDate {
+                epoch: date.epoch,
+                slot_id: 0,
+            };
+            (start_date, eras.time(start_date))
+        }
+        VoteBucketing::Slots(size) => {
+            let start_date = BlockDate {
+                epoch: date.epoch,
+                slot_id: date.slot_id - date.slot_id % size.max(1),
+            };
+            (start_date, eras.time(start_date))
+        }
+        VoteBucketing::Seconds(size) => {
+            let block0_time = eras.time(BlockDate::first());
+            let elapsed = eras.time(date) - block0_time;
+            let start_time = block0_time + elapsed - elapsed % size.max(1);
+            (eras.date_from(start_time), start_time)
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::db::{
+        eras::Era,
+        indexing::{ExplorerVote, ExplorerVoteCast, Hamt},
+        persistent_sequence::PersistentSequence,
+        BlockchainConfig,
+    };
+    use chain_addr::{Address, Discrimination, Kind};
+    use chain_impl_mockchain::{
+        chaintypes::ConsensusVersion,
+        config::{Block0Date, ConfigParam},
+        fee::LinearFee,
+        fragment::ConfigParams,
+        tokens::identifier::TokenIdentifier,
+        vote::{Choice, Options, PayloadType},
+    };
+    use std::{convert::TryInto, sync::Arc};
+
+    /// 10 slots per epoch from the date 1000, the slots last 2 seconds until
+    /// epoch 2 (at 1040) and 5 seconds from then on
+    fn eras() -> Eras {
+        let mut params = ConfigParams::new();
+        params.push(ConfigParam::Discrimination(Discrimination::Test));
+        params.push(ConfigParam::ConsensusVersion(ConsensusVersion::Bft));
+        params.push(ConfigParam::LinearFee(LinearFee::new(0, 0, 0)));
+        params.push(ConfigParam::EpochStabilityDepth(10));
+        params.push(ConfigParam::Block0Date(Block0Date(1000)));
+        params.push(ConfigParam::SlotsPerEpoch(10));
+        params.push(ConfigParam::SlotDuration(2));
+
+        let eras = Eras::new(&params, &BlockchainConfig::from_config_params(&params));
+        Eras {
+            eras: eras.eras.append(Era {
+                first_epoch: 2,
+                start_time: 1040,
+                slot_duration: 5,
+            }),
+            ..eras
+        }
+    }
+
+    fn date(epoch: u32, slot_id: u32) -> BlockDate {
+        BlockDate { epoch, slot_id }
+    }
+
+    fn voter(i: u8) -> ExplorerAddress {
+        ExplorerAddress::New(Address(Discrimination::Test, Kind::Multisig([i; 32])))
+    }
+
+    fn proposal(votes: &[(u8, BlockDate, u64)]) -> ExplorerVoteProposal {
+        let mut proposal = ExplorerVoteProposal {
+            proposal_id: [0; 32].into(),
+            options: Options::new_length(3).unwrap(),
+            tally: None,
+            votes: Hamt::new(),
+            vote_history: PersistentSequence::new(),
+        };
+        for (i, date, stake) in votes {
+            proposal.votes = proposal.votes.insert_or_update_simple(
+                voter(*i),
+                Arc::new(ExplorerVote::Public(Choice::new(1))),
+                |vote| Some(vote.clone()),
+            );
+            proposal.vote_history = proposal.vote_history.append(ExplorerVoteCast {
+                voter: voter(*i),
+                date: *date,
+                stake: Value(*stake),
+            });
+        }
+        proposal
+    }
+
+    fn vote_plan(proposals: Vec<ExplorerVoteProposal>) -> ExplorerVotePlan {
+        ExplorerVotePlan {
+            id: [0; 32].into(),
+            vote_start: date(0, 0),
+            vote_end: date(5, 0),
+            committee_end: date(6, 0),
+            payload_type: PayloadType::Public,
+            voting_token: TokenIdentifier {
+                policy_hash: [0; 28].into(),
+                token_name: Vec::new().try_into().unwrap(),
+            },
+            proposals,
+        }
+    }
+
+    #[test]
+    fn slot_buckets_restart_at_every_epoch() {
+        let eras = eras();
+        let bucketing = VoteBucketing::Slots(4);
+
+        assert_eq!(
+            bucket_start(date(0, 0), &eras, bucketing),
+            (date(0, 0), 1000)
+        );
+        assert_eq!(
+            bucket_start(date(0, 3), &eras, bucketing),
+            (date(0, 0), 1000)
+        );
+        assert_eq!(
+            bucket_start(date(0, 4), &eras, bucketing),
+            (date(0, 4), 1008)
+        );
+        // the last bucket of the epoch is shorter
+        assert_eq!(
+            bucket_start(date(0, 9), &eras, bucketing),
+            (date(0, 8), 1016)
+        );
+        assert_eq!(
+            bucket_start(date(1, 0), &eras, bucketing),
+            (date(1, 0), 1020)
+        );
+        assert_eq!(
+            bucket_start(date(2, 7), &eras, bucketing),
+            (date(2, 4), 1060)
+        );
+    }
+
+    #[test]
+    fn epoch_buckets_follow_the_eras() {
+        let eras = eras();
+        let bucketing = VoteBucketing::Epoch;
+
+        assert_eq!(
+            bucket_start(date(1, 9), &eras, bucketing),
+            (date(1, 0), 1020)
+        );
+        assert_eq!(
+            bucket_start(date(2, 0), &eras, bucketing),
+            (date(2, 0), 1040)
+        );
+        assert_eq!(
+            bucket_start(date(2, 9), &eras, bucketing),
+            (date(2, 0), 1040)
+        );
+        assert_eq!(
+            bucket_start(date(3, 0), &eras, bucketing),
+            (date(3, 0), 1090)
+        );
+    }
+
+    #[test]
+    fn second_buckets_start_at_block0() {
+        let eras = eras();
+        let bucketing = VoteBucketing::Seconds(30);
+
+        assert_eq!(
+            bucket_start(date(0, 0), &eras, bucketing),
+            (date(0, 0), 1000)
+        );
+        assert_eq!(
+            bucket_start(date(1, 4), &eras, bucketing),
+            (date(0, 0), 1000)
+        );
+        assert_eq!(
+            bucket_start(date(1, 5), &eras, bucketing),
+            (date(1, 5), 1030)
+        );
+        // across the change of slot duration
+        assert_eq!(
+            bucket_start(date(2, 3), &eras, bucketing),
+            (date(1, 5), 1030)
+        );
+        assert_eq!(
+            bucket_start(date(2, 4), &eras, bucketing),
+            (date(2, 4), 1060)
+        );
+        // the date is the first slot starting in the bucket
+        assert_eq!(
+            bucket_start(date(1, 3), &eras, VoteBucketing::Seconds(25)),
+            (date(1, 3), 1025)
+        );
+    }
+
+    #[test]
+    fn vote_time_series_sums_the_stake_at_vote_time() {
+        let eras = eras();
+        let votes = proposal(&[
+            (1, date(1, 9), 10),
+            (2, date(2, 0), 20),
+            // the second vote of a voter is counted with its new stake
+            (1, date(2, 9), 15),
+            (3, date(3, 0), 5),
+        ]);
+
+        assert_eq!(
+            compute_vote_time_series(&votes, &eras, VoteBucketing::Epoch),
+            vec![
+                VoteBucket {
+                    start_date: date(1, 0),
+                    start_time: 1020,
+                    votes: 1,
+                    stake: 10,
+                },
+                VoteBucket {
+                    start_date: date(2, 0),
+                    start_time: 1040,
+                    votes: 2,
+                    stake: 35,
+                },
+                VoteBucket {
+                    start_date: date(3, 0),
+                    start_time: 1090,
+                    votes: 1,
+                    stake: 5,
+                },
+            ]
+        );
+        assert!(compute_vote_time_series(&proposal(&[]), &eras, VoteBucketing::Epoch).is_empty());
+    }
+
+    #[test]
+    fn unique_voters_are_counted_once_across_vote_plans() {
+        let vote_plans = [
+            vote_plan(vec![
+                proposal(&[(1, date(0, 1), 1), (2, date(0, 2), 1)]),
+                proposal(&[(1, date(0, 3), 1)]),
+            ]),
+            vote_plan(vec![proposal(&[(1, date(1, 0), 1), (3, date(1, 1), 1)])]),
+        ];
+
+        assert_eq!(count_unique_voters(&vote_plans), 3);
+        assert_eq!(count_unique_voters(&vote_plans[1..]), 2);
+        assert_eq!(count_unique_voters(&[] as &[ExplorerVotePlan]), 0);
+    }
+}
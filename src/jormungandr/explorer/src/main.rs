@@ -8,10 +8,11 @@ use crate::indexer::Indexer;
 use anyhow::Context;
 use chain_core::{packer::Codec, property::Deserialize};
 use chain_impl_mockchain::block::Block;
-use chain_network::grpc::watch::client::{
-    BlockSubscription, Client, SyncMultiverseStream, TipSubscription,
+use chain_network::{
+    data::BlockId,
+    grpc::watch::client::{BlockSubscription, Client, SyncMultiverseStream, TipSubscription},
 };
-use db::ExplorerDb;
+use db::{storage::Storage, ExplorerDb};
 use futures::stream::StreamExt;
 use futures_util::{future, pin_mut, FutureExt, TryFutureExt};
 use settings::Settings;
@@ -45,6 +46,8 @@ pub enum BootstrapError {
     DbError(db::error::ExplorerError),
     #[error("empty bootstrap stream")]
     EmptyStream,
+    #[error("failed to open the explorer storage")]
+    StorageError(#[from] db::storage::StorageError),
 }
 
 #[derive(Clone)]
@@ -77,13 +80,38 @@ async fn main() -> Result<(), Error> {
     let (bootstrap, mut services) = {
         let settings = settings.take().unwrap();
 
+        let storage = settings
+            .storage
+            .as_ref()
+            .map(Storage::file)
+            .transpose()
+            .map_err(BootstrapError::StorageError)?;
+
+        let db = match storage.clone() {
+            Some(storage) => ExplorerDb::load(storage)
+                .instrument(span!(Level::INFO, "restore from storage"))
+                .await
+                .map_err(BootstrapError::DbError)?,
+            None => None,
+        };
+
+        let checkpoints: Vec<BlockId> = match &db {
+            Some(db) => db
+                .checkpoints()
+                .await
+                .iter()
+                .map(|hash| BlockId::try_from(hash.as_bytes()).unwrap())
+                .collect(),
+            None => Vec::new(),
+        };
+
         let mut client = Client::connect(settings.node.clone())
             .await
             .context("Couldn't establish connection with node")
             .map_err(Error::UnrecoverableError)?;
 
         let sync_stream = client
-            .sync_multiverse(vec![])
+            .sync_multiverse(&checkpoints)
             .await
             .context("Failed to establish bootstrap stream")
             .map_err(Error::UnrecoverableError)?;
@@ -105,7 +133,7 @@ async fn main() -> Result<(), Error> {
 
             tokio::spawn(
                 async move {
-                    let db = bootstrap(sync_stream).await?;
+                    let db = bootstrap(sync_stream, db, storage).await?;
 
                     let msg = GlobalState::Ready(Indexer::new(db));
 
@@ -210,11 +238,13 @@ async fn main() -> Result<(), Error> {
     Ok(())
 }
 
-async fn bootstrap(mut sync_stream: SyncMultiverseStream) -> Result<ExplorerDb, Error> {
+async fn bootstrap(
+    mut sync_stream: SyncMultiverseStream,
+    mut db: Option<ExplorerDb>,
+    storage: Option<Storage>,
+) -> Result<ExplorerDb, Error> {
     tracing::info!("starting bootstrap process");
 
-    let mut db: Option<ExplorerDb> = None;
-
     // TODO: technically, blocks with the same length can be applied in parallel
     // but it is simpler to do it serially for now at least
     while let Some(block) = sync_stream.next().await {
@@ -238,7 +268,9 @@ async fn bootstrap(mut sync_stream: SyncMultiverseStream) -> Result<ExplorerDb,
                 .await
                 .map_err(BootstrapError::DbError)?;
         } else {
-            db = Some(ExplorerDb::bootstrap(block).map_err(BootstrapError::DbError)?)
+            db = Some(
+                ExplorerDb::bootstrap(block, storage.clone()).map_err(BootstrapError::DbError)?,
+            )
         }
     }
 
@@ -41,6 +41,7 @@ pub enum Error {
 
 pub struct Settings {
     pub node: Uri,
+    pub storage: Option<PathBuf>,
     pub binding_address: SocketAddr,
     pub address_bech32_prefix: String,
     pub query_depth_limit: usize,
@@ -68,6 +69,8 @@ impl Settings {
             .or_else(|| file.node.clone())
             .unwrap_or_else(|| "127.0.0.1:8299".parse().unwrap());
 
+        let storage = cmd.storage.clone().or_else(|| file.storage.clone());
+
         let binding_address = cmd
             .binding_address
             .or(file.binding_address)
@@ -96,6 +99,7 @@ impl Settings {
 
         Ok(Settings {
             node,
+            storage,
             binding_address,
             address_bech32_prefix,
             query_depth_limit,
@@ -150,6 +154,10 @@ impl Settings {
 struct CommandLine {
     #[structopt(long)]
     pub node: Option<Uri>,
+    /// Directory of the on-disk storage of the indexed blocks.
+    /// If not configured anywhere, nothing is persisted.
+    #[structopt(long)]
+    pub storage: Option<PathBuf>,
     #[structopt(long)]
     pub binding_address: Option<SocketAddr>,
     #[structopt(long)]
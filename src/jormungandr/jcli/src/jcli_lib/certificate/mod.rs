@@ -1,3 +1,4 @@
+mod new_burn_token;
 #[cfg(feature = "evm")]
 mod new_evm_mapping;
 mod new_owner_stake_delegation;
@@ -7,6 +8,7 @@ mod new_stake_pool_retirement;
 mod new_update_proposal;
 mod new_update_vote;
 mod new_vote_cast;
+mod new_vote_delegation;
 mod new_vote_plan;
 mod new_vote_tally;
 mod show;
@@ -21,8 +23,11 @@ use crate::jcli_lib::utils::{
     io, key_parser,
     vote::{SharesError, VotePlanError},
 };
-use chain_impl_mockchain::{block::BlockDate, certificate::DecryptedPrivateTallyError};
-use chain_vote::UnitVectorInitializationError;
+use chain_impl_mockchain::{
+    block::BlockDate, certificate::DecryptedPrivateTallyError, tokens::name::TokenNameTooLong,
+    vote::WeightedVoteTooLarge,
+};
+use chain_vote::{UnitVectorInitializationError, WeightedVoteInitializationError};
 use jormungandr_lib::interfaces::{self, CertificateFromBech32Error, CertificateFromStrError};
 use std::{
     fmt::Display,
@@ -66,6 +71,10 @@ pub enum Error {
     OwnerStakeDelegationDoesntNeedSignature,
     #[error("mint token does not need a signature")]
     MintTokenDoesntNeedSignature,
+    #[error("burn token does not need a signature")]
+    BurnTokenDoesntNeedSignature,
+    #[error("vote delegation does not need a signature")]
+    VoteDelegationDoesntNeedSignature,
     #[error("vote plan certificate does not need a signature")]
     VotePlanDoesntNeedSignature,
     #[error("vote cast certificate does not need a signature")]
@@ -84,6 +93,12 @@ pub enum Error {
     TooManyPoolDelegations { actual: usize, max: usize },
     #[error("failed to build pool delegation")]
     InvalidPoolDelegation,
+    #[error("attempted to build vote delegation to {actual} representatives, maximum is {max}")]
+    TooManyVoteDelegations { actual: usize, max: usize },
+    #[error(
+        "failed to build vote delegation, representatives must be distinct with non zero weights"
+    )]
+    InvalidVoteDelegation,
     #[error("BlockDates should be consecutive, vote start ({vote_start}) cannot be bigger than vote end ({vote_end})")]
     InvalidVotePlanVoteBlockDates {
         vote_start: BlockDate,
@@ -122,6 +137,18 @@ pub enum Error {
     ConfigFileCorrupted(#[source] serde_yaml::Error),
     #[error(transparent)]
     InvalidChoice(#[from] UnitVectorInitializationError),
+    #[error(transparent)]
+    InvalidWeights(#[from] WeightedVoteInitializationError),
+    #[error("expected exactly one of --approval or --budget")]
+    WeightedBallotKindExpected,
+    #[error(transparent)]
+    WeightedVoteTooLarge(#[from] WeightedVoteTooLarge),
+    #[error("invalid hexadecimal token name")]
+    InvalidTokenNameHex(#[from] hex::FromHexError),
+    #[error(transparent)]
+    InvalidTokenName(#[from] TokenNameTooLong),
+    #[error("invalid minting policy configuration")]
+    MintingPolicyConfig(#[source] serde_yaml::Error),
 }
 
 #[allow(clippy::large_enum_variant)]
@@ -174,10 +201,14 @@ pub enum NewArgs {
     VoteTally(new_vote_tally::VoteTallyRegistration),
     /// create a new update vote certificate
     UpdateVote(new_update_vote::UpdateVote),
+    /// create a new token burning certificate
+    BurnToken(new_burn_token::BurnToken),
     /// create a new update proposal certificate
     UpdateProposal(new_update_proposal::UpdateProposal),
     /// create a vote cast certificate
     VoteCast(new_vote_cast::VoteCastCmd),
+    /// create a vote delegation certificate
+    VoteDelegation(new_vote_delegation::VoteDelegation),
     #[cfg(feature = "evm")]
     /// create an EVM address mapping certificate
     EvmMapping(new_evm_mapping::EvmMapCmd),
@@ -213,6 +244,8 @@ impl NewArgs {
             NewArgs::VoteCast(args) => args.exec()?,
             NewArgs::UpdateVote(args) => args.exec()?,
             NewArgs::UpdateProposal(args) => args.exec()?,
+            NewArgs::BurnToken(args) => args.exec()?,
+            NewArgs::VoteDelegation(args) => args.exec()?,
             #[cfg(feature = "evm")]
             NewArgs::EvmMapping(args) => args.exec()?,
         }
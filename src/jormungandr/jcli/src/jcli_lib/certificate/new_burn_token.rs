@@ -0,0 +1,62 @@
+use crate::jcli_lib::{
+    certificate::{write_cert, Error},
+    utils::{io, key_parser::parse_pub_key},
+};
+use chain_crypto::{Ed25519, PublicKey};
+use chain_impl_mockchain::{
+    certificate::{BurnToken as Burn, Certificate},
+    tokens::name::TokenName,
+    value::Value,
+};
+use jormungandr_lib::interfaces::MintingPolicy;
+use std::{convert::TryFrom, path::PathBuf};
+use structopt::StructOpt;
+
+/// create a token burning certificate
+///
+/// the transaction including the certificate needs to be signed by the
+/// account holding the tokens and by the accounts of the minting policy of
+/// the token
+#[derive(StructOpt)]
+#[structopt(rename_all = "kebab-case")]
+pub struct BurnToken {
+    /// the account the tokens are burnt from
+    #[structopt(name = "ACCOUNT_KEY", parse(try_from_str = parse_pub_key))]
+    from: PublicKey<Ed25519>,
+
+    /// the name of the token, in hexadecimal
+    #[structopt(long, parse(try_from_str = parse_token_name))]
+    name: TokenName,
+
+    /// the file containing the minting policy of the token (YAML), in the
+    /// same format as the `policy` of the initial tokens of the genesis file
+    #[structopt(long)]
+    policy: PathBuf,
+
+    /// the amount of tokens to burn
+    #[structopt(long)]
+    value: u64,
+
+    /// write the output to the given file or print it to the standard output if not defined
+    #[structopt(short = "o", long = "output")]
+    output: Option<PathBuf>,
+}
+
+fn parse_token_name(s: &str) -> Result<TokenName, Error> {
+    Ok(TokenName::try_from(hex::decode(s)?)?)
+}
+
+impl BurnToken {
+    pub fn exec(self) -> Result<(), Error> {
+        let policy = io::open_file_read(&Some(&self.policy))?;
+        let policy: MintingPolicy =
+            serde_yaml::from_reader(policy).map_err(Error::MintingPolicyConfig)?;
+        let cert = Certificate::BurnToken(Burn {
+            name: self.name,
+            policy: policy.into(),
+            from: self.from.into(),
+            value: Value(self.value),
+        });
+        write_cert(self.output.as_deref(), cert.into())
+    }
+}
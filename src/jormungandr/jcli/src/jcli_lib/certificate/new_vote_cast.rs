@@ -7,8 +7,9 @@ use chain_impl_mockchain::{
     certificate::{Certificate, VoteCast, VotePlanId},
     vote::{Choice, Payload},
 };
+use chain_vote::{WeightedBallotKind, WeightedVote};
 use rand_chacha::rand_core::SeedableRng;
-use std::path::PathBuf;
+use std::{num::NonZeroU8, path::PathBuf};
 use structopt::StructOpt;
 
 #[derive(StructOpt)]
@@ -57,11 +58,43 @@ pub struct PrivateVoteCast {
     output: Option<PathBuf>,
 }
 
+#[derive(StructOpt)]
+pub struct PrivateWeightedVoteCast {
+    /// the vote plan identified on the blockchain
+    #[structopt(long = "vote-plan-id")]
+    vote_plan_id: VotePlanId,
+
+    /// the number of proposal in the vote plan you vote for
+    #[structopt(long = "proposal-index")]
+    proposal_index: u8,
+
+    /// the maximum number of options that can be selected, for an approval vote plan
+    #[structopt(long = "approval", conflicts_with = "budget")]
+    approval: Option<NonZeroU8>,
+
+    /// the number of points that can be spread over the options, for a budget vote plan
+    #[structopt(long = "budget")]
+    budget: Option<NonZeroU8>,
+
+    /// the weight given to each option of the proposal, in order
+    #[structopt(long = "weights", required = true)]
+    weights: Vec<u8>,
+
+    /// key to encrypt the vote with
+    #[structopt(long = "key-path")]
+    election_key_path: Option<PathBuf>,
+
+    /// write the output to the given file or print it to the standard output if not defined
+    #[structopt(long = "output")]
+    output: Option<PathBuf>,
+}
+
 /// create a vote cast certificate
 #[derive(StructOpt)]
 pub enum VoteCastCmd {
     Public(PublicVoteCast),
     Private(PrivateVoteCast),
+    PrivateWeighted(PrivateWeightedVoteCast),
 }
 
 impl PublicVoteCast {
@@ -98,11 +131,40 @@ impl PrivateVoteCast {
     }
 }
 
+impl PrivateWeightedVoteCast {
+    pub fn exec(self) -> Result<(), Error> {
+        let kind = match (self.approval, self.budget) {
+            (Some(max_selections), None) => WeightedBallotKind::Approval { max_selections },
+            (None, Some(budget)) => WeightedBallotKind::Budget { budget },
+            _ => return Err(Error::WeightedBallotKindExpected),
+        };
+
+        let mut rng = rand_chacha::ChaChaRng::from_entropy();
+        let key_line = utils::io::read_line(&self.election_key_path)?;
+        let key = chain_vote::ElectionPublicKey::try_from_bech32_str(&key_line)?;
+
+        let vote = WeightedVote::new(kind, self.weights)?;
+        let crs = chain_vote::Crs::from_hash(self.vote_plan_id.as_ref());
+        let (encrypted_vote, proof) =
+            chain_impl_mockchain::vote::encrypt_weighted_vote(&mut rng, &crs, &key, &vote)?;
+
+        let payload = Payload::PrivateWeighted {
+            encrypted_vote,
+            proof,
+        };
+
+        let vote_cast = VoteCast::new(self.vote_plan_id, self.proposal_index, payload);
+        let cert = Certificate::VoteCast(vote_cast);
+        write_cert(self.output.as_deref(), cert.into())
+    }
+}
+
 impl VoteCastCmd {
     pub fn exec(self) -> Result<(), Error> {
         match self {
             VoteCastCmd::Public(vote_cast) => vote_cast.exec(),
             VoteCastCmd::Private(vote_cast) => vote_cast.exec(),
+            VoteCastCmd::PrivateWeighted(vote_cast) => vote_cast.exec(),
         }
     }
 }
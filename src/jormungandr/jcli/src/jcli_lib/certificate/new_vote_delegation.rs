@@ -0,0 +1,110 @@
+use crate::jcli_lib::{
+    certificate::{write_cert, Error},
+    utils::key_parser::parse_pub_key,
+};
+use chain_crypto::{Ed25519, PublicKey};
+use chain_impl_mockchain::{
+    accounting::account::DELEGATION_RATIO_MAX_DECLS,
+    certificate::{Certificate, VoteDelegation as Delegation, VotePlanId},
+    tokens::identifier::TokenIdentifier,
+    vote::{VoteDelegationRatio, VoteDelegationScope, VoteDelegationType},
+};
+use std::{error::Error as StdError, path::PathBuf, str::FromStr};
+use structopt::StructOpt;
+
+/// create a vote delegation certificate
+///
+/// the voting power of the account of the single input of the transaction
+/// including the certificate is delegated to the given representatives.
+/// Without representatives, the previous delegation for the scope is removed.
+#[derive(StructOpt)]
+#[structopt(rename_all = "kebab-case")]
+pub struct VoteDelegation {
+    /// delegate for the given vote plan only, takes precedence over the
+    /// delegation for the voting token of the vote plan
+    #[structopt(
+        long,
+        conflicts_with = "voting-token",
+        required_unless = "voting-token"
+    )]
+    vote_plan_id: Option<VotePlanId>,
+
+    /// delegate for all the vote plans using the given voting token
+    #[structopt(long)]
+    voting_token: Option<TokenIdentifier>,
+
+    /// public keys of the representatives and their numeric weights in format
+    /// "account_key:weight". If weight is not provided, it defaults to 1.
+    #[structopt(name = "REPRESENTATIVES")]
+    representatives: Vec<WeightedRepresentative>,
+
+    /// write the output to the given file or print it to the standard output if not defined
+    #[structopt(short = "o", long = "output")]
+    output: Option<PathBuf>,
+}
+
+struct WeightedRepresentative {
+    key: PublicKey<Ed25519>,
+    weight: u8,
+}
+
+impl FromStr for WeightedRepresentative {
+    type Err = Box<dyn StdError>;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut split = s.splitn(2, ':');
+        Ok(WeightedRepresentative {
+            key: parse_pub_key(split.next().unwrap())?,
+            weight: split.next().map_or(Ok(1), str::parse)?,
+        })
+    }
+}
+
+impl VoteDelegation {
+    pub fn exec(self) -> Result<(), Error> {
+        let scope = match (self.vote_plan_id, self.voting_token) {
+            (Some(id), _) => VoteDelegationScope::VotePlan(id),
+            (None, Some(token)) => VoteDelegationScope::Token(token),
+            (None, None) => unreachable!("enforced by the command line arguments"),
+        };
+        let cert = Certificate::VoteDelegation(Delegation {
+            scope,
+            delegation: delegation_type(self.representatives)?,
+        });
+        write_cert(self.output.as_deref(), cert.into())
+    }
+}
+
+fn delegation_type(
+    representatives: Vec<WeightedRepresentative>,
+) -> Result<VoteDelegationType, Error> {
+    if representatives.len() > DELEGATION_RATIO_MAX_DECLS {
+        return Err(Error::TooManyVoteDelegations {
+            actual: representatives.len(),
+            max: DELEGATION_RATIO_MAX_DECLS,
+        });
+    }
+    match representatives.len() {
+        0 => Ok(VoteDelegationType::NonDelegated),
+        1 => Ok(VoteDelegationType::Full(
+            representatives[0].key.clone().into(),
+        )),
+        _ => {
+            let parts = representatives
+                .iter()
+                .map(|representative| representative.weight as u64)
+                .sum::<u64>();
+            let parts = u8::try_from(parts).map_err(|_| Error::InvalidPoolDelegationWeights {
+                actual: parts,
+                max: u8::MAX as u64,
+            })?;
+            let representatives = representatives
+                .into_iter()
+                .map(|representative| (representative.key.into(), representative.weight))
+                .collect();
+            VoteDelegationRatio::new(parts, representatives)
+                .map(VoteDelegationType::Ratio)
+                .ok_or(Error::InvalidVoteDelegation)
+        }
+    }
+}
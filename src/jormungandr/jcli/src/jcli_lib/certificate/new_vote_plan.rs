@@ -29,7 +29,7 @@ fn validate_voteplan(voteplan: &certificate::VotePlan) -> Result<(), Error> {
     // if voteplan is private committee member keys should be filled
     match voteplan.payload_type() {
         PayloadType::Public => {}
-        PayloadType::Private => {
+        PayloadType::Private | PayloadType::PrivateWeighted => {
             if voteplan.committee_public_keys().is_empty() {
                 return Err(Error::InvalidPrivateVotePlanCommitteeKeys);
             }
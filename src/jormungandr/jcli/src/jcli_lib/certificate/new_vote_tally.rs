@@ -78,7 +78,7 @@ impl PrivateTally {
     pub fn exec(self) -> Result<(), Error> {
         let vote_plan =
             vote::get_vote_plan_by_id(Some(self.vote_plan), self.vote_plan_id.as_ref())?;
-        let shares: Vec<Vec<chain_vote::TallyDecryptShare>> =
+        let shares: Vec<Vec<Option<chain_vote::TallyDecryptShare>>> =
             vote::read_vote_plan_shares_from_file(
                 Some(self.shares),
                 vote_plan.proposals.len(),
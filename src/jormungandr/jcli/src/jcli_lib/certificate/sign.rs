@@ -125,6 +125,8 @@ impl Sign {
                     })??
             }
             Certificate::MintToken(_) => return Err(Error::MintTokenDoesntNeedSignature),
+            Certificate::BurnToken(_) => return Err(Error::BurnTokenDoesntNeedSignature),
+            Certificate::VoteDelegation(_) => return Err(Error::VoteDelegationDoesntNeedSignature),
             Certificate::EvmMapping(uv) => {
                 let txbuilder = Transaction::block0_payload_builder(&uv);
                 keys_str
@@ -156,7 +158,9 @@ pub(crate) fn committee_vote_tally_sign(
 
     let proof = match vote_tally.tally_type() {
         PayloadType::Public => TallyProof::Public { id, signature },
-        PayloadType::Private => TallyProof::Private { id, signature },
+        PayloadType::Private | PayloadType::PrivateWeighted => {
+            TallyProof::Private { id, signature }
+        }
     };
     Ok(SignedCertificate::VoteTally(vote_tally, proof))
 }
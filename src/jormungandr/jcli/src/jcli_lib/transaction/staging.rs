@@ -287,7 +287,9 @@ impl Staging {
                         .map_err(|error| Error::CertificateError { error })??;
                     self.extra_authed = Some(sc.into())
                 }
-                Certificate::MintToken(_) => unreachable!(),
+                Certificate::MintToken(_)
+                | Certificate::BurnToken(_)
+                | Certificate::VoteDelegation(_) => unreachable!(),
             },
         };
         self.kind = StagingKind::Authed;
@@ -408,6 +410,12 @@ impl Staging {
                 Certificate::MintToken(vt) => {
                     self.finalize_payload(&vt, fee_algorithm, output_policy)
                 }
+                Certificate::BurnToken(vt) => {
+                    self.finalize_payload(&vt, fee_algorithm, output_policy)
+                }
+                Certificate::VoteDelegation(vd) => {
+                    self.finalize_payload(&vd, fee_algorithm, output_policy)
+                }
                 Certificate::EvmMapping(vt) => {
                     self.finalize_payload(&vt, fee_algorithm, output_policy)
                 }
@@ -521,6 +529,12 @@ impl Staging {
                         Certificate::MintToken(mint_token) => {
                             self.make_fragment(&mint_token, &(), Fragment::MintToken)
                         }
+                        Certificate::BurnToken(burn_token) => {
+                            self.make_fragment(&burn_token, &(), Fragment::BurnToken)
+                        }
+                        Certificate::VoteDelegation(vote_delegation) => {
+                            self.make_fragment(&vote_delegation, &(), Fragment::VoteDelegation)
+                        }
                         _ => unreachable!(),
                     },
                 }
@@ -624,6 +638,12 @@ impl Staging {
                 Certificate::MintToken(vt) => {
                     self.transaction_sign_data_hash_on(TxBuilder::new().set_payload(&vt))
                 }
+                Certificate::BurnToken(vt) => {
+                    self.transaction_sign_data_hash_on(TxBuilder::new().set_payload(&vt))
+                }
+                Certificate::VoteDelegation(vd) => {
+                    self.transaction_sign_data_hash_on(TxBuilder::new().set_payload(&vd))
+                }
                 Certificate::EvmMapping(vt) => {
                     self.transaction_sign_data_hash_on(TxBuilder::new().set_payload(&vt))
                 }
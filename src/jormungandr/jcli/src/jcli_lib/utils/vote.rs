@@ -83,9 +83,11 @@ pub struct TallyDecryptShare(#[serde(with = "serde_base64_bytes")] Vec<u8>);
 pub struct MemberVotePlanShares(Vec<TallyDecryptShare>);
 
 // Set of decrypt shares (belonging to different committee members)
-// that decrypts a vote plan
+// that decrypts a vote plan. The shares of a proposal are in the order of the
+// committee members, members of a threshold committee that did not take part
+// in the decryption have a `null` share.
 #[derive(Debug, Serialize, Deserialize)]
-pub struct VotePlanDecryptShares(Vec<Vec<TallyDecryptShare>>);
+pub struct VotePlanDecryptShares(Vec<Vec<Option<TallyDecryptShare>>>);
 
 impl TryFrom<TallyDecryptShare> for chain_vote::TallyDecryptShare {
     type Error = SharesError;
@@ -106,28 +108,37 @@ impl From<Vec<chain_vote::TallyDecryptShare>> for MemberVotePlanShares {
     }
 }
 
-impl TryFrom<Vec<MemberVotePlanShares>> for VotePlanDecryptShares {
+// the shares of the committee members in the committee order, `None` for the
+// members that did not provide their shares
+impl TryFrom<Vec<Option<MemberVotePlanShares>>> for VotePlanDecryptShares {
     type Error = SharesError;
-    fn try_from(shares: Vec<MemberVotePlanShares>) -> Result<Self, Self::Error> {
-        let shares = shares.into_iter().map(|s| s.0).collect::<Vec<_>>();
-        if shares.is_empty() {
-            return Err(SharesError::Empty);
-        }
-        let mut res = vec![Vec::new(); shares[0].len()];
+    fn try_from(shares: Vec<Option<MemberVotePlanShares>>) -> Result<Self, Self::Error> {
+        let proposals = shares
+            .iter()
+            .flatten()
+            .map(|s| s.0.len())
+            .next()
+            .ok_or(SharesError::Empty)?;
+        let mut res = vec![Vec::new(); proposals];
         // transponse 2d array
         for member_shares in shares {
-            if member_shares.len() != res.len() {
-                return Err(SharesError::ProposalSharesNotBalanced);
-            }
-            for (i, share) in member_shares.into_iter().enumerate() {
-                res[i].push(share);
+            match member_shares {
+                Some(member_shares) => {
+                    if member_shares.0.len() != res.len() {
+                        return Err(SharesError::ProposalSharesNotBalanced);
+                    }
+                    for (i, share) in member_shares.0.into_iter().enumerate() {
+                        res[i].push(Some(share));
+                    }
+                }
+                None => res.iter_mut().for_each(|shares| shares.push(None)),
             }
         }
         Ok(VotePlanDecryptShares(res))
     }
 }
 
-impl TryFrom<VotePlanDecryptShares> for Vec<Vec<chain_vote::TallyDecryptShare>> {
+impl TryFrom<VotePlanDecryptShares> for Vec<Vec<Option<chain_vote::TallyDecryptShare>>> {
     type Error = SharesError;
     fn try_from(vote_plan: VotePlanDecryptShares) -> Result<Self, Self::Error> {
         vote_plan
@@ -135,7 +146,11 @@ impl TryFrom<VotePlanDecryptShares> for Vec<Vec<chain_vote::TallyDecryptShare>>
             .into_iter()
             .map(|v| {
                 v.into_iter()
-                    .map(chain_vote::TallyDecryptShare::try_from)
+                    .map(|share| {
+                        share
+                            .map(chain_vote::TallyDecryptShare::try_from)
+                            .transpose()
+                    })
                     .collect::<Result<Vec<_>, Self::Error>>()
             })
             .collect::<Result<Vec<_>, Self::Error>>()
@@ -149,7 +164,8 @@ pub fn read_vote_plan_shares_from_file<P: AsRef<Path>>(
 ) -> Result<VotePlanDecryptShares, SharesError> {
     let vote_plan_shares: VotePlanDecryptShares =
         serde_json::from_reader(io::open_file_read(&share_path)?)?;
-    if vote_plan_shares.0.len() != proposals || vote_plan_shares.0[0].len() < threshold.unwrap_or(1)
+    if vote_plan_shares.0.len() != proposals
+        || vote_plan_shares.0[0].iter().flatten().count() < threshold.unwrap_or(1)
     {
         return Err(SharesError::InsufficientShares);
     }
@@ -0,0 +1,260 @@
+use crate::jcli_lib::{
+    utils::io,
+    vote::{Error, OutputFile, Seed},
+};
+use chain_crypto::bech32::Bech32;
+use chain_vote::{
+    committee::MemberCommunicationPublicKey,
+    dkg::{Complaints, Dealing, Disputes, DkgMember},
+    MemberCommunicationKey,
+};
+use rand::rngs::OsRng;
+use rand_chacha::{rand_core::SeedableRng, ChaCha20Rng};
+use serde::Serialize;
+use std::{
+    io::Write,
+    path::{Path, PathBuf},
+};
+use structopt::StructOpt;
+
+/// Run the rounds of a distributed key generation of the committee election key.
+///
+/// Every member runs the same command for a round, and shares the produced
+/// message with all the other members before moving to the next round.
+#[derive(StructOpt)]
+#[structopt(rename_all = "kebab-case")]
+pub enum Dkg {
+    /// start the key generation and produce the dealing to share with the committee
+    Init(Init),
+    /// process the dealings of the committee and produce the complaints to share
+    Complain(Complain),
+    /// process the complaints of the committee and produce the disputes to share
+    Dispute(Dispute),
+    /// process the disputes of the committee and produce the member secret key
+    Finalize(Finalize),
+}
+
+#[derive(StructOpt)]
+#[structopt(rename_all = "kebab-case")]
+pub struct Init {
+    /// threshold number of the committee members sufficient for
+    /// decrypting the tally
+    #[structopt(long, short, name = "THRESHOLD", parse(try_from_str))]
+    threshold: usize,
+
+    /// communication keys of all committee members
+    #[structopt(long, short, name = "COMMUNICATION_KEYS",
+        parse(try_from_str = MemberCommunicationPublicKey::try_from_bech32_str),
+        required = true,
+    )]
+    keys: Vec<MemberCommunicationPublicKey>,
+
+    /// index of the committee member running the key generation
+    #[structopt(long, short, name = "INDEX", parse(try_from_str))]
+    index: usize,
+
+    /// the file with the communication secret key of the committee member
+    #[structopt(long)]
+    communication_key: PathBuf,
+
+    /// the file the secret state of the member is written to, and updated
+    /// at each round. It must be kept secret until the end of the key generation
+    #[structopt(long)]
+    state: PathBuf,
+
+    /// optional seed to generate the member secrets, for the same entropy the
+    /// same secrets will be generated (32 bytes in hexadecimal). This seed will
+    /// be fed to ChaChaRNG and allow pseudo random key generation. Do not use
+    /// if you are not sure.
+    #[structopt(long = "seed", short = "s", name = "SEED", parse(try_from_str))]
+    seed: Option<Seed>,
+
+    #[structopt(flatten)]
+    output_file: OutputFile,
+}
+
+#[derive(StructOpt)]
+#[structopt(rename_all = "kebab-case")]
+pub struct Complain {
+    /// the file with the secret state of the member
+    #[structopt(long)]
+    state: PathBuf,
+
+    /// the files with the dealings of all the committee members
+    #[structopt(long, required = true)]
+    dealings: Vec<PathBuf>,
+
+    #[structopt(flatten)]
+    output_file: OutputFile,
+}
+
+#[derive(StructOpt)]
+#[structopt(rename_all = "kebab-case")]
+pub struct Dispute {
+    /// the file with the secret state of the member
+    #[structopt(long)]
+    state: PathBuf,
+
+    /// the files with the complaints of all the committee members
+    #[structopt(long, required = true)]
+    complaints: Vec<PathBuf>,
+
+    #[structopt(flatten)]
+    output_file: OutputFile,
+}
+
+#[derive(StructOpt)]
+#[structopt(rename_all = "kebab-case")]
+pub struct Finalize {
+    /// the file with the secret state of the member
+    #[structopt(long)]
+    state: PathBuf,
+
+    /// the files with the disputes of all the committee members
+    #[structopt(long, required = true)]
+    disputes: Vec<PathBuf>,
+
+    /// the file the election public key and the public keys of the committee
+    /// members are written to, in JSON
+    #[structopt(long)]
+    public_output: PathBuf,
+
+    #[structopt(flatten)]
+    output_file: OutputFile,
+}
+
+#[derive(Serialize)]
+struct PublicOutput {
+    election_public_key: String,
+    member_public_keys: Vec<String>,
+    committee_threshold: usize,
+    qualified: Vec<usize>,
+}
+
+fn read_hex(path: &Path) -> Result<Vec<u8>, Error> {
+    let line = io::read_line(&Some(path)).map_err(|source| Error::InputInvalid {
+        source,
+        path: path.to_path_buf(),
+    })?;
+    Ok(hex::decode(line.trim())?)
+}
+
+fn read_messages<T>(
+    paths: &[PathBuf],
+    kind: &'static str,
+    from_bytes: impl Fn(&[u8]) -> Option<T>,
+) -> Result<Vec<T>, Error> {
+    paths
+        .iter()
+        .map(|path| {
+            from_bytes(&read_hex(path)?).ok_or_else(|| Error::DkgMessageInvalid {
+                kind,
+                path: path.clone(),
+            })
+        })
+        .collect()
+}
+
+fn read_state(path: &Path) -> Result<DkgMember, Error> {
+    DkgMember::from_bytes(&read_hex(path)?).ok_or_else(|| Error::DkgMessageInvalid {
+        kind: "member state",
+        path: path.to_path_buf(),
+    })
+}
+
+fn write_state(path: &Path, state: &DkgMember) -> Result<(), Error> {
+    let mut output = io::open_file_write(&Some(path))?;
+    writeln!(output, "{}", hex::encode(state.to_bytes()))?;
+    Ok(())
+}
+
+impl Init {
+    fn exec(self) -> Result<(), Error> {
+        let mut rng = match self.seed {
+            Some(seed) => ChaCha20Rng::from_seed(seed.0),
+            None => ChaCha20Rng::from_rng(OsRng)?,
+        };
+
+        let line = io::read_line(&Some(&self.communication_key))?;
+        let communication_key = MemberCommunicationKey::try_from_bech32_str(&line)?;
+
+        let (state, dealing) = DkgMember::init(
+            &mut rng,
+            self.threshold,
+            &self.keys,
+            &communication_key,
+            self.index,
+        )?;
+        write_state(&self.state, &state)?;
+
+        let mut output = self.output_file.open()?;
+        writeln!(output, "{}", hex::encode(dealing.to_bytes()))?;
+        Ok(())
+    }
+}
+
+impl Complain {
+    fn exec(self) -> Result<(), Error> {
+        let mut state = read_state(&self.state)?;
+        let dealings = read_messages(&self.dealings, "dealing", Dealing::from_bytes)?;
+
+        let complaints = state.handle_dealings(&dealings)?;
+        write_state(&self.state, &state)?;
+
+        let mut output = self.output_file.open()?;
+        writeln!(output, "{}", hex::encode(complaints.to_bytes()))?;
+        Ok(())
+    }
+}
+
+impl Dispute {
+    fn exec(self) -> Result<(), Error> {
+        let mut state = read_state(&self.state)?;
+        let complaints = read_messages(&self.complaints, "complaints", Complaints::from_bytes)?;
+
+        let disputes = state.handle_complaints(&complaints)?;
+        write_state(&self.state, &state)?;
+
+        let mut output = self.output_file.open()?;
+        writeln!(output, "{}", hex::encode(disputes.to_bytes()))?;
+        Ok(())
+    }
+}
+
+impl Finalize {
+    fn exec(self) -> Result<(), Error> {
+        let mut state = read_state(&self.state)?;
+        let disputes = read_messages(&self.disputes, "disputes", Disputes::from_bytes)?;
+
+        let dkg = state.handle_disputes(&disputes)?;
+        write_state(&self.state, &state)?;
+
+        let public_output = PublicOutput {
+            election_public_key: dkg.election_public_key().to_bech32_str(),
+            member_public_keys: dkg
+                .member_public_keys()
+                .iter()
+                .map(Bech32::to_bech32_str)
+                .collect(),
+            committee_threshold: state.threshold(),
+            qualified: dkg.qualified().to_vec(),
+        };
+        let public_file = io::open_file_write(&Some(&self.public_output))?;
+        serde_json::to_writer_pretty(public_file, &public_output)?;
+
+        let mut output = self.output_file.open()?;
+        writeln!(output, "{}", dkg.secret_key().to_bech32_str())?;
+        Ok(())
+    }
+}
+
+impl Dkg {
+    pub fn exec(self) -> Result<(), Error> {
+        match self {
+            Dkg::Init(args) => args.exec(),
+            Dkg::Complain(args) => args.exec(),
+            Dkg::Dispute(args) => args.exec(),
+            Dkg::Finalize(args) => args.exec(),
+        }
+    }
+}
@@ -1,4 +1,5 @@
 mod communication_key;
+mod dkg;
 mod member_key;
 
 use super::Error;
@@ -11,6 +12,8 @@ pub enum Committee {
     CommunicationKey(communication_key::CommunicationKey),
     /// commands for managing committee member stake keys
     MemberKey(member_key::MemberKey),
+    /// commands for generating the committee member keys without a trusted dealer
+    Dkg(dkg::Dkg),
 }
 
 impl Committee {
@@ -18,6 +21,7 @@ impl Committee {
         match self {
             Committee::CommunicationKey(args) => args.exec(),
             Committee::MemberKey(args) => args.exec(),
+            Committee::Dkg(args) => args.exec(),
         }
     }
 }
@@ -15,14 +15,38 @@ pub struct ElectionPublicKey {
     )]
     member_keys: Vec<chain_vote::committee::MemberPublicKey>,
 
+    /// Minimum number of committee members required to decrypt the tally.
+    /// If omitted, every committee member has to take part in the decryption.
+    #[structopt(short = "t", long = "threshold")]
+    threshold: Option<usize>,
+
     #[structopt(flatten)]
     output_file: OutputFile,
 }
 
 impl ElectionPublicKey {
     pub fn exec(&self) -> Result<(), Error> {
-        let election_public_key =
-            chain_vote::ElectionPublicKey::from_participants(&self.member_keys);
+        let election_public_key = match self.threshold {
+            Some(threshold) => {
+                if threshold == 0 || threshold > self.member_keys.len() {
+                    return Err(Error::InvalidThreshold {
+                        threshold,
+                        committee_members: self.member_keys.len(),
+                    });
+                }
+                if !chain_vote::ElectionPublicKey::check_threshold_participants(
+                    threshold,
+                    &self.member_keys,
+                ) {
+                    return Err(Error::InconsistentCommitteeKeys);
+                }
+                chain_vote::ElectionPublicKey::from_threshold_participants(
+                    threshold,
+                    &self.member_keys,
+                )
+            }
+            None => chain_vote::ElectionPublicKey::from_participants(&self.member_keys),
+        };
 
         let mut output = self.output_file.open()?;
         writeln!(output, "{}", election_public_key.to_bech32_str()).map_err(Error::from)
@@ -14,7 +14,7 @@ mod committee;
 mod election_public_key;
 mod tally;
 
-pub use tally::MergedVotePlan;
+pub use tally::{decrypt_vote_plan, MergedVotePlan};
 
 #[derive(Debug, Error)]
 pub enum Error {
@@ -43,6 +43,8 @@ pub enum Error {
         threshold: usize,
         committee_members: usize,
     },
+    #[error("the committee member keys are not shares of the same election key")]
+    InconsistentCommitteeKeys,
     #[error("invalid committee member index")]
     InvalidCommitteMemberIndex,
     #[error("failed to read encrypted tally bytes")]
@@ -53,6 +55,8 @@ pub enum Error {
     PrivateTallyExpected { found: &'static str },
     #[error(transparent)]
     TallyError(#[from] chain_vote::tally::TallyError),
+    #[error("cannot load the tally optimization table")]
+    TallyTable(#[from] chain_vote::TallyOptimizationTableError),
     #[error(transparent)]
     FormatError(#[from] crate::jcli_lib::utils::output_format::Error),
     #[error(transparent)]
@@ -87,6 +91,10 @@ pub enum Error {
     },
     #[error(transparent)]
     MergeError(#[from] tally::merge_results::Error),
+    #[error(transparent)]
+    Dkg(#[from] chain_vote::dkg::DkgError),
+    #[error("invalid key generation {kind} in '{path}'")]
+    DkgMessageInvalid { kind: &'static str, path: PathBuf },
 }
 
 #[derive(StructOpt)]
@@ -1,16 +1,22 @@
 use super::Error;
 use crate::jcli_lib::utils::{
-    vote::{self, SharesError},
+    vote::{self, SharesError, VotePlanDecryptShares},
     OutputFormat,
 };
-use chain_vote::tally::{batch_decrypt, EncryptedTally};
+use chain_vote::{
+    tally::{batch_decrypt, batch_decrypt_with_table, EncryptedTally},
+    TallyOptimizationTable,
+};
 use jormungandr_lib::{
     crypto::hash::Hash,
-    interfaces::{PrivateTallyState, Tally},
+    interfaces::{PrivateTallyState, Tally, VotePlanStatus},
 };
 use rayon::prelude::*;
 use serde::Serialize;
-use std::{convert::TryInto, path::PathBuf};
+use std::{
+    convert::TryInto,
+    path::{Path, PathBuf},
+};
 use structopt::StructOpt;
 
 #[derive(StructOpt)]
@@ -33,6 +39,11 @@ pub struct TallyVotePlanWithAllShares {
     /// from the standard input.
     #[structopt(long)]
     shares: Option<PathBuf>,
+    /// The path to a directory of precomputed tables speeding up the decryption.
+    /// The table needed for the vote plan is loaded from there, or generated and
+    /// saved there first if missing, so it can be reused by later decryptions.
+    #[structopt(long)]
+    tally_tables: Option<PathBuf>,
     #[structopt(flatten)]
     output_format: OutputFormat,
 }
@@ -46,58 +57,89 @@ impl TallyVotePlanWithAllShares {
     pub fn exec(&self) -> Result<(), Error> {
         let mut vote_plan =
             vote::get_vote_plan_by_id(self.vote_plan.as_ref(), self.vote_plan_id.as_ref())?;
-        let shares: Vec<Vec<chain_vote::TallyDecryptShare>> =
-            vote::read_vote_plan_shares_from_file(
-                self.shares.as_ref(),
-                vote_plan.proposals.len(),
-                Some(self.threshold),
-            )?
-            .try_into()?;
-        let committee_member_keys = vote_plan.committee_member_keys.clone();
+        let shares = vote::read_vote_plan_shares_from_file(
+            self.shares.as_ref(),
+            vote_plan.proposals.len(),
+            Some(self.threshold),
+        )?;
+        decrypt_vote_plan(&mut vote_plan, shares, self.tally_tables.as_deref())?;
 
-        let validated_tallies = (&vote_plan.proposals)
-            .into_par_iter()
-            .zip(shares.into_par_iter())
-            .map(|(proposal, shares)| {
-                let encrypted_tally = match &proposal.tally {
-                    Tally::Private {
-                        state:
-                            PrivateTallyState::Encrypted {
-                                encrypted_tally, ..
-                            },
-                    } => encrypted_tally,
-                    _ => unreachable!("expected encrypted private tally"),
-                };
+        let output = self
+            .output_format
+            .format_json(serde_json::to_value(vote_plan)?)?;
+        println!("{}", output);
 
-                let encrypted_tally = EncryptedTally::from_bytes(encrypted_tally.as_ref())
-                    .ok_or(Error::EncryptedTallyRead)?;
+        Ok(())
+    }
+}
 
-                encrypted_tally
-                    .validate_partial_decryptions(&committee_member_keys, &shares)
-                    .map_err(SharesError::ValidationFailed)
-                    .map_err(Error::SharesError)
-            })
-            .collect::<Result<Vec<_>, _>>()?;
+/// Decrypt the tally of all the proposals of `vote_plan` with the decryption
+/// `shares` of every proposal. The shares of some members may be missing only
+/// if the vote plan has a committee threshold. If `tally_tables` is given,
+/// the baby-step giant-step table used for the decryption is loaded from this
+/// directory, or generated and saved there if missing.
+pub fn decrypt_vote_plan(
+    vote_plan: &mut VotePlanStatus,
+    shares: VotePlanDecryptShares,
+    tally_tables: Option<&Path>,
+) -> Result<(), Error> {
+    let shares: Vec<Vec<Option<chain_vote::TallyDecryptShare>>> = shares.try_into()?;
+    let committee_member_keys = vote_plan.committee_member_keys.clone();
+    let committee_threshold = vote_plan.committee_threshold;
 
-        let decrypted_tallies = batch_decrypt(validated_tallies)?;
+    let validated_tallies = (&vote_plan.proposals)
+        .into_par_iter()
+        .zip(shares.into_par_iter())
+        .map(|(proposal, shares)| {
+            let encrypted_tally = match &proposal.tally {
+                Tally::Private {
+                    state:
+                        PrivateTallyState::Encrypted {
+                            encrypted_tally, ..
+                        },
+                } => encrypted_tally,
+                _ => unreachable!("expected encrypted private tally"),
+            };
 
-        for (proposal, decrypted_tally) in vote_plan
-            .proposals
-            .iter_mut()
-            .zip(decrypted_tallies.into_iter())
-        {
-            proposal.tally = Tally::Private {
-                state: PrivateTallyState::Decrypted {
-                    result: decrypted_tally.into(),
+            let encrypted_tally = EncryptedTally::from_bytes(encrypted_tally.as_ref())
+                .ok_or(Error::EncryptedTallyRead)?;
+
+            let validated = match committee_threshold {
+                Some(threshold) => encrypted_tally.validate_threshold_partial_decryptions(
+                    threshold.get() as usize,
+                    &committee_member_keys,
+                    &shares,
+                ),
+                None => match shares.into_iter().collect::<Option<Vec<_>>>() {
+                    Some(shares) => encrypted_tally
+                        .validate_partial_decryptions(&committee_member_keys, &shares),
+                    None => return Err(Error::SharesError(SharesError::InsufficientShares)),
                 },
-            }
-        }
+            };
+            validated
+                .map_err(SharesError::ValidationFailed)
+                .map_err(Error::SharesError)
+        })
+        .collect::<Result<Vec<_>, _>>()?;
 
-        let output = self
-            .output_format
-            .format_json(serde_json::to_value(vote_plan)?)?;
-        println!("{}", output);
+    let decrypted_tallies = match tally_tables {
+        Some(dir) => batch_decrypt_with_table(validated_tallies, |max_stake| {
+            TallyOptimizationTable::load_or_generate(dir, max_stake).map_err(Error::from)
+        })?,
+        None => batch_decrypt(validated_tallies)?,
+    };
 
-        Ok(())
+    for (proposal, decrypted_tally) in vote_plan
+        .proposals
+        .iter_mut()
+        .zip(decrypted_tallies.into_iter())
+    {
+        proposal.tally = Tally::Private {
+            state: PrivateTallyState::Decrypted {
+                result: decrypted_tally.into(),
+            },
+        }
     }
+
+    Ok(())
 }
@@ -1,7 +1,7 @@
 use super::Error;
 use crate::jcli_lib::utils::{
     io,
-    vote::{self, MemberVotePlanShares, VotePlanDecryptShares},
+    vote::{self, MemberVotePlanShares, SharesError, VotePlanDecryptShares},
 };
 use chain_crypto::bech32::Bech32;
 use chain_vote::tally::{EncryptedTally, OpeningVoteKey};
@@ -40,8 +40,13 @@ pub struct TallyGenerateVotePlanDecryptionShares {
 #[derive(StructOpt)]
 #[structopt(rename_all = "kebab-case")]
 pub struct MergeShares {
-    /// The path to the shares to merge
+    /// The path to the shares to merge, in the order of the committee members
     shares: Vec<PathBuf>,
+    /// The positions in the committee of the members of a threshold committee
+    /// that did not provide their shares. Their shares are left empty so the
+    /// other shares stay in the order of the committee member keys.
+    #[structopt(long)]
+    missing: Vec<usize>,
 }
 
 impl TallyGenerateVotePlanDecryptionShares {
@@ -78,11 +83,24 @@ impl TallyGenerateVotePlanDecryptionShares {
 
 impl MergeShares {
     pub fn exec(&self) -> Result<(), Error> {
-        let shares = self
+        let mut shares = self
             .shares
             .iter()
-            .map(|path| Ok(serde_json::from_reader(io::open_file_read(&Some(path))?)?))
-            .collect::<Result<Vec<MemberVotePlanShares>, Error>>()?;
+            .map(|path| {
+                Ok(Some(serde_json::from_reader(io::open_file_read(&Some(
+                    path,
+                ))?)?))
+            })
+            .collect::<Result<Vec<Option<MemberVotePlanShares>>, Error>>()?;
+        let mut missing = self.missing.clone();
+        missing.sort_unstable();
+        missing.dedup();
+        for position in missing {
+            if position > shares.len() {
+                return Err(Error::SharesError(SharesError::InsufficientShares));
+            }
+            shares.insert(position, None);
+        }
         let vote_plan_shares = VotePlanDecryptShares::try_from(shares)?;
         println!("{}", serde_json::to_string(&vote_plan_shares)?);
         Ok(())
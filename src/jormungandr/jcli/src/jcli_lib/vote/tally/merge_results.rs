@@ -165,6 +165,7 @@ mod tests {
             vote_end: BlockDate::new(0, 1),
             committee_end: BlockDate::new(0, 2),
             committee_member_keys: vec![],
+            committee_threshold: None,
             proposals: vec![VoteProposalStatus {
                 index: 0,
                 proposal_id,
@@ -116,6 +116,14 @@ impl property::Serialize for Certificate {
                 codec.put_bytes(&[12])?;
                 codec.put_bytes(c.serialize().as_slice())?;
             }
+            certificate::Certificate::BurnToken(c) => {
+                codec.put_bytes(&[13])?;
+                codec.put_bytes(c.serialize().as_slice())?;
+            }
+            certificate::Certificate::VoteDelegation(c) => {
+                codec.put_bytes(&[14])?;
+                codec.put_bytes(c.serialize().as_slice())?;
+            }
         };
         Ok(())
     }
@@ -178,6 +186,14 @@ impl property::DeserializeFromSlice for Certificate {
                 let cert = certificate::EvmMapping::deserialize_from_slice(codec)?;
                 Ok(Certificate(certificate::Certificate::EvmMapping(cert)))
             }
+            13 => {
+                let cert = certificate::BurnToken::deserialize_from_slice(codec)?;
+                Ok(Certificate(certificate::Certificate::BurnToken(cert)))
+            }
+            14 => {
+                let cert = certificate::VoteDelegation::deserialize_from_slice(codec)?;
+                Ok(Certificate(certificate::Certificate::VoteDelegation(cert)))
+            }
             t => Err(property::ReadError::UnknownTag(t as u32)),
         }
     }
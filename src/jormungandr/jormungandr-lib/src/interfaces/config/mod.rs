@@ -6,7 +6,7 @@ mod secret;
 pub use log::{Log, LogEntry, LogOutput};
 pub use mempool::{LogMaxEntries, Mempool, PersistentLog, PoolMaxEntries};
 pub use node::{
-    Bootstrap, Connection, Cors, CorsOrigin, JRpc, LayersConfig, NodeConfig, NodeId, P2p, Policy,
-    PreferredListConfig, Rest, Tls, TopicsOfInterest, TrustedPeer,
+    Bootstrap, Connection, Cors, CorsOrigin, JRpc, JRpcWebSocket, LayersConfig, NodeConfig, NodeId,
+    P2p, Policy, PreferredListConfig, Rest, Tls, TopicsOfInterest, TrustedPeer,
 };
 pub use secret::{Bft, GenesisPraos, NodeSecret};
@@ -25,6 +25,25 @@ pub struct Rest {
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
 pub struct JRpc {
     pub listen: SocketAddr,
+    /// Keeps the ledger state and the EVM logs of every block, to serve
+    /// requests on historical blocks
+    #[serde(default)]
+    pub archive: bool,
+    /// Enables `eth_subscribe` over WebSocket if provided
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub websocket: Option<JRpcWebSocket>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(deny_unknown_fields)]
+pub struct JRpcWebSocket {
+    pub listen: SocketAddr,
+    /// Maximum number of simultaneous WebSocket connections
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub max_connections: Option<u32>,
+    /// Maximum number of active subscriptions on a single connection
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub max_subscriptions_per_connection: Option<u32>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
@@ -1,8 +1,8 @@
-use super::Value;
-use crate::crypto::account::Identifier;
+use super::{BlockDate, Value};
+use crate::crypto::{account::Identifier, hash::Hash};
 use chain_core::packer::Codec;
 use chain_impl_mockchain::{
-    certificate,
+    certificate, key,
     tokens::{identifier, minting_policy, name},
 };
 use serde::{Deserialize, Serialize};
@@ -118,11 +118,81 @@ impl From<MintingPolicy> for minting_policy::MintingPolicy {
     }
 }
 
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+enum MintingPolicyEntry {
+    Account(Identifier),
+    Multisig(Hash),
+    TimeLock(BlockDate),
+    MaxSupply(Value),
+}
+
+impl From<&minting_policy::MintingPolicyEntry> for MintingPolicyEntry {
+    fn from(val: &minting_policy::MintingPolicyEntry) -> Self {
+        match val {
+            minting_policy::MintingPolicyEntry::Account(id) => Self::Account(id.clone().into()),
+            minting_policy::MintingPolicyEntry::Multisig(id) => {
+                let bytes: [u8; 32] = id.clone().into();
+                Self::Multisig(key::Hash::from(bytes).into())
+            }
+            minting_policy::MintingPolicyEntry::TimeLock(date) => Self::TimeLock((*date).into()),
+            minting_policy::MintingPolicyEntry::MaxSupply(value) => {
+                Self::MaxSupply((*value).into())
+            }
+        }
+    }
+}
+
+impl From<MintingPolicyEntry> for minting_policy::MintingPolicyEntry {
+    fn from(val: MintingPolicyEntry) -> Self {
+        match val {
+            MintingPolicyEntry::Account(id) => Self::Account(id.to_inner()),
+            MintingPolicyEntry::Multisig(hash) => {
+                let bytes: [u8; 32] = key::Hash::from(hash).into();
+                Self::Multisig(bytes.into())
+            }
+            MintingPolicyEntry::TimeLock(date) => Self::TimeLock(date.into()),
+            MintingPolicyEntry::MaxSupply(value) => Self::MaxSupply(value.into()),
+        }
+    }
+}
+
+impl Serialize for MintingPolicy {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: serde::Serializer,
+    {
+        self.0
+            .entries()
+            .iter()
+            .map(MintingPolicyEntry::from)
+            .collect::<Vec<_>>()
+            .serialize(serializer)
+    }
+}
+
+impl<'de> Deserialize<'de> for MintingPolicy {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: serde::Deserializer<'de>,
+    {
+        let entries = Vec::<MintingPolicyEntry>::deserialize(deserializer)?;
+        entries
+            .into_iter()
+            .map(Into::into)
+            .try_fold(
+                minting_policy::MintingPolicy::new(),
+                minting_policy::MintingPolicy::with_entry,
+            )
+            .map(Self)
+            .map_err(<D::Error as serde::de::Error>::custom)
+    }
+}
+
 #[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
 pub struct MintToken {
     name: TokenName,
-    // TODO add a serde implementation for the MintingPolicy when it will be well specified
-    #[serde(skip)]
+    #[serde(default)]
     policy: MintingPolicy,
     to: Identifier,
     value: Value,
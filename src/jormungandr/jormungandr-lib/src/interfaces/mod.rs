@@ -69,7 +69,7 @@ pub use self::{
     },
     leadership_log::{LeadershipLog, LeadershipLogId, LeadershipLogStatus},
     linear_fee::{LinearFeeDef, PerCertificateFeeDef, PerVoteCertificateFeeDef},
-    mint_token::TokenIdentifier,
+    mint_token::{MintingPolicy, TokenIdentifier},
     old_address::OldAddress,
     peer_stats::{PeerRecord, PeerStats, Subscription},
     ratio::{ParseRatioError, Ratio},
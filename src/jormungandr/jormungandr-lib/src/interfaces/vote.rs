@@ -9,11 +9,12 @@ use chain_impl_mockchain::{
     value::Value,
     vote::{self, Choice, Options, Weight},
 };
-use chain_vote::MemberPublicKey;
+use chain_vote::{MemberPublicKey, WeightedBallotKind};
 use serde::{de::Visitor, Deserialize, Deserializer, Serialize, Serializer};
 use std::{
     convert::TryInto,
     fmt,
+    num::NonZeroU8,
     ops::Range,
     str::{self, FromStr},
 };
@@ -27,10 +28,11 @@ pub struct VotePrivacy(#[serde(with = "PayloadTypeDef")] pub vote::PayloadType);
 enum PayloadTypeDef {
     Public,
     Private,
+    PrivateWeighted,
 }
 
 #[derive(Debug, thiserror::Error)]
-#[error("Invalid vote privacy, expected \"public\" or \"private\".")]
+#[error("Invalid vote privacy, expected \"public\", \"private\" or \"private_weighted\".")]
 pub struct VotePrivacyFromStrError;
 
 impl FromStr for VotePrivacy {
@@ -39,6 +41,7 @@ impl FromStr for VotePrivacy {
         match s {
             "public" => Ok(VotePrivacy(vote::PayloadType::Public)),
             "private" => Ok(VotePrivacy(vote::PayloadType::Private)),
+            "private_weighted" => Ok(VotePrivacy(vote::PayloadType::PrivateWeighted)),
             _ => Err(VotePrivacyFromStrError),
         }
     }
@@ -49,6 +52,7 @@ impl fmt::Display for VotePrivacy {
         let s = match self.0 {
             vote::PayloadType::Public => "public",
             vote::PayloadType::Private => "private",
+            vote::PayloadType::PrivateWeighted => "private_weighted",
         };
         s.fmt(f)
     }
@@ -155,8 +159,16 @@ pub struct VotePlan {
     pub committee_end: BlockDate,
     #[serde(with = "serde_proposals")]
     pub proposals: Proposals,
+    #[serde(
+        with = "serde_weighted_ballot",
+        default,
+        skip_serializing_if = "Option::is_none"
+    )]
+    pub weighted_ballot: Option<WeightedBallotKind>,
     #[serde(with = "serde_committee_member_public_keys", default = "Vec::new")]
     pub committee_member_public_keys: Vec<chain_vote::MemberPublicKey>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub committee_threshold: Option<NonZeroU8>,
     pub voting_token: TokenIdentifier,
 }
 
@@ -192,6 +204,17 @@ enum ParametersGovernanceActionDef {
         #[serde(with = "ValueDef")]
         value: Value,
     },
+    UpdateFees {
+        constant: u64,
+        coefficient: u64,
+        certificate: u64,
+    },
+    SlotDuration {
+        duration: u8,
+    },
+    BlockContentMaxSize {
+        size: u32,
+    },
     NoOp,
 }
 
@@ -213,7 +236,9 @@ impl From<certificate::VotePlan> for VotePlan {
             committee_end: vp.committee_end().into(),
             proposals: vp.proposals().clone(),
             payload_type: vp.payload_type().into(),
+            weighted_ballot: vp.weighted_ballot().copied(),
             committee_member_public_keys: vp.committee_public_keys().to_vec(),
+            committee_threshold: vp.committee_threshold(),
             voting_token: vp.voting_token().clone().into(),
         }
     }
@@ -227,12 +252,44 @@ impl From<VotePlan> for certificate::VotePlan {
             vpd.committee_end.into(),
             vpd.proposals,
             vpd.payload_type.into(),
+            vpd.weighted_ballot,
             vpd.committee_member_public_keys,
+            vpd.committee_threshold,
             vpd.voting_token.into(),
         )
     }
 }
 
+#[derive(Deserialize, Serialize)]
+#[serde(remote = "WeightedBallotKind", rename_all = "snake_case")]
+enum WeightedBallotKindDef {
+    Approval { max_selections: NonZeroU8 },
+    Budget { budget: NonZeroU8 },
+}
+
+pub mod serde_weighted_ballot {
+    use super::WeightedBallotKindDef;
+    use chain_vote::WeightedBallotKind;
+    use serde::{Deserialize, Deserializer, Serialize, Serializer};
+
+    #[derive(Deserialize, Serialize)]
+    struct Wrapper(#[serde(with = "WeightedBallotKindDef")] WeightedBallotKind);
+
+    pub fn deserialize<'de, D>(deserializer: D) -> Result<Option<WeightedBallotKind>, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        Ok(Option::<Wrapper>::deserialize(deserializer)?.map(|Wrapper(kind)| kind))
+    }
+
+    pub fn serialize<S>(kind: &Option<WeightedBallotKind>, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        kind.map(Wrapper).serialize(serializer)
+    }
+}
+
 pub mod serde_committee_member_public_keys {
     use crate::interfaces::vote::SerdeMemberPublicKey;
     use serde::{
@@ -449,6 +506,8 @@ pub struct VotePlanStatus {
     pub committee_end: BlockDate,
     #[serde(with = "serde_committee_member_public_keys")]
     pub committee_member_keys: Vec<MemberPublicKey>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub committee_threshold: Option<NonZeroU8>,
     pub proposals: Vec<VoteProposalStatus>,
     pub voting_token: TokenIdentifier,
 }
@@ -591,6 +650,12 @@ pub enum VotePayload {
         #[serde(with = "serde_base64_bytes")]
         proof: Vec<u8>,
     },
+    PrivateWeighted {
+        #[serde(with = "serde_base64_bytes")]
+        encrypted_vote: Vec<u8>,
+        #[serde(with = "serde_base64_bytes")]
+        proof: Vec<u8>,
+    },
 }
 
 #[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
@@ -615,6 +680,13 @@ impl From<vote::Payload> for VotePayload {
                 encrypted_vote: encrypted_vote.serialize().into(),
                 proof: proof.serialize().into(),
             },
+            vote::Payload::PrivateWeighted {
+                encrypted_vote,
+                proof,
+            } => Self::PrivateWeighted {
+                encrypted_vote: encrypted_vote.serialize().into(),
+                proof: proof.serialize().into(),
+            },
         }
     }
 }
@@ -623,7 +695,7 @@ impl VotePayload {
     pub fn choice(&self) -> Option<u8> {
         match self {
             VotePayload::Public { choice } => Some(*choice),
-            VotePayload::Private { .. } => None,
+            VotePayload::Private { .. } | VotePayload::PrivateWeighted { .. } => None,
         }
     }
 }
@@ -740,6 +812,7 @@ impl From<vote::VotePlanStatus> for VotePlanStatus {
             committee_end: this.committee_end.into(),
             payload: this.payload,
             committee_member_keys: this.committee_public_keys,
+            committee_threshold: this.committee_threshold,
             proposals: this.proposals.into_iter().map(|p| p.into()).collect(),
             voting_token: this.voting_token.into(),
         }
@@ -755,6 +828,7 @@ impl From<VotePlanStatus> for vote::VotePlanStatus {
             committee_end: vote_plan_status.committee_end.into(),
             payload: vote_plan_status.payload,
             committee_public_keys: vote_plan_status.committee_member_keys,
+            committee_threshold: vote_plan_status.committee_threshold,
             proposals: vote_plan_status
                 .proposals
                 .into_iter()
@@ -819,7 +893,9 @@ mod test {
             tally,
             proposals,
             vote::PayloadType::Private,
+            None,
             vec![member_key],
+            None,
             voting_token,
         )
         .into();
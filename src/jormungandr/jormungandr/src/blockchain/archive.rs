@@ -0,0 +1,242 @@
+use crate::{
+    blockcfg::{ChainLength, HeaderHash},
+    blockchain::{Ref, Storage, StorageError},
+};
+#[cfg(feature = "evm")]
+use chain_impl_mockchain::{fragment::FragmentId, ledger::EvmReceipt};
+use lru::LruCache;
+#[cfg(feature = "evm")]
+use std::collections::{BTreeMap, HashMap};
+use std::sync::Arc;
+use tokio::sync::Mutex;
+
+/// object that keeps the history of the blocks applied by the node, when
+/// the node runs in archive mode.
+///
+/// * the [`Ref`] of the most recently applied or accessed blocks are kept
+///   in a LRU cache of a fixed capacity, the `Ledger` state after an evicted
+///   block is rebuilt from the storage by [`Blockchain::archived_ref`];
+/// * the receipts of the EVM transactions are kept for every block that
+///   contains some, so the logs of any block remain available. The blocks
+///   of the branches abandoned below the stability depth are removed from
+///   this index by [`Archive::prune`].
+///
+/// The chain lengths and the parents of the blocks are read from the
+/// storage. The EVM index is derived from the blocks of the storage: it is
+/// rebuilt when the blocks of the main branch are applied again on startup.
+///
+/// [`Blockchain::archived_ref`]: super::Blockchain::archived_ref
+///
+/// [`Ref`]: ./struct.Ref.html
+#[derive(Clone)]
+pub struct Archive {
+    storage: Storage,
+    inner: Arc<Mutex<ArchiveData>>,
+}
+
+struct ArchiveData {
+    refs: LruCache<HeaderHash, Arc<Ref>>,
+    #[cfg(feature = "evm")]
+    evm_receipts: HashMap<HeaderHash, Arc<[EvmReceipt]>>,
+    /// the blocks in which every EVM transaction was applied, on any branch
+    #[cfg(feature = "evm")]
+    evm_transactions: HashMap<FragmentId, Vec<HeaderHash>>,
+    /// the indexed blocks not yet below the stability depth, by chain length
+    #[cfg(feature = "evm")]
+    evm_unstable_blocks: BTreeMap<u32, Vec<HeaderHash>>,
+}
+
+/// the receipts of the EVM transactions applied in a block
+#[cfg(feature = "evm")]
+pub struct BlockReceipts {
+    pub hash: HeaderHash,
+    pub chain_length: ChainLength,
+    pub receipts: Arc<[EvmReceipt]>,
+}
+
+#[cfg(feature = "evm")]
+impl BlockReceipts {
+    pub fn new(reference: &Ref) -> Self {
+        BlockReceipts {
+            hash: reference.hash(),
+            chain_length: reference.chain_length(),
+            receipts: reference.ledger().evm_receipts().into(),
+        }
+    }
+}
+
+impl Archive {
+    /// create a new `Archive` keeping the `Ref` of at most `cap` blocks
+    pub fn new(storage: Storage, cap: usize) -> Self {
+        Archive {
+            storage,
+            inner: Arc::new(Mutex::new(ArchiveData {
+                refs: LruCache::new(cap),
+                #[cfg(feature = "evm")]
+                evm_receipts: HashMap::new(),
+                #[cfg(feature = "evm")]
+                evm_transactions: HashMap::new(),
+                #[cfg(feature = "evm")]
+                evm_unstable_blocks: BTreeMap::new(),
+            })),
+        }
+    }
+
+    /// record the given [`Ref`] and index the EVM transactions applied
+    /// in its block
+    pub async fn insert(&self, reference: Arc<Ref>) {
+        let mut guard = self.inner.lock().await;
+        #[cfg(feature = "evm")]
+        {
+            let receipts = reference.ledger().evm_receipts();
+            // the `Ref` of an evicted block is inserted again when rebuilt
+            if !receipts.is_empty() && !guard.evm_receipts.contains_key(&reference.hash()) {
+                for receipt in receipts {
+                    guard
+                        .evm_transactions
+                        .entry(receipt.fragment_id)
+                        .or_default()
+                        .push(reference.hash());
+                }
+                guard.evm_receipts.insert(reference.hash(), receipts.into());
+                guard
+                    .evm_unstable_blocks
+                    .entry(u32::from(reference.chain_length()))
+                    .or_default()
+                    .push(reference.hash());
+            }
+        }
+        guard.refs.put(reference.hash(), reference);
+    }
+
+    /// get the hash of the ancestor of `tip` (or `tip` itself) with the given
+    /// chain length, `None` if there is no such block
+    pub fn ancestor(
+        &self,
+        tip: HeaderHash,
+        chain_length: ChainLength,
+    ) -> Result<Option<HeaderHash>, StorageError> {
+        let tip_chain_length = match self.storage.get_chain_length(tip) {
+            Some(tip_chain_length) => tip_chain_length,
+            None => return Ok(None),
+        };
+        let distance = match tip_chain_length.checked_sub(u32::from(chain_length)) {
+            Some(distance) => distance,
+            None => return Ok(None),
+        };
+        Ok(self
+            .storage
+            .get_nth_ancestor(tip, distance)?
+            .map(|block| block.header().hash()))
+    }
+
+    /// get the [`Ref`] of the given block, `None` if it was evicted from the
+    /// archive
+    pub async fn get(&self, header_hash: &HeaderHash) -> Option<Arc<Ref>> {
+        let mut guard = self.inner.lock().await;
+        guard.refs.get(header_hash).map(Arc::clone)
+    }
+
+    /// remove from the EVM index the blocks with a chain length up to
+    /// `stable_chain_length` that are not on the branch of `main_tip`: these
+    /// branches are abandoned and are pruned from the storage
+    #[cfg(feature = "evm")]
+    pub async fn prune(&self, main_tip: HeaderHash, stable_chain_length: u32) {
+        let mut guard = self.inner.lock().await;
+        let unstable_blocks = guard
+            .evm_unstable_blocks
+            .split_off(&stable_chain_length.saturating_add(1));
+        let stable_blocks = std::mem::replace(&mut guard.evm_unstable_blocks, unstable_blocks);
+        for header_hash in stable_blocks.into_values().flatten() {
+            if header_hash == main_tip || self.storage.is_ancestor(header_hash, main_tip) {
+                continue;
+            }
+            let receipts = match guard.evm_receipts.remove(&header_hash) {
+                Some(receipts) => receipts,
+                None => continue,
+            };
+            for receipt in receipts.iter() {
+                if let Some(blocks) = guard.evm_transactions.get_mut(&receipt.fragment_id) {
+                    blocks.retain(|block| *block != header_hash);
+                    if blocks.is_empty() {
+                        guard.evm_transactions.remove(&receipt.fragment_id);
+                    }
+                }
+            }
+        }
+    }
+
+    /// get the EVM receipts of the given block, `None` if the block is not
+    /// in the storage
+    #[cfg(feature = "evm")]
+    pub async fn receipts(&self, header_hash: HeaderHash) -> Option<BlockReceipts> {
+        let chain_length = self.storage.get_chain_length(header_hash)?;
+        let guard = self.inner.lock().await;
+        Some(BlockReceipts {
+            hash: header_hash,
+            chain_length: chain_length.into(),
+            receipts: guard
+                .evm_receipts
+                .get(&header_hash)
+                .map(Arc::clone)
+                .unwrap_or_else(|| Vec::new().into()),
+        })
+    }
+
+    /// get the EVM receipts of the ancestors of `tip` (including `tip`
+    /// itself) with a chain length within `from..=to`, oldest first. The
+    /// blocks without any EVM transaction are skipped.
+    #[cfg(feature = "evm")]
+    pub async fn branch_receipts(
+        &self,
+        tip: HeaderHash,
+        from: ChainLength,
+        to: ChainLength,
+    ) -> Result<Vec<BlockReceipts>, StorageError> {
+        let tip_chain_length = match self.storage.get_chain_length(tip) {
+            Some(tip_chain_length) => tip_chain_length,
+            None => return Ok(Vec::new()),
+        };
+        // start from the last block of the range
+        let to = u32::from(to).min(tip_chain_length);
+        let mut current = match self.storage.get_nth_ancestor(tip, tip_chain_length - to)? {
+            Some(block) => Some(block.header().hash()),
+            None => return Ok(Vec::new()),
+        };
+        let mut chain_length = ChainLength::from(to);
+        let mut branch = Vec::new();
+        while let Some(header_hash) = current {
+            if chain_length < from {
+                break;
+            }
+            let guard = self.inner.lock().await;
+            if let Some(receipts) = guard.evm_receipts.get(&header_hash) {
+                branch.push(BlockReceipts {
+                    hash: header_hash,
+                    chain_length,
+                    receipts: Arc::clone(receipts),
+                });
+            }
+            drop(guard);
+            if chain_length == ChainLength::from(0) {
+                break;
+            }
+            current = self.storage.get_parent(header_hash)?;
+            chain_length = ChainLength::from(u32::from(chain_length) - 1);
+        }
+        branch.reverse();
+        Ok(branch)
+    }
+
+    /// get the hashes of the blocks in which the given EVM transaction was
+    /// applied. The blocks may not belong to the main branch.
+    #[cfg(feature = "evm")]
+    pub async fn evm_transaction_blocks(&self, fragment_id: &FragmentId) -> Vec<HeaderHash> {
+        let guard = self.inner.lock().await;
+        guard
+            .evm_transactions
+            .get(fragment_id)
+            .cloned()
+            .unwrap_or_default()
+    }
+}
@@ -55,10 +55,10 @@ use crate::{
         Block, Block0Error, BlockDate, ChainLength, Epoch, EpochRewardsInfo, Header, HeaderDesc,
         HeaderHash, Leadership, Ledger, RewardsInfoParameters,
     },
-    blockchain::{Branch, Checkpoints, Multiverse, Ref, Storage, StorageError, Tip},
+    blockchain::{Archive, Branch, Checkpoints, Multiverse, Ref, Storage, StorageError, Tip},
 };
 use chain_impl_mockchain::{leadership::Verification, ledger};
-use chain_time::TimeFrame;
+use chain_time::{EpochPosition, EpochSlotOffset, Slot, SlotDuration, TimeFrame};
 use futures::{StreamExt, TryStreamExt};
 use std::sync::Arc;
 
@@ -165,12 +165,17 @@ pub fn pre_verify_link(
 /// * `RefCache`: a cache of blocks headers and associated states;
 /// * `Multiverse`: of ledger. It is a cache of different ledger states.
 ///
+/// In archive mode it also keeps an `Archive` of the states after the
+/// recently applied blocks and of the EVM receipts of every block.
+///
 #[derive(Clone)]
 pub struct Blockchain {
     ref_cache: RefCache,
 
     ledgers: Multiverse<Ledger>,
 
+    archive: Option<Archive>,
+
     storage: Storage,
 
     block0: HeaderHash,
@@ -268,10 +273,12 @@ impl Blockchain {
         storage: Storage,
         cache_capacity: usize,
         rewards_report_all: bool,
+        archive: bool,
     ) -> Self {
         Blockchain {
             ref_cache: RefCache::new(cache_capacity),
             ledgers: Multiverse::new(),
+            archive: archive.then(|| Archive::new(storage.clone(), cache_capacity)),
             storage,
             block0,
             rewards_report_all,
@@ -286,6 +293,71 @@ impl Blockchain {
         &self.storage
     }
 
+    /// the archive of all the applied blocks, `None` if the node does not
+    /// run in archive mode
+    pub fn archive(&self) -> Option<&Archive> {
+        self.archive.as_ref()
+    }
+
+    /// get the [`Ref`] of the ancestor of `tip` (or `tip` itself) with the
+    /// given chain length, `None` if there is no such block or if the node
+    /// does not run in archive mode
+    ///
+    /// The `Ref` of a block evicted from the archive is rebuilt, applying
+    /// again the blocks from the storage since its closest ancestor still in
+    /// the archive, or since the block0.
+    pub async fn archived_ref(
+        &self,
+        tip: HeaderHash,
+        chain_length: ChainLength,
+    ) -> Result<Option<Arc<Ref>>> {
+        let archive = match &self.archive {
+            Some(archive) => archive,
+            None => return Ok(None),
+        };
+        let mut header_hash = match archive.ancestor(tip, chain_length)? {
+            Some(header_hash) => header_hash,
+            None => return Ok(None),
+        };
+
+        let mut evicted = Vec::new();
+        let mut reference = loop {
+            if let Some(reference) = archive.get(&header_hash).await {
+                break reference;
+            }
+            if header_hash == self.block0 {
+                let block0 = self
+                    .storage
+                    .get(header_hash)?
+                    .ok_or(Error::MissingParentBlock(header_hash))?;
+                break self.apply_block0(&block0).await?.get_ref();
+            }
+            evicted.push(header_hash);
+            header_hash = self
+                .storage
+                .get_parent(header_hash)?
+                .ok_or(Error::MissingParentBlock(header_hash))?;
+        };
+
+        for header_hash in evicted.into_iter().rev() {
+            let block = self
+                .storage
+                .get(header_hash)?
+                .ok_or(Error::MissingParentBlock(header_hash))?;
+            let post_checked = self
+                .post_check_header(
+                    block.header().clone(),
+                    reference,
+                    CheckHeaderProof::SkipFromStorage,
+                )
+                .await?;
+            let ledger = self.apply_block_dry_run(&post_checked, &block)?;
+            self.apply_block_check_rewards(&post_checked, &ledger)?;
+            reference = self.apply_block_finalize(post_checked, ledger).await;
+        }
+        Ok(Some(reference))
+    }
+
     pub async fn branches(&self) -> Result<Vec<Branch>> {
         futures::stream::iter(self.storage().get_branches()?)
             // FIXME: this should always return a valid ref, as the branches
@@ -301,6 +373,12 @@ impl Blockchain {
         let depth = tip.ledger().settings().epoch_stability_depth;
         self.ledgers.gc(depth).await;
         self.storage.gc(depth, tip.hash().as_ref())?;
+        #[cfg(feature = "evm")]
+        if let Some(archive) = &self.archive {
+            if let Some(stable_chain_length) = u32::from(tip.chain_length()).checked_sub(depth) {
+                archive.prune(tip.hash(), stable_chain_length).await;
+            }
+        }
         Ok(())
     }
 
@@ -332,6 +410,9 @@ impl Blockchain {
         );
         let reference = Arc::new(reference);
         ref_cache.insert(header_hash, Arc::clone(&reference)).await;
+        if let Some(archive) = &self.archive {
+            archive.insert(Arc::clone(&reference)).await;
+        }
         reference
     }
 
@@ -759,7 +840,8 @@ impl Blockchain {
         while let Some(block) = block_stream.next().await.transpose()? {
             reporter.append_block(&block);
             // this is a stream of consecutive blocks up to the last known main branch,
-            // no need for special rules for updating the tip
+            // no need for special rules for updating the tip. The EVM index of the
+            // archive is rebuilt as the blocks are applied
             last_ref = self
                 .handle_bootstrap_block(block, CheckHeaderProof::SkipFromStorage)
                 .await?;
@@ -839,9 +921,6 @@ pub fn new_epoch_leadership_from(
     let parent_date = parent.block_date();
 
     if parent_date.epoch < epoch {
-        // TODO: the time frame may change in the future, we will need to handle this
-        //       special case but it is not actually clear how to modify the time frame
-        //       for the blockchain
         use chain_impl_mockchain::chaintypes::ConsensusVersion;
 
         let ledger = parent_ledger_state
@@ -882,13 +961,18 @@ pub fn new_epoch_leadership_from(
             transition_state.clone()
         };
 
+        // 3. the slot duration may have been changed by a parameters governance
+        //    action, in which case the time frame restarts at the first slot of
+        //    the new epoch
+        let time_frame = new_epoch_time_frame(epoch, &transition_state, parent_time_frame);
+
         let leadership = Arc::new(Leadership::new(epoch, &epoch_state));
         let previous_epoch_state = Some(parent);
         EpochLeadership {
             state: transition_state,
             leadership,
             rewards_info: epoch_rewards_info,
-            time_frame: parent_time_frame,
+            time_frame,
             previous_state: previous_epoch_state,
         }
     } else {
@@ -902,6 +986,26 @@ pub fn new_epoch_leadership_from(
     }
 }
 
+fn new_epoch_time_frame(
+    epoch: Epoch,
+    transition_state: &Ledger,
+    parent_time_frame: Arc<TimeFrame>,
+) -> Arc<TimeFrame> {
+    let slot_duration = transition_state.settings().slot_duration;
+    if u64::from(slot_duration) == parent_time_frame.slot_duration() {
+        return parent_time_frame;
+    }
+
+    let epoch_start = transition_state.era().from_era_to_slot(EpochPosition {
+        epoch: chain_time::Epoch(epoch),
+        slot: EpochSlotOffset(0),
+    });
+    let offset = Slot(epoch_start.0 - parent_time_frame.slot0().0);
+    Arc::new(
+        parent_time_frame.change_frame(offset, SlotDuration::from_secs(u32::from(slot_duration))),
+    )
+}
+
 pub struct StreamReporter<R> {
     stream_info: StreamInfo,
     report: R,
@@ -1,3 +1,4 @@
+mod archive;
 mod bootstrap;
 mod branch;
 mod candidate;
@@ -26,6 +27,7 @@ mod chunk_sizes {
 // Re-exports
 
 pub use self::{
+    archive::Archive,
     bootstrap::{bootstrap_from_stream, Error as BootstrapError},
     branch::Branch,
     chain::{
@@ -40,3 +42,5 @@ pub use self::{
     storage::{Error as StorageError, Storage},
     tip::Tip,
 };
+#[cfg(feature = "evm")]
+pub use self::archive::BlockReceipts;
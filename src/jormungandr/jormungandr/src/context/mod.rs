@@ -39,6 +39,7 @@ pub struct Context {
     blockchain: Option<Blockchain>,
     blockchain_tip: Option<Tip>,
     bootstrap_stopper: Option<CancellationToken>,
+    jrpc_notifier: crate::jrpc::Notifier,
     #[cfg(feature = "evm")]
     evm_filters: crate::jrpc::EvmFilters,
 }
@@ -78,6 +79,7 @@ impl Context {
             blockchain: Default::default(),
             blockchain_tip: Default::default(),
             bootstrap_stopper: Default::default(),
+            jrpc_notifier: Default::default(),
             #[cfg(feature = "evm")]
             evm_filters: Default::default(),
         }
@@ -141,6 +143,10 @@ impl Context {
         self.blockchain_tip.as_ref().ok_or(Error::BlockchainTip)
     }
 
+    pub fn jrpc_notifier(&self) -> &crate::jrpc::Notifier {
+        &self.jrpc_notifier
+    }
+
     #[cfg(feature = "evm")]
     pub fn evm_filters(&mut self) -> &mut crate::jrpc::EvmFilters {
         &mut self.evm_filters
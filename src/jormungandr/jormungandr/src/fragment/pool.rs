@@ -1,5 +1,5 @@
 use crate::{
-    blockcfg::ApplyBlockLedger,
+    blockcfg::{ApplyBlockLedger, Ledger},
     blockchain::{Ref, Tip},
     fragment::{
         selection::{
@@ -9,6 +9,7 @@ use crate::{
         Fragment, FragmentId, Logs,
     },
     intercom::{NetworkMsg, PropagateMsg},
+    jrpc::Notifier,
     metrics::{Metrics, MetricsBackend},
     utils::async_msg::MessageBox,
 };
@@ -24,7 +25,7 @@ use jormungandr_lib::{
     },
     time::SecondsSinceUnixEpoch,
 };
-use std::mem;
+use std::{collections::HashSet, mem};
 use thiserror::Error;
 use tokio::{
     fs::File,
@@ -45,6 +46,7 @@ pub struct Pool {
     persistent_log: Option<BufWriter<File>>,
     tip: Tip,
     metrics: Metrics,
+    jrpc_notifier: Option<Notifier>,
 }
 
 #[derive(Debug, Error)]
@@ -61,6 +63,7 @@ impl Pool {
         persistent_log: Option<File>,
         tip: Tip,
         metrics: Metrics,
+        jrpc_notifier: Option<Notifier>,
     ) -> Self {
         Pool {
             logs,
@@ -70,6 +73,7 @@ impl Pool {
                 .map(|file| BufWriter::with_capacity(DEFAULT_BUF_SIZE, file)),
             tip,
             metrics,
+            jrpc_notifier,
         }
     }
 
@@ -128,6 +132,10 @@ impl Pool {
             return Err(FragmentRejectionReason::FragmentInvalid);
         }
 
+        Ok(())
+    }
+
+    async fn write_persistent_log(&mut self, fragment: &Fragment) {
         if let Some(persistent_log) = self.persistent_log.as_mut() {
             let entry = PersistentFragmentLog {
                 time: SecondsSinceUnixEpoch::now(),
@@ -144,9 +152,6 @@ impl Pool {
                 tracing::error!(err = %err, "failed to write persistent fragment log entry");
             }
         }
-
-        tracing::debug!("including fragment to the pool");
-        Ok(())
     }
 
     /// Returns number of registered fragments. Setting `fail_fast` to `true` will force this
@@ -192,6 +197,31 @@ impl Pool {
             }
         }
 
+        // the proofs of the private votes are verified together, which is much
+        // cheaper than verifying them one by one
+        let invalid_votes = invalid_private_votes(&ledger, &filtered_fragments);
+        let mut verified_fragments = Vec::with_capacity(filtered_fragments.len());
+        let mut previous_invalid = false;
+        for (fragment, id) in filtered_fragments {
+            if previous_invalid {
+                rejected.push(RejectedFragmentInfo {
+                    id,
+                    reason: FragmentRejectionReason::PreviousFragmentInvalid,
+                });
+            } else if invalid_votes.contains(&id) {
+                tracing::debug!(fragment_id=?id, "vote proof is invalid, not including to the pool");
+                rejected.push(RejectedFragmentInfo {
+                    id,
+                    reason: FragmentRejectionReason::FragmentInvalid,
+                });
+                previous_invalid = fail_fast;
+            } else {
+                tracing::debug!(fragment_id=?id, "including fragment to the pool");
+                self.write_persistent_log(&fragment).await;
+                verified_fragments.push((fragment, id));
+            }
+        }
+
         // flush every request to minimize possibility of losing fragments at the expense of non optimal performance
         if let Some(persistent_log) = self.persistent_log.as_mut() {
             if let Err(error) = persistent_log.flush().await {
@@ -214,7 +244,7 @@ impl Pool {
         let span = tracing::trace_span!("pool_insert_fragment");
         let _enter = span.enter();
 
-        let mut fragments = filtered_fragments.into_iter();
+        let mut fragments = verified_fragments.into_iter();
         let new_fragments = self.pool.insert_all(fragments.by_ref());
         let count = new_fragments.len();
         tracing::debug!("{} of the received fragments were added to the pool", count);
@@ -231,6 +261,9 @@ impl Pool {
         for (fragment, id) in new_fragments {
             tracing::debug!(fragment_id=?id, "inserted fragment to the pool");
             accepted.push(id);
+            if let Some(jrpc_notifier) = &self.jrpc_notifier {
+                jrpc_notifier.notify_new_fragment(&fragment, id);
+            }
             let fragment_msg = NetworkMsg::Propagate(Box::new(PropagateMsg::Fragment(fragment)));
             network_msg_box
                 .send(fragment_msg)
@@ -359,6 +392,8 @@ fn is_fragment_valid(fragment: &Fragment) -> bool {
         Fragment::VoteCast(ref tx) => is_transaction_valid(tx),
         Fragment::VoteTally(ref tx) => is_transaction_valid(tx),
         Fragment::MintToken(ref tx) => is_transaction_valid(tx),
+        Fragment::BurnToken(ref tx) => is_transaction_valid(tx),
+        Fragment::VoteDelegation(ref tx) => is_transaction_valid(tx),
         // evm stuff
         // TODO, maybe we need to develop some evm specific stateless validation in this place
         Fragment::Evm(_) => true,
@@ -370,6 +405,30 @@ fn is_transaction_valid<E>(tx: &Transaction<E>) -> bool {
     tx.verify_possibly_balanced().is_ok()
 }
 
+/// Verifies the proofs of the private votes among `fragments` against the vote
+/// plans of `ledger`, all at once. Only if the batch is invalid are the votes
+/// verified one by one, to find out which ones have an invalid proof.
+fn invalid_private_votes(
+    ledger: &Ledger,
+    fragments: &[(Fragment, FragmentId)],
+) -> HashSet<FragmentId> {
+    let (votes, ids): (Vec<_>, Vec<_>) = fragments
+        .iter()
+        .filter_map(|(fragment, id)| match fragment {
+            Fragment::VoteCast(tx) => Some((tx.as_slice().payload().into_payload(), *id)),
+            _ => None,
+        })
+        .unzip();
+
+    ledger
+        .verify_private_vote_proofs(&votes)
+        .into_iter()
+        .zip(ids)
+        .filter(|(valid, _)| !valid)
+        .map(|(_, id)| id)
+        .collect()
+}
+
 fn get_transaction_expiry_date(fragment: &Fragment) -> Option<BlockDate> {
     match fragment {
         Fragment::Initial(_) => None,
@@ -387,6 +446,8 @@ fn get_transaction_expiry_date(fragment: &Fragment) -> Option<BlockDate> {
         Fragment::VoteCast(tx) => Some(tx.as_slice().valid_until()),
         Fragment::VoteTally(tx) => Some(tx.as_slice().valid_until()),
         Fragment::MintToken(tx) => Some(tx.as_slice().valid_until()),
+        Fragment::BurnToken(tx) => Some(tx.as_slice().valid_until()),
+        Fragment::VoteDelegation(tx) => Some(tx.as_slice().valid_until()),
         Fragment::EvmMapping(tx) => Some(tx.as_slice().valid_until()),
     }
 }
@@ -779,3 +840,92 @@ pub(super) mod internal {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chain_core::property::Fragment as _;
+    use chain_impl_mockchain::{
+        certificate::{VoteCast, VotePlan},
+        testing::{
+            scenario::{prepare_scenario, proposal, vote_plan, wallet},
+            TestGen, VoteTestGen,
+        },
+        tokens::name::{TokenName, TOKEN_NAME_MAX_SIZE},
+        vote::{Choice, Payload, PayloadType},
+    };
+
+    #[test]
+    fn only_the_votes_with_an_invalid_proof_are_rejected() {
+        let aliases = ["Alice", "Bob", "Clarice"];
+        let mut rng = TestGen::rand();
+        let members = VoteTestGen::committee_members_manager(3, 2);
+        let voting_token = TokenName::try_from(vec![0u8; TOKEN_NAME_MAX_SIZE]).unwrap();
+
+        let mut initials: Vec<_> = aliases
+            .iter()
+            .map(|alias| {
+                let mut initial = wallet(alias);
+                initial.with(1_000).with_token(voting_token.clone(), 1_000);
+                initial
+            })
+            .collect();
+        initials[0].committee_member();
+
+        let (ledger, controller) = prepare_scenario()
+            .with_initials(initials.iter_mut().collect())
+            .with_vote_plans(vec![vote_plan("fund1")
+                .owner(aliases[0])
+                .consecutive_epoch_dates()
+                .payload_type(PayloadType::Private)
+                .committee_keys(members.members_keys())
+                .with_proposal(proposal(VoteTestGen::external_proposal_id()).options(3))])
+            .build()
+            .unwrap();
+
+        let vote_plan: VotePlan = controller.vote_plan("fund1").unwrap().into();
+        let proposal = &vote_plan.proposals()[0];
+        let mut votes: Vec<_> = (0..3)
+            .map(|i| {
+                VoteTestGen::private_vote_cast_payload_for(
+                    &vote_plan,
+                    proposal,
+                    Choice::new(i),
+                    &mut rng,
+                )
+            })
+            .collect();
+
+        // the proof of the second vote does not match its encrypted vote anymore
+        votes[1] = match (&votes[1], &votes[2]) {
+            (
+                Payload::Private { proof, .. },
+                Payload::Private {
+                    encrypted_vote: other_encrypted_vote,
+                    ..
+                },
+            ) => Payload::private(other_encrypted_vote.clone(), proof.clone()),
+            _ => unreachable!("private votes"),
+        };
+
+        let fragments: Vec<_> = aliases
+            .iter()
+            .zip(votes)
+            .map(|(alias, payload)| {
+                let fragment = controller.fragment_factory().vote_cast(
+                    ledger.date(),
+                    &controller.wallet(alias).unwrap(),
+                    VoteCast::new(vote_plan.to_id(), 0, payload),
+                );
+                let id = fragment.id();
+                (fragment, id)
+            })
+            .collect();
+
+        assert_eq!(
+            invalid_private_votes(&ledger.ledger, &fragments),
+            std::iter::once(fragments[1].1).collect::<HashSet<_>>()
+        );
+        assert!(invalid_private_votes(&ledger.ledger, &fragments[..1]).is_empty());
+    }
+}
@@ -2,6 +2,7 @@ use crate::{
     blockchain::Tip,
     fragment::{Logs, Pool},
     intercom::{NetworkMsg, TransactionMsg},
+    jrpc::Notifier,
     metrics::{Metrics, MetricsBackend},
     utils::{
         async_msg::{MessageBox, MessageQueue},
@@ -26,6 +27,7 @@ pub struct Process {
     pool_max_entries: usize,
     logs_max_entries: usize,
     network_msg_box: MessageBox<NetworkMsg>,
+    jrpc_notifier: Option<Notifier>,
 }
 
 #[derive(Debug, Error)]
@@ -41,11 +43,13 @@ impl Process {
         pool_max_entries: usize,
         logs_max_entries: usize,
         network_msg_box: MessageBox<NetworkMsg>,
+        jrpc_notifier: Option<Notifier>,
     ) -> Self {
         Process {
             pool_max_entries,
             logs_max_entries,
             network_msg_box,
+            jrpc_notifier,
         }
     }
 
@@ -112,7 +116,8 @@ impl Process {
                 self.network_msg_box,
                 persistent_log,
                 tip,
-                stats_counter.clone()
+                stats_counter.clone(),
+                self.jrpc_notifier,
             );
             loop {
                 tokio::select! {
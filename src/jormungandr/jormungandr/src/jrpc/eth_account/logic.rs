@@ -1,6 +1,7 @@
 use crate::{
     context::Context,
     jrpc::{
+        eth_block_info::get_ledger_by_number_from_context,
         eth_types::{block_number::BlockNumber, bytes::Bytes, number::Number},
         Error,
     },
@@ -21,15 +22,15 @@ pub async fn get_transaction_count(
     block_number: BlockNumber,
     context: &Context,
 ) -> Result<Number, Error> {
-    match block_number {
-        BlockNumber::Latest => {
-            let ledger = context.blockchain_tip()?.get_ref().await.ledger();
-            let address = ledger.get_jormungandr_mapped_address(&address);
-            let account = ledger.accounts().get_state(&address)?;
-            Ok(account.evm_state.nonce.into())
-        }
-        _ => Err(Error::NonArchiveNode),
-    }
+    let ledger = get_ledger_by_number_from_context(
+        block_number,
+        context.blockchain()?,
+        context.blockchain_tip()?.get_ref().await,
+    )
+    .await?;
+    let address = ledger.get_jormungandr_mapped_address(&address);
+    let account = ledger.accounts().get_state(&address)?;
+    Ok(account.evm_state.nonce.into())
 }
 
 pub async fn get_balance(
@@ -37,15 +38,15 @@ pub async fn get_balance(
     block_number: BlockNumber,
     context: &Context,
 ) -> Result<Number, Error> {
-    match block_number {
-        BlockNumber::Latest => {
-            let ledger = context.blockchain_tip()?.get_ref().await.ledger();
-            let address = ledger.get_jormungandr_mapped_address(&address);
-            let account = ledger.accounts().get_state(&address)?;
-            Ok(account.value.0.into())
-        }
-        _ => Err(Error::NonArchiveNode),
-    }
+    let ledger = get_ledger_by_number_from_context(
+        block_number,
+        context.blockchain()?,
+        context.blockchain_tip()?.get_ref().await,
+    )
+    .await?;
+    let address = ledger.get_jormungandr_mapped_address(&address);
+    let account = ledger.accounts().get_state(&address)?;
+    Ok(account.value.0.into())
 }
 
 pub async fn get_code(
@@ -53,15 +54,15 @@ pub async fn get_code(
     block_number: BlockNumber,
     context: &Context,
 ) -> Result<Bytes, Error> {
-    match block_number {
-        BlockNumber::Latest => {
-            let ledger = context.blockchain_tip()?.get_ref().await.ledger();
-            let address = ledger.get_jormungandr_mapped_address(&address);
-            let account = ledger.accounts().get_state(&address)?;
-            Ok(account.evm_state.code.clone().into())
-        }
-        _ => Err(Error::NonArchiveNode),
-    }
+    let ledger = get_ledger_by_number_from_context(
+        block_number,
+        context.blockchain()?,
+        context.blockchain_tip()?.get_ref().await,
+    )
+    .await?;
+    let address = ledger.get_jormungandr_mapped_address(&address);
+    let account = ledger.accounts().get_state(&address)?;
+    Ok(account.evm_state.code.clone().into())
 }
 
 pub async fn get_storage_at(
@@ -70,18 +71,18 @@ pub async fn get_storage_at(
     block_number: BlockNumber,
     context: &Context,
 ) -> Result<H256, Error> {
-    match block_number {
-        BlockNumber::Latest => {
-            let ledger = context.blockchain_tip()?.get_ref().await.ledger();
-            let address = ledger.get_jormungandr_mapped_address(&address);
-            let account = ledger.accounts().get_state(&address)?;
-            Ok(account
-                .evm_state
-                .storage
-                .get(&key)
-                .cloned()
-                .unwrap_or_default())
-        }
-        _ => Err(Error::NonArchiveNode),
-    }
+    let ledger = get_ledger_by_number_from_context(
+        block_number,
+        context.blockchain()?,
+        context.blockchain_tip()?.get_ref().await,
+    )
+    .await?;
+    let address = ledger.get_jormungandr_mapped_address(&address);
+    let account = ledger.accounts().get_state(&address)?;
+    Ok(account
+        .evm_state
+        .storage
+        .get(&key)
+        .cloned()
+        .unwrap_or_default())
 }
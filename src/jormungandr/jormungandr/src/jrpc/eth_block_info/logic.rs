@@ -1,4 +1,5 @@
 use crate::{
+    blockcfg::{ChainLength, Ledger},
     blockchain::{Blockchain, Ref},
     context::Context,
     jrpc::{
@@ -36,6 +37,34 @@ pub fn get_block_by_number_from_context(
     }
 }
 
+/// chain length of the block selected by `number` on the branch of `blockchain_tip`
+pub fn get_chain_length_from_number(number: &BlockNumber, blockchain_tip: &Ref) -> ChainLength {
+    match number {
+        BlockNumber::Latest | BlockNumber::Pending => blockchain_tip.chain_length(),
+        BlockNumber::Earliest => 0.into(),
+        BlockNumber::Num(number) => (*number).into(),
+    }
+}
+
+/// ledger state after the block selected by `number`, only the state of the
+/// tip is available if the node does not run in archive mode
+pub async fn get_ledger_by_number_from_context(
+    number: BlockNumber,
+    blockchain: &Blockchain,
+    blockchain_tip: Arc<Ref>,
+) -> Result<Arc<Ledger>, Error> {
+    let chain_length = get_chain_length_from_number(&number, &blockchain_tip);
+    if chain_length == blockchain_tip.chain_length() {
+        return Ok(blockchain_tip.ledger());
+    }
+    blockchain.archive().ok_or(Error::NonArchiveNode)?;
+    blockchain
+        .archived_ref(blockchain_tip.hash(), chain_length)
+        .await?
+        .map(|block_ref| block_ref.ledger())
+        .ok_or(Error::BlockNotFound)
+}
+
 pub async fn get_block_by_hash(
     hash: H256,
     full: bool,
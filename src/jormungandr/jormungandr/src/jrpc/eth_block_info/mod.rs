@@ -1,6 +1,9 @@
 use crate::context::ContextLock;
-use jsonrpsee_http_server::RpcModule;
-pub use logic::get_block_by_number_from_context;
+use jsonrpsee_server::RpcModule;
+pub use logic::{
+    get_block_by_number_from_context, get_chain_length_from_number,
+    get_ledger_by_number_from_context,
+};
 
 mod logic;
 
@@ -1,5 +1,5 @@
 use crate::context::ContextLock;
-use jsonrpsee_http_server::RpcModule;
+use jsonrpsee_server::RpcModule;
 
 mod logic;
 
@@ -4,25 +4,42 @@ use std::collections::HashMap;
 #[derive(Default)]
 pub struct EvmFilters {
     last_key: Number,
-    filters: HashMap<Number, FilterType>,
+    filters: HashMap<Number, FilterState>,
 }
 
 impl EvmFilters {
-    pub fn insert(&mut self, filter: FilterType) -> Number {
+    pub fn insert(&mut self, filter: FilterType, last_block: u32) -> Number {
         self.last_key.inc();
-        self.filters.insert(self.last_key.clone(), filter);
+        self.filters.insert(
+            self.last_key.clone(),
+            FilterState {
+                filter_type: filter,
+                last_block,
+            },
+        );
         self.last_key.clone()
     }
 
-    pub fn get(&self, index: &Number) -> Option<&FilterType> {
+    pub fn get(&self, index: &Number) -> Option<&FilterState> {
         self.filters.get(index)
     }
 
+    pub fn get_mut(&mut self, index: &Number) -> Option<&mut FilterState> {
+        self.filters.get_mut(index)
+    }
+
     pub fn remove(&mut self, index: &Number) -> bool {
         self.filters.remove(index).map_or_else(|| false, |_| true)
     }
 }
 
+#[derive(Debug, PartialEq, Eq)]
+pub struct FilterState {
+    pub filter_type: FilterType,
+    /// chain length of the last block whose changes were reported
+    pub last_block: u32,
+}
+
 #[derive(Debug, PartialEq, Eq)]
 pub enum FilterType {
     Block,
@@ -38,16 +55,28 @@ mod tests {
     fn evm_filters_test() {
         let mut filters = EvmFilters::default();
 
-        let index1 = filters.insert(FilterType::Block);
-        let index2 = filters.insert(FilterType::PendingTransaction);
+        let index1 = filters.insert(FilterType::Block, 0);
+        let index2 = filters.insert(FilterType::PendingTransaction, 1);
+
+        assert_eq!(
+            filters.get(&index1).map(|state| &state.filter_type),
+            Some(&FilterType::Block)
+        );
+        assert_eq!(
+            filters.get(&index2).map(|state| &state.filter_type),
+            Some(&FilterType::PendingTransaction)
+        );
 
-        assert_eq!(filters.get(&index1), Some(&FilterType::Block));
-        assert_eq!(filters.get(&index2), Some(&FilterType::PendingTransaction));
+        filters.get_mut(&index2).unwrap().last_block = 2;
+        assert_eq!(filters.get(&index2).map(|state| state.last_block), Some(2));
 
         assert!(filters.remove(&index1));
 
         assert_eq!(filters.get(&index1), None);
-        assert_eq!(filters.get(&index2), Some(&FilterType::PendingTransaction));
+        assert_eq!(
+            filters.get(&index2).map(|state| &state.filter_type),
+            Some(&FilterType::PendingTransaction)
+        );
 
         assert!(!filters.remove(&index1));
     }
@@ -1,7 +1,10 @@
 use super::filters::FilterType;
 use crate::{
+    blockcfg::ChainLength,
+    blockchain::{BlockReceipts, Blockchain, Ref},
     context::Context,
     jrpc::{
+        eth_block_info::get_chain_length_from_number,
         eth_types::{
             filter::{Filter, FilterChanges},
             log::Log,
@@ -10,20 +13,28 @@ use crate::{
         Error,
     },
 };
+use chain_evm::ethereum_types::H256;
+use std::{collections::HashMap, sync::Arc};
 
-pub fn new_filter(filter: Filter, context: &mut Context) -> Result<Number, Error> {
+/// maximum number of blocks the logs are collected from in a single request
+const MAX_LOGS_BLOCK_RANGE: u32 = 10_000;
+
+pub async fn new_filter(filter: Filter, context: &mut Context) -> Result<Number, Error> {
+    let last_block = context.blockchain_tip()?.get_ref().await.chain_length();
     let filters = context.evm_filters();
-    Ok(filters.insert(FilterType::Log(filter)))
+    Ok(filters.insert(FilterType::Log(filter), last_block.into()))
 }
 
-pub fn new_block_filter(context: &mut Context) -> Result<Number, Error> {
+pub async fn new_block_filter(context: &mut Context) -> Result<Number, Error> {
+    let last_block = context.blockchain_tip()?.get_ref().await.chain_length();
     let filters = context.evm_filters();
-    Ok(filters.insert(FilterType::Block))
+    Ok(filters.insert(FilterType::Block, last_block.into()))
 }
 
-pub fn new_pending_transaction_filter(context: &mut Context) -> Result<Number, Error> {
+pub async fn new_pending_transaction_filter(context: &mut Context) -> Result<Number, Error> {
+    let last_block = context.blockchain_tip()?.get_ref().await.chain_length();
     let filters = context.evm_filters();
-    Ok(filters.insert(FilterType::PendingTransaction))
+    Ok(filters.insert(FilterType::PendingTransaction, last_block.into()))
 }
 
 pub fn uninstall_filter(filter_id: Number, context: &mut Context) -> Result<bool, Error> {
@@ -31,14 +42,189 @@ pub fn uninstall_filter(filter_id: Number, context: &mut Context) -> Result<bool
     Ok(filters.remove(&filter_id))
 }
 
-pub fn get_filter_changes(_filter_id: Number, _context: &Context) -> Result<FilterChanges, Error> {
-    Err(Error::NonArchiveNode)
+pub async fn get_filter_changes(
+    filter_id: Number,
+    context: &mut Context,
+) -> Result<FilterChanges, Error> {
+    let blockchain = context.blockchain()?.clone();
+    let blockchain_tip = context.blockchain_tip()?.get_ref().await;
+    let filter = context
+        .evm_filters()
+        .get_mut(&filter_id)
+        .ok_or(Error::FilterNotFound)?;
+
+    // at most `MAX_LOGS_BLOCK_RANGE` blocks are scanned at once, the next
+    // call carries on after the last scanned block
+    let tip_chain_length = u32::from(blockchain_tip.chain_length());
+    let from = ChainLength::from(filter.last_block + 1);
+    let to = ChainLength::from(
+        tip_chain_length.min(filter.last_block.saturating_add(MAX_LOGS_BLOCK_RANGE)),
+    );
+    let changes = match &filter.filter_type {
+        FilterType::Block => {
+            let mut hashes = Vec::new();
+            let last_block = blockchain
+                .storage()
+                .get_nth_ancestor(blockchain_tip.hash(), tip_chain_length - u32::from(to))?;
+            if let Some(last_block) = last_block {
+                let mut block_hash = last_block.header().hash();
+                for _ in u32::from(from)..=u32::from(to) {
+                    hashes.push(H256::from_slice(block_hash.as_bytes()));
+                    match blockchain.storage().get_parent(block_hash)? {
+                        Some(parent_hash) => block_hash = parent_hash,
+                        None => break,
+                    }
+                }
+            }
+            hashes.reverse();
+            FilterChanges::Hashes(hashes)
+        }
+        // transactions are not tracked before they are included in a block
+        FilterType::PendingTransaction => FilterChanges::Empty,
+        FilterType::Log(log_filter) => {
+            let chain_length = |number| get_chain_length_from_number(number, &blockchain_tip);
+            let from = log_filter
+                .from_block()
+                .map(chain_length)
+                .map_or(from, |from_block| from_block.max(from));
+            let to = log_filter
+                .to_block()
+                .map(chain_length)
+                .map_or(to, |to_block| to_block.min(to));
+            FilterChanges::Logs(
+                logs_in_range(&blockchain, &blockchain_tip, log_filter, from, to).await?,
+            )
+        }
+    };
+    filter.last_block = to.into();
+    Ok(changes)
+}
+
+pub async fn get_filter_logs(filter_id: Number, context: &mut Context) -> Result<Vec<Log>, Error> {
+    let blockchain = context.blockchain()?.clone();
+    let blockchain_tip = context.blockchain_tip()?.get_ref().await;
+    match context.evm_filters().get(&filter_id) {
+        Some(filter) => match &filter.filter_type {
+            FilterType::Log(log_filter) => {
+                filter_logs(&blockchain, &blockchain_tip, log_filter).await
+            }
+            _ => Err(Error::FilterNotFound),
+        },
+        None => Err(Error::FilterNotFound),
+    }
 }
 
-pub fn get_filter_logs(_filter_id: Number, _context: &Context) -> Result<Vec<Log>, Error> {
-    Err(Error::NonArchiveNode)
+pub async fn get_logs(filter: Filter, context: &Context) -> Result<FilterChanges, Error> {
+    let blockchain = context.blockchain()?;
+    let blockchain_tip = context.blockchain_tip()?.get_ref().await;
+    Ok(FilterChanges::Logs(
+        filter_logs(blockchain, &blockchain_tip, &filter).await?,
+    ))
 }
 
-pub fn get_logs(_filter: Filter, _context: &Context) -> Result<FilterChanges, Error> {
-    Err(Error::NonArchiveNode)
+/// logs matching the filter, in the block with the given hash or in the given
+/// range of blocks of the branch of `blockchain_tip`
+async fn filter_logs(
+    blockchain: &Blockchain,
+    blockchain_tip: &Arc<Ref>,
+    filter: &Filter,
+) -> Result<Vec<Log>, Error> {
+    match filter.block_hash() {
+        Some(block_hash) => {
+            let archive = blockchain.archive().ok_or(Error::NonArchiveNode)?;
+            let block = archive
+                .receipts(block_hash.0.into())
+                .await
+                .ok_or(Error::BlockNotFound)?;
+            block_logs(blockchain, &block, filter)
+        }
+        None => {
+            let chain_length = |number| get_chain_length_from_number(number, blockchain_tip);
+            let from = filter
+                .from_block()
+                .map(chain_length)
+                .unwrap_or_else(|| blockchain_tip.chain_length());
+            let to = filter
+                .to_block()
+                .map(chain_length)
+                .unwrap_or_else(|| blockchain_tip.chain_length());
+            logs_in_range(blockchain, blockchain_tip, filter, from, to).await
+        }
+    }
+}
+
+async fn logs_in_range(
+    blockchain: &Blockchain,
+    blockchain_tip: &Arc<Ref>,
+    filter: &Filter,
+    from: ChainLength,
+    to: ChainLength,
+) -> Result<Vec<Log>, Error> {
+    let archive = blockchain.archive().ok_or(Error::NonArchiveNode)?;
+    let range = u32::from(to)
+        .saturating_sub(u32::from(from))
+        .saturating_add(1);
+    if range > MAX_LOGS_BLOCK_RANGE {
+        return Err(Error::BlockRangeTooLarge {
+            range,
+            max: MAX_LOGS_BLOCK_RANGE,
+        });
+    }
+    let mut logs = Vec::new();
+    for block in archive
+        .branch_receipts(blockchain_tip.hash(), from, to)
+        .await?
+    {
+        logs.extend(block_logs(blockchain, &block, filter)?);
+    }
+    Ok(logs)
+}
+
+/// logs of the given block matching the filter
+pub fn block_logs(
+    blockchain: &Blockchain,
+    block: &BlockReceipts,
+    filter: &Filter,
+) -> Result<Vec<Log>, Error> {
+    let receipts = &block.receipts;
+    if receipts.is_empty() {
+        return Ok(Vec::new());
+    }
+
+    let block_hash = block.hash;
+    let block_number = Number::from(u32::from(block.chain_length) as u64);
+    let block = blockchain
+        .storage()
+        .get(block_hash)?
+        .ok_or(Error::BlockNotFound)?;
+    let transaction_indices: HashMap<_, _> = block
+        .fragments()
+        .enumerate()
+        .map(|(i, fragment)| (fragment.hash(), i as u64))
+        .collect();
+    let block_hash = H256::from_slice(block_hash.as_bytes());
+
+    let mut logs = Vec::new();
+    let mut log_index = 0u64;
+    for receipt in receipts.iter() {
+        let transaction_hash = H256::from_slice(receipt.fragment_id.as_bytes());
+        let transaction_index = transaction_indices
+            .get(&receipt.fragment_id)
+            .copied()
+            .unwrap_or_default();
+        for log in &receipt.logs {
+            if filter.matches(&log.address, &log.topics) {
+                logs.push(Log::new(
+                    log,
+                    log_index.into(),
+                    transaction_index.into(),
+                    transaction_hash,
+                    block_hash,
+                    block_number.clone(),
+                ));
+            }
+            log_index += 1;
+        }
+    }
+    Ok(logs)
 }
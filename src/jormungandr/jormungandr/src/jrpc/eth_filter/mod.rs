@@ -1,6 +1,7 @@
 use crate::context::ContextLock;
 pub use filters::EvmFilters;
-use jsonrpsee_http_server::RpcModule;
+use jsonrpsee_server::RpcModule;
+pub use logic::block_logs;
 
 mod filters;
 mod logic;
@@ -13,6 +14,7 @@ pub fn eth_filter_module(context: ContextLock) -> RpcModule<ContextLock> {
             let mut context = context.write().await;
             let filter = params.parse()?;
             logic::new_filter(filter, &mut context)
+                .await
                 .map_err(|err| jsonrpsee_core::Error::Custom(err.to_string()))
         })
         .unwrap();
@@ -21,6 +23,7 @@ pub fn eth_filter_module(context: ContextLock) -> RpcModule<ContextLock> {
         .register_async_method("eth_newBlockFilter", |_, context| async move {
             let mut context = context.write().await;
             logic::new_block_filter(&mut context)
+                .await
                 .map_err(|err| jsonrpsee_core::Error::Custom(err.to_string()))
         })
         .unwrap();
@@ -29,6 +32,7 @@ pub fn eth_filter_module(context: ContextLock) -> RpcModule<ContextLock> {
         .register_async_method("eth_newPendingTransactionFilter", |_, context| async move {
             let mut context = context.write().await;
             logic::new_pending_transaction_filter(&mut context)
+                .await
                 .map_err(|err| jsonrpsee_core::Error::Custom(err.to_string()))
         })
         .unwrap();
@@ -44,18 +48,20 @@ pub fn eth_filter_module(context: ContextLock) -> RpcModule<ContextLock> {
 
     module
         .register_async_method("eth_getFilterChanges", |params, context| async move {
-            let context = context.read().await;
+            let mut context = context.write().await;
             let filter_id = params.parse()?;
-            logic::get_filter_changes(filter_id, &context)
+            logic::get_filter_changes(filter_id, &mut context)
+                .await
                 .map_err(|err| jsonrpsee_core::Error::Custom(err.to_string()))
         })
         .unwrap();
 
     module
         .register_async_method("eth_getFilterLogs", |params, context| async move {
-            let context = context.read().await;
+            let mut context = context.write().await;
             let filter_id = params.parse()?;
-            logic::get_filter_logs(filter_id, &context)
+            logic::get_filter_logs(filter_id, &mut context)
+                .await
                 .map_err(|err| jsonrpsee_core::Error::Custom(err.to_string()))
         })
         .unwrap();
@@ -65,6 +71,7 @@ pub fn eth_filter_module(context: ContextLock) -> RpcModule<ContextLock> {
             let context = context.read().await;
             let filter = params.parse()?;
             logic::get_logs(filter, &context)
+                .await
                 .map_err(|err| jsonrpsee_core::Error::Custom(err.to_string()))
         })
         .unwrap();
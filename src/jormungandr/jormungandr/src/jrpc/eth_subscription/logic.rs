@@ -0,0 +1,118 @@
+use crate::{
+    blockcfg::Header as JorHeader,
+    blockchain::{BlockReceipts, Blockchain},
+    context::ContextLock,
+    jrpc::{
+        eth_filter::block_logs,
+        eth_types::{block::Header, filter::Filter, log::Log, subscription::SubscriptionKind},
+        Notifier,
+    },
+};
+use chain_evm::ethereum_types::H256;
+use futures::{
+    future,
+    stream::{self, BoxStream},
+    StreamExt,
+};
+use jsonrpsee_server::{
+    types::error::{ErrorObject, CALL_EXECUTION_FAILED_CODE},
+    SubscriptionSink,
+};
+use std::sync::Arc;
+use tokio_stream::wrappers::BroadcastStream;
+
+pub async fn subscribe(
+    kind: SubscriptionKind,
+    filter: Filter,
+    mut sink: SubscriptionSink,
+    context: &ContextLock,
+) {
+    let (blockchain, notifier) = {
+        let context = context.read().await;
+        (
+            context.blockchain().cloned(),
+            context.jrpc_notifier().clone(),
+        )
+    };
+    let blockchain = match blockchain {
+        Ok(blockchain) => blockchain,
+        Err(err) => {
+            let _ = sink.reject(ErrorObject::owned(
+                CALL_EXECUTION_FAILED_CODE,
+                err.to_string(),
+                None::<()>,
+            ));
+            return;
+        }
+    };
+
+    // the node streams never end, so the subscription is only closed by the
+    // client unsubscribing or disconnecting and there is no reason to report
+    match kind {
+        SubscriptionKind::NewHeads => {
+            sink.pipe_from_stream(new_heads(&notifier, blockchain))
+                .await
+        }
+        SubscriptionKind::Logs => {
+            sink.pipe_from_stream(logs(&notifier, blockchain, filter))
+                .await
+        }
+        SubscriptionKind::NewPendingTransactions => {
+            sink.pipe_from_stream(new_pending_transactions(&notifier))
+                .await
+        }
+    };
+}
+
+fn new_heads(notifier: &Notifier, blockchain: Blockchain) -> BoxStream<'static, Header> {
+    tips(notifier)
+        .filter_map(move |header| {
+            let blockchain = blockchain.clone();
+            async move {
+                let block_ref = blockchain.get_ref(header.hash()).await.ok()??;
+                let gas_limit = block_ref.ledger().get_evm_block_gas_limit();
+                Some(Header::build(header, gas_limit))
+            }
+        })
+        .boxed()
+}
+
+fn logs(notifier: &Notifier, blockchain: Blockchain, filter: Filter) -> BoxStream<'static, Log> {
+    let filter = Arc::new(filter);
+    tips(notifier)
+        .filter_map(move |header| {
+            let blockchain = blockchain.clone();
+            let filter = Arc::clone(&filter);
+            async move {
+                let block_ref = blockchain.get_ref(header.hash()).await.ok()??;
+                match block_logs(&blockchain, &BlockReceipts::new(&block_ref), &filter) {
+                    Ok(logs) => Some(stream::iter(logs)),
+                    Err(err) => {
+                        tracing::debug!("cannot collect the logs of the new tip: {}", err);
+                        None
+                    }
+                }
+            }
+        })
+        .flatten()
+        .boxed()
+}
+
+fn new_pending_transactions(notifier: &Notifier) -> BoxStream<'static, H256> {
+    BroadcastStream::new(notifier.subscribe_pending_transactions())
+        .filter_map(|fragment_id| {
+            future::ready(
+                fragment_id
+                    .ok()
+                    .map(|fragment_id| H256::from_slice(fragment_id.as_bytes())),
+            )
+        })
+        .boxed()
+}
+
+/// headers of the new tips, the ones missed by a lagging subscriber are skipped
+fn tips(notifier: &Notifier) -> BoxStream<'static, JorHeader> {
+    BroadcastStream::new(notifier.subscribe_tips())
+        .filter_map(|header| future::ready(header.ok()))
+        .boxed()
+}
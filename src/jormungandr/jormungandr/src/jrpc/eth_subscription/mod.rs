@@ -0,0 +1,45 @@
+use crate::{context::ContextLock, jrpc::WebSocketConfig};
+use jsonrpsee_server::{RpcModule, ServerBuilder, ServerHandle};
+use std::sync::Arc;
+
+mod logic;
+
+pub fn eth_subscription_module(context: ContextLock) -> RpcModule<ContextLock> {
+    let mut module = RpcModule::new(context);
+
+    module
+        .register_subscription(
+            "eth_subscribe",
+            "eth_subscription",
+            "eth_unsubscribe",
+            |params, mut sink, context| {
+                let mut params = params.sequence();
+                let (kind, filter) = match (params.next(), params.optional_next()) {
+                    (Ok(kind), Ok(filter)) => (kind, filter),
+                    (Err(err), _) | (_, Err(err)) => {
+                        sink.reject(err)?;
+                        return Ok(());
+                    }
+                };
+                let context = Arc::clone(&context);
+                tokio::spawn(async move {
+                    logic::subscribe(kind, filter.unwrap_or_default(), sink, &context).await
+                });
+                Ok(())
+            },
+        )
+        .unwrap();
+    module
+}
+
+pub async fn start_websocket_server(config: WebSocketConfig, context: ContextLock) -> ServerHandle {
+    let server = ServerBuilder::default()
+        .max_connections(config.max_connections)
+        .max_subscriptions_per_connection(config.max_subscriptions_per_connection)
+        .ws_only()
+        .build(config.listen)
+        .await
+        .unwrap();
+
+    server.start(eth_subscription_module(context)).unwrap()
+}
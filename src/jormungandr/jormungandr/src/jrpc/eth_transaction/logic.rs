@@ -2,7 +2,7 @@ use crate::{
     context::Context,
     intercom::{self, TransactionMsg},
     jrpc::{
-        eth_block_info::get_block_by_number_from_context,
+        eth_block_info::{get_block_by_number_from_context, get_ledger_by_number_from_context},
         eth_types::{
             block::Block, block_number::BlockNumber, bytes::Bytes, number::Number,
             receipt::Receipt, transaction::Transaction,
@@ -89,11 +89,38 @@ pub async fn send_raw_transaction(raw_tx: Bytes, context: &Context) -> Result<H2
 }
 
 pub async fn get_transaction_by_hash(
-    _hash: H256,
-    _context: &Context,
+    hash: H256,
+    context: &Context,
 ) -> Result<Option<Transaction>, Error> {
-    // TODO implement
-    Err(Error::NonArchiveNode)
+    let blockchain = context.blockchain()?;
+    let blockchain_tip = context.blockchain_tip()?.get_ref().await;
+    let archive = blockchain.archive().ok_or(Error::NonArchiveNode)?;
+    let fragment_id = hash.0.into();
+    // the transaction may have been applied in the blocks of other branches
+    let block_hash = archive
+        .evm_transaction_blocks(&fragment_id)
+        .await
+        .into_iter()
+        .find(|block_hash| {
+            *block_hash == blockchain_tip.hash()
+                || blockchain
+                    .storage()
+                    .is_ancestor(*block_hash, blockchain_tip.hash())
+        });
+    let block_hash = match block_hash {
+        Some(block_hash) => block_hash,
+        None => return Ok(None),
+    };
+    let gas_price = blockchain_tip.ledger().get_evm_gas_price();
+    let block = blockchain.storage().get(block_hash)?;
+    let index = block.as_ref().and_then(|block| {
+        block
+            .fragments()
+            .position(|fragment| fragment.hash() == fragment_id)
+    });
+    Ok(index.and_then(|index| {
+        get_transaction_from_block_by_index(block, (index as u64).into(), gas_price)
+    }))
 }
 
 pub async fn get_transaction_by_block_hash_and_index(
@@ -163,10 +190,18 @@ pub fn sign(address: H160, message: Bytes, context: &Context) -> Result<H512, Er
     Ok(H512::from_slice(&signature[..]))
 }
 
-pub async fn call(tx: Transaction, _: BlockNumber, context: &Context) -> Result<Bytes, Error> {
-    let blockchain_tip = context.blockchain_tip()?.get_ref().await;
-    Ok(blockchain_tip
-        .ledger()
+pub async fn call(
+    tx: Transaction,
+    block_number: BlockNumber,
+    context: &Context,
+) -> Result<Bytes, Error> {
+    let ledger = get_ledger_by_number_from_context(
+        block_number,
+        context.blockchain()?,
+        context.blockchain_tip()?.get_ref().await,
+    )
+    .await?;
+    Ok(ledger
         .call_evm_transaction(tx.into())
         .map_err(Box::new)?
         .into())
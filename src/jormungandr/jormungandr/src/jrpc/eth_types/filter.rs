@@ -14,13 +14,32 @@ pub enum VariadicValue<T> {
     Null,
 }
 
+impl<T> Default for VariadicValue<T> {
+    fn default() -> Self {
+        VariadicValue::Null
+    }
+}
+
+impl<T: PartialEq> VariadicValue<T> {
+    /// whether the value is accepted: `Null` and an empty list accept anything
+    fn accepts(&self, value: &T) -> bool {
+        match self {
+            VariadicValue::Single(expected) => expected == value,
+            VariadicValue::Multiple(expected) => {
+                expected.is_empty() || expected.iter().any(|expected| expected == value)
+            }
+            VariadicValue::Null => true,
+        }
+    }
+}
+
 /// Filter Address
 pub type FilterAddress = VariadicValue<H160>;
 /// Topic, supports `A` | `null` | `[A,B,C]` | `[A,[B,C]]` | [null,[B,C]] | [null,[null,C]]
 pub type Topic = VariadicValue<VariadicValue<H256>>;
 
 /// Filter
-#[derive(Debug, Deserialize, PartialEq, Eq)]
+#[derive(Debug, Default, Deserialize, PartialEq, Eq)]
 #[serde(deny_unknown_fields)]
 #[serde(rename_all = "camelCase")]
 pub struct Filter {
@@ -29,24 +48,63 @@ pub struct Filter {
     /// To Block
     to_block: Option<BlockNumber>,
     /// Address
+    #[serde(default)]
     address: FilterAddress,
     /// Topics
+    #[serde(default)]
     topics: Topic,
     /// Block hash
     block_hash: Option<H256>,
 }
 
+impl Filter {
+    pub fn from_block(&self) -> Option<&BlockNumber> {
+        self.from_block.as_ref()
+    }
+
+    pub fn to_block(&self) -> Option<&BlockNumber> {
+        self.to_block.as_ref()
+    }
+
+    pub fn block_hash(&self) -> Option<&H256> {
+        self.block_hash.as_ref()
+    }
+
+    /// whether a log emitted by `address` with the given `topics` matches the filter
+    pub fn matches(&self, address: &H160, topics: &[H256]) -> bool {
+        self.address.accepts(address) && self.matches_topics(topics)
+    }
+
+    fn matches_topics(&self, topics: &[H256]) -> bool {
+        let matches_at = |i: usize, expected: &VariadicValue<H256>| match expected {
+            VariadicValue::Null => true,
+            expected => topics.get(i).map_or(false, |topic| expected.accepts(topic)),
+        };
+        match &self.topics {
+            VariadicValue::Null => true,
+            // a plain list of hashes `[A, B]`, each constraining the topic at
+            // the same position
+            VariadicValue::Single(VariadicValue::Multiple(expected)) => expected
+                .iter()
+                .enumerate()
+                .all(|(i, expected)| topics.get(i) == Some(expected)),
+            VariadicValue::Single(expected) => matches_at(0, expected),
+            VariadicValue::Multiple(expected) => expected
+                .iter()
+                .enumerate()
+                .all(|(i, expected)| matches_at(i, expected)),
+        }
+    }
+}
+
 /// Results of the filter_changes RPC.
 #[derive(Debug, PartialEq, Eq)]
 pub enum FilterChanges {
-    #[allow(dead_code)]
     /// New logs.
     Logs(Vec<Log>),
-    #[allow(dead_code)]
     /// New hashes (block or transactions)
     Hashes(Vec<H256>),
     /// Empty result,
-    #[allow(dead_code)]
     Empty,
 }
 
@@ -125,6 +183,39 @@ mod tests {
         assert_eq!(t_null, Topic::Single(<VariadicValue<H256>>::Null));
     }
 
+    #[test]
+    fn filter_matches() {
+        let address = H160::from_low_u64_be(1);
+        let topic_a = H256::from_low_u64_be(1);
+        let topic_b = H256::from_low_u64_be(2);
+
+        let filter: Filter = serde_json::from_value(serde_json::json!({})).unwrap();
+        assert!(filter.matches(&address, &[]));
+
+        let filter: Filter = serde_json::from_value(serde_json::json!({
+            "address": H160::from_low_u64_be(2),
+        }))
+        .unwrap();
+        assert!(!filter.matches(&address, &[topic_a]));
+
+        let filter: Filter = serde_json::from_value(serde_json::json!({
+            "address": [address, H160::from_low_u64_be(2)],
+            "topics": [topic_a, topic_b],
+        }))
+        .unwrap();
+        assert!(filter.matches(&address, &[topic_a, topic_b]));
+        assert!(!filter.matches(&address, &[topic_a]));
+        assert!(!filter.matches(&address, &[topic_b, topic_a]));
+
+        let filter: Filter = serde_json::from_value(serde_json::json!({
+            "topics": [null, [topic_a, topic_b]],
+        }))
+        .unwrap();
+        assert!(filter.matches(&address, &[topic_a, topic_b]));
+        assert!(filter.matches(&address, &[topic_b, topic_a]));
+        assert!(!filter.matches(&address, &[topic_a]));
+    }
+
     #[test]
     fn filter_changes_serialize() {
         let fc_log = FilterChanges::Logs(vec![Log::build()]);
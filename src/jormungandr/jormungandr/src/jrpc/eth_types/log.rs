@@ -1,5 +1,8 @@
 use super::{bytes::Bytes, number::Number};
-use chain_evm::ethereum_types::{H160, H256};
+use chain_evm::{
+    ethereum_types::{H160, H256},
+    machine::Log as EvmLog,
+};
 use serde::Serialize;
 
 /// Log
@@ -27,6 +30,27 @@ pub struct Log {
 }
 
 impl Log {
+    pub fn new(
+        log: &EvmLog,
+        log_index: Number,
+        transaction_index: Number,
+        transaction_hash: H256,
+        block_hash: H256,
+        block_number: Number,
+    ) -> Self {
+        Self {
+            removed: false,
+            log_index: Some(log_index),
+            transaction_index: Some(transaction_index),
+            transaction_hash: Some(transaction_hash),
+            block_hash: Some(block_hash),
+            block_number: Some(block_number),
+            address: Some(log.address),
+            data: Some(log.data.clone().into_boxed_slice().into()),
+            topics: log.topics.clone(),
+        }
+    }
+
     pub fn build() -> Self {
         Self {
             removed: true,
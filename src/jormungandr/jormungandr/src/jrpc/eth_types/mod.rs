@@ -6,6 +6,7 @@ pub mod filter;
 pub mod log;
 pub mod number;
 pub mod receipt;
+pub mod subscription;
 pub mod sync;
 pub mod transaction;
 pub mod work;
@@ -0,0 +1,34 @@
+use serde::Deserialize;
+
+/// Kind of the events an `eth_subscribe` subscription is notified of
+#[derive(Debug, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub enum SubscriptionKind {
+    /// Headers of the blocks becoming the tip
+    NewHeads,
+    /// Logs emitted in the blocks becoming the tip, matching the filter
+    Logs,
+    /// Hashes of the transactions entering the fragment pool
+    NewPendingTransactions,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn subscription_kind_deserialize() {
+        let sk_new_heads: SubscriptionKind = serde_json::from_str(r#""newHeads""#).unwrap();
+        let sk_logs: SubscriptionKind = serde_json::from_str(r#""logs""#).unwrap();
+        let sk_new_pending_transactions: SubscriptionKind =
+            serde_json::from_str(r#""newPendingTransactions""#).unwrap();
+
+        assert_eq!(sk_new_heads, SubscriptionKind::NewHeads);
+        assert_eq!(sk_logs, SubscriptionKind::Logs);
+        assert_eq!(
+            sk_new_pending_transactions,
+            SubscriptionKind::NewPendingTransactions
+        );
+        assert!(serde_json::from_str::<SubscriptionKind>(r#""syncing""#).is_err());
+    }
+}
@@ -9,9 +9,12 @@ mod eth_filter;
 #[cfg(feature = "evm")]
 mod eth_miner;
 #[cfg(feature = "evm")]
+mod eth_subscription;
+#[cfg(feature = "evm")]
 mod eth_transaction;
 #[cfg(feature = "evm")]
 mod eth_types;
+mod notifier;
 
 use crate::{
     context::ContextLock,
@@ -22,12 +25,23 @@ use chain_impl_mockchain::ledger::Error as LedgerError;
 pub use eth_filter::EvmFilters;
 use futures::channel::mpsc::TrySendError;
 use jormungandr_lib::interfaces::FragmentsProcessingSummary;
-use jsonrpsee_http_server::{HttpServerBuilder, RpcModule};
+use jsonrpsee_server::{RpcModule, ServerBuilder, ServerHandle};
+pub use notifier::Notifier;
 use std::net::SocketAddr;
 use thiserror::Error;
 
+pub const DEFAULT_WEBSOCKET_MAX_CONNECTIONS: u32 = 100;
+pub const DEFAULT_WEBSOCKET_MAX_SUBSCRIPTIONS_PER_CONNECTION: u32 = 1024;
+
 pub struct Config {
     pub listen: SocketAddr,
+    pub websocket: Option<WebSocketConfig>,
+}
+
+pub struct WebSocketConfig {
+    pub listen: SocketAddr,
+    pub max_connections: u32,
+    pub max_subscriptions_per_connection: u32,
 }
 
 #[derive(Debug, Error)]
@@ -36,8 +50,16 @@ pub enum Error {
     ContextError(#[from] crate::context::Error),
     #[error(transparent)]
     Storage(#[from] crate::blockchain::StorageError),
-    #[error("Currently we dont support archive and full modes, so unfortunately this functionality is not working at this moment")]
+    #[error(transparent)]
+    Blockchain(#[from] crate::blockchain::Error),
+    #[error("This functionality is only available when the node runs in the archive mode")]
     NonArchiveNode,
+    #[error("Block not found")]
+    BlockNotFound,
+    #[error("The range of {range} blocks is larger than the maximum of {max} blocks")]
+    BlockRangeTooLarge { range: u32, max: u32 },
+    #[error("Filter not found")]
+    FilterNotFound,
     #[error(transparent)]
     IntercomError(#[from] intercom::Error),
     #[error(transparent)]
@@ -62,12 +84,31 @@ pub enum Error {
     EthereumSignatureError(String),
 }
 
-pub async fn start_jrpc_server(config: Config, _context: ContextLock) {
-    let server = HttpServerBuilder::default()
-        .build(config.listen)
+pub async fn start_jrpc_server(config: Config, context: ContextLock) {
+    let http_server = start_http_server(config.listen, context.clone()).await;
+
+    #[cfg(feature = "evm")]
+    if let Some(websocket_config) = config.websocket {
+        let websocket_server =
+            eth_subscription::start_websocket_server(websocket_config, context).await;
+        futures::future::join(http_server.stopped(), websocket_server.stopped()).await;
+        return;
+    }
+
+    http_server.stopped().await
+}
+
+async fn start_http_server(listen: SocketAddr, context: ContextLock) -> ServerHandle {
+    let server = ServerBuilder::default()
+        .http_only()
+        .build(listen)
         .await
         .unwrap();
 
+    server.start(http_module(context)).unwrap()
+}
+
+fn http_module(_context: ContextLock) -> RpcModule<()> {
     #[allow(unused_mut)]
     let mut modules = RpcModule::new(());
 
@@ -98,5 +139,59 @@ pub async fn start_jrpc_server(config: Config, _context: ContextLock) {
             .unwrap();
     }
 
-    server.start(modules).unwrap().await
+    modules
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::context::Context;
+    use serde_json::{json, Value};
+    use std::sync::Arc;
+    use tokio::sync::RwLock;
+
+    async fn call(listen: SocketAddr, method: &str, params: Value) -> Value {
+        let request = json!({
+            "jsonrpc": "2.0",
+            "id": 1,
+            "method": method,
+            "params": params,
+        });
+        let response = reqwest::Client::new()
+            .post(format!("http://{}", listen))
+            .header("content-type", "application/json")
+            .body(request.to_string())
+            .send()
+            .await
+            .unwrap();
+        assert!(response.status().is_success());
+        serde_json::from_str(&response.text().await.unwrap()).unwrap()
+    }
+
+    #[tokio::test]
+    async fn http_server_answers_calls() {
+        let context = Arc::new(RwLock::new(Context::new()));
+        let server = ServerBuilder::default()
+            .http_only()
+            .build("127.0.0.1:0")
+            .await
+            .unwrap();
+        let listen = server.local_addr().unwrap();
+        let handle = server.start(http_module(context)).unwrap();
+
+        let response = call(listen, "unknown_method", json!([])).await;
+        assert_eq!(response["id"], 1);
+        assert_eq!(response["error"]["code"], -32601);
+
+        #[cfg(feature = "evm")]
+        {
+            let response = call(listen, "eth_mining", json!([])).await;
+            assert_eq!(response["id"], 1);
+            let message = response["error"]["message"].as_str().unwrap();
+            assert!(message.contains(&Error::MiningIsNotAllowed.to_string()));
+        }
+
+        handle.stop().unwrap();
+        handle.stopped().await;
+    }
 }
@@ -0,0 +1,60 @@
+use crate::{
+    blockcfg::Header,
+    fragment::{Fragment, FragmentId},
+};
+use tokio::sync::broadcast;
+
+const NOTIFIER_CHANNEL_CAPACITY: usize = 64;
+
+/// broadcasts the node events the JSON-RPC subscriptions are fed from.
+///
+/// Sending never blocks the node tasks: events are dropped when there is
+/// no subscriber, and a subscriber lagging more than the channel capacity
+/// behind misses the oldest events.
+#[derive(Clone)]
+pub struct Notifier {
+    tips: broadcast::Sender<Header>,
+    pending_transactions: broadcast::Sender<FragmentId>,
+}
+
+impl Default for Notifier {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Notifier {
+    pub fn new() -> Self {
+        let (tips, _) = broadcast::channel(NOTIFIER_CHANNEL_CAPACITY);
+        let (pending_transactions, _) = broadcast::channel(NOTIFIER_CHANNEL_CAPACITY);
+        Self {
+            tips,
+            pending_transactions,
+        }
+    }
+
+    /// notify the subscribers that the block with the given header became the tip
+    pub fn notify_new_tip(&self, header: Header) {
+        if self.tips.send(header).is_err() {
+            tracing::trace!("there are no subscribers to notify of the new tip");
+        }
+    }
+
+    /// notify the subscribers that the fragment was accepted in the pool,
+    /// only EVM transactions are of interest to them
+    pub fn notify_new_fragment(&self, fragment: &Fragment, id: FragmentId) {
+        if let Fragment::Evm(_) = fragment {
+            if self.pending_transactions.send(id).is_err() {
+                tracing::trace!("there are no subscribers to notify of the pending transaction");
+            }
+        }
+    }
+
+    pub fn subscribe_tips(&self) -> broadcast::Receiver<Header> {
+        self.tips.subscribe()
+    }
+
+    pub fn subscribe_pending_transactions(&self) -> broadcast::Receiver<FragmentId> {
+        self.pending_transactions.subscribe()
+    }
+}
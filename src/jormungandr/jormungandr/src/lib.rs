@@ -96,6 +96,10 @@ fn start_services(bootstrapped_node: BootstrappedNode) -> Result<(), start_up::E
     let (topology_msgbox, topology_queue) = async_msg::channel(TOPOLOGY_TASK_QUEUE_LEN);
     let blockchain_tip = bootstrapped_node.blockchain_tip;
     let blockchain = bootstrapped_node.blockchain;
+    let jrpc_notifier = bootstrapped_node
+        .context
+        .as_ref()
+        .map(|context| block_on(async { context.read().await.jrpc_notifier().clone() }));
     let leadership_logs =
         leadership::Logs::new(bootstrapped_node.settings.leadership.logs_capacity);
 
@@ -130,7 +134,7 @@ fn start_services(bootstrapped_node: BootstrappedNode) -> Result<(), start_up::E
         let current_tip = block_on(async { blockchain_tip.get_ref().await.header().clone() });
 
         let (client, message_processor) =
-            watch_client::WatchClient::new(current_tip, blockchain.clone());
+            watch_client::WatchClient::new(current_tip, blockchain.clone(), jrpc_notifier.clone());
 
         services.spawn_future("watch_client", move |info| async move {
             message_processor.start(info, queue).await
@@ -296,6 +300,7 @@ fn start_services(bootstrapped_node: BootstrappedNode) -> Result<(), start_up::E
             bootstrapped_node.settings.mempool.pool_max_entries.into(),
             bootstrapped_node.settings.mempool.log_max_entries.into(),
             network_msgbox.clone(),
+            jrpc_notifier,
         );
         let fragment_log_dir = bootstrapped_node
             .settings
@@ -446,10 +451,16 @@ async fn bootstrap_internal(
     let block0_hash = block0.header().hash();
 
     let cache_capacity = 102_400;
+    let archive = settings.jrpc.as_ref().map_or(false, |jrpc| jrpc.archive);
 
-    let (blockchain, blockchain_tip) =
-        start_up::load_blockchain(block0, storage, cache_capacity, settings.rewards_report_all)
-            .await?;
+    let (blockchain, blockchain_tip) = start_up::load_blockchain(
+        block0,
+        storage,
+        cache_capacity,
+        settings.rewards_report_all,
+        archive,
+    )
+    .await?;
 
     if let Some(context) = &context {
         let mut context = context.write().await;
@@ -663,6 +674,17 @@ fn initialize_node() -> Result<InitializedNode, start_up::Error> {
 
             let jrpc_config = jrpc::Config {
                 listen: jrpc_config.listen,
+                websocket: jrpc_config
+                    .websocket
+                    .map(|websocket| jrpc::WebSocketConfig {
+                        listen: websocket.listen,
+                        max_connections: websocket
+                            .max_connections
+                            .unwrap_or(jrpc::DEFAULT_WEBSOCKET_MAX_CONNECTIONS),
+                        max_subscriptions_per_connection: websocket
+                            .max_subscriptions_per_connection
+                            .unwrap_or(jrpc::DEFAULT_WEBSOCKET_MAX_SUBSCRIPTIONS_PER_CONNECTION),
+                    }),
             };
             let server_handler = jrpc::start_jrpc_server(jrpc_config, context.clone());
             let service_context = context.clone();
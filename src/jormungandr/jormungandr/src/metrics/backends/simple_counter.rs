@@ -203,6 +203,8 @@ impl MetricsBackend for SimpleCounter {
                     }
                     Fragment::VoteTally(tx) => totals(tx),
                     Fragment::MintToken(tx) => totals(tx),
+                    Fragment::BurnToken(tx) => totals(tx),
+                    Fragment::VoteDelegation(tx) => totals(tx),
                     Fragment::UpdateProposal(tx) => totals(tx),
                     Fragment::UpdateVote(tx) => totals(tx),
                     Fragment::EvmMapping(tx) => totals(tx),
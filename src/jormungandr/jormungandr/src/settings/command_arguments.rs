@@ -77,6 +77,17 @@ pub struct JRpcArguments {
     /// If not configured anywhere, defaults to JRPC API being disabled
     #[structopt(name = "jrpc-listen")]
     pub listen: Option<SocketAddr>,
+
+    /// Run the JRPC API in archive mode, keeping the EVM logs of every block
+    /// and the ledger state of the recent blocks to serve requests on
+    /// historical blocks
+    #[structopt(long = "jrpc-archive")]
+    pub archive: bool,
+
+    /// JRPC WebSocket listening address, serving `eth_subscribe`.
+    /// If not configured anywhere, defaults to subscriptions being disabled
+    #[structopt(long = "jrpc-websocket-listen")]
+    pub websocket_listen: Option<SocketAddr>,
 }
 
 #[derive(StructOpt, Debug)]
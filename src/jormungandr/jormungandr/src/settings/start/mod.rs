@@ -10,7 +10,7 @@ use crate::{
     topology::layers::{self, LayersConfig, PreferredListConfig, RingsConfig},
 };
 use chain_crypto::Ed25519;
-pub use jormungandr_lib::interfaces::{Cors, JRpc, Mempool, Rest, Tls};
+pub use jormungandr_lib::interfaces::{Cors, JRpc, JRpcWebSocket, Mempool, Rest, Tls};
 use jormungandr_lib::{crypto::key::SigningKey, multiaddr};
 use std::{convert::TryFrom, fs::File, path::PathBuf};
 use thiserror::Error;
@@ -127,11 +127,41 @@ impl RawSettings {
 
     fn jrpc_config(&self) -> Option<JRpc> {
         let cmd_listen_opt = self.command_line.jrpc_arguments.listen;
+        let cmd_archive = self.command_line.jrpc_arguments.archive;
+        let cmd_websocket_listen_opt = self.command_line.jrpc_arguments.websocket_listen;
         let config_rpc_opt = self.config.as_ref().and_then(|cfg| cfg.jrpc.clone());
+        let websocket = |config_websocket: Option<JRpcWebSocket>| match (
+            config_websocket,
+            cmd_websocket_listen_opt,
+        ) {
+            (Some(config_websocket), Some(cmd_websocket_listen)) => Some(JRpcWebSocket {
+                listen: cmd_websocket_listen,
+                ..config_websocket
+            }),
+            (Some(config_websocket), None) => Some(config_websocket),
+            (None, Some(cmd_websocket_listen)) => Some(JRpcWebSocket {
+                listen: cmd_websocket_listen,
+                max_connections: None,
+                max_subscriptions_per_connection: None,
+            }),
+            (None, None) => None,
+        };
         match (config_rpc_opt, cmd_listen_opt) {
-            (Some(_), Some(cmd_listen)) => Some(JRpc { listen: cmd_listen }),
-            (Some(config_rpc), None) => Some(config_rpc),
-            (None, Some(cmd_listen)) => Some(JRpc { listen: cmd_listen }),
+            (Some(config_rpc), Some(cmd_listen)) => Some(JRpc {
+                listen: cmd_listen,
+                archive: config_rpc.archive || cmd_archive,
+                websocket: websocket(config_rpc.websocket),
+            }),
+            (Some(config_rpc), None) => Some(JRpc {
+                archive: config_rpc.archive || cmd_archive,
+                websocket: websocket(config_rpc.websocket.clone()),
+                ..config_rpc
+            }),
+            (None, Some(cmd_listen)) => Some(JRpc {
+                listen: cmd_listen,
+                archive: cmd_archive,
+                websocket: websocket(None),
+            }),
             (None, None) => None,
         }
     }
@@ -152,12 +152,14 @@ pub async fn load_blockchain(
     storage: Storage,
     cache_capacity: usize,
     rewards_report_all: bool,
+    archive: bool,
 ) -> Result<(Blockchain, Tip), Error> {
     let blockchain = Blockchain::new(
         block0.header().hash(),
         storage,
         cache_capacity,
         rewards_report_all,
+        archive,
     );
 
     let tip = match blockchain.load_from_block0(block0.clone()).await {
@@ -3,6 +3,7 @@ use crate::{
     blockcfg::HeaderHash,
     blockchain::{Blockchain, Storage},
     intercom::{self, ReplyStream, ReplyStreamHandle},
+    jrpc::Notifier,
     utils::{
         async_msg::{MessageBox, MessageQueue},
         task::TokioServiceInfo,
@@ -41,6 +42,7 @@ pub struct MessageProcessor {
     requests: MessageQueue<RequestMsg>,
     storage: Storage,
     blockchain: Blockchain,
+    jrpc_notifier: Option<Notifier>,
 }
 
 enum RequestMsg {
@@ -113,6 +115,9 @@ impl MessageProcessor {
                     );
                 }
                 Message::NewTip(header) => {
+                    if let Some(jrpc_notifier) = &self.jrpc_notifier {
+                        jrpc_notifier.notify_new_tip(header.clone());
+                    }
                     let tip_sender = Arc::clone(&self.tip_sender);
                     let tip_id = header.id();
                     info.spawn(
@@ -144,6 +149,7 @@ impl WatchClient {
     pub fn new(
         current_tip: header::Header,
         blockchain: Blockchain,
+        jrpc_notifier: Option<Notifier>,
     ) -> (WatchClient, MessageProcessor) {
         let storage = blockchain.storage().clone();
         let (tip_sender, tip_receiver) = watch::channel(Header::from_bytes(
@@ -169,6 +175,7 @@ impl WatchClient {
             storage,
             blockchain,
             requests,
+            jrpc_notifier,
         };
 
         (client, message_processor)
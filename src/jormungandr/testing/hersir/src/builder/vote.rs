@@ -48,7 +48,9 @@ pub fn generate_vote_plans(
             vote_end: template.vote_end,
             committee_end: template.committee_end,
             proposals: template.proposals.clone(),
+            weighted_ballot: None,
             committee_member_public_keys: vec![],
+            committee_threshold: None,
             voting_token: template.voting_token.clone(),
         };
 
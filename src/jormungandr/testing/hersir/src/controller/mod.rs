@@ -199,6 +199,19 @@ impl Controller {
                     ParametersGovernanceAction::NoOp => {
                         proposal_builder.action_parameters_no_op();
                     }
+                    ParametersGovernanceAction::UpdateFees {
+                        constant,
+                        coefficient,
+                        certificate,
+                    } => {
+                        proposal_builder.action_update_fees(*constant, *coefficient, *certificate);
+                    }
+                    ParametersGovernanceAction::SlotDuration { duration } => {
+                        proposal_builder.action_slot_duration(*duration);
+                    }
+                    ParametersGovernanceAction::BlockContentMaxSize { size } => {
+                        proposal_builder.action_block_content_max_size(*size);
+                    }
                 },
             };
 
@@ -238,5 +238,6 @@ fn payload_type_to_string(payload_type: PayloadType) -> &'static str {
     match payload_type {
         PayloadType::Public => "public",
         PayloadType::Private => "private",
+        PayloadType::PrivateWeighted => "private_weighted",
     }
 }
@@ -59,6 +59,8 @@ impl Default for NodeConfigBuilder {
             },
             jrpc: JRpc {
                 listen: format!("{}:{}", DEFAULT_HOST, jrpc_port).parse().unwrap(),
+                archive: false,
+                websocket: None,
             },
             p2p: P2p {
                 bootstrap: Bootstrap {
@@ -49,6 +49,8 @@ impl NodeConfigBuilder {
             },
             jrpc: JRpc {
                 listen: format!("{}:{}", DEFAULT_HOST, jrpc_port).parse().unwrap(),
+                archive: false,
+                websocket: None,
             },
             p2p: P2p {
                 node_key_file: None,
@@ -53,6 +53,10 @@ impl ExplorerVerifier {
                             vote_plan_status.payload,
                             vote::PayloadType::Private
                         )),
+                        all_vote_plans::PayloadType::PRIVATE_WEIGHTED => assert!(matches!(
+                            vote_plan_status.payload,
+                            vote::PayloadType::PrivateWeighted
+                        )),
                         all_vote_plans::PayloadType::Other(_) => panic!("Wrong payload type"),
                     }
 
@@ -176,6 +180,13 @@ impl ExplorerVerifier {
                                                 assert_eq!(choice.choice as u8, vote.1.as_byte())
                                             }
                                             VotePayloadPrivateStatus(_) => todo!(),
+                                            VotePayloadPrivateWeightedStatus(payload) => {
+                                                Self::assert_private_weighted_vote(
+                                                    &payload.encrypted_vote,
+                                                    &payload.proof,
+                                                    vote_proposal_status.options.len(),
+                                                )
+                                            }
                                         }
                                     }
                                 }
@@ -261,6 +261,39 @@ impl ExplorerVerifier {
                                             //Not implemented because of the bug EAS-238
                                            return Err(VerifierError::Unimplemented);
                                         }
+                                        BlockByIdBlockTransactionsEdgesNodeCertificate::BurnToken(explorer_cert) => {
+                                            if let Fragment::BurnToken(fragment_cert) = fragment {
+                                                Self::assert_block_transaction_param(
+                                                    &fragment_cert.clone(),
+                                                    explorer_transaction,
+                                                )
+                                                .unwrap();
+                                                let burn_token = fragment_cert.as_slice().payload().into_payload();
+                                                assert_eq!(explorer_cert.value, burn_token.value.to_string());
+                                            } else {
+                                              return Err(VerifierError::InvalidCertificate {
+                                                    received: "BurnToken".to_string(),
+                                                });
+                                            }
+                                        }
+                                        BlockByIdBlockTransactionsEdgesNodeCertificate::VoteDelegation(explorer_cert) => {
+                                            if let Fragment::VoteDelegation(fragment_cert) = fragment {
+                                                Self::assert_block_transaction_param(
+                                                    &fragment_cert.clone(),
+                                                    explorer_transaction,
+                                                )
+                                                .unwrap();
+                                                let vote_delegation = fragment_cert.as_slice().payload().into_payload();
+                                                assert_eq!(
+                                                    explorer_cert.representatives.len(),
+                                                    vote_delegation.delegation.representatives().len()
+                                                );
+                                            } else {
+                                              return Err(VerifierError::InvalidCertificate {
+                                                    received: "VoteDelegation".to_string(),
+                                                });
+                                            }
+                                        }
                                     }
                             }
                         }
@@ -835,6 +868,10 @@ impl ExplorerVerifier {
                 explorer_cert.payload_type,
                 expPayloadType::PRIVATE
             )),
+            PayloadType::PrivateWeighted => assert!(matches!(
+                explorer_cert.payload_type,
+                expPayloadType::PRIVATE_WEIGHTED
+            )),
         }
 
         assert_eq!(
@@ -107,4 +107,21 @@ impl ExplorerVerifier {
         let reader = std::io::Cursor::new(&bytes_block);
         Block::deserialize(&mut Codec::new(reader)).unwrap()
     }
+
+    /// the explorer only exposes the encoded private weighted vote, check that
+    /// it is a well formed vote for a proposal with `options` options
+    pub fn assert_private_weighted_vote(encrypted_vote: &str, proof: &str, options: usize) {
+        let encrypted_vote = base64::decode_config(encrypted_vote, base64::URL_SAFE).unwrap();
+        let (len, ciphertexts) = encrypted_vote.split_first().unwrap();
+        let encrypted_vote = chain_vote::EncryptedWeightedVote::from_bytes(ciphertexts)
+            .expect("invalid encrypted weighted vote");
+        assert_eq!(encrypted_vote.len(), *len as usize);
+        // every option is encrypted with at least one bit, followed by the slack
+        assert!(encrypted_vote.len() > options);
+
+        let proof = base64::decode_config(proof, base64::URL_SAFE).unwrap();
+        let (len, proof) = proof.split_at(2);
+        assert_eq!(u16::from_be_bytes([len[0], len[1]]) as usize, proof.len());
+        assert!(chain_vote::ProofOfCorrectWeightedVote::from_bytes(proof).is_some());
+    }
 }
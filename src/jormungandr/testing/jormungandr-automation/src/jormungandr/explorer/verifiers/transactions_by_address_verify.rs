@@ -235,6 +235,41 @@ impl ExplorerVerifier {
                 //Not implemented because of the bug EAS-238
                 Err(VerifierError::Unimplemented)
             }
+            TransactionsByAddressTipTransactionsByAddressEdgesNodeCertificate::BurnToken(explorer_cert) => {
+                if let Fragment::BurnToken(fragment_cert) = fragment {
+                    Self::assert_transaction_address_params(
+                        fragment_cert,
+                        explorer_transaction,
+                    )
+                    .unwrap();
+                    let burn_token = fragment_cert.as_slice().payload().into_payload();
+                    assert_eq!(explorer_cert.value, burn_token.value.to_string());
+                    Ok(())
+                } else {
+                    Err(VerifierError::InvalidCertificate {
+                        received: "BurnToken".to_string(),
+                    })
+                }
+            }
+            TransactionsByAddressTipTransactionsByAddressEdgesNodeCertificate::VoteDelegation(explorer_cert) => {
+                if let Fragment::VoteDelegation(fragment_cert) = fragment {
+                    Self::assert_transaction_address_params(
+                        fragment_cert,
+                        explorer_transaction,
+                    )
+                    .unwrap();
+                    let vote_delegation = fragment_cert.as_slice().payload().into_payload();
+                    assert_eq!(
+                        explorer_cert.representatives.len(),
+                        vote_delegation.delegation.representatives().len()
+                    );
+                    Ok(())
+                } else {
+                    Err(VerifierError::InvalidCertificate {
+                        received: "VoteDelegation".to_string(),
+                    })
+                }
+            }
         }
         }
     }
@@ -525,6 +560,10 @@ impl ExplorerVerifier {
                 explorer_cert.payload_type,
                 expPayloadType::PRIVATE
             )),
+            PayloadType::PrivateWeighted => assert!(matches!(
+                explorer_cert.payload_type,
+                expPayloadType::PRIVATE_WEIGHTED
+            )),
         }
 
         assert_eq!(
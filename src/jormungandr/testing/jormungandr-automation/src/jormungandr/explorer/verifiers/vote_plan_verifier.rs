@@ -47,6 +47,10 @@ impl ExplorerVerifier {
                 vote_plan_status.payload,
                 vote::PayloadType::Private
             )),
+            vote_plan_by_id::PayloadType::PRIVATE_WEIGHTED => assert!(matches!(
+                vote_plan_status.payload,
+                vote::PayloadType::PrivateWeighted
+            )),
             vote_plan_by_id::PayloadType::Other(_) => panic!("Wrong payload type"),
         }
 
@@ -149,6 +153,13 @@ impl ExplorerVerifier {
                                     assert_eq!(choice.choice as u8, vote.1.as_byte())
                                 }
                                 VotePayloadPrivateStatus(_) => todo!(),
+                                VotePayloadPrivateWeightedStatus(payload) => {
+                                    Self::assert_private_weighted_vote(
+                                        &payload.encrypted_vote,
+                                        &payload.proof,
+                                        vote_proposal_status.options.len(),
+                                    )
+                                }
                             }
                         }
                     }
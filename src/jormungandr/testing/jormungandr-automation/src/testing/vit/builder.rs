@@ -115,7 +115,9 @@ impl VotePlanBuilder {
             self.tally_end,
             proposals,
             self.payload,
+            None,
             self.member_keys.clone(),
+            None,
             self.voting_token,
         )
     }
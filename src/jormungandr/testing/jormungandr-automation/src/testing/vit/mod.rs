@@ -4,6 +4,7 @@ mod vote_cast_register;
 pub use builder::VotePlanBuilder;
 use chain_crypto::bech32::Bech32;
 use chain_impl_mockchain::{certificate::VotePlan, vote::PayloadType};
+use chain_vote::WeightedBallotKind;
 pub use vote_cast_register::VoteCastCounter;
 
 pub trait VotePlanExtension {
@@ -18,10 +19,27 @@ impl VotePlanExtension for VotePlan {
         let payload = match self.payload_type() {
             PayloadType::Public => "public",
             PayloadType::Private => "private",
+            PayloadType::PrivateWeighted => "private_weighted",
         };
 
         data["payload_type"] = json::JsonValue::String(payload.to_owned());
 
+        if let Some(weighted_ballot) = self.weighted_ballot() {
+            let mut kind = json::JsonValue::new_object();
+            let mut weighted = json::JsonValue::new_object();
+            match weighted_ballot {
+                WeightedBallotKind::Approval { max_selections } => {
+                    kind["max_selections"] = max_selections.get().into();
+                    weighted["approval"] = kind;
+                }
+                WeightedBallotKind::Budget { budget } => {
+                    kind["budget"] = budget.get().into();
+                    weighted["budget"] = kind;
+                }
+            }
+            data["weighted_ballot"] = weighted;
+        }
+
         let mut vote_start = json::JsonValue::new_object();
         vote_start["epoch"] = self.vote_start().epoch.into();
         vote_start["slot_id"] = self.vote_start().slot_id.into();
@@ -83,6 +101,38 @@ impl VotePlanExtension for VotePlan {
                                 }}
                             }}"#,value)).unwrap();
                         }
+                        chain_impl_mockchain::ledger::governance::ParametersGovernanceAction::UpdateFees { constant, coefficient, certificate } => {
+                            item["action"] = json::parse(&format!(r#"
+                            {{
+                                "governance": {{
+                                    "update_fees": {{
+                                        "constant": {},
+                                        "coefficient": {},
+                                        "certificate": {}
+                                    }},
+                                }}
+                            }}"#,constant,coefficient,certificate)).unwrap();
+                        }
+                        chain_impl_mockchain::ledger::governance::ParametersGovernanceAction::SlotDuration { duration } => {
+                            item["action"] = json::parse(&format!(r#"
+                            {{
+                                "governance": {{
+                                    "slot_duration": {{
+                                        "duration": {}
+                                    }},
+                                }}
+                            }}"#,duration)).unwrap();
+                        }
+                        chain_impl_mockchain::ledger::governance::ParametersGovernanceAction::BlockContentMaxSize { size } => {
+                            item["action"] = json::parse(&format!(r#"
+                            {{
+                                "governance": {{
+                                    "block_content_max_size": {{
+                                        "size": {}
+                                    }},
+                                }}
+                            }}"#,size)).unwrap();
+                        }
                     }
                 }
             }
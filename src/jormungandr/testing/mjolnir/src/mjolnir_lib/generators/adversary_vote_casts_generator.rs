@@ -76,6 +76,10 @@ impl<'a, S: SyncNode + Send> AdversaryVoteCastsGenerator<'a, S> {
                 FragmentBuilder::new(&block0_hash, &fees, self.expiry_generator.block_date())
                     .private_vote_cast(&self.voter, &vote_plan, 0, &Choice::new(0))
             }
+            PayloadType::PrivateWeighted => {
+                FragmentBuilder::new(&block0_hash, &fees, self.expiry_generator.block_date())
+                    .private_weighted_vote_cast(&self.voter, &vote_plan, 0, &Choice::new(0))
+            }
         }
     }
 
@@ -97,6 +101,10 @@ impl<'a, S: SyncNode + Send> AdversaryVoteCastsGenerator<'a, S> {
                 FragmentBuilder::new(&block0_hash, &fees, self.expiry_generator.block_date())
                     .private_vote_cast(&self.voter, vote_plan, 255, &Choice::new(0))
             }
+            PayloadType::PrivateWeighted => {
+                FragmentBuilder::new(&block0_hash, &fees, self.expiry_generator.block_date())
+                    .private_weighted_vote_cast(&self.voter, vote_plan, 255, &Choice::new(0))
+            }
         }
     }
 
@@ -114,6 +122,10 @@ impl<'a, S: SyncNode + Send> AdversaryVoteCastsGenerator<'a, S> {
                 FragmentBuilder::new(&block0_hash, &fees, self.expiry_generator.block_date())
                     .public_vote_cast(&self.voter, vote_plan, 0, &Choice::new(0))
             }
+            PayloadType::PrivateWeighted => {
+                FragmentBuilder::new(&block0_hash, &fees, self.expiry_generator.block_date())
+                    .public_vote_cast(&self.voter, vote_plan, 0, &Choice::new(0))
+            }
         }
     }
 
@@ -132,6 +144,10 @@ impl<'a, S: SyncNode + Send> AdversaryVoteCastsGenerator<'a, S> {
                 FragmentBuilder::new(&block0_hash, &fees, self.expiry_generator.block_date())
                     .private_vote_cast(&self.voter, vote_plan, 0, &Choice::new(options))
             }
+            PayloadType::PrivateWeighted => {
+                FragmentBuilder::new(&block0_hash, &fees, self.expiry_generator.block_date())
+                    .private_weighted_vote_cast(&self.voter, vote_plan, 0, &Choice::new(options))
+            }
         }
     }
 }
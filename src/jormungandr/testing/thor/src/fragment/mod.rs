@@ -19,7 +19,8 @@ use chain_impl_mockchain::evm::EvmTransaction;
 use chain_impl_mockchain::{
     block::BlockDate,
     certificate::{
-        PoolId, UpdateProposal, UpdateVote, VoteCast, VotePlan, VoteTally, VoteTallyPayload,
+        PoolId, UpdateProposal, UpdateVote, VoteCast, VoteDelegation, VotePlan, VoteTally,
+        VoteTallyPayload,
     },
     fee::{FeeAlgorithm, LinearFee},
     fragment::Fragment,
@@ -280,6 +281,9 @@ impl FragmentBuilder {
             PayloadType::Private => {
                 self.private_vote_cast(wallet, vote_plan, proposal_index, choice)
             }
+            PayloadType::PrivateWeighted => {
+                self.private_weighted_vote_cast(wallet, vote_plan, proposal_index, choice)
+            }
         }
     }
 
@@ -305,8 +309,7 @@ impl FragmentBuilder {
     ) -> Fragment {
         let mut rng = ChaCha20Rng::from_seed([0u8; 32]);
 
-        let election_key =
-            chain_vote::ElectionPublicKey::from_participants(vote_plan.committee_public_keys());
+        let election_key = vote_plan.election_public_key();
 
         let options = vote_plan
             .proposals()
@@ -342,6 +345,67 @@ impl FragmentBuilder {
             .vote_cast(self.valid_until, &inner_wallet, vote_cast)
     }
 
+    /// Cast a weighted vote giving the largest weight allowed by the vote plan
+    /// to `choice`, and nothing to the other options.
+    pub fn private_weighted_vote_cast(
+        &self,
+        wallet: &Wallet,
+        vote_plan: &VotePlan,
+        proposal_index: u8,
+        choice: &Choice,
+    ) -> Fragment {
+        let mut rng = ChaCha20Rng::from_seed([0u8; 32]);
+
+        let election_key = vote_plan.election_public_key();
+        let kind = *vote_plan
+            .weighted_ballot()
+            .expect("weighted vote plan without a weighted ballot");
+
+        let options = vote_plan
+            .proposals()
+            .iter()
+            .nth((proposal_index).into())
+            .unwrap()
+            .options();
+
+        let length = options
+            .choice_range()
+            .end
+            .checked_sub(options.choice_range().start)
+            .unwrap();
+
+        let mut weights = vec![0u8; length as usize];
+        weights[(choice.as_byte() - options.choice_range().start) as usize] = kind.max_weight();
+        let vote = chain_vote::WeightedVote::new(kind, weights).unwrap();
+        let crs = chain_vote::Crs::from_hash(vote_plan.to_id().as_ref());
+        let (encrypted_vote, proof) =
+            chain_impl_mockchain::vote::encrypt_weighted_vote(&mut rng, &crs, &election_key, &vote)
+                .expect("weighted vote too large");
+
+        let vote_cast = VoteCast::new(
+            vote_plan.to_id(),
+            proposal_index,
+            Payload::PrivateWeighted {
+                encrypted_vote,
+                proof,
+            },
+        );
+
+        let inner_wallet = wallet.clone().into();
+
+        self.fragment_factory
+            .vote_cast(self.valid_until, &inner_wallet, vote_cast)
+    }
+
+    pub fn vote_delegation(&self, wallet: &Wallet, vote_delegation: &VoteDelegation) -> Fragment {
+        let inner_wallet = wallet.clone().into();
+        self.fragment_factory.vote_delegation(
+            self.valid_until,
+            &inner_wallet,
+            vote_delegation.clone(),
+        )
+    }
+
     pub fn vote_tally(
         &self,
         wallet: &Wallet,
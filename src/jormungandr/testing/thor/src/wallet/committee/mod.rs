@@ -217,7 +217,7 @@ impl CommitteeDataManager {
                 .map(
                     |(tally_result, decrypt_shares)| DecryptedPrivateTallyProposal {
                         tally_result: tally_result.votes.into_boxed_slice(),
-                        decrypt_shares: decrypt_shares.into_boxed_slice(),
+                        decrypt_shares: decrypt_shares.into_iter().map(Some).collect(),
                     },
                 )
                 .collect(),
@@ -8,8 +8,12 @@ use thiserror::Error;
 use time::{Duration, OffsetDateTime};
 use vit_servicing_station_lib::{
     db::{
-        load_db_connection_pool, models::api_tokens::ApiTokenData,
-        queries::api_tokens::insert_token_data, DbConnection, Error as DbPoolError,
+        load_db_connection_pool,
+        models::api_tokens::{ApiTokenData, ApiTokenScope},
+        queries::api_tokens::{
+            insert_token_data, query_all_token_data, query_audit_log, revoke_token_data,
+        },
+        DbConnection, Error as DbPoolError,
     },
     v0::api_token::ApiToken,
 };
@@ -32,6 +36,9 @@ pub enum Error {
     #[error("Error connecting to db")]
     DbConnection(#[from] r2d2::Error),
 
+    #[error("token `{0}` not found or already revoked")]
+    TokenNotFound(String),
+
     #[error(transparent)]
     Io(#[from] std::io::Error),
 }
@@ -47,6 +54,47 @@ pub enum ApiTokenCmd {
         /// URL of the vit-servicing-station database to interact with
         #[structopt(long = "db-url")]
         db_url: String,
+
+        /// What the tokens give access to: read-only, snapshot-write or admin
+        #[structopt(long = "scope", default_value = "read-only")]
+        scope: ApiTokenScope,
+
+        /// Number of days before the tokens expire
+        #[structopt(long = "expire-in-days", default_value = "365")]
+        expire_in_days: i64,
+    },
+
+    /// Revoke the provided tokens, they are rejected from then on
+    Revoke {
+        /// List of tokens in URL safe base64
+        #[structopt(long = "tokens", required = true)]
+        tokens: Vec<String>,
+
+        /// URL of the vit-servicing-station database to interact with
+        #[structopt(long = "db-url")]
+        db_url: String,
+    },
+
+    /// List the tokens with their scope, expiry and revocation time
+    List {
+        /// URL of the vit-servicing-station database to interact with
+        #[structopt(long = "db-url")]
+        db_url: String,
+    },
+
+    /// Print the writes performed with API tokens, the most recent first
+    Audit {
+        /// Only print the writes performed with this token, in URL safe base64
+        #[structopt(long = "token")]
+        token: Option<String>,
+
+        /// Maximum number of entries to print
+        #[structopt(long = "limit", default_value = "100")]
+        limit: i64,
+
+        /// URL of the vit-servicing-station database to interact with
+        #[structopt(long = "db-url")]
+        db_url: String,
     },
 
     /// Generate API tokens, URL safe base64 encoded.
@@ -72,7 +120,24 @@ impl ApiTokenCmd {
             .collect()
     }
 
-    fn add_tokens_from_stream(db_conn: &DbConnection) -> Result<(), Error> {
+    fn decode_token(base64_token: &str) -> Result<Vec<u8>, Error> {
+        base64::decode_config(base64_token, base64::URL_SAFE_NO_PAD).map_err(|e| {
+            Error::Base64Decode {
+                source: e,
+                token: base64_token.to_string(),
+            }
+        })
+    }
+
+    fn encode_token(token: &ApiToken) -> String {
+        base64::encode_config(token, base64::URL_SAFE_NO_PAD)
+    }
+
+    fn add_tokens_from_stream(
+        db_conn: &DbConnection,
+        scope: ApiTokenScope,
+        expire_in_days: i64,
+    ) -> Result<(), Error> {
         let mut base64_tokens: Vec<String> = Vec::new();
         let mut input = String::new();
         while let Ok(n) = io::stdin().read_line(&mut input) {
@@ -83,50 +148,73 @@ impl ApiTokenCmd {
             input.pop();
             base64_tokens.push(input.clone());
         }
-        ApiTokenCmd::add_tokens(&base64_tokens, db_conn)
+        ApiTokenCmd::add_tokens(&base64_tokens, db_conn, scope, expire_in_days)
     }
 
-    fn add_tokens(base64_tokens: &[String], db_conn: &DbConnection) -> Result<(), Error> {
+    fn add_tokens(
+        base64_tokens: &[String],
+        db_conn: &DbConnection,
+        scope: ApiTokenScope,
+        expire_in_days: i64,
+    ) -> Result<(), Error> {
         // filter duplicated tokens
         let base64_tokens: HashSet<String> = base64_tokens.iter().cloned().collect();
         for base64_token in base64_tokens {
-            let token =
-                base64::decode_config(&base64_token, base64::URL_SAFE_NO_PAD).map_err(|e| {
-                    Error::Base64Decode {
-                        source: e,
-                        token: base64_token,
-                    }
-                })?;
+            let token = ApiTokenCmd::decode_token(&base64_token)?;
             let api_token_data = ApiTokenData {
                 token: ApiToken::new(token),
                 creation_time: OffsetDateTime::now_utc().unix_timestamp(),
-                expire_time: (OffsetDateTime::now_utc() + Duration::days(365)).unix_timestamp(),
+                expire_time: (OffsetDateTime::now_utc() + Duration::days(expire_in_days))
+                    .unix_timestamp(),
+                scope,
+                revoked_time: None,
             };
             insert_token_data(api_token_data, db_conn).map_err(Error::Db)?;
         }
         Ok(())
     }
 
-    fn handle_api_token_add(tokens: &Option<Vec<String>>, db_url: &str) -> Result<(), Error> {
+    fn revoke_tokens(base64_tokens: &[String], db_conn: &DbConnection) -> Result<(), Error> {
+        for base64_token in base64_tokens {
+            let token = ApiTokenCmd::decode_token(base64_token)?;
+            if revoke_token_data(&token, db_conn).map_err(Error::Db)? == 0 {
+                return Err(Error::TokenNotFound(base64_token.clone()));
+            }
+        }
+        Ok(())
+    }
+
+    fn open_db(db_url: &str) -> Result<DbConnection, Error> {
         // check if db file exists
         db_file_exists(db_url)?;
 
         let pool = load_db_connection_pool(db_url).map_err(Error::DbPool)?;
-        let db_conn = pool.get()?;
+        Ok(pool.get()?)
+    }
+
+    fn handle_api_token_add(
+        tokens: &Option<Vec<String>>,
+        db_url: &str,
+        scope: ApiTokenScope,
+        expire_in_days: i64,
+    ) -> Result<(), Error> {
+        let db_conn = ApiTokenCmd::open_db(db_url)?;
 
         match tokens {
             // if not tokens are provided then listen to stdin for input ones
-            None => ApiTokenCmd::add_tokens_from_stream(&db_conn),
+            None => ApiTokenCmd::add_tokens_from_stream(&db_conn, scope, expire_in_days),
             // process the provided tokens
-            Some(tokens) => ApiTokenCmd::add_tokens(tokens, &db_conn),
+            Some(tokens) => ApiTokenCmd::add_tokens(tokens, &db_conn, scope, expire_in_days),
         }
     }
 
     fn handle_api_token_add_with_db_backup(
         tokens: &Option<Vec<String>>,
         db_url: &str,
+        scope: ApiTokenScope,
+        expire_in_days: i64,
     ) -> Result<(), Error> {
-        if let Err(e) = Self::handle_api_token_add(tokens, db_url) {
+        if let Err(e) = Self::handle_api_token_add(tokens, db_url, scope, expire_in_days) {
             if !db_url.starts_with("postgres://") {
                 let backup_file = backup_db_file(db_url)?;
                 restore_db_file(backup_file, db_url)?;
@@ -138,6 +226,43 @@ impl ApiTokenCmd {
         }
     }
 
+    fn handle_list(db_url: &str) -> Result<(), Error> {
+        let db_conn = ApiTokenCmd::open_db(db_url)?;
+        for token_data in query_all_token_data(&db_conn)? {
+            let revoked = token_data
+                .revoked_time
+                .map_or_else(|| "-".to_string(), |time| time.to_string());
+            println!(
+                "{} scope={} created={} expires={} revoked={}",
+                ApiTokenCmd::encode_token(&token_data.token),
+                token_data.scope,
+                token_data.creation_time,
+                token_data.expire_time,
+                revoked
+            );
+        }
+        Ok(())
+    }
+
+    fn handle_audit(token: &Option<String>, limit: i64, db_url: &str) -> Result<(), Error> {
+        let token = token
+            .as_deref()
+            .map(ApiTokenCmd::decode_token)
+            .transpose()?;
+        let db_conn = ApiTokenCmd::open_db(db_url)?;
+        for entry in query_audit_log(token.as_deref(), limit, &db_conn)? {
+            println!(
+                "{} {} {} {} {}",
+                entry.time,
+                ApiTokenCmd::encode_token(&entry.token),
+                entry.method,
+                entry.path,
+                entry.status
+            );
+        }
+        Ok(())
+    }
+
     fn handle_generate(n: usize, size: usize) {
         let tokens = ApiTokenCmd::generate(n, size);
         for token in tokens {
@@ -152,9 +277,27 @@ impl ExecTask for ApiTokenCmd {
 
     fn exec(&self) -> Result<(), Error> {
         match self {
-            ApiTokenCmd::Add { tokens, db_url } => {
-                ApiTokenCmd::handle_api_token_add_with_db_backup(tokens, db_url)
+            ApiTokenCmd::Add {
+                tokens,
+                db_url,
+                scope,
+                expire_in_days,
+            } => ApiTokenCmd::handle_api_token_add_with_db_backup(
+                tokens,
+                db_url,
+                *scope,
+                *expire_in_days,
+            ),
+            ApiTokenCmd::Revoke { tokens, db_url } => {
+                let db_conn = ApiTokenCmd::open_db(db_url)?;
+                ApiTokenCmd::revoke_tokens(tokens, &db_conn)
             }
+            ApiTokenCmd::List { db_url } => ApiTokenCmd::handle_list(db_url),
+            ApiTokenCmd::Audit {
+                token,
+                limit,
+                db_url,
+            } => ApiTokenCmd::handle_audit(token, *limit, db_url),
             ApiTokenCmd::Generate { n, size } => {
                 ApiTokenCmd::handle_generate(*n, *size);
                 Ok(())
@@ -193,14 +336,40 @@ mod test {
         let connection_pool = load_db_connection_pool("").unwrap();
         initialize_db_with_migration(&connection_pool.get().unwrap()).unwrap();
         let db_conn = connection_pool.get().unwrap();
-        ApiTokenCmd::add_tokens(&tokens, &db_conn).unwrap();
+        ApiTokenCmd::add_tokens(&tokens, &db_conn, ApiTokenScope::ReadOnly, 365).unwrap();
         for token in tokens
             .iter()
             .map(|t| base64::decode_config(t, base64::URL_SAFE_NO_PAD).unwrap())
         {
-            assert!(query_token_data_by_token(token.as_ref(), &db_conn)
+            let token_data = query_token_data_by_token(token.as_ref(), &db_conn)
                 .unwrap()
-                .is_some());
+                .unwrap();
+            assert_eq!(token_data.scope, ApiTokenScope::ReadOnly);
         }
     }
+
+    #[test]
+    fn revoke_token() {
+        let tokens = ApiTokenCmd::generate(2, 10);
+        let connection_pool = load_db_connection_pool("").unwrap();
+        initialize_db_with_migration(&connection_pool.get().unwrap()).unwrap();
+        let db_conn = connection_pool.get().unwrap();
+        ApiTokenCmd::add_tokens(&tokens, &db_conn, ApiTokenScope::Admin, 365).unwrap();
+
+        ApiTokenCmd::revoke_tokens(&tokens[..1], &db_conn).unwrap();
+        assert!(ApiTokenCmd::revoke_tokens(&tokens[..1], &db_conn).is_err());
+
+        let revoked = base64::decode_config(&tokens[0], base64::URL_SAFE_NO_PAD).unwrap();
+        let kept = base64::decode_config(&tokens[1], base64::URL_SAFE_NO_PAD).unwrap();
+        assert!(query_token_data_by_token(&revoked, &db_conn)
+            .unwrap()
+            .unwrap()
+            .revoked_time
+            .is_some());
+        assert!(query_token_data_by_token(&kept, &db_conn)
+            .unwrap()
+            .unwrap()
+            .revoked_time
+            .is_none());
+    }
 }
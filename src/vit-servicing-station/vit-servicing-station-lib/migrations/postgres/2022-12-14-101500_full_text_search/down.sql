@@ -0,0 +1,8 @@
+DROP INDEX community_advisors_reviews_search_document_idx;
+ALTER TABLE community_advisors_reviews DROP COLUMN search_document;
+
+DROP INDEX challenges_search_document_idx;
+ALTER TABLE challenges DROP COLUMN search_document;
+
+DROP INDEX proposals_search_document_idx;
+ALTER TABLE proposals DROP COLUMN search_document;
@@ -0,0 +1,20 @@
+-- Weighted search documents, kept up to date by the database.
+-- `full_proposals_info` expanded `p.*` when it was created, so it doesn't include the new column.
+ALTER TABLE proposals ADD COLUMN search_document tsvector GENERATED ALWAYS AS (
+    setweight(to_tsvector('english', proposal_title), 'A') ||
+    setweight(to_tsvector('english', proposal_summary), 'B') ||
+    setweight(to_tsvector('english', proposer_name), 'C') ||
+    setweight(to_tsvector('english', proposer_relevant_experience), 'D')
+) STORED;
+CREATE INDEX proposals_search_document_idx ON proposals USING GIN (search_document);
+
+ALTER TABLE challenges ADD COLUMN search_document tsvector GENERATED ALWAYS AS (
+    setweight(to_tsvector('english', title), 'A') ||
+    setweight(to_tsvector('english', description), 'B')
+) STORED;
+CREATE INDEX challenges_search_document_idx ON challenges USING GIN (search_document);
+
+ALTER TABLE community_advisors_reviews ADD COLUMN search_document tsvector GENERATED ALWAYS AS (
+    to_tsvector('english', impact_alignment_note || ' ' || feasibility_note || ' ' || auditability_note)
+) STORED;
+CREATE INDEX community_advisors_reviews_search_document_idx ON community_advisors_reviews USING GIN (search_document);
@@ -0,0 +1,3 @@
+DROP TABLE api_token_audit_log;
+ALTER TABLE api_tokens DROP revoked_time;
+ALTER TABLE api_tokens DROP scope;
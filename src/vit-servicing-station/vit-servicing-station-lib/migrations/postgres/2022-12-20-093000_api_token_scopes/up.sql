@@ -0,0 +1,13 @@
+-- Tokens created before scopes existed keep full access
+ALTER TABLE api_tokens ADD scope VARCHAR NOT NULL DEFAULT 'admin';
+ALTER TABLE api_tokens ADD revoked_time BIGINT;
+
+create table api_token_audit_log
+(
+    id SERIAL PRIMARY KEY,
+    token BYTEA NOT NULL,
+    time BIGINT NOT NULL,
+    method VARCHAR NOT NULL,
+    path VARCHAR NOT NULL,
+    status INTEGER NOT NULL
+);
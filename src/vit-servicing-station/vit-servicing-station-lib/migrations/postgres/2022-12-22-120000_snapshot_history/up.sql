@@ -0,0 +1,40 @@
+-- Every update of a snapshot tag is kept as a new version, the `voters` and
+-- `contributions` tables keep holding the latest version only.
+-- The history tables do not reference `snapshots` so that replacing a snapshot
+-- does not cascade to them.
+create table snapshot_versions (
+    tag TEXT NOT NULL,
+    version INTEGER NOT NULL,
+    last_updated BIGINT NOT NULL,
+    PRIMARY KEY(tag, version)
+);
+
+create table voters_history (
+    voting_key TEXT NOT NULL,
+    voting_power BIGINT NOT NULL,
+    voting_group TEXT NOT NULL,
+    snapshot_tag TEXT NOT NULL,
+    version INTEGER NOT NULL,
+    PRIMARY KEY(voting_key, voting_group, snapshot_tag, version)
+);
+
+create table contributions_history (
+    stake_public_key TEXT NOT NULL,
+    reward_address TEXT NOT NULL,
+    value BIGINT NOT NULL,
+    voting_key TEXT NOT NULL,
+    voting_group TEXT NOT NULL,
+    snapshot_tag TEXT NOT NULL,
+    version INTEGER NOT NULL,
+    PRIMARY KEY(stake_public_key, voting_key, voting_group, snapshot_tag, version)
+);
+
+-- the snapshots already stored become the first version of their tag
+INSERT INTO snapshot_versions (tag, version, last_updated)
+SELECT tag, 1, last_updated FROM snapshots;
+
+INSERT INTO voters_history (voting_key, voting_power, voting_group, snapshot_tag, version)
+SELECT voting_key, voting_power, voting_group, snapshot_tag, 1 FROM voters;
+
+INSERT INTO contributions_history (stake_public_key, reward_address, value, voting_key, voting_group, snapshot_tag, version)
+SELECT stake_public_key, reward_address, value, voting_key, voting_group, snapshot_tag, 1 FROM contributions;
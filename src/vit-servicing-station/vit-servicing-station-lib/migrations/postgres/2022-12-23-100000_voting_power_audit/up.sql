@@ -0,0 +1,6 @@
+-- How the voting power of the voters was derived from their stake, only known
+-- for the snapshots computed from a raw snapshot
+ALTER TABLE voters ADD raw_stake BIGINT;
+ALTER TABLE voters ADD transformed_power BIGINT;
+ALTER TABLE voters_history ADD raw_stake BIGINT;
+ALTER TABLE voters_history ADD transformed_power BIGINT;
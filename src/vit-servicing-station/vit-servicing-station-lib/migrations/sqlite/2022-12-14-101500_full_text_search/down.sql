@@ -0,0 +1,14 @@
+DROP TRIGGER community_advisors_reviews_fts_update;
+DROP TRIGGER community_advisors_reviews_fts_delete;
+DROP TRIGGER community_advisors_reviews_fts_insert;
+DROP TABLE community_advisors_reviews_fts;
+
+DROP TRIGGER challenges_fts_update;
+DROP TRIGGER challenges_fts_delete;
+DROP TRIGGER challenges_fts_insert;
+DROP TABLE challenges_fts;
+
+DROP TRIGGER proposals_fts_update;
+DROP TRIGGER proposals_fts_delete;
+DROP TRIGGER proposals_fts_insert;
+DROP TABLE proposals_fts;
@@ -0,0 +1,80 @@
+-- Full-text indexes, kept in sync with their content tables by triggers
+CREATE VIRTUAL TABLE proposals_fts USING fts5(
+    proposal_title,
+    proposal_summary,
+    proposer_name,
+    proposer_relevant_experience,
+    content='proposals',
+    content_rowid='id'
+);
+
+CREATE TRIGGER proposals_fts_insert AFTER INSERT ON proposals BEGIN
+    INSERT INTO proposals_fts(rowid, proposal_title, proposal_summary, proposer_name, proposer_relevant_experience)
+    VALUES (new.id, new.proposal_title, new.proposal_summary, new.proposer_name, new.proposer_relevant_experience);
+END;
+
+CREATE TRIGGER proposals_fts_delete AFTER DELETE ON proposals BEGIN
+    INSERT INTO proposals_fts(proposals_fts, rowid, proposal_title, proposal_summary, proposer_name, proposer_relevant_experience)
+    VALUES ('delete', old.id, old.proposal_title, old.proposal_summary, old.proposer_name, old.proposer_relevant_experience);
+END;
+
+CREATE TRIGGER proposals_fts_update AFTER UPDATE ON proposals BEGIN
+    INSERT INTO proposals_fts(proposals_fts, rowid, proposal_title, proposal_summary, proposer_name, proposer_relevant_experience)
+    VALUES ('delete', old.id, old.proposal_title, old.proposal_summary, old.proposer_name, old.proposer_relevant_experience);
+    INSERT INTO proposals_fts(rowid, proposal_title, proposal_summary, proposer_name, proposer_relevant_experience)
+    VALUES (new.id, new.proposal_title, new.proposal_summary, new.proposer_name, new.proposer_relevant_experience);
+END;
+
+CREATE VIRTUAL TABLE challenges_fts USING fts5(
+    title,
+    description,
+    content='challenges',
+    content_rowid='internal_id'
+);
+
+CREATE TRIGGER challenges_fts_insert AFTER INSERT ON challenges BEGIN
+    INSERT INTO challenges_fts(rowid, title, description)
+    VALUES (new.internal_id, new.title, new.description);
+END;
+
+CREATE TRIGGER challenges_fts_delete AFTER DELETE ON challenges BEGIN
+    INSERT INTO challenges_fts(challenges_fts, rowid, title, description)
+    VALUES ('delete', old.internal_id, old.title, old.description);
+END;
+
+CREATE TRIGGER challenges_fts_update AFTER UPDATE ON challenges BEGIN
+    INSERT INTO challenges_fts(challenges_fts, rowid, title, description)
+    VALUES ('delete', old.internal_id, old.title, old.description);
+    INSERT INTO challenges_fts(rowid, title, description)
+    VALUES (new.internal_id, new.title, new.description);
+END;
+
+CREATE VIRTUAL TABLE community_advisors_reviews_fts USING fts5(
+    impact_alignment_note,
+    feasibility_note,
+    auditability_note,
+    content='community_advisors_reviews',
+    content_rowid='id'
+);
+
+CREATE TRIGGER community_advisors_reviews_fts_insert AFTER INSERT ON community_advisors_reviews BEGIN
+    INSERT INTO community_advisors_reviews_fts(rowid, impact_alignment_note, feasibility_note, auditability_note)
+    VALUES (new.id, new.impact_alignment_note, new.feasibility_note, new.auditability_note);
+END;
+
+CREATE TRIGGER community_advisors_reviews_fts_delete AFTER DELETE ON community_advisors_reviews BEGIN
+    INSERT INTO community_advisors_reviews_fts(community_advisors_reviews_fts, rowid, impact_alignment_note, feasibility_note, auditability_note)
+    VALUES ('delete', old.id, old.impact_alignment_note, old.feasibility_note, old.auditability_note);
+END;
+
+CREATE TRIGGER community_advisors_reviews_fts_update AFTER UPDATE ON community_advisors_reviews BEGIN
+    INSERT INTO community_advisors_reviews_fts(community_advisors_reviews_fts, rowid, impact_alignment_note, feasibility_note, auditability_note)
+    VALUES ('delete', old.id, old.impact_alignment_note, old.feasibility_note, old.auditability_note);
+    INSERT INTO community_advisors_reviews_fts(rowid, impact_alignment_note, feasibility_note, auditability_note)
+    VALUES (new.id, new.impact_alignment_note, new.feasibility_note, new.auditability_note);
+END;
+
+-- Index the rows inserted before this migration
+INSERT INTO proposals_fts(proposals_fts) VALUES ('rebuild');
+INSERT INTO challenges_fts(challenges_fts) VALUES ('rebuild');
+INSERT INTO community_advisors_reviews_fts(community_advisors_reviews_fts) VALUES ('rebuild');
@@ -0,0 +1,14 @@
+-- Tokens created before scopes existed keep full access
+ALTER TABLE api_tokens ADD scope VARCHAR NOT NULL DEFAULT 'admin';
+ALTER TABLE api_tokens ADD revoked_time BIGINT;
+
+create table api_token_audit_log
+(
+    id INTEGER NOT NULL
+        primary key autoincrement,
+    token BLOB NOT NULL,
+    time BIGINT NOT NULL,
+    method VARCHAR NOT NULL,
+    path VARCHAR NOT NULL,
+    status INTEGER NOT NULL
+);
@@ -0,0 +1,3 @@
+DROP TABLE contributions_history;
+DROP TABLE voters_history;
+DROP TABLE snapshot_versions;
@@ -0,0 +1,4 @@
+ALTER TABLE voters_history DROP transformed_power;
+ALTER TABLE voters_history DROP raw_stake;
+ALTER TABLE voters DROP transformed_power;
+ALTER TABLE voters DROP raw_stake;
@@ -21,6 +21,22 @@ pub enum DbConnectionPool {
     Postgres(Pool<ConnectionManager<PgConnection>>),
 }
 
+impl DbConnection {
+    /// Run `f` in a transaction: the queries made with this connection within `f`
+    /// are committed together, or not at all if `f` fails. The transactions they
+    /// start become savepoints of this one.
+    pub fn transaction<T, E, F>(&self, f: F) -> Result<T, E>
+    where
+        F: FnOnce() -> Result<T, E>,
+        E: From<diesel::result::Error>,
+    {
+        match self {
+            DbConnection::Sqlite(conn) => conn.transaction(f),
+            DbConnection::Postgres(conn) => conn.transaction(f),
+        }
+    }
+}
+
 impl DbConnectionPool {
     pub fn get(&self) -> Result<DbConnection, r2d2::Error> {
         match self {
@@ -1,21 +1,77 @@
-use crate::db::schema::api_tokens;
+use crate::db::schema::{api_token_audit_log, api_tokens};
 use crate::v0::api_token::ApiToken;
 use diesel::backend::Backend;
-use diesel::sql_types::{BigInt, Binary};
+use diesel::sql_types::{BigInt, Binary, Integer, Text};
 use diesel::types::FromSql;
 use diesel::{ExpressionMethods, Insertable, Queryable};
+use serde::{Deserialize, Serialize};
+
+/// What a token gives access to. Every scope includes the ones before it.
+#[derive(Serialize, Deserialize, Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord)]
+#[serde(rename_all = "kebab-case")]
+pub enum ApiTokenScope {
+    /// read endpoints only
+    ReadOnly,
+    /// snapshot updates
+    SnapshotWrite,
+    /// every endpoint, including the fund administration
+    Admin,
+}
+
+impl ApiTokenScope {
+    pub fn allows(&self, required: ApiTokenScope) -> bool {
+        *self >= required
+    }
+}
+
+impl std::str::FromStr for ApiTokenScope {
+    type Err = std::io::Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "read-only" => Ok(ApiTokenScope::ReadOnly),
+            "snapshot-write" => Ok(ApiTokenScope::SnapshotWrite),
+            "admin" => Ok(ApiTokenScope::Admin),
+            s => Err(std::io::Error::new(
+                std::io::ErrorKind::InvalidData,
+                format!(
+                    "Expected any of [read-only | snapshot-write | admin], found: {}",
+                    s
+                ),
+            )),
+        }
+    }
+}
+
+impl std::fmt::Display for ApiTokenScope {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        // should be implemented and safe to unwrap here
+        let repr = serde_json::to_string(&self).unwrap();
+        write!(f, "{}", repr.trim_matches('"'))
+    }
+}
 
 #[derive(Debug, Clone)]
 pub struct ApiTokenData {
     pub token: ApiToken,
     pub creation_time: i64,
     pub expire_time: i64,
+    pub scope: ApiTokenScope,
+    pub revoked_time: Option<i64>,
+}
+
+impl ApiTokenData {
+    /// Whether the token can still be used at the given unix time
+    pub fn is_active(&self, now: i64) -> bool {
+        self.revoked_time.is_none() && now < self.expire_time
+    }
 }
 
 impl<DB: Backend> Queryable<api_tokens::SqlType, DB> for ApiTokenData
 where
     i64: FromSql<BigInt, DB>,
     Vec<u8>: FromSql<Binary, DB>,
+    String: FromSql<Text, DB>,
 {
     type Row = (
         // 0 -> token
@@ -24,6 +80,10 @@ where
         i64,
         // 2-> expire_time
         i64,
+        // 3 -> scope
+        String,
+        // 4 -> revoked_time
+        Option<i64>,
     );
 
     fn build(row: Self::Row) -> Self {
@@ -31,6 +91,9 @@ where
             token: ApiToken::new(row.0),
             creation_time: row.1,
             expire_time: row.2,
+            // an unknown scope only gets the least privileges
+            scope: row.3.parse().unwrap_or(ApiTokenScope::ReadOnly),
+            revoked_time: row.4,
         }
     }
 }
@@ -43,6 +106,8 @@ impl Insertable<api_tokens::table> for ApiTokenData {
         diesel::dsl::Eq<api_tokens::token, Vec<u8>>,
         diesel::dsl::Eq<api_tokens::creation_time, i64>,
         diesel::dsl::Eq<api_tokens::expire_time, i64>,
+        diesel::dsl::Eq<api_tokens::scope, String>,
+        diesel::dsl::Eq<api_tokens::revoked_time, Option<i64>>,
     );
 
     fn values(self) -> Self::Values {
@@ -50,6 +115,54 @@ impl Insertable<api_tokens::table> for ApiTokenData {
             api_tokens::token.eq(self.token.as_ref().to_vec()),
             api_tokens::creation_time.eq(self.creation_time),
             api_tokens::expire_time.eq(self.expire_time),
+            api_tokens::scope.eq(self.scope.to_string()),
+            api_tokens::revoked_time.eq(self.revoked_time),
         )
     }
 }
+
+/// A write performed with an API token
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct AuditLogEntry {
+    pub id: i32,
+    pub token: ApiToken,
+    pub time: i64,
+    pub method: String,
+    pub path: String,
+    /// HTTP status of the response
+    pub status: i32,
+}
+
+impl<DB: Backend> Queryable<api_token_audit_log::SqlType, DB> for AuditLogEntry
+where
+    i32: FromSql<Integer, DB>,
+    i64: FromSql<BigInt, DB>,
+    Vec<u8>: FromSql<Binary, DB>,
+    String: FromSql<Text, DB>,
+{
+    type Row = (
+        // 0 -> id
+        i32,
+        // 1 -> token
+        Vec<u8>,
+        // 2 -> time
+        i64,
+        // 3 -> method
+        String,
+        // 4 -> path
+        String,
+        // 5 -> status
+        i32,
+    );
+
+    fn build(row: Self::Row) -> Self {
+        Self {
+            id: row.0,
+            token: ApiToken::new(row.1),
+            time: row.2,
+            method: row.3,
+            path: row.4,
+            status: row.5,
+        }
+    }
+}
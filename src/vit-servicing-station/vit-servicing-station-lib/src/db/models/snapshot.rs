@@ -1,6 +1,8 @@
 #![allow(clippy::extra_unused_lifetimes)]
 
-use crate::db::schema::{contributions, snapshots, voters};
+use crate::db::schema::{
+    contributions, contributions_history, snapshot_versions, snapshots, voters, voters_history,
+};
 use diesel::{Insertable, Queryable};
 use serde::{Deserialize, Serialize};
 
@@ -24,6 +26,13 @@ pub struct Voter {
     pub voting_power: i64,
     pub voting_group: String,
     pub snapshot_tag: String,
+    /// Stake delegated to the voting key, when the snapshot was computed from a raw snapshot
+    #[serde(default)]
+    pub raw_stake: Option<i64>,
+    /// Voting power after the voting power transform but before the influence cap, when the
+    /// snapshot was computed from a raw snapshot
+    #[serde(default)]
+    pub transformed_power: Option<i64>,
 }
 
 #[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize, Queryable, Insertable)]
@@ -37,3 +46,67 @@ pub struct Contribution {
     pub voting_group: String,
     pub snapshot_tag: String,
 }
+
+/// A past or current version of a snapshot, every update of a tag creates a new one
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize, Queryable, Insertable)]
+#[table_name = "snapshot_versions"]
+pub struct SnapshotVersion {
+    pub tag: String,
+    /// Starts at 1 and is incremented on every update of the tag
+    pub version: i32,
+    #[serde(deserialize_with = "crate::utils::serde::deserialize_unix_timestamp_from_rfc3339")]
+    #[serde(serialize_with = "crate::utils::serde::serialize_unix_timestamp_as_rfc3339")]
+    pub last_updated: i64,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq, Insertable)]
+#[table_name = "voters_history"]
+pub struct VersionedVoter {
+    pub voting_key: String,
+    pub voting_power: i64,
+    pub voting_group: String,
+    pub snapshot_tag: String,
+    pub version: i32,
+    pub raw_stake: Option<i64>,
+    pub transformed_power: Option<i64>,
+}
+
+impl VersionedVoter {
+    pub fn new(voter: &Voter, version: i32) -> Self {
+        Self {
+            voting_key: voter.voting_key.clone(),
+            voting_power: voter.voting_power,
+            voting_group: voter.voting_group.clone(),
+            snapshot_tag: voter.snapshot_tag.clone(),
+            version,
+            raw_stake: voter.raw_stake,
+            transformed_power: voter.transformed_power,
+        }
+    }
+}
+
+#[derive(Clone, Debug, PartialEq, Eq, Insertable)]
+#[table_name = "contributions_history"]
+pub struct VersionedContribution {
+    pub stake_public_key: String,
+    pub reward_address: String,
+    pub value: i64,
+    pub voting_key: String,
+    pub voting_group: String,
+    pub snapshot_tag: String,
+    pub version: i32,
+}
+
+impl VersionedContribution {
+    pub fn new(contribution: &Contribution, version: i32) -> Self {
+        Self {
+            stake_public_key: contribution.stake_public_key.clone(),
+            reward_address: contribution.reward_address.clone(),
+            value: contribution.value,
+            voting_key: contribution.voting_key.clone(),
+            voting_group: contribution.voting_group.clone(),
+            snapshot_tag: contribution.snapshot_tag.clone(),
+            version,
+        }
+    }
+}
@@ -1,7 +1,7 @@
-use crate::db::models::api_tokens::ApiTokenData;
+use crate::db::models::api_tokens::{ApiTokenData, ApiTokenScope, AuditLogEntry};
 use crate::db::{
     models::api_tokens as api_token_model,
-    schema::{api_tokens, api_tokens::dsl::api_tokens as api_tokens_dsl},
+    schema::{api_token_audit_log, api_tokens, api_tokens::dsl::api_tokens as api_tokens_dsl},
     DbConnection, DbConnectionPool,
 };
 use crate::v0::api_token::ApiToken;
@@ -26,13 +26,19 @@ pub async fn query_token(
 
 /// Insert a token asynchronously. This method is a wrapper over `insert_data_token` that uses the same
 /// approach synchronously for a complete formed APITokenData object related to the database model.
-pub async fn insert_token(token: &ApiToken, pool: &DbConnectionPool) -> Result<(), HandleError> {
+pub async fn insert_token(
+    token: &ApiToken,
+    scope: ApiTokenScope,
+    pool: &DbConnectionPool,
+) -> Result<(), HandleError> {
     let db_conn = pool.get().map_err(HandleError::DatabaseError)?;
 
     let api_token_data = ApiTokenData {
         token: token.clone(),
         creation_time: OffsetDateTime::now_utc().unix_timestamp(),
         expire_time: (OffsetDateTime::now_utc() + Duration::days(365)).unix_timestamp(),
+        scope,
+        revoked_time: None,
     };
 
     tokio::task::spawn_blocking(move || {
@@ -44,6 +50,34 @@ pub async fn insert_token(token: &ApiToken, pool: &DbConnectionPool) -> Result<(
     .map_err(|_| HandleError::InternalError("Error executing request".to_string()))?
 }
 
+/// Record a write performed with the given token
+pub async fn insert_audit_log_entry(
+    token: ApiToken,
+    method: String,
+    path: String,
+    status: u16,
+    pool: &DbConnectionPool,
+) -> Result<(), HandleError> {
+    let db_conn = pool.get().map_err(HandleError::DatabaseError)?;
+    tokio::task::spawn_blocking(move || {
+        let values = (
+            api_token_audit_log::token.eq(token.as_ref().to_vec()),
+            api_token_audit_log::time.eq(OffsetDateTime::now_utc().unix_timestamp()),
+            api_token_audit_log::method.eq(method),
+            api_token_audit_log::path.eq(path),
+            api_token_audit_log::status.eq(i32::from(status)),
+        );
+        execute_q!(
+            db_conn,
+            diesel::insert_into(api_token_audit_log::table).values(values)
+        )
+        .map(|_| ())
+        .map_err(|e| HandleError::InternalError(e.to_string()))
+    })
+    .await
+    .map_err(|_| HandleError::InternalError("Error executing request".to_string()))?
+}
+
 pub fn query_token_data_by_token(
     raw_token: &[u8],
     db_conn: &DbConnection,
@@ -57,6 +91,48 @@ pub fn query_token_data_by_token(
     )
 }
 
+pub fn query_all_token_data(
+    db_conn: &DbConnection,
+) -> Result<Vec<api_token_model::ApiTokenData>, diesel::result::Error> {
+    q!(
+        db_conn,
+        api_tokens_dsl
+            .order_by(api_tokens::creation_time)
+            .load::<api_token_model::ApiTokenData>(db_conn)
+    )
+}
+
+/// Mark the token as revoked, returns the number of tokens revoked
+pub fn revoke_token_data(raw_token: &[u8], db_conn: &DbConnection) -> QueryResult<usize> {
+    execute_q!(
+        db_conn,
+        diesel::update(
+            api_tokens_dsl
+                .filter(api_tokens::token.eq(raw_token))
+                .filter(api_tokens::revoked_time.is_null())
+        )
+        .set(api_tokens::revoked_time.eq(Some(OffsetDateTime::now_utc().unix_timestamp())))
+    )
+}
+
+/// Writes performed with the given token, or with any token, the most recent first
+pub fn query_audit_log(
+    raw_token: Option<&[u8]>,
+    limit: i64,
+    db_conn: &DbConnection,
+) -> Result<Vec<AuditLogEntry>, diesel::result::Error> {
+    q!(db_conn, {
+        let mut query = api_token_audit_log::table
+            .order_by(api_token_audit_log::id.desc())
+            .limit(limit)
+            .into_boxed();
+        if let Some(raw_token) = raw_token {
+            query = query.filter(api_token_audit_log::token.eq(raw_token));
+        }
+        query.load::<AuditLogEntry>(db_conn)
+    })
+}
+
 pub fn insert_token_data(token_data: ApiTokenData, db_conn: &DbConnection) -> QueryResult<usize> {
     q!(
         db_conn,
@@ -97,8 +173,47 @@ mod test {
 
         // checks
         let token = ApiToken::new(b"foo_bar_zen".to_vec());
-        insert_token(&token, &pool).await.unwrap();
+        insert_token(&token, ApiTokenScope::SnapshotWrite, &pool)
+            .await
+            .unwrap();
         let token_data: ApiTokenData = query_token(token.clone(), &pool).await.unwrap().unwrap();
         assert_eq!(token_data.token, token);
+        assert_eq!(token_data.scope, ApiTokenScope::SnapshotWrite);
+        assert!(token_data.revoked_time.is_none());
+    }
+
+    #[tokio::test]
+    async fn api_token_revoke_and_audit() {
+        let pool: DbConnectionPool = load_db_connection_pool("").unwrap();
+        db_testing::initialize_db_with_migration(&pool.get().unwrap()).unwrap();
+
+        let token = ApiToken::new(b"foo_bar_zen".to_vec());
+        insert_token(&token, ApiTokenScope::Admin, &pool)
+            .await
+            .unwrap();
+        insert_audit_log_entry(
+            token.clone(),
+            "PUT".to_string(),
+            "/api/v0/admin/fund".to_string(),
+            200,
+            &pool,
+        )
+        .await
+        .unwrap();
+
+        let db_conn = pool.get().unwrap();
+        assert_eq!(revoke_token_data(token.as_ref(), &db_conn).unwrap(), 1);
+        // already revoked
+        assert_eq!(revoke_token_data(token.as_ref(), &db_conn).unwrap(), 0);
+        let token_data = query_token_data_by_token(token.as_ref(), &db_conn)
+            .unwrap()
+            .unwrap();
+        assert!(!token_data.is_active(OffsetDateTime::now_utc().unix_timestamp()));
+
+        let log = query_audit_log(Some(token.as_ref()), 10, &db_conn).unwrap();
+        assert_eq!(log.len(), 1);
+        assert_eq!(log[0].token, token);
+        assert_eq!(log[0].path, "/api/v0/admin/fund");
+        assert_eq!(log[0].status, 200);
     }
 }
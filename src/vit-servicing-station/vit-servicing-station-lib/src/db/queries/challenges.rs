@@ -100,6 +100,59 @@ pub async fn query_challenge_proposals_by_id_and_group_id(
     .map_err(|_e| HandleError::InternalError("Error executing request".to_string()))?
 }
 
+pub async fn query_challenge_proposals_count(
+    id: i32,
+    voter_group_id: Option<String>,
+    pool: &DbConnectionPool,
+) -> Result<i64, HandleError> {
+    let db_conn = pool.get().map_err(HandleError::DatabaseError)?;
+    tokio::task::spawn_blocking(move || {
+        q!(db_conn, {
+            let mut query = proposals_dsl::full_proposals_info
+                .filter(proposals_dsl::challenge_id.eq(id))
+                .into_boxed();
+            if let Some(voter_group_id) = voter_group_id {
+                query = query.filter(proposals_dsl::group_id.eq(voter_group_id));
+            }
+            query.count().get_result::<i64>(&db_conn)
+        })
+        .map_err(|_e| HandleError::InternalError("Error counting proposals".to_string()))
+    })
+    .await
+    .map_err(|_e| HandleError::InternalError("Error executing request".to_string()))?
+}
+
+/// `limit` proposals of the challenge, of the voter group if one is given, skipping the first
+/// `offset` ones
+pub async fn query_challenge_proposals_page(
+    id: i32,
+    voter_group_id: Option<String>,
+    offset: i64,
+    limit: i64,
+    pool: &DbConnectionPool,
+) -> Result<Vec<Proposal>, HandleError> {
+    let db_conn = pool.get().map_err(HandleError::DatabaseError)?;
+    tokio::task::spawn_blocking(move || {
+        q!(db_conn, {
+            let mut query = proposals_dsl::full_proposals_info
+                .filter(proposals_dsl::challenge_id.eq(id))
+                .into_boxed();
+            if let Some(voter_group_id) = voter_group_id {
+                query = query.filter(proposals_dsl::group_id.eq(voter_group_id));
+            }
+            query
+                // a proposal has a row for each of its voter groups
+                .order_by((proposals_dsl::id, proposals_dsl::group_id))
+                .offset(offset)
+                .limit(limit)
+                .load::<Proposal>(&db_conn)
+        })
+        .map_err(|_e| HandleError::NotFound("Error loading challenge".to_string()))
+    })
+    .await
+    .map_err(|_e| HandleError::InternalError("Error executing request".to_string()))?
+}
+
 pub fn batch_insert_challenges(
     challenges: &[<Challenge as Insertable<challenges::table>>::Values],
     db_conn: &DbConnection,
@@ -29,6 +29,51 @@ pub async fn query_reviews_by_fund_id(
     .map_err(|_e| HandleError::InternalError("Error executing request".to_string()))?
 }
 
+pub async fn query_proposal_reviews_count(
+    proposal_id: i32,
+    pool: &DbConnectionPool,
+) -> Result<i64, HandleError> {
+    let db_conn = pool.get().map_err(HandleError::DatabaseError)?;
+    tokio::task::spawn_blocking(move || {
+        q!(
+            db_conn,
+            reviews_dsl::community_advisors_reviews
+                .filter(reviews_dsl::proposal_id.eq(proposal_id))
+                .count()
+                .get_result::<i64>(&db_conn)
+        )
+        .map_err(|_e| {
+            HandleError::InternalError("Error counting community advisors reviews".to_string())
+        })
+    })
+    .await
+    .map_err(|_e| HandleError::InternalError("Error executing request".to_string()))?
+}
+
+/// `limit` reviews of the proposal, skipping the first `offset` ones
+pub async fn query_proposal_reviews_page(
+    proposal_id: i32,
+    offset: i64,
+    limit: i64,
+    pool: &DbConnectionPool,
+) -> Result<Vec<AdvisorReview>, HandleError> {
+    let db_conn = pool.get().map_err(HandleError::DatabaseError)?;
+    tokio::task::spawn_blocking(move || {
+        q!(
+            db_conn,
+            reviews_dsl::community_advisors_reviews
+                .filter(reviews_dsl::proposal_id.eq(proposal_id))
+                .order_by(reviews_dsl::id)
+                .offset(offset)
+                .limit(limit)
+                .load::<AdvisorReview>(&db_conn)
+        )
+        .map_err(|_e| HandleError::NotFound("Error loading community advisors reviews".to_string()))
+    })
+    .await
+    .map_err(|_e| HandleError::InternalError("Error executing request".to_string()))?
+}
+
 pub fn batch_insert_advisor_reviews(
     reviews: &[AdvisorReview],
     db_conn: &DbConnection,
@@ -149,6 +149,45 @@ pub async fn query_all_funds(pool: &DbConnectionPool) -> Result<Vec<i32>, Handle
     .map_err(|_e| HandleError::InternalError("Error executing request".to_string()))?
 }
 
+pub async fn query_funds_count(pool: &DbConnectionPool) -> Result<i64, HandleError> {
+    let db_conn = pool.get().map_err(HandleError::DatabaseError)?;
+    tokio::task::spawn_blocking(move || {
+        q!(db_conn, fund_dsl::funds.count().get_result::<i64>(&db_conn))
+            .map_err(|_| HandleError::InternalError("Error counting funds".to_string()))
+    })
+    .await
+    .map_err(|_e| HandleError::InternalError("Error executing request".to_string()))?
+}
+
+/// `limit` funds in creation order, skipping the first `offset` ones
+pub async fn query_funds_page(
+    offset: i64,
+    limit: i64,
+    pool: &DbConnectionPool,
+) -> Result<Vec<Fund>, HandleError> {
+    let db_conn = pool.get().map_err(HandleError::DatabaseError)?;
+    tokio::task::spawn_blocking(move || {
+        let db_conn = &db_conn;
+
+        let funds: Vec<Fund> = q!(
+            db_conn,
+            fund_dsl::funds
+                .order(fund_dsl::id)
+                .offset(offset)
+                .limit(limit)
+                .load(db_conn)
+        )
+        .map_err(|_| HandleError::InternalError("Error retrieving funds".to_string()))?;
+
+        funds
+            .into_iter()
+            .map(|fund| join_fund(fund, db_conn))
+            .collect()
+    })
+    .await
+    .map_err(|_e| HandleError::InternalError("Error executing request".to_string()))?
+}
+
 pub fn insert_fund(fund: Fund, db_conn: &DbConnection) -> QueryResult<Fund> {
     q!(
         db_conn,
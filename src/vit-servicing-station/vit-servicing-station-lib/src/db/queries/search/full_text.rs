@@ -0,0 +1,449 @@
+use super::{map_limit, map_offset};
+use crate::{
+    db::{DbConnection, DbConnectionPool},
+    q,
+    v0::{
+        endpoints::search::requests::{
+            FacetCount, FullTextSearchQuery, FullTextSearchResponse, FullTextTable, SearchFacets,
+            SearchHit,
+        },
+        errors::HandleError,
+    },
+};
+use diesel::{
+    pg::Pg,
+    sql_query,
+    sql_types::{BigInt, Double, Integer, Nullable, Text},
+    sqlite::Sqlite,
+    QueryResult, QueryableByName, RunQueryDsl,
+};
+
+pub async fn full_text_search_db(
+    query: FullTextSearchQuery,
+    pool: &DbConnectionPool,
+) -> Result<FullTextSearchResponse, HandleError> {
+    let db_conn = pool.get().map_err(HandleError::DatabaseError)?;
+    tokio::task::spawn_blocking(move || full_text_search(query, &db_conn))
+        .await
+        .map_err(|_e| HandleError::InternalError("Error executing request".to_string()))?
+}
+
+// ⚠ WARNING ⚠ : The queries below are backend specific, the sqlite ones rely on the
+// `*_fts` virtual tables and the postgres ones on the `search_document` columns.
+// Every query takes the same parameters: the search, then the fund, challenge and
+// category filters. The snippet is only computed for the page of hits returned.
+
+fn sqlite_proposals_query(snippet: bool) -> String {
+    let snippet = if snippet {
+        "snippet(proposals_fts, -1, '<b>', '</b>', '...', 24)"
+    } else {
+        "''"
+    };
+    format!(
+        "
+SELECT
+    p.id AS id,
+    p.proposal_id AS proposal_id,
+    p.proposal_title AS title,
+    {snippet} AS snippet,
+    -bm25(proposals_fts, 4.0, 2.0, 1.0, 1.0) AS rank,
+    p.challenge_id AS challenge_id,
+    ch.fund_id AS fund_id,
+    p.proposal_category AS category
+FROM proposals_fts
+INNER JOIN proposals p ON p.id = proposals_fts.rowid
+LEFT JOIN challenges ch ON ch.id = p.challenge_id
+WHERE proposals_fts MATCH ?1
+    AND (?2 IS NULL OR ch.fund_id = ?2)
+    AND (?3 IS NULL OR p.challenge_id = ?3)
+    AND (?4 IS NULL OR p.proposal_category = ?4)
+"
+    )
+}
+
+fn sqlite_challenges_query(snippet: bool) -> String {
+    let snippet = if snippet {
+        "snippet(challenges_fts, -1, '<b>', '</b>', '...', 24)"
+    } else {
+        "''"
+    };
+    format!(
+        "
+SELECT
+    ch.id AS id,
+    NULL AS proposal_id,
+    ch.title AS title,
+    {snippet} AS snippet,
+    -bm25(challenges_fts, 2.0, 1.0) AS rank,
+    ch.id AS challenge_id,
+    ch.fund_id AS fund_id,
+    NULL AS category
+FROM challenges_fts
+INNER JOIN challenges ch ON ch.internal_id = challenges_fts.rowid
+WHERE challenges_fts MATCH ?1
+    AND (?2 IS NULL OR ch.fund_id = ?2)
+    AND (?3 IS NULL OR ch.id = ?3)
+    AND ?4 IS NULL
+"
+    )
+}
+
+fn sqlite_reviews_query(snippet: bool) -> String {
+    let snippet = if snippet {
+        "snippet(community_advisors_reviews_fts, -1, '<b>', '</b>', '...', 24)"
+    } else {
+        "''"
+    };
+    format!(
+        "
+SELECT
+    r.id AS id,
+    p.proposal_id AS proposal_id,
+    p.proposal_title AS title,
+    {snippet} AS snippet,
+    -bm25(community_advisors_reviews_fts) AS rank,
+    p.challenge_id AS challenge_id,
+    ch.fund_id AS fund_id,
+    p.proposal_category AS category
+FROM community_advisors_reviews_fts
+INNER JOIN community_advisors_reviews r ON r.id = community_advisors_reviews_fts.rowid
+INNER JOIN proposals p ON p.proposal_id = CAST(r.proposal_id AS TEXT)
+LEFT JOIN challenges ch ON ch.id = p.challenge_id
+WHERE community_advisors_reviews_fts MATCH ?1
+    AND (?2 IS NULL OR ch.fund_id = ?2)
+    AND (?3 IS NULL OR p.challenge_id = ?3)
+    AND (?4 IS NULL OR p.proposal_category = ?4)
+"
+    )
+}
+
+/// `ts_headline` of the document, an empty snippet if `snippet` is false
+fn postgres_headline(document: &str, snippet: bool) -> String {
+    if snippet {
+        format!(
+            "ts_headline('english', {document}, q, \
+             'StartSel=<b>, StopSel=</b>, MaxWords=35, MinWords=15, MaxFragments=2')"
+        )
+    } else {
+        "''".to_string()
+    }
+}
+
+fn postgres_proposals_query(snippet: bool) -> String {
+    let snippet = postgres_headline("p.proposal_title || ' ' || p.proposal_summary", snippet);
+    format!(
+        "
+SELECT
+    p.id AS id,
+    p.proposal_id AS proposal_id,
+    p.proposal_title AS title,
+    {snippet} AS snippet,
+    ts_rank(p.search_document, q)::DOUBLE PRECISION AS rank,
+    p.challenge_id AS challenge_id,
+    ch.fund_id AS fund_id,
+    p.proposal_category AS category
+FROM proposals p
+CROSS JOIN websearch_to_tsquery('english', $1) q
+LEFT JOIN challenges ch ON ch.id = p.challenge_id
+WHERE p.search_document @@ q
+    AND ($2::INTEGER IS NULL OR ch.fund_id = $2)
+    AND ($3::INTEGER IS NULL OR p.challenge_id = $3)
+    AND ($4::VARCHAR IS NULL OR p.proposal_category = $4)
+"
+    )
+}
+
+fn postgres_challenges_query(snippet: bool) -> String {
+    let snippet = postgres_headline("ch.title || ' ' || ch.description", snippet);
+    format!(
+        "
+SELECT
+    ch.id AS id,
+    NULL::VARCHAR AS proposal_id,
+    ch.title AS title,
+    {snippet} AS snippet,
+    ts_rank(ch.search_document, q)::DOUBLE PRECISION AS rank,
+    ch.id AS challenge_id,
+    ch.fund_id AS fund_id,
+    NULL::VARCHAR AS category
+FROM challenges ch
+CROSS JOIN websearch_to_tsquery('english', $1) q
+WHERE ch.search_document @@ q
+    AND ($2::INTEGER IS NULL OR ch.fund_id = $2)
+    AND ($3::INTEGER IS NULL OR ch.id = $3)
+    AND $4::VARCHAR IS NULL
+"
+    )
+}
+
+fn postgres_reviews_query(snippet: bool) -> String {
+    let snippet = postgres_headline(
+        "r.impact_alignment_note || ' ' || r.feasibility_note || ' ' || r.auditability_note",
+        snippet,
+    );
+    format!(
+        "
+SELECT
+    r.id AS id,
+    p.proposal_id AS proposal_id,
+    p.proposal_title AS title,
+    {snippet} AS snippet,
+    ts_rank(r.search_document, q)::DOUBLE PRECISION AS rank,
+    p.challenge_id AS challenge_id,
+    ch.fund_id AS fund_id,
+    p.proposal_category AS category
+FROM community_advisors_reviews r
+CROSS JOIN websearch_to_tsquery('english', $1) q
+INNER JOIN proposals p ON p.proposal_id = r.proposal_id::VARCHAR
+LEFT JOIN challenges ch ON ch.id = p.challenge_id
+WHERE r.search_document @@ q
+    AND ($2::INTEGER IS NULL OR ch.fund_id = $2)
+    AND ($3::INTEGER IS NULL OR p.challenge_id = $3)
+    AND ($4::VARCHAR IS NULL OR p.proposal_category = $4)
+"
+    )
+}
+
+/// The hits of all the searched tables, with the table they come from in the
+/// `source` column
+fn hits_query(tables: &[FullTextTable], conn: &DbConnection, snippet: bool) -> String {
+    tables
+        .iter()
+        .map(|table| {
+            let query = match (conn, table) {
+                (DbConnection::Sqlite(_), FullTextTable::Proposals) => {
+                    sqlite_proposals_query(snippet)
+                }
+                (DbConnection::Sqlite(_), FullTextTable::Challenges) => {
+                    sqlite_challenges_query(snippet)
+                }
+                (DbConnection::Sqlite(_), FullTextTable::Reviews) => sqlite_reviews_query(snippet),
+                (DbConnection::Postgres(_), FullTextTable::Proposals) => {
+                    postgres_proposals_query(snippet)
+                }
+                (DbConnection::Postgres(_), FullTextTable::Challenges) => {
+                    postgres_challenges_query(snippet)
+                }
+                (DbConnection::Postgres(_), FullTextTable::Reviews) => {
+                    postgres_reviews_query(snippet)
+                }
+            };
+            format!(
+                "SELECT '{}' AS source, t.* FROM ({query}) t",
+                table_name(*table)
+            )
+        })
+        .collect::<Vec<_>>()
+        .join(" UNION ALL ")
+}
+
+fn table_name(table: FullTextTable) -> &'static str {
+    match table {
+        FullTextTable::Proposals => "proposals",
+        FullTextTable::Challenges => "challenges",
+        FullTextTable::Reviews => "reviews",
+    }
+}
+
+/// The page of hits, the best ranked first
+fn page_query(tables: &[FullTextTable], conn: &DbConnection, limit: &str, offset: i64) -> String {
+    format!(
+        "SELECT * FROM ({}) hits ORDER BY rank DESC, source, id LIMIT {limit} OFFSET {offset}",
+        hits_query(tables, conn, true)
+    )
+}
+
+fn total_query(tables: &[FullTextTable], conn: &DbConnection) -> String {
+    format!(
+        "SELECT COUNT(*) AS count FROM ({}) hits",
+        hits_query(tables, conn, false)
+    )
+}
+
+/// Number of hits for every value of `column`, the most frequent first
+fn facet_query(tables: &[FullTextTable], conn: &DbConnection, column: &str) -> String {
+    format!(
+        "SELECT {column} AS value, COUNT(*) AS count FROM ({}) hits \
+         WHERE {column} IS NOT NULL GROUP BY {column} ORDER BY count DESC, value",
+        hits_query(tables, conn, false)
+    )
+}
+
+#[derive(QueryableByName)]
+struct HitRow {
+    #[sql_type = "Text"]
+    source: String,
+    #[sql_type = "Integer"]
+    id: i32,
+    #[sql_type = "Nullable<Text>"]
+    proposal_id: Option<String>,
+    #[sql_type = "Text"]
+    title: String,
+    #[sql_type = "Text"]
+    snippet: String,
+    #[sql_type = "Double"]
+    rank: f64,
+    #[sql_type = "Nullable<Integer>"]
+    challenge_id: Option<i32>,
+    #[sql_type = "Nullable<Integer>"]
+    fund_id: Option<i32>,
+    #[sql_type = "Nullable<Text>"]
+    category: Option<String>,
+}
+
+impl HitRow {
+    fn into_hit(self) -> Result<SearchHit, HandleError> {
+        let table = FullTextTable::all()
+            .into_iter()
+            .find(|table| table_name(*table) == self.source)
+            .ok_or_else(|| {
+                HandleError::InternalError(format!("unknown search source: {}", self.source))
+            })?;
+        Ok(SearchHit {
+            table,
+            id: self.id,
+            proposal_id: self.proposal_id,
+            title: self.title,
+            snippet: self.snippet,
+            rank: self.rank,
+            challenge_id: self.challenge_id,
+            fund_id: self.fund_id,
+            category: self.category,
+        })
+    }
+}
+
+#[derive(QueryableByName)]
+struct CountRow {
+    #[sql_type = "BigInt"]
+    count: i64,
+}
+
+#[derive(QueryableByName)]
+struct IntegerFacetRow {
+    #[sql_type = "Integer"]
+    value: i32,
+    #[sql_type = "BigInt"]
+    count: i64,
+}
+
+#[derive(QueryableByName)]
+struct TextFacetRow {
+    #[sql_type = "Text"]
+    value: String,
+    #[sql_type = "BigInt"]
+    count: i64,
+}
+
+/// Run one of the queries above with the search and the filters of `query`
+fn load_rows<R>(
+    sql: String,
+    query: &FullTextSearchQuery,
+    conn: &DbConnection,
+) -> QueryResult<Vec<R>>
+where
+    R: QueryableByName<Sqlite> + QueryableByName<Pg>,
+{
+    let search = match conn {
+        DbConnection::Sqlite(_) => fts5_query(&query.query),
+        DbConnection::Postgres(_) => query.query.clone(),
+    };
+    q!(
+        conn,
+        sql_query(&sql)
+            .bind::<Text, _>(search)
+            .bind::<Nullable<Integer>, _>(query.fund_id)
+            .bind::<Nullable<Integer>, _>(query.challenge_id)
+            .bind::<Nullable<Text>, _>(query.category.clone())
+            .load(conn)
+    )
+}
+
+fn full_text_search(
+    query: FullTextSearchQuery,
+    conn: &DbConnection,
+) -> Result<FullTextSearchResponse, HandleError> {
+    if query.query.split_whitespace().next().is_none() {
+        return Err(HandleError::BadRequest(
+            "search query must not be empty".to_string(),
+        ));
+    }
+    // `LIMIT -1` is no limit for sqlite, but an error for postgres
+    let limit = match (query.limit, conn) {
+        (Some(limit), _) => map_limit(limit)?.to_string(),
+        (None, DbConnection::Sqlite(_)) => "-1".to_string(),
+        (None, DbConnection::Postgres(_)) => "ALL".to_string(),
+    };
+    let offset = map_offset(query.offset.unwrap_or(0))?;
+
+    let mut tables = Vec::new();
+    for table in &query.tables {
+        if !tables.contains(table) {
+            tables.push(*table);
+        }
+    }
+
+    let search_error =
+        |e: diesel::result::Error| HandleError::InternalError(format!("error searching: {e}"));
+
+    let hits = load_rows::<HitRow>(page_query(&tables, conn, &limit, offset), &query, conn)
+        .map_err(search_error)?
+        .into_iter()
+        .map(HitRow::into_hit)
+        .collect::<Result<_, _>>()?;
+    let total = load_rows::<CountRow>(total_query(&tables, conn), &query, conn)
+        .map_err(search_error)?
+        .first()
+        .map_or(0, |row| row.count as u64);
+    let facets = SearchFacets {
+        challenges: load_rows(facet_query(&tables, conn, "challenge_id"), &query, conn)
+            .map_err(search_error)?
+            .into_iter()
+            .map(|row: IntegerFacetRow| facet_count(row.value, row.count))
+            .collect(),
+        funds: load_rows(facet_query(&tables, conn, "fund_id"), &query, conn)
+            .map_err(search_error)?
+            .into_iter()
+            .map(|row: IntegerFacetRow| facet_count(row.value, row.count))
+            .collect(),
+        categories: load_rows(facet_query(&tables, conn, "category"), &query, conn)
+            .map_err(search_error)?
+            .into_iter()
+            .map(|row: TextFacetRow| facet_count(row.value, row.count))
+            .collect(),
+    };
+
+    Ok(FullTextSearchResponse {
+        total,
+        hits,
+        facets,
+    })
+}
+
+fn facet_count<T>(value: T, count: i64) -> FacetCount<T> {
+    FacetCount {
+        value,
+        count: count as u64,
+    }
+}
+
+/// Turn free text into an FTS5 query matching all its words, quoting every word so
+/// that the FTS5 query syntax characters are searched literally
+fn fts5_query(search: &str) -> String {
+    search
+        .split_whitespace()
+        .map(|word| format!("\"{}\"", word.replace('"', "\"\"")))
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn fts5_query_quotes_words() {
+        assert_eq!(fts5_query("  defi  wallet "), r#""defi" "wallet""#);
+        assert_eq!(fts5_query(r#"say "hi" OR*"#), r#""say" """hi""" "OR*""#);
+    }
+}
@@ -1,3 +1,7 @@
+mod full_text;
+
+pub use full_text::full_text_search_db;
+
 use std::convert::TryInto;
 
 use crate::{
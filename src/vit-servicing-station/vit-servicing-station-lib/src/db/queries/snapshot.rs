@@ -1,7 +1,12 @@
 use crate::{
     db::{
-        models::snapshot::{Contribution, Snapshot, Voter},
-        schema::{contributions, snapshots, voters},
+        models::snapshot::{
+            Contribution, Snapshot, SnapshotVersion, VersionedContribution, VersionedVoter, Voter,
+        },
+        schema::{
+            contributions, contributions_history, snapshot_versions, snapshots, voters,
+            voters_history,
+        },
         DbConnection, DbConnectionPool,
     },
     q,
@@ -16,6 +21,10 @@ use itertools::Itertools;
 
 const BATCH_PUT_CHUNK_SIZE: usize = 1000;
 
+/// Number of versions kept in the history of a snapshot tag, the older ones are
+/// removed when a new version is recorded
+pub const SNAPSHOT_VERSIONS_KEPT: i32 = 10;
+
 pub async fn query_all_snapshots(pool: &DbConnectionPool) -> Result<Vec<Snapshot>, HandleError> {
     let db_conn = pool.get().map_err(HandleError::DatabaseError)?;
     tokio::task::spawn_blocking(move || {
@@ -49,13 +58,11 @@ pub async fn query_snapshot_by_tag(
     .map_err(|e| HandleError::InternalError(format!("Error executing request: {}", e)))?
 }
 
-pub fn put_snapshot(snapshot: Snapshot, pool: &DbConnectionPool) -> Result<(), HandleError> {
-    let db_conn = pool.get().map_err(HandleError::DatabaseError)?;
-
+pub fn put_snapshot(snapshot: Snapshot, db_conn: &DbConnection) -> Result<(), HandleError> {
     match db_conn {
         DbConnection::Sqlite(db_conn) => diesel::replace_into(snapshots::table)
             .values(snapshot)
-            .execute(&db_conn),
+            .execute(db_conn),
         DbConnection::Postgres(db_conn) => {
             // TODO: Find a better way to do this?
             //
@@ -67,11 +74,11 @@ pub fn put_snapshot(snapshot: Snapshot, pool: &DbConnectionPool) -> Result<(), H
             db_conn.transaction(|| {
                 diesel::delete(snapshots::table)
                     .filter(snapshots::tag.eq(&snapshot.tag))
-                    .execute(&db_conn)?;
+                    .execute(db_conn)?;
 
                 diesel::insert_into(snapshots::table)
                     .values(snapshot)
-                    .execute(&db_conn)
+                    .execute(db_conn)
             })
         }
     }
@@ -152,7 +159,7 @@ pub fn batch_put_voters(voters: &[Voter], db_conn: &DbConnection) -> Result<(),
                 (&v.voting_key, &v.voting_group, &v.snapshot_tag)
             });
 
-            db_conn.build_transaction().read_write().run(move || {
+            db_conn.transaction(move || {
                 for chunk in &unique_voters.into_iter().chunks(BATCH_PUT_CHUNK_SIZE) {
                     diesel::insert_into(voters::table)
                         .values(chunk.collect::<Vec<_>>())
@@ -162,7 +169,11 @@ pub fn batch_put_voters(voters: &[Voter], db_conn: &DbConnection) -> Result<(),
                             voters::snapshot_tag,
                         ))
                         .do_update()
-                        .set((voters::voting_power.eq(excluded(voters::voting_power)),))
+                        .set((
+                            voters::voting_power.eq(excluded(voters::voting_power)),
+                            voters::raw_stake.eq(excluded(voters::raw_stake)),
+                            voters::transformed_power.eq(excluded(voters::transformed_power)),
+                        ))
                         .execute(db_conn)?;
                 }
 
@@ -256,7 +267,7 @@ pub fn batch_put_contributions(
                 )
             });
 
-            db_conn.build_transaction().read_write().run(move || {
+            db_conn.transaction(move || {
                 for chunk in &unique_contributions
                     .into_iter()
                     .chunks(BATCH_PUT_CHUNK_SIZE)
@@ -285,3 +296,172 @@ pub fn batch_put_contributions(
     .map_err(|e| HandleError::InternalError(format!("Error executing request: {}", e)))?;
     Ok(())
 }
+
+/// Record a new version of the snapshot with the given voters and contributions,
+/// and remove the versions of the tag older than the last [`SNAPSHOT_VERSIONS_KEPT`].
+/// Returns the number of the new version
+pub fn put_snapshot_version(
+    snapshot: &Snapshot,
+    voters: &[Voter],
+    contributions: &[Contribution],
+    db_conn: &DbConnection,
+) -> Result<i32, HandleError> {
+    // keep the same rows the `voters` and `contributions` tables end up with
+    let unique_voters = dedup_by_key_keep_last(voters.iter(), |v| (&v.voting_key, &v.voting_group));
+    let unique_contributions = dedup_by_key_keep_last(contributions.iter(), |c| {
+        (&c.stake_public_key, &c.voting_key, &c.voting_group)
+    });
+
+    q!(
+        db_conn,
+        db_conn.transaction(|| {
+            let last_version: Option<i32> = snapshot_versions::table
+                .filter(snapshot_versions::tag.eq(&snapshot.tag))
+                .select(diesel::dsl::max(snapshot_versions::version))
+                .first(db_conn)?;
+            let version = last_version.unwrap_or(0) + 1;
+
+            diesel::insert_into(snapshot_versions::table)
+                .values(SnapshotVersion {
+                    tag: snapshot.tag.clone(),
+                    version,
+                    last_updated: snapshot.last_updated,
+                })
+                .execute(db_conn)?;
+
+            for chunk in &unique_voters.iter().chunks(BATCH_PUT_CHUNK_SIZE) {
+                let rows: Vec<_> = chunk
+                    .map(|voter| VersionedVoter::new(voter, version))
+                    .collect();
+                diesel::insert_into(voters_history::table)
+                    .values(&rows)
+                    .execute(db_conn)?;
+            }
+
+            for chunk in &unique_contributions.iter().chunks(BATCH_PUT_CHUNK_SIZE) {
+                let rows: Vec<_> = chunk
+                    .map(|contribution| VersionedContribution::new(contribution, version))
+                    .collect();
+                diesel::insert_into(contributions_history::table)
+                    .values(&rows)
+                    .execute(db_conn)?;
+            }
+
+            let oldest_kept = version - SNAPSHOT_VERSIONS_KEPT + 1;
+            diesel::delete(snapshot_versions::table)
+                .filter(snapshot_versions::tag.eq(&snapshot.tag))
+                .filter(snapshot_versions::version.lt(oldest_kept))
+                .execute(db_conn)?;
+            diesel::delete(voters_history::table)
+                .filter(voters_history::snapshot_tag.eq(&snapshot.tag))
+                .filter(voters_history::version.lt(oldest_kept))
+                .execute(db_conn)?;
+            diesel::delete(contributions_history::table)
+                .filter(contributions_history::snapshot_tag.eq(&snapshot.tag))
+                .filter(contributions_history::version.lt(oldest_kept))
+                .execute(db_conn)?;
+
+            Ok(version)
+        })
+    )
+    .map_err(|e: diesel::result::Error| {
+        HandleError::InternalError(format!("Error executing request: {}", e))
+    })
+}
+
+pub async fn query_snapshot_versions_by_tag(
+    tag: String,
+    pool: &DbConnectionPool,
+) -> Result<Vec<SnapshotVersion>, HandleError> {
+    let db_conn = pool.get().map_err(HandleError::DatabaseError)?;
+    tokio::task::spawn_blocking(move || {
+        q!(
+            db_conn,
+            snapshot_versions::dsl::snapshot_versions
+                .filter(snapshot_versions::dsl::tag.eq(tag))
+                .order_by(snapshot_versions::dsl::version.asc())
+                .load(&db_conn)
+        )
+        .map_err(|e| {
+            HandleError::InternalError(format!("Error retrieving snapshot versions: {}", e))
+        })
+    })
+    .await
+    .map_err(|e| HandleError::InternalError(format!("Error executing request: {}", e)))?
+}
+
+pub async fn query_snapshot_version(
+    tag: String,
+    version: i32,
+    pool: &DbConnectionPool,
+) -> Result<SnapshotVersion, HandleError> {
+    let db_conn = pool.get().map_err(HandleError::DatabaseError)?;
+    tokio::task::spawn_blocking(move || {
+        q!(
+            db_conn,
+            snapshot_versions::dsl::snapshot_versions
+                .filter(snapshot_versions::dsl::tag.eq(tag))
+                .filter(snapshot_versions::dsl::version.eq(version))
+                .first(&db_conn)
+        )
+        .map_err(|e| HandleError::NotFound(format!("Error loading snapshot version: {}", e)))
+    })
+    .await
+    .map_err(|e| HandleError::InternalError(format!("Error executing request: {}", e)))?
+}
+
+pub async fn query_voters_by_snapshot_tag_and_version(
+    tag: String,
+    version: i32,
+    pool: &DbConnectionPool,
+) -> Result<Vec<Voter>, HandleError> {
+    let db_conn = pool.get().map_err(HandleError::DatabaseError)?;
+    tokio::task::spawn_blocking(move || {
+        q!(
+            db_conn,
+            voters_history::dsl::voters_history
+                .filter(voters_history::dsl::snapshot_tag.eq(tag))
+                .filter(voters_history::dsl::version.eq(version))
+                .select((
+                    voters_history::dsl::voting_key,
+                    voters_history::dsl::voting_power,
+                    voters_history::dsl::voting_group,
+                    voters_history::dsl::snapshot_tag,
+                    voters_history::dsl::raw_stake,
+                    voters_history::dsl::transformed_power,
+                ))
+                .load(&db_conn)
+        )
+        .map_err(|e| HandleError::NotFound(format!("Error loading voters: {}", e)))
+    })
+    .await
+    .map_err(|e| HandleError::InternalError(format!("Error executing voters: {}", e)))?
+}
+
+pub async fn query_contributions_by_snapshot_tag_and_version(
+    tag: String,
+    version: i32,
+    pool: &DbConnectionPool,
+) -> Result<Vec<Contribution>, HandleError> {
+    let db_conn = pool.get().map_err(HandleError::DatabaseError)?;
+    tokio::task::spawn_blocking(move || {
+        q!(
+            db_conn,
+            contributions_history::dsl::contributions_history
+                .filter(contributions_history::dsl::snapshot_tag.eq(tag))
+                .filter(contributions_history::dsl::version.eq(version))
+                .select((
+                    contributions_history::dsl::stake_public_key,
+                    contributions_history::dsl::reward_address,
+                    contributions_history::dsl::value,
+                    contributions_history::dsl::voting_key,
+                    contributions_history::dsl::voting_group,
+                    contributions_history::dsl::snapshot_tag,
+                ))
+                .load(&db_conn)
+        )
+        .map_err(|e| HandleError::NotFound(format!("Error loading contributions: {}", e)))
+    })
+    .await
+    .map_err(|e| HandleError::InternalError(format!("Error executing request: {}", e)))?
+}
@@ -1,8 +1,21 @@
+table! {
+    api_token_audit_log (id) {
+        id -> Integer,
+        token -> Binary,
+        time -> BigInt,
+        method -> Text,
+        path -> Text,
+        status -> Integer,
+    }
+}
+
 table! {
     api_tokens (token) {
         token -> Binary,
         creation_time -> BigInt,
         expire_time -> BigInt,
+        scope -> Text,
+        revoked_time -> Nullable<BigInt>,
     }
 }
 
@@ -44,6 +57,22 @@ table! {
         voting_key -> Text,
         voting_group -> Text,
         snapshot_tag -> Text,
+        raw_stake -> Nullable<BigInt>,
+        transformed_power -> Nullable<BigInt>,
+    }
+}
+
+table! {
+    contributions_history (stake_public_key, voting_key, voting_group, snapshot_tag, version) {
+        stake_public_key -> Text,
+        reward_address -> Text,
+        value -> BigInt,
+        voting_key -> Text,
+        voting_group -> Text,
+        snapshot_tag -> Text,
+        version -> Integer,
+        raw_stake -> Nullable<BigInt>,
+        transformed_power -> Nullable<BigInt>,
     }
 }
 
@@ -145,6 +174,14 @@ table! {
     }
 }
 
+table! {
+    snapshot_versions (tag, version) {
+        tag -> Text,
+        version -> Integer,
+        last_updated -> BigInt,
+    }
+}
+
 table! {
     voteplans (id) {
         id -> Integer,
@@ -168,6 +205,16 @@ table! {
     }
 }
 
+table! {
+    voters_history (voting_key, voting_group, snapshot_tag, version) {
+        voting_key -> Text,
+        voting_power -> BigInt,
+        voting_group -> Text,
+        snapshot_tag -> Text,
+        version -> Integer,
+    }
+}
+
 table! {
     votes (fragment_id) {
         fragment_id -> Text,
@@ -185,10 +232,12 @@ joinable!(goals -> funds (fund_id));
 joinable!(voters -> snapshots (snapshot_tag));
 
 allow_tables_to_appear_in_same_query!(
+    api_token_audit_log,
     api_tokens,
     challenges,
     community_advisors_reviews,
     contributions,
+    contributions_history,
     funds,
     goals,
     groups,
@@ -196,8 +245,10 @@ allow_tables_to_appear_in_same_query!(
     proposal_simple_challenge,
     proposals,
     proposals_voteplans,
+    snapshot_versions,
     snapshots,
     voteplans,
     voters,
+    voters_history,
     votes,
 );
@@ -1,3 +1,4 @@
+use crate::v0::endpoints::graphql::QueryLimits;
 use serde::{de::Visitor, Deserialize, Deserializer, Serialize};
 use simplelog::LevelFilter;
 use std::io::ErrorKind;
@@ -61,6 +62,10 @@ pub struct ServiceSettings {
     #[structopt(long)]
     pub enable_api_tokens: bool,
 
+    #[serde(default)]
+    #[structopt(flatten)]
+    pub graphql: GraphQl,
+
     #[serde(default)]
     #[structopt(flatten)]
     pub log: Log,
@@ -82,6 +87,19 @@ pub struct Tls {
     pub priv_key_file: Option<String>,
 }
 
+#[derive(Debug, Clone, Default, Serialize, Deserialize, PartialEq, Eq, StructOpt)]
+#[serde(deny_unknown_fields)]
+#[structopt(rename_all = "kebab-case")]
+pub struct GraphQl {
+    /// Maximum nesting depth of the GraphQL queries
+    #[structopt(long = "graphql-query-depth-limit")]
+    pub query_depth_limit: Option<usize>,
+
+    /// Maximum complexity of the GraphQL queries, paginated fields count once per requested item
+    #[structopt(long = "graphql-query-complexity-limit")]
+    pub query_complexity_limit: Option<usize>,
+}
+
 #[derive(Debug, Clone, Default, Serialize, PartialEq, Eq)]
 pub struct CorsOrigin(String);
 
@@ -191,6 +209,15 @@ impl ServiceSettings {
 
         return_settings.enable_api_tokens = other_settings.enable_api_tokens;
 
+        if other_settings.graphql.query_depth_limit.is_some() {
+            return_settings.graphql.query_depth_limit = other_settings.graphql.query_depth_limit;
+        }
+
+        if other_settings.graphql.query_complexity_limit.is_some() {
+            return_settings.graphql.query_complexity_limit =
+                other_settings.graphql.query_complexity_limit;
+        }
+
         return_settings
     }
 }
@@ -201,6 +228,16 @@ impl Tls {
     }
 }
 
+impl GraphQl {
+    pub fn query_limits(&self) -> QueryLimits {
+        let default = QueryLimits::default();
+        QueryLimits {
+            depth: self.query_depth_limit.unwrap_or(default.depth),
+            complexity: self.query_complexity_limit.unwrap_or(default.complexity),
+        }
+    }
+}
+
 impl<'de> Deserialize<'de> for CorsOrigin {
     fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
     where
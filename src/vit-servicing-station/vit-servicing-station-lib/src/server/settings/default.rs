@@ -1,5 +1,7 @@
 use super::config::ServiceSettings;
-use super::config::{Cors, Log, Tls, ADDRESS_DEFAULT, BLOCK0_PATH_DEFAULT, DB_URL_DEFAULT};
+use super::config::{
+    Cors, GraphQl, Log, Tls, ADDRESS_DEFAULT, BLOCK0_PATH_DEFAULT, DB_URL_DEFAULT,
+};
 use std::net::SocketAddr;
 use std::str::FromStr;
 
@@ -15,6 +17,7 @@ impl Default for ServiceSettings {
             block0_path: Some(BLOCK0_PATH_DEFAULT.to_string()),
             block0_paths: None,
             enable_api_tokens: false,
+            graphql: GraphQl::default(),
             log: Log::default(),
             service_version: "".to_string(),
         }
@@ -2,6 +2,6 @@ mod config;
 mod default;
 
 pub use config::{
-    dump_settings_to_file, load_settings_from_file, Cors, CorsOrigin, LogLevel, ServiceSettings,
-    Tls,
+    dump_settings_to_file, load_settings_from_file, Cors, CorsOrigin, GraphQl, LogLevel,
+    ServiceSettings, Tls,
 };
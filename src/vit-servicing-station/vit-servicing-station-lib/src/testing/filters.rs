@@ -8,7 +8,10 @@ use warp::{
 
 use crate::{
     db::{migrations::initialize_db_with_migration, DbConnection},
-    v0::context::{test::new_db_test_shared_context, SharedContext},
+    v0::context::{
+        test::{new_db_test_shared_context, new_db_test_shared_context_for},
+        SharedContext,
+    },
 };
 
 /// Initialize an in-memory database with migrations and return a tuple containing:
@@ -18,8 +21,26 @@ pub async fn test_context() -> (
     impl Filter<Extract = (SharedContext,), Error = Infallible> + Clone,
     DbConnection,
 ) {
-    let shared_context = new_db_test_shared_context();
+    with_migrations(new_db_test_shared_context()).await
+}
 
+/// Same as [`test_context`], with a database of the given kind whatever the
+/// `TEST_DB` environment variable
+pub async fn test_context_for(
+    db: &str,
+) -> (
+    impl Filter<Extract = (SharedContext,), Error = Infallible> + Clone,
+    DbConnection,
+) {
+    with_migrations(new_db_test_shared_context_for(db)).await
+}
+
+async fn with_migrations(
+    shared_context: SharedContext,
+) -> (
+    impl Filter<Extract = (SharedContext,), Error = Infallible> + Clone,
+    DbConnection,
+) {
     let conn = shared_context
         .read()
         .await
@@ -1,6 +1,11 @@
-use crate::db::{queries::api_tokens as api_tokens_queries, DbConnectionPool};
+use crate::db::{
+    models::api_tokens::{ApiTokenData, ApiTokenScope},
+    queries::api_tokens as api_tokens_queries,
+    DbConnectionPool,
+};
 use crate::v0::{context::SharedContext, errors::HandleError};
-use warp::{Filter, Rejection};
+use time::OffsetDateTime;
+use warp::{http::Method, path::FullPath, reply::Response, Filter, Rejection};
 
 /// Header where token should be present in requests
 pub const API_TOKEN_HEADER: &str = "API-Token";
@@ -40,10 +45,12 @@ impl ApiTokenManager {
         Self { connection_pool }
     }
 
-    async fn is_token_valid(&self, token: ApiToken) -> Result<bool, HandleError> {
+    /// Returns the token data if the token exists, and is neither expired nor revoked
+    async fn active_token(&self, token: ApiToken) -> Result<Option<ApiTokenData>, HandleError> {
         match api_tokens_queries::query_token(token, &self.connection_pool).await {
-            Ok(Some(_)) => Ok(true),
-            Ok(None) => Ok(false),
+            Ok(token_data) => Ok(token_data.filter(|token_data| {
+                token_data.is_active(OffsetDateTime::now_utc().unix_timestamp())
+            })),
             Err(e) => Err(HandleError::InternalError(format!(
                 "Error retrieving token: {}",
                 e
@@ -51,13 +58,31 @@ impl ApiTokenManager {
         }
     }
 
-    #[allow(dead_code)]
-    async fn revoke_token(&self, _token: ApiToken) -> Result<(), ()> {
-        Ok(())
+    async fn record_write(
+        &self,
+        token: ApiToken,
+        method: &Method,
+        path: &FullPath,
+        response: &Response,
+    ) -> Result<(), HandleError> {
+        api_tokens_queries::insert_audit_log_entry(
+            token,
+            method.to_string(),
+            path.as_str().to_string(),
+            response.status().as_u16(),
+            &self.connection_pool,
+        )
+        .await
     }
 }
 
-async fn authorize_token(token: String, context: SharedContext) -> Result<(), Rejection> {
+async fn authorize_token(
+    token: String,
+    context: SharedContext,
+    required_scope: ApiTokenScope,
+    method: Method,
+    path: FullPath,
+) -> Result<ApiToken, Rejection> {
     let manager = ApiTokenManager::new(context.read().await.db_connection_pool.clone());
 
     let mut token_vec: Vec<u8> = Vec::new();
@@ -70,9 +95,20 @@ async fn authorize_token(token: String, context: SharedContext) -> Result<(), Re
 
     let api_token = ApiToken(token_vec);
 
-    match manager.is_token_valid(api_token).await {
-        Ok(true) => Ok(()),
-        Ok(false) => {
+    match manager.active_token(api_token.clone()).await {
+        Ok(Some(token_data)) if token_data.scope.allows(required_scope) => Ok(api_token),
+        Ok(Some(token_data)) => {
+            tracing::event!(
+                tracing::Level::INFO,
+                "Token with scope {} used on {} {}, which requires {}",
+                token_data.scope,
+                method,
+                path.as_str(),
+                required_scope
+            );
+            Err(warp::reject::custom(HandleError::ForbiddenToken))
+        }
+        Ok(None) => {
             tracing::event!(
                 tracing::Level::INFO,
                 "Unauthorized token received: {}",
@@ -84,37 +120,102 @@ async fn authorize_token(token: String, context: SharedContext) -> Result<(), Re
     }
 }
 
-/// A warp filter that checks authorization through API tokens.
-/// The header `API_TOKEN_HEADER` should be present and valid otherwise the request is rejected.
-pub async fn api_token_filter(
+async fn record_write(
+    token: ApiToken,
+    method: Method,
+    path: FullPath,
+    response: Response,
     context: SharedContext,
-) -> impl Filter<Extract = (), Error = Rejection> + Clone {
+) -> Response {
+    let manager = ApiTokenManager::new(context.read().await.db_connection_pool.clone());
+    if let Err(e) = manager.record_write(token, &method, &path, &response).await {
+        // the write already happened, its response is still sent
+        tracing::event!(
+            tracing::Level::ERROR,
+            "Could not record {} {} in the audit log: {}",
+            method,
+            path.as_str(),
+            e
+        );
+    }
+    response
+}
+
+fn authorized_token_filter(
+    context: SharedContext,
+    required_scope: ApiTokenScope,
+) -> impl Filter<Extract = (ApiToken,), Error = Rejection> + Clone {
     let with_context = warp::any().map(move || context.clone());
     warp::header::header(API_TOKEN_HEADER)
         .and(with_context)
+        .and(warp::any().map(move || required_scope))
+        .and(warp::method())
+        .and(warp::path::full())
         .and_then(authorize_token)
-        .and(warp::any())
+}
+
+/// A warp filter that checks authorization through API tokens.
+/// The header `API_TOKEN_HEADER` should be present, valid and hold a token with (at least) the
+/// required scope, otherwise the request is rejected.
+pub async fn api_token_filter(
+    context: SharedContext,
+    required_scope: ApiTokenScope,
+) -> impl Filter<Extract = (), Error = Rejection> + Clone {
+    authorized_token_filter(context, required_scope)
+        .map(|_token| ())
         .untuple_one()
 }
 
+/// Same as [`api_token_filter`], for the endpoints of `handler` that write to the database.
+/// Once `handler` replied, the request is recorded in the audit log with the token used and
+/// the status of the response. The requests rejected by `handler` are not recorded.
+pub async fn api_token_write_filter<F>(
+    context: SharedContext,
+    required_scope: ApiTokenScope,
+    handler: F,
+) -> impl Filter<Extract = (Response,), Error = Rejection> + Clone
+where
+    F: Filter<Extract = (Response,), Error = Rejection> + Clone + Send + Sync + 'static,
+{
+    let with_context = {
+        let context = context.clone();
+        warp::any().map(move || context.clone())
+    };
+    authorized_token_filter(context, required_scope)
+        .and(warp::method())
+        .and(warp::path::full())
+        .and(handler)
+        .and(with_context)
+        .then(record_write)
+}
+
 #[cfg(test)]
 mod test {
     use crate::db::{
-        migrations as db_testing, models::api_tokens as api_token_model,
-        models::api_tokens::ApiTokenData, schema::api_tokens, DbConnectionPool,
+        migrations as db_testing,
+        models::api_tokens as api_token_model,
+        models::api_tokens::{ApiTokenData, ApiTokenScope},
+        queries::api_tokens::query_audit_log,
+        schema::api_tokens,
+        DbConnectionPool,
     };
     use crate::q;
-    use crate::v0::api_token::{api_token_filter, ApiToken, API_TOKEN_HEADER};
+    use crate::v0::api_token::{
+        api_token_filter, api_token_write_filter, ApiToken, API_TOKEN_HEADER,
+    };
     use crate::v0::context::test::new_db_test_shared_context;
     use diesel::{ExpressionMethods, RunQueryDsl};
-    use time::OffsetDateTime;
+    use time::{Duration, OffsetDateTime};
+    use warp::{http::StatusCode, Filter, Reply};
 
-    pub fn get_testing_token() -> (api_token_model::ApiTokenData, String) {
+    pub fn get_testing_token(scope: ApiTokenScope) -> (api_token_model::ApiTokenData, String) {
         let data = b"ffffffffffffffffffffffffffffffff".to_vec();
         let token_data = ApiTokenData {
             token: ApiToken(data.clone()),
             creation_time: OffsetDateTime::now_utc().unix_timestamp(),
-            expire_time: OffsetDateTime::now_utc().unix_timestamp(),
+            expire_time: (OffsetDateTime::now_utc() + Duration::days(1)).unix_timestamp(),
+            scope,
+            revoked_time: None,
         };
         (
             token_data,
@@ -128,6 +229,8 @@ mod test {
             api_tokens::dsl::token.eq(token.token.0.clone()),
             api_tokens::dsl::creation_time.eq(token.creation_time),
             api_tokens::dsl::expire_time.eq(token.expire_time),
+            api_tokens::dsl::scope.eq(token.scope.to_string()),
+            api_tokens::dsl::revoked_time.eq(token.revoked_time),
         );
         q!(
             conn,
@@ -141,7 +244,7 @@ mod test {
     #[tokio::test]
     async fn api_token_filter_reject() {
         let shared_context = new_db_test_shared_context();
-        let filter = api_token_filter(shared_context).await;
+        let filter = api_token_filter(shared_context, ApiTokenScope::ReadOnly).await;
 
         assert!(warp::test::request()
             .header(API_TOKEN_HEADER, "foobar")
@@ -157,10 +260,10 @@ mod test {
         // initialize db
         let pool = &shared_context.read().await.db_connection_pool;
         db_testing::initialize_db_with_migration(&pool.get().unwrap()).unwrap();
-        let (token, base64_token) = get_testing_token();
+        let (token, base64_token) = get_testing_token(ApiTokenScope::ReadOnly);
         insert_token_to_db(token, pool);
 
-        let filter = api_token_filter(shared_context.clone()).await;
+        let filter = api_token_filter(shared_context.clone(), ApiTokenScope::ReadOnly).await;
 
         assert!(warp::test::request()
             .header(API_TOKEN_HEADER, base64_token)
@@ -168,4 +271,67 @@ mod test {
             .await
             .is_ok());
     }
+
+    #[tokio::test]
+    async fn api_token_filter_checks_scope_and_audits_writes() {
+        let shared_context = new_db_test_shared_context();
+
+        let pool = &shared_context.read().await.db_connection_pool;
+        db_testing::initialize_db_with_migration(&pool.get().unwrap()).unwrap();
+        let (token, base64_token) = get_testing_token(ApiTokenScope::SnapshotWrite);
+        insert_token_to_db(token.clone(), pool);
+
+        let handler = warp::any()
+            .map(|| warp::reply::with_status(warp::reply(), StatusCode::CREATED).into_response());
+        let snapshot_filter = api_token_write_filter(
+            shared_context.clone(),
+            ApiTokenScope::SnapshotWrite,
+            handler.clone(),
+        )
+        .await;
+        let admin_filter =
+            api_token_write_filter(shared_context.clone(), ApiTokenScope::Admin, handler).await;
+
+        let response = warp::test::request()
+            .method("PUT")
+            .path("/api/v0/admin/snapshot/voters/tag")
+            .header(API_TOKEN_HEADER, base64_token.clone())
+            .filter(&snapshot_filter)
+            .await
+            .unwrap();
+        assert_eq!(response.status(), StatusCode::CREATED);
+        assert!(warp::test::request()
+            .method("PUT")
+            .path("/api/v0/admin/fund")
+            .header(API_TOKEN_HEADER, base64_token)
+            .filter(&admin_filter)
+            .await
+            .is_err());
+
+        let log = query_audit_log(Some(token.token.as_ref()), 10, &pool.get().unwrap()).unwrap();
+        assert_eq!(log.len(), 1);
+        assert_eq!(log[0].method, "PUT");
+        assert_eq!(log[0].path, "/api/v0/admin/snapshot/voters/tag");
+        // recorded once the handler replied
+        assert_eq!(log[0].status, i32::from(StatusCode::CREATED.as_u16()));
+    }
+
+    #[tokio::test]
+    async fn api_token_filter_rejects_expired() {
+        let shared_context = new_db_test_shared_context();
+
+        let pool = &shared_context.read().await.db_connection_pool;
+        db_testing::initialize_db_with_migration(&pool.get().unwrap()).unwrap();
+        let (mut token, base64_token) = get_testing_token(ApiTokenScope::Admin);
+        token.expire_time = OffsetDateTime::now_utc().unix_timestamp() - 1;
+        insert_token_to_db(token, pool);
+
+        let filter = api_token_filter(shared_context.clone(), ApiTokenScope::ReadOnly).await;
+
+        assert!(warp::test::request()
+            .header(API_TOKEN_HEADER, base64_token)
+            .filter(&filter)
+            .await
+            .is_err());
+    }
 }
@@ -52,13 +52,19 @@ pub mod test {
     use super::*;
     use crate::db;
 
+    /// context backed by a new database of the kind given by the `TEST_DB`
+    /// environment variable, sqlite by default
     pub fn new_db_test_shared_context() -> SharedContext {
         let db = match std::env::var("TEST_DB") {
             Ok(db) => db,
             Err(std::env::VarError::NotPresent) => "sqlite".to_string(),
             Err(e) => panic!("{}", e),
         };
+        new_db_test_shared_context_for(&db)
+    }
 
+    /// context backed by a new `sqlite` or `postgres` database
+    pub fn new_db_test_shared_context_for(db: &str) -> SharedContext {
         let name = thread_rng()
             .sample_iter(Alphanumeric)
             .filter(u8::is_ascii_alphabetic)
@@ -67,7 +73,7 @@ pub mod test {
             .collect::<String>()
             .to_lowercase();
 
-        let db_url = match db.as_str() {
+        let db_url = match db {
             "sqlite" => {
                 format!("file:{}?mode=memory&cache=shared", name)
             }
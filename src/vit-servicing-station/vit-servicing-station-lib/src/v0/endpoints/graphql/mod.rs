@@ -0,0 +1,590 @@
+mod pagination;
+mod routes;
+
+use self::pagination::{page_complexity, paginate, paginate_items, PageConnection};
+use crate::{
+    db::{
+        models::{
+            challenges::Challenge as ChallengeModel,
+            community_advisors_reviews::{AdvisorReview, ReviewRanking as ReviewRankingModel},
+            funds::{Fund as FundModel, FundStageDates},
+            goals::Goal as GoalModel,
+            groups::Group as GroupModel,
+            proposals::{ChallengeType as ChallengeTypeModel, Proposal as ProposalModel},
+            voteplans::Voteplan as VoteplanModel,
+        },
+        queries::{
+            challenges::{
+                query_challenge_by_id, query_challenge_proposals_count,
+                query_challenge_proposals_page,
+            },
+            community_advisors_reviews::{
+                query_proposal_reviews_count, query_proposal_reviews_page,
+            },
+            funds::{query_current_fund, query_fund_by_id, query_funds_count, query_funds_page},
+            proposals::query_proposal_by_id,
+        },
+    },
+    v0::context::SharedContext,
+};
+use async_graphql::{Context, EmptyMutation, EmptySubscription, Enum, FieldResult, Object};
+pub use routes::filter;
+use time::{format_description::well_known::Rfc3339, OffsetDateTime};
+
+pub type Schema = async_graphql::Schema<Query, EmptyMutation, EmptySubscription>;
+
+pub const DEFAULT_QUERY_DEPTH_LIMIT: usize = 15;
+pub const DEFAULT_QUERY_COMPLEXITY_LIMIT: usize = 10_000;
+
+/// Limits applied to every GraphQL query before it is executed
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct QueryLimits {
+    pub depth: usize,
+    pub complexity: usize,
+}
+
+impl Default for QueryLimits {
+    fn default() -> Self {
+        Self {
+            depth: DEFAULT_QUERY_DEPTH_LIMIT,
+            complexity: DEFAULT_QUERY_COMPLEXITY_LIMIT,
+        }
+    }
+}
+
+pub fn schema(context: SharedContext, limits: QueryLimits) -> Schema {
+    async_graphql::Schema::build(Query, EmptyMutation, EmptySubscription)
+        .limit_depth(limits.depth)
+        .limit_complexity(limits.complexity)
+        .data(context)
+        .finish()
+}
+
+fn shared_context<'a>(context: &Context<'a>) -> &'a SharedContext {
+    context.data_unchecked::<SharedContext>()
+}
+
+fn to_rfc3339(timestamp: i64) -> FieldResult<String> {
+    Ok(OffsetDateTime::from_unix_timestamp(timestamp)?.format(&Rfc3339)?)
+}
+
+pub struct Query;
+
+#[Object]
+impl Query {
+    /// All the funds, in creation order
+    #[graphql(complexity = "page_complexity(first, last, child_complexity)")]
+    async fn funds(
+        &self,
+        context: &Context<'_>,
+        first: Option<i32>,
+        last: Option<i32>,
+        before: Option<String>,
+        after: Option<String>,
+    ) -> FieldResult<PageConnection<Fund>> {
+        let pool = &shared_context(context).read().await.db_connection_pool;
+        let total_count = query_funds_count(pool).await? as usize;
+        paginate(
+            total_count,
+            first,
+            last,
+            before,
+            after,
+            |offset, limit| async move {
+                let funds = query_funds_page(offset as i64, limit as i64, pool).await?;
+                Ok(funds.into_iter().map(Fund).collect())
+            },
+        )
+        .await
+    }
+
+    async fn fund(&self, context: &Context<'_>, id: i32) -> FieldResult<Fund> {
+        let pool = &shared_context(context).read().await.db_connection_pool;
+        Ok(Fund(query_fund_by_id(id, pool).await?))
+    }
+
+    /// The fund currently running, or the last one if none is running
+    async fn current_fund(&self, context: &Context<'_>) -> FieldResult<Fund> {
+        let pool = &shared_context(context).read().await.db_connection_pool;
+        Ok(Fund(query_current_fund(pool).await?.fund))
+    }
+
+    async fn challenge(&self, context: &Context<'_>, id: i32) -> FieldResult<Challenge> {
+        let pool = &shared_context(context).read().await.db_connection_pool;
+        Ok(Challenge(query_challenge_by_id(id, pool).await?))
+    }
+
+    async fn proposal(
+        &self,
+        context: &Context<'_>,
+        id: i32,
+        voting_group: String,
+    ) -> FieldResult<Proposal> {
+        let pool = &shared_context(context).read().await.db_connection_pool;
+        Ok(Proposal(
+            query_proposal_by_id(id, voting_group, pool).await?.proposal,
+        ))
+    }
+}
+
+pub struct Fund(FundModel);
+
+#[Object]
+impl Fund {
+    async fn id(&self) -> i32 {
+        self.0.id
+    }
+
+    async fn name(&self) -> &str {
+        &self.0.fund_name
+    }
+
+    async fn goal(&self) -> &str {
+        &self.0.fund_goal
+    }
+
+    async fn voting_power_threshold(&self) -> i64 {
+        self.0.voting_power_threshold
+    }
+
+    async fn start_time(&self) -> FieldResult<String> {
+        to_rfc3339(self.0.fund_start_time)
+    }
+
+    async fn end_time(&self) -> FieldResult<String> {
+        to_rfc3339(self.0.fund_end_time)
+    }
+
+    async fn next_start_time(&self) -> FieldResult<String> {
+        to_rfc3339(self.0.next_fund_start_time)
+    }
+
+    async fn registration_snapshot_time(&self) -> FieldResult<String> {
+        to_rfc3339(self.0.registration_snapshot_time)
+    }
+
+    async fn next_registration_snapshot_time(&self) -> FieldResult<String> {
+        to_rfc3339(self.0.next_registration_snapshot_time)
+    }
+
+    async fn stage_dates(&self) -> StageDates {
+        StageDates(self.0.stage_dates.clone())
+    }
+
+    async fn results_url(&self) -> &str {
+        &self.0.results_url
+    }
+
+    async fn survey_url(&self) -> &str {
+        &self.0.survey_url
+    }
+
+    async fn vote_plans(&self) -> Vec<VotePlan> {
+        self.0
+            .chain_vote_plans
+            .iter()
+            .cloned()
+            .map(VotePlan)
+            .collect()
+    }
+
+    async fn goals(&self) -> Vec<Goal> {
+        self.0.goals.iter().cloned().map(Goal).collect()
+    }
+
+    async fn groups(&self) -> Vec<VoterGroup> {
+        self.0.groups.iter().cloned().map(VoterGroup).collect()
+    }
+
+    #[graphql(complexity = "page_complexity(first, last, child_complexity)")]
+    async fn challenges(
+        &self,
+        first: Option<i32>,
+        last: Option<i32>,
+        before: Option<String>,
+        after: Option<String>,
+    ) -> FieldResult<PageConnection<Challenge>> {
+        let challenges = self.0.challenges.iter().cloned().map(Challenge).collect();
+        paginate_items(challenges, first, last, before, after).await
+    }
+}
+
+pub struct StageDates(FundStageDates);
+
+#[Object]
+impl StageDates {
+    async fn insight_sharing_start(&self) -> FieldResult<String> {
+        to_rfc3339(self.0.insight_sharing_start)
+    }
+
+    async fn proposal_submission_start(&self) -> FieldResult<String> {
+        to_rfc3339(self.0.proposal_submission_start)
+    }
+
+    async fn refine_proposals_start(&self) -> FieldResult<String> {
+        to_rfc3339(self.0.refine_proposals_start)
+    }
+
+    async fn finalize_proposals_start(&self) -> FieldResult<String> {
+        to_rfc3339(self.0.finalize_proposals_start)
+    }
+
+    async fn proposal_assessment_start(&self) -> FieldResult<String> {
+        to_rfc3339(self.0.proposal_assessment_start)
+    }
+
+    async fn assessment_qa_start(&self) -> FieldResult<String> {
+        to_rfc3339(self.0.assessment_qa_start)
+    }
+
+    async fn snapshot_start(&self) -> FieldResult<String> {
+        to_rfc3339(self.0.snapshot_start)
+    }
+
+    async fn voting_start(&self) -> FieldResult<String> {
+        to_rfc3339(self.0.voting_start)
+    }
+
+    async fn voting_end(&self) -> FieldResult<String> {
+        to_rfc3339(self.0.voting_end)
+    }
+
+    async fn tallying_end(&self) -> FieldResult<String> {
+        to_rfc3339(self.0.tallying_end)
+    }
+}
+
+pub struct VotePlan(VoteplanModel);
+
+#[Object]
+impl VotePlan {
+    async fn id(&self) -> i32 {
+        self.0.id
+    }
+
+    async fn chain_voteplan_id(&self) -> &str {
+        &self.0.chain_voteplan_id
+    }
+
+    async fn chain_vote_start_time(&self) -> FieldResult<String> {
+        to_rfc3339(self.0.chain_vote_start_time)
+    }
+
+    async fn chain_vote_end_time(&self) -> FieldResult<String> {
+        to_rfc3339(self.0.chain_vote_end_time)
+    }
+
+    async fn chain_committee_end_time(&self) -> FieldResult<String> {
+        to_rfc3339(self.0.chain_committee_end_time)
+    }
+
+    async fn chain_voteplan_payload(&self) -> &str {
+        &self.0.chain_voteplan_payload
+    }
+
+    async fn token_identifier(&self) -> &str {
+        &self.0.token_identifier
+    }
+}
+
+pub struct Goal(GoalModel);
+
+#[Object]
+impl Goal {
+    async fn id(&self) -> i32 {
+        self.0.id
+    }
+
+    async fn name(&self) -> &str {
+        &self.0.goal_name
+    }
+}
+
+pub struct VoterGroup(GroupModel);
+
+#[Object]
+impl VoterGroup {
+    async fn group_id(&self) -> &str {
+        &self.0.group_id
+    }
+
+    async fn token_identifier(&self) -> &str {
+        &self.0.token_identifier
+    }
+}
+
+#[derive(Enum, Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ChallengeType {
+    Simple,
+    CommunityChoice,
+}
+
+impl From<&ChallengeTypeModel> for ChallengeType {
+    fn from(challenge_type: &ChallengeTypeModel) -> Self {
+        match challenge_type {
+            ChallengeTypeModel::Simple => Self::Simple,
+            ChallengeTypeModel::CommunityChoice => Self::CommunityChoice,
+        }
+    }
+}
+
+pub struct Challenge(ChallengeModel);
+
+#[Object]
+impl Challenge {
+    async fn id(&self) -> i32 {
+        self.0.id
+    }
+
+    async fn challenge_type(&self) -> ChallengeType {
+        (&self.0.challenge_type).into()
+    }
+
+    async fn title(&self) -> &str {
+        &self.0.title
+    }
+
+    async fn description(&self) -> &str {
+        &self.0.description
+    }
+
+    async fn rewards_total(&self) -> i64 {
+        self.0.rewards_total
+    }
+
+    async fn proposers_rewards(&self) -> i64 {
+        self.0.proposers_rewards
+    }
+
+    async fn fund_id(&self) -> i32 {
+        self.0.fund_id
+    }
+
+    async fn challenge_url(&self) -> &str {
+        &self.0.challenge_url
+    }
+
+    async fn sponsor(&self) -> Option<&str> {
+        self.0
+            .highlights
+            .as_ref()
+            .map(|highlights| highlights.sponsor.as_str())
+    }
+
+    /// Proposals of the challenge, only the ones of the voting group if one is given
+    #[graphql(complexity = "page_complexity(first, last, child_complexity)")]
+    async fn proposals(
+        &self,
+        context: &Context<'_>,
+        voting_group: Option<String>,
+        first: Option<i32>,
+        last: Option<i32>,
+        before: Option<String>,
+        after: Option<String>,
+    ) -> FieldResult<PageConnection<Proposal>> {
+        let pool = &shared_context(context).read().await.db_connection_pool;
+        let id = self.0.id;
+        let total_count =
+            query_challenge_proposals_count(id, voting_group.clone(), pool).await? as usize;
+        paginate(
+            total_count,
+            first,
+            last,
+            before,
+            after,
+            |offset, limit| async move {
+                let proposals = query_challenge_proposals_page(
+                    id,
+                    voting_group,
+                    offset as i64,
+                    limit as i64,
+                    pool,
+                )
+                .await?;
+                Ok(proposals.into_iter().map(Proposal).collect())
+            },
+        )
+        .await
+    }
+}
+
+pub struct Proposal(ProposalModel);
+
+#[Object]
+impl Proposal {
+    async fn internal_id(&self) -> i32 {
+        self.0.internal_id
+    }
+
+    async fn proposal_id(&self) -> &str {
+        &self.0.proposal_id
+    }
+
+    async fn category_id(&self) -> &str {
+        &self.0.proposal_category.category_id
+    }
+
+    async fn category_name(&self) -> &str {
+        &self.0.proposal_category.category_name
+    }
+
+    async fn title(&self) -> &str {
+        &self.0.proposal_title
+    }
+
+    async fn summary(&self) -> &str {
+        &self.0.proposal_summary
+    }
+
+    async fn public_key(&self) -> &str {
+        &self.0.proposal_public_key
+    }
+
+    async fn funds(&self) -> i64 {
+        self.0.proposal_funds
+    }
+
+    async fn url(&self) -> &str {
+        &self.0.proposal_url
+    }
+
+    async fn files_url(&self) -> &str {
+        &self.0.proposal_files_url
+    }
+
+    async fn impact_score(&self) -> i64 {
+        self.0.proposal_impact_score
+    }
+
+    async fn proposer_name(&self) -> &str {
+        &self.0.proposer.proposer_name
+    }
+
+    async fn proposer_url(&self) -> &str {
+        &self.0.proposer.proposer_url
+    }
+
+    /// Identifier of the proposal on the chain, hex encoded
+    async fn chain_proposal_id(&self) -> String {
+        hex::encode(&self.0.chain_proposal_id)
+    }
+
+    async fn chain_voteplan_payload(&self) -> &str {
+        &self.0.chain_voteplan_payload
+    }
+
+    async fn fund_id(&self) -> i32 {
+        self.0.fund_id
+    }
+
+    async fn challenge_id(&self) -> i32 {
+        self.0.challenge_id
+    }
+
+    async fn reviews_count(&self) -> i32 {
+        self.0.reviews_count
+    }
+
+    async fn challenge(&self, context: &Context<'_>) -> FieldResult<Challenge> {
+        let pool = &shared_context(context).read().await.db_connection_pool;
+        Ok(Challenge(
+            query_challenge_by_id(self.0.challenge_id, pool).await?,
+        ))
+    }
+
+    #[graphql(complexity = "page_complexity(first, last, child_complexity)")]
+    async fn reviews(
+        &self,
+        context: &Context<'_>,
+        first: Option<i32>,
+        last: Option<i32>,
+        before: Option<String>,
+        after: Option<String>,
+    ) -> FieldResult<PageConnection<Review>> {
+        let pool = &shared_context(context).read().await.db_connection_pool;
+        // reviews are only stored for numerical proposal ids
+        let proposal_id = match self.0.proposal_id.parse() {
+            Ok(proposal_id) => proposal_id,
+            Err(_) => return paginate_items(Vec::new(), first, last, before, after).await,
+        };
+        let total_count = query_proposal_reviews_count(proposal_id, pool).await? as usize;
+        paginate(
+            total_count,
+            first,
+            last,
+            before,
+            after,
+            |offset, limit| async move {
+                let reviews =
+                    query_proposal_reviews_page(proposal_id, offset as i64, limit as i64, pool)
+                        .await?;
+                Ok(reviews.into_iter().map(Review).collect())
+            },
+        )
+        .await
+    }
+}
+
+#[derive(Enum, Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ReviewRanking {
+    Excellent,
+    Good,
+    FilteredOut,
+    NotReviewed,
+}
+
+impl From<ReviewRankingModel> for ReviewRanking {
+    fn from(ranking: ReviewRankingModel) -> Self {
+        match ranking {
+            ReviewRankingModel::Excellent => Self::Excellent,
+            ReviewRankingModel::Good => Self::Good,
+            ReviewRankingModel::FilteredOut => Self::FilteredOut,
+            ReviewRankingModel::NA => Self::NotReviewed,
+        }
+    }
+}
+
+pub struct Review(AdvisorReview);
+
+#[Object]
+impl Review {
+    async fn id(&self) -> i32 {
+        self.0.id
+    }
+
+    async fn proposal_id(&self) -> i32 {
+        self.0.proposal_id
+    }
+
+    async fn assessor(&self) -> &str {
+        &self.0.assessor
+    }
+
+    async fn impact_alignment_rating_given(&self) -> i32 {
+        self.0.impact_alignment_rating_given
+    }
+
+    async fn impact_alignment_note(&self) -> &str {
+        &self.0.impact_alignment_note
+    }
+
+    async fn feasibility_rating_given(&self) -> i32 {
+        self.0.feasibility_rating_given
+    }
+
+    async fn feasibility_note(&self) -> &str {
+        &self.0.feasibility_note
+    }
+
+    async fn auditability_rating_given(&self) -> i32 {
+        self.0.auditability_rating_given
+    }
+
+    async fn auditability_note(&self) -> &str {
+        &self.0.auditability_note
+    }
+
+    async fn ranking(&self) -> ReviewRanking {
+        self.0.ranking.into()
+    }
+}
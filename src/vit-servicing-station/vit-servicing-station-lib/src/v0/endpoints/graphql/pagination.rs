@@ -0,0 +1,190 @@
+use async_graphql::{
+    connection::{query, Connection, Edge, EmptyFields},
+    FieldResult, OutputType, SimpleObject,
+};
+use std::future::Future;
+
+/// Number of items returned when neither `first` nor `last` are given
+pub const DEFAULT_PAGE_SIZE: usize = 20;
+/// Maximum value accepted for `first` and `last`
+pub const MAX_PAGE_SIZE: usize = 100;
+
+#[derive(SimpleObject)]
+pub struct ConnectionFields {
+    pub total_count: u64,
+}
+
+pub type PageConnection<T> = Connection<usize, T, ConnectionFields, EmptyFields>;
+
+/// Complexity of a paginated field: the page size times the complexity of one item
+pub fn page_complexity(first: Option<i32>, last: Option<i32>, child_complexity: usize) -> usize {
+    let page_size = first
+        .or(last)
+        .map_or(DEFAULT_PAGE_SIZE, |size| size.max(0) as usize);
+    page_size
+        .min(MAX_PAGE_SIZE)
+        .saturating_mul(child_complexity)
+}
+
+/// Relay style pagination over `total_count` items, the cursor being the index of the item.
+///
+/// `load` retrieves the items of the page, given the index of the first one and their number.
+pub async fn paginate<T, F, Fut>(
+    total_count: usize,
+    first: Option<i32>,
+    last: Option<i32>,
+    before: Option<String>,
+    after: Option<String>,
+    load: F,
+) -> FieldResult<PageConnection<T>>
+where
+    T: OutputType,
+    F: FnOnce(usize, usize) -> Fut,
+    Fut: Future<Output = FieldResult<Vec<T>>>,
+{
+    query(
+        after,
+        before,
+        first,
+        last,
+        |after, before, first, last| async move {
+            if first.max(last).map_or(false, |size| size > MAX_PAGE_SIZE) {
+                return Err(format!("cannot request more than {} items", MAX_PAGE_SIZE).into());
+            }
+
+            let mut start = after.map_or(0, |after| after.saturating_add(1));
+            let mut end = before.unwrap_or(total_count).min(total_count);
+            if start > end {
+                start = end;
+            }
+
+            match (first, last) {
+                (Some(first), _) => end = end.min(start.saturating_add(first)),
+                (None, Some(last)) => start = start.max(end.saturating_sub(last)),
+                (None, None) => end = end.min(start.saturating_add(DEFAULT_PAGE_SIZE)),
+            }
+
+            let items = if start < end {
+                load(start, end - start).await?
+            } else {
+                Vec::new()
+            };
+
+            let mut connection = Connection::with_additional_fields(
+                start > 0,
+                end < total_count,
+                ConnectionFields {
+                    total_count: total_count as u64,
+                },
+            );
+            connection.edges.extend(
+                items
+                    .into_iter()
+                    .zip(start..)
+                    .map(|(item, index)| Edge::new(index, item)),
+            );
+
+            Ok::<_, async_graphql::Error>(connection)
+        },
+    )
+    .await
+}
+
+/// [`paginate`] over already loaded items
+pub async fn paginate_items<T: OutputType>(
+    items: Vec<T>,
+    first: Option<i32>,
+    last: Option<i32>,
+    before: Option<String>,
+    after: Option<String>,
+) -> FieldResult<PageConnection<T>> {
+    let total_count = items.len();
+    paginate(
+        total_count,
+        first,
+        last,
+        before,
+        after,
+        |offset, limit| async move { Ok(items.into_iter().skip(offset).take(limit).collect()) },
+    )
+    .await
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    async fn page(
+        first: Option<i32>,
+        last: Option<i32>,
+        before: Option<usize>,
+        after: Option<usize>,
+    ) -> (Vec<u32>, bool, bool) {
+        use async_graphql::connection::CursorType;
+
+        let connection = paginate_items(
+            (0..50).collect(),
+            first,
+            last,
+            before.map(|cursor| cursor.encode_cursor()),
+            after.map(|cursor| cursor.encode_cursor()),
+        )
+        .await
+        .unwrap();
+
+        (
+            connection.edges.iter().map(|edge| edge.node).collect(),
+            connection.has_previous_page,
+            connection.has_next_page,
+        )
+    }
+
+    #[tokio::test]
+    async fn pages() {
+        assert_eq!(
+            page(None, None, None, None).await,
+            ((0..20).collect(), false, true)
+        );
+        assert_eq!(
+            page(Some(5), None, None, Some(9)).await,
+            ((10..15).collect(), true, true)
+        );
+        assert_eq!(
+            page(None, Some(5), None, None).await,
+            ((45..50).collect(), true, false)
+        );
+        assert_eq!(
+            page(None, Some(5), Some(10), None).await,
+            ((5..10).collect(), true, true)
+        );
+    }
+
+    #[tokio::test]
+    async fn only_the_page_is_loaded() {
+        let connection = paginate(50, None, Some(5), None, None, |offset, limit| async move {
+            assert_eq!((offset, limit), (45, 5));
+            Ok((45..50u32).collect())
+        })
+        .await
+        .unwrap();
+        assert_eq!(connection.edges.len(), 5);
+        assert!(connection.has_previous_page);
+        assert!(!connection.has_next_page);
+
+        let connection = paginate::<u32, _, _>(0, None, Some(5), None, None, |_, _| async move {
+            panic!("nothing to load")
+        })
+        .await
+        .unwrap();
+        assert!(connection.edges.is_empty());
+    }
+
+    #[tokio::test]
+    async fn page_size_is_limited() {
+        assert!(paginate_items(vec![0u32], Some(101), None, None, None)
+            .await
+            .is_err());
+        assert_eq!(page_complexity(Some(1000), None, 2), MAX_PAGE_SIZE * 2);
+        assert_eq!(page_complexity(None, None, 2), DEFAULT_PAGE_SIZE * 2);
+    }
+}
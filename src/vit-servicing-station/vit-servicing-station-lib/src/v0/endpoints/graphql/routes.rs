@@ -0,0 +1,174 @@
+use super::{schema, QueryLimits, Schema};
+use crate::v0::context::SharedContext;
+use async_graphql::http::{playground_source, GraphQLPlaygroundConfig};
+use async_graphql_warp::GraphQLResponse;
+use std::convert::Infallible;
+use warp::filters::BoxedFilter;
+use warp::{http::Response as HttpResponse, Filter, Rejection, Reply};
+
+pub fn filter(
+    root: BoxedFilter<()>,
+    context: SharedContext,
+    limits: QueryLimits,
+) -> impl Filter<Extract = impl Reply, Error = Rejection> + Clone {
+    let graphql = warp::path::end()
+        .and(async_graphql_warp::graphql(schema(context, limits)))
+        .and_then(
+            |(schema, request): (Schema, async_graphql::Request)| async move {
+                Ok::<_, Infallible>(GraphQLResponse::from(schema.execute(request).await))
+            },
+        );
+
+    let playground = warp::path!("playground").and(warp::get()).map(|| {
+        HttpResponse::builder()
+            .header("content-type", "text/html")
+            .body(playground_source(GraphQLPlaygroundConfig::new(
+                "/api/v0/graphql",
+            )))
+    });
+
+    root.and(playground.or(graphql))
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::db::{
+        migrations as db_testing,
+        models::{
+            community_advisors_reviews::test as reviews_testing,
+            funds::{test as funds_testing, Fund},
+            proposals::test as proposals_testing,
+        },
+    };
+    use crate::v0::context::test::new_db_test_shared_context;
+    use serde_json::{json, Value};
+
+    async fn run_query(context: SharedContext, limits: QueryLimits, query: &str) -> Value {
+        let filter = filter(warp::path!("graphql" / ..).boxed(), context, limits);
+        let result = warp::test::request()
+            .method("POST")
+            .path("/graphql")
+            .json(&json!({ "query": query }))
+            .reply(&filter)
+            .await;
+        assert_eq!(result.status(), warp::http::StatusCode::OK);
+        serde_json::from_slice(result.body()).unwrap()
+    }
+
+    #[tokio::test]
+    async fn nested_fund_query() {
+        let shared_context = new_db_test_shared_context();
+        let pool = &shared_context.read().await.db_connection_pool;
+        db_testing::initialize_db_with_migration(&pool.get().unwrap()).unwrap();
+        let fund: Fund = funds_testing::get_test_fund(None);
+        funds_testing::populate_db_with_fund(&fund, pool);
+
+        let query = format!(
+            "{{ fund(id: {}) {{ name challenges(first: 1) {{ totalCount edges {{ node {{ id title proposals {{ totalCount }} }} }} }} }} }}",
+            fund.id
+        );
+        let response = run_query(shared_context.clone(), QueryLimits::default(), &query).await;
+
+        let challenge = &fund.challenges[0];
+        assert_eq!(
+            response,
+            json!({
+                "data": {
+                    "fund": {
+                        "name": fund.fund_name,
+                        "challenges": {
+                            "totalCount": 1,
+                            "edges": [{
+                                "node": {
+                                    "id": challenge.id,
+                                    "title": challenge.title,
+                                    "proposals": { "totalCount": 0 },
+                                },
+                            }],
+                        },
+                    },
+                },
+            })
+        );
+    }
+
+    #[tokio::test]
+    async fn pages_are_loaded_from_the_database() {
+        let shared_context = new_db_test_shared_context();
+        let pool = &shared_context.read().await.db_connection_pool;
+        db_testing::initialize_db_with_migration(&pool.get().unwrap()).unwrap();
+        for id in 1..=3 {
+            let mut fund = funds_testing::get_test_fund(Some(id));
+            fund.challenges.clear();
+            fund.chain_vote_plans.clear();
+            funds_testing::populate_db_with_fund(&fund, pool);
+        }
+        let (proposal, challenge) =
+            proposals_testing::add_test_proposal_and_challenge(1, &pool.get().unwrap());
+        for i in 0..3 {
+            let mut review = reviews_testing::get_test_advisor_review_with_proposal_id(1);
+            review.assessor = format!("assessor {i}");
+            reviews_testing::populate_db_with_advisor_review(&review, pool);
+        }
+
+        let query = format!(
+            r#"{{
+                funds(last: 2) {{ totalCount edges {{ node {{ id }} }} pageInfo {{ hasPreviousPage hasNextPage }} }}
+                challenge(id: {}) {{ proposals(votingGroup: "{}") {{ totalCount edges {{ node {{ proposalId chainProposalId }} }} }} }}
+                proposal(id: 1, votingGroup: "{}") {{ reviews(first: 1, after: "0") {{ totalCount edges {{ node {{ assessor }} }} }} }}
+            }}"#,
+            challenge.id, proposal.group_id, proposal.group_id,
+        );
+        let response = run_query(shared_context.clone(), QueryLimits::default(), &query).await;
+
+        assert_eq!(
+            response,
+            json!({
+                "data": {
+                    "funds": {
+                        "totalCount": 3,
+                        "edges": [{ "node": { "id": 2 } }, { "node": { "id": 3 } }],
+                        "pageInfo": { "hasPreviousPage": true, "hasNextPage": false },
+                    },
+                    "challenge": {
+                        "proposals": {
+                            "totalCount": 1,
+                            "edges": [{
+                                "node": {
+                                    "proposalId": proposal.proposal.proposal_id,
+                                    "chainProposalId": hex::encode(&proposal.proposal.chain_proposal_id),
+                                },
+                            }],
+                        },
+                    },
+                    "proposal": {
+                        "reviews": {
+                            "totalCount": 3,
+                            "edges": [{ "node": { "assessor": "assessor 1" } }],
+                        },
+                    },
+                },
+            })
+        );
+    }
+
+    #[tokio::test]
+    async fn complexity_is_limited() {
+        let shared_context = new_db_test_shared_context();
+        let pool = &shared_context.read().await.db_connection_pool;
+        db_testing::initialize_db_with_migration(&pool.get().unwrap()).unwrap();
+
+        let limits = QueryLimits {
+            complexity: 100,
+            ..Default::default()
+        };
+        let query = "{ funds(first: 10) { edges { node { challenges(first: 10) { edges { node { id title } } } } } } }";
+        let response = run_query(shared_context.clone(), limits, query).await;
+
+        assert!(response["errors"][0]["message"]
+            .as_str()
+            .unwrap()
+            .contains("complex"));
+    }
+}
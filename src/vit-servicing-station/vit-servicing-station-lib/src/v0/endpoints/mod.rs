@@ -2,6 +2,7 @@ mod advisor_reviews;
 pub mod challenges;
 mod funds;
 mod genesis;
+pub mod graphql;
 mod health;
 pub mod proposals;
 pub mod search;
@@ -11,14 +12,17 @@ mod votes;
 
 use crate::v0::context::SharedContext;
 
+use crate::db::models::api_tokens::ApiTokenScope;
 use crate::v0::api_token;
 use warp::filters::BoxedFilter;
+use warp::reply::Response;
 use warp::{Filter, Rejection, Reply};
 
 pub async fn filter(
     root: BoxedFilter<()>,
     context: SharedContext,
     enable_api_tokens: bool,
+    graphql_limits: graphql::QueryLimits,
 ) -> impl Filter<Extract = impl Reply, Error = Rejection> + Clone {
     // mount health endpoint
     let health_root = warp::path!("health" / ..);
@@ -54,6 +58,10 @@ pub async fn filter(
     let search_root = warp::path!("search" / ..);
     let search_filter = search::search_filter(search_root.boxed(), context.clone()).await;
 
+    let full_text_search_root = warp::path!("search_full_text" / ..);
+    let full_text_search_filter =
+        search::full_text_search_filter(full_text_search_root.boxed(), context.clone()).await;
+
     let search_count_root = warp::path!("search_count" / ..);
     let search_count_filter =
         search::search_count_filter(search_count_root.boxed(), context.clone()).await;
@@ -61,25 +69,64 @@ pub async fn filter(
     let snapshot_root = warp::path!("snapshot" / ..);
     let snapshot_rx_filter = snapshot::filter(snapshot_root.boxed(), context.clone());
 
+    let graphql_root = warp::path!("graphql" / ..);
+    let graphql_filter = graphql::filter(graphql_root.boxed(), context.clone(), graphql_limits);
+
+    let token_filter = |scope| {
+        let context = context.clone();
+        async move {
+            if enable_api_tokens {
+                api_token::api_token_filter(context, scope).await.boxed()
+            } else {
+                warp::any().boxed()
+            }
+        }
+    };
+
+    // the writes are recorded in the audit log once they are handled
+    let write_filter = |scope, handler: BoxedFilter<(Response,)>| {
+        let context = context.clone();
+        async move {
+            if enable_api_tokens {
+                api_token::api_token_write_filter(context, scope, handler)
+                    .await
+                    .boxed()
+            } else {
+                handler
+            }
+        }
+    };
+
+    // the path is matched before the token is checked, so that a token without the
+    // required scope is only reported for the endpoints it can't access
     let admin_filter = {
         let base = warp::path!("admin" / ..);
 
-        let snapshot_tx_filter =
-            warp::path!("snapshot" / ..).and(snapshot::update_filter(context.clone()).boxed());
-
-        let fund_filter = warp::path!("fund" / ..).and(funds::admin_filter(context.clone()));
+        let snapshot_tx_filter = warp::path!("snapshot" / ..).and(
+            write_filter(
+                ApiTokenScope::SnapshotWrite,
+                snapshot::update_filter(context.clone())
+                    .map(Reply::into_response)
+                    .boxed(),
+            )
+            .await,
+        );
+
+        let fund_filter = warp::path!("fund" / ..).and(
+            write_filter(
+                ApiTokenScope::Admin,
+                funds::admin_filter(context.clone())
+                    .map(Reply::into_response)
+                    .boxed(),
+            )
+            .await,
+        );
 
         base.and(snapshot_tx_filter.or(fund_filter))
     };
 
-    let api_token_filter = if enable_api_tokens {
-        api_token::api_token_filter(context).await.boxed()
-    } else {
-        warp::any().boxed()
-    };
-
     root.and(
-        api_token_filter.and(
+        admin_filter.or(token_filter(ApiTokenScope::ReadOnly).await.and(
             health_filter
                 .or(genesis_filter)
                 .or(chain_data_filter)
@@ -90,9 +137,10 @@ pub async fn filter(
                 .or(votes_filter)
                 .or(search_filter)
                 .or(search_count_filter)
+                .or(full_text_search_filter)
                 .or(snapshot_rx_filter)
-                .or(admin_filter),
-        ),
+                .or(graphql_filter),
+        )),
     )
     .boxed()
 }
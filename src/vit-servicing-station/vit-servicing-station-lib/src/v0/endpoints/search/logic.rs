@@ -1,11 +1,11 @@
 use warp::{Rejection, Reply};
 
 use crate::{
-    db::queries::search::{search_count_db, search_db},
+    db::queries::search::{full_text_search_db, search_count_db, search_db},
     v0::{context::SharedContext, result::HandlerResult},
 };
 
-use super::requests::{SearchCountQuery, SearchQuery};
+use super::requests::{FullTextSearchQuery, SearchCountQuery, SearchQuery};
 
 pub(super) async fn search(
     query: SearchQuery,
@@ -23,17 +23,30 @@ pub(super) async fn search_count(
     Ok(HandlerResult(search_count_db(query, &pool).await))
 }
 
+pub(super) async fn full_text_search(
+    query: FullTextSearchQuery,
+    ctx: SharedContext,
+) -> Result<impl Reply, Rejection> {
+    let pool = ctx.read().await.db_connection_pool.clone();
+    Ok(HandlerResult(full_text_search_db(query, &pool).await))
+}
+
 #[cfg(test)]
 mod test {
     use super::*;
     use crate::db::models::challenges::Challenge;
     use crate::db::models::proposals::test::add_test_proposal_and_challenge;
+    use crate::db::DbConnection;
     use crate::testing::filters::test_context;
+    use crate::testing::filters::test_context_for;
     use crate::testing::filters::ResponseBytesExt;
     use crate::v0::endpoints::search::requests::Column;
     use crate::v0::endpoints::search::requests::Constraint;
+    use crate::v0::endpoints::search::requests::FullTextSearchResponse;
+    use crate::v0::endpoints::search::requests::FullTextTable;
     use crate::v0::endpoints::search::requests::OrderBy;
     use crate::v0::endpoints::search::requests::Table;
+    use std::convert::Infallible;
     use warp::Filter;
 
     #[tokio::test]
@@ -308,4 +321,114 @@ mod test {
         let output = vec![challenge_2, challenge_3, challenge_4, challenge_5];
         assert_eq!(challenges, output);
     }
+
+    #[tokio::test]
+    async fn full_text_search_ranks_and_highlights() {
+        let (with_context, conn) = test_context().await;
+        check_full_text_search(with_context, conn).await;
+    }
+
+    /// same as above, searching the tsvector `search_document` columns
+    #[tokio::test]
+    #[ignore = "requires the postgres server of `new_db_test_shared_context_for`"]
+    async fn full_text_search_ranks_and_highlights_with_postgres() {
+        let (with_context, conn) = test_context_for("postgres").await;
+        check_full_text_search(with_context, conn).await;
+    }
+
+    async fn check_full_text_search(
+        with_context: impl Filter<Extract = (SharedContext,), Error = Infallible>
+            + Clone
+            + Send
+            + Sync
+            + 'static,
+        conn: DbConnection,
+    ) {
+        add_test_proposal_and_challenge(1, &conn);
+        add_test_proposal_and_challenge(2, &conn);
+        add_test_proposal_and_challenge(3, &conn);
+
+        let filter = warp::path!("search_full_text")
+            .and(warp::post())
+            .and(warp::body::json())
+            .and(with_context)
+            .and_then(full_text_search);
+
+        let body = serde_json::to_string(&FullTextSearchQuery {
+            query: "description 2".to_string(),
+            tables: vec![FullTextTable::Challenges],
+            fund_id: None,
+            challenge_id: None,
+            category: None,
+            limit: None,
+            offset: None,
+        })
+        .unwrap();
+
+        let response: FullTextSearchResponse = warp::test::request()
+            .method("POST")
+            .path("/search_full_text")
+            .body(body)
+            .reply(&filter)
+            .await
+            .as_json();
+
+        assert_eq!(response.total, 1);
+        assert_eq!(response.hits[0].table, FullTextTable::Challenges);
+        assert_eq!(response.hits[0].id, 2);
+        assert!(response.hits[0].snippet.contains("<b>description</b>"));
+        assert_eq!(response.facets.challenges[0].value, 2);
+
+        let body = serde_json::to_string(&FullTextSearchQuery {
+            query: "proposal number".to_string(),
+            tables: FullTextTable::all(),
+            fund_id: None,
+            challenge_id: None,
+            category: None,
+            limit: Some(2),
+            offset: None,
+        })
+        .unwrap();
+
+        let response: FullTextSearchResponse = warp::test::request()
+            .method("POST")
+            .path("/search_full_text")
+            .body(body)
+            .reply(&filter)
+            .await
+            .as_json();
+
+        assert_eq!(response.total, 3);
+        assert_eq!(response.hits.len(), 2);
+        assert!(response
+            .hits
+            .iter()
+            .all(|hit| hit.table == FullTextTable::Proposals));
+        assert!(response.hits[0].rank >= response.hits[1].rank);
+        assert_eq!(response.facets.challenges.len(), 3);
+
+        // the pages are cut from the same ranking
+        let body = serde_json::to_string(&FullTextSearchQuery {
+            query: "proposal number".to_string(),
+            tables: FullTextTable::all(),
+            fund_id: None,
+            challenge_id: None,
+            category: None,
+            limit: Some(1),
+            offset: Some(1),
+        })
+        .unwrap();
+
+        let page: FullTextSearchResponse = warp::test::request()
+            .method("POST")
+            .path("/search_full_text")
+            .body(body)
+            .reply(&filter)
+            .await
+            .as_json();
+
+        assert_eq!(page.total, 3);
+        assert_eq!(page.hits, vec![response.hits[1].clone()]);
+        assert_eq!(page.facets, response.facets);
+    }
 }
@@ -2,5 +2,6 @@ mod logic;
 pub mod requests;
 mod routes;
 
+pub use routes::full_text_search_filter;
 pub use routes::search_count_filter;
 pub use routes::search_filter;
@@ -65,6 +65,78 @@ pub enum Column {
     ImpactScore,
 }
 
+/// Full-text search over proposals, challenges and community advisors reviews, ranked by
+/// relevance
+#[derive(Debug, Clone, Deserialize, Serialize)]
+#[serde(rename_all = "kebab-case")]
+pub struct FullTextSearchQuery {
+    pub query: String,
+    #[serde(default = "FullTextTable::all")]
+    pub tables: Vec<FullTextTable>,
+    pub fund_id: Option<i32>,
+    pub challenge_id: Option<i32>,
+    pub category: Option<String>,
+    pub limit: Option<u64>,
+    pub offset: Option<u64>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize, Serialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum FullTextTable {
+    Proposals,
+    Challenges,
+    Reviews,
+}
+
+impl FullTextTable {
+    pub fn all() -> Vec<Self> {
+        vec![Self::Proposals, Self::Challenges, Self::Reviews]
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub struct FullTextSearchResponse {
+    /// number of matches, before `limit` and `offset` are applied
+    pub total: u64,
+    pub hits: Vec<SearchHit>,
+    pub facets: SearchFacets,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub struct SearchHit {
+    pub table: FullTextTable,
+    /// internal id of the proposal, id of the challenge, or id of the review
+    pub id: i32,
+    /// proposal the hit belongs to, for proposals and reviews
+    pub proposal_id: Option<String>,
+    pub title: String,
+    /// matching text, with the matched terms surrounded by `<b>` and `</b>`
+    pub snippet: String,
+    /// relevance of the hit, higher is better
+    pub rank: f64,
+    pub challenge_id: Option<i32>,
+    pub fund_id: Option<i32>,
+    pub category: Option<String>,
+}
+
+/// Number of hits for each challenge, fund and proposal category
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub struct SearchFacets {
+    pub challenges: Vec<FacetCount<i32>>,
+    pub funds: Vec<FacetCount<i32>>,
+    pub categories: Vec<FacetCount<String>>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub struct FacetCount<T> {
+    pub value: T,
+    pub count: u64,
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 #[serde(untagged)] // should serialize as if it is either a `Vec<Challenge>` or `Vec<FullProposalInfo>`
 pub enum SearchResponse {
@@ -97,6 +169,12 @@ mod tests {
         assert!(s.ends_with(']'));
     }
 
+    #[test]
+    fn full_text_search_defaults_to_all_tables() {
+        let query = from_value::<FullTextSearchQuery>(json!({"query": "cardano"})).unwrap();
+        assert_eq!(query.tables, FullTextTable::all());
+    }
+
     #[test]
     fn filters_and_orders_are_optional() {
         from_value::<SearchQuery>(json!({"table": "proposals"})).unwrap();
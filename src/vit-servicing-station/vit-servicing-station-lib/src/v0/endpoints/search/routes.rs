@@ -16,6 +16,18 @@ pub async fn search_filter(
     )
 }
 
+pub async fn full_text_search_filter(
+    root: BoxedFilter<()>,
+    context: SharedContext,
+) -> impl Filter<Extract = impl Reply, Error = Rejection> + Clone {
+    root.and(
+        warp::post()
+            .and(warp::body::json())
+            .and(warp::any().map(move || context.clone()))
+            .and_then(logic::full_text_search),
+    )
+}
+
 pub async fn search_count_filter(
     root: BoxedFilter<()>,
     context: SharedContext,
@@ -0,0 +1,308 @@
+use super::{Group, Tag};
+use crate::db::models::snapshot::{Contribution, SnapshotVersion, Voter};
+use jormungandr_lib::interfaces::Value;
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+
+/// Changes between two versions of the same snapshot
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SnapshotDiff {
+    pub tag: Tag,
+    pub from: SnapshotVersion,
+    pub to: SnapshotVersion,
+    /// Voters only present in the `to` version
+    pub added_voters: Vec<VoterEntry>,
+    /// Voters only present in the `from` version
+    pub removed_voters: Vec<VoterEntry>,
+    /// Voters present in both versions with a different voting power
+    pub voting_power_changes: Vec<VotingPowerChange>,
+    /// Stake keys whose delegation to a voter was added, removed or changed value
+    pub delegation_changes: Vec<DelegationChange>,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct VoterEntry {
+    pub voting_key: String,
+    pub voting_group: Group,
+    pub voting_power: Value,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct VotingPowerChange {
+    pub voting_key: String,
+    pub voting_group: Group,
+    pub old_voting_power: Value,
+    pub new_voting_power: Value,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct DelegationChange {
+    pub stake_public_key: String,
+    pub voting_key: String,
+    pub voting_group: Group,
+    /// `None` if the stake key did not delegate to the voter in the `from` version
+    pub old_value: Option<Value>,
+    /// `None` if the stake key does not delegate to the voter anymore in the `to` version
+    pub new_value: Option<Value>,
+}
+
+type VoterKey = (String, Group);
+type DelegationKey = (String, String, Group);
+
+/// Compare the voters and contributions of two snapshot versions. When `voting_key` is
+/// given only the changes concerning that voter are reported.
+pub fn diff_snapshots(
+    (from, from_voters, from_contributions): (SnapshotVersion, Vec<Voter>, Vec<Contribution>),
+    (to, to_voters, to_contributions): (SnapshotVersion, Vec<Voter>, Vec<Contribution>),
+    voting_key: Option<&str>,
+) -> SnapshotDiff {
+    let selected = |key: &str| voting_key.map_or(true, |voting_key| voting_key == key);
+
+    let index_voters = |voters: Vec<Voter>| -> BTreeMap<VoterKey, i64> {
+        voters
+            .into_iter()
+            .filter(|voter| selected(&voter.voting_key))
+            .map(|voter| ((voter.voting_key, voter.voting_group), voter.voting_power))
+            .collect()
+    };
+    let index_contributions = |contributions: Vec<Contribution>| -> BTreeMap<DelegationKey, i64> {
+        contributions
+            .into_iter()
+            .filter(|contribution| selected(&contribution.voting_key))
+            .map(|contribution| {
+                (
+                    (
+                        contribution.stake_public_key,
+                        contribution.voting_key,
+                        contribution.voting_group,
+                    ),
+                    contribution.value,
+                )
+            })
+            .collect()
+    };
+
+    let from_voters = index_voters(from_voters);
+    let mut to_voters = index_voters(to_voters);
+
+    let mut removed_voters = Vec::new();
+    let mut voting_power_changes = Vec::new();
+    for ((voting_key, voting_group), old_power) in from_voters {
+        match to_voters.remove(&(voting_key.clone(), voting_group.clone())) {
+            None => removed_voters.push(VoterEntry {
+                voting_key,
+                voting_group,
+                voting_power: to_value(old_power),
+            }),
+            Some(new_power) if new_power != old_power => {
+                voting_power_changes.push(VotingPowerChange {
+                    voting_key,
+                    voting_group,
+                    old_voting_power: to_value(old_power),
+                    new_voting_power: to_value(new_power),
+                })
+            }
+            Some(_) => {}
+        }
+    }
+    let added_voters = to_voters
+        .into_iter()
+        .map(|((voting_key, voting_group), power)| VoterEntry {
+            voting_key,
+            voting_group,
+            voting_power: to_value(power),
+        })
+        .collect();
+
+    let from_contributions = index_contributions(from_contributions);
+    let mut to_contributions = index_contributions(to_contributions);
+
+    let mut delegation_changes = Vec::new();
+    for (key, old_value) in from_contributions {
+        let new_value = to_contributions.remove(&key);
+        if new_value != Some(old_value) {
+            let (stake_public_key, voting_key, voting_group) = key;
+            delegation_changes.push(DelegationChange {
+                stake_public_key,
+                voting_key,
+                voting_group,
+                old_value: Some(to_value(old_value)),
+                new_value: new_value.map(to_value),
+            });
+        }
+    }
+    delegation_changes.extend(to_contributions.into_iter().map(
+        |((stake_public_key, voting_key, voting_group), new_value)| DelegationChange {
+            stake_public_key,
+            voting_key,
+            voting_group,
+            old_value: None,
+            new_value: Some(to_value(new_value)),
+        },
+    ));
+    delegation_changes.sort_by(|a, b| {
+        (&a.stake_public_key, &a.voting_key, &a.voting_group).cmp(&(
+            &b.stake_public_key,
+            &b.voting_key,
+            &b.voting_group,
+        ))
+    });
+
+    SnapshotDiff {
+        tag: to.tag.clone(),
+        from,
+        to,
+        added_voters,
+        removed_voters,
+        voting_power_changes,
+        delegation_changes,
+    }
+}
+
+fn to_value(value: i64) -> Value {
+    // stored values are converted from `u64` when the snapshot is updated
+    Value::from(value as u64)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const TAG: &str = "tag";
+
+    fn version(version: i32) -> SnapshotVersion {
+        SnapshotVersion {
+            tag: TAG.to_string(),
+            version,
+            last_updated: version.into(),
+        }
+    }
+
+    fn voter(voting_key: &str, voting_power: i64) -> Voter {
+        Voter {
+            voting_key: voting_key.to_string(),
+            voting_power,
+            voting_group: "direct".to_string(),
+            snapshot_tag: TAG.to_string(),
+            raw_stake: None,
+            transformed_power: None,
+        }
+    }
+
+    fn contribution(stake_public_key: &str, voting_key: &str, value: i64) -> Contribution {
+        Contribution {
+            stake_public_key: stake_public_key.to_string(),
+            reward_address: "address".to_string(),
+            value,
+            voting_key: voting_key.to_string(),
+            voting_group: "direct".to_string(),
+            snapshot_tag: TAG.to_string(),
+        }
+    }
+
+    fn example() -> SnapshotDiff {
+        diff_snapshots(
+            (
+                version(1),
+                vec![voter("a", 10), voter("b", 5), voter("c", 1)],
+                vec![
+                    contribution("stake_1", "a", 10),
+                    contribution("stake_2", "b", 5),
+                    contribution("stake_3", "c", 1),
+                ],
+            ),
+            (
+                version(2),
+                vec![voter("a", 15), voter("c", 1), voter("d", 2)],
+                vec![
+                    contribution("stake_1", "a", 10),
+                    contribution("stake_2", "a", 5),
+                    contribution("stake_3", "c", 1),
+                    contribution("stake_4", "d", 2),
+                ],
+            ),
+            None,
+        )
+    }
+
+    #[test]
+    fn voters_changes() {
+        let diff = example();
+
+        assert_eq!(diff.from, version(1));
+        assert_eq!(diff.to, version(2));
+        assert_eq!(
+            diff.added_voters,
+            vec![VoterEntry {
+                voting_key: "d".to_string(),
+                voting_group: "direct".to_string(),
+                voting_power: 2.into(),
+            }]
+        );
+        assert_eq!(
+            diff.removed_voters,
+            vec![VoterEntry {
+                voting_key: "b".to_string(),
+                voting_group: "direct".to_string(),
+                voting_power: 5.into(),
+            }]
+        );
+        assert_eq!(
+            diff.voting_power_changes,
+            vec![VotingPowerChange {
+                voting_key: "a".to_string(),
+                voting_group: "direct".to_string(),
+                old_voting_power: 10.into(),
+                new_voting_power: 15.into(),
+            }]
+        );
+    }
+
+    #[test]
+    fn delegation_changes() {
+        let changes = example()
+            .delegation_changes
+            .into_iter()
+            .map(|change| {
+                (
+                    change.stake_public_key,
+                    change.voting_key,
+                    change.old_value.map(u64::from),
+                    change.new_value.map(u64::from),
+                )
+            })
+            .collect::<Vec<_>>();
+
+        assert_eq!(
+            changes,
+            vec![
+                ("stake_2".to_string(), "a".to_string(), None, Some(5)),
+                ("stake_2".to_string(), "b".to_string(), Some(5), None),
+                ("stake_4".to_string(), "d".to_string(), None, Some(2)),
+            ]
+        );
+    }
+
+    #[test]
+    fn filter_by_voting_key() {
+        let diff = diff_snapshots(
+            (
+                version(1),
+                vec![voter("a", 10), voter("b", 5)],
+                vec![contribution("stake_2", "b", 5)],
+            ),
+            (
+                version(2),
+                vec![voter("a", 15)],
+                vec![contribution("stake_2", "a", 5)],
+            ),
+            Some("b"),
+        );
+
+        assert!(diff.added_voters.is_empty());
+        assert!(diff.voting_power_changes.is_empty());
+        assert_eq!(diff.removed_voters.len(), 1);
+        assert_eq!(diff.delegation_changes.len(), 1);
+        assert_eq!(diff.delegation_changes[0].voting_key, "b");
+    }
+}
@@ -2,7 +2,7 @@ use crate::v0::context::SharedContext;
 use crate::v0::result::HandlerResult;
 use jormungandr_lib::interfaces::Value;
 use serde::{Deserialize, Serialize};
-use snapshot_lib::{Fraction, RawSnapshot, SnapshotInfo};
+use snapshot_lib::{Fraction, RawSnapshot, SnapshotInfo, VotingPowerTransform};
 use warp::{Rejection, Reply};
 
 #[tracing::instrument(skip(context))]
@@ -32,6 +32,36 @@ pub async fn get_tags(context: SharedContext) -> Result<impl Reply, Rejection> {
     Ok(HandlerResult(super::get_tags(context).await))
 }
 
+#[tracing::instrument(skip(context))]
+pub async fn get_snapshot_versions(
+    tag: String,
+    context: SharedContext,
+) -> Result<impl Reply, Rejection> {
+    Ok(HandlerResult(
+        super::get_snapshot_versions(tag, context).await,
+    ))
+}
+
+/// Optional filters of a snapshot diff
+#[derive(Clone, Debug, Default, PartialEq, Eq, Deserialize, Serialize)]
+pub struct SnapshotDiffQuery {
+    /// Only report the changes concerning this voter
+    pub voting_key: Option<String>,
+}
+
+#[tracing::instrument(skip(context))]
+pub async fn get_snapshot_diff(
+    tag: String,
+    from: i32,
+    to: i32,
+    query: SnapshotDiffQuery,
+    context: SharedContext,
+) -> Result<impl Reply, Rejection> {
+    Ok(HandlerResult(
+        super::get_snapshot_diff(tag, from, to, query.voting_key, context).await,
+    ))
+}
+
 /// Snapshot information update with timestamp.
 #[derive(Clone, Debug, PartialEq, Eq, Deserialize, Serialize)]
 pub struct SnapshotInfoInput {
@@ -54,6 +84,9 @@ pub struct RawSnapshotInput {
     pub voting_power_cap: Fraction,
     pub direct_voters_group: Option<String>,
     pub representatives_group: Option<String>,
+    /// Mapping from stake to voting power, linear if not provided
+    #[serde(default)]
+    pub voting_power_transform: VotingPowerTransform,
 }
 
 #[tracing::instrument(skip(context))]
@@ -71,6 +104,7 @@ pub async fn put_raw_snapshot(
             input.voting_power_cap,
             input.direct_voters_group,
             input.representatives_group,
+            input.voting_power_transform,
             context,
         )
         .await,
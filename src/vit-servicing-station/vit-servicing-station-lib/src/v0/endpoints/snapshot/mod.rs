@@ -1,3 +1,4 @@
+mod diff;
 mod handlers;
 mod routes;
 
@@ -5,26 +6,29 @@ use crate::{
     db::{
         models::{
             self,
-            snapshot::{Contribution, Voter},
+            snapshot::{Contribution, SnapshotVersion, Voter},
         },
         queries::snapshot::{
-            batch_put_contributions, batch_put_voters, put_snapshot, query_all_snapshots,
+            batch_put_contributions, batch_put_voters, put_snapshot, put_snapshot_version,
+            query_all_snapshots, query_contributions_by_snapshot_tag_and_version,
             query_contributions_by_stake_public_key_and_snapshot_tag,
             query_contributions_by_voting_key_and_voter_group_and_snapshot_tag,
-            query_snapshot_by_tag, query_total_voting_power_by_voting_group_and_snapshot_tag,
-            query_voters_by_voting_key_and_snapshot_tag,
+            query_snapshot_by_tag, query_snapshot_version, query_snapshot_versions_by_tag,
+            query_total_voting_power_by_voting_group_and_snapshot_tag,
+            query_voters_by_snapshot_tag_and_version, query_voters_by_voting_key_and_snapshot_tag,
         },
     },
     v0::{context::SharedContext, errors::HandleError},
 };
-pub use handlers::{RawSnapshotInput, SnapshotInfoInput};
+pub use diff::{DelegationChange, SnapshotDiff, VoterEntry, VotingPowerChange};
+pub use handlers::{RawSnapshotInput, SnapshotDiffQuery, SnapshotInfoInput};
 use itertools::Itertools;
 use jormungandr_lib::interfaces::Value;
 pub use routes::{filter, update_filter};
 use serde::{Deserialize, Serialize};
 use snapshot_lib::{
     voting_group::{RepsVotersAssigner, DEFAULT_DIRECT_VOTER_GROUP, DEFAULT_REPRESENTATIVE_GROUP},
-    Fraction, RawSnapshot, Snapshot, SnapshotInfo,
+    Fraction, RawSnapshot, Snapshot, SnapshotInfo, VotingPowerTransform,
 };
 
 pub type Tag = String;
@@ -151,6 +155,45 @@ pub async fn get_tags(context: SharedContext) -> Result<Vec<Tag>, HandleError> {
         .collect())
 }
 
+/// All the versions of the snapshot, the oldest first
+#[tracing::instrument(skip(context))]
+pub async fn get_snapshot_versions(
+    tag: String,
+    context: SharedContext,
+) -> Result<Vec<SnapshotVersion>, HandleError> {
+    let pool = &context.read().await.db_connection_pool;
+
+    let versions = query_snapshot_versions_by_tag(tag.clone(), pool).await?;
+    if versions.is_empty() {
+        return Err(HandleError::NotFound(format!("snapshot {}", tag)));
+    }
+    Ok(versions)
+}
+
+#[tracing::instrument(skip(context))]
+pub async fn get_snapshot_diff(
+    tag: String,
+    from: i32,
+    to: i32,
+    voting_key: Option<String>,
+    context: SharedContext,
+) -> Result<SnapshotDiff, HandleError> {
+    let pool = &context.read().await.db_connection_pool;
+
+    let mut versions = Vec::with_capacity(2);
+    for version in [from, to] {
+        versions.push((
+            query_snapshot_version(tag.clone(), version, pool).await?,
+            query_voters_by_snapshot_tag_and_version(tag.clone(), version, pool).await?,
+            query_contributions_by_snapshot_tag_and_version(tag.clone(), version, pool).await?,
+        ));
+    }
+    let to = versions.pop().expect("both versions were loaded");
+    let from = versions.pop().expect("both versions were loaded");
+
+    Ok(diff::diff_snapshots(from, to, voting_key.as_deref()))
+}
+
 #[allow(clippy::too_many_arguments)]
 #[tracing::instrument(skip(snapshot, context))]
 pub async fn update_from_raw_snapshot(
@@ -161,16 +204,22 @@ pub async fn update_from_raw_snapshot(
     voting_power_cap: Fraction,
     direct_voters_group: Option<String>,
     representatives_group: Option<String>,
+    voting_power_transform: VotingPowerTransform,
     context: SharedContext,
 ) -> Result<(), HandleError> {
     let direct_voter = direct_voters_group.unwrap_or_else(|| DEFAULT_DIRECT_VOTER_GROUP.into());
     let representative =
         representatives_group.unwrap_or_else(|| DEFAULT_REPRESENTATIVE_GROUP.into());
     let assigner = RepsVotersAssigner::new(direct_voter, representative);
-    let snapshot =
-        Snapshot::from_raw_snapshot(snapshot, min_stake_threshold, voting_power_cap, &assigner)
-            .map_err(|e| HandleError::InternalError(e.to_string()))?
-            .to_full_snapshot_info();
+    let snapshot = Snapshot::from_raw_snapshot(
+        snapshot,
+        min_stake_threshold,
+        voting_power_cap,
+        &assigner,
+        &voting_power_transform,
+    )
+    .map_err(|e| HandleError::InternalError(e.to_string()))?
+    .to_full_snapshot_info();
 
     update_from_snapshot_info(tag, snapshot, update_timestamp, context).await
 }
@@ -182,32 +231,43 @@ pub fn convert_snapshot_to_contrib(
     let mut contributions = Vec::new();
     let mut voters = Vec::new();
     for entry in snapshot {
-        contributions.extend(entry.contributions.into_iter().map(|contribution| {
-            Contribution {
-                stake_public_key: contribution.stake_public_key,
-                reward_address: contribution.reward_address,
-                value: contribution
-                    .value
-                    .try_into()
-                    .expect("value should not exceed i64 limit"),
-                voting_key: entry.hir.voting_key.to_hex(),
-                voting_group: entry.hir.voting_group.clone(),
-                snapshot_tag: tag.clone(),
-            }
-        }));
+        let (raw_stake, transformed_power) = match entry.voting_power_audit {
+            Some(audit) => (
+                Some(to_i64(audit.raw_stake.into())),
+                Some(to_i64(audit.transformed_power.into())),
+            ),
+            None => (None, None),
+        };
+        contributions.extend(
+            entry
+                .contributions
+                .into_iter()
+                .map(|contribution| Contribution {
+                    stake_public_key: contribution.stake_public_key,
+                    reward_address: contribution.reward_address,
+                    value: to_i64(contribution.value),
+                    voting_key: entry.hir.voting_key.to_hex(),
+                    voting_group: entry.hir.voting_group.clone(),
+                    snapshot_tag: tag.clone(),
+                }),
+        );
 
         voters.push(Voter {
             voting_key: entry.hir.voting_key.to_hex(),
             voting_group: entry.hir.voting_group.clone(),
-            voting_power: Into::<u64>::into(entry.hir.voting_power)
-                .try_into()
-                .expect("value should not exceed i64 limit"),
+            voting_power: to_i64(entry.hir.voting_power.into()),
             snapshot_tag: tag.clone(),
+            raw_stake,
+            transformed_power,
         });
     }
     (voters, contributions)
 }
 
+fn to_i64(value: u64) -> i64 {
+    value.try_into().expect("value should not exceed i64 limit")
+}
+
 #[tracing::instrument(skip(snapshot, context))]
 pub async fn update_from_snapshot_info(
     tag: String,
@@ -217,32 +277,33 @@ pub async fn update_from_snapshot_info(
 ) -> Result<(), HandleError> {
     let pool = &context.read().await.db_connection_pool;
 
-    put_snapshot(
-        models::snapshot::Snapshot {
-            tag: tag.clone(),
-            last_updated: update_timestamp,
-        },
-        pool,
-    )?;
-
+    let snapshot_entry = models::snapshot::Snapshot {
+        tag: tag.clone(),
+        last_updated: update_timestamp,
+    };
     let (voters, contributions) = convert_snapshot_to_contrib(tag, snapshot);
 
     let db_conn = pool.get().map_err(HandleError::DatabaseError)?;
 
-    batch_put_voters(&voters, &db_conn)?;
-    batch_put_contributions(&contributions, &db_conn)?;
-
-    Ok(())
+    // the snapshot is replaced and its new version recorded all at once
+    db_conn.transaction(|| {
+        put_snapshot(snapshot_entry.clone(), &db_conn)?;
+        batch_put_voters(&voters, &db_conn)?;
+        batch_put_contributions(&contributions, &db_conn)?;
+        put_snapshot_version(&snapshot_entry, &voters, &contributions, &db_conn)?;
+        Ok(())
+    })
 }
 
 #[cfg(test)]
 mod test {
     use super::*;
     use crate::db::migrations::initialize_db_with_migration;
+    use crate::db::queries::snapshot::SNAPSHOT_VERSIONS_KEPT;
     use crate::v0::context::test::new_db_test_shared_context;
     use jormungandr_lib::crypto::account::Identifier;
     use snapshot_lib::registration::{Delegations, VotingRegistration};
-    use snapshot_lib::{KeyContribution, SnapshotInfo, VoterHIR};
+    use snapshot_lib::{KeyContribution, SnapshotInfo, VoterHIR, VotingPowerAudit};
     use tracing::Level;
     use warp::hyper::StatusCode;
     use warp::{Filter, Reply};
@@ -333,6 +394,7 @@ mod test {
                         voting_group,
                         voting_power,
                     },
+                    voting_power_audit: None,
                 },
             )
             .chain(
@@ -367,6 +429,7 @@ mod test {
                                 voting_group,
                                 voting_power,
                             },
+                            voting_power_audit: None,
                         },
                     ),
             )
@@ -409,6 +472,7 @@ mod test {
                         voting_group,
                         voting_power,
                     },
+                    voting_power_audit: None,
                 },
             )
             .collect::<Vec<_>>();
@@ -547,6 +611,7 @@ mod test {
                     voting_group: "GROUP1".into(),
                     voting_power: 1.into(),
                 },
+                voting_power_audit: None,
             },
             SnapshotInfo {
                 contributions: vec![],
@@ -555,6 +620,7 @@ mod test {
                     voting_group: "GROUP2".into(),
                     voting_power: 1.into(),
                 },
+                voting_power_audit: None,
             },
         ];
 
@@ -652,6 +718,233 @@ mod test {
         );
     }
 
+    #[tokio::test]
+    pub async fn test_snapshot_history_and_diff() {
+        const TAG: &str = "tag";
+
+        let context = new_db_test_shared_context();
+        let db_conn = &context.read().await.db_connection_pool.get().unwrap();
+        initialize_db_with_migration(db_conn).unwrap();
+
+        let keys = [
+            Identifier::from_hex(
+                "0000000000000000000000000000000000000000000000000000000000000000",
+            )
+            .unwrap(),
+            Identifier::from_hex(
+                "1111111111111111111111111111111111111111111111111111111111111111",
+            )
+            .unwrap(),
+        ];
+        let entry = |voting_key: &Identifier, stake_public_key: &str, value: u64| SnapshotInfo {
+            contributions: vec![KeyContribution {
+                reward_address: "address".to_string(),
+                stake_public_key: stake_public_key.to_string(),
+                value,
+            }],
+            hir: VoterHIR {
+                voting_key: voting_key.clone(),
+                voting_group: "direct".to_string(),
+                voting_power: value.into(),
+            },
+            voting_power_audit: None,
+        };
+
+        update_from_snapshot_info(
+            TAG.to_string(),
+            vec![entry(&keys[0], "stake_1", 1), entry(&keys[1], "stake_2", 2)],
+            0,
+            context.clone(),
+        )
+        .await
+        .unwrap();
+        update_from_snapshot_info(
+            TAG.to_string(),
+            vec![entry(&keys[0], "stake_1", 3)],
+            1,
+            context.clone(),
+        )
+        .await
+        .unwrap();
+
+        let versions = get_snapshot_versions(TAG.to_string(), context.clone())
+            .await
+            .unwrap();
+        assert_eq!(
+            versions
+                .iter()
+                .map(|version| (version.version, version.last_updated))
+                .collect::<Vec<_>>(),
+            vec![(1, 0), (2, 1)]
+        );
+        assert!(get_snapshot_versions("other".to_string(), context.clone())
+            .await
+            .is_err());
+
+        let diff = get_snapshot_diff(TAG.to_string(), 1, 2, None, context.clone())
+            .await
+            .unwrap();
+        assert!(diff.added_voters.is_empty());
+        assert_eq!(
+            diff.removed_voters,
+            vec![VoterEntry {
+                voting_key: keys[1].to_hex(),
+                voting_group: "direct".to_string(),
+                voting_power: 2.into(),
+            }]
+        );
+        assert_eq!(
+            diff.voting_power_changes,
+            vec![VotingPowerChange {
+                voting_key: keys[0].to_hex(),
+                voting_group: "direct".to_string(),
+                old_voting_power: 1.into(),
+                new_voting_power: 3.into(),
+            }]
+        );
+        assert_eq!(diff.delegation_changes.len(), 2);
+
+        let diff = get_snapshot_diff(
+            TAG.to_string(),
+            1,
+            2,
+            Some(keys[0].to_hex()),
+            context.clone(),
+        )
+        .await
+        .unwrap();
+        assert!(diff.removed_voters.is_empty());
+        assert_eq!(
+            diff.delegation_changes,
+            vec![DelegationChange {
+                stake_public_key: "stake_1".to_string(),
+                voting_key: keys[0].to_hex(),
+                voting_group: "direct".to_string(),
+                old_value: Some(1.into()),
+                new_value: Some(3.into()),
+            }]
+        );
+
+        assert!(get_snapshot_diff(TAG.to_string(), 1, 3, None, context)
+            .await
+            .is_err());
+    }
+
+    #[tokio::test]
+    pub async fn test_snapshot_stores_the_voting_power_audit() {
+        const TAG: &str = "tag";
+
+        let context = new_db_test_shared_context();
+        let db_conn = &context.read().await.db_connection_pool.get().unwrap();
+        initialize_db_with_migration(db_conn).unwrap();
+
+        let voting_key = Identifier::from_hex(
+            "0000000000000000000000000000000000000000000000000000000000000000",
+        )
+        .unwrap();
+        let entry = SnapshotInfo {
+            contributions: vec![],
+            hir: VoterHIR {
+                voting_key: voting_key.clone(),
+                voting_group: "direct".to_string(),
+                voting_power: 10.into(),
+            },
+            voting_power_audit: Some(VotingPowerAudit {
+                raw_stake: 400.into(),
+                transformed_power: 20.into(),
+            }),
+        };
+        update_from_snapshot_info(TAG.to_string(), vec![entry], 0, context.clone())
+            .await
+            .unwrap();
+
+        let pool = &context.read().await.db_connection_pool;
+        let expected = vec![Voter {
+            voting_key: voting_key.to_hex(),
+            voting_power: 10,
+            voting_group: "direct".to_string(),
+            snapshot_tag: TAG.to_string(),
+            raw_stake: Some(400),
+            transformed_power: Some(20),
+        }];
+        assert_eq!(
+            query_voters_by_voting_key_and_snapshot_tag(voting_key.to_hex(), TAG.to_string(), pool)
+                .await
+                .unwrap(),
+            expected
+        );
+        assert_eq!(
+            query_voters_by_snapshot_tag_and_version(TAG.to_string(), 1, pool)
+                .await
+                .unwrap(),
+            expected
+        );
+    }
+
+    #[tokio::test]
+    pub async fn test_snapshot_history_keeps_the_last_versions() {
+        const TAG: &str = "tag";
+
+        let context = new_db_test_shared_context();
+        let db_conn = &context.read().await.db_connection_pool.get().unwrap();
+        initialize_db_with_migration(db_conn).unwrap();
+
+        let voting_key = Identifier::from_hex(
+            "0000000000000000000000000000000000000000000000000000000000000000",
+        )
+        .unwrap();
+        let last_version = SNAPSHOT_VERSIONS_KEPT + 2;
+        for version in 1..=last_version {
+            let entry = SnapshotInfo {
+                contributions: vec![],
+                hir: VoterHIR {
+                    voting_key: voting_key.clone(),
+                    voting_group: "direct".to_string(),
+                    voting_power: (version as u64).into(),
+                },
+                voting_power_audit: None,
+            };
+            update_from_snapshot_info(
+                TAG.to_string(),
+                vec![entry],
+                version.into(),
+                context.clone(),
+            )
+            .await
+            .unwrap();
+        }
+
+        let versions = get_snapshot_versions(TAG.to_string(), context.clone())
+            .await
+            .unwrap();
+        assert_eq!(
+            versions
+                .iter()
+                .map(|version| version.version)
+                .collect::<Vec<_>>(),
+            (3..=last_version).collect::<Vec<_>>()
+        );
+
+        // the removed versions can't be compared anymore
+        assert!(
+            get_snapshot_diff(TAG.to_string(), 2, last_version, None, context.clone())
+                .await
+                .is_err()
+        );
+        let diff = get_snapshot_diff(TAG.to_string(), 3, last_version, None, context)
+            .await
+            .unwrap();
+        assert_eq!(
+            diff.voting_power_changes,
+            vec![VotingPowerChange {
+                voting_key: voting_key.to_hex(),
+                voting_group: "direct".to_string(),
+                old_voting_power: 3.into(),
+                new_voting_power: (last_version as u64).into(),
+            }]
+        );
+    }
+
     async fn get_voters_info<F>(
         tag: &str,
         voting_key: &str,
@@ -726,6 +1019,7 @@ mod test {
                         voting_group: GROUP1.to_string(),
                         voting_power: 1.into(),
                     },
+                    voting_power_audit: None,
                 },
                 SnapshotInfo {
                     contributions: vec![KeyContribution {
@@ -738,6 +1032,7 @@ mod test {
                         voting_group: GROUP2.to_string(),
                         voting_power: 2.into(),
                     },
+                    voting_power_audit: None,
                 },
             ],
             update_timestamp: 0,
@@ -752,6 +1047,7 @@ mod test {
                     voting_group: GROUP1.to_string(),
                     voting_power: 2.into(),
                 },
+                voting_power_audit: None,
             }],
             update_timestamp: 1,
         })
@@ -858,6 +1154,7 @@ mod test {
             voting_power_cap: 100.into(),
             direct_voters_group: None,
             representatives_group: None,
+            voting_power_transform: Default::default(),
         })
         .unwrap();
 
@@ -892,6 +1189,7 @@ mod test {
             voting_power_cap: 100.into(),
             direct_voters_group: None,
             representatives_group: None,
+            voting_power_transform: Default::default(),
         })
         .unwrap();
 
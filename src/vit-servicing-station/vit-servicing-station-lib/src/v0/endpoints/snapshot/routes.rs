@@ -1,7 +1,8 @@
 use crate::v0::context::SharedContext;
 
 use super::handlers::{
-    get_delegator_info, get_tags, get_voters_info, put_raw_snapshot, put_snapshot_info,
+    get_delegator_info, get_snapshot_diff, get_snapshot_versions, get_tags, get_voters_info,
+    put_raw_snapshot, put_snapshot_info, SnapshotDiffQuery,
 };
 use warp::filters::BoxedFilter;
 use warp::{Filter, Rejection, Reply};
@@ -22,12 +23,29 @@ pub fn filter(
         .and(with_context.clone())
         .and_then(get_delegator_info);
 
+    let get_snapshot_versions = warp::path!("history" / String)
+        .and(warp::get())
+        .and(with_context.clone())
+        .and_then(get_snapshot_versions);
+
+    let get_snapshot_diff = warp::path!("diff" / String / i32 / i32)
+        .and(warp::get())
+        .and(warp::query::<SnapshotDiffQuery>())
+        .and(with_context.clone())
+        .and_then(get_snapshot_diff);
+
     let get_tags = warp::path::end()
         .and(warp::get())
         .and(with_context)
         .and_then(get_tags);
 
-    root.and(get_voters_info.or(get_delegator_info).or(get_tags))
+    root.and(
+        get_voters_info
+            .or(get_delegator_info)
+            .or(get_snapshot_versions)
+            .or(get_snapshot_diff)
+            .or(get_tags),
+    )
 }
 
 pub fn update_filter(
@@ -12,6 +12,9 @@ pub enum HandleError {
     #[error("Unauthorized token")]
     UnauthorizedToken,
 
+    #[error("Token scope does not allow this request")]
+    ForbiddenToken,
+
     #[error("Internal error, cause: {0}")]
     InternalError(String),
 
@@ -22,6 +25,12 @@ pub enum HandleError {
     BadRequest(String),
 }
 
+impl From<diesel::result::Error> for HandleError {
+    fn from(e: diesel::result::Error) -> Self {
+        HandleError::InternalError(format!("Error executing request: {}", e))
+    }
+}
+
 impl HandleError {
     fn to_status_code(&self) -> warp::http::StatusCode {
         match self {
@@ -29,6 +38,7 @@ impl HandleError {
             HandleError::DatabaseError(_) => warp::http::StatusCode::SERVICE_UNAVAILABLE,
             HandleError::InternalError(_) => warp::http::StatusCode::INTERNAL_SERVER_ERROR,
             HandleError::UnauthorizedToken => warp::http::StatusCode::UNAUTHORIZED,
+            HandleError::ForbiddenToken => warp::http::StatusCode::FORBIDDEN,
             HandleError::InvalidHeader(_, _) => warp::http::StatusCode::BAD_REQUEST,
             HandleError::BadRequest(_) => warp::http::StatusCode::BAD_REQUEST,
         }
@@ -12,6 +12,7 @@ const V0_REQUEST_TRACE_NAME: &str = "v0_request";
 pub async fn filter(
     ctx: context::SharedContext,
     enable_api_tokens: bool,
+    graphql_limits: endpoints::graphql::QueryLimits,
 ) -> impl Filter<Extract = impl Reply, Error = Rejection> + Clone {
     let api_root = warp::path!("api" / ..);
 
@@ -45,7 +46,13 @@ pub async fn filter(
         span
     });
 
-    let v0 = endpoints::filter(v0_root.boxed(), ctx.clone(), enable_api_tokens).await;
+    let v0 = endpoints::filter(
+        v0_root.boxed(),
+        ctx.clone(),
+        enable_api_tokens,
+        graphql_limits,
+    )
+    .await;
 
     let service_version =
         endpoints::service_version::filter(service_version_root.boxed(), ctx).await;
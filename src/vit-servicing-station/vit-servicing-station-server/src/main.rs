@@ -212,7 +212,12 @@ async fn main() -> ApplicationExitCode {
 
     let context = v0::context::new_shared_context(db_pool, paths, &settings.service_version);
 
-    let app = v0::filter(context, settings.enable_api_tokens).await;
+    let app = v0::filter(
+        context,
+        settings.enable_api_tokens,
+        settings.graphql.query_limits(),
+    )
+    .await;
 
     info!(
         "Running server at {}, database located at {}",
@@ -8,7 +8,10 @@ use rand::RngCore;
 use rand_core::OsRng;
 use std::{collections::HashMap, iter};
 use time::{Duration, OffsetDateTime};
-use vit_servicing_station_lib::{db::models::api_tokens::ApiTokenData, v0::api_token::ApiToken};
+use vit_servicing_station_lib::{
+    db::models::api_tokens::{ApiTokenData, ApiTokenScope},
+    v0::api_token::ApiToken,
+};
 
 #[derive(Clone)]
 pub struct ArbitraryGenerator {
@@ -67,6 +70,8 @@ impl ArbitraryGenerator {
             token: ApiToken::new(data.clone()),
             creation_time: token_creation_time.unix_timestamp(),
             expire_time: toket_expiry_time.unix_timestamp(),
+            scope: ApiTokenScope::Admin,
+            revoked_time: None,
         };
         (
             base64::encode_config(data, base64::URL_SAFE_NO_PAD),
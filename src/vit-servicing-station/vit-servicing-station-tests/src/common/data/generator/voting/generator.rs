@@ -47,6 +47,7 @@ impl ValidVotePlanGenerator {
                 let payload_type = match vote_plan.payload_type() {
                     chain_impl_mockchain::vote::PayloadType::Public => "public",
                     chain_impl_mockchain::vote::PayloadType::Private => "private",
+                    chain_impl_mockchain::vote::PayloadType::PrivateWeighted => "private_weighted",
                 };
 
                 Voteplan {
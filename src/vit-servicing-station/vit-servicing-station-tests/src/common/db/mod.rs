@@ -50,6 +50,8 @@ impl<'a> DbInserter<'a> {
             api_tokens::dsl::token.eq(token_data.token.as_ref()),
             api_tokens::dsl::creation_time.eq(token_data.creation_time),
             api_tokens::dsl::expire_time.eq(token_data.expire_time),
+            api_tokens::dsl::scope.eq(token_data.scope.to_string()),
+            api_tokens::dsl::revoked_time.eq(token_data.revoked_time),
         );
 
         let conn = self.connection;
@@ -36,6 +36,7 @@ impl RawSnapshot {
                 voting_power_cap: 0.into(),
                 direct_voters_group: None,
                 representatives_group: None,
+                voting_power_transform: Default::default(),
             },
         }
     }
@@ -58,6 +59,7 @@ impl RawSnapshotExtension for RawSnapshot {
             self.content.min_stake_threshold,
             self.content.voting_power_cap,
             assigner,
+            &self.content.voting_power_transform,
         )?
         .to_full_snapshot_info())
     }
@@ -185,6 +187,7 @@ impl RawSnapshotBuilder {
                 voting_power_cap: self.voting_power_cap,
                 direct_voters_group: self.direct_voters_group,
                 representatives_group: self.representatives_group,
+                voting_power_transform: Default::default(),
             },
             tag: self.tag,
         }
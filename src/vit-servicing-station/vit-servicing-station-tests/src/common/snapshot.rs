@@ -102,6 +102,7 @@ impl SnapshotBuilder {
                                 .to_string(),
                             voting_power: rng.gen_range(1u64, 1_000u64).into(),
                         },
+                        voting_power_audit: None,
                     })
                 })
                 .take(voters_count)
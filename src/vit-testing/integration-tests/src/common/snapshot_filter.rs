@@ -6,7 +6,7 @@ use jormungandr_lib::interfaces::InitialUTxO;
 use jormungandr_lib::interfaces::Value;
 use snapshot_lib::registration::VotingRegistration;
 use snapshot_lib::voting_group::VotingGroupAssigner;
-use snapshot_lib::{RawSnapshot, Snapshot, VoterHIR};
+use snapshot_lib::{RawSnapshot, Snapshot, VoterHIR, VotingPowerTransform};
 use snapshot_trigger_service::client::SnapshotResult;
 use std::collections::HashSet;
 
@@ -80,6 +80,7 @@ impl SnapshotFilter {
                 voting_threshold,
                 cap,
                 voting_group_assigner,
+                &VotingPowerTransform::Linear,
             )
             .unwrap(),
         }
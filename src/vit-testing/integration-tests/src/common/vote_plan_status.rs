@@ -82,6 +82,7 @@ impl VotePlanStatusProvider for Block0Configuration {
                         payload: v.payload_type(),
                         voting_token: v.voting_token().clone().into(),
                         committee_member_keys: v.committee_public_keys().into(),
+                        committee_threshold: v.committee_threshold(),
                         proposals: v
                             .proposals()
                             .iter()
@@ -94,11 +95,14 @@ impl VotePlanStatusProvider for Block0Configuration {
                                     PayloadType::Public => Tally::Public {
                                         result: TallyResultLib::new(p.options().clone()).into(),
                                     },
-                                    PayloadType::Private => Tally::Private {
-                                        state: PrivateTallyState::Decrypted {
-                                            result: TallyResultLib::new(p.options().clone()).into(),
-                                        },
-                                    },
+                                    PayloadType::Private | PayloadType::PrivateWeighted => {
+                                        Tally::Private {
+                                            state: PrivateTallyState::Decrypted {
+                                                result: TallyResultLib::new(p.options().clone())
+                                                    .into(),
+                                            },
+                                        }
+                                    }
                                 },
                                 votes_cast: 0,
                             })
@@ -0,0 +1,228 @@
+use cardano_serialization_lib::Block;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+/// Extension of the files holding a single block
+pub const BLOCK_FILE_EXTENSION: &str = "block";
+
+/// Era tag of the first era after Byron in the `[era, block]` envelope of the hard fork combinator
+const SHELLEY_ERA_TAG: u8 = 2;
+
+/// Directory of blocks stored on disk, one CBOR encoded block per `.block` file.
+///
+/// Blocks are expected in the format understood by [`Block::from_bytes`] (i.e. post Byron era
+/// blocks), optionally wrapped in the `[era, block]` envelope of the hard fork combinator as served
+/// by the node. Byron era blocks are not supported. File names must sort in chain order, as the
+/// ones written by [`BlockFiles::write`].
+pub struct BlockFiles {
+    root: PathBuf,
+}
+
+impl BlockFiles {
+    /// Creates new instance pointing to `root` directory
+    #[must_use]
+    pub fn new(root: impl Into<PathBuf>) -> Self {
+        Self { root: root.into() }
+    }
+
+    /// Directory holding the block files
+    #[must_use]
+    pub fn root(&self) -> &Path {
+        &self.root
+    }
+
+    /// Iterates over the blocks of the directory in the order of their file names, reading one
+    /// block file at a time
+    ///
+    /// # Errors
+    ///
+    /// On I/O error while listing the directory. The iterator yields an error if a block file
+    /// can't be read or decoded, or if its block has a lower slot than the previous one
+    pub fn blocks(&self) -> Result<Blocks, Error> {
+        let mut paths = Vec::new();
+        for entry in fs::read_dir(&self.root)? {
+            let path = entry?.path();
+            if path
+                .extension()
+                .map_or(false, |ext| ext == BLOCK_FILE_EXTENSION)
+            {
+                paths.push(path);
+            }
+        }
+        paths.sort();
+
+        Ok(Blocks {
+            paths: paths.into_iter(),
+            last_slot: 0,
+        })
+    }
+
+    /// Reads all blocks from the directory, see [`BlockFiles::blocks`]
+    ///
+    /// # Errors
+    ///
+    /// On I/O error, if one of the block files can't be decoded or if the blocks are not in chain
+    /// order
+    pub fn read(&self) -> Result<Vec<Block>, Error> {
+        self.blocks()?.collect()
+    }
+
+    /// Writes `blocks` to the directory, creating it if needed. Each file is named after the slot
+    /// of its block and its position in `blocks`, so that the order is kept when read back.
+    ///
+    /// # Errors
+    ///
+    /// On I/O error
+    pub fn write(&self, blocks: &[Block]) -> Result<(), Error> {
+        fs::create_dir_all(&self.root)?;
+        for (position, block) in blocks.iter().enumerate() {
+            let file_name = format!(
+                "{:020}-{:010}.{}",
+                u64::from(block.header().header_body().slot_bignum()),
+                position,
+                BLOCK_FILE_EXTENSION
+            );
+            fs::write(self.root.join(file_name), block.to_bytes())?;
+        }
+        Ok(())
+    }
+}
+
+/// Iterator over the blocks of [`BlockFiles`]
+pub struct Blocks {
+    paths: std::vec::IntoIter<PathBuf>,
+    last_slot: u64,
+}
+
+impl Iterator for Blocks {
+    type Item = Result<Block, Error>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let path = self.paths.next()?;
+        Some(self.read_block(path))
+    }
+}
+
+impl Blocks {
+    fn read_block(&mut self, path: PathBuf) -> Result<Block, Error> {
+        let block = decode_block(&path, fs::read(&path)?)?;
+        let slot = u64::from(block.header().header_body().slot_bignum());
+        if slot < self.last_slot {
+            return Err(Error::OutOfOrder {
+                path,
+                slot,
+                previous_slot: self.last_slot,
+            });
+        }
+        self.last_slot = slot;
+        Ok(block)
+    }
+}
+
+fn decode_block(path: &Path, mut bytes: Vec<u8>) -> Result<Block, Error> {
+    // a bare block is an array of 4 or 5 items, the envelope an array of 2
+    if let [0x82, era, ..] = bytes[..] {
+        if era < SHELLEY_ERA_TAG {
+            return Err(Error::ByronBlock {
+                path: path.to_path_buf(),
+            });
+        }
+        bytes.drain(..2);
+    }
+    Block::from_bytes(bytes).map_err(|e| Error::InvalidBlock {
+        path: path.to_path_buf(),
+        details: e.to_string(),
+    })
+}
+
+/// Block files error
+#[derive(thiserror::Error, Debug)]
+pub enum Error {
+    /// I/O related error
+    #[error(transparent)]
+    Io(#[from] std::io::Error),
+    /// Block file content is not a valid block
+    #[error("cannot decode block from '{path}': {details}")]
+    InvalidBlock {
+        /// path to block file
+        path: PathBuf,
+        /// decoding error
+        details: String,
+    },
+    /// Byron era blocks can't be decoded
+    #[error("'{path}' is a Byron era block, which is not supported")]
+    ByronBlock {
+        /// path to block file
+        path: PathBuf,
+    },
+    /// Block file names are not in chain order
+    #[error("block of '{path}' at slot {slot} comes after a block at slot {previous_slot}")]
+    OutOfOrder {
+        /// path to block file
+        path: PathBuf,
+        /// slot of the block
+        slot: u64,
+        /// slot of the previous block
+        previous_slot: u64,
+    },
+}
+
+#[cfg(test)]
+mod tests {
+    use super::Error;
+    use crate::{Block0, BlockFiles, CardanoWallet, Ledger};
+    use assert_fs::TempDir;
+    use cardano_serialization_lib::Block;
+
+    #[test]
+    fn write_read_bijection() {
+        let testing_directory = TempDir::new().unwrap();
+        let wallet = CardanoWallet::new(1_000);
+
+        let mut ledger = Ledger::new(Block0::default());
+        ledger.push_transaction(wallet.generate_direct_voting_registration(1));
+        ledger.mint_block().unwrap();
+        ledger.push_transaction(wallet.generate_voting_deregistration(2));
+        ledger.mint_block().unwrap();
+
+        let block_files = BlockFiles::new(testing_directory.path());
+        block_files.write(ledger.blockchain()).unwrap();
+        let blocks = block_files.read().unwrap();
+
+        assert_eq!(
+            blocks.iter().map(Block::to_bytes).collect::<Vec<_>>(),
+            ledger
+                .blockchain()
+                .iter()
+                .map(Block::to_bytes)
+                .collect::<Vec<_>>()
+        );
+    }
+
+    #[test]
+    fn era_envelope_is_unwrapped() {
+        let testing_directory = TempDir::new().unwrap();
+        let ledger = Ledger::new(Block0::default());
+        let block = &ledger.blockchain()[0];
+
+        // babbage era
+        let mut bytes = vec![0x82, 0x06];
+        bytes.extend(block.to_bytes());
+        std::fs::write(testing_directory.path().join("0.block"), bytes).unwrap();
+
+        let blocks = BlockFiles::new(testing_directory.path()).read().unwrap();
+        assert_eq!(blocks.len(), 1);
+        assert_eq!(blocks[0].to_bytes(), block.to_bytes());
+    }
+
+    #[test]
+    fn byron_blocks_are_rejected() {
+        let testing_directory = TempDir::new().unwrap();
+        std::fs::write(testing_directory.path().join("0.block"), [0x82, 0x01, 0x80]).unwrap();
+
+        assert!(matches!(
+            BlockFiles::new(testing_directory.path()).read(),
+            Err(Error::ByronBlock { .. })
+        ));
+    }
+}
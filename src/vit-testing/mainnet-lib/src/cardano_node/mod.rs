@@ -1,10 +1,12 @@
 mod block;
+mod chain_files;
 mod in_memory;
 mod ledger;
 mod settings;
 mod transaction;
 
 pub use block::{Block0, BlockBuilder};
+pub use chain_files::{BlockFiles, Blocks, Error as BlockFilesError, BLOCK_FILE_EXTENSION};
 pub use in_memory::InMemoryNode;
 pub use ledger::Ledger;
 pub use settings::Settings;
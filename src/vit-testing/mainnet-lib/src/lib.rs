@@ -31,13 +31,17 @@ mod exports {
         SharedInMemoryDbSync,
     };
     pub use crate::wallet::{
-        CardanoWallet, GeneralTransactionMetadataInfo, JsonConversionError,
-        RegistrationTransactionBuilder, METADATUM_1, METADATUM_2, METADATUM_3, METADATUM_4,
+        CardanoWallet, DeregistrationTransactionBuilder, GeneralTransactionMetadataInfo,
+        JsonConversionError, RegistrationTransactionBuilder, DEREGISTRATION_METADATA_IDX,
+        DEREGISTRATION_METADATA_LABEL, METADATUM_1, METADATUM_2, METADATUM_3, METADATUM_4,
         REGISTRATION_METADATA_IDX, REGISTRATION_METADATA_LABEL,
         REGISTRATION_METADATA_SIGNATURE_LABEL, REGISTRATION_SIGNATURE_METADATA_IDX,
     };
 
-    pub use crate::cardano_node::{Block0, BlockBuilder, TransactionBuilder};
+    pub use crate::cardano_node::{
+        Block0, BlockBuilder, BlockFiles, BlockFilesError, Blocks, TransactionBuilder,
+        BLOCK_FILE_EXTENSION,
+    };
     pub use crate::cardano_node::{InMemoryNode, Ledger, Settings};
     pub use crate::network::{
         wallet_state, Initials, MainnetNetworkBuilder, Parameters as SnapshotParameters,
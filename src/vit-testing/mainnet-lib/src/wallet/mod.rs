@@ -1,7 +1,8 @@
 mod registration;
 
 pub use crate::wallet::registration::{
-    GeneralTransactionMetadataInfo, JsonConversionError, RegistrationTransactionBuilder,
+    DeregistrationTransactionBuilder, GeneralTransactionMetadataInfo, JsonConversionError,
+    RegistrationTransactionBuilder, DEREGISTRATION_METADATA_IDX, DEREGISTRATION_METADATA_LABEL,
     METADATUM_1, METADATUM_2, METADATUM_3, METADATUM_4, REGISTRATION_METADATA_IDX,
     REGISTRATION_METADATA_LABEL, REGISTRATION_METADATA_SIGNATURE_LABEL,
     REGISTRATION_SIGNATURE_METADATA_IDX,
@@ -138,6 +139,15 @@ impl CardanoWallet {
         )
     }
 
+    /// Creates voting deregistration metadata according to [Cip-36](https://cips.cardano.org/cips/cip36/) on given absolut slot number,
+    /// which is used as nonce.
+    #[must_use]
+    pub fn generate_voting_deregistration(&self, slot_no: u64) -> Transaction {
+        DeregistrationTransactionBuilder::new(self)
+            .on(slot_no)
+            .build()
+    }
+
     /// current amount of ada
     #[must_use]
     pub fn stake(&self) -> u64 {
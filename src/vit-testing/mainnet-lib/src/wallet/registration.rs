@@ -18,10 +18,14 @@ lazy_static::lazy_static! {
    pub static ref REGISTRATION_METADATA_IDX: u32 = 61284u32;
     /// registration signature metadata index constant
     pub static ref REGISTRATION_SIGNATURE_METADATA_IDX: u32 = 61285u32;
+    /// deregistration metadata index constant
+    pub static ref DEREGISTRATION_METADATA_IDX: u32 = 61286u32;
     /// registration metadata constant
    pub static ref REGISTRATION_METADATA_LABEL: TransactionMetadatumLabel = TransactionMetadatumLabel::from(*REGISTRATION_METADATA_IDX);
     ///registration signature metadata constant
     pub static ref REGISTRATION_METADATA_SIGNATURE_LABEL: TransactionMetadatumLabel = TransactionMetadatumLabel::from(*REGISTRATION_SIGNATURE_METADATA_IDX);
+    /// deregistration metadata constant
+    pub static ref DEREGISTRATION_METADATA_LABEL: TransactionMetadatumLabel = TransactionMetadatumLabel::from(*DEREGISTRATION_METADATA_IDX);
     /// metadatum label 1 constant
    pub static ref METADATUM_1: TransactionMetadatum = TransactionMetadatum::new_int(&Int::new_i32(1));
     /// metadatum label 2 constant
@@ -156,6 +160,85 @@ impl<'a> RegistrationTransactionBuilder<'a> {
     }
 }
 
+/// Responsible for building [Cip-36](https://cips.cardano.org/cips/cip36/) deregistration
+/// transaction metadata
+pub struct DeregistrationTransactionBuilder<'a> {
+    wallet: &'a CardanoWallet,
+    nonce: u64,
+}
+
+impl<'a> DeregistrationTransactionBuilder<'a> {
+    /// Creates deregistration builder for given wallet
+    #[must_use]
+    pub fn new(wallet: &'a CardanoWallet) -> Self {
+        Self { wallet, nonce: 0 }
+    }
+
+    /// Defines slot number for deregistration transaction. This will be used as nonce
+    #[must_use]
+    pub fn on(mut self, slot_no: u64) -> Self {
+        self.nonce = slot_no;
+        self
+    }
+
+    /// Creates transaction metadata
+    ///
+    /// # Panics
+    ///
+    /// On metadata size overflow
+    #[must_use]
+    fn build_metadata(&self) -> GeneralTransactionMetadata {
+        let mut meta_map: MetadataMap = MetadataMap::new();
+
+        meta_map.insert(
+            &METADATUM_1,
+            &TransactionMetadatum::new_bytes(self.wallet.stake_public_key().as_bytes()).unwrap(),
+        );
+        meta_map.insert(
+            &METADATUM_2,
+            &TransactionMetadatum::new_int(&Int::new(&BigNum::from(self.nonce))),
+        );
+        meta_map.insert(
+            &METADATUM_3,
+            &TransactionMetadatum::new_int(&Int::new(&BigNum::zero())),
+        );
+
+        let mut metadata = GeneralTransactionMetadata::new();
+        metadata.insert(
+            &BigNum::from(*DEREGISTRATION_METADATA_IDX),
+            &TransactionMetadatum::new_map(&meta_map),
+        );
+
+        let meta_bytes = metadata.to_bytes();
+        let meta_bytes_hash = Blake2b256::new(&meta_bytes);
+        let signature = self.wallet.stake_key.sign(meta_bytes_hash.as_hash_bytes());
+
+        let mut meta_sign_map: MetadataMap = MetadataMap::new();
+
+        meta_sign_map.insert(
+            &METADATUM_1,
+            &TransactionMetadatum::new_bytes(signature.to_bytes()).unwrap(),
+        );
+
+        metadata.insert(
+            &BigNum::from(*REGISTRATION_SIGNATURE_METADATA_IDX),
+            &TransactionMetadatum::new_map(&meta_sign_map),
+        );
+        metadata
+    }
+
+    /// Builds transaction instance. Unlike registrations, the transaction doesn't move any stake
+    #[must_use]
+    pub fn build(self) -> Transaction {
+        let metadata = self.build_metadata();
+        TransactionBuilder::build_transaction_with_metadata(
+            &self.wallet.reward_address().to_address(),
+            0,
+            &metadata,
+        )
+    }
+}
+
 /// Metadata conversion error
 #[derive(thiserror::Error, Debug)]
 pub enum JsonConversionError {
@@ -69,6 +69,7 @@ impl OutputsExtension for Vec<Output> {
                 voting_power_cap: parameters.voting_power_cap,
                 direct_voters_group: parameters.direct_voters_group.clone(),
                 representatives_group: parameters.representatives_group,
+                voting_power_transform: Default::default(),
             },
         })
     }
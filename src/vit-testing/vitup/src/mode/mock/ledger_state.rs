@@ -353,6 +353,8 @@ fn is_fragment_valid(fragment: &Fragment) -> bool {
         Fragment::Evm(_) => false,
         Fragment::EvmMapping(ref tx) => is_transaction_valid(tx),
         Fragment::MintToken(ref tx) => is_transaction_valid(tx),
+        Fragment::BurnToken(ref tx) => is_transaction_valid(tx),
+        Fragment::VoteDelegation(ref tx) => is_transaction_valid(tx),
         Fragment::Transaction(ref tx) => is_transaction_valid(tx),
         Fragment::StakeDelegation(ref tx) => is_transaction_valid(tx),
         Fragment::OwnerStakeDelegation(ref tx) => is_transaction_valid(tx),
@@ -65,6 +65,7 @@ pub async fn put_raw_snapshot(
         input.min_stake_threshold,
         input.voting_power_cap,
         &assigner,
+        &input.voting_power_transform,
     )
     .map_err(|e| HandleError::InternalError(e.to_string()))?
     .to_full_snapshot_info();
@@ -35,6 +35,7 @@ impl VoterSnapshot {
                 parameters.min_stake_threshold,
                 parameters.voting_power_cap,
                 &|_vk: &Identifier| String::new(),
+                &snapshot.content.voting_power_transform,
             )?
             .to_full_snapshot_info();
 
@@ -46,6 +47,14 @@ impl VoterSnapshot {
                     voting_power: u64::from(voter_hir.voting_power) as i64,
                     voting_group: voter_hir.voting_group.to_string(),
                     snapshot_tag: initials.parameters.tag.to_string(),
+                    raw_stake: snapshot_info
+                        .voting_power_audit
+                        .as_ref()
+                        .map(|audit| u64::from(audit.raw_stake) as i64),
+                    transformed_power: snapshot_info
+                        .voting_power_audit
+                        .as_ref()
+                        .map(|audit| u64::from(audit.transformed_power) as i64),
                 });
 
                 snapshot_info.contributions.iter().for_each(|contribution| {
@@ -242,6 +251,8 @@ impl Arbitrary for VoterSnapshot {
                 voting_power: u64::from(drep.0.voting_power) as i64,
                 voting_group: drep.0.voting_group.to_string(),
                 snapshot_tag: tags[random % tags.len()].clone(),
+                raw_stake: None,
+                transformed_power: None,
             }));
 
             snapshot_voters.extend(voters.iter().map(|voter| Voter {
@@ -249,6 +260,8 @@ impl Arbitrary for VoterSnapshot {
                 voting_power: u64::from(voter.0.voting_power) as i64,
                 voting_group: voter.0.voting_group.to_string(),
                 snapshot_tag: tags[random % tags.len()].clone(),
+                raw_stake: None,
+                transformed_power: None,
             }));
 
             let mut contributions = vec![];
@@ -1,11 +1,18 @@
+use std::collections::BTreeMap;
+use std::path::{Path, PathBuf};
 use std::{fs::File, io::BufWriter};
 
 use clap::Parser;
+use color_eyre::eyre::bail;
 use color_eyre::Result;
 use mainnet_lib::InMemoryDbSync;
-use tracing::info;
+use serde::Serialize;
+use tracing::{info, warn};
 use voting_tools_rs::test_api::MockDbProvider;
-use voting_tools_rs::{voting_power, Args, DataProvider, Db, DbConfig, DryRunCommand};
+use voting_tools_rs::{
+    voting_power_from_checkpoint, voting_power_with_rejections, Args, ChainFiles, Checkpoint,
+    DataProvider, Db, DbConfig, DryRunCommand, Output, Rejection,
+};
 
 fn main() -> Result<()> {
     color_eyre::install()?;
@@ -17,9 +24,14 @@ fn main() -> Result<()> {
         db_user,
         db_host,
         db_pass,
+        chain_dir,
+        ledger_state,
         min_slot_no,
         max_slot_no,
+        checkpoint,
+        verify_checkpoint,
         out_file,
+        rejections_file,
         pretty,
         dry_run,
         ..
@@ -32,18 +44,118 @@ fn main() -> Result<()> {
         password: db_pass,
     };
 
-    let db = get_data_provider(db_config, dry_run)?;
+    let db: &dyn DataProvider = Box::leak(get_data_provider(
+        db_config,
+        chain_dir,
+        ledger_state,
+        dry_run,
+    )?);
+
+    let (outputs, rejections) = match (checkpoint, max_slot_no) {
+        (Some(checkpoint_file), Some(max_slot_no)) => {
+            let previous = match checkpoint_file.exists() {
+                true => Some(Checkpoint::load(&checkpoint_file)?),
+                false => None,
+            };
+            if let Some(previous) = &previous {
+                info!("resuming from checkpoint at slot {}", previous.slot().0);
+            }
+
+            let (outputs, rejections, checkpoint) = voting_power_from_checkpoint(
+                db,
+                previous,
+                min_slot_no,
+                max_slot_no,
+                testnet_magic,
+            )?;
 
-    let outputs = voting_power(Box::leak(db), min_slot_no, max_slot_no, testnet_magic)?;
+            if verify_checkpoint {
+                let (expected_outputs, expected_rejections) = voting_power_with_rejections(
+                    db,
+                    min_slot_no,
+                    Some(max_slot_no),
+                    testnet_magic,
+                )?;
+                verify(
+                    (&outputs, &rejections),
+                    (&expected_outputs, &expected_rejections),
+                )?;
+            }
+
+            checkpoint.save(&checkpoint_file)?;
+            (outputs, rejections)
+        }
+        _ => voting_power_with_rejections(db, min_slot_no, max_slot_no, testnet_magic)?,
+    };
 
     info!("calculated {} outputs", outputs.len());
+    log_rejections(&rejections);
+
+    write_json(&out_file, &outputs, pretty)?;
+    if let Some(rejections_file) = rejections_file {
+        write_json(&rejections_file, &rejections, pretty)?;
+    }
+
+    Ok(())
+}
+
+/// Compares an incremental snapshot to a full one
+fn verify<'a>(
+    (outputs, rejections): (&'a [Output], &[Rejection]),
+    (expected_outputs, expected_rejections): (&'a [Output], &[Rejection]),
+) -> Result<()> {
+    let by_tx_id = |outputs: &'a [Output]| {
+        outputs
+            .iter()
+            .map(|output| (output.tx_id, output))
+            .collect::<BTreeMap<_, _>>()
+    };
+    let (outputs, expected_outputs) = (by_tx_id(outputs), by_tx_id(expected_outputs));
+
+    let missing = expected_outputs
+        .iter()
+        .filter(|(tx_id, output)| outputs.get(*tx_id) != Some(*output))
+        .count();
+    let unexpected = outputs
+        .keys()
+        .filter(|tx_id| !expected_outputs.contains_key(*tx_id))
+        .count();
+
+    if missing > 0 || unexpected > 0 || rejections != expected_rejections {
+        warn!(
+            "{missing} outputs of the full snapshot are missing or differ in the incremental \
+             snapshot, which has {unexpected} other outputs"
+        );
+        bail!("incremental snapshot differs from the full snapshot");
+    }
+
+    info!("incremental snapshot matches the full snapshot");
+    Ok(())
+}
+
+fn log_rejections(rejections: &[Rejection]) {
+    let mut by_reason = BTreeMap::new();
+    for rejection in rejections {
+        *by_reason.entry(rejection.reason.kind()).or_insert(0) += 1;
+    }
+
+    info!("rejected {} registrations", rejections.len());
+    for (reason, count) in by_reason {
+        info!("  {reason}: {count}");
+    }
+}
 
-    let file = File::options().write(true).create(true).open(out_file)?;
+fn write_json(path: &Path, value: &impl Serialize, pretty: bool) -> Result<()> {
+    let file = File::options()
+        .write(true)
+        .create(true)
+        .truncate(true)
+        .open(path)?;
     let writer = BufWriter::new(file);
 
     match pretty {
-        true => serde_json::to_writer_pretty(writer, &outputs),
-        false => serde_json::to_writer(writer, &outputs),
+        true => serde_json::to_writer_pretty(writer, value),
+        false => serde_json::to_writer(writer, value),
     }?;
 
     Ok(())
@@ -51,9 +163,16 @@ fn main() -> Result<()> {
 
 fn get_data_provider(
     real_db_config: DbConfig,
+    chain_dir: Option<PathBuf>,
+    ledger_state: Option<PathBuf>,
     maybe_dry_run: Option<DryRunCommand>,
 ) -> Result<Box<dyn DataProvider>> {
-    if let Some(dry_run) = maybe_dry_run {
+    if let Some(chain_dir) = chain_dir {
+        Ok(Box::new(ChainFiles::load(
+            &chain_dir,
+            ledger_state.as_deref(),
+        )?))
+    } else if let Some(dry_run) = maybe_dry_run {
         match dry_run {
             DryRunCommand::DryRun { mock_json_file } => Ok(Box::new(MockDbProvider::from(
                 InMemoryDbSync::restore(mock_json_file)?,
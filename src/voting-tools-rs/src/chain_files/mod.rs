@@ -0,0 +1,479 @@
+use crate::data_provider::DataProvider;
+use crate::db::{process_deregistrations, process_registrations, MetadataRow};
+use crate::model::{Dereg, Reg, Rejection, SlotNo};
+use bigdecimal::BigDecimal;
+use cardano_serialization_lib::address::{Address, BaseAddress, RewardAddress};
+use cardano_serialization_lib::metadata::{
+    decode_metadatum_to_json_str, GeneralTransactionMetadata, MetadataJsonSchema,
+    TransactionMetadatumLabel,
+};
+use cardano_serialization_lib::utils::{hash_transaction, BigNum};
+use cardano_serialization_lib::{Block, TransactionBody};
+use color_eyre::eyre::{bail, Context, Result};
+use mainnet_lib::{
+    BlockFiles, DEREGISTRATION_METADATA_LABEL, REGISTRATION_METADATA_LABEL,
+    REGISTRATION_METADATA_SIGNATURE_LABEL,
+};
+use serde_json::Value;
+use std::collections::{HashMap, HashSet};
+use std::fs::File;
+use std::path::Path;
+use std::str::FromStr;
+
+/// Data provider reading the chain from files on disk, so that no db-sync instance is needed.
+///
+/// Registrations are read from a directory of block files (see [`BlockFiles`]), and are numbered
+/// by their position in the chain, which is used as their transaction id.
+///
+/// Stake values are taken from a ledger state dump if one is provided: a json object mapping
+/// stake addresses (hex or bech32) to their stake in lovelace. Otherwise they are computed by
+/// replaying the `UTxO` set of the blocks, which are then expected to start from genesis.
+#[derive(Debug, Default)]
+pub struct ChainFiles {
+    registrations: Vec<(SlotNo, MetadataRow)>,
+    deregistrations: Vec<(SlotNo, MetadataRow)>,
+    stakes: HashMap<String, BigDecimal>,
+    /// stake addresses spending or receiving outputs, unknown if the stakes come from a ledger
+    /// state dump
+    stake_changes: Option<Vec<(SlotNo, String)>>,
+}
+
+impl ChainFiles {
+    /// Loads the blocks stored in `blocks_dir`, and the stakes from `ledger_state` if provided
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the blocks or the ledger state can't be read, see
+    /// [`ChainFiles::from_blocks`]
+    pub fn load(blocks_dir: &Path, ledger_state: Option<&Path>) -> Result<Self> {
+        let read_error = || format!("failed to read blocks from {}", blocks_dir.display());
+        let blocks = BlockFiles::new(blocks_dir)
+            .blocks()
+            .with_context(read_error)?;
+        let stakes = ledger_state.map(read_ledger_state).transpose()?;
+
+        Self::from_blocks(blocks.map(|block| block.with_context(read_error)), stakes)
+    }
+
+    /// Indexes the registrations of `blocks`, which must be in chain order. If `stakes` is `None`,
+    /// they are computed from the `UTxO` set of `blocks`.
+    ///
+    /// The replay of the `UTxO` set only knows the outputs of `blocks`: the first block must be
+    /// the genesis block, and the funds of the genesis file (e.g. the `UTxOs` of the Byron era)
+    /// and the rewards of the stake addresses are not counted. Real chains need a ledger state
+    /// dump.
+    ///
+    /// # Errors
+    ///
+    /// Returns the error of `blocks`, or an error if `stakes` is `None` and the first block is not
+    /// the genesis block
+    pub fn from_blocks(
+        blocks: impl IntoIterator<Item = Result<Block>>,
+        stakes: Option<HashMap<String, BigDecimal>>,
+    ) -> Result<Self> {
+        let mut chain = Self::default();
+        let mut utxos = HashMap::new();
+        let mut stake_changes = Vec::new();
+        let mut unknown_inputs = 0;
+        let mut tx_id = 0;
+        let mut block_count = 0;
+
+        for block in blocks {
+            let block = block?;
+            let header = block.header().header_body();
+            if block_count == 0 && stakes.is_none() && header.prev_hash().is_some() {
+                bail!(
+                    "the first block (number {}) is not the genesis block, the stake values can't \
+                     be computed without a ledger state",
+                    header.block_number()
+                );
+            }
+            block_count += 1;
+
+            let slot = SlotNo(header.slot_bignum().into());
+            let bodies = block.transaction_bodies();
+            let auxiliary_data = block.auxiliary_data_set();
+            let invalid_transactions = block.invalid_transactions();
+
+            for i in 0..bodies.len() {
+                tx_id += 1;
+                let Ok(index) = u32::try_from(i) else {
+                    continue;
+                };
+                // failed phase-2 validation, only its collateral is spent
+                let valid = !invalid_transactions.contains(&index);
+
+                if stakes.is_none() {
+                    let changed = apply_transaction(&mut utxos, &bodies.get(i), valid);
+                    unknown_inputs += changed.unknown_inputs;
+                    stake_changes.extend(
+                        changed
+                            .stake_addresses
+                            .into_iter()
+                            .map(|address| (slot, address)),
+                    );
+                }
+
+                if !valid {
+                    continue;
+                }
+                if let Some(metadata) = auxiliary_data.get(index).and_then(|aux| aux.metadata()) {
+                    chain.index_metadata(tx_id, slot, &metadata);
+                }
+            }
+        }
+
+        debug!("loaded {block_count} blocks");
+        if unknown_inputs > 0 {
+            warn!(
+                "{unknown_inputs} spent outputs are not in the blocks, the stake values computed \
+                 from them are wrong"
+            );
+        }
+
+        match stakes {
+            Some(stakes) => chain.stakes = stakes,
+            None => {
+                chain.stakes = stakes_from_utxos(utxos.into_values());
+                chain.stake_changes = Some(stake_changes);
+            }
+        }
+        Ok(chain)
+    }
+
+    fn index_metadata(&mut self, tx_id: i64, slot: SlotNo, metadata: &GeneralTransactionMetadata) {
+        let signature = metadatum_json(metadata, &REGISTRATION_METADATA_SIGNATURE_LABEL);
+        let slot_no = i64::try_from(slot.0).ok();
+
+        if let Some(registration) = metadatum_json(metadata, &REGISTRATION_METADATA_LABEL) {
+            self.registrations.push((
+                slot,
+                (tx_id, Some(registration), signature.clone(), slot_no),
+            ));
+        }
+        if let Some(deregistration) = metadatum_json(metadata, &DEREGISTRATION_METADATA_LABEL) {
+            self.deregistrations
+                .push((slot, (tx_id, Some(deregistration), signature, slot_no)));
+        }
+    }
+}
+
+/// Rows of `rows` whose slot is within the inclusive bounds
+fn within_bounds(
+    rows: &[(SlotNo, MetadataRow)],
+    lower: Option<SlotNo>,
+    upper: Option<SlotNo>,
+) -> Vec<MetadataRow> {
+    let lower = lower.unwrap_or(SlotNo(0));
+    let upper = upper.unwrap_or(SlotNo(u64::MAX));
+    rows.iter()
+        .filter(|(slot, _)| lower <= *slot && *slot <= upper)
+        .map(|(_, row)| row.clone())
+        .collect()
+}
+
+/// Metadatum under `label` in the json format used by db-sync (bytes as `0x` prefixed hex strings)
+fn metadatum_json(
+    metadata: &GeneralTransactionMetadata,
+    label: &TransactionMetadatumLabel,
+) -> Option<Value> {
+    let metadatum = metadata.get(label)?;
+    // metadata which can't be represented as json is reported as malformed
+    let json = decode_metadatum_to_json_str(&metadatum, MetadataJsonSchema::BasicConversions)
+        .ok()
+        .and_then(|json| serde_json::from_str(&json).ok())
+        .unwrap_or(Value::Null);
+    Some(json)
+}
+
+type Utxos = HashMap<(Vec<u8>, u32), (Option<String>, BigNum)>;
+
+/// Outcome of [`apply_transaction`]
+struct AppliedTransaction {
+    /// stake addresses of the spent and created outputs
+    stake_addresses: Vec<String>,
+    /// number of spent outputs which are not in the `UTxO` set
+    unknown_inputs: usize,
+}
+
+/// Spends the inputs of `body` and adds its outputs to the `UTxO` set, or only spends its
+/// collateral and adds its collateral return if it is not `valid`.
+fn apply_transaction(utxos: &mut Utxos, body: &TransactionBody, valid: bool) -> AppliedTransaction {
+    let mut applied = AppliedTransaction {
+        stake_addresses: Vec::new(),
+        unknown_inputs: 0,
+    };
+
+    let inputs = if valid {
+        Some(body.inputs())
+    } else {
+        body.collateral()
+    };
+    if let Some(inputs) = inputs {
+        for i in 0..inputs.len() {
+            let input = inputs.get(i);
+            match utxos.remove(&(input.transaction_id().to_bytes(), input.index())) {
+                Some((Some(stake_address), _)) => applied.stake_addresses.push(stake_address),
+                Some((None, _)) => (),
+                None => applied.unknown_inputs += 1,
+            }
+        }
+    }
+
+    let outputs = body.outputs();
+    let outputs: Vec<_> = if valid {
+        (0..outputs.len()).map(|i| (i, outputs.get(i))).collect()
+    } else {
+        // the collateral return comes after the outputs
+        body.collateral_return()
+            .map(|output| (outputs.len(), output))
+            .into_iter()
+            .collect()
+    };
+
+    let tx_hash = hash_transaction(body).to_bytes();
+    for (i, output) in outputs {
+        let Ok(index) = u32::try_from(i) else {
+            continue;
+        };
+        let stake_address = stake_address(&output.address());
+        applied.stake_addresses.extend(stake_address.clone());
+        let coin = output.amount().coin();
+        // identical transactions can't be on a real chain, but happen in generated ones
+        utxos
+            .entry((tx_hash.clone(), index))
+            .and_modify(|(_, value)| {
+                if let Ok(sum) = value.checked_add(&coin) {
+                    *value = sum;
+                }
+            })
+            .or_insert((stake_address, coin));
+    }
+
+    applied
+}
+
+/// Hex of the reward address holding the stake of `address`, like the ones of
+/// [`crate::logic::get_stake_address`]
+fn stake_address(address: &Address) -> Option<String> {
+    if let Some(reward_address) = RewardAddress::from_address(address) {
+        return Some(reward_address.to_address().to_hex());
+    }
+    let base_address = BaseAddress::from_address(address)?;
+    let network_id = address.network_id().ok()?;
+    Some(
+        RewardAddress::new(network_id, &base_address.stake_cred())
+            .to_address()
+            .to_hex(),
+    )
+}
+
+fn stakes_from_utxos(
+    utxos: impl Iterator<Item = (Option<String>, BigNum)>,
+) -> HashMap<String, BigDecimal> {
+    let mut stakes = HashMap::new();
+    for (stake_address, value) in utxos {
+        let Some(stake_address) = stake_address else {
+            continue;
+        };
+        *stakes
+            .entry(stake_address)
+            .or_insert_with(|| BigDecimal::from(0)) += big_num_to_decimal(&value);
+    }
+    stakes
+}
+
+fn big_num_to_decimal(value: &BigNum) -> BigDecimal {
+    BigDecimal::from_str(&value.to_str()).unwrap_or_else(|_| BigDecimal::from(0))
+}
+
+/// Reads a json object mapping stake addresses, either hex or bech32 encoded, to lovelace
+fn read_ledger_state(path: &Path) -> Result<HashMap<String, BigDecimal>> {
+    let file = File::open(path)
+        .with_context(|| format!("failed to open ledger state {}", path.display()))?;
+    let ledger_state: HashMap<String, u64> = serde_json::from_reader(file)
+        .with_context(|| format!("failed to parse ledger state {}", path.display()))?;
+
+    ledger_state
+        .into_iter()
+        .map(|(stake_address, stake)| {
+            let stake_address = match Address::from_bech32(&stake_address) {
+                Ok(address) => address.to_hex(),
+                Err(_) => stake_address.trim_start_matches("0x").to_lowercase(),
+            };
+            Ok((stake_address, BigDecimal::from(stake)))
+        })
+        .collect()
+}
+
+impl DataProvider for ChainFiles {
+    fn vote_registrations(
+        &self,
+        lower: Option<SlotNo>,
+        upper: Option<SlotNo>,
+    ) -> Result<(Vec<Reg>, Vec<Rejection>)> {
+        Ok(process_registrations(within_bounds(
+            &self.registrations,
+            lower,
+            upper,
+        )))
+    }
+
+    fn vote_deregistrations(
+        &self,
+        lower: Option<SlotNo>,
+        upper: Option<SlotNo>,
+    ) -> Result<(Vec<Dereg>, Vec<Rejection>)> {
+        Ok(process_deregistrations(within_bounds(
+            &self.deregistrations,
+            lower,
+            upper,
+        )))
+    }
+
+    fn stake_values<'a>(&self, stake_addrs: &'a [String]) -> Result<HashMap<&'a str, BigDecimal>> {
+        Ok(stake_addrs
+            .iter()
+            .map(|addr| {
+                let stake = self
+                    .stakes
+                    .get(addr)
+                    .cloned()
+                    .unwrap_or_else(|| BigDecimal::from(0));
+                (addr.as_str(), stake)
+            })
+            .collect())
+    }
+
+    fn stake_addresses_changed(
+        &self,
+        lower: Option<SlotNo>,
+        upper: Option<SlotNo>,
+    ) -> Result<Option<HashSet<String>>> {
+        let lower = lower.unwrap_or(SlotNo(0));
+        let upper = upper.unwrap_or(SlotNo(u64::MAX));
+        Ok(self.stake_changes.as_ref().map(|stake_changes| {
+            stake_changes
+                .iter()
+                .filter(|(slot, _)| lower <= *slot && *slot <= upper)
+                .map(|(_, address)| address.clone())
+                .collect()
+        }))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_api::VerifiableSnapshotOutput;
+    use crate::{voting_power, voting_power_with_rejections, RejectionReason};
+    use assert_fs::TempDir;
+    use cardano_serialization_lib::metadata::TransactionMetadatum;
+    use cardano_serialization_lib::utils::Int;
+    use cardano_serialization_lib::Transaction;
+    use mainnet_lib::{Block0, CardanoWallet, Ledger};
+    use std::io::Write;
+
+    fn ledger_with(transactions: Vec<Transaction>) -> Ledger {
+        let mut ledger = Ledger::new(Block0::default());
+        ledger.push_transactions(transactions);
+        ledger.mint_block().unwrap();
+        ledger
+    }
+
+    #[test]
+    fn registrations_from_block_files() {
+        let stake = 10_000;
+        let alice_wallet = CardanoWallet::new(stake);
+        let bob_wallet = CardanoWallet::new(stake);
+        let ledger = ledger_with(vec![
+            alice_wallet.generate_direct_voting_registration(1),
+            bob_wallet.generate_direct_voting_registration(1),
+            bob_wallet.generate_voting_deregistration(2),
+        ]);
+
+        let testing_directory = TempDir::new().unwrap();
+        BlockFiles::new(testing_directory.path())
+            .write(ledger.blockchain())
+            .unwrap();
+        let chain = ChainFiles::load(testing_directory.path(), None).unwrap();
+
+        let (outputs, rejections) = voting_power_with_rejections(&chain, None, None, None).unwrap();
+
+        assert_eq!(outputs.len(), 1);
+        let assertions = outputs[0].assert();
+        assertions.stake_key(&alice_wallet.stake_public_key());
+        assertions.voting_power(stake);
+        assert!(chain
+            .stake_addresses_changed(None, None)
+            .unwrap()
+            .unwrap()
+            .contains(&alice_wallet.reward_address().to_address().to_hex()));
+
+        assert_eq!(rejections.len(), 1);
+        assert!(matches!(
+            rejections[0].reason,
+            RejectionReason::Deregistered { .. }
+        ));
+    }
+
+    #[test]
+    fn stakes_from_ledger_state() {
+        let alice_wallet = CardanoWallet::new(10_000);
+        let ledger = ledger_with(vec![alice_wallet.generate_direct_voting_registration(1)]);
+
+        let testing_directory = TempDir::new().unwrap();
+        let ledger_state = testing_directory.path().join("ledger_state.json");
+        let mut file = File::create(&ledger_state).unwrap();
+        write!(
+            file,
+            r#"{{ "{}": 42 }}"#,
+            alice_wallet
+                .reward_address()
+                .to_address()
+                .to_bech32(None)
+                .unwrap()
+        )
+        .unwrap();
+
+        let stakes = read_ledger_state(&ledger_state).unwrap();
+        let chain =
+            ChainFiles::from_blocks(ledger.blockchain().iter().cloned().map(Ok), Some(stakes))
+                .unwrap();
+        let outputs = voting_power(&chain, None, None, None).unwrap();
+        assert_eq!(chain.stake_addresses_changed(None, None).unwrap(), None);
+
+        assert_eq!(outputs.len(), 1);
+        outputs[0].assert().voting_power(42);
+    }
+
+    #[test]
+    fn replay_requires_the_genesis_block() {
+        let alice_wallet = CardanoWallet::new(10_000);
+        let ledger = ledger_with(vec![alice_wallet.generate_direct_voting_registration(1)]);
+        let blocks = || ledger.blockchain().iter().skip(1).cloned().map(Ok);
+
+        assert!(ChainFiles::from_blocks(blocks(), None).is_err());
+        assert!(ChainFiles::from_blocks(blocks(), Some(HashMap::new())).is_ok());
+    }
+
+    #[test]
+    fn malformed_registration_is_rejected() {
+        let mut metadata = GeneralTransactionMetadata::new();
+        metadata.insert(
+            &REGISTRATION_METADATA_LABEL,
+            &TransactionMetadatum::new_int(&Int::new_i32(1)),
+        );
+        let mut chain = ChainFiles::default();
+        chain.index_metadata(1, SlotNo(10), &metadata);
+
+        let (regs, rejections) = chain.vote_registrations(None, None).unwrap();
+        assert!(regs.is_empty());
+        assert_eq!(rejections.len(), 1);
+        assert!(chain
+            .vote_registrations(Some(SlotNo(11)), None)
+            .unwrap()
+            .1
+            .is_empty());
+    }
+}
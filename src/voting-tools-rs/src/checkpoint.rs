@@ -0,0 +1,287 @@
+use crate::data_provider::DataProvider;
+use crate::logic::{outputs, registration_events, registration_set, stake_addresses};
+use crate::model::{network_info, Dereg, Output, Reg, Rejection, SlotNo, TestnetMagic};
+use bigdecimal::BigDecimal;
+use color_eyre::eyre::{bail, Context, Result};
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, HashSet};
+use std::fs::{self, File};
+use std::io::{BufReader, BufWriter, IntoInnerError};
+use std::path::{Path, PathBuf};
+
+/// State of a snapshot at a slot, from which a later snapshot can be computed incrementally.
+///
+/// It holds every registration event up to the checkpoint slot, so that the CIP-36 lifecycle can
+/// be applied again with the newer events, and the stake value of every registered stake address.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Checkpoint {
+    min_slot: Option<SlotNo>,
+    testnet_magic: Option<TestnetMagic>,
+    slot: SlotNo,
+    registrations: Vec<Reg>,
+    deregistrations: Vec<Dereg>,
+    rejections: Vec<Rejection>,
+    stake_values: BTreeMap<String, BigDecimal>,
+}
+
+impl Checkpoint {
+    /// Last slot (inclusive) whose registrations are part of the checkpoint
+    #[must_use]
+    pub fn slot(&self) -> SlotNo {
+        self.slot
+    }
+
+    /// Reads a checkpoint from a json file
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the file can't be read or isn't a valid checkpoint
+    pub fn load(path: &Path) -> Result<Self> {
+        let file = File::open(path)
+            .with_context(|| format!("failed to open checkpoint {}", path.display()))?;
+        serde_json::from_reader(BufReader::new(file))
+            .with_context(|| format!("failed to parse checkpoint {}", path.display()))
+    }
+
+    /// Writes the checkpoint to a json file, replacing it if it exists
+    ///
+    /// The checkpoint is written to a temporary file next to `path` first, which is then renamed,
+    /// so that an interrupted write doesn't corrupt the previous checkpoint.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the file can't be written
+    pub fn save(&self, path: &Path) -> Result<()> {
+        let mut tmp_path = path.as_os_str().to_owned();
+        tmp_path.push(".tmp");
+        let tmp_path = PathBuf::from(tmp_path);
+
+        let file = File::create(&tmp_path)
+            .with_context(|| format!("failed to create checkpoint {}", tmp_path.display()))?;
+        let mut writer = BufWriter::new(file);
+        serde_json::to_writer(&mut writer, self)?;
+        let file = writer.into_inner().map_err(IntoInnerError::into_error)?;
+        file.sync_all()?;
+
+        fs::rename(&tmp_path, path)
+            .with_context(|| format!("failed to replace checkpoint {}", path.display()))?;
+        Ok(())
+    }
+}
+
+/// Calculate voting power info like [`crate::voting_power_with_rejections`], resuming from
+/// `checkpoint` if provided, and returns the checkpoint at `max_slot` along with the result.
+///
+/// Only the registrations after the checkpoint slot are queried, and only the stake values of the
+/// new stake addresses, or of the ones the data provider reports as changed since the checkpoint
+/// slot. This is only faster with a db-sync database ([`crate::Db`]), the other data providers
+/// hold the whole chain anyway.
+///
+/// # Errors
+///
+/// Returns an error if `checkpoint` was taken with other parameters or after `max_slot`, or on
+/// data provider error
+#[instrument(skip(checkpoint))]
+pub fn voting_power_from_checkpoint(
+    db: &dyn DataProvider,
+    checkpoint: Option<Checkpoint>,
+    min_slot: Option<SlotNo>,
+    max_slot: SlotNo,
+    testnet_magic: Option<TestnetMagic>,
+) -> Result<(Vec<Output>, Vec<Rejection>, Checkpoint)> {
+    let network_info = network_info(testnet_magic);
+
+    let (mut checkpoint, lower, changed) = match checkpoint {
+        Some(checkpoint) => {
+            if checkpoint.min_slot != min_slot || checkpoint.testnet_magic != testnet_magic {
+                bail!("checkpoint was taken with a different minimum slot or network");
+            }
+            if checkpoint.slot > max_slot {
+                bail!(
+                    "checkpoint slot {} is after the maximum slot {}",
+                    checkpoint.slot.0,
+                    max_slot.0
+                );
+            }
+
+            let lower = SlotNo(checkpoint.slot.0 + 1);
+            // stake values are queried at the tip of the chain, which may be after `max_slot`
+            let changed = db.stake_addresses_changed(Some(lower), None)?;
+            (checkpoint, Some(lower), changed)
+        }
+        None => {
+            let checkpoint = Checkpoint {
+                min_slot,
+                testnet_magic,
+                slot: max_slot,
+                registrations: Vec::new(),
+                deregistrations: Vec::new(),
+                rejections: Vec::new(),
+                stake_values: BTreeMap::new(),
+            };
+            (checkpoint, min_slot, None)
+        }
+    };
+
+    let (regs, deregs, rejections) = registration_events(db, lower, Some(max_slot))?;
+    debug!(
+        "resuming from slot {} with {} new registrations",
+        checkpoint.slot.0,
+        regs.len()
+    );
+    checkpoint.slot = max_slot;
+    checkpoint.registrations.extend(regs);
+    checkpoint.deregistrations.extend(deregs);
+    checkpoint.rejections.extend(rejections);
+
+    let (regs, rejections) = registration_set(
+        checkpoint.registrations.clone(),
+        checkpoint.deregistrations.clone(),
+        checkpoint.rejections.clone(),
+    );
+
+    let stake_addrs = stake_addresses(&regs, &network_info);
+    let outdated = stake_addrs
+        .iter()
+        .filter(|stake_addr| {
+            !checkpoint.stake_values.contains_key(*stake_addr)
+                || changed
+                    .as_ref()
+                    .map_or(true, |changed| changed.contains(*stake_addr))
+        })
+        .cloned()
+        .collect::<Vec<_>>();
+
+    debug!(
+        "querying {} out of {} stake values",
+        outdated.len(),
+        stake_addrs.len()
+    );
+
+    let values = db.stake_values(&outdated)?;
+    for (stake_addr, value) in values {
+        checkpoint
+            .stake_values
+            .insert(stake_addr.to_string(), value);
+    }
+    // addresses of registrations which are no longer in use
+    let stake_addrs = stake_addrs.into_iter().collect::<HashSet<_>>();
+    checkpoint
+        .stake_values
+        .retain(|stake_addr, _| stake_addrs.contains(stake_addr));
+
+    let (outputs, rejections) = outputs(regs, rejections, &network_info, |stake_addr| {
+        checkpoint.stake_values.get(stake_addr).cloned()
+    });
+
+    Ok((outputs, rejections, checkpoint))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_api::MockDbProvider;
+    use crate::voting_power_with_rejections;
+    use cardano_serialization_lib::{Block, Header, Transaction};
+    use mainnet_lib::{BlockBuilder, CardanoWallet, InMemoryDbSync};
+
+    /// Block holding `transactions` with the given block number, used as slot by the db sync mock
+    fn block(number: u32, transactions: &[Transaction]) -> Block {
+        let block = BlockBuilder::next_block(None, transactions);
+        Block::new(
+            &Header::new(
+                &BlockBuilder::block_header(number, None),
+                &block.header().body_signature(),
+            ),
+            &block.transaction_bodies(),
+            &block.transaction_witness_sets(),
+            &block.auxiliary_data_set(),
+            block.invalid_transactions(),
+        )
+    }
+
+    fn db_with_blocks(blocks: &[Block]) -> MockDbProvider {
+        let mut db_sync = InMemoryDbSync::default();
+        for block in blocks {
+            db_sync.on_block_propagation(block);
+        }
+        MockDbProvider::from(db_sync)
+    }
+
+    #[test]
+    fn incremental_snapshot_matches_full_snapshot() {
+        let stake = 10_000;
+        let alice_wallet = CardanoWallet::new(stake);
+        let bob_wallet = CardanoWallet::new(stake);
+        let clarice_wallet = CardanoWallet::new(stake);
+
+        let first = block(
+            1,
+            &[
+                alice_wallet.generate_direct_voting_registration(1),
+                bob_wallet.generate_direct_voting_registration(1),
+            ],
+        );
+        let second = block(
+            2,
+            &[
+                bob_wallet.generate_voting_deregistration(2),
+                clarice_wallet.generate_direct_voting_registration(2),
+            ],
+        );
+
+        let db = db_with_blocks(&[first.clone()]);
+        let (outputs, _, checkpoint) =
+            voting_power_from_checkpoint(&db, None, None, SlotNo(1), None).unwrap();
+        assert_eq!(outputs.len(), 2);
+        assert_eq!(checkpoint.slot(), SlotNo(1));
+
+        let db = db_with_blocks(&[first, second]);
+        let (outputs, rejections, checkpoint) =
+            voting_power_from_checkpoint(&db, Some(checkpoint), None, SlotNo(2), None).unwrap();
+
+        assert_eq!(outputs.len(), 2);
+        assert_eq!(
+            (outputs, rejections),
+            voting_power_with_rejections(&db, None, Some(SlotNo(2)), None).unwrap()
+        );
+        assert_eq!(checkpoint.slot(), SlotNo(2));
+        // bob's stake value is dropped along with his registration
+        assert_eq!(checkpoint.stake_values.len(), 2);
+    }
+
+    #[test]
+    fn checkpoint_must_match_parameters() {
+        let db = MockDbProvider::from(InMemoryDbSync::default());
+        let (_, _, checkpoint) =
+            voting_power_from_checkpoint(&db, None, None, SlotNo(10), None).unwrap();
+
+        assert!(
+            voting_power_from_checkpoint(&db, Some(checkpoint.clone()), None, SlotNo(5), None)
+                .is_err()
+        );
+        assert!(voting_power_from_checkpoint(
+            &db,
+            Some(checkpoint),
+            Some(SlotNo(1)),
+            SlotNo(20),
+            None
+        )
+        .is_err());
+    }
+
+    #[test]
+    fn save_replaces_the_checkpoint() {
+        let testing_directory = assert_fs::TempDir::new().unwrap();
+        let path = testing_directory.path().join("checkpoint.json");
+        let db = MockDbProvider::from(InMemoryDbSync::default());
+
+        for slot in [SlotNo(10), SlotNo(20)] {
+            let (_, _, checkpoint) =
+                voting_power_from_checkpoint(&db, None, None, slot, None).unwrap();
+            checkpoint.save(&path).unwrap();
+            assert_eq!(Checkpoint::load(&path).unwrap(), checkpoint);
+        }
+        assert_eq!(fs::read_dir(testing_directory.path()).unwrap().count(), 1);
+    }
+}
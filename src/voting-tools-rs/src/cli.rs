@@ -28,6 +28,19 @@ pub struct Args {
     #[clap(long)]
     pub db_pass: Option<DbPass>,
 
+    /// Directory of block files to read the chain from, instead of the cardano-db-sync database.
+    /// One CBOR encoded post Byron era block per `.block` file, bare or wrapped in the era envelope
+    /// of the node, with file names sorting in chain order. Takes precedence over the dry run mock
+    #[clap(long)]
+    pub chain_dir: Option<PathBuf>,
+
+    /// Ledger state dump (json object mapping stake addresses to lovelace) to read the stake values
+    /// from. If not provided, they are computed from the `UTxOs` of the blocks of `--chain-dir`,
+    /// which must then start from the genesis block, without the funds of the genesis file nor the
+    /// rewards
+    #[clap(long, requires = "chain_dir")]
+    pub ledger_state: Option<PathBuf>,
+
     /// Lower bound for slot number to be included in queries
     #[clap(long)]
     pub min_slot_no: Option<SlotNo>,
@@ -36,10 +49,25 @@ pub struct Args {
     #[clap(long)]
     pub max_slot_no: Option<SlotNo>,
 
+    /// Checkpoint file to resume the snapshot from, if it exists, which is then updated to
+    /// `--max-slot-no`. Only the registrations and stake values changed since the checkpoint are
+    /// queried, which only speeds up snapshots taken from a db-sync database
+    #[clap(long, requires = "max_slot_no")]
+    pub checkpoint: Option<PathBuf>,
+
+    /// Check that the snapshot resumed from the checkpoint is the same as a full snapshot. The
+    /// checkpoint isn't updated if they differ
+    #[clap(long, requires = "checkpoint")]
+    pub verify_checkpoint: bool,
+
     /// File to output the signed transaction to
     #[clap(long, short = 'o')]
     pub out_file: PathBuf,
 
+    /// File to output the rejected registrations to, along with the reason they were rejected
+    #[clap(long)]
+    pub rejections_file: Option<PathBuf>,
+
     /// Whether to pretty-print the json
     #[clap(long, short = 'p')]
     pub pretty: bool,
@@ -79,12 +107,21 @@ mod tests {
             "localhost",
             "--db-pass",
             "super secret password",
+            "--chain-dir",
+            "chain/path",
+            "--ledger-state",
+            "ledger/path",
             "--min-slot-no",
             "123",
             "--max-slot-no",
             "234",
+            "--checkpoint",
+            "checkpoint/path",
+            "--verify-checkpoint",
             "-o",
             "some/path",
+            "--rejections-file",
+            "rejections/path",
             "-p",
         ]);
 
@@ -96,21 +133,45 @@ mod tests {
                 db_user: "db_user".into(),
                 db_host: "localhost".into(),
                 db_pass: Some(DbPass::new("super secret password".to_string())),
+                chain_dir: Some("chain/path".into()),
+                ledger_state: Some("ledger/path".into()),
                 min_slot_no: Some(123.into()),
                 max_slot_no: Some(234.into()),
+                checkpoint: Some("checkpoint/path".into()),
+                verify_checkpoint: true,
                 out_file: "some/path".into(),
+                rejections_file: Some("rejections/path".into()),
                 dry_run: None,
                 pretty: true,
             }
         );
     }
 
+    #[test]
+    fn ledger_state_requires_chain_dir() {
+        assert!(
+            Args::try_parse_from(["binary_name", "-o", "some/path", "--ledger-state", "path"])
+                .is_err()
+        );
+    }
+
+    #[test]
+    fn checkpoint_requires_max_slot() {
+        assert!(
+            Args::try_parse_from(["binary_name", "-o", "some/path", "--checkpoint", "path"])
+                .is_err()
+        );
+    }
+
     #[test]
     fn can_parse_only_required_values() {
         let args = Args::parse_from(["binary_name", "-o", "some/path"]);
 
         assert_eq!(args.out_file, PathBuf::from("some/path"));
         assert_eq!(args.testnet_magic, None);
+        assert_eq!(args.chain_dir, None);
+        assert_eq!(args.checkpoint, None);
+        assert_eq!(args.rejections_file, None);
         assert_eq!(args.pretty, false);
     }
 }
@@ -1,19 +1,37 @@
-use crate::model::{Reg, SlotNo};
+use crate::model::{Dereg, Reg, Rejection, SlotNo};
 use bigdecimal::BigDecimal;
 use color_eyre::eyre::Result;
-use std::collections::HashMap;
+use std::collections::{HashMap, HashSet};
 use std::fmt::Debug;
 
 /// Abstraction trait over data provider for voting tools. This approach can allow various data sources
 /// for registration queries like standard db sync database or in memory one including mocks.
 pub trait DataProvider: Debug {
     /// Retrieves voter registration for optional bounds. They need to be expressed as absolute
-    /// slot numbers
+    /// slot numbers. Registrations which can't be parsed are returned as rejections.
     ///
     /// # Errors
     ///
     /// Returns error on reading data issue
-    fn vote_registrations(&self, lower: Option<SlotNo>, upper: Option<SlotNo>) -> Result<Vec<Reg>>;
+    fn vote_registrations(
+        &self,
+        lower: Option<SlotNo>,
+        upper: Option<SlotNo>,
+    ) -> Result<(Vec<Reg>, Vec<Rejection>)>;
+
+    /// Retrieves CIP-36 voter deregistrations for optional bounds. They need to be expressed as
+    /// absolute slot numbers. Transaction ids must be comparable with the ones of
+    /// [`DataProvider::vote_registrations`], as they define the order of events within a nonce.
+    /// Deregistrations which can't be parsed are returned as rejections.
+    ///
+    /// # Errors
+    ///
+    /// Returns error on reading data issue
+    fn vote_deregistrations(
+        &self,
+        lower: Option<SlotNo>,
+        upper: Option<SlotNo>,
+    ) -> Result<(Vec<Dereg>, Vec<Rejection>)>;
 
     /// Retrieves stakes values for given array of addresses
     ///
@@ -21,4 +39,19 @@ pub trait DataProvider: Debug {
     ///
     /// Returns error on reading data issue
     fn stake_values<'a>(&self, stake_addrs: &'a [String]) -> Result<HashMap<&'a str, BigDecimal>>;
+
+    /// Retrieves the stake addresses whose stake value may have changed in the given bounds, used
+    /// to update the stake values of an incremental snapshot. `None` means the provider can't tell
+    /// and all the stake values need to be retrieved again.
+    ///
+    /// # Errors
+    ///
+    /// Returns error on reading data issue
+    fn stake_addresses_changed(
+        &self,
+        _lower: Option<SlotNo>,
+        _upper: Option<SlotNo>,
+    ) -> Result<Option<HashSet<String>>> {
+        Ok(None)
+    }
 }
@@ -16,6 +16,7 @@ mod schema;
 mod utils;
 
 pub use inner::{Conn, Db};
+pub(crate) use queries::{process_deregistrations, process_registrations, MetadataRow};
 use serde::Deserialize;
 
 /// Information required to connect to a database
@@ -1,26 +1,46 @@
 use crate::data_provider::DataProvider;
-use crate::model::Reg;
 use crate::model::SlotNo;
+use crate::model::{Dereg, Reg, Rejection};
 use crate::Db;
 use bigdecimal::BigDecimal;
-use std::collections::HashMap;
+use std::collections::{HashMap, HashSet};
 
 mod stake_value;
 mod vote_registrations;
 
+pub(crate) use vote_registrations::{
+    process as process_registrations, process_deregistrations, Row as MetadataRow,
+};
+
 impl DataProvider for Db {
     fn vote_registrations(
         &self,
         lower: Option<SlotNo>,
         upper: Option<SlotNo>,
-    ) -> color_eyre::Result<Vec<Reg>> {
+    ) -> color_eyre::Result<(Vec<Reg>, Vec<Rejection>)> {
         self.vote_registrations(lower, upper)
     }
 
+    fn vote_deregistrations(
+        &self,
+        lower: Option<SlotNo>,
+        upper: Option<SlotNo>,
+    ) -> color_eyre::Result<(Vec<Dereg>, Vec<Rejection>)> {
+        self.vote_deregistrations(lower, upper)
+    }
+
     fn stake_values<'a>(
         &self,
         stake_addrs: &'a [String],
     ) -> color_eyre::Result<HashMap<&'a str, BigDecimal>> {
         self.stake_values(stake_addrs)
     }
+
+    fn stake_addresses_changed(
+        &self,
+        lower: Option<SlotNo>,
+        upper: Option<SlotNo>,
+    ) -> color_eyre::Result<Option<HashSet<String>>> {
+        self.stake_addresses_changed(lower, upper).map(Some)
+    }
 }
@@ -1,4 +1,4 @@
-use std::collections::HashMap;
+use std::collections::{HashMap, HashSet};
 
 use bigdecimal::BigDecimal;
 use color_eyre::{Report, Result};
@@ -6,6 +6,7 @@ use diesel::{BoolExpressionMethods, JoinOnDsl, NullableExpressionMethods, QueryD
 
 use crate::db::inner::DbQuery;
 use crate::db::Db;
+use crate::model::SlotNo;
 use diesel::sql_types::Text;
 use diesel::ExpressionMethods;
 
@@ -37,6 +38,64 @@ impl Db {
 
         Ok(result)
     }
+
+    /// Query the stake addresses which received or spent an output in the given slot range
+    ///
+    /// 'lower' is an optional inclusive lower bound. If `None`, `0` is used instead.
+    /// 'upper' is an optional inclusive upper bound. If `None`, `i64::MAX` is used instead.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if either of `lower` or `upper` doesn't fit in an `i64`
+    #[instrument]
+    pub fn stake_addresses_changed(
+        &self,
+        lower: Option<SlotNo>,
+        upper: Option<SlotNo>,
+    ) -> Result<HashSet<String>> {
+        let lower = lower.unwrap_or(SlotNo(0)).into_i64()?;
+        let upper = upper.unwrap_or(SlotNo(i64::MAX as u64)).into_i64()?;
+
+        let created = self.exec(move |conn| created_query(lower, upper).load(conn))?;
+        let spent = self.exec(move |conn| spent_query(lower, upper).load(conn))?;
+
+        Ok(created.into_iter().chain(spent).map(hex::encode).collect())
+    }
+}
+
+/// Stake addresses of the outputs created in the slot range
+fn created_query(lower: i64, upper: i64) -> impl DbQuery<'static, Vec<u8>> {
+    use crate::db::schema::{block, stake_address, tx, tx_out};
+
+    tx_out::table
+        .inner_join(tx::table.on(tx::id.eq(tx_out::tx_id)))
+        .inner_join(block::table.on(block::id.eq(tx::block_id)))
+        .inner_join(
+            stake_address::table.on(stake_address::id.nullable().eq(tx_out::stake_address_id)),
+        )
+        .filter(block::slot_no.ge(lower).and(block::slot_no.le(upper)))
+        .select(stake_address::hash_raw)
+        .distinct()
+}
+
+/// Stake addresses of the outputs spent in the slot range
+fn spent_query(lower: i64, upper: i64) -> impl DbQuery<'static, Vec<u8>> {
+    use crate::db::schema::{block, stake_address, tx, tx_in, tx_out};
+
+    tx_in::table
+        .inner_join(tx::table.on(tx::id.eq(tx_in::tx_in_id)))
+        .inner_join(block::table.on(block::id.eq(tx::block_id)))
+        .inner_join(
+            tx_out::table.on(tx_out::tx_id
+                .eq(tx_in::tx_out_id)
+                .and(tx_out::index.eq(tx_in::tx_out_index))),
+        )
+        .inner_join(
+            stake_address::table.on(stake_address::id.nullable().eq(tx_out::stake_address_id)),
+        )
+        .filter(block::slot_no.ge(lower).and(block::slot_no.le(upper)))
+        .select(stake_address::hash_raw)
+        .distinct()
 }
 
 fn query(stake_addr: &str) -> impl DbQuery<'_, BigDecimal> {
@@ -1,21 +1,24 @@
 use crate::{db::inner::DbQuery, Db};
 
 use crate::db::schema::{block, tx, tx_metadata};
-use crate::model::{Reg, SlotNo};
+use crate::model::{Dereg, Reg, RegoSignature, Rejection, RejectionReason, SlotNo, TxId};
 use bigdecimal::{BigDecimal, FromPrimitive};
-use color_eyre::eyre::Result;
+use color_eyre::eyre::{eyre, Context, Result};
 use diesel::RunQueryDsl;
 use diesel::{
-    BoolExpressionMethods, ExpressionMethods, JoinOnDsl, PgAnyJsonExpressionMethods,
-    PgJsonbExpressionMethods, QueryDsl,
+    BoolExpressionMethods, ExpressionMethods, JoinOnDsl, NullableExpressionMethods, QueryDsl,
 };
 use once_cell::sync::Lazy;
+use serde::de::DeserializeOwned;
 use serde_json::Value;
 
 static METADATA_KEY: Lazy<BigDecimal> = Lazy::new(|| BigDecimal::from_isize(61284).unwrap());
 static SIGNATURE_KEY: Lazy<BigDecimal> = Lazy::new(|| BigDecimal::from_isize(61285).unwrap());
+static DEREGISTRATION_KEY: Lazy<BigDecimal> = Lazy::new(|| BigDecimal::from_isize(61286).unwrap());
 
-type Row = (i64, Option<Value>, Option<Value>, Option<i64>);
+/// Transaction id, registration metadata, signature metadata and slot number of the block of a
+/// registration
+pub(crate) type Row = (i64, Option<Value>, Option<Value>, Option<i64>);
 
 impl Db {
     /// This query is detailed in ``src/db/design/vote_registration_query.md``
@@ -25,6 +28,8 @@ impl Db {
     ///
     /// Returns an error if either `lower` or `upper` is greater than `i64::MAX`
     ///
+    /// Rows whose metadata or signature can't be parsed are returned as rejections
+    ///
     /// # Errors
     ///
     /// Returns an error if either of `lower` or `upper` doesn't fit in an `i64`
@@ -32,33 +37,59 @@ impl Db {
         &self,
         lower: Option<SlotNo>,
         upper: Option<SlotNo>,
-    ) -> Result<Vec<Reg>> {
+    ) -> Result<(Vec<Reg>, Vec<Rejection>)> {
         let lower = lower.unwrap_or(SlotNo(0)).into_i64()?;
         let upper = upper.unwrap_or(SlotNo(i64::MAX as u64)).into_i64()?;
-        let q = query(lower, upper);
+        let q = query(&METADATA_KEY, lower, upper);
 
         let results = self.exec(move |conn| q.load(conn))?;
         let results = process(results);
 
         Ok(results)
     }
+
+    /// Same as [`Db::vote_registrations`], but for CIP-36 deregistrations (metadata label 61286)
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if either of `lower` or `upper` doesn't fit in an `i64`
+    pub fn vote_deregistrations(
+        &self,
+        lower: Option<SlotNo>,
+        upper: Option<SlotNo>,
+    ) -> Result<(Vec<Dereg>, Vec<Rejection>)> {
+        let lower = lower.unwrap_or(SlotNo(0)).into_i64()?;
+        let upper = upper.unwrap_or(SlotNo(i64::MAX as u64)).into_i64()?;
+        let q = query(&DEREGISTRATION_KEY, lower, upper);
+
+        let results = self.exec(move |conn| q.load(conn))?;
+        let results = process_deregistrations(results);
+
+        Ok(results)
+    }
 }
 
 /// This query doesn't exactly match the query in the doc. In particular, some filtering of invalid
 /// JSONs isn't performed, since it breaks something (not sure what, just get no rows back). For
-/// now, we'll just do the filtering in Rust
-fn query(lower: i64, upper: i64) -> impl DbQuery<'static, Row> {
+/// now, we'll just do the filtering in Rust.
+///
+/// All the registrations of a stake key are returned, the most recent one being selected in
+/// [`crate::voting_power`] according to CIP-36. The signature is left joined, so that the
+/// registrations without a valid signature are rejected by [`parse_rows`] instead of being
+/// silently skipped.
+fn query(metadata_key: &'static BigDecimal, lower: i64, upper: i64) -> impl DbQuery<'static, Row> {
     let (meta_table, sig_table) = alias!(tx_metadata as meta_table, tx_metadata as sig_table);
 
     let metadata = meta_table.field(tx_metadata::json);
-    let signature = sig_table.field(tx_metadata::json);
+    let signature = sig_table.field(tx_metadata::json).nullable();
 
     let tables = meta_table
         .inner_join(tx::table.on(tx::id.eq(meta_table.field(tx_metadata::tx_id))))
-        .inner_join(
+        .left_join(
             sig_table.on(sig_table
                 .field(tx_metadata::tx_id)
-                .eq(meta_table.field(tx_metadata::tx_id))),
+                .eq(meta_table.field(tx_metadata::tx_id))
+                .and(sig_table.field(tx_metadata::key).eq(&*SIGNATURE_KEY))),
         )
         .inner_join(block::table.on(block::id.eq(tx::block_id)));
 
@@ -69,34 +100,80 @@ fn query(lower: i64, upper: i64) -> impl DbQuery<'static, Row> {
         block::slot_no,
     );
 
-    let signature_keys_predicate = signature.has_all_keys(["1"].as_slice());
     let block_number_predicate = block::slot_no.ge(lower).and(block::slot_no.le(upper));
 
-    let metadata_2 = metadata.retrieve_as_object("2");
-
     tables
-        .filter(meta_table.field(tx_metadata::key).eq(&*METADATA_KEY))
-        .filter(sig_table.field(tx_metadata::key).eq(&*SIGNATURE_KEY))
-        .filter(signature_keys_predicate)
+        .filter(meta_table.field(tx_metadata::key).eq(metadata_key))
         .filter(block_number_predicate)
         .select(selection)
-        .distinct_on(metadata_2)
+        .order_by(meta_table.field(tx_metadata::tx_id))
 }
 
-fn process(rows: Vec<Row>) -> Vec<Reg> {
-    rows.into_iter()
-        .filter_map(|(tx_id, metadata, signature, _)| {
-            let tx_id = u64::try_from(tx_id).ok()?.into();
-            let metadata = serde_json::from_value(metadata?).ok()?;
-            let signature = serde_json::from_value(signature?).ok()?;
+pub(crate) fn process(rows: Vec<Row>) -> (Vec<Reg>, Vec<Rejection>) {
+    parse_rows(rows, "2", |tx_id, slot_no, metadata, signature| Reg {
+        tx_id,
+        slot_no,
+        metadata,
+        signature,
+    })
+}
 
-            Some(Reg {
+pub(crate) fn process_deregistrations(rows: Vec<Row>) -> (Vec<Dereg>, Vec<Rejection>) {
+    parse_rows(rows, "1", |tx_id, slot_no, metadata, signature| Dereg {
+        tx_id,
+        slot_no,
+        metadata,
+        signature,
+    })
+}
+
+/// Rows that can't be parsed are rejected, `stake_key` being the metadata key used to recover the
+/// stake public key of the rejected registration
+fn parse_rows<M: DeserializeOwned, T>(
+    rows: Vec<Row>,
+    stake_key: &str,
+    build: impl Fn(TxId, Option<SlotNo>, M, RegoSignature) -> T,
+) -> (Vec<T>, Vec<Rejection>) {
+    let mut parsed = Vec::new();
+    let mut rejections = Vec::new();
+
+    for (tx_id, metadata, signature, slot_no) in rows {
+        let Ok(tx_id) = u64::try_from(tx_id).map(TxId::from) else {
+            continue;
+        };
+        let slot_no = slot_no
+            .and_then(|slot_no| u64::try_from(slot_no).ok())
+            .map(SlotNo);
+
+        let parse = |metadata: Option<Value>, signature: Option<Value>| -> Result<_> {
+            let metadata =
+                serde_json::from_value(metadata.ok_or_else(|| eyre!("missing metadata"))?)
+                    .context("invalid metadata")?;
+            let signature =
+                serde_json::from_value(signature.ok_or_else(|| eyre!("missing signature"))?)
+                    .context("invalid signature")?;
+            Ok((metadata, signature))
+        };
+
+        match parse(metadata.clone(), signature) {
+            Ok((metadata, signature)) => {
+                parsed.push(build(tx_id, slot_no, metadata, signature));
+            }
+            Err(e) => rejections.push(Rejection {
                 tx_id,
-                metadata,
-                signature,
-            })
-        })
-        .collect()
+                stake_public_key: metadata
+                    .as_ref()
+                    .and_then(|metadata| metadata.get(stake_key)?.as_str())
+                    .map(Into::into),
+                slot: slot_no,
+                reason: RejectionReason::MalformedMetadata {
+                    error: format!("{e:#}"),
+                },
+            }),
+        }
+    }
+
+    (parsed, rejections)
 }
 
 #[cfg(test)]
@@ -122,29 +199,72 @@ mod tests {
 
     #[test]
     fn process_happy_path() {
-        let rows = vec![(1, Some(good_meta()), Some(good_sig()), None)];
+        let rows = vec![(1, Some(good_meta()), Some(good_sig()), Some(500))];
         let regs = vec![Reg {
             tx_id: 1.into(),
+            slot_no: Some(SlotNo(500)),
             metadata: from_value(good_meta()).unwrap(),
             signature: from_value(good_sig()).unwrap(),
         }];
 
-        assert_eq!(process(rows), regs);
+        assert_eq!(process(rows), (regs, Vec::new()));
     }
 
     #[test]
     fn filters_bad_rows() {
-        fn check(row: Row) {
-            assert!(process(vec![row]).is_empty());
+        fn check(row: Row, slot: Option<SlotNo>) {
+            let (regs, rejections) = process(vec![row]);
+            assert!(regs.is_empty());
+            assert_eq!(rejections.len(), 1);
+            assert!(matches!(
+                rejections[0].reason,
+                RejectionReason::MalformedMetadata { .. }
+            ));
+            // the slot of the block, not the nonce of the registration
+            assert_eq!(rejections[0].slot, slot);
         }
 
         // bad sig
-        check((1, Some(good_meta()), Some(json!("random json")), None));
+        check(
+            (1, Some(good_meta()), Some(json!("random json")), Some(500)),
+            Some(SlotNo(500)),
+        );
+
+        // missing sig
+        check((1, Some(good_meta()), None, Some(500)), Some(SlotNo(500)));
 
         // bad meta
-        check((1, Some(json!("random json")), Some(good_sig()), None));
+        check(
+            (1, Some(json!("random json")), Some(good_sig()), Some(500)),
+            Some(SlotNo(500)),
+        );
 
         // none
-        check((1, None, None, None));
+        check((1, None, None, None), None);
+    }
+
+    #[test]
+    fn process_deregistration() {
+        let meta = json!({
+            "1": "stakevkey",
+            "2": 123,
+        });
+        let rows = vec![
+            (1, Some(meta.clone()), Some(good_sig()), Some(500)),
+            (2, Some(good_meta()), Some(good_sig()), Some(600)),
+        ];
+        let deregs = vec![Dereg {
+            tx_id: 1.into(),
+            slot_no: Some(SlotNo(500)),
+            metadata: from_value(meta).unwrap(),
+            signature: from_value(good_sig()).unwrap(),
+        }];
+
+        let (parsed, rejections) = process_deregistrations(rows);
+        assert_eq!(parsed, deregs);
+        assert_eq!(rejections.len(), 1);
+        assert_eq!(rejections[0].tx_id, 2.into());
+        assert_eq!(rejections[0].stake_public_key, Some("legacy".into()));
+        assert_eq!(rejections[0].slot, Some(SlotNo(600)));
     }
 }
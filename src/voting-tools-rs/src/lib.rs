@@ -3,7 +3,8 @@
 //! Original Haskell repository is <https://github.com/input-output-hk/voting-tools>
 //!
 //! The queries themselves (as well as the details of the CLI) are different, but they should
-//! produce similar outputs. Malformed registrations are silently ignored.
+//! produce similar outputs. Malformed registrations are not part of the output, but can be listed
+//! with [`voting_power_with_rejections`].
 
 #![forbid(missing_docs)]
 #![warn(clippy::pedantic)]
@@ -19,6 +20,8 @@ extern crate tracing;
 #[macro_use]
 extern crate diesel;
 
+mod chain_files;
+mod checkpoint;
 mod cli;
 mod data_provider;
 mod db;
@@ -29,10 +32,12 @@ mod testing;
 // this export style forces us to be explicit about what is in the public API
 pub use exports::*;
 mod exports {
+    pub use crate::chain_files::ChainFiles;
+    pub use crate::checkpoint::{voting_power_from_checkpoint, Checkpoint};
     pub use crate::cli::{Args, DryRunCommand};
     pub use crate::data_provider::DataProvider;
     pub use crate::db::{Conn, Db, DbConfig};
-    pub use crate::logic::voting_power;
-    pub use crate::model::{Delegations, Output};
+    pub use crate::logic::{voting_power, voting_power_with_rejections};
+    pub use crate::model::{Delegations, Output, Rejection, RejectionReason};
     pub use crate::testing::*;
 }
@@ -1,4 +1,5 @@
 use crate::data_provider::DataProvider;
+use bigdecimal::BigDecimal;
 use cardano_serialization_lib::address::{Address, NetworkInfo, RewardAddress};
 use cardano_serialization_lib::chain_crypto::Blake2b256;
 use cardano_serialization_lib::crypto::Ed25519Signature;
@@ -10,13 +11,24 @@ use cardano_serialization_lib::{address::StakeCredential, crypto::PublicKey};
 use color_eyre::eyre::{bail, eyre};
 use color_eyre::eyre::{Context, Result};
 use microtype::Microtype;
+use std::collections::HashMap;
 
-use crate::model::{network_info, Delegations, Output, Reg, SlotNo, StakeVKey, TestnetMagic};
+use crate::model::{
+    network_info, Delegations, Dereg, Output, Reg, RegoSignature, Rejection, RejectionReason,
+    SlotNo, StakeVKey, TestnetMagic, TxId,
+};
+
+const REGISTRATION_LABEL: u32 = 61284;
+const DEREGISTRATION_LABEL: u32 = 61286;
 
 /// Calculate voting power info by querying a db-sync instance
 ///
 /// Invalid registrations are silently ignored (e.g. if they contain bad/null JSON metadata, if
-/// they have invalid signatures, etc).
+/// they have invalid signatures, etc). Use [`voting_power_with_rejections`] to get them as well.
+///
+/// Registrations follow the CIP-36 lifecycle: for each stake key and voting purpose, only the
+/// valid registration or deregistration with the highest nonce is taken into account. If it's a
+/// deregistration, the stake key doesn't appear in the output.
 ///
 /// If provided, `min_slot` and `max_slot` can  be used to constrain the time period to query. If
 /// `None` they default to:
@@ -28,52 +40,201 @@ use crate::model::{network_info, Delegations, Output, Reg, SlotNo, StakeVKey, Te
 /// # Errors
 ///
 /// Returns an error if either of `lower` or `upper` doesn't fit in an `i64`
-#[instrument]
 pub fn voting_power(
     db: &dyn DataProvider,
     min_slot: Option<SlotNo>,
     max_slot: Option<SlotNo>,
     testnet_magic: Option<TestnetMagic>,
 ) -> Result<Vec<Output>> {
+    voting_power_with_rejections(db, min_slot, max_slot, testnet_magic).map(|(outputs, _)| outputs)
+}
+
+/// Same as [`voting_power`], but also returns every registration and deregistration which is not
+/// part of the output, along with the reason it was rejected
+///
+/// # Errors
+///
+/// Returns an error if either of `lower` or `upper` doesn't fit in an `i64`
+#[instrument]
+pub fn voting_power_with_rejections(
+    db: &dyn DataProvider,
+    min_slot: Option<SlotNo>,
+    max_slot: Option<SlotNo>,
+    testnet_magic: Option<TestnetMagic>,
+) -> Result<(Vec<Output>, Vec<Rejection>)> {
     let network_info = network_info(testnet_magic);
-    let regs = db.vote_registrations(min_slot, max_slot)?;
+    let (regs, deregs, rejections) = registration_events(db, min_slot, max_slot)?;
+    let (regs, rejections) = registration_set(regs, deregs, rejections);
 
-    debug!("found {} possible registrations", regs.len());
+    let stake_addrs = stake_addresses(&regs, &network_info);
+    let values = db.stake_values(&stake_addrs)?;
 
-    let stake_addrs = regs
-        .iter()
-        .filter_map(|reg| get_stake_address(&reg.metadata.stake_vkey, &network_info).ok())
-        .collect::<Vec<_>>();
+    Ok(outputs(regs, rejections, &network_info, |stake_addr| {
+        values.get(stake_addr).cloned()
+    }))
+}
 
-    let values = db.stake_values(&stake_addrs)?;
+/// Registrations and deregistrations in the slot range, along with the ones which can't be parsed
+pub(crate) fn registration_events(
+    db: &dyn DataProvider,
+    min_slot: Option<SlotNo>,
+    max_slot: Option<SlotNo>,
+) -> Result<(Vec<Reg>, Vec<Dereg>, Vec<Rejection>)> {
+    let (regs, mut rejections) = db.vote_registrations(min_slot, max_slot)?;
+    let (deregs, dereg_rejections) = db.vote_deregistrations(min_slot, max_slot)?;
+    rejections.extend(dereg_rejections);
 
-    let reg_voting_power = regs
-        .into_iter()
-        .filter_map(|reg| {
-            if let Err(e) = reg.check_valid() {
-                warn!("invalid reg on tx: '{}': {e}", reg.tx_id);
-                return None;
-            }
+    debug!(
+        "found {} possible registrations and {} possible deregistrations",
+        regs.len(),
+        deregs.len()
+    );
+
+    Ok((regs, deregs, rejections))
+}
+
+/// Registrations in use after applying all the registration events, see [`latest_registrations`]
+pub(crate) fn registration_set(
+    regs: Vec<Reg>,
+    deregs: Vec<Dereg>,
+    mut rejections: Vec<Rejection>,
+) -> (Vec<Reg>, Vec<Rejection>) {
+    let (regs, lifecycle_rejections) = latest_registrations(regs, deregs);
+    rejections.extend(lifecycle_rejections);
+    (regs, rejections)
+}
+
+/// Stake addresses of `regs`, the ones which can't be computed are skipped
+pub(crate) fn stake_addresses(regs: &[Reg], network_info: &NetworkInfo) -> Vec<String> {
+    regs.iter()
+        .filter_map(|reg| get_stake_address(&reg.metadata.stake_vkey, network_info).ok())
+        .collect()
+}
+
+/// Voting power of each registration, given the stake value of its stake address.
+///
+/// Registrations without stake value are rejected, and rejections are sorted by transaction id.
+pub(crate) fn outputs(
+    regs: Vec<Reg>,
+    mut rejections: Vec<Rejection>,
+    network_info: &NetworkInfo,
+    stake_value: impl Fn(&str) -> Option<BigDecimal>,
+) -> (Vec<Output>, Vec<Rejection>) {
+    let mut reg_voting_power = Vec::with_capacity(regs.len());
+    for reg in regs {
+        let voting_power = get_stake_address(&reg.metadata.stake_vkey, network_info)
+            .ok()
+            .and_then(|stake_addr| stake_value(stake_addr.as_str()));
 
-            debug!("registration on tx '{}' is valid", reg.tx_id);
+        let Some(voting_power) = voting_power else {
+            rejections.push(reg.rejection(RejectionReason::UnknownStakeAddress));
+            continue;
+        };
 
-            let stake_addr = get_stake_address(&reg.metadata.stake_vkey, &network_info).ok()?;
-            let voting_power = values.get(&stake_addr as &str)?.clone();
+        reg_voting_power.push(Output {
+            tx_id: reg.tx_id,
+            delegations: reg.metadata.delegations.clone(),
+            rewards_address: reg.metadata.rewards_addr.clone(),
+            stake_public_key: reg.metadata.stake_vkey.convert(),
+            voting_purpose: reg.metadata.purpose,
+            voting_power,
+        });
+    }
 
-            let output = Output {
-                tx_id: reg.tx_id,
-                delegations: reg.metadata.delegations.clone(),
-                rewards_address: reg.metadata.rewards_addr.clone(),
-                stake_public_key: reg.metadata.stake_vkey.convert(),
-                voting_purpose: reg.metadata.purpose,
-                voting_power,
-            };
+    // the reason breaks ties, so that the order doesn't depend on the order of the events
+    rejections.sort_by(|a, b| (a.tx_id, a.reason.kind()).cmp(&(b.tx_id, b.reason.kind())));
 
-            Some(output)
-        })
-        .collect();
+    (reg_voting_power, rejections)
+}
+
+/// Keeps, for each stake key and voting purpose, the valid registration or deregistration with the
+/// highest nonce (transaction order breaks ties) and drops the stake keys that were deregistered.
+///
+/// The result is sorted by transaction id. Every other registration is rejected, as well as the
+/// invalid deregistrations.
+fn latest_registrations(regs: Vec<Reg>, deregs: Vec<Dereg>) -> (Vec<Reg>, Vec<Rejection>) {
+    enum Action {
+        Register(Reg),
+        Deregister(TxId),
+    }
+
+    let mut rejections = Vec::new();
+    let mut events: HashMap<(String, u64), Vec<((SlotNo, TxId), Action)>> = HashMap::new();
+
+    for reg in regs {
+        if let Err(reason) = reg.check_valid() {
+            warn!("invalid reg on tx: '{}': {reason:?}", reg.tx_id);
+            rejections.push(reg.rejection(reason));
+            continue;
+        }
+
+        debug!("registration on tx '{}' is valid", reg.tx_id);
+
+        let key = (
+            normalized_stake_vkey(&reg.metadata.stake_vkey),
+            reg.metadata.purpose.0,
+        );
+        let order = (reg.metadata.slot, reg.tx_id);
+        events
+            .entry(key)
+            .or_default()
+            .push((order, Action::Register(reg)));
+    }
+
+    for dereg in deregs {
+        if let Err(reason) = dereg.check_valid() {
+            warn!("invalid dereg on tx: '{}': {reason:?}", dereg.tx_id);
+            rejections.push(Rejection {
+                tx_id: dereg.tx_id,
+                stake_public_key: Some(dereg.metadata.stake_vkey),
+                slot: dereg.slot_no,
+                reason,
+            });
+            continue;
+        }
+
+        debug!("deregistration on tx '{}' is valid", dereg.tx_id);
+
+        let key = (
+            normalized_stake_vkey(&dereg.metadata.stake_vkey),
+            dereg.metadata.purpose.unwrap_or_default().0,
+        );
+        let order = (dereg.metadata.nonce, dereg.tx_id);
+        events
+            .entry(key)
+            .or_default()
+            .push((order, Action::Deregister(dereg.tx_id)));
+    }
 
-    Ok(reg_voting_power)
+    let mut latest = Vec::new();
+    for mut events in events.into_values() {
+        events.sort_by_key(|(order, _)| *order);
+        let Some((_, winner)) = events.pop() else {
+            continue;
+        };
+
+        let reason = match &winner {
+            Action::Register(reg) => RejectionReason::Superseded {
+                by_tx_id: reg.tx_id,
+            },
+            Action::Deregister(tx_id) => RejectionReason::Deregistered { by_tx_id: *tx_id },
+        };
+        rejections.extend(events.into_iter().filter_map(|(_, action)| match action {
+            Action::Register(reg) => Some(reg.rejection(reason.clone())),
+            Action::Deregister(_) => None,
+        }));
+
+        if let Action::Register(reg) = winner {
+            latest.push(reg);
+        }
+    }
+
+    latest.sort_by_key(|reg| reg.tx_id);
+    (latest, rejections)
+}
+
+fn normalized_stake_vkey(stake_vkey: &StakeVKey) -> String {
+    stake_vkey.trim_start_matches("0x").to_lowercase()
 }
 
 #[instrument]
@@ -101,21 +262,56 @@ pub(crate) fn get_stake_address(
 impl Reg {
     /// Checks if this registration is valid
     ///
-    /// Returns `Result` rather than `bool` to allow structured failures
+    /// Returns the reason of the rejection if it isn't
     #[instrument]
-    fn check_valid(&self) -> Result<()> {
-        let stake_vkey = self.metadata.stake_vkey.trim_start_matches("0x");
-        if stake_vkey.len() == 128 {
-            // TODO: why is this bad? can we give a better error here?
-            bail!("stake_vkey has length 128");
-        }
+    fn check_valid(&self) -> Result<(), RejectionReason> {
+        let pub_key = decode_stake_vkey(&self.metadata.stake_vkey).map_err(|e| {
+            RejectionReason::InvalidStakeKey {
+                error: format!("{e:#}"),
+            }
+        })?;
+        let rewards_addr =
+            self.rewards_address()
+                .map_err(|e| RejectionReason::InvalidRewardsAddress {
+                    error: format!("{e:#}"),
+                })?;
+        let delegations =
+            self.delegations_metadatum()
+                .map_err(|e| RejectionReason::InvalidDelegations {
+                    error: format!("{e:#}"),
+                })?;
 
-        let hex = hex::decode(stake_vkey).context("failed to decode hex")?;
-        let pub_key =
-            // this error doesn't impl `std::err::Error`
-            PublicKey::from_bytes(&hex).map_err(|e| eyre!("error decoding public key: {e}"))?;
+        // Translate registration to Cardano metadata type so we can serialize it correctly
+        let mut meta_map: MetadataMap = MetadataMap::new();
+        meta_map.insert(
+            &TransactionMetadatum::new_int(&Int::new_i32(1)),
+            &delegations,
+        );
+        meta_map.insert(
+            &TransactionMetadatum::new_int(&Int::new_i32(2)),
+            &TransactionMetadatum::new_bytes(pub_key.as_bytes()).unwrap(),
+        );
+        meta_map.insert(
+            &TransactionMetadatum::new_int(&Int::new_i32(3)),
+            &TransactionMetadatum::new_bytes(rewards_addr.to_bytes()).unwrap(),
+        );
+        meta_map.insert(
+            &TransactionMetadatum::new_int(&Int::new_i32(4)),
+            &TransactionMetadatum::new_int(&Int::new(&BigNum::from(self.metadata.slot.0))),
+        );
+        meta_map.insert(
+            &TransactionMetadatum::new_int(&Int::new_i32(5)),
+            &TransactionMetadatum::new_int(&Int::new(&BigNum::from(self.metadata.purpose.0))),
+        );
 
-        // Get rewards address
+        verify_signature(&pub_key, REGISTRATION_LABEL, &meta_map, &self.signature).map_err(|e| {
+            RejectionReason::InvalidSignature {
+                error: format!("{e:#}"),
+            }
+        })
+    }
+
+    fn rewards_address(&self) -> Result<Address> {
         let rewards_addr = self.metadata.rewards_addr.trim_start_matches("0x");
         let rewards_addr: Address = Address::from_bytes(hex::decode(rewards_addr)?)
             .map_err(|_| eyre!("invalid address"))?;
@@ -124,8 +320,10 @@ impl Reg {
             bail!("invalid reward address");
         }
 
-        // Translate registration to Cardano metadata type so we can serialize it correctly
-        let mut meta_map: MetadataMap = MetadataMap::new();
+        Ok(rewards_addr)
+    }
+
+    fn delegations_metadatum(&self) -> Result<TransactionMetadatum> {
         let delegations = match self.metadata.delegations.clone() {
             Delegations::Delegated(delegations) => {
                 let mut metadata_list = MetadataList::new();
@@ -155,45 +353,91 @@ impl Reg {
             }
         };
 
+        Ok(delegations)
+    }
+
+    fn rejection(&self, reason: RejectionReason) -> Rejection {
+        Rejection {
+            tx_id: self.tx_id,
+            stake_public_key: Some(self.metadata.stake_vkey.clone()),
+            slot: self.slot_no,
+            reason,
+        }
+    }
+}
+
+impl Dereg {
+    /// Checks if this deregistration is valid
+    ///
+    /// Returns the reason of the rejection if it isn't
+    #[instrument]
+    fn check_valid(&self) -> Result<(), RejectionReason> {
+        let pub_key = decode_stake_vkey(&self.metadata.stake_vkey).map_err(|e| {
+            RejectionReason::InvalidStakeKey {
+                error: format!("{e:#}"),
+            }
+        })?;
+
+        let mut meta_map: MetadataMap = MetadataMap::new();
         meta_map.insert(
             &TransactionMetadatum::new_int(&Int::new_i32(1)),
-            &delegations,
-        );
-        meta_map.insert(
-            &TransactionMetadatum::new_int(&Int::new_i32(2)),
             &TransactionMetadatum::new_bytes(pub_key.as_bytes()).unwrap(),
         );
         meta_map.insert(
-            &TransactionMetadatum::new_int(&Int::new_i32(3)),
-            &TransactionMetadatum::new_bytes(rewards_addr.to_bytes()).unwrap(),
-        );
-        meta_map.insert(
-            &TransactionMetadatum::new_int(&Int::new_i32(4)),
-            &TransactionMetadatum::new_int(&Int::new(&BigNum::from(self.metadata.slot.0))),
-        );
-        meta_map.insert(
-            &TransactionMetadatum::new_int(&Int::new_i32(5)),
-            &TransactionMetadatum::new_int(&Int::new(&BigNum::from(self.metadata.purpose.0))),
+            &TransactionMetadatum::new_int(&Int::new_i32(2)),
+            &TransactionMetadatum::new_int(&Int::new(&BigNum::from(self.metadata.nonce.0))),
         );
+        // the voting purpose is optional, and is part of the signed data only when present
+        if let Some(purpose) = self.metadata.purpose {
+            meta_map.insert(
+                &TransactionMetadatum::new_int(&Int::new_i32(3)),
+                &TransactionMetadatum::new_int(&Int::new(&BigNum::from(purpose.0))),
+            );
+        }
 
-        let mut meta = GeneralTransactionMetadata::new();
-        meta.insert(
-            &BigNum::from(61284u32),
-            &TransactionMetadatum::new_map(&meta_map),
-        );
+        verify_signature(&pub_key, DEREGISTRATION_LABEL, &meta_map, &self.signature).map_err(|e| {
+            RejectionReason::InvalidSignature {
+                error: format!("{e:#}"),
+            }
+        })
+    }
+}
 
-        let meta_bytes = meta.to_bytes();
-        let meta_bytes_hash = Blake2b256::new(&meta_bytes);
+fn decode_stake_vkey(stake_vkey: &StakeVKey) -> Result<PublicKey> {
+    let stake_vkey = stake_vkey.trim_start_matches("0x");
+    if stake_vkey.len() == 128 {
+        // TODO: why is this bad? can we give a better error here?
+        bail!("stake_vkey has length 128");
+    }
+
+    let hex = hex::decode(stake_vkey).context("failed to decode hex")?;
+    // this error doesn't impl `std::err::Error`
+    PublicKey::from_bytes(&hex).map_err(|e| eyre!("error decoding public key: {e}"))
+}
 
-        // Get signature from rego
-        let sig_str = self.signature.signature.0.clone().split_off(2);
-        let sig =
-            Ed25519Signature::from_hex(&sig_str).map_err(|e| eyre!("invalid ed25519 sig: {e}"))?;
+/// Verifies the signature of `meta_map` stored under `label`, as specified by CIP-36
+fn verify_signature(
+    pub_key: &PublicKey,
+    label: u32,
+    meta_map: &MetadataMap,
+    signature: &RegoSignature,
+) -> Result<()> {
+    let mut meta = GeneralTransactionMetadata::new();
+    meta.insert(
+        &BigNum::from(label),
+        &TransactionMetadatum::new_map(meta_map),
+    );
 
-        match pub_key.verify(meta_bytes_hash.as_hash_bytes(), &sig) {
-            true => Ok(()),
-            false => Err(eyre!("signature verification failed")),
-        }
+    let meta_bytes = meta.to_bytes();
+    let meta_bytes_hash = Blake2b256::new(&meta_bytes);
+
+    // Get signature from rego
+    let sig_str = signature.signature.trim_start_matches("0x");
+    let sig = Ed25519Signature::from_hex(sig_str).map_err(|e| eyre!("invalid ed25519 sig: {e}"))?;
+
+    match pub_key.verify(meta_bytes_hash.as_hash_bytes(), &sig) {
+        true => Ok(()),
+        false => Err(eyre!("signature verification failed")),
     }
 }
 
@@ -234,7 +478,8 @@ mod tests {
 
         let regs = MockDbProvider::from(db_sync)
             .vote_registrations(None, None)
-            .unwrap();
+            .unwrap()
+            .0;
 
         assert!(regs[0].check_valid().is_ok());
     }
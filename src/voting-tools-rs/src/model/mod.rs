@@ -46,19 +46,118 @@ pub struct Output {
     pub tx_id: TxId,
 }
 
+/// Registration or deregistration which is not taken into account in the snapshot
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Rejection {
+    /// transaction id of the rejected registration
+    pub tx_id: TxId,
+    /// stake public key, if it could be read from the registration
+    pub stake_public_key: Option<StakeVKey>,
+    /// slot number of the block of the registration, if known
+    pub slot: Option<SlotNo>,
+    /// why the registration was rejected
+    #[serde(flatten)]
+    pub reason: RejectionReason,
+}
+
+/// Reason for a registration to be rejected
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "reason", rename_all = "snake_case")]
+pub enum RejectionReason {
+    /// metadata or signature json doesn't follow CIP-15/CIP-36
+    MalformedMetadata {
+        /// parsing error
+        error: String,
+    },
+    /// stake public key can't be decoded
+    InvalidStakeKey {
+        /// decoding error
+        error: String,
+    },
+    /// rewards address can't be decoded or isn't a reward address
+    InvalidRewardsAddress {
+        /// decoding error
+        error: String,
+    },
+    /// voting keys in the delegations can't be decoded
+    InvalidDelegations {
+        /// decoding error
+        error: String,
+    },
+    /// signature can't be decoded or doesn't match the metadata and stake key
+    InvalidSignature {
+        /// verification error
+        error: String,
+    },
+    /// a registration with a higher nonce exists for the same stake key and voting purpose
+    Superseded {
+        /// transaction id of the registration in use
+        by_tx_id: TxId,
+    },
+    /// the stake key was deregistered with a higher nonce
+    Deregistered {
+        /// transaction id of the deregistration
+        by_tx_id: TxId,
+    },
+    /// no stake could be found for the stake address
+    UnknownStakeAddress,
+}
+
+impl RejectionReason {
+    /// Name of the reason, without the details
+    #[must_use]
+    pub fn kind(&self) -> &'static str {
+        match self {
+            Self::MalformedMetadata { .. } => "malformed_metadata",
+            Self::InvalidStakeKey { .. } => "invalid_stake_key",
+            Self::InvalidRewardsAddress { .. } => "invalid_rewards_address",
+            Self::InvalidDelegations { .. } => "invalid_delegations",
+            Self::InvalidSignature { .. } => "invalid_signature",
+            Self::Superseded { .. } => "superseded",
+            Self::Deregistered { .. } => "deregistered",
+            Self::UnknownStakeAddress => "unknown_stake_address",
+        }
+    }
+}
+
 #[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
 pub struct RegoSignature {
     #[serde(rename = "1")]
     pub signature: Signature,
 }
 
-#[derive(Debug, Clone, PartialEq)]
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
 pub struct Reg {
     pub tx_id: TxId,
+    /// slot number of the block of the registration, if known
+    #[serde(default)]
+    pub slot_no: Option<SlotNo>,
     pub metadata: RegoMetadata,
     pub signature: RegoSignature,
 }
 
+/// CIP-36 deregistration metadata (label 61286)
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+pub struct DeregoMetadata {
+    #[serde(rename = "1")]
+    pub stake_vkey: StakeVKey,
+    #[serde(rename = "2")]
+    pub nonce: SlotNo,
+    #[serde(rename = "3")]
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub purpose: Option<VotingPurpose>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+pub struct Dereg {
+    pub tx_id: TxId,
+    /// slot number of the block of the deregistration, if known
+    #[serde(default)]
+    pub slot_no: Option<SlotNo>,
+    pub metadata: DeregoMetadata,
+    pub signature: RegoSignature,
+}
+
 // Create newtype wrappers for better type safety
 microtype! {
     #[derive(Debug, PartialEq, Clone)]
@@ -62,3 +62,11 @@ CREATE TABLE block (
     op_cert BYTEA,
     op_cert_counter INT8
 );
+
+CREATE TABLE stake_address (
+    id INT8 PRIMARY KEY,
+    hash_raw BYTEA NOT NULL,
+    view VARCHAR NOT NULL,
+    script_hash BYTEA,
+    tx_id INT8 NOT NULL
+);
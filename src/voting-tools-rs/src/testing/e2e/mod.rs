@@ -6,7 +6,11 @@
 //! These tests are also snapshot tests, meaning the easiest way to run them is with `cargo insta`,
 //! which is provided by `flake.nix`
 
-use crate::voting_power;
+use std::collections::HashSet;
+
+use diesel::connection::SimpleConnection;
+
+use crate::{model::SlotNo, voting_power};
 
 use init::reference_db;
 
@@ -18,3 +22,28 @@ fn simple_query() {
     let simple_result = voting_power(db, None, None, None).unwrap();
     insta::assert_json_snapshot!(simple_result);
 }
+
+#[test]
+fn stake_addresses_changed() {
+    let db = reference_db();
+    db.exec(|conn| conn.batch_execute(include_str!("stake_changes.sql")))
+        .unwrap();
+
+    let changed = |lower: Option<u64>, upper: Option<u64>| {
+        db.stake_addresses_changed(lower.map(SlotNo), upper.map(SlotNo))
+            .unwrap()
+    };
+    let set = |addresses: &[&str]| {
+        addresses
+            .iter()
+            .map(ToString::to_string)
+            .collect::<HashSet<_>>()
+    };
+
+    assert_eq!(changed(None, None), set(&["e0aa", "e0bb", "e0cc"]));
+    // spent and created outputs
+    assert_eq!(changed(Some(11), None), set(&["e0bb", "e0cc"]));
+    // inclusive upper bound
+    assert_eq!(changed(None, Some(10)), set(&["e0aa", "e0bb"]));
+    assert_eq!(changed(Some(21), None), set(&[]));
+}
@@ -0,0 +1,23 @@
+-- outputs to 'e0aa' and 'e0bb' at slot 10, 'e0bb' is spent and 'e0cc' receives an output at slot 20
+
+INSERT INTO stake_address (id, hash_raw, view, tx_id) VALUES
+    (1001, '\xe0aa', 'stake_test_aa', 1001),
+    (1002, '\xe0bb', 'stake_test_bb', 1001),
+    (1003, '\xe0cc', 'stake_test_cc', 1002);
+
+INSERT INTO block (id, hash, slot_no, slot_leader_id, size, time, tx_count, proto_major, proto_minor) VALUES
+    (1001, '\x01', 10, 1, 0, '2022-12-01 00:00:00', 1, 7, 0),
+    (1002, '\x02', 20, 1, 0, '2022-12-01 00:00:10', 1, 7, 0);
+
+INSERT INTO tx (id, hash, block_id, block_index, out_sum, fee, deposit, size, valid_contract, script_size) VALUES
+    (1001, '\x01', 1001, 0, 0, 0, 0, 0, true, 0),
+    (1002, '\x02', 1002, 0, 0, 0, 0, 0, true, 0);
+
+INSERT INTO tx_out (id, tx_id, index, address, address_raw, address_has_script, stake_address_id, value) VALUES
+    (1001, 1001, 0, 'addr_test_aa', '\x00', false, 1001, 1),
+    (1002, 1001, 1, 'addr_test_bb', '\x00', false, 1002, 1),
+    (1003, 1002, 0, 'addr_test_cc', '\x00', false, 1003, 1),
+    (1004, 1002, 1, 'addr_test_enterprise', '\x00', false, NULL, 1);
+
+INSERT INTO tx_in (id, tx_in_id, tx_out_id, tx_out_index) VALUES
+    (1001, 1002, 1001, 1);
@@ -1,7 +1,16 @@
 use crate::test_api::{MockDbProvider, VerifiableSnapshotOutput};
-use crate::Delegations;
+use crate::{Delegations, RejectionReason};
+use cardano_serialization_lib::chain_crypto::Blake2b256;
+use cardano_serialization_lib::metadata::{
+    GeneralTransactionMetadata, MetadataMap, TransactionMetadatum,
+};
+use cardano_serialization_lib::utils::Int;
+use cardano_serialization_lib::Transaction;
 use mainnet_lib::wallet_state::MainnetWalletStateBuilder;
-use mainnet_lib::{CardanoWallet, MainnetNetworkBuilder};
+use mainnet_lib::{
+    BlockBuilder, CardanoWallet, InMemoryDbSync, MainnetNetworkBuilder, TransactionBuilder,
+    DEREGISTRATION_METADATA_LABEL, METADATUM_1, METADATUM_2, REGISTRATION_METADATA_SIGNATURE_LABEL,
+};
 
 #[test]
 fn cip15_correctly_signed_before_snapshot() {
@@ -55,3 +64,173 @@ fn cip36_correctly_signed_before_snapshot() {
     assertions.reward_address(&clarice_wallet.reward_address());
     assertions.stake_key(&clarice_wallet.stake_public_key());
 }
+
+/// Db provider with all transactions in a single block, in the given order
+fn db_with_transactions(transactions: &[Transaction]) -> MockDbProvider {
+    let mut db_sync = InMemoryDbSync::default();
+    db_sync.on_block_propagation(&BlockBuilder::next_block(None, transactions));
+    MockDbProvider::from(db_sync)
+}
+
+/// Deregistration of `victim` stake key signed with the stake key of `signer`
+fn forged_deregistration(signer: &CardanoWallet, victim: &CardanoWallet) -> Transaction {
+    let mut meta_map = MetadataMap::new();
+    meta_map.insert(
+        &METADATUM_1,
+        &TransactionMetadatum::new_bytes(victim.stake_public_key().as_bytes()).unwrap(),
+    );
+    meta_map.insert(
+        &METADATUM_2,
+        &TransactionMetadatum::new_int(&Int::new_i32(10)),
+    );
+
+    let mut metadata = GeneralTransactionMetadata::new();
+    metadata.insert(
+        &DEREGISTRATION_METADATA_LABEL,
+        &TransactionMetadatum::new_map(&meta_map),
+    );
+
+    let signature = signer
+        .stake_key()
+        .sign(Blake2b256::new(&metadata.to_bytes()).as_hash_bytes());
+    let mut signature_map = MetadataMap::new();
+    signature_map.insert(
+        &METADATUM_1,
+        &TransactionMetadatum::new_bytes(signature.to_bytes()).unwrap(),
+    );
+    metadata.insert(
+        &REGISTRATION_METADATA_SIGNATURE_LABEL,
+        &TransactionMetadatum::new_map(&signature_map),
+    );
+
+    TransactionBuilder::build_transaction_with_metadata(
+        &victim.reward_address().to_address(),
+        0,
+        &metadata,
+    )
+}
+
+#[test]
+fn cip36_registration_with_highest_nonce_wins() {
+    let stake = 10_000;
+    let alice_wallet = CardanoWallet::new(stake);
+    let bob_wallet = CardanoWallet::new(stake);
+
+    let db = db_with_transactions(&[
+        alice_wallet.generate_direct_voting_registration(1),
+        alice_wallet
+            .generate_delegated_voting_registration(vec![(bob_wallet.catalyst_public_key(), 1)], 3),
+        // submitted later, but with a lower nonce
+        alice_wallet.generate_direct_voting_registration(2),
+    ]);
+    let outputs = crate::voting_power(&db, None, None, None).unwrap();
+
+    assert_eq!(outputs.len(), 1);
+
+    let assertions = outputs[0].assert();
+
+    // every registration transaction also sends `stake` to the wallet
+    assertions.voting_power(3 * stake);
+    assertions.delegations(&Delegations::Delegated(vec![(
+        bob_wallet.catalyst_public_key().to_hex(),
+        1,
+    )]));
+    assertions.stake_key(&alice_wallet.stake_public_key());
+}
+
+#[test]
+fn cip36_deregistration_removes_voter() {
+    let stake = 10_000;
+    let alice_wallet = CardanoWallet::new(stake);
+    let bob_wallet = CardanoWallet::new(stake);
+
+    let db = db_with_transactions(&[
+        alice_wallet.generate_direct_voting_registration(1),
+        bob_wallet.generate_direct_voting_registration(1),
+        alice_wallet.generate_voting_deregistration(2),
+    ]);
+    let outputs = crate::voting_power(&db, None, None, None).unwrap();
+
+    assert_eq!(outputs.len(), 1);
+
+    let assertions = outputs[0].assert();
+
+    assertions.voting_power(stake);
+    assertions.stake_key(&bob_wallet.stake_public_key());
+}
+
+#[test]
+fn cip36_registration_after_deregistration() {
+    let stake = 10_000;
+    let alice_wallet = CardanoWallet::new(stake);
+
+    let db = db_with_transactions(&[
+        alice_wallet.generate_direct_voting_registration(1),
+        alice_wallet.generate_voting_deregistration(2),
+        alice_wallet.generate_direct_voting_registration(3),
+    ]);
+    let outputs = crate::voting_power(&db, None, None, None).unwrap();
+
+    assert_eq!(outputs.len(), 1);
+    outputs[0]
+        .assert()
+        .stake_key(&alice_wallet.stake_public_key());
+}
+
+#[test]
+fn cip36_stale_deregistration_is_ignored() {
+    let stake = 10_000;
+    let alice_wallet = CardanoWallet::new(stake);
+    let bob_wallet = CardanoWallet::new(stake);
+
+    let db = db_with_transactions(&[
+        alice_wallet.generate_direct_voting_registration(5),
+        // lower nonce than the registration, e.g. replayed
+        alice_wallet.generate_voting_deregistration(4),
+        // signed by another stake key than the one it deregisters
+        forged_deregistration(&bob_wallet, &alice_wallet),
+    ]);
+    let outputs = crate::voting_power(&db, None, None, None).unwrap();
+
+    assert_eq!(outputs.len(), 1);
+    outputs[0]
+        .assert()
+        .stake_key(&alice_wallet.stake_public_key());
+}
+
+#[test]
+fn cip36_rejected_registrations_are_reported() {
+    let stake = 10_000;
+    let alice_wallet = CardanoWallet::new(stake);
+    let bob_wallet = CardanoWallet::new(stake);
+
+    let db = db_with_transactions(&[
+        alice_wallet.generate_direct_voting_registration(1),
+        alice_wallet.generate_direct_voting_registration(2),
+        bob_wallet.generate_direct_voting_registration(1),
+        bob_wallet.generate_voting_deregistration(2),
+        forged_deregistration(&alice_wallet, &bob_wallet),
+    ]);
+    let (outputs, rejections) = crate::voting_power_with_rejections(&db, None, None, None).unwrap();
+
+    assert_eq!(outputs.len(), 1);
+    outputs[0]
+        .assert()
+        .stake_key(&alice_wallet.stake_public_key());
+
+    let reasons: Vec<_> = rejections
+        .iter()
+        .map(|rejection| rejection.reason.kind())
+        .collect();
+    assert_eq!(
+        reasons,
+        vec!["superseded", "deregistered", "invalid_signature"]
+    );
+    assert_eq!(
+        rejections[0].reason,
+        RejectionReason::Superseded {
+            by_tx_id: outputs[0].tx_id
+        }
+    );
+    assert!(rejections.iter().all(|rejection| rejection.slot.is_some()));
+}
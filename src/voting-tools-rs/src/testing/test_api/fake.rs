@@ -1,15 +1,18 @@
 use crate::data_provider::DataProvider;
 use crate::model::{
-    Delegations, Reg, RegoMetadata, RegoSignature, RewardsAddr, Signature, SlotNo, StakeVKey, TxId,
-    VotingPurpose,
+    Delegations, Dereg, DeregoMetadata, Reg, RegoMetadata, RegoSignature, Rejection, RewardsAddr,
+    Signature, SlotNo, StakeVKey, TxId, VotingPurpose,
 };
 use bigdecimal::{BigDecimal, FromPrimitive};
 use cardano_serialization_lib::address::Address;
 use cardano_serialization_lib::crypto::{Ed25519Signature, PublicKey};
+use cardano_serialization_lib::metadata::{
+    GeneralTransactionMetadata, MetadataMap, TransactionMetadatum,
+};
 use cardano_serialization_lib::utils::BigNum;
 use mainnet_lib::{
-    InMemoryDbSync, METADATUM_1, METADATUM_2, METADATUM_3, METADATUM_4,
-    REGISTRATION_METADATA_LABEL, REGISTRATION_METADATA_SIGNATURE_LABEL,
+    InMemoryDbSync, DEREGISTRATION_METADATA_LABEL, METADATUM_1, METADATUM_2, METADATUM_3,
+    METADATUM_4, REGISTRATION_METADATA_LABEL, REGISTRATION_METADATA_SIGNATURE_LABEL,
 };
 use std::collections::HashMap;
 use std::str::FromStr;
@@ -21,12 +24,14 @@ pub struct MockDbProvider {
     db_sync_instance: InMemoryDbSync,
 }
 
-impl DataProvider for MockDbProvider {
-    fn vote_registrations(
+impl MockDbProvider {
+    /// Transactions metadata within bounds, in chain order, numbered by their position, with the
+    /// slot of their block
+    fn transactions_metadata(
         &self,
         lower: Option<SlotNo>,
         upper: Option<SlotNo>,
-    ) -> color_eyre::Result<Vec<Reg>> {
+    ) -> color_eyre::Result<Vec<(TxId, SlotNo, GeneralTransactionMetadata)>> {
         let lower = if let Some(lower) = lower {
             Some(lower.into_i64()?.try_into()?)
         } else {
@@ -39,96 +44,117 @@ impl DataProvider for MockDbProvider {
             None
         };
 
-        let filtered_regs = self
+        let mut filtered_regs = self
             .db_sync_instance
-            .query_voting_transactions_with_bounds(lower, upper);
-
-        let mut tx_id = 0;
+            .query_voting_transactions_with_bounds(lower, upper)
+            .into_iter()
+            .collect::<Vec<_>>();
+        filtered_regs.sort_by_key(|(block_no, _)| *block_no);
 
         Ok(filtered_regs
-            .values()
-            .map(|registrations| {
-                registrations
-                    .iter()
-                    .map(|r| {
-                        let metadata = r.get(&REGISTRATION_METADATA_LABEL).unwrap();
-                        let metadata_map = metadata.as_map().unwrap();
-
-                        let signature_metadata =
-                            r.get(&REGISTRATION_METADATA_SIGNATURE_LABEL).unwrap();
-                        let signature_metadata_map = signature_metadata.as_map().unwrap();
-
-                        let delegations = {
-                            let metadata = metadata_map.get(&METADATUM_1).unwrap();
-
-                            if let Ok(data) = metadata.as_bytes() {
-                                Delegations::Legacy(hex::encode(data))
-                            } else {
-                                let mut delegations = vec![];
-                                let delgation_list = metadata.as_list().unwrap();
-                                for i in 0..delgation_list.len() {
-                                    let inner_list = delgation_list.get(i).as_list().unwrap();
-
-                                    let delegation = inner_list.get(0).as_bytes().unwrap();
-                                    let weight = inner_list.get(1).as_int().unwrap();
-                                    let weight =
-                                        u32::from_i32(weight.as_i32_or_fail().unwrap()).unwrap();
-                                    delegations.push((hex::encode(delegation), weight));
-                                }
-
-                                Delegations::Delegated(delegations)
-                            }
-                        };
-
-                        tx_id += 1;
-
-                        let pub_key = PublicKey::from_bytes(
-                            &metadata_map.get(&METADATUM_2).unwrap().as_bytes().unwrap(),
-                        )
-                        .unwrap();
-                        let rewards_address = Address::from_bytes(
-                            metadata_map.get(&METADATUM_3).unwrap().as_bytes().unwrap(),
-                        )
-                        .unwrap();
-                        let sig = Ed25519Signature::from_bytes(
-                            signature_metadata_map
-                                .get(&METADATUM_1)
-                                .unwrap()
-                                .as_bytes()
-                                .unwrap(),
-                        )
-                        .unwrap();
-
-                        Reg {
-                            tx_id: TxId::from(tx_id),
-                            metadata: RegoMetadata {
-                                delegations,
-                                stake_vkey: StakeVKey(pub_key.to_hex()),
-                                rewards_addr: RewardsAddr(rewards_address.to_hex()),
-                                slot: SlotNo(
-                                    u64::from_str(
-                                        &metadata_map
-                                            .get(&METADATUM_4)
-                                            .unwrap()
-                                            .as_int()
-                                            .unwrap()
-                                            .to_str(),
-                                    )
-                                    .unwrap(),
-                                ),
-                                purpose: VotingPurpose(0),
-                            },
-                            signature: RegoSignature {
-                                signature: Signature(format!("0x{}", sig.to_hex())),
-                            },
+            .into_iter()
+            .flat_map(|(block_no, metadata)| {
+                metadata
+                    .into_iter()
+                    .map(move |metadata| (SlotNo(u64::from(block_no)), metadata))
+            })
+            .enumerate()
+            .map(|(i, (slot_no, metadata))| (TxId::from(i as u64 + 1), slot_no, metadata))
+            .collect())
+    }
+}
+
+impl DataProvider for MockDbProvider {
+    fn vote_registrations(
+        &self,
+        lower: Option<SlotNo>,
+        upper: Option<SlotNo>,
+    ) -> color_eyre::Result<(Vec<Reg>, Vec<Rejection>)> {
+        // the in memory db sync only holds well formed registrations
+        let regs = self
+            .transactions_metadata(lower, upper)?
+            .into_iter()
+            .filter_map(|(tx_id, slot_no, r)| {
+                let metadata = r.get(&REGISTRATION_METADATA_LABEL)?;
+                let metadata_map = metadata.as_map().unwrap();
+
+                let delegations = {
+                    let metadata = metadata_map.get(&METADATUM_1).unwrap();
+
+                    if let Ok(data) = metadata.as_bytes() {
+                        Delegations::Legacy(hex::encode(data))
+                    } else {
+                        let mut delegations = vec![];
+                        let delgation_list = metadata.as_list().unwrap();
+                        for i in 0..delgation_list.len() {
+                            let inner_list = delgation_list.get(i).as_list().unwrap();
+
+                            let delegation = inner_list.get(0).as_bytes().unwrap();
+                            let weight = inner_list.get(1).as_int().unwrap();
+                            let weight = u32::from_i32(weight.as_i32_or_fail().unwrap()).unwrap();
+                            delegations.push((hex::encode(delegation), weight));
                         }
-                    })
-                    .collect()
+
+                        Delegations::Delegated(delegations)
+                    }
+                };
+
+                let pub_key = PublicKey::from_bytes(
+                    &metadata_map.get(&METADATUM_2).unwrap().as_bytes().unwrap(),
+                )
+                .unwrap();
+                let rewards_address = Address::from_bytes(
+                    metadata_map.get(&METADATUM_3).unwrap().as_bytes().unwrap(),
+                )
+                .unwrap();
+
+                Some(Reg {
+                    tx_id,
+                    slot_no: Some(slot_no),
+                    metadata: RegoMetadata {
+                        delegations,
+                        stake_vkey: StakeVKey(pub_key.to_hex()),
+                        rewards_addr: RewardsAddr(rewards_address.to_hex()),
+                        slot: SlotNo(metadata_u64(&metadata_map, &METADATUM_4).unwrap()),
+                        purpose: VotingPurpose(0),
+                    },
+                    signature: signature(&r),
+                })
             })
-            .fold(vec![], |mut acc, sub_vec: Vec<Reg>| {
-                acc.extend(sub_vec);
-                acc
-            }))
+            .collect();
+        Ok((regs, Vec::new()))
+    }
+
+    fn vote_deregistrations(
+        &self,
+        lower: Option<SlotNo>,
+        upper: Option<SlotNo>,
+    ) -> color_eyre::Result<(Vec<Dereg>, Vec<Rejection>)> {
+        let deregs = self
+            .transactions_metadata(lower, upper)?
+            .into_iter()
+            .filter_map(|(tx_id, slot_no, r)| {
+                let metadata = r.get(&DEREGISTRATION_METADATA_LABEL)?;
+                let metadata_map = metadata.as_map().unwrap();
+
+                let pub_key = PublicKey::from_bytes(
+                    &metadata_map.get(&METADATUM_1).unwrap().as_bytes().unwrap(),
+                )
+                .unwrap();
+
+                Some(Dereg {
+                    tx_id,
+                    slot_no: Some(slot_no),
+                    metadata: DeregoMetadata {
+                        stake_vkey: StakeVKey(pub_key.to_hex()),
+                        nonce: SlotNo(metadata_u64(&metadata_map, &METADATUM_2).unwrap()),
+                        purpose: metadata_u64(&metadata_map, &METADATUM_3).map(VotingPurpose),
+                    },
+                    signature: signature(&r),
+                })
+            })
+            .collect();
+        Ok((deregs, Vec::new()))
     }
 
     fn stake_values<'a>(
@@ -150,6 +176,30 @@ impl DataProvider for MockDbProvider {
     }
 }
 
+fn metadata_u64(metadata_map: &MetadataMap, key: &TransactionMetadatum) -> Option<u64> {
+    let value = metadata_map.get(key).ok()?;
+    Some(u64::from_str(&value.as_int().ok()?.to_str()).unwrap())
+}
+
+fn signature(metadata: &GeneralTransactionMetadata) -> RegoSignature {
+    let signature_metadata = metadata
+        .get(&REGISTRATION_METADATA_SIGNATURE_LABEL)
+        .unwrap();
+    let signature_metadata_map = signature_metadata.as_map().unwrap();
+    let sig = Ed25519Signature::from_bytes(
+        signature_metadata_map
+            .get(&METADATUM_1)
+            .unwrap()
+            .as_bytes()
+            .unwrap(),
+    )
+    .unwrap();
+
+    RegoSignature {
+        signature: Signature(format!("0x{}", sig.to_hex())),
+    }
+}
+
 impl From<InMemoryDbSync> for MockDbProvider {
     fn from(db_sync: InMemoryDbSync) -> Self {
         Self {